@@ -5,8 +5,8 @@ extern crate const_format;
 
 use bumpalo::Bump;
 use clap::{
-    builder::PossibleValuesParser, parser::ValueSource, value_parser, Arg, ArgAction, ArgMatches,
-    Command,
+    builder::PossibleValuesParser, parser::ValueSource, value_parser, Arg, ArgAction, ArgGroup,
+    ArgMatches, Command,
 };
 use roc_build::link::{LinkType, LinkingStrategy};
 use roc_build::program::{
@@ -22,7 +22,7 @@ use roc_module::symbol::ModuleId;
 use roc_mono::ir::OptLevel;
 use roc_packaging::cache::RocCacheDir;
 use roc_packaging::tarball::Compression;
-use roc_reporting::report::ANSI_STYLE_CODES;
+use roc_reporting::report::{RenderTarget, ANSI_STYLE_CODES};
 use roc_target::{Architecture, Target};
 use std::env;
 use std::ffi::{CString, OsStr, OsString};
@@ -39,6 +39,9 @@ use tempfile::TempDir;
 mod format;
 pub use format::{format_files, format_src, FormatMode};
 
+#[cfg(not(windows))]
+mod test_report;
+
 pub const CMD_BUILD: &str = "build";
 pub const CMD_RUN: &str = "run";
 pub const CMD_DEV: &str = "dev";
@@ -46,6 +49,13 @@ pub const CMD_REPL: &str = "repl";
 pub const CMD_DOCS: &str = "docs";
 pub const CMD_CHECK: &str = "check";
 pub const CMD_VERSION: &str = "version";
+pub const CMD_EXPLAIN: &str = "explain";
+pub const CMD_VENDOR: &str = "vendor";
+pub const CMD_INSPECT_BUNDLE: &str = "inspect-bundle";
+pub const CMD_CACHE: &str = "cache";
+pub const CMD_CACHE_LIST: &str = "list";
+pub const CMD_CACHE_VERIFY: &str = "verify";
+pub const CMD_CACHE_GC: &str = "gc";
 pub const CMD_FORMAT: &str = "format";
 pub const CMD_TEST: &str = "test";
 pub const CMD_GLUE: &str = "glue";
@@ -72,7 +82,18 @@ pub const FLAG_STDOUT: &str = "stdout";
 pub const FLAG_WASM_STACK_SIZE_KB: &str = "wasm-stack-size-kb";
 pub const FLAG_OUTPUT: &str = "output";
 pub const FLAG_FUZZ: &str = "fuzz";
+pub const FLAG_FILTER: &str = "filter";
+pub const FLAG_MODULE: &str = "module";
+pub const FLAG_LIST: &str = "list";
+pub const FLAG_OUTPUT_FORMAT: &str = "output-format";
+pub const FLAG_TIMEOUT: &str = "timeout";
+pub const FLAG_ERROR_FORMAT: &str = "error-format";
+pub const FLAG_USED_BY: &str = "used-by";
+pub const FLAG_UNUSED_FOR_DAYS: &str = "unused-for-days";
+pub const FLAG_DRY_RUN: &str = "dry-run";
 pub const ROC_FILE: &str = "ROC_FILE";
+pub const REPORT_CODE: &str = "REPORT_CODE";
+pub const BUNDLE_FILE: &str = "BUNDLE_FILE";
 pub const ROC_DIR: &str = "ROC_DIR";
 pub const GLUE_DIR: &str = "GLUE_DIR";
 pub const GLUE_SPEC: &str = "GLUE_SPEC";
@@ -149,6 +170,13 @@ pub fn build_app() -> Command {
         .action(ArgAction::SetTrue)
         .required(false);
 
+    let flag_error_format = Arg::new(FLAG_ERROR_FORMAT)
+        .long(FLAG_ERROR_FORMAT)
+        .help("How to report errors and warnings\n(json prints one object per line and sarif prints a SARIF 2.1 log, for tools that annotate pull requests.)")
+        .value_parser(["text", "json", "sarif"])
+        .default_value("text")
+        .required(false);
+
     let roc_file_to_run = Arg::new(ROC_FILE)
         .help("The .roc file of an app to run")
         .value_parser(value_parser!(PathBuf))
@@ -187,6 +215,7 @@ pub fn build_app() -> Command {
             .arg(flag_prebuilt.clone())
             .arg(flag_fuzz.clone())
             .arg(flag_wasm_stack_size_kb)
+            .arg(flag_error_format.clone())
             .arg(
                 Arg::new(FLAG_TARGET)
                     .long(FLAG_TARGET)
@@ -205,9 +234,9 @@ pub fn build_app() -> Command {
             .arg(
                 Arg::new(FLAG_BUNDLE)
                     .long(FLAG_BUNDLE)
-                    .help("Create an archive of a package (for example, a .tar, .tar.gz, or .tar.br file), so others can add it as a HTTPS dependency.")
+                    .help("Create an archive of a package (for example, a .tar, .tar.gz, .tar.br or .tar.zst file), so others can add it as a HTTPS dependency.")
                     .conflicts_with(FLAG_TARGET)
-                    .value_parser([".tar", ".tar.gz", ".tar.br"])
+                    .value_parser([".tar", ".tar.gz", ".tar.br", ".tar.zst"])
                     .required(false),
             )
             .arg(
@@ -244,6 +273,50 @@ pub fn build_app() -> Command {
                     .action(ArgAction::SetTrue)
                     .required(false)
             )
+            .arg(
+                Arg::new(FLAG_FILTER)
+                    .long(FLAG_FILTER)
+                    .help("Only run the `expect`s whose module name, or the name of the def they follow, contains this text")
+                    .value_parser(value_parser!(String))
+                    .required(false)
+            )
+            .arg(
+                Arg::new(FLAG_MODULE)
+                    .long(FLAG_MODULE)
+                    .help("Only run the `expect`s in the module with this name")
+                    .value_parser(value_parser!(String))
+                    .required(false)
+            )
+            .arg(
+                Arg::new(FLAG_LIST)
+                    .long(FLAG_LIST)
+                    .help("List the `expect`s that would run, with their locations, without running them")
+                    .action(ArgAction::SetTrue)
+                    .required(false)
+            )
+            .arg(
+                Arg::new(FLAG_TIMEOUT)
+                    .long(FLAG_TIMEOUT)
+                    .help("Fail any `expect` that is still running after this many seconds\n(Use 0 for no limit.)")
+                    .value_parser(value_parser!(u64))
+                    .default_value("60")
+                    .required(false)
+            )
+            .arg(
+                Arg::new(FLAG_OUTPUT_FORMAT)
+                    .long(FLAG_OUTPUT_FORMAT)
+                    .help("How to report the results\n(json and junit give a record per `expect`, for CI systems and other tools to read.)")
+                    .value_parser(["text", "json", "junit"])
+                    .default_value("text")
+                    .required(false)
+            )
+            .arg(
+                Arg::new(FLAG_OUTPUT)
+                    .long(FLAG_OUTPUT)
+                    .help("Write the json or junit results to this file instead of stdout")
+                    .value_parser(value_parser!(PathBuf))
+                    .required(false)
+            )
             .arg(
                 Arg::new(ROC_FILE)
                     .help("The .roc file for the main module")
@@ -323,6 +396,7 @@ pub fn build_app() -> Command {
             .about("Check the code for problems, but don’t build or run it")
             .arg(flag_time.clone())
             .arg(flag_max_threads.clone())
+            .arg(flag_error_format)
             .arg(
                 Arg::new(ROC_FILE)
                     .help("The .roc file of an app to check")
@@ -331,6 +405,74 @@ pub fn build_app() -> Command {
                     .default_value(DEFAULT_ROC_FILENAME),
             )
             )
+        .subcommand(Command::new(CMD_VENDOR)
+            .about("Save the packages and platform an app uses next to it, so it can be built offline")
+            .arg(
+                Arg::new(ROC_FILE)
+                    .help("The .roc file of the app whose packages should be saved")
+                    .value_parser(value_parser!(PathBuf))
+                    .required(false)
+                    .default_value(DEFAULT_ROC_FILENAME),
+            )
+            .after_help("The packages are saved in a roc-vendor directory next to the .roc file.\nWhen it exists, packages are installed from there instead of being downloaded.")
+        )
+        .subcommand(Command::new(CMD_INSPECT_BUNDLE)
+            .about("List the files in a bundle made by `roc build --bundle`, with their hashes and the modules they import")
+            .arg(
+                Arg::new(BUNDLE_FILE)
+                    .help("The .tar, .tar.gz, .tar.br or .tar.zst file to inspect")
+                    .value_parser(value_parser!(PathBuf))
+                    .required(true),
+            )
+            .after_help("Bundles are reproducible, so building the same package on another machine should\ngive a bundle with the same name, and the same hashes listed here.")
+        )
+        .subcommand(Command::new(CMD_CACHE)
+            .about("Manage the packages that have been downloaded into the cache")
+            .subcommand_required(true)
+            .subcommand(Command::new(CMD_CACHE_LIST)
+                .about("List the cached packages, with their sizes and when they were last used"))
+            .subcommand(Command::new(CMD_CACHE_VERIFY)
+                .about("Check that the files of each cached package still match its hash"))
+            .subcommand(Command::new(CMD_CACHE_GC)
+                .about("Remove cached packages that aren’t needed anymore")
+                .arg(
+                    Arg::new(FLAG_USED_BY)
+                        .long(FLAG_USED_BY)
+                        .help("Keep the packages used by this .roc file, or by the .roc files in this directory\n(Can be given more than once.)")
+                        .value_parser(value_parser!(PathBuf))
+                        .action(ArgAction::Append)
+                        .required(false)
+                )
+                .arg(
+                    Arg::new(FLAG_UNUSED_FOR_DAYS)
+                        .long(FLAG_UNUSED_FOR_DAYS)
+                        .help("Keep the packages used within this many days")
+                        .value_parser(value_parser!(u64))
+                        .required(false)
+                )
+                .arg(
+                    Arg::new(FLAG_DRY_RUN)
+                        .long(FLAG_DRY_RUN)
+                        .help("List the packages that would be removed, without removing them")
+                        .action(ArgAction::SetTrue)
+                        .required(false)
+                )
+                .group(
+                    ArgGroup::new("keep")
+                        .args([FLAG_USED_BY, FLAG_UNUSED_FOR_DAYS])
+                        .multiple(true)
+                        .required(true)
+                )
+                .after_help("A package is removed only if none of the --used-by projects use it, and it\nwasn’t used within --unused-for-days days."))
+        )
+        .subcommand(Command::new(CMD_EXPLAIN)
+            .about("Explain a kind of problem that `roc check` reports, with an example")
+            .arg(
+                Arg::new(REPORT_CODE)
+                    .help("The code shown next to the problem's title, like R0253")
+                    .required(true),
+            )
+        )
         .subcommand(
             Command::new(CMD_DOCS)
                 .about("Generate documentation for a Roc package")
@@ -457,6 +599,7 @@ pub fn test(matches: &ArgMatches, target: Target) -> io::Result<i32> {
     use roc_build::program::report_problems_monomorphized;
     use roc_load::{ExecutionMode, FunctionKind, LoadConfig, LoadMonomorphizedError};
     use roc_packaging::cache;
+    use test_report::{TestOutputFormat, TestRecord};
 
     let start_time = Instant::now();
     let arena = Bump::new();
@@ -492,6 +635,21 @@ pub fn test(matches: &ArgMatches, target: Target) -> io::Result<i32> {
         process::exit(1);
     }
 
+    let output_format = matches
+        .get_one::<String>(FLAG_OUTPUT_FORMAT)
+        .map(|format| TestOutputFormat::try_from(format.as_str()).unwrap())
+        .unwrap_or(TestOutputFormat::Text);
+    let output_path = matches.get_one::<PathBuf>(FLAG_OUTPUT);
+
+    let timeout = match matches.get_one::<u64>(FLAG_TIMEOUT) {
+        None | Some(0) => None,
+        Some(seconds) => Some(Duration::from_secs(*seconds)),
+    };
+
+    if output_format == TestOutputFormat::Text && output_path.is_some() {
+        user_error!("`--{FLAG_OUTPUT}` needs `--{FLAG_OUTPUT_FORMAT} json` or `--{FLAG_OUTPUT_FORMAT} junit`");
+    }
+
     let arena = &arena;
     // TODO may need to determine this dynamically based on dev builds.
     let function_kind = FunctionKind::LambdaSet;
@@ -519,10 +677,35 @@ pub fn test(matches: &ArgMatches, target: Target) -> io::Result<i32> {
             return handle_loading_problem(problem);
         }
         Err(LoadMonomorphizedError::ErrorModule(module)) => {
-            return handle_error_module(module, start_time.elapsed(), path.as_os_str(), false);
+            return handle_error_module(
+                module,
+                start_time.elapsed(),
+                path.as_os_str(),
+                false,
+                RenderTarget::ColorTerminal,
+            );
         }
     };
-    let problems = report_problems_monomorphized(&mut loaded);
+    let problems = report_problems_monomorphized(&mut loaded, RenderTarget::ColorTerminal);
+
+    let filter = ExpectFilter {
+        module: matches.get_one::<String>(FLAG_MODULE).map(String::as_str),
+        name: matches.get_one::<String>(FLAG_FILTER).map(String::as_str),
+    };
+    let selected = select_expects(
+        &mut loaded.toplevel_expects,
+        &loaded.sources,
+        &loaded.interns,
+        &filter,
+    );
+
+    if matches.get_flag(FLAG_LIST) {
+        for expect in &selected {
+            println!("{}:{} {}", expect.path.display(), expect.line, expect.name);
+        }
+
+        return Ok(0);
+    }
 
     let mut expectations = std::mem::take(&mut loaded.expectations);
 
@@ -545,7 +728,7 @@ pub fn test(matches: &ArgMatches, target: Target) -> io::Result<i32> {
             problems.errors, 0,
             "if there were errors, we would have already exited."
         );
-        if problems.warnings > 0 {
+        if problems.warnings > 0 && output_format == TestOutputFormat::Text {
             problems.print_error_warning_count(start_time.elapsed());
             println!(".\n\nRunning tests…\n\n\x1B[36m{}\x1B[39m", "─".repeat(80));
         }
@@ -555,12 +738,19 @@ pub fn test(matches: &ArgMatches, target: Target) -> io::Result<i32> {
     let arena = &bumpalo::Bump::new();
     let interns = arena.alloc(interns);
 
-    let mut writer = std::io::stdout();
+    // Machine-readable formats report failures in the records instead of as they happen
+    let (mut writer, render_target): (Box<dyn io::Write>, _) = match output_format {
+        TestOutputFormat::Text => (Box::new(io::stdout()), RenderTarget::ColorTerminal),
+        TestOutputFormat::Json | TestOutputFormat::Junit => {
+            (Box::new(io::sink()), RenderTarget::Generic)
+        }
+    };
 
     let mut total_failed_count = 0;
     let mut total_passed_count = 0;
 
     let mut results_by_module = Vec::new();
+    let mut records = Vec::new();
     let global_layout_interner = layout_interner.into_global();
 
     let compilation_duration = start_time.elapsed();
@@ -568,20 +758,34 @@ pub fn test(matches: &ArgMatches, target: Target) -> io::Result<i32> {
     for (module_id, expects) in expects_by_module.into_iter() {
         let test_start_time = Instant::now();
 
-        let (failed_count, passed_count) = roc_repl_expect::run::run_toplevel_expects(
+        let results = roc_repl_expect::run::run_toplevel_expects(
             &mut writer,
-            roc_reporting::report::RenderTarget::ColorTerminal,
+            render_target,
             arena,
             interns,
             &global_layout_interner,
             &dyn_lib,
             &mut expectations,
             expects,
+            timeout,
         )
         .unwrap();
 
         let tests_duration = test_start_time.elapsed();
 
+        let failed_count = results.iter().filter(|result| !result.passed).count();
+        let passed_count = results.len() - failed_count;
+
+        records.extend(results.into_iter().map(|result| {
+            let name = selected
+                .iter()
+                .find(|expect| expect.module_id == module_id && expect.region == result.region)
+                .map(|expect| expect.name.clone())
+                .unwrap_or_else(|| module_id.to_ident_str(interns).as_str().to_string());
+
+            TestRecord::new(result, name, interns, &sources)
+        }));
+
         results_by_module.push(ModuleTestResults {
             module_id,
             failed_count,
@@ -595,6 +799,26 @@ pub fn test(matches: &ArgMatches, target: Target) -> io::Result<i32> {
 
     let total_duration = start_time.elapsed();
 
+    if output_format != TestOutputFormat::Text {
+        records.sort_by(|a, b| (&a.path, a.region.start.line).cmp(&(&b.path, b.region.start.line)));
+
+        let report = match output_format {
+            TestOutputFormat::Json => test_report::to_json(&records),
+            TestOutputFormat::Junit => test_report::to_junit(&records),
+            TestOutputFormat::Text => unreachable!(),
+        };
+
+        match output_path {
+            Some(output_path) => std::fs::write(output_path, report)?,
+            None => println!("{report}"),
+        }
+
+        return Ok(match (total_failed_count, total_passed_count) {
+            (0, 0) => 2,
+            (failed, _) => (failed > 0) as i32,
+        });
+    }
+
     if total_failed_count == 0 && total_passed_count == 0 {
         // TODO print this in a more nicely formatted way!
         println!("No expectations were found.");
@@ -621,6 +845,127 @@ pub fn test(matches: &ArgMatches, target: Target) -> io::Result<i32> {
     }
 }
 
+/// Which of the top-level `expect`s `roc test` should run.
+struct ExpectFilter<'a> {
+    /// The name of the only module to run the `expect`s of.
+    module: Option<&'a str>,
+    /// Text that the module name, or the name of the def an `expect` follows, must contain.
+    name: Option<&'a str>,
+}
+
+/// A top-level `expect` as `roc test --list` shows it.
+struct SelectedExpect {
+    module_id: ModuleId,
+    region: roc_region::all::Region,
+    /// The module name, followed by the name of the def the `expect` follows if there is one,
+    /// e.g. `Str.splitFirst`.
+    name: String,
+    path: PathBuf,
+    /// The 1-based line the `expect` starts on.
+    line: u32,
+}
+
+/// Removes the `expect`s that `filter` rules out, so they don't get compiled or run, and
+/// describes the ones that are left in source order.
+#[cfg(not(windows))]
+fn select_expects(
+    toplevel_expects: &mut MutMap<ModuleId, roc_load::ToplevelExpects>,
+    sources: &MutMap<ModuleId, (PathBuf, Box<str>)>,
+    interns: &roc_module::symbol::Interns,
+    filter: &ExpectFilter,
+) -> Vec<SelectedExpect> {
+    use roc_region::all::{LineInfo, Region};
+
+    let mut selected = Vec::new();
+
+    for (module_id, expects) in toplevel_expects.iter_mut() {
+        let module_name = module_id.to_ident_str(interns).as_str();
+        let (path, source) = sources.get(module_id).unwrap();
+
+        if filter.module.map_or(false, |module| module != module_name) {
+            expects.pure.clear();
+            expects.fx.clear();
+            continue;
+        }
+
+        let arena = Bump::new();
+        let defs = toplevel_def_regions(&arena, source);
+        let line_info = LineInfo::new(source);
+
+        let name_of = |region: &Region| {
+            let def_name = defs
+                .iter()
+                .take_while(|(def_region, _)| def_region.end() <= region.start())
+                .last();
+
+            match def_name {
+                Some((_, def_name)) => format!("{module_name}.{def_name}"),
+                None => module_name.to_string(),
+            }
+        };
+        let is_selected = |region: &Region| {
+            filter
+                .name
+                .map_or(true, |name| name_of(region).contains(name))
+        };
+
+        for expects in [&mut expects.pure, &mut expects.fx] {
+            *expects = std::mem::take(expects)
+                .into_iter()
+                .filter(|(_, region)| is_selected(region))
+                .collect();
+
+            selected.extend(expects.values().map(|region| SelectedExpect {
+                module_id: *module_id,
+                region: *region,
+                name: name_of(region),
+                path: path.clone(),
+                line: line_info.convert_pos(region.start()).line + 1,
+            }));
+        }
+    }
+
+    toplevel_expects.retain(|_, expects| !expects.pure.is_empty() || !expects.fx.is_empty());
+
+    selected.sort_by(|a, b| (&a.path, a.line).cmp(&(&b.path, b.line)));
+    selected
+}
+
+/// The regions and names of the top-level defs of a module that are named by an identifier, in
+/// source order. Modules that don't parse have none.
+#[cfg(not(windows))]
+fn toplevel_def_regions<'a>(
+    arena: &'a Bump,
+    source: &'a str,
+) -> Vec<(roc_region::all::Region, &'a str)> {
+    use roc_parse::ast::{Pattern, ValueDef};
+
+    let Ok(ast) = format::parse_all(arena, source) else {
+        return vec![];
+    };
+
+    ast.defs
+        .defs()
+        .zip(ast.defs.regions.iter())
+        .filter_map(|(def, region)| {
+            let pattern = match def {
+                Err(ValueDef::Annotation(pattern, _)) => pattern,
+                Err(ValueDef::Body(pattern, _))
+                | Err(ValueDef::AnnotatedBody {
+                    body_pattern: pattern,
+                    ..
+                }) => *pattern,
+                _ => return None,
+            };
+
+            match pattern.value {
+                Pattern::Identifier(name) => Some((*region, name)),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
 fn print_test_results(
     module_test_results: ModuleTestResults,
     sources: &MutMap<ModuleId, (PathBuf, Box<str>)>,
@@ -738,6 +1083,9 @@ pub fn build(
                 Compression::Brotli => {
                     println!("Compressing with Brotli at maximum quality level…\n\n(Note: Brotli compression can take awhile! Using --{FLAG_BUNDLE} .tar.gz takes less time, but usually produces a significantly larger output file. Brotli is generally worth the up-front wait if this is a file people will be downloading!)\n");
                 }
+                Compression::Zstd => {
+                    println!("Compressing with zstd at a high quality level, using every core…\n\n(Note: zstd is usually much faster than Brotli, and produces files that are only a little larger. It's a good choice for large packages, like platforms with prebuilt hosts.)\n");
+                }
                 Compression::Gzip => {
                     println!("Compressing with gzip at minimum quality…\n\n(Note: Gzip usually runs faster than Brotli but typically produces significantly larger output files. Consider using --{FLAG_BUNDLE} .tar.br if this is a file people will be downloading!)\n");
                 }
@@ -858,7 +1206,17 @@ pub fn build(
         fuzz,
     };
 
-    let load_config = standard_load_config(target, build_ordering, threading);
+    let mut load_config = standard_load_config(target, build_ordering, threading);
+
+    // Only `roc build` has this flag; `roc run` and `roc dev` always report to the terminal
+    let render = matches
+        .try_get_one::<String>(FLAG_ERROR_FORMAT)
+        .ok()
+        .flatten()
+        .map_or(RenderTarget::ColorTerminal, |format| {
+            error_format_render_target(format)
+        });
+    load_config.render = render;
 
     let res_binary_path = build_file(
         &arena,
@@ -895,8 +1253,10 @@ pub fn build(
                     // since the process is about to exit anyway.
                     // std::mem::forget(arena);
 
-                    problems.print_error_warning_count(total_time);
-                    println!(" while successfully building:\n\n    {generated_filename}");
+                    if !render.is_structured() {
+                        problems.print_error_warning_count(total_time);
+                        println!(" while successfully building:\n\n    {generated_filename}");
+                    }
 
                     // Return a nonzero exit code if there were problems
                     Ok(problems.exit_code())
@@ -969,12 +1329,21 @@ pub fn build(
             }
         }
         Err(BuildFileError::ErrorModule { module, total_time }) => {
-            handle_error_module(module, total_time, path.as_os_str(), true)
+            handle_error_module(module, total_time, path.as_os_str(), true, render)
         }
         Err(BuildFileError::LoadingProblem(problem)) => handle_loading_problem(problem),
     }
 }
 
+/// How to render reports for a value of `--error-format`.
+pub fn error_format_render_target(format: &str) -> RenderTarget {
+    match format {
+        "json" => RenderTarget::Json,
+        "sarif" => RenderTarget::Sarif,
+        _ => RenderTarget::ColorTerminal,
+    }
+}
+
 fn roc_run<'a, I: IntoIterator<Item = &'a OsStr>>(
     arena: &Bump,
     opt_level: OptLevel,
@@ -3,11 +3,13 @@ use bumpalo::Bump;
 use roc_build::link::LinkType;
 use roc_build::program::{check_file, CodeGenBackend};
 use roc_cli::{
-    build_app, format_files, format_src, test, BuildConfig, FormatMode, CMD_BUILD, CMD_CHECK,
-    CMD_DEV, CMD_DOCS, CMD_FORMAT, CMD_GEN_STUB_LIB, CMD_GLUE, CMD_PREPROCESS_HOST, CMD_REPL,
-    CMD_RUN, CMD_TEST, CMD_VERSION, DIRECTORY_OR_FILES, FLAG_CHECK, FLAG_DEV, FLAG_LIB,
-    FLAG_NO_LINK, FLAG_OUTPUT, FLAG_STDIN, FLAG_STDOUT, FLAG_TARGET, FLAG_TIME, GLUE_DIR,
-    GLUE_SPEC, ROC_FILE,
+    build_app, error_format_render_target, format_files, format_src, test, BuildConfig, FormatMode,
+    BUNDLE_FILE, CMD_BUILD, CMD_CACHE, CMD_CACHE_GC, CMD_CACHE_LIST, CMD_CACHE_VERIFY, CMD_CHECK,
+    CMD_DEV, CMD_DOCS, CMD_EXPLAIN, CMD_FORMAT, CMD_GEN_STUB_LIB, CMD_GLUE, CMD_INSPECT_BUNDLE,
+    CMD_PREPROCESS_HOST, CMD_REPL, CMD_RUN, CMD_TEST, CMD_VENDOR, CMD_VERSION, DIRECTORY_OR_FILES,
+    FLAG_CHECK, FLAG_DEV, FLAG_DRY_RUN, FLAG_ERROR_FORMAT, FLAG_LIB, FLAG_NO_LINK, FLAG_OUTPUT,
+    FLAG_STDIN, FLAG_STDOUT, FLAG_TARGET, FLAG_TIME, FLAG_UNUSED_FOR_DAYS, FLAG_USED_BY, GLUE_DIR,
+    GLUE_SPEC, REPORT_CODE, ROC_FILE,
 };
 use roc_docs::generate_docs_html;
 use roc_error_macros::user_error;
@@ -20,6 +22,7 @@ use std::fs::{self, FileType};
 use std::io::{self, Read, Write};
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::time::{Duration, SystemTime};
 use target_lexicon::Triple;
 
 #[macro_use]
@@ -32,6 +35,8 @@ use std::ffi::{OsStr, OsString};
 
 use roc_cli::build;
 
+use clap::ArgMatches;
+
 fn main() -> io::Result<()> {
     let _tracing_guards = roc_tracing::setup_tracing!();
 
@@ -199,6 +204,8 @@ fn main() -> io::Result<()> {
                 Some(1) => Threading::Single,
                 Some(n) => Threading::AtMost(*n),
             };
+            let render =
+                error_format_render_target(matches.get_one::<String>(FLAG_ERROR_FORMAT).unwrap());
 
             match check_file(
                 &arena,
@@ -206,9 +213,12 @@ fn main() -> io::Result<()> {
                 emit_timings,
                 RocCacheDir::Persistent(cache::roc_cache_dir().as_path()),
                 threading,
+                render,
             ) {
                 Ok((problems, total_time)) => {
-                    problems.print_error_warning_count(total_time);
+                    if !render.is_structured() {
+                        problems.print_error_warning_count(total_time);
+                    }
                     Ok(problems.exit_code())
                 }
 
@@ -337,6 +347,153 @@ fn main() -> io::Result<()> {
 
             Ok(format_exit_code)
         }
+        Some((CMD_VENDOR, matches)) => {
+            use roc_packaging::vendor::{self, VendorProblem};
+
+            let roc_file_path = matches.get_one::<PathBuf>(ROC_FILE).unwrap();
+
+            match vendor::vendor(roc_file_path) {
+                Ok(packages) => {
+                    for package in packages.iter() {
+                        let status = if package.downloaded {
+                            "Saved"
+                        } else {
+                            "Verified"
+                        };
+
+                        println!(
+                            "{status} \u{001b}[36m{}\u{001b}[0m\n    as {}",
+                            package.url,
+                            package.tarball.display()
+                        );
+                    }
+
+                    println!(
+                        "\n{} package(s) are vendored, and will be installed without using the network.",
+                        packages.len()
+                    );
+
+                    Ok(0)
+                }
+                Err(VendorProblem::Package {
+                    url,
+                    module_path,
+                    problem,
+                }) => {
+                    let report = roc_reporting::report::to_https_problem_report_string(
+                        &url,
+                        problem,
+                        module_path,
+                    );
+                    eprintln!("{report}");
+
+                    Ok(1)
+                }
+                Err(VendorProblem::Mirrors(problem)) => {
+                    let report = roc_reporting::report::to_https_problem_report_string(
+                        "",
+                        problem,
+                        roc_file_path.clone(),
+                    );
+                    eprintln!("{report}");
+
+                    Ok(1)
+                }
+                Err(VendorProblem::InvalidHeader(path)) => {
+                    eprintln!(
+                        "I couldn't parse the header of {}. Run `roc check` on it for details.",
+                        path.display()
+                    );
+
+                    Ok(1)
+                }
+                Err(VendorProblem::IoErr(path, err)) => {
+                    eprintln!("I couldn't read or write {}: {err}", path.display());
+
+                    Ok(1)
+                }
+            }
+        }
+        Some((CMD_CACHE, matches)) => manage_cache(matches),
+        Some((CMD_INSPECT_BUNDLE, matches)) => {
+            let bundle_path = matches.get_one::<PathBuf>(BUNDLE_FILE).unwrap();
+
+            match roc_packaging::tarball::inspect(bundle_path) {
+                Ok(contents) => {
+                    let filename = bundle_path
+                        .file_name()
+                        .map(|name| name.to_string_lossy())
+                        .unwrap_or_default();
+                    let ext = filename.find(".tar").map_or("", |index| &filename[index..]);
+
+                    println!("Content hash: {}", contents.content_hash);
+                    println!("\n{:>10}  {:<43}  FILE", "SIZE", "BLAKE3 HASH");
+
+                    for file in contents.files.iter() {
+                        println!("{:>10}  {}  {}", file.size, file.hash, file.path);
+                    }
+
+                    println!("\nMODULES");
+
+                    for module in contents.modules.iter() {
+                        match &module.header {
+                            Some(header) => println!("\n{}  ({header})", module.path),
+                            None => println!("\n{}  (header could not be parsed)", module.path),
+                        }
+
+                        for (label, names) in [
+                            ("packages", &module.packages),
+                            ("exposes", &module.exposes),
+                            ("imports", &module.imports),
+                        ] {
+                            if !names.is_empty() {
+                                println!("    {label} {}", names.join(", "));
+                            }
+                        }
+                    }
+
+                    if filename == format!("{}{ext}", contents.content_hash) {
+                        println!("\n{filename} is named after its content hash.");
+
+                        Ok(0)
+                    } else {
+                        eprintln!(
+                            "\n{filename} is not named after its content hash, so a URL to it won't be accepted. Its name should be {}{ext}",
+                            contents.content_hash
+                        );
+
+                        Ok(1)
+                    }
+                }
+                Err(err) => {
+                    eprintln!(
+                        "I couldn't read the bundle {}: {err}",
+                        bundle_path.display()
+                    );
+
+                    Ok(1)
+                }
+            }
+        }
+        Some((CMD_EXPLAIN, matches)) => {
+            let code = matches.get_one::<String>(REPORT_CODE).unwrap();
+
+            match roc_reporting::codes::find_code(code) {
+                Some(report_code) => {
+                    let title = format!("{} [{}]", report_code.titles[0], report_code.code);
+
+                    println!("{}\n", roc_reporting::report::pretty_header(&title));
+                    print!("{}", report_code.explanation);
+
+                    Ok(0)
+                }
+                None => {
+                    eprintln!("There is no kind of problem with the code `{code}`. Codes look like R0253, and are shown next to the title of each problem `roc check` reports.");
+
+                    Ok(1)
+                }
+            }
+        }
         Some((CMD_VERSION, _)) => {
             print!(
                 "{}",
@@ -351,6 +508,200 @@ fn main() -> io::Result<()> {
     std::process::exit(exit_code);
 }
 
+fn manage_cache(matches: &ArgMatches) -> io::Result<i32> {
+    use roc_packaging::cached::{self, CachedPackage, UsageProblem};
+    use roc_packaging::index::Corruption;
+
+    let cache_dir = cache::roc_cache_dir();
+    let packages = cached::cached_packages(&cache_dir)?;
+    let name = |package: &CachedPackage| {
+        package
+            .dir
+            .strip_prefix(&cache_dir)
+            .unwrap_or(&package.dir)
+            .display()
+            .to_string()
+    };
+
+    match matches.subcommand() {
+        Some((CMD_CACHE_LIST, _)) => {
+            println!("{:>10}  {:<14}  PACKAGE", "SIZE", "LAST USED");
+
+            for package in packages.iter() {
+                println!(
+                    "{:>10}  {:<14}  {}",
+                    format_size(package.size),
+                    format_last_used(package.last_used),
+                    name(package)
+                );
+            }
+
+            let total: u64 = packages.iter().map(|package| package.size).sum();
+
+            println!(
+                "\n{} package(s), {} in total, in {}",
+                packages.len(),
+                format_size(total),
+                cache_dir.display()
+            );
+
+            Ok(0)
+        }
+        Some((CMD_CACHE_VERIFY, _)) => {
+            let mut corrupted = 0;
+            let mut unverified = 0;
+
+            for package in packages.iter() {
+                match package.verify()? {
+                    Some(corruptions) if corruptions.is_empty() => {
+                        println!("\u{001b}[32mOK\u{001b}[0m  {}", name(package));
+                    }
+                    Some(corruptions) => {
+                        corrupted += 1;
+                        println!("\u{001b}[31mCORRUPTED\u{001b}[0m  {}", name(package));
+
+                        for corruption in corruptions {
+                            match corruption {
+                                Corruption::Missing(path) => {
+                                    println!("    missing: {}", path.display())
+                                }
+                                Corruption::Changed(path) => {
+                                    println!("    changed: {}", path.display())
+                                }
+                                Corruption::Added(path) => {
+                                    println!("    added: {}", path.display())
+                                }
+                                Corruption::Index => {
+                                    println!("    its index doesn’t match its hash")
+                                }
+                            }
+                        }
+                    }
+                    None => {
+                        unverified += 1;
+                        println!(
+                            "\u{001b}[33mUNVERIFIED\u{001b}[0m  {} (installed without an index)",
+                            name(package)
+                        );
+                    }
+                }
+            }
+
+            println!(
+                "\n{} package(s) verified, {corrupted} corrupted, {unverified} could not be verified.",
+                packages.len() - unverified
+            );
+
+            if corrupted > 0 || unverified > 0 {
+                println!("Packages that are removed will be downloaded and verified again the next time they’re used, e.g. after `roc cache gc --unused-for-days 0`.");
+            }
+
+            Ok(if corrupted > 0 { 1 } else { 0 })
+        }
+        Some((CMD_CACHE_GC, matches)) => {
+            let used = match matches.get_many::<PathBuf>(FLAG_USED_BY) {
+                Some(paths) => {
+                    let paths: Vec<PathBuf> = paths.cloned().collect();
+
+                    match cached::packages_used_by(&cache_dir, &paths) {
+                        Ok(used) => Some(used),
+                        Err(UsageProblem::IoErr(path, err)) => {
+                            eprintln!("I couldn't read {}: {err}", path.display());
+
+                            return Ok(1);
+                        }
+                        Err(UsageProblem::InvalidHeader(path)) => {
+                            eprintln!(
+                                "I couldn't parse the header of {}, so I can't tell which packages it uses. Run `roc check` on it for details.",
+                                path.display()
+                            );
+
+                            return Ok(1);
+                        }
+                    }
+                }
+                None => None,
+            };
+            let max_age = matches
+                .get_one::<u64>(FLAG_UNUSED_FOR_DAYS)
+                .map(|days| Duration::from_secs(days * 24 * 60 * 60));
+            let dry_run = matches.get_flag(FLAG_DRY_RUN);
+            let mut removed = 0;
+            let mut freed = 0;
+
+            for package in packages.iter() {
+                let is_used = used
+                    .as_ref()
+                    .is_some_and(|used| used.contains(&package.dir));
+                let is_recent = max_age.is_some_and(|max_age| !package.unused_for(max_age));
+
+                if is_used || is_recent {
+                    continue;
+                }
+
+                if !dry_run {
+                    package.remove()?;
+                }
+
+                removed += 1;
+                freed += package.size;
+                println!(
+                    "{} {} ({})",
+                    if dry_run { "Would remove" } else { "Removed" },
+                    name(package),
+                    format_size(package.size)
+                );
+            }
+
+            println!(
+                "\n{removed} package(s) {}, freeing {}.",
+                if dry_run {
+                    "would be removed"
+                } else {
+                    "removed"
+                },
+                format_size(freed)
+            );
+
+            Ok(0)
+        }
+        _ => unreachable!(),
+    }
+}
+
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+
+    if bytes < 1000 {
+        return format!("{bytes} B");
+    }
+
+    let mut size = bytes as f64 / 1000.0;
+    let mut unit = UNITS[0];
+
+    for next_unit in UNITS.iter().skip(1) {
+        if size < 1000.0 {
+            break;
+        }
+
+        size /= 1000.0;
+        unit = next_unit;
+    }
+
+    format!("{size:.1} {unit}")
+}
+
+fn format_last_used(last_used: Option<SystemTime>) -> String {
+    let elapsed = last_used.and_then(|time| SystemTime::now().duration_since(time).ok());
+
+    match elapsed.map(|elapsed| elapsed.as_secs() / (24 * 60 * 60)) {
+        Some(0) => "today".to_string(),
+        Some(1) => "1 day ago".to_string(),
+        Some(days) => format!("{days} days ago"),
+        None => "unknown".to_string(),
+    }
+}
+
 fn read_all_roc_files(
     dir: &OsString,
     roc_file_paths: &mut Vec<OsString>,
@@ -0,0 +1,192 @@
+//! Machine-readable results of `roc test`, for CI systems and editors to consume.
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use roc_collections::MutMap;
+use roc_module::symbol::{Interns, ModuleId};
+use roc_region::all::{LineColumnRegion, LineInfo};
+use roc_repl_expect::run::ExpectResult;
+
+/// How `roc test` reports the results of the `expect`s it ran.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TestOutputFormat {
+    /// Failures as they happen and a summary at the end, for people to read.
+    Text,
+    /// One JSON object with a record per `expect`.
+    Json,
+    /// A JUnit XML report with a test suite per module and a test case per `expect`.
+    Junit,
+}
+
+impl TryFrom<&str> for TestOutputFormat {
+    type Error = ();
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            "junit" => Ok(Self::Junit),
+            _ => Err(()),
+        }
+    }
+}
+
+/// One `expect` that was run, with everything needed to point back at its source.
+pub struct TestRecord {
+    /// The module name, followed by the name of the def the `expect` follows if there is one.
+    pub name: String,
+    pub module: String,
+    pub path: PathBuf,
+    /// Where the `expect` is, with 0-based lines and columns.
+    pub region: LineColumnRegion,
+    pub passed: bool,
+    pub duration: Duration,
+    /// The rendered failure, without terminal colors. Empty if the `expect` passed.
+    pub message: String,
+}
+
+impl TestRecord {
+    pub fn new(
+        result: ExpectResult,
+        name: String,
+        interns: &Interns,
+        sources: &MutMap<ModuleId, (PathBuf, Box<str>)>,
+    ) -> Self {
+        let module_id = result.symbol.module_id();
+        let (path, source) = sources.get(&module_id).unwrap();
+        let region = LineInfo::new(source).convert_region(result.region);
+
+        Self {
+            name,
+            module: module_id.to_ident_str(interns).as_str().to_string(),
+            path: path.clone(),
+            region,
+            passed: result.passed,
+            duration: result.duration,
+            message: result.report.trim().to_string(),
+        }
+    }
+}
+
+/// The results as a JSON object, with a `tests` array holding a record per `expect`.
+pub fn to_json(records: &[TestRecord]) -> String {
+    use serde_json::json;
+
+    let tests: Vec<_> = records
+        .iter()
+        .map(|record| {
+            let position = |pos: roc_region::all::LineColumn| {
+                json!({ "line": pos.line + 1, "column": pos.column + 1 })
+            };
+
+            json!({
+                "name": record.name,
+                "module": record.module,
+                "path": record.path.display().to_string(),
+                "region": {
+                    "start": position(record.region.start),
+                    "end": position(record.region.end),
+                },
+                "status": if record.passed { "passed" } else { "failed" },
+                "duration_ms": record.duration.as_secs_f64() * 1000.0,
+                "message": (!record.passed).then_some(&record.message),
+            })
+        })
+        .collect();
+
+    let failed = records.iter().filter(|record| !record.passed).count();
+
+    let report = json!({
+        "passed": records.len() - failed,
+        "failed": failed,
+        "tests": tests,
+    });
+
+    serde_json::to_string_pretty(&report).unwrap()
+}
+
+/// The results as a JUnit XML report, with a `<testsuite>` per module in the order they ran.
+pub fn to_junit(records: &[TestRecord]) -> String {
+    let mut modules: Vec<&str> = Vec::new();
+    for record in records {
+        if !modules.contains(&record.module.as_str()) {
+            modules.push(&record.module);
+        }
+    }
+
+    let summary = |records: &[&TestRecord]| {
+        let failures = records.iter().filter(|record| !record.passed).count();
+        let time: Duration = records.iter().map(|record| record.duration).sum();
+
+        format!(
+            r#"tests="{}" failures="{failures}" errors="0" time="{:.6}""#,
+            records.len(),
+            time.as_secs_f64()
+        )
+    };
+
+    let all: Vec<_> = records.iter().collect();
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str(&format!("<testsuites {}>\n", summary(&all)));
+
+    for module in modules {
+        let suite: Vec<_> = all
+            .iter()
+            .copied()
+            .filter(|record| record.module == module)
+            .collect();
+
+        xml.push_str(&format!(
+            "  <testsuite name=\"{}\" {}>\n",
+            escape_xml(module),
+            summary(&suite)
+        ));
+
+        for record in suite {
+            xml.push_str(&format!(
+                "    <testcase name=\"{}\" classname=\"{}\" file=\"{}\" line=\"{}\" time=\"{:.6}\"",
+                escape_xml(&record.name),
+                escape_xml(&record.module),
+                escape_xml(&record.path.display().to_string()),
+                record.region.start.line + 1,
+                record.duration.as_secs_f64()
+            ));
+
+            if record.passed {
+                xml.push_str("/>\n");
+            } else {
+                xml.push_str(&format!(
+                    ">\n      <failure message=\"expect failed\">{}</failure>\n    </testcase>\n",
+                    escape_xml(&record.message)
+                ));
+            }
+        }
+
+        xml.push_str("  </testsuite>\n");
+    }
+
+    xml.push_str("</testsuites>");
+    xml
+}
+
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            // XML 1.0 can't represent most control characters, even escaped
+            '\n' | '\r' | '\t' => escaped.push(c),
+            c if c.is_control() => {}
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
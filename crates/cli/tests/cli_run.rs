@@ -562,7 +562,7 @@ mod cli_run {
             &[],
             indoc!(
                 r#"
-                ── EXPECT FAILED in tests/expects/expects.roc ──────────────────────────────────
+                ── EXPECT FAILED [R0800] in tests/expects/expects.roc ──────────────────────────
 
                 This expectation failed:
 
@@ -595,7 +595,7 @@ mod cli_run {
             &[],
             indoc!(
                 r#"
-                ── EXPECT FAILED in tests/expects/expects.roc ──────────────────────────────────
+                ── EXPECT FAILED [R0800] in tests/expects/expects.roc ──────────────────────────
 
                 This expectation failed:
 
@@ -607,7 +607,7 @@ mod cli_run {
                 a : Num *
                 a = 1
 
-                ── EXPECT FAILED in tests/expects/expects.roc ──────────────────────────────────
+                ── EXPECT FAILED [R0800] in tests/expects/expects.roc ──────────────────────────
 
                 This expectation failed:
 
@@ -619,7 +619,7 @@ mod cli_run {
                 a : Num *
                 a = 1
 
-                ── EXPECT FAILED in tests/expects/expects.roc ──────────────────────────────────
+                ── EXPECT FAILED [R0800] in tests/expects/expects.roc ──────────────────────────
 
                 This expectation failed:
 
@@ -682,6 +682,69 @@ mod cli_run {
         );
     }
 
+    #[test]
+    #[cfg_attr(windows, ignore)]
+    fn transitive_expects_filtered_by_def() {
+        test_roc_expect(
+            "crates/cli/tests/expects_transitive",
+            "main.roc",
+            &["--filter", "Stringify"],
+            indoc!(
+                r#"
+                0 failed and 2 passed in <ignored for test> ms.
+                "#
+            ),
+        );
+    }
+
+    #[test]
+    #[cfg_attr(windows, ignore)]
+    fn transitive_expects_in_one_module() {
+        test_roc_expect(
+            "crates/cli/tests/expects_transitive",
+            "main.roc",
+            &["--module", "Transitive"],
+            indoc!(
+                r#"
+                0 failed and 1 passed in <ignored for test> ms.
+                "#
+            ),
+        );
+    }
+
+    #[test]
+    #[cfg_attr(windows, ignore)]
+    fn transitive_expects_list() {
+        test_roc_expect(
+            "crates/cli/tests/expects_transitive",
+            "main.roc",
+            &["--list"],
+            indoc!(
+                r#"
+                expects_transitive/Direct.roc:12 Direct.addAndStringify
+                expects_transitive/Direct.roc:14 Direct.addAndStringify
+                expects_transitive/Transitive.roc:9 Transitive.add
+                "#
+            ),
+        );
+    }
+
+    #[test]
+    #[cfg_attr(windows, ignore)]
+    fn transitive_expects_junit() {
+        test_roc_expect(
+            "crates/cli/tests/expects_transitive",
+            "main.roc",
+            &["--module", "Direct", "--output-format", "junit"],
+            indoc!(
+                r#"
+                  </testsuite>
+                </testsuites>
+                "#
+            ),
+        );
+    }
+
     #[test]
     #[cfg_attr(
         windows,
@@ -1401,7 +1464,7 @@ mod cli_run {
             &[],
             indoc!(
                 r#"
-                ── TYPE MISMATCH in tests/known_bad/TypeError.roc ──────────────────────────────
+                ── TYPE MISMATCH [R0412] in tests/known_bad/TypeError.roc ──────────────────────
 
                 Something is off with the body of the main definition:
 
@@ -1437,7 +1500,7 @@ mod cli_run {
             &[],
             indoc!(
                 r#"
-                ── UNUSED IMPORT in ...nown_bad/UnusedImportButWithALongFileNameForTesting.roc ─
+                ── UNUSED IMPORT [R0256] in .../UnusedImportButWithALongFileNameForTesting.roc ─
 
                 Nothing from Symbol is used in this module.
 
@@ -1460,7 +1523,7 @@ mod cli_run {
             &[],
             indoc!(
                 r#"
-                ── MISSING DEFINITION in tests/known_bad/ExposedNotDefined.roc ─────────────────
+                ── MISSING DEFINITION [R0223] in tests/known_bad/ExposedNotDefined.roc ─────────
 
                 bar is listed as exposed, but it isn't defined in this module.
 
@@ -1481,7 +1544,7 @@ mod cli_run {
             &[],
             indoc!(
                 r#"
-                ── UNUSED IMPORT in tests/known_bad/UnusedImport.roc ───────────────────────────
+                ── UNUSED IMPORT [R0256] in tests/known_bad/UnusedImport.roc ───────────────────
 
                 Nothing from Symbol is used in this module.
 
@@ -1504,7 +1567,7 @@ mod cli_run {
             &[],
             indoc!(
                 r#"
-                ── UNKNOWN GENERATES FUNCTION in tests/known_bad/UnknownGeneratesWith.roc ──────
+                ── UNKNOWN GENERATES FUNCTION [R0249] in ...known_bad/UnknownGeneratesWith.roc ─
 
                 I don't know how to generate the foobar function.
 
@@ -5,7 +5,7 @@ use bumpalo::Bump;
 use inkwell::memory_buffer::MemoryBuffer;
 use roc_error_macros::internal_error;
 use roc_gen_dev::AssemblyBackendMode;
-use roc_gen_llvm::llvm::build::{module_from_builtins, LlvmBackendMode};
+use roc_gen_llvm::llvm::build::{module_from_builtins, LlvmBackendMode, HOST_VISIBLE_BUILTINS};
 use roc_gen_llvm::llvm::externs::add_default_roc_externs;
 use roc_load::{
     EntryPoint, ExecutionMode, ExpectMetadata, FunctionKind, LoadConfig, LoadMonomorphizedError,
@@ -38,21 +38,26 @@ pub struct CodeGenTiming {
     pub total: Duration,
 }
 
-pub fn report_problems_monomorphized(loaded: &mut MonomorphizedModule) -> Problems {
+pub fn report_problems_monomorphized(
+    loaded: &mut MonomorphizedModule,
+    render: RenderTarget,
+) -> Problems {
     report_problems(
         &loaded.sources,
         &loaded.interns,
         &mut loaded.can_problems,
         &mut loaded.type_problems,
+        render,
     )
 }
 
-pub fn report_problems_typechecked(loaded: &mut LoadedModule) -> Problems {
+pub fn report_problems_typechecked(loaded: &mut LoadedModule, render: RenderTarget) -> Problems {
     report_problems(
         &loaded.sources,
         &loaded.interns,
         &mut loaded.can_problems,
         &mut loaded.type_problems,
+        render,
     )
 }
 
@@ -179,7 +184,7 @@ fn gen_from_mono_module_llvm<'a>(
         let name = function.get_name().to_str().unwrap();
 
         // mark our zig-defined builtins as internal
-        if name.starts_with("roc_builtins") {
+        if name.starts_with("roc_builtins") && !HOST_VISIBLE_BUILTINS.contains(&name) {
             function.set_linkage(Linkage::Internal);
         }
 
@@ -649,10 +654,16 @@ pub fn handle_error_module(
     total_time: std::time::Duration,
     filename: &OsStr,
     print_run_anyway_hint: bool,
+    render: RenderTarget,
 ) -> std::io::Result<i32> {
     debug_assert!(module.total_problems() > 0);
 
-    let problems = report_problems_typechecked(&mut module);
+    let problems = report_problems_typechecked(&mut module, render);
+
+    // Structured output is all that should go to stdout
+    if render.is_structured() {
+        return Ok(problems.exit_code());
+    }
 
     problems.print_error_warning_count(total_time);
 
@@ -733,6 +744,7 @@ pub fn build_file<'a>(
     out_path: Option<&Path>,
 ) -> Result<BuiltFile<'a>, BuildFileError<'a>> {
     let compilation_start = Instant::now();
+    let render = load_config.render;
 
     // Step 1: compile the app and generate the .o file
     let loaded =
@@ -750,6 +762,7 @@ pub fn build_file<'a>(
         prebuilt_requested,
         wasm_dev_stack_bytes,
         loaded,
+        render,
         compilation_start,
         out_path,
     )
@@ -767,6 +780,7 @@ fn build_loaded_file<'a>(
     prebuilt_requested: bool,
     wasm_dev_stack_bytes: Option<u32>,
     loaded: roc_load::MonomorphizedModule<'a>,
+    render: RenderTarget,
     compilation_start: Instant,
     out_path: Option<&Path>,
 ) -> Result<BuiltFile<'a>, BuildFileError<'a>> {
@@ -907,7 +921,7 @@ fn build_loaded_file<'a>(
     // This only needs to be mutable for report_problems. This can't be done
     // inside a nested scope without causing a borrow error!
     let mut loaded = loaded;
-    let problems = report_problems_monomorphized(&mut loaded);
+    let problems = report_problems_monomorphized(&mut loaded, render);
     let loaded = loaded;
 
     enum HostRebuildTiming {
@@ -1190,6 +1204,7 @@ pub fn check_file<'a>(
     emit_timings: bool,
     roc_cache_dir: RocCacheDir<'_>,
     threading: Threading,
+    render: RenderTarget,
 ) -> Result<(Problems, Duration), LoadingProblem<'a>> {
     let compilation_start = Instant::now();
 
@@ -1203,8 +1218,7 @@ pub fn check_file<'a>(
         target,
         // TODO: we may not want this for just checking.
         function_kind: FunctionKind::LambdaSet,
-        // TODO: expose this from CLI?
-        render: RenderTarget::ColorTerminal,
+        render,
         palette: DEFAULT_PALETTE,
         threading,
         exec_mode: ExecutionMode::Check,
@@ -1254,7 +1268,10 @@ pub fn check_file<'a>(
         println!("Finished checking in {} ms\n", compilation_end.as_millis(),);
     }
 
-    Ok((report_problems_typechecked(&mut loaded), compilation_end))
+    Ok((
+        report_problems_typechecked(&mut loaded, render),
+        compilation_end,
+    ))
 }
 
 pub fn build_str_test<'a>(
@@ -1283,6 +1300,7 @@ pub fn build_str_test<'a>(
     let threading = Threading::AtMost(2);
 
     let load_config = standard_load_config(target, build_ordering, threading);
+    let render = load_config.render;
 
     let compilation_start = std::time::Instant::now();
 
@@ -1308,6 +1326,7 @@ pub fn build_str_test<'a>(
         assume_prebuild,
         wasm_dev_stack_bytes,
         loaded,
+        render,
         compilation_start,
         None,
     )
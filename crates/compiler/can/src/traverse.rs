@@ -963,3 +963,70 @@ pub fn find_declaration(symbol: Symbol, decls: &'_ Declarations) -> Option<Found
         }
     }
 }
+
+/// The places a symbol is bound and looked up, as found by [find_references].
+#[derive(Debug, Default)]
+pub struct References {
+    pub bindings: Vec<Region>,
+    pub lookups: Vec<Region>,
+}
+
+/// Finds every region where `symbol` is bound or looked up in `decls`.
+///
+/// The regions are those of the enclosing pattern or expression, so a lookup of a qualified name
+/// like `Foo.bar` will have the region of the whole `Foo.bar`.
+pub fn find_references(symbol: Symbol, decls: &Declarations) -> References {
+    let mut visitor = Finder {
+        symbol,
+        found: References::default(),
+    };
+    visitor.visit_decls(decls);
+    return visitor.found;
+
+    struct Finder {
+        symbol: Symbol,
+        found: References,
+    }
+
+    impl Visitor for Finder {
+        fn visit_pattern(&mut self, pattern: &Pattern, region: Region, _opt_var: Option<Variable>) {
+            match pattern {
+                Pattern::Identifier(symbol)
+                | Pattern::Shadowed(_, _, symbol)
+                | Pattern::As(_, symbol)
+                | Pattern::AbilityMemberSpecialization { ident: symbol, .. }
+                    if *symbol == self.symbol =>
+                {
+                    self.found.bindings.push(region);
+                }
+                _ => {}
+            }
+
+            walk_pattern(self, pattern);
+        }
+
+        fn visit_record_destruct(&mut self, destruct: &RecordDestruct, region: Region) {
+            // when a record field has a pattern guard, the symbol is bound by the guard
+            if destruct.symbol == self.symbol && !matches!(destruct.typ, DestructType::Guard(..)) {
+                self.found.bindings.push(region);
+            }
+
+            walk_record_destruct(self, destruct);
+        }
+
+        fn visit_expr(&mut self, expr: &Expr, region: Region, var: Variable) {
+            match expr {
+                Expr::Var(symbol, _)
+                | Expr::AbilityMember(symbol, _, _)
+                | Expr::RecordUpdate { symbol, .. }
+                    if *symbol == self.symbol =>
+                {
+                    self.found.lookups.push(region);
+                }
+                _ => {}
+            }
+
+            walk_expr(self, expr, var);
+        }
+    }
+}
@@ -163,6 +163,10 @@ flags! {
     /// Writes a `final.wasm` file to /tmp
     ROC_WRITE_FINAL_WASM
 
+    /// Writes the final Wasm module to roc/gen_wasm/final.wat in the temp directory, in
+    /// WebAssembly text format
+    ROC_WRITE_FINAL_WAT
+
     /// Prints Wasm interpreter debug log in test_gen
     ROC_LOG_WASM_INTERP
 
@@ -1009,6 +1009,12 @@ impl<'a, 'ctx, 'env> Env<'a, 'ctx, 'env> {
     }
 }
 
+/// Builtins that hosts can call, so they must stay visible outside of the app.
+pub const HOST_VISIBLE_BUILTINS: [&str; 1] = [
+    // Hosts that build Dicts (e.g. with roc_std's RocDict) need the seed Roc hashes keys with.
+    bitcode::UTILS_DICT_PSEUDO_SEED,
+];
+
 pub fn module_from_builtins<'ctx>(
     target: Target,
     ctx: &'ctx Context,
@@ -1090,7 +1096,7 @@ pub fn module_from_builtins<'ctx>(
     // mark that the builtin functions are allowed to be DCE'd if they aren't used.
     for func in module.get_functions() {
         let name = func.get_name().to_string_lossy();
-        if name.starts_with("roc_builtins.") {
+        if name.starts_with("roc_builtins.") && !HOST_VISIBLE_BUILTINS.contains(&name.as_ref()) {
             func.set_linkage(Linkage::Private);
         }
     }
@@ -14,6 +14,9 @@ use bumpalo::collections::Vec;
 use bumpalo::{self, Bump};
 
 use roc_collections::all::{MutMap, MutSet};
+use roc_debug_flags::dbg_do;
+#[cfg(debug_assertions)]
+use roc_debug_flags::ROC_WRITE_FINAL_WAT;
 use roc_module::symbol::{Interns, ModuleId, Symbol};
 use roc_mono::code_gen_help::CodeGenHelp;
 use roc_mono::ir::{Proc, ProcLayout};
@@ -75,12 +78,35 @@ pub fn build_app_binary<'a, 'r>(
         build_app_module(env, layout_interner, interns, host_module, procedures);
 
     wasm_module.eliminate_dead_code(env.arena, called_fns);
+    dump_final_wat(&wasm_module);
 
     let mut buffer = std::vec::Vec::with_capacity(wasm_module.size());
     wasm_module.serialize(&mut buffer);
     buffer
 }
 
+/// Write the finished module to roc/gen_wasm/final.wat in the temp directory, in WebAssembly
+/// text format, if the `ROC_WRITE_FINAL_WAT` debug flag is set. Call this after dead code
+/// elimination.
+pub fn dump_final_wat(wasm_module: &WasmModule<'_>) {
+    if !write_final_wat() {
+        return;
+    }
+    let debug_dir = std::env::temp_dir().join("roc").join("gen_wasm");
+    let final_wat_path = debug_dir.join("final.wat");
+    std::fs::create_dir_all(&debug_dir).unwrap();
+    std::fs::write(&final_wat_path, wasm_module.to_wat()).unwrap();
+    // stdout may be the output of the program being compiled (e.g. in the REPL or tests)
+    eprintln!("Wrote WebAssembly text to\n\t{}", final_wat_path.display());
+}
+
+fn write_final_wat() -> bool {
+    dbg_do!(ROC_WRITE_FINAL_WAT, {
+        return true;
+    });
+    false
+}
+
 /// Generate an unserialized Wasm module
 /// Shared by all consumers of gen_wasm: roc_build, roc_repl_wasm, and test_gen
 /// (roc_repl_wasm and test_gen will add more generated code for a wrapper function
@@ -19,7 +19,6 @@ mod test_reporting {
     use roc_parse::module::parse_header;
     use roc_parse::state::State;
     use roc_parse::test_helpers::parse_expr_with;
-    use roc_problem::Severity;
     use roc_region::all::LineInfo;
     use roc_reporting::report::{
         can_problem, parse_problem, type_problem, RenderTarget, Report, ANSI_STYLE_CODES,
@@ -38,13 +37,25 @@ mod test_reporting {
         filename
     }
 
-    fn to_simple_report(doc: RocDocBuilder) -> Report {
-        Report {
-            title: "".to_string(),
-            doc,
-            filename: filename_from_string(r"/code/proj/Main.roc"),
-            severity: Severity::RuntimeError,
-        }
+    fn render_color_terminal(doc: RocDocBuilder, buf: &mut String) {
+        doc.1
+            .render_raw(
+                70,
+                &mut roc_reporting::report::ColorWrite::new(&DEFAULT_PALETTE, buf),
+            )
+            .expect("<buffer is not a utf-8 encoded string>");
+    }
+
+    /// Fails for reports whose title isn't one of the titles of their code, e.g. because the
+    /// report was reworded without updating [roc_reporting::codes].
+    fn assert_report_code(report: &Report) {
+        assert!(
+            report.code.titles.contains(&report.title.as_str()),
+            "the report titled {:?} has code {}, whose titles are {:?}",
+            report.title,
+            report.code.code,
+            report.code.titles,
+        );
     }
 
     fn promote_expr_to_module(src: &str) -> String {
@@ -195,6 +206,7 @@ mod test_reporting {
 
                 for problem in can_problems {
                     let report = can_problem(&alloc, &lines, filename.clone(), problem.clone());
+                    assert_report_code(&report);
                     reports.push(report);
                 }
 
@@ -202,6 +214,7 @@ mod test_reporting {
                     if let Some(report) =
                         type_problem(&alloc, &lines, filename.clone(), problem.clone())
                     {
+                        assert_report_code(&report);
                         reports.push(report);
                     }
                 }
@@ -309,6 +322,7 @@ mod test_reporting {
 
                 let problem = fail.into_file_error(filename.clone());
                 let doc = parse_problem(&alloc, &lines, filename, 0, problem);
+                assert_report_code(&doc);
 
                 callback(doc.pretty(&alloc).append(alloc.line()), buf)
             }
@@ -319,6 +333,7 @@ mod test_reporting {
 
                 for problem in can_problems {
                     let report = can_problem(&alloc, &lines, filename.clone(), problem.clone());
+                    assert_report_code(&report);
                     reports.push(report);
                 }
 
@@ -326,6 +341,7 @@ mod test_reporting {
                     if let Some(report) =
                         type_problem(&alloc, &lines, filename.clone(), problem.clone())
                     {
+                        assert_report_code(&report);
                         reports.push(report);
                     }
                 }
@@ -369,6 +385,7 @@ mod test_reporting {
                     .map_problem(SyntaxError::Header)
                     .into_file_error(filename.clone());
                 let doc = parse_problem(&alloc, &lines, filename, 0, problem);
+                assert_report_code(&doc);
 
                 callback(doc.pretty(&alloc).append(alloc.line()), buf)
             }
@@ -478,7 +495,7 @@ mod test_reporting {
         "
         ),
         @r"
-    ── NOT EXPOSED in /code/proj/Main.roc ──────────────────────────────────────────
+    ── NOT EXPOSED [R0232] in /code/proj/Main.roc ──────────────────────────────────
 
     The List module does not expose `isempty`:
 
@@ -505,7 +522,7 @@ mod test_reporting {
         "
         ),
         @r"
-    ── UNUSED DEFINITION in /code/proj/Main.roc ────────────────────────────────────
+    ── UNUSED DEFINITION [R0255] in /code/proj/Main.roc ────────────────────────────
 
     `y` is not used anywhere in your code.
 
@@ -530,7 +547,7 @@ mod test_reporting {
        "
         ),
         @r"
-    ── DUPLICATE NAME in /code/proj/Main.roc ───────────────────────────────────────
+    ── DUPLICATE NAME [R0214] in /code/proj/Main.roc ───────────────────────────────
 
     The `i` name is first defined here:
 
@@ -562,7 +579,7 @@ mod test_reporting {
        "
         ),
         @r"
-    ── DUPLICATE NAME in /code/proj/Main.roc ───────────────────────────────────────
+    ── DUPLICATE NAME [R0214] in /code/proj/Main.roc ───────────────────────────────
 
     The `Booly` name is first defined here:
 
@@ -594,7 +611,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [R0258] in /code/proj/Main.roc ───────────────────────────────
 
     Using != and == together requires parentheses, to clarify how they
     should be grouped.
@@ -623,7 +640,7 @@ mod test_reporting {
             "#
         ),
         @r#"
-        ── UNRECOGNIZED NAME in /code/proj/Main.roc ────────────────────────────────────
+        ── UNRECOGNIZED NAME [R0253] in /code/proj/Main.roc ────────────────────────────
 
         Nothing is named `bar` in this scope.
 
@@ -639,6 +656,77 @@ mod test_reporting {
         "#
     );
 
+    #[test]
+    fn unrecognized_name_as_structured_report() {
+        let arena = Bump::new();
+        let src = indoc!(
+            r#"
+            baz = 3
+
+            bar baz
+            "#
+        );
+
+        let (module_src, _, can_problems, home, interns) =
+            infer_expr_help_new("unrecognized_name_as_structured_report", &arena, src).unwrap();
+        let lines = LineInfo::new(&module_src);
+        let src_lines: Vec<&str> = module_src.split('\n').collect();
+        let alloc = RocDocAllocator::new(&src_lines, home, &interns);
+        let filename = filename_from_string(r"/code/proj/Main.roc");
+
+        let diagnostics: Vec<_> = can_problems
+            .into_iter()
+            .map(|problem| {
+                alloc.take_highlight();
+                can_problem(&alloc, &lines, filename.clone(), problem).to_diagnostic(&alloc)
+            })
+            .collect();
+
+        assert_eq!(diagnostics.len(), 1);
+        let diagnostic = &diagnostics[0];
+
+        assert_eq!(diagnostic.title, "UNRECOGNIZED NAME");
+        assert!(diagnostic
+            .message
+            .starts_with("Nothing is named `bar` in this scope."));
+        assert_eq!(diagnostic.suggestions[0], "baz");
+
+        let json = diagnostic.to_json();
+        assert_eq!(json["severity"], "error");
+        assert_eq!(json["code"], "R0253");
+        assert_eq!(json["region"]["start"]["line"], 6);
+        assert_eq!(json["region"]["start"]["column"], 5);
+        assert_eq!(json["region"]["end"]["column"], 8);
+
+        let sarif = roc_reporting::structured::sarif_log(&diagnostics);
+        let result = &sarif["runs"][0]["results"][0];
+        assert_eq!(result["ruleId"], "R0253");
+        assert_eq!(
+            result["fixes"][0]["artifactChanges"][0]["replacements"][0]["insertedContent"]["text"],
+            "baz"
+        );
+    }
+
+    #[test]
+    fn report_codes_are_unique() {
+        use roc_reporting::codes::{find_code, REPORT_CODES};
+
+        let mut codes = std::collections::HashSet::new();
+
+        for report_code in REPORT_CODES {
+            assert!(
+                codes.insert(report_code.code),
+                "{} is used twice",
+                report_code.code
+            );
+
+            assert!(!report_code.titles.is_empty());
+            assert!(!report_code.explanation.trim().is_empty());
+        }
+
+        assert_eq!(find_code(" r0253 ").unwrap().titles, ["UNRECOGNIZED NAME"]);
+    }
+
     test_report!(
         lowercase_primitive_tag_bool,
         indoc!(
@@ -647,7 +735,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── UNRECOGNIZED NAME in /code/proj/Main.roc ────────────────────────────────────
+    ── UNRECOGNIZED NAME [R0253] in /code/proj/Main.roc ────────────────────────────
 
     Nothing is named `true` in this scope.
 
@@ -679,7 +767,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [R0258] in /code/proj/Main.roc ───────────────────────────────
 
     Using more than one == like this requires parentheses, to clarify how
     things should be grouped.
@@ -705,7 +793,7 @@ mod test_reporting {
          "#
         ),
         @r#"
-    ── UNUSED ARGUMENT in /code/proj/Main.roc ──────────────────────────────────────
+    ── UNUSED ARGUMENT [R0254] in /code/proj/Main.roc ──────────────────────────────
 
     `box` doesn't use `htmlChildren`.
 
@@ -718,7 +806,7 @@ mod test_reporting {
     at the start of a variable name is a way of saying that the variable
     is not used.
 
-    ── UNUSED DEFINITION in /code/proj/Main.roc ────────────────────────────────────
+    ── UNUSED DEFINITION [R0255] in /code/proj/Main.roc ────────────────────────────
 
     `y` is not used anywhere in your code.
 
@@ -751,11 +839,7 @@ mod test_reporting {
 
         let symbol = interns.symbol(test_home(), "activityIndicatorLarge".into());
 
-        to_simple_report(alloc.symbol_unqualified(symbol)).render_color_terminal(
-            &mut buf,
-            &alloc,
-            &DEFAULT_PALETTE,
-        );
+        render_color_terminal(alloc.symbol_unqualified(symbol), &mut buf);
 
         assert_eq!(human_readable(&buf), "<cyan>activityIndicatorLarge<reset>");
     }
@@ -780,11 +864,7 @@ mod test_reporting {
         let module_id = interns.module_id(&"Util.Int".into());
 
         let alloc = RocDocAllocator::new(&src_lines, home, &interns);
-        to_simple_report(alloc.module(module_id)).render_color_terminal(
-            &mut buf,
-            &alloc,
-            &DEFAULT_PALETTE,
-        );
+        render_color_terminal(alloc.module(module_id), &mut buf);
 
         assert_eq!(human_readable(&buf), "<green>Util.Int<reset>");
     }
@@ -802,7 +882,7 @@ mod test_reporting {
             ),
             indoc!(
                 r"
-                <cyan>── UNRECOGNIZED NAME in /code/proj/Main.roc ────────────────────────────────────<reset>
+                <cyan>── UNRECOGNIZED NAME [R0253] in /code/proj/Main.roc ────────────────────────────<reset>
 
                 Nothing is named `theAdmin` in this scope.
 
@@ -828,7 +908,7 @@ mod test_reporting {
             "#
         ),
         @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0408] in /code/proj/Main.roc ────────────────────────────────
 
     This `if` condition needs to be a Bool:
 
@@ -854,7 +934,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0408] in /code/proj/Main.roc ────────────────────────────────
 
     This `if` guard condition needs to be a Bool:
 
@@ -879,7 +959,7 @@ mod test_reporting {
             "#
         ),
         @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0408] in /code/proj/Main.roc ────────────────────────────────
 
     This `if` has an `else` branch with a different type from its `then` branch:
 
@@ -906,7 +986,7 @@ mod test_reporting {
              "#
         ),
         @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0408] in /code/proj/Main.roc ────────────────────────────────
 
     The 3rd branch of this `if` does not match all the previous branches:
 
@@ -936,7 +1016,7 @@ mod test_reporting {
             "#
         ),
         @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0408] in /code/proj/Main.roc ────────────────────────────────
 
     The 2nd branch of this `when` does not match all the previous branches:
 
@@ -974,7 +1054,7 @@ mod test_reporting {
             "#
         ),
         @r#"
-    ── UNSAFE PATTERN in /code/proj/Main.roc ───────────────────────────────────────
+    ── UNSAFE PATTERN [R0410] in /code/proj/Main.roc ───────────────────────────────
 
     This `when` does not cover all the possibilities:
 
@@ -1018,7 +1098,7 @@ mod test_reporting {
             "#
         ),
         @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0408] in /code/proj/Main.roc ────────────────────────────────
 
     This list contains elements with different types:
 
@@ -1045,7 +1125,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0408] in /code/proj/Main.roc ────────────────────────────────
 
     This list contains elements with different types:
 
@@ -1078,7 +1158,7 @@ mod test_reporting {
             "#
         ),
         @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0408] in /code/proj/Main.roc ────────────────────────────────
 
     I cannot update the `.foo` field like this:
 
@@ -1108,7 +1188,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── CIRCULAR TYPE in /code/proj/Main.roc ────────────────────────────────────────
+    ── CIRCULAR TYPE [R0400] in /code/proj/Main.roc ────────────────────────────────
 
     I'm inferring a weird self-referential type for `f`:
 
@@ -1133,7 +1213,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── CIRCULAR TYPE in /code/proj/Main.roc ────────────────────────────────────────
+    ── CIRCULAR TYPE [R0400] in /code/proj/Main.roc ────────────────────────────────
 
     I'm inferring a weird self-referential type for `f`:
 
@@ -1159,7 +1239,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── CIRCULAR TYPE in /code/proj/Main.roc ────────────────────────────────────────
+    ── CIRCULAR TYPE [R0400] in /code/proj/Main.roc ────────────────────────────────
 
     I'm inferring a weird self-referential type for `f`:
 
@@ -1172,7 +1252,7 @@ mod test_reporting {
 
         List ∞ -> *
 
-    ── CIRCULAR TYPE in /code/proj/Main.roc ────────────────────────────────────────
+    ── CIRCULAR TYPE [R0400] in /code/proj/Main.roc ────────────────────────────────
 
     I'm inferring a weird self-referential type for `g`:
 
@@ -1199,7 +1279,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0408] in /code/proj/Main.roc ────────────────────────────────
 
     This expression is used in an unexpected way:
 
@@ -1235,7 +1315,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0408] in /code/proj/Main.roc ────────────────────────────────
 
     This expression is used in an unexpected way:
 
@@ -1269,7 +1349,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── CIRCULAR TYPE in /code/proj/Main.roc ────────────────────────────────────────
+    ── CIRCULAR TYPE [R0400] in /code/proj/Main.roc ────────────────────────────────
 
     I'm inferring a weird self-referential type for `f`:
 
@@ -1295,7 +1375,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── CIRCULAR TYPE in /code/proj/Main.roc ────────────────────────────────────────
+    ── CIRCULAR TYPE [R0400] in /code/proj/Main.roc ────────────────────────────────
 
     I'm inferring a weird self-referential type for `f`:
 
@@ -1333,7 +1413,7 @@ mod test_reporting {
         // against that extra variable, rather than possibly having to translate a `Type`
         // again.
         @r"
-    ── CIRCULAR TYPE in /code/proj/Main.roc ────────────────────────────────────────
+    ── CIRCULAR TYPE [R0400] in /code/proj/Main.roc ────────────────────────────────
 
     I'm inferring a weird self-referential type for `f`:
 
@@ -1346,7 +1426,7 @@ mod test_reporting {
 
         List ∞ -> List *
 
-    ── CIRCULAR TYPE in /code/proj/Main.roc ────────────────────────────────────────
+    ── CIRCULAR TYPE [R0400] in /code/proj/Main.roc ────────────────────────────────
 
     I'm inferring a weird self-referential type for `g`:
 
@@ -1373,7 +1453,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── CIRCULAR TYPE in /code/proj/Main.roc ────────────────────────────────────────
+    ── CIRCULAR TYPE [R0400] in /code/proj/Main.roc ────────────────────────────────
 
     I'm inferring a weird self-referential type for `f`:
 
@@ -1386,7 +1466,7 @@ mod test_reporting {
 
         List ∞ -> List *
 
-    ── CIRCULAR TYPE in /code/proj/Main.roc ────────────────────────────────────────
+    ── CIRCULAR TYPE [R0400] in /code/proj/Main.roc ────────────────────────────────
 
     I'm inferring a weird self-referential type for `g`:
 
@@ -1414,7 +1494,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0408] in /code/proj/Main.roc ────────────────────────────────
 
     This 1st argument to `f` has an unexpected type:
 
@@ -1448,7 +1528,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0408] in /code/proj/Main.roc ────────────────────────────────
 
     This 1st argument to `f` has an unexpected type:
 
@@ -1485,7 +1565,7 @@ mod test_reporting {
             "#
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0408] in /code/proj/Main.roc ────────────────────────────────
 
     This 1st argument to `f` has an unexpected type:
 
@@ -1522,7 +1602,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0412] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the `then` branch of this `if` expression:
 
@@ -1556,7 +1636,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0412] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the body of the `x` definition:
 
@@ -1589,7 +1669,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0412] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the body of the `x` definition:
 
@@ -1621,7 +1701,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── TOO MANY ARGS in /code/proj/Main.roc ────────────────────────────────────────
+    ── TOO MANY ARGS [R0407] in /code/proj/Main.roc ────────────────────────────────
 
     The `x` value is not a function, but it was given 1 argument:
 
@@ -1643,7 +1723,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── TOO MANY ARGS in /code/proj/Main.roc ────────────────────────────────────────
+    ── TOO MANY ARGS [R0407] in /code/proj/Main.roc ────────────────────────────────
 
     The `f` function expects 1 argument, but it got 2 instead:
 
@@ -1665,7 +1745,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── TOO FEW ARGS in /code/proj/Main.roc ─────────────────────────────────────────
+    ── TOO FEW ARGS [R0406] in /code/proj/Main.roc ─────────────────────────────────
 
     The `f` function expects 2 arguments, but it got only 1:
 
@@ -1686,7 +1766,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0408] in /code/proj/Main.roc ────────────────────────────────
 
     The branches of this `when` expression don't match the condition:
 
@@ -1715,7 +1795,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0413] in /code/proj/Main.roc ────────────────────────────────
 
     The 2nd pattern in this `when` does not match the previous ones:
 
@@ -1741,7 +1821,7 @@ mod test_reporting {
              "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0408] in /code/proj/Main.roc ────────────────────────────────
 
     The branches of this `when` expression don't match the condition:
 
@@ -1769,7 +1849,7 @@ mod test_reporting {
              "#
         ),
         @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0408] in /code/proj/Main.roc ────────────────────────────────
 
     The branches of this `when` expression don't match the condition:
 
@@ -1798,7 +1878,7 @@ mod test_reporting {
              "
         ),
         @r"
-    ── UNRECOGNIZED NAME in /code/proj/Main.roc ────────────────────────────────────
+    ── UNRECOGNIZED NAME [R0253] in /code/proj/Main.roc ────────────────────────────
 
     Nothing is named `foo` in this scope.
 
@@ -1854,7 +1934,7 @@ mod test_reporting {
         ),
         // Just putting this here. We should probably handle or-patterns better
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0413] in /code/proj/Main.roc ────────────────────────────────
 
     The 2nd pattern in this branch does not match the previous ones:
 
@@ -1882,7 +1962,7 @@ mod test_reporting {
         ),
         // Maybe this should specifically say the pattern doesn't work?
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0408] in /code/proj/Main.roc ────────────────────────────────
 
     This expression is used in an unexpected way:
 
@@ -1910,7 +1990,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0412] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the body of this definition:
 
@@ -1941,7 +2021,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [R0263] in /code/proj/Main.roc ───────────────────────────────
 
     This integer pattern is malformed:
 
@@ -1962,7 +2042,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [R0263] in /code/proj/Main.roc ───────────────────────────────
 
     This float pattern is malformed:
 
@@ -1983,7 +2063,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [R0263] in /code/proj/Main.roc ───────────────────────────────
 
     This hex integer pattern is malformed:
 
@@ -2004,7 +2084,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [R0263] in /code/proj/Main.roc ───────────────────────────────
 
     This octal integer pattern is malformed:
 
@@ -2025,7 +2105,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [R0263] in /code/proj/Main.roc ───────────────────────────────
 
     This binary integer pattern is malformed:
 
@@ -2047,7 +2127,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0412] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the body of the `x` definition:
 
@@ -2097,7 +2177,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0412] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the `else` branch of this `if` expression:
 
@@ -2131,7 +2211,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0412] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the body of the `f` definition:
 
@@ -2166,7 +2246,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0412] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the body of the `f` definition:
 
@@ -2202,7 +2282,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── UNRECOGNIZED NAME in /code/proj/Main.roc ────────────────────────────────────
+    ── UNRECOGNIZED NAME [R0253] in /code/proj/Main.roc ────────────────────────────
 
     Nothing is named `ok` in this scope.
 
@@ -2233,7 +2313,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── UNUSED DEFINITION in /code/proj/Main.roc ────────────────────────────────────
+    ── UNUSED DEFINITION [R0255] in /code/proj/Main.roc ────────────────────────────
 
     `ok` is not used anywhere in your code.
 
@@ -2243,7 +2323,7 @@ mod test_reporting {
     If you didn't intend on using `ok` then remove it so future readers of
     your code don't wonder why it is there.
 
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0412] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the body of the `f` definition:
 
@@ -2275,7 +2355,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── CIRCULAR DEFINITION in /code/proj/Main.roc ──────────────────────────────────
+    ── CIRCULAR DEFINITION [R0207] in /code/proj/Main.roc ──────────────────────────
 
     `f` is defined directly in terms of itself:
 
@@ -2302,7 +2382,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── CIRCULAR DEFINITION in /code/proj/Main.roc ──────────────────────────────────
+    ── CIRCULAR DEFINITION [R0207] in /code/proj/Main.roc ──────────────────────────
 
     The `foo` definition is causing a very tricky infinite loop:
 
@@ -2330,7 +2410,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0415] in /code/proj/Main.roc ────────────────────────────────
 
     This `x` record doesn’t have a `foo` field:
 
@@ -2352,7 +2432,7 @@ mod test_reporting {
         ),
         // TODO also suggest fields with the correct type
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0415] in /code/proj/Main.roc ────────────────────────────────
 
     This `x` record doesn’t have a `foo` field:
 
@@ -2385,7 +2465,7 @@ mod test_reporting {
         ),
         // TODO also suggest fields with the correct type
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0415] in /code/proj/Main.roc ────────────────────────────────
 
     This `r` record doesn’t have a `foo` field:
 
@@ -2413,7 +2493,7 @@ mod test_reporting {
         ),
         // TODO also suggest fields with the correct type
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0415] in /code/proj/Main.roc ────────────────────────────────
 
     This `x` record doesn’t have a `foo` field:
 
@@ -2443,7 +2523,7 @@ mod test_reporting {
         ),
         // TODO also suggest fields with the correct type
         @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0408] in /code/proj/Main.roc ────────────────────────────────
 
     This 2nd argument to + has an unexpected type:
 
@@ -2468,7 +2548,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0408] in /code/proj/Main.roc ────────────────────────────────
 
     This 2nd argument to + has an unexpected type:
 
@@ -2496,7 +2576,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0408] in /code/proj/Main.roc ────────────────────────────────
 
     This 2nd argument to + has an unexpected type:
 
@@ -2524,7 +2604,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0412] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the body of the `f` definition:
 
@@ -2558,7 +2638,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0412] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the body of the `f` definition:
 
@@ -2601,7 +2681,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── UNSAFE PATTERN in /code/proj/Main.roc ───────────────────────────────────────
+    ── UNSAFE PATTERN [R0410] in /code/proj/Main.roc ───────────────────────────────
 
     This pattern does not cover all the possibilities:
 
@@ -2616,7 +2696,7 @@ mod test_reporting {
     matching in function arguments, put a `when` in the function body to
     account for all possibilities.
 
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0412] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the body of the `f` definition:
 
@@ -2653,7 +2733,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0408] in /code/proj/Main.roc ────────────────────────────────
 
     This expression is used in an unexpected way:
 
@@ -2684,7 +2764,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── UNSAFE PATTERN in /code/proj/Main.roc ───────────────────────────────────────
+    ── UNSAFE PATTERN [R0410] in /code/proj/Main.roc ───────────────────────────────
 
     This `when` does not cover all the possibilities:
 
@@ -2711,7 +2791,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── UNSAFE PATTERN in /code/proj/Main.roc ───────────────────────────────────────
+    ── UNSAFE PATTERN [R0410] in /code/proj/Main.roc ───────────────────────────────
 
     This `when` does not cover all the possibilities:
 
@@ -2739,7 +2819,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── UNSAFE PATTERN in /code/proj/Main.roc ───────────────────────────────────────
+    ── UNSAFE PATTERN [R0410] in /code/proj/Main.roc ───────────────────────────────
 
     This `when` does not cover all the possibilities:
 
@@ -2768,7 +2848,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── UNSAFE PATTERN in /code/proj/Main.roc ───────────────────────────────────────
+    ── UNSAFE PATTERN [R0410] in /code/proj/Main.roc ───────────────────────────────
 
     This `when` does not cover all the possibilities:
 
@@ -2797,7 +2877,7 @@ mod test_reporting {
         ),
         // Tip: Looks like a record field guard is not exhaustive. Learn more about record pattern matches at TODO.
         @r"
-    ── UNSAFE PATTERN in /code/proj/Main.roc ───────────────────────────────────────
+    ── UNSAFE PATTERN [R0410] in /code/proj/Main.roc ───────────────────────────────
 
     This `when` does not cover all the possibilities:
 
@@ -2826,7 +2906,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── UNSAFE PATTERN in /code/proj/Main.roc ───────────────────────────────────────
+    ── UNSAFE PATTERN [R0410] in /code/proj/Main.roc ───────────────────────────────
 
     This `when` does not cover all the possibilities:
 
@@ -2852,7 +2932,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── UNSAFE PATTERN in /code/proj/Main.roc ───────────────────────────────────────
+    ── UNSAFE PATTERN [R0410] in /code/proj/Main.roc ───────────────────────────────
 
     This `when` does not cover all the possibilities:
 
@@ -2879,7 +2959,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── REDUNDANT PATTERN in /code/proj/Main.roc ────────────────────────────────────
+    ── REDUNDANT PATTERN [R0405] in /code/proj/Main.roc ────────────────────────────
 
     The 2nd pattern is redundant:
 
@@ -2907,7 +2987,7 @@ mod test_reporting {
         ),
         // de-aliases the alias to give a better error message
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0408] in /code/proj/Main.roc ────────────────────────────────
 
     This 1st argument to `f` has an unexpected type:
 
@@ -2945,7 +3025,7 @@ mod test_reporting {
         ),
         // should not report Bar as unused!
         @r"
-    ── CYCLIC ALIAS in /code/proj/Main.roc ─────────────────────────────────────────
+    ── CYCLIC ALIAS [R0401] in /code/proj/Main.roc ─────────────────────────────────
 
     The `Foo` alias is self-recursive in an invalid way:
 
@@ -2971,7 +3051,7 @@ mod test_reporting {
         ),
         // should not report Bar as unused!
         @r"
-    ── CYCLIC ALIAS in /code/proj/Main.roc ─────────────────────────────────────────
+    ── CYCLIC ALIAS [R0401] in /code/proj/Main.roc ─────────────────────────────────
 
     The `Foo` alias is self-recursive in an invalid way:
 
@@ -2991,7 +3071,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── DUPLICATE FIELD NAME in /code/proj/Main.roc ─────────────────────────────────
+    ── DUPLICATE FIELD NAME [R0212] in /code/proj/Main.roc ─────────────────────────
 
     This record defines the `.x` field twice!
 
@@ -3015,7 +3095,7 @@ mod test_reporting {
             "#
         ),
         @r#"
-    ── DUPLICATE FIELD NAME in /code/proj/Main.roc ─────────────────────────────────
+    ── DUPLICATE FIELD NAME [R0212] in /code/proj/Main.roc ─────────────────────────
 
     This record defines the `.x` field twice!
 
@@ -3043,7 +3123,7 @@ mod test_reporting {
             "#
         ),
         @r#"
-    ── DUPLICATE FIELD NAME in /code/proj/Main.roc ─────────────────────────────────
+    ── DUPLICATE FIELD NAME [R0212] in /code/proj/Main.roc ─────────────────────────
 
     This record defines the `.x` field twice!
 
@@ -3078,7 +3158,7 @@ mod test_reporting {
             "#
         ),
         @r#"
-    ── DUPLICATE FIELD NAME in /code/proj/Main.roc ─────────────────────────────────
+    ── DUPLICATE FIELD NAME [R0212] in /code/proj/Main.roc ─────────────────────────
 
     This record defines the `.x` field twice!
 
@@ -3111,7 +3191,7 @@ mod test_reporting {
             "#
         ),
         @r"
-    ── DUPLICATE FIELD NAME in /code/proj/Main.roc ─────────────────────────────────
+    ── DUPLICATE FIELD NAME [R0212] in /code/proj/Main.roc ─────────────────────────
 
     This record type defines the `.foo` field twice!
 
@@ -3139,7 +3219,7 @@ mod test_reporting {
             "#
         ),
         @r"
-    ── DUPLICATE TAG NAME in /code/proj/Main.roc ───────────────────────────────────
+    ── DUPLICATE TAG NAME [R0215] in /code/proj/Main.roc ───────────────────────────
 
     This tag union type defines the `Foo` tag twice!
 
@@ -3168,7 +3248,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── NAMING PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── NAMING PROBLEM [R0262] in /code/proj/Main.roc ───────────────────────────────
 
     This annotation does not match the definition immediately following
     it:
@@ -3204,7 +3284,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [R0260] in /code/proj/Main.roc ───────────────────────────────
 
     This definition of `MyAlias` has an unexpected pattern:
 
@@ -3213,7 +3293,7 @@ mod test_reporting {
 
     Only type variables like `a` or `value` can occur in this position.
 
-    ── UNUSED DEFINITION in /code/proj/Main.roc ────────────────────────────────────
+    ── UNUSED DEFINITION [R0255] in /code/proj/Main.roc ────────────────────────────
 
     `MyAlias` is not used anywhere in your code.
 
@@ -3236,7 +3316,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [R0260] in /code/proj/Main.roc ───────────────────────────────
 
     This definition of `Age` has an unexpected pattern:
 
@@ -3258,7 +3338,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── TOO MANY TYPE ARGUMENTS in /code/proj/Main.roc ──────────────────────────────
+    ── TOO MANY TYPE ARGUMENTS [R0245] in /code/proj/Main.roc ──────────────────────
 
     The `Num` opaque expects 1 type argument, but it got 2 instead:
 
@@ -3280,7 +3360,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── TOO MANY TYPE ARGUMENTS in /code/proj/Main.roc ──────────────────────────────
+    ── TOO MANY TYPE ARGUMENTS [R0245] in /code/proj/Main.roc ──────────────────────
 
     The `Num` opaque expects 1 type argument, but it got 2 instead:
 
@@ -3304,7 +3384,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── TOO FEW TYPE ARGUMENTS in /code/proj/Main.roc ───────────────────────────────
+    ── TOO FEW TYPE ARGUMENTS [R0244] in /code/proj/Main.roc ───────────────────────
 
     The `Pair` alias expects 2 type arguments, but it got 1 instead:
 
@@ -3328,7 +3408,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── TOO MANY TYPE ARGUMENTS in /code/proj/Main.roc ──────────────────────────────
+    ── TOO MANY TYPE ARGUMENTS [R0245] in /code/proj/Main.roc ──────────────────────
 
     The `Pair` alias expects 2 type arguments, but it got 3 instead:
 
@@ -3351,7 +3431,7 @@ mod test_reporting {
             "
         ),
         @r#"
-    ── UNUSED TYPE ALIAS PARAMETER in /code/proj/Main.roc ──────────────────────────
+    ── UNUSED TYPE ALIAS PARAMETER [R0257] in /code/proj/Main.roc ──────────────────
 
     The `a` type parameter is not used in the `Foo` alias definition:
 
@@ -3373,7 +3453,7 @@ mod test_reporting {
             "
         ),
         @r#"
-    ── ARGUMENTS BEFORE EQUALS in tmp/elm_function_syntax/Test.roc ─────────────────
+    ── ARGUMENTS BEFORE EQUALS [R0001] in tmp/elm_function_syntax/Test.roc ─────────
 
     I am partway through parsing a definition, but I got stuck here:
 
@@ -3401,7 +3481,7 @@ mod test_reporting {
             "#
         ),
         @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0412] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the body of the `x` definition:
 
@@ -3450,7 +3530,7 @@ mod test_reporting {
         // TODO render tag unions across multiple lines
         // TODO do not show recursion var if the recursion var does not render on the surface of a type
         @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0412] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the body of the `x` definition:
 
@@ -3504,7 +3584,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [R0266] in /code/proj/Main.roc ───────────────────────────────
 
     This integer literal is too big:
 
@@ -3516,7 +3596,7 @@ mod test_reporting {
 
     Tip: Learn more about number literals at TODO
 
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [R0266] in /code/proj/Main.roc ───────────────────────────────
 
     This integer literal is too small:
 
@@ -3528,7 +3608,7 @@ mod test_reporting {
 
     Tip: Learn more about number literals at TODO
 
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [R0266] in /code/proj/Main.roc ───────────────────────────────
 
     This integer literal is too big:
 
@@ -3540,7 +3620,7 @@ mod test_reporting {
 
     Tip: Learn more about number literals at TODO
 
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [R0266] in /code/proj/Main.roc ───────────────────────────────
 
     This integer literal is too small:
 
@@ -3552,7 +3632,7 @@ mod test_reporting {
 
     Tip: Learn more about number literals at TODO
 
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0408] in /code/proj/Main.roc ────────────────────────────────
 
     This 2nd argument to + has an unexpected type:
 
@@ -3581,7 +3661,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [R0265] in /code/proj/Main.roc ───────────────────────────────
 
     This float literal is too big:
 
@@ -3593,7 +3673,7 @@ mod test_reporting {
 
     Tip: Learn more about number literals at TODO
 
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [R0265] in /code/proj/Main.roc ───────────────────────────────
 
     This float literal is too small:
 
@@ -3626,7 +3706,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [R0266] in /code/proj/Main.roc ───────────────────────────────
 
     This integer literal contains an invalid digit:
 
@@ -3638,7 +3718,7 @@ mod test_reporting {
 
     Tip: Learn more about number literals at TODO
 
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [R0266] in /code/proj/Main.roc ───────────────────────────────
 
     This hex integer literal contains an invalid digit:
 
@@ -3650,7 +3730,7 @@ mod test_reporting {
 
     Tip: Learn more about number literals at TODO
 
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [R0266] in /code/proj/Main.roc ───────────────────────────────
 
     This octal integer literal contains an invalid digit:
 
@@ -3662,7 +3742,7 @@ mod test_reporting {
 
     Tip: Learn more about number literals at TODO
 
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [R0266] in /code/proj/Main.roc ───────────────────────────────
 
     This binary integer literal contains an invalid digit:
 
@@ -3692,7 +3772,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [R0266] in /code/proj/Main.roc ───────────────────────────────
 
     This hex integer literal contains no digits:
 
@@ -3704,7 +3784,7 @@ mod test_reporting {
 
     Tip: Learn more about number literals at TODO
 
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [R0266] in /code/proj/Main.roc ───────────────────────────────
 
     This octal integer literal contains no digits:
 
@@ -3716,7 +3796,7 @@ mod test_reporting {
 
     Tip: Learn more about number literals at TODO
 
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [R0266] in /code/proj/Main.roc ───────────────────────────────
 
     This binary integer literal contains no digits:
 
@@ -3740,7 +3820,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [R0265] in /code/proj/Main.roc ───────────────────────────────
 
     This float literal contains an invalid digit:
 
@@ -3771,7 +3851,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [R0267] in /code/proj/Main.roc ───────────────────────────────
 
     This expression cannot be updated:
 
@@ -3780,7 +3860,7 @@ mod test_reporting {
 
     Only variables can be updated with record update syntax.
 
-    ── MODULE NOT IMPORTED in /code/proj/Main.roc ──────────────────────────────────
+    ── MODULE NOT IMPORTED [R0224] in /code/proj/Main.roc ──────────────────────────
 
     The `Test` module is not imported:
 
@@ -3795,7 +3875,7 @@ mod test_reporting {
         Dict
         Hash
 
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [R0267] in /code/proj/Main.roc ───────────────────────────────
 
     This expression cannot be updated:
 
@@ -3814,7 +3894,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── MODULE NOT IMPORTED in /code/proj/Main.roc ──────────────────────────────────
+    ── MODULE NOT IMPORTED [R0224] in /code/proj/Main.roc ──────────────────────────
 
     The `Foo` module is not imported:
 
@@ -3839,7 +3919,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0408] in /code/proj/Main.roc ────────────────────────────────
 
     This 2nd argument to + has an unexpected type:
 
@@ -3867,7 +3947,7 @@ mod test_reporting {
             "#
         ),
         @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0413] in /code/proj/Main.roc ────────────────────────────────
 
     The 1st argument to `f` is weird:
 
@@ -3896,7 +3976,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0412] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the body of this definition:
 
@@ -3927,7 +4007,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0413] in /code/proj/Main.roc ────────────────────────────────
 
     The 1st argument to `f` is weird:
 
@@ -3960,7 +4040,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0408] in /code/proj/Main.roc ────────────────────────────────
 
     The branches of this `when` expression don't match the condition:
 
@@ -3993,7 +4073,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0408] in /code/proj/Main.roc ────────────────────────────────
 
     This expression is used in an unexpected way:
 
@@ -4024,7 +4104,7 @@ mod test_reporting {
                 "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0408] in /code/proj/Main.roc ────────────────────────────────
 
     This 1st argument to this function has an unexpected type:
 
@@ -4058,7 +4138,7 @@ mod test_reporting {
             "#
         ),
         @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0408] in /code/proj/Main.roc ────────────────────────────────
 
     The branches of this `when` expression don't match the condition:
 
@@ -4092,7 +4172,7 @@ mod test_reporting {
             "#
         ),
         @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0408] in /code/proj/Main.roc ────────────────────────────────
 
     The branches of this `when` expression don't match the condition:
 
@@ -4120,7 +4200,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── BAD OPTIONAL VALUE in /code/proj/Main.roc ───────────────────────────────────
+    ── BAD OPTIONAL VALUE [R0206] in /code/proj/Main.roc ───────────────────────────
 
     This record uses an optional value for the `.y` field in an incorrect
     context!
@@ -4157,7 +4237,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── REDUNDANT PATTERN in /code/proj/Main.roc ────────────────────────────────────
+    ── REDUNDANT PATTERN [R0405] in /code/proj/Main.roc ────────────────────────────
 
     The 3rd pattern is redundant:
 
@@ -4202,7 +4282,7 @@ mod test_reporting {
             "
         ),
         @r#"
-    ── UNUSED ARGUMENT in /code/proj/Main.roc ──────────────────────────────────────
+    ── UNUSED ARGUMENT [R0254] in /code/proj/Main.roc ──────────────────────────────
 
     `f` doesn't use `foo`.
 
@@ -4224,7 +4304,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [R0269] in /code/proj/Main.roc ───────────────────────────────
 
     I am trying to parse a qualified name here:
 
@@ -4245,7 +4325,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [R0269] in /code/proj/Main.roc ───────────────────────────────
 
     I am trying to parse a qualified name here:
 
@@ -4265,7 +4345,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [R0269] in /code/proj/Main.roc ───────────────────────────────
 
     I am trying to parse a record field access here:
 
@@ -4287,7 +4367,7 @@ mod test_reporting {
             "
         ),
         @r#"
-    ── UNKNOWN OPERATOR in tmp/type_annotation_double_colon/Test.roc ───────────────
+    ── UNKNOWN OPERATOR [R0062] in tmp/type_annotation_double_colon/Test.roc ───────
 
     This looks like an operator, but it's not one I recognize!
 
@@ -4319,7 +4399,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── TOO MANY ARGS in /code/proj/Main.roc ────────────────────────────────────────
+    ── TOO MANY ARGS [R0407] in /code/proj/Main.roc ────────────────────────────────
 
     This value is not a function, but it was given 3 arguments:
 
@@ -4338,7 +4418,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── UNFINISHED TAG UNION TYPE in tmp/tag_union_open/Test.roc ────────────────────
+    ── UNFINISHED TAG UNION TYPE [R0059] in tmp/tag_union_open/Test.roc ────────────
 
     I am partway through parsing a tag union type, but I got stuck here:
 
@@ -4360,7 +4440,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── UNFINISHED TAG UNION TYPE in tmp/tag_union_end/Test.roc ─────────────────────
+    ── UNFINISHED TAG UNION TYPE [R0059] in tmp/tag_union_end/Test.roc ─────────────
 
     I am partway through parsing a tag union type, but I got stuck here:
 
@@ -4382,7 +4462,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── WEIRD TAG NAME in tmp/tag_union_lowercase_tag_name/Test.roc ─────────────────
+    ── WEIRD TAG NAME [R0076] in tmp/tag_union_lowercase_tag_name/Test.roc ─────────
 
     I am partway through parsing a tag union type, but I got stuck here:
 
@@ -4403,7 +4483,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── WEIRD TAG NAME in tmp/tag_union_second_lowercase_tag_name/Test.roc ──────────
+    ── WEIRD TAG NAME [R0076] in tmp/tag_union_second_lowercase_tag_name/Test.roc ──
 
     I am partway through parsing a tag union type, but I got stuck here:
 
@@ -4424,7 +4504,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── UNFINISHED RECORD TYPE in tmp/record_type_open/Test.roc ─────────────────────
+    ── UNFINISHED RECORD TYPE [R0058] in tmp/record_type_open/Test.roc ─────────────
 
     I am partway through parsing a record type, but I got stuck here:
 
@@ -4447,7 +4527,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── UNFINISHED RECORD TYPE in tmp/record_type_open_indent/Test.roc ──────────────
+    ── UNFINISHED RECORD TYPE [R0058] in tmp/record_type_open_indent/Test.roc ──────
 
     I am partway through parsing a record type, but I got stuck here:
 
@@ -4470,7 +4550,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── UNFINISHED RECORD TYPE in tmp/record_type_end/Test.roc ──────────────────────
+    ── UNFINISHED RECORD TYPE [R0058] in tmp/record_type_end/Test.roc ──────────────
 
     I am partway through parsing a record type, but I got stuck here:
 
@@ -4492,7 +4572,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── UNFINISHED RECORD TYPE in tmp/record_type_keyword_field_name/Test.roc ───────
+    ── UNFINISHED RECORD TYPE [R0058] in ...ecord_type_keyword_field_name/Test.roc ─
 
     I just started parsing a record type, but I got stuck on this field
     name:
@@ -4514,7 +4594,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── UNFINISHED RECORD TYPE in tmp/record_type_missing_comma/Test.roc ────────────
+    ── UNFINISHED RECORD TYPE [R0058] in tmp/record_type_missing_comma/Test.roc ────
 
     I am partway through parsing a record type, but I got stuck here:
 
@@ -4531,7 +4611,7 @@ mod test_reporting {
         record_type_tab,
         "f : { foo \t }",
         @r###"
-    ── TAB CHARACTER in tmp/record_type_tab/Test.roc ───────────────────────────────
+    ── TAB CHARACTER [R0042] in tmp/record_type_tab/Test.roc ───────────────────────
 
     I encountered a tab character:
 
@@ -4546,7 +4626,7 @@ mod test_reporting {
         comment_with_tab,
         "# comment with a \t\n4",
         @r###"
-    ── TAB CHARACTER in tmp/comment_with_tab/Test.roc ──────────────────────────────
+    ── TAB CHARACTER [R0042] in tmp/comment_with_tab/Test.roc ──────────────────────
 
     I encountered a tab character:
 
@@ -4561,7 +4641,7 @@ mod test_reporting {
         comment_with_control_character,
         "# comment with a \x07\n",
         @r"
-    ── ASCII CONTROL CHARACTER in tmp/comment_with_control_character/Test.roc ──────
+    ── ASCII CONTROL CHARACTER [R0002] in ...mment_with_control_character/Test.roc ─
 
     I encountered an ASCII control character:
 
@@ -4576,7 +4656,7 @@ mod test_reporting {
         record_type_carriage_return,
         "f : { \r foo }",
         @r"
-    ── MISPLACED CARRIAGE RETURN in tmp/record_type_carriage_return/Test.roc ───────
+    ── MISPLACED CARRIAGE RETURN [R0026] in ...ecord_type_carriage_return/Test.roc ─
 
     I encountered a stray carriage return (\r):
 
@@ -4596,7 +4676,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── UNFINISHED PARENTHESES in tmp/type_in_parens_start/Test.roc ─────────────────
+    ── UNFINISHED PARENTHESES [R0055] in tmp/type_in_parens_start/Test.roc ─────────
 
     I am partway through parsing a type in parentheses, but I got stuck
     here:
@@ -4619,7 +4699,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── UNFINISHED PARENTHESES in tmp/type_in_parens_end/Test.roc ───────────────────
+    ── UNFINISHED PARENTHESES [R0055] in tmp/type_in_parens_end/Test.roc ───────────
 
     I am partway through parsing a type in parentheses, but I got stuck
     here:
@@ -4644,7 +4724,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [R0264] in /code/proj/Main.roc ───────────────────────────────
 
     I am confused by this type name:
 
@@ -4655,7 +4735,7 @@ mod test_reporting {
     qualified by a module name, like Bool or Http.Request.Request.
     "
     );
-    //                ── DOUBLE DOT ──────────────────────────────────────────────────────────────────
+    //                ── DOUBLE DOT [R0010] ──────────────────────────────────────────────────────────
     //
     //                I encountered two dots in a row:
     //
@@ -4674,7 +4754,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [R0264] in /code/proj/Main.roc ───────────────────────────────
 
     I am confused by this type name:
 
@@ -4685,7 +4765,7 @@ mod test_reporting {
     qualified by a module name, like Bool or Http.Request.Request.
     "
     );
-    //                ── TRAILING DOT ────────────────────────────────────────────────────────────────
+    //                ── TRAILING DOT [R0043] ────────────────────────────────────────────────────────
     //
     //                I encountered a dot with nothing after it:
     //
@@ -4703,7 +4783,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── UNFINISHED TYPE in tmp/type_apply_stray_dot/Test.roc ────────────────────────
+    ── UNFINISHED TYPE [R0060] in tmp/type_apply_stray_dot/Test.roc ────────────────
 
     I just started parsing a type, but I got stuck here:
 
@@ -4724,7 +4804,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [R0264] in /code/proj/Main.roc ───────────────────────────────
 
     I am confused by this type name:
 
@@ -4735,7 +4815,7 @@ mod test_reporting {
     qualified by a module name, like Bool or Http.Request.Request.
     "
     );
-    //                ── WEIRD QUALIFIED NAME ────────────────────────────────────────────────────────
+    //                ── WEIRD QUALIFIED NAME [R0075] ────────────────────────────────────────────────
     //
     //                I encountered a number at the start of a qualified name segment:
     //
@@ -4755,7 +4835,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [R0264] in /code/proj/Main.roc ───────────────────────────────
 
     I am confused by this type name:
 
@@ -4775,7 +4855,7 @@ mod test_reporting {
             "
         ),
         @r#"
-    ── MISSING FINAL EXPRESSION in tmp/def_missing_final_expression/Test.roc ───────
+    ── MISSING FINAL EXPRESSION [R0029] in ...ef_missing_final_expression/Test.roc ─
 
     I am partway through parsing a definition, but I got stuck here:
 
@@ -4803,7 +4883,7 @@ mod test_reporting {
             "
         ),
         @r#"
-    ── INDENT ENDS AFTER EXPRESSION in tmp/expression_indentation_end/Test.roc ─────
+    ── INDENT ENDS AFTER EXPRESSION [R0020] in ...ression_indentation_end/Test.roc ─
 
     I am partway through parsing an expression, but I got stuck here:
 
@@ -4829,7 +4909,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── UNFINISHED INLINE ALIAS in tmp/type_inline_alias/Test.roc ───────────────────
+    ── UNFINISHED INLINE ALIAS [R0052] in tmp/type_inline_alias/Test.roc ───────────
 
     I just started parsing an inline type alias, but I got stuck here:
 
@@ -4851,7 +4931,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── DOUBLE COMMA in tmp/type_double_comma/Test.roc ──────────────────────────────
+    ── DOUBLE COMMA [R0009] in tmp/type_double_comma/Test.roc ──────────────────────
 
     I just started parsing a function argument type, but I encountered two
     commas in a row:
@@ -4874,7 +4954,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── UNFINISHED TYPE in tmp/type_argument_no_arrow/Test.roc ──────────────────────
+    ── UNFINISHED TYPE [R0060] in tmp/type_argument_no_arrow/Test.roc ──────────────
 
     I am partway through parsing a type, but I got stuck here:
 
@@ -4897,7 +4977,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── UNFINISHED TYPE in tmp/type_argument_arrow_then_nothing/Test.roc ────────────
+    ── UNFINISHED TYPE [R0060] in tmp/type_argument_arrow_then_nothing/Test.roc ────
 
     I just started parsing a type, but I got stuck here:
 
@@ -4922,7 +5002,7 @@ mod test_reporting {
             "#
         ),
         @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0412] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the body of the `myDict` definition:
 
@@ -4957,7 +5037,7 @@ mod test_reporting {
             "#
         ),
         @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0412] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the body of the `myDict` definition:
 
@@ -4989,7 +5069,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── IF GUARD NO CONDITION in tmp/if_guard_without_condition/Test.roc ────────────
+    ── IF GUARD NO CONDITION [R0017] in tmp/if_guard_without_condition/Test.roc ────
 
     I just started parsing an if guard, but there is no guard condition:
 
@@ -5014,7 +5094,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── UNFINISHED PATTERN in tmp/empty_or_pattern/Test.roc ─────────────────────────
+    ── UNFINISHED PATTERN [R0056] in tmp/empty_or_pattern/Test.roc ─────────────────
 
     I just started parsing a pattern, but I got stuck here:
 
@@ -5039,7 +5119,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── MISSING ARROW in tmp/pattern_binds_keyword/Test.roc ─────────────────────────
+    ── MISSING ARROW [R0027] in tmp/pattern_binds_keyword/Test.roc ─────────────────
 
     I am partway through parsing a `when` expression, but got stuck here:
 
@@ -5075,7 +5155,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── UNFINISHED WHEN in tmp/when_missing_arrow/Test.roc ──────────────────────────
+    ── UNFINISHED WHEN [R0061] in tmp/when_missing_arrow/Test.roc ──────────────────
 
     I was partway through parsing a `when` expression, but I got stuck here:
 
@@ -5108,7 +5188,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── UNFINISHED ARGUMENT LIST in tmp/lambda_double_comma/Test.roc ────────────────
+    ── UNFINISHED ARGUMENT LIST [R0049] in tmp/lambda_double_comma/Test.roc ────────
 
     I am partway through parsing a function argument list, but I got stuck
     at this comma:
@@ -5129,7 +5209,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── UNFINISHED ARGUMENT LIST in tmp/lambda_leading_comma/Test.roc ───────────────
+    ── UNFINISHED ARGUMENT LIST [R0049] in tmp/lambda_leading_comma/Test.roc ───────
 
     I am partway through parsing a function argument list, but I got stuck
     at this comma:
@@ -5145,7 +5225,7 @@ mod test_reporting {
     // this should get better with time
     // TODO this formerly gave
     //
-    //                ── UNFINISHED WHEN ─────────────────────────────────────────────────────────────
+    //                ── UNFINISHED WHEN [R0061] ─────────────────────────────────────────────────────
     //
     //                I was partway through parsing a `when` expression, but I got stuck here:
     //
@@ -5176,7 +5256,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── NOT END OF FILE in tmp/when_outdented_branch/Test.roc ───────────────────────
+    ── NOT END OF FILE [R0035] in tmp/when_outdented_branch/Test.roc ───────────────
 
     I expected to reach the end of the file, but got stuck here:
 
@@ -5195,7 +5275,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── UNEXPECTED ARROW in tmp/when_over_indented_underscore/Test.roc ──────────────
+    ── UNEXPECTED ARROW [R0046] in tmp/when_over_indented_underscore/Test.roc ──────
 
     I am parsing a `when` expression right now, but this arrow is confusing
     me:
@@ -5232,7 +5312,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── UNEXPECTED ARROW in tmp/when_over_indented_int/Test.roc ─────────────────────
+    ── UNEXPECTED ARROW [R0046] in tmp/when_over_indented_int/Test.roc ─────────────
 
     I am parsing a `when` expression right now, but this arrow is confusing
     me:
@@ -5272,7 +5352,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── UNFINISHED IF in tmp/if_outdented_then/Test.roc ─────────────────────────────
+    ── UNFINISHED IF [R0051] in tmp/if_outdented_then/Test.roc ─────────────────────
 
     I was partway through parsing an `if` expression, but I got stuck here:
 
@@ -5292,7 +5372,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── UNFINISHED IF in tmp/if_missing_else/Test.roc ───────────────────────────────
+    ── UNFINISHED IF [R0051] in tmp/if_missing_else/Test.roc ───────────────────────
 
     I was partway through parsing an `if` expression, but I got stuck here:
 
@@ -5311,7 +5391,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── UNFINISHED LIST in tmp/list_double_comma/Test.roc ───────────────────────────
+    ── UNFINISHED LIST [R0053] in tmp/list_double_comma/Test.roc ───────────────────
 
     I am partway through started parsing a list, but I got stuck here:
 
@@ -5331,7 +5411,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── UNFINISHED LIST in tmp/list_without_end/Test.roc ────────────────────────────
+    ── UNFINISHED LIST [R0053] in tmp/list_without_end/Test.roc ────────────────────
 
     I am partway through started parsing a list, but I got stuck here:
 
@@ -5357,7 +5437,7 @@ mod test_reporting {
             "
         ),
         @r"
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [R0265] in /code/proj/Main.roc ───────────────────────────────
 
     This float literal contains an invalid digit:
 
@@ -5375,7 +5455,7 @@ mod test_reporting {
         unicode_not_hex,
         r#""abc\u(zzzz)def""#,
         @r#"
-    ── WEIRD CODE POINT in tmp/unicode_not_hex/Test.roc ────────────────────────────
+    ── WEIRD CODE POINT [R0065] in tmp/unicode_not_hex/Test.roc ────────────────────
 
     I am partway through parsing a unicode code point, but I got stuck
     here:
@@ -5393,7 +5473,7 @@ mod test_reporting {
         unicode_too_large,
         r#""abc\u(110000)def""#,
         @r#"
-    ── INVALID UNICODE in /code/proj/Main.roc ──────────────────────────────────────
+    ── INVALID UNICODE [R0221] in /code/proj/Main.roc ──────────────────────────────
 
     This unicode code point is invalid:
 
@@ -5408,7 +5488,7 @@ mod test_reporting {
         weird_escape,
         r#""abc\qdef""#,
         @r###"
-    ── WEIRD ESCAPE in tmp/weird_escape/Test.roc ───────────────────────────────────
+    ── WEIRD ESCAPE [R0066] in tmp/weird_escape/Test.roc ───────────────────────────
 
     I was partway through parsing a  string literal, but I got stuck here:
 
@@ -5432,7 +5512,7 @@ mod test_reporting {
         single_quote_too_long,
         r"'abcdef'",
         @r#"
-    ── INVALID SCALAR in tmp/single_quote_too_long/Test.roc ────────────────────────
+    ── INVALID SCALAR [R0025] in tmp/single_quote_too_long/Test.roc ────────────────
 
     I am part way through parsing this scalar literal (character literal),
     but it's too long to fit in a U32 so it's not a valid scalar.
@@ -5449,7 +5529,7 @@ mod test_reporting {
         single_no_end,
         r#""there is no end"#,
         @r#"
-    ── ENDLESS STRING in tmp/single_no_end/Test.roc ────────────────────────────────
+    ── ENDLESS STRING [R0015] in tmp/single_no_end/Test.roc ────────────────────────
 
     I cannot find the end of this string:
 
@@ -5465,7 +5545,7 @@ mod test_reporting {
         multi_no_end,
         r#""""there is no end"#,
         @r#"
-    ── ENDLESS STRING in tmp/multi_no_end/Test.roc ─────────────────────────────────
+    ── ENDLESS STRING [R0015] in tmp/multi_no_end/Test.roc ─────────────────────────
 
     I cannot find the end of this block string:
 
@@ -5481,7 +5561,7 @@ mod test_reporting {
         multi_insufficient_indent,
         "    \"\"\"\n  testing\n    \"\"\"", // 4 space indent on the start, 2 space on the `testing` line
         @r#"
-    ── INSUFFICIENT INDENT IN MULTI-LINE STRING in ...insufficient_indent/Test.roc ─
+    ── INSUFFICIENT INDENT IN MULTI-LINE STRING [R0021] in ...ient_indent/Test.roc ─
 
     This multiline string is not sufficiently indented:
 
@@ -5503,7 +5583,7 @@ mod test_reporting {
             "
         ),
         @r#"
-    ── INDENT ENDS AFTER EXPRESSION in tmp/dbg_without_final_expression/Test.roc ───
+    ── INDENT ENDS AFTER EXPRESSION [R0020] in ...ithout_final_expression/Test.roc ─
 
     I am partway through parsing a dbg statement, but I got stuck here:
 
@@ -5525,7 +5605,7 @@ mod test_reporting {
             "
         ),
         @r#"
-    ── INDENT ENDS AFTER EXPRESSION in ...expect_without_final_expression/Test.roc ─
+    ── INDENT ENDS AFTER EXPRESSION [R0020] in ...ithout_final_expression/Test.roc ─
 
     I am partway through parsing an expect statement, but I got stuck
     here:
@@ -5551,7 +5631,7 @@ mod test_reporting {
             "#,
             ),
             @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0408] in /code/proj/Main.roc ────────────────────────────────
 
     This `if` has an `else` branch with a different type from its `then` branch:
 
@@ -5577,7 +5657,7 @@ mod test_reporting {
                 $name,
                 &format!(r#"if Bool.true then "abc" else 1 {} 2"#, $op),
                 |golden| assert_eq!(golden, format!(
-r#"── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+r#"── TYPE MISMATCH [R0408] in /code/proj/Main.roc ────────────────────────────────
 
 This `if` has an `else` branch with a different type from its `then` branch:
 
@@ -5620,7 +5700,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0415] in /code/proj/Main.roc ────────────────────────────────
 
     This `foo` record doesn’t have a `if` field:
 
@@ -5639,7 +5719,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r###"
-    ── NOT EXPOSED in /code/proj/Main.roc ──────────────────────────────────────────
+    ── NOT EXPOSED [R0232] in /code/proj/Main.roc ──────────────────────────────────
 
     The Num module does not expose `if`:
 
@@ -5663,7 +5743,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r"
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [R0269] in /code/proj/Main.roc ───────────────────────────────
 
     I am trying to parse a record field access here:
 
@@ -5682,7 +5762,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r"
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [R0269] in /code/proj/Main.roc ───────────────────────────────
 
     I am very confused by this field access:
 
@@ -5701,7 +5781,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r"
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [R0269] in /code/proj/Main.roc ───────────────────────────────
 
     I am very confused by this field access
 
@@ -5722,7 +5802,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r"
-    ── NAMING PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── NAMING PROBLEM [R0228] in /code/proj/Main.roc ───────────────────────────────
 
     I am trying to parse an identifier here:
 
@@ -5756,7 +5836,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r#"
-    ── UNKNOWN OPERATOR in tmp/case_of/Test.roc ────────────────────────────────────
+    ── UNKNOWN OPERATOR [R0062] in tmp/case_of/Test.roc ────────────────────────────
 
     This looks like an operator, but it's not one I recognize!
 
@@ -5789,7 +5869,7 @@ All branches in an `if` must have the same type!
             "#
         ),
         @r###"
-    ── UNRECOGNIZED NAME in /code/proj/Main.roc ────────────────────────────────────
+    ── UNRECOGNIZED NAME [R0253] in /code/proj/Main.roc ────────────────────────────
 
     Nothing is named `bar` in this scope.
 
@@ -5814,7 +5894,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r#"
-    ── UNKNOWN OPERATOR in tmp/invalid_operator/Test.roc ───────────────────────────
+    ── UNKNOWN OPERATOR [R0062] in tmp/invalid_operator/Test.roc ───────────────────
 
     This looks like an operator, but it's not one I recognize!
 
@@ -5839,7 +5919,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r#"
-    ── UNKNOWN OPERATOR in tmp/double_plus/Test.roc ────────────────────────────────
+    ── UNKNOWN OPERATOR [R0062] in tmp/double_plus/Test.roc ────────────────────────
 
     This looks like an operator, but it's not one I recognize!
 
@@ -5866,7 +5946,7 @@ All branches in an `if` must have the same type!
             "
         ),
         @r#"
-    ── UNKNOWN OPERATOR in tmp/inline_hastype/Test.roc ─────────────────────────────
+    ── UNKNOWN OPERATOR [R0062] in tmp/inline_hastype/Test.roc ─────────────────────
 
     This looks like an operator, but it's not one I recognize!
 
@@ -5897,7 +5977,7 @@ All branches in an `if` must have the same type!
         |golden| pretty_assertions::assert_eq!(
             golden,
             &format!(
-                r#"── UNKNOWN OPERATOR in tmp/wild_case_arrow/Test.roc ────────────────────────────
+                r#"── UNKNOWN OPERATOR [R0062] in tmp/wild_case_arrow/Test.roc ────────────────────
 
 This looks like an operator, but it's not one I recognize!
 
@@ -5930,7 +6010,7 @@ In roc, functions are always written as a lambda, like{}
             ),
             indoc!(
                 r"
-                ── WEIRD PROVIDES in /code/proj/Main.roc ───────────────────────────────────────
+                ── WEIRD PROVIDES [R0074] in /code/proj/Main.roc ───────────────────────────────
 
                 I am partway through parsing a provides list, but I got stuck here:
 
@@ -5965,7 +6045,7 @@ In roc, functions are always written as a lambda, like{}
             ),
             indoc!(
                 r"
-                ── WEIRD PROVIDES in /code/proj/Main.roc ───────────────────────────────────────
+                ── WEIRD PROVIDES [R0074] in /code/proj/Main.roc ───────────────────────────────
 
                 I am partway through parsing a header, but I got stuck here:
 
@@ -5991,7 +6071,7 @@ In roc, functions are always written as a lambda, like{}
             ),
             indoc!(
                 r#"
-                ── WEIRD PROVIDES in /code/proj/Main.roc ───────────────────────────────────────
+                ── WEIRD PROVIDES [R0074] in /code/proj/Main.roc ───────────────────────────────
 
                 I am partway through parsing a header, but I got stuck here:
 
@@ -6018,7 +6098,7 @@ In roc, functions are always written as a lambda, like{}
             ),
             indoc!(
                 r#"
-                ── WEIRD PROVIDES in /code/proj/Main.roc ───────────────────────────────────────
+                ── WEIRD PROVIDES [R0074] in /code/proj/Main.roc ───────────────────────────────
 
                 I am partway through parsing a header, but I got stuck here:
 
@@ -6055,7 +6135,7 @@ In roc, functions are always written as a lambda, like{}
             ),
             indoc!(
                 r#"
-                ── BAD REQUIRES in /code/proj/Main.roc ─────────────────────────────────────────
+                ── BAD REQUIRES [R0006] in /code/proj/Main.roc ─────────────────────────────────
 
                 I am partway through parsing a header, but I got stuck here:
 
@@ -6083,7 +6163,7 @@ In roc, functions are always written as a lambda, like{}
             ),
             indoc!(
                 r"
-                ── WEIRD IMPORTS in /code/proj/Main.roc ────────────────────────────────────────
+                ── WEIRD IMPORTS [R0071] in /code/proj/Main.roc ────────────────────────────────
 
                 I am partway through parsing a header, but I got stuck here:
 
@@ -6110,7 +6190,7 @@ In roc, functions are always written as a lambda, like{}
             ),
             indoc!(
                 r"
-                ── WEIRD EXPOSES in /code/proj/Main.roc ────────────────────────────────────────
+                ── WEIRD EXPOSES [R0067] in /code/proj/Main.roc ────────────────────────────────
 
                 I am partway through parsing an `exposes` list, but I got stuck here:
 
@@ -6138,7 +6218,7 @@ In roc, functions are always written as a lambda, like{}
             ),
             indoc!(
                 r"
-                ── WEIRD MODULE NAME in /code/proj/Main.roc ────────────────────────────────────
+                ── WEIRD MODULE NAME [R0072] in /code/proj/Main.roc ────────────────────────────
 
                 I am partway through parsing a header, but got stuck here:
 
@@ -6164,7 +6244,7 @@ In roc, functions are always written as a lambda, like{}
             ),
             indoc!(
                 r#"
-                ── WEIRD APP NAME in /code/proj/Main.roc ───────────────────────────────────────
+                ── WEIRD APP NAME [R0063] in /code/proj/Main.roc ───────────────────────────────
 
                 I am partway through parsing a header, but got stuck here:
 
@@ -6188,7 +6268,7 @@ In roc, functions are always written as a lambda, like{}
             "
         ),
         @r"
-    ── TOO MANY ARGS in /code/proj/Main.roc ────────────────────────────────────────
+    ── TOO MANY ARGS [R0407] in /code/proj/Main.roc ────────────────────────────────
 
     This value is not a function, but it was given 2 arguments:
 
@@ -6209,7 +6289,7 @@ In roc, functions are always written as a lambda, like{}
             "
         ),
         @r"
-    ── TOO MANY ARGS in /code/proj/Main.roc ────────────────────────────────────────
+    ── TOO MANY ARGS [R0407] in /code/proj/Main.roc ────────────────────────────────
 
     This value is not a function, but it was given 2 arguments:
 
@@ -6231,7 +6311,7 @@ In roc, functions are always written as a lambda, like{}
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0412] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the body of the `x` definition:
 
@@ -6257,7 +6337,7 @@ In roc, functions are always written as a lambda, like{}
             "
         ),
         @r"
-    ── UNFINISHED PARENTHESES in tmp/pattern_in_parens_open/Test.roc ───────────────
+    ── UNFINISHED PARENTHESES [R0055] in tmp/pattern_in_parens_open/Test.roc ───────
 
     I am partway through parsing a pattern in parentheses, but I got stuck
     here:
@@ -6280,7 +6360,7 @@ In roc, functions are always written as a lambda, like{}
             "
         ),
         @r"
-    ── UNFINISHED PARENTHESES in tmp/pattern_in_parens_end_comma/Test.roc ──────────
+    ── UNFINISHED PARENTHESES [R0055] in tmp/pattern_in_parens_end_comma/Test.roc ──
 
     I am partway through parsing a pattern in parentheses, but I got stuck
     here:
@@ -6303,7 +6383,7 @@ In roc, functions are always written as a lambda, like{}
             "
         ),
         @r"
-    ── UNFINISHED PARENTHESES in tmp/pattern_in_parens_end/Test.roc ────────────────
+    ── UNFINISHED PARENTHESES [R0055] in tmp/pattern_in_parens_end/Test.roc ────────
 
     I am partway through parsing a pattern in parentheses, but I got stuck
     here:
@@ -6327,7 +6407,7 @@ In roc, functions are always written as a lambda, like{}
             "
         ),
         @r"
-    ── UNFINISHED FUNCTION in tmp/unfinished_closure_pattern_in_parens/Test.roc ────
+    ── UNFINISHED FUNCTION [R0050] in ...nished_closure_pattern_in_parens/Test.roc ─
 
     I was partway through parsing a  function, but I got stuck here:
 
@@ -6347,7 +6427,7 @@ In roc, functions are always written as a lambda, like{}
             "
         ),
         @r"
-    ── UNFINISHED PARENTHESES in tmp/pattern_in_parens_indent_open/Test.roc ────────
+    ── UNFINISHED PARENTHESES [R0055] in ...pattern_in_parens_indent_open/Test.roc ─
 
     I am partway through parsing a pattern in parentheses, but I got stuck
     here:
@@ -6372,7 +6452,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0408] in /code/proj/Main.roc ────────────────────────────────
 
     This 2nd argument to `map` has an unexpected type:
 
@@ -6400,7 +6480,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0408] in /code/proj/Main.roc ────────────────────────────────
 
     This `expect` condition needs to be a Bool:
 
@@ -6427,7 +6507,7 @@ In roc, functions are always written as a lambda, like{}
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0408] in /code/proj/Main.roc ────────────────────────────────
 
     This 2nd argument to * has an unexpected type:
 
@@ -6442,7 +6522,7 @@ In roc, functions are always written as a lambda, like{}
 
         Num *
 
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0412] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the body of the `mult` definition:
 
@@ -6471,7 +6551,7 @@ In roc, functions are always written as a lambda, like{}
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0408] in /code/proj/Main.roc ────────────────────────────────
 
     This 2nd argument to * has an unexpected type:
 
@@ -6486,7 +6566,7 @@ In roc, functions are always written as a lambda, like{}
 
         Num a
 
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0412] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the body of the `mult` definition:
 
@@ -6521,7 +6601,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r"
-    ── DUPLICATE NAME in /code/proj/Main.roc ───────────────────────────────────────
+    ── DUPLICATE NAME [R0214] in /code/proj/Main.roc ───────────────────────────────
 
     This alias has the same name as a builtin:
 
@@ -6531,7 +6611,7 @@ In roc, functions are always written as a lambda, like{}
     All builtin aliases are in scope by default, so I need this alias to
     have a different name!
 
-    ── TOO FEW TYPE ARGUMENTS in /code/proj/Main.roc ───────────────────────────────
+    ── TOO FEW TYPE ARGUMENTS [R0244] in /code/proj/Main.roc ───────────────────────
 
     The `Result` alias expects 2 type arguments, but it got 1 instead:
 
@@ -6559,7 +6639,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r"
-    ── DUPLICATE NAME in /code/proj/Main.roc ───────────────────────────────────────
+    ── DUPLICATE NAME [R0214] in /code/proj/Main.roc ───────────────────────────────
 
     This alias has the same name as a builtin:
 
@@ -6569,7 +6649,7 @@ In roc, functions are always written as a lambda, like{}
     All builtin aliases are in scope by default, so I need this alias to
     have a different name!
 
-    ── TOO MANY TYPE ARGUMENTS in /code/proj/Main.roc ──────────────────────────────
+    ── TOO MANY TYPE ARGUMENTS [R0245] in /code/proj/Main.roc ──────────────────────
 
     The `Result` alias expects 2 type arguments, but it got 3 instead:
 
@@ -6591,7 +6671,7 @@ In roc, functions are always written as a lambda, like{}
         ),
         // TODO: We should tell the user that we inferred `_` as `a`
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0412] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the body of the `f` definition:
 
@@ -6625,7 +6705,7 @@ In roc, functions are always written as a lambda, like{}
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0412] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the body of the `f` definition:
 
@@ -6657,7 +6737,7 @@ In roc, functions are always written as a lambda, like{}
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0412] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the body of the `f` definition:
 
@@ -6697,7 +6777,7 @@ In roc, functions are always written as a lambda, like{}
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0412] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the body of the `inner` definition:
 
@@ -6726,7 +6806,7 @@ In roc, functions are always written as a lambda, like{}
             "
         ),
         @r"
-    ── NOT AN INLINE ALIAS in tmp/error_inline_alias_not_an_alias/Test.roc ─────────
+    ── NOT AN INLINE ALIAS [R0034] in tmp/error_inline_alias_not_an_alias/Test.roc ─
 
     The inline type after this `as` is not a type alias:
 
@@ -6746,7 +6826,7 @@ In roc, functions are always written as a lambda, like{}
             "
         ),
         @r"
-    ── QUALIFIED ALIAS NAME in tmp/error_inline_alias_qualified/Test.roc ───────────
+    ── QUALIFIED ALIAS NAME [R0039] in tmp/error_inline_alias_qualified/Test.roc ───
 
     This type alias has a qualified name:
 
@@ -6766,7 +6846,7 @@ In roc, functions are always written as a lambda, like{}
             "
         ),
         @r"
-    ── TYPE ARGUMENT NOT LOWERCASE in ..._inline_alias_argument_uppercase/Test.roc ─
+    ── TYPE ARGUMENT NOT LOWERCASE [R0045] in ...alias_argument_uppercase/Test.roc ─
 
     This alias type argument is not lowercase:
 
@@ -6790,7 +6870,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0408] in /code/proj/Main.roc ────────────────────────────────
 
     This 1st argument to `isEmpty` has an unexpected type:
 
@@ -6826,7 +6906,7 @@ In roc, functions are always written as a lambda, like{}
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0408] in /code/proj/Main.roc ────────────────────────────────
 
     This 1st argument to `c` has an unexpected type:
 
@@ -6859,7 +6939,7 @@ In roc, functions are always written as a lambda, like{}
             "
         ),
         @r"
-    ── CYCLIC ALIAS in /code/proj/Main.roc ─────────────────────────────────────────
+    ── CYCLIC ALIAS [R0401] in /code/proj/Main.roc ─────────────────────────────────
 
     The `F` alias is self-recursive in an invalid way:
 
@@ -6882,7 +6962,7 @@ In roc, functions are always written as a lambda, like{}
             "
         ),
         @r"
-    ── CYCLIC ALIAS in /code/proj/Main.roc ─────────────────────────────────────────
+    ── CYCLIC ALIAS [R0401] in /code/proj/Main.roc ─────────────────────────────────
 
     The `F` alias is self-recursive in an invalid way:
 
@@ -6904,7 +6984,7 @@ In roc, functions are always written as a lambda, like{}
             "
         ),
         @r"
-    ── CYCLIC ALIAS in /code/proj/Main.roc ─────────────────────────────────────────
+    ── CYCLIC ALIAS [R0401] in /code/proj/Main.roc ─────────────────────────────────
 
     The `F` alias is self-recursive in an invalid way:
 
@@ -6929,7 +7009,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0413] in /code/proj/Main.roc ────────────────────────────────
 
     The 1st argument to `job` is weird:
 
@@ -6964,7 +7044,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0412] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the body of the `job` definition:
 
@@ -6995,7 +7075,7 @@ In roc, functions are always written as a lambda, like{}
             "
         ),
         @r"
-    ── NESTED DATATYPE in /code/proj/Main.roc ──────────────────────────────────────
+    ── NESTED DATATYPE [R0229] in /code/proj/Main.roc ──────────────────────────────
 
     `Nested` is a nested datatype. Here is one recursive usage of it:
 
@@ -7023,7 +7103,7 @@ In roc, functions are always written as a lambda, like{}
             "
         ),
         @r"
-    ── NESTED DATATYPE in /code/proj/Main.roc ──────────────────────────────────────
+    ── NESTED DATATYPE [R0229] in /code/proj/Main.roc ──────────────────────────────
 
     `Nested` is a nested datatype. Here is one recursive usage of it:
 
@@ -7071,7 +7151,7 @@ In roc, functions are always written as a lambda, like{}
 
                     let real = format!(indoc!(
                         r"
-                        ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+                        ── TYPE MISMATCH [R0408] in /code/proj/Main.roc ────────────────────────────────
 
                         This 1st argument to `use` has an unexpected type:
 
@@ -7137,7 +7217,7 @@ In roc, functions are always written as a lambda, like{}
 
                     let real = format!(indoc!(
                         r"
-                        ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+                        ── TYPE MISMATCH [R0408] in /code/proj/Main.roc ────────────────────────────────
 
                         The branches of this `when` expression don't match the condition:
 
@@ -7188,7 +7268,7 @@ In roc, functions are always written as a lambda, like{}
         ),
         // TODO: link to number suffixes
         @r"
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [R0266] in /code/proj/Main.roc ───────────────────────────────
 
     This integer literal contains an invalid digit:
 
@@ -7211,7 +7291,7 @@ In roc, functions are always written as a lambda, like{}
         ),
         // TODO: link to number suffixes
         @r"
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [R0266] in /code/proj/Main.roc ───────────────────────────────
 
     This integer literal contains an invalid digit:
 
@@ -7233,7 +7313,7 @@ In roc, functions are always written as a lambda, like{}
             "
         ),
         @r"
-    ── CONFLICTING NUMBER SUFFIX in /code/proj/Main.roc ────────────────────────────
+    ── CONFLICTING NUMBER SUFFIX [R0208] in /code/proj/Main.roc ────────────────────
 
     This number literal is an integer, but it has a float suffix:
 
@@ -7250,7 +7330,7 @@ In roc, functions are always written as a lambda, like{}
             "
         ),
         @r"
-    ── CONFLICTING NUMBER SUFFIX in /code/proj/Main.roc ────────────────────────────
+    ── CONFLICTING NUMBER SUFFIX [R0208] in /code/proj/Main.roc ────────────────────
 
     This number literal is a float, but it has an integer suffix:
 
@@ -7263,7 +7343,7 @@ In roc, functions are always written as a lambda, like{}
         u8_overflow,
         "256u8",
         @r"
-    ── NUMBER OVERFLOWS SUFFIX in /code/proj/Main.roc ──────────────────────────────
+    ── NUMBER OVERFLOWS SUFFIX [R0233] in /code/proj/Main.roc ──────────────────────
 
     This integer literal overflows the type indicated by its suffix:
 
@@ -7279,7 +7359,7 @@ In roc, functions are always written as a lambda, like{}
         negative_u8,
         "-1u8",
         @r"
-    ── NUMBER UNDERFLOWS SUFFIX in /code/proj/Main.roc ─────────────────────────────
+    ── NUMBER UNDERFLOWS SUFFIX [R0234] in /code/proj/Main.roc ─────────────────────
 
     This integer literal underflows the type indicated by its suffix:
 
@@ -7295,7 +7375,7 @@ In roc, functions are always written as a lambda, like{}
         u16_overflow,
         "65536u16",
         @r"
-    ── NUMBER OVERFLOWS SUFFIX in /code/proj/Main.roc ──────────────────────────────
+    ── NUMBER OVERFLOWS SUFFIX [R0233] in /code/proj/Main.roc ──────────────────────
 
     This integer literal overflows the type indicated by its suffix:
 
@@ -7311,7 +7391,7 @@ In roc, functions are always written as a lambda, like{}
         negative_u16,
         "-1u16",
         @r"
-    ── NUMBER UNDERFLOWS SUFFIX in /code/proj/Main.roc ─────────────────────────────
+    ── NUMBER UNDERFLOWS SUFFIX [R0234] in /code/proj/Main.roc ─────────────────────
 
     This integer literal underflows the type indicated by its suffix:
 
@@ -7327,7 +7407,7 @@ In roc, functions are always written as a lambda, like{}
         u32_overflow,
         "4_294_967_296u32",
         @r"
-    ── NUMBER OVERFLOWS SUFFIX in /code/proj/Main.roc ──────────────────────────────
+    ── NUMBER OVERFLOWS SUFFIX [R0233] in /code/proj/Main.roc ──────────────────────
 
     This integer literal overflows the type indicated by its suffix:
 
@@ -7343,7 +7423,7 @@ In roc, functions are always written as a lambda, like{}
         negative_u32,
         "-1u32",
         @r"
-    ── NUMBER UNDERFLOWS SUFFIX in /code/proj/Main.roc ─────────────────────────────
+    ── NUMBER UNDERFLOWS SUFFIX [R0234] in /code/proj/Main.roc ─────────────────────
 
     This integer literal underflows the type indicated by its suffix:
 
@@ -7359,7 +7439,7 @@ In roc, functions are always written as a lambda, like{}
         u64_overflow,
         "18_446_744_073_709_551_616u64",
         @r"
-    ── NUMBER OVERFLOWS SUFFIX in /code/proj/Main.roc ──────────────────────────────
+    ── NUMBER OVERFLOWS SUFFIX [R0233] in /code/proj/Main.roc ──────────────────────
 
     This integer literal overflows the type indicated by its suffix:
 
@@ -7375,7 +7455,7 @@ In roc, functions are always written as a lambda, like{}
         negative_u64,
         "-1u64",
         @r"
-    ── NUMBER UNDERFLOWS SUFFIX in /code/proj/Main.roc ─────────────────────────────
+    ── NUMBER UNDERFLOWS SUFFIX [R0234] in /code/proj/Main.roc ─────────────────────
 
     This integer literal underflows the type indicated by its suffix:
 
@@ -7391,7 +7471,7 @@ In roc, functions are always written as a lambda, like{}
         negative_u128,
         "-1u128",
         @r"
-    ── NUMBER UNDERFLOWS SUFFIX in /code/proj/Main.roc ─────────────────────────────
+    ── NUMBER UNDERFLOWS SUFFIX [R0234] in /code/proj/Main.roc ─────────────────────
 
     This integer literal underflows the type indicated by its suffix:
 
@@ -7407,7 +7487,7 @@ In roc, functions are always written as a lambda, like{}
         i8_overflow,
         "128i8",
         @r"
-    ── NUMBER OVERFLOWS SUFFIX in /code/proj/Main.roc ──────────────────────────────
+    ── NUMBER OVERFLOWS SUFFIX [R0233] in /code/proj/Main.roc ──────────────────────
 
     This integer literal overflows the type indicated by its suffix:
 
@@ -7423,7 +7503,7 @@ In roc, functions are always written as a lambda, like{}
         i8_underflow,
         "-129i8",
         @r"
-    ── NUMBER UNDERFLOWS SUFFIX in /code/proj/Main.roc ─────────────────────────────
+    ── NUMBER UNDERFLOWS SUFFIX [R0234] in /code/proj/Main.roc ─────────────────────
 
     This integer literal underflows the type indicated by its suffix:
 
@@ -7439,7 +7519,7 @@ In roc, functions are always written as a lambda, like{}
         i16_overflow,
         "32768i16",
         @r"
-    ── NUMBER OVERFLOWS SUFFIX in /code/proj/Main.roc ──────────────────────────────
+    ── NUMBER OVERFLOWS SUFFIX [R0233] in /code/proj/Main.roc ──────────────────────
 
     This integer literal overflows the type indicated by its suffix:
 
@@ -7455,7 +7535,7 @@ In roc, functions are always written as a lambda, like{}
         i16_underflow,
         "-32769i16",
         @r"
-    ── NUMBER UNDERFLOWS SUFFIX in /code/proj/Main.roc ─────────────────────────────
+    ── NUMBER UNDERFLOWS SUFFIX [R0234] in /code/proj/Main.roc ─────────────────────
 
     This integer literal underflows the type indicated by its suffix:
 
@@ -7471,7 +7551,7 @@ In roc, functions are always written as a lambda, like{}
         i32_overflow,
         "2_147_483_648i32",
         @r"
-    ── NUMBER OVERFLOWS SUFFIX in /code/proj/Main.roc ──────────────────────────────
+    ── NUMBER OVERFLOWS SUFFIX [R0233] in /code/proj/Main.roc ──────────────────────
 
     This integer literal overflows the type indicated by its suffix:
 
@@ -7487,7 +7567,7 @@ In roc, functions are always written as a lambda, like{}
         i32_underflow,
         "-2_147_483_649i32",
         @r"
-    ── NUMBER UNDERFLOWS SUFFIX in /code/proj/Main.roc ─────────────────────────────
+    ── NUMBER UNDERFLOWS SUFFIX [R0234] in /code/proj/Main.roc ─────────────────────
 
     This integer literal underflows the type indicated by its suffix:
 
@@ -7503,7 +7583,7 @@ In roc, functions are always written as a lambda, like{}
         i64_overflow,
         "9_223_372_036_854_775_808i64",
         @r"
-    ── NUMBER OVERFLOWS SUFFIX in /code/proj/Main.roc ──────────────────────────────
+    ── NUMBER OVERFLOWS SUFFIX [R0233] in /code/proj/Main.roc ──────────────────────
 
     This integer literal overflows the type indicated by its suffix:
 
@@ -7519,7 +7599,7 @@ In roc, functions are always written as a lambda, like{}
         i64_underflow,
         "-9_223_372_036_854_775_809i64",
         @r"
-    ── NUMBER UNDERFLOWS SUFFIX in /code/proj/Main.roc ─────────────────────────────
+    ── NUMBER UNDERFLOWS SUFFIX [R0234] in /code/proj/Main.roc ─────────────────────
 
     This integer literal underflows the type indicated by its suffix:
 
@@ -7535,7 +7615,7 @@ In roc, functions are always written as a lambda, like{}
         i128_overflow,
         "170_141_183_460_469_231_731_687_303_715_884_105_728i128",
         @r"
-    ── NUMBER OVERFLOWS SUFFIX in /code/proj/Main.roc ──────────────────────────────
+    ── NUMBER OVERFLOWS SUFFIX [R0233] in /code/proj/Main.roc ──────────────────────
 
     This integer literal overflows the type indicated by its suffix:
 
@@ -7557,7 +7637,7 @@ In roc, functions are always written as a lambda, like{}
         // TODO: this error message could be improved, e.g. something like "This argument can
         // be used as ... because of its literal value"
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0408] in /code/proj/Main.roc ────────────────────────────────
 
     This 2nd argument to `get` has an unexpected type:
 
@@ -7583,7 +7663,7 @@ In roc, functions are always written as a lambda, like{}
              "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0408] in /code/proj/Main.roc ────────────────────────────────
 
     This 2nd argument to `get` has an unexpected type:
 
@@ -7610,7 +7690,7 @@ In roc, functions are always written as a lambda, like{}
              "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0408] in /code/proj/Main.roc ────────────────────────────────
 
     This 2nd argument to `get` has an unexpected type:
 
@@ -7637,7 +7717,7 @@ In roc, functions are always written as a lambda, like{}
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0408] in /code/proj/Main.roc ────────────────────────────────
 
     The branches of this `when` expression don't match the condition:
 
@@ -7668,7 +7748,7 @@ In roc, functions are always written as a lambda, like{}
             "
         ),
         @r"
-    ── CYCLIC ALIAS in /code/proj/Main.roc ─────────────────────────────────────────
+    ── CYCLIC ALIAS [R0401] in /code/proj/Main.roc ─────────────────────────────────
 
     The `R` alias is self-recursive in an invalid way:
 
@@ -7691,7 +7771,7 @@ In roc, functions are always written as a lambda, like{}
             "
         ),
         @r"
-    ── CYCLIC ALIAS in /code/proj/Main.roc ─────────────────────────────────────────
+    ── CYCLIC ALIAS [R0401] in /code/proj/Main.roc ─────────────────────────────────
 
     The `R` alias is self-recursive in an invalid way:
 
@@ -7715,7 +7795,7 @@ In roc, functions are always written as a lambda, like{}
             "
         ),
         @r"
-    ── CYCLIC ALIAS in /code/proj/Main.roc ─────────────────────────────────────────
+    ── CYCLIC ALIAS [R0401] in /code/proj/Main.roc ─────────────────────────────────
 
     The `Foo` alias is recursive in an invalid way:
 
@@ -7750,7 +7830,7 @@ In roc, functions are always written as a lambda, like{}
             "
         ),
         @r"
-    ── DUPLICATE NAME in /code/proj/Main.roc ───────────────────────────────────────
+    ── DUPLICATE NAME [R0214] in /code/proj/Main.roc ───────────────────────────────
 
     This alias has the same name as a builtin:
 
@@ -7770,7 +7850,7 @@ In roc, functions are always written as a lambda, like{}
             "
         ),
         @r"
-    ── OPAQUE TYPE NOT DEFINED in /code/proj/Main.roc ──────────────────────────────
+    ── OPAQUE TYPE NOT DEFINED [R0238] in /code/proj/Main.roc ──────────────────────
 
     The opaque type Age referenced here is not defined:
 
@@ -7791,7 +7871,7 @@ In roc, functions are always written as a lambda, like{}
             "
         ),
         @r"
-    ── OPAQUE TYPE NOT DEFINED in /code/proj/Main.roc ──────────────────────────────
+    ── OPAQUE TYPE NOT DEFINED [R0238] in /code/proj/Main.roc ──────────────────────
 
     The opaque type Age referenced here is not defined:
 
@@ -7805,7 +7885,7 @@ In roc, functions are always written as a lambda, like{}
 
     Note: It looks like there are no opaque types declared in this scope yet!
 
-    ── UNUSED DEFINITION in /code/proj/Main.roc ────────────────────────────────────
+    ── UNUSED DEFINITION [R0255] in /code/proj/Main.roc ────────────────────────────
 
     `Age` is not used anywhere in your code.
 
@@ -7828,7 +7908,7 @@ In roc, functions are always written as a lambda, like{}
         // and checking it during can. The reason the error appears is because it is parsed as
         // Apply(Error(OtherModule), [@Age, 21])
         @r"
-    ── OPAQUE TYPE NOT DEFINED in /code/proj/Main.roc ──────────────────────────────
+    ── OPAQUE TYPE NOT DEFINED [R0238] in /code/proj/Main.roc ──────────────────────
 
     The opaque type Age referenced here is not defined:
 
@@ -7837,7 +7917,7 @@ In roc, functions are always written as a lambda, like{}
 
     Note: It looks like there are no opaque types declared in this scope yet!
 
-    ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+    ── SYNTAX PROBLEM [R0269] in /code/proj/Main.roc ───────────────────────────────
 
     I am trying to parse a qualified name here:
 
@@ -7864,7 +7944,7 @@ In roc, functions are always written as a lambda, like{}
         // `@Age` can be linked to the declaration of `Age` inside `age`, and a suggestion to
         // raise that declaration to the outer scope.
         @r"
-    ── UNUSED DEFINITION in /code/proj/Main.roc ────────────────────────────────────
+    ── UNUSED DEFINITION [R0255] in /code/proj/Main.roc ────────────────────────────
 
     `Age` is not used anywhere in your code.
 
@@ -7874,7 +7954,7 @@ In roc, functions are always written as a lambda, like{}
     If you didn't intend on using `Age` then remove it so future readers of
     your code don't wonder why it is there.
 
-    ── OPAQUE TYPE NOT DEFINED in /code/proj/Main.roc ──────────────────────────────
+    ── OPAQUE TYPE NOT DEFINED [R0238] in /code/proj/Main.roc ──────────────────────
 
     The opaque type Age referenced here is not defined:
 
@@ -7895,7 +7975,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r"
-    ── MODULE NOT IMPORTED in /code/proj/Main.roc ──────────────────────────────────
+    ── MODULE NOT IMPORTED [R0224] in /code/proj/Main.roc ──────────────────────────
 
     The `Task` module is not imported:
 
@@ -7927,7 +8007,7 @@ In roc, functions are always written as a lambda, like{}
         // TODO(opaques): error could be improved by saying that the opaque definition demands
         // that the argument be a U8, and linking to the definitin!
         @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0408] in /code/proj/Main.roc ────────────────────────────────
 
     This expression is used in an unexpected way:
 
@@ -7956,7 +8036,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0408] in /code/proj/Main.roc ────────────────────────────────
 
     This expression is used in an unexpected way:
 
@@ -7986,7 +8066,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0412] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the body of the `v` definition:
 
@@ -8024,7 +8104,7 @@ In roc, functions are always written as a lambda, like{}
         // TODO(opaques): error could be improved by saying that the user-provided pattern
         // probably wants to change "Age" to "@Age"!
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0413] in /code/proj/Main.roc ────────────────────────────────
 
     The 1st argument to `f` is weird:
 
@@ -8059,7 +8139,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0413] in /code/proj/Main.roc ────────────────────────────────
 
     The 2nd pattern in this `when` does not match the previous ones:
 
@@ -8090,7 +8170,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0408] in /code/proj/Main.roc ────────────────────────────────
 
     The branches of this `when` expression don't match the condition:
 
@@ -8128,7 +8208,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r#"
-    ── UNSAFE PATTERN in /code/proj/Main.roc ───────────────────────────────────────
+    ── UNSAFE PATTERN [R0410] in /code/proj/Main.roc ───────────────────────────────
 
     This `when` does not cover all the possibilities:
 
@@ -8161,7 +8241,7 @@ In roc, functions are always written as a lambda, like{}
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0408] in /code/proj/Main.roc ────────────────────────────────
 
     This 1st argument to `y` has an unexpected type:
 
@@ -8194,7 +8274,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r#"
-    ── UNSAFE PATTERN in /code/proj/Main.roc ───────────────────────────────────────
+    ── UNSAFE PATTERN [R0410] in /code/proj/Main.roc ───────────────────────────────
 
     This `when` does not cover all the possibilities:
 
@@ -8218,7 +8298,7 @@ In roc, functions are always written as a lambda, like{}
             "
         ),
         @r"
-    ── INVALID_EXTENSION_TYPE in /code/proj/Main.roc ───────────────────────────────
+    ── INVALID_EXTENSION_TYPE [R0222] in /code/proj/Main.roc ───────────────────────
 
     This record extension type is invalid:
 
@@ -8239,7 +8319,7 @@ In roc, functions are always written as a lambda, like{}
             "
         ),
         @r"
-    ── INVALID_EXTENSION_TYPE in /code/proj/Main.roc ───────────────────────────────
+    ── INVALID_EXTENSION_TYPE [R0222] in /code/proj/Main.roc ───────────────────────
 
     This tag union extension type is invalid:
 
@@ -8266,7 +8346,7 @@ In roc, functions are always written as a lambda, like{}
             "
         ),
         @r"
-    ── UNRECOGNIZED NAME in /code/proj/Main.roc ────────────────────────────────────
+    ── UNRECOGNIZED NAME [R0253] in /code/proj/Main.roc ────────────────────────────
 
     Nothing is named `UnknownType` in this scope.
 
@@ -8280,7 +8360,7 @@ In roc, functions are always written as a lambda, like{}
         Unsigned16
         Unsigned64
 
-    ── UNRECOGNIZED NAME in /code/proj/Main.roc ────────────────────────────────────
+    ── UNRECOGNIZED NAME [R0253] in /code/proj/Main.roc ────────────────────────────
 
     Nothing is named `UnknownType` in this scope.
 
@@ -8307,7 +8387,7 @@ In roc, functions are always written as a lambda, like{}
             "
         ),
         @r"
-    ── UNFINISHED ABILITY in tmp/ability_first_demand_not_indented_enough/Test.roc ─
+    ── UNFINISHED ABILITY [R0048] in ..._first_demand_not_indented_enough/Test.roc ─
 
     I was partway through parsing an ability definition, but I got stuck
     here:
@@ -8332,7 +8412,7 @@ In roc, functions are always written as a lambda, like{}
             "
         ),
         @r"
-        ── UNFINISHED ABILITY in tmp/ability_demands_not_indented_with_first/Test.roc ──
+        ── UNFINISHED ABILITY [R0048] in ...y_demands_not_indented_with_first/Test.roc ─
 
         I was partway through parsing an ability definition, but I got stuck
         here:
@@ -8355,7 +8435,7 @@ In roc, functions are always written as a lambda, like{}
                 "
         ),
         @r"
-        ── UNFINISHED ABILITY in tmp/ability_demand_value_has_args/Test.roc ────────────
+        ── UNFINISHED ABILITY [R0048] in tmp/ability_demand_value_has_args/Test.roc ────
 
         I was partway through parsing an ability definition, but I got stuck
         here:
@@ -8379,7 +8459,7 @@ In roc, functions are always written as a lambda, like{}
             "
         ),
         @r"
-    ── UNFINISHED ABILITY in tmp/ability_non_signature_expression/Test.roc ─────────
+    ── UNFINISHED ABILITY [R0048] in tmp/ability_non_signature_expression/Test.roc ─
 
     I was partway through parsing an ability definition, but I got stuck
     here:
@@ -8402,7 +8482,7 @@ In roc, functions are always written as a lambda, like{}
             "
         ),
         @r"
-    ── UNBOUND TYPE VARIABLE in /code/proj/Main.roc ────────────────────────────────
+    ── UNBOUND TYPE VARIABLE [R0248] in /code/proj/Main.roc ────────────────────────
 
     The definition of `I` has an unbound type variable:
 
@@ -8424,7 +8504,7 @@ In roc, functions are always written as a lambda, like{}
             "
         ),
         @r"
-    ── UNBOUND TYPE VARIABLE in /code/proj/Main.roc ────────────────────────────────
+    ── UNBOUND TYPE VARIABLE [R0248] in /code/proj/Main.roc ────────────────────────
 
     The definition of `I` has an unbound type variable:
 
@@ -8446,7 +8526,7 @@ In roc, functions are always written as a lambda, like{}
             "
         ),
         @r"
-    ── UNBOUND TYPE VARIABLE in /code/proj/Main.roc ────────────────────────────────
+    ── UNBOUND TYPE VARIABLE [R0248] in /code/proj/Main.roc ────────────────────────
 
     The definition of `I` has 2 unbound type variables.
 
@@ -8470,7 +8550,7 @@ In roc, functions are always written as a lambda, like{}
             "
         ),
         @r"
-    ── UNBOUND TYPE VARIABLE in /code/proj/Main.roc ────────────────────────────────
+    ── UNBOUND TYPE VARIABLE [R0248] in /code/proj/Main.roc ────────────────────────
 
     The definition of `I` has an unbound type variable:
 
@@ -8492,7 +8572,7 @@ In roc, functions are always written as a lambda, like{}
             "
         ),
         @r"
-    ── UNBOUND TYPE VARIABLE in /code/proj/Main.roc ────────────────────────────────
+    ── UNBOUND TYPE VARIABLE [R0248] in /code/proj/Main.roc ────────────────────────
 
     The definition of `I` has an unbound type variable:
 
@@ -8515,7 +8595,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r"
-    ── ABILITY HAS TYPE VARIABLES in /code/proj/Main.roc ───────────────────────────
+    ── ABILITY HAS TYPE VARIABLES [R0200] in /code/proj/Main.roc ───────────────────
 
     The definition of the `MHash` ability includes type variables:
 
@@ -8525,7 +8605,7 @@ In roc, functions are always written as a lambda, like{}
     Abilities cannot depend on type variables, but their member values
     can!
 
-    ── UNUSED DEFINITION in /code/proj/Main.roc ────────────────────────────────────
+    ── UNUSED DEFINITION [R0255] in /code/proj/Main.roc ────────────────────────────
 
     `MHash` is not used anywhere in your code.
 
@@ -8547,7 +8627,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r#"
-    ── IMPLEMENTS CLAUSE IS NOT AN ABILITY in /code/proj/Main.roc ──────────────────
+    ── IMPLEMENTS CLAUSE IS NOT AN ABILITY [R0219] in /code/proj/Main.roc ──────────
 
     The type referenced in this "implements" clause is not an ability:
 
@@ -8566,7 +8646,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r"
-        ── DUPLICATE NAME in /code/proj/Main.roc ───────────────────────────────────────
+        ── DUPLICATE NAME [R0214] in /code/proj/Main.roc ───────────────────────────────
 
         The `a` name is first defined here:
 
@@ -8595,7 +8675,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r"
-        ── DUPLICATE NAME in /code/proj/Main.roc ───────────────────────────────────────
+        ── DUPLICATE NAME [R0214] in /code/proj/Main.roc ───────────────────────────────
 
         The `Ability` name is first defined here:
 
@@ -8622,7 +8702,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r"
-        ── ABILITY MEMBER MISSING IMPLEMENTS CLAUSE in /code/proj/Main.roc ─────────────
+        ── ABILITY MEMBER MISSING IMPLEMENTS CLAUSE [R0203] in /code/proj/Main.roc ─────
 
         The definition of the ability member `ab` does not include an `implements`
         clause binding a type variable to the ability `Ability`:
@@ -8637,7 +8717,7 @@ In roc, functions are always written as a lambda, like{}
 
         Otherwise, the function does not need to be part of the ability!
 
-        ── UNUSED DEFINITION in /code/proj/Main.roc ────────────────────────────────────
+        ── UNUSED DEFINITION [R0255] in /code/proj/Main.roc ────────────────────────────
 
         `Ability` is not used anywhere in your code.
 
@@ -8659,7 +8739,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r"
-        ── ABILITY MEMBER BINDS MULTIPLE VARIABLES in /code/proj/Main.roc ──────────────
+        ── ABILITY MEMBER BINDS MULTIPLE VARIABLES [R0202] in /code/proj/Main.roc ──────
 
         The definition of the ability member `eq` includes multiple variables
         bound to the `MEq`` ability:`
@@ -8687,7 +8767,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r"
-    ── ILLEGAL IMPLEMENTS CLAUSE in /code/proj/Main.roc ────────────────────────────
+    ── ILLEGAL IMPLEMENTS CLAUSE [R0217] in /code/proj/Main.roc ────────────────────
 
     An `implements` clause is not allowed here:
 
@@ -8697,7 +8777,7 @@ In roc, functions are always written as a lambda, like{}
     `implements` clauses can only be specified on the top-level type
     annotations.
 
-    ── ABILITY MEMBER MISSING IMPLEMENTS CLAUSE in /code/proj/Main.roc ─────────────
+    ── ABILITY MEMBER MISSING IMPLEMENTS CLAUSE [R0203] in /code/proj/Main.roc ─────
 
     The definition of the ability member `hash` does not include an
     `implements` clause binding a type variable to the ability `MHash`:
@@ -8728,7 +8808,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r"
-        ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+        ── TYPE MISMATCH [R0408] in /code/proj/Main.roc ────────────────────────────────
 
         Something is off with this specialization of `hash`:
 
@@ -8761,7 +8841,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r"
-    ── INCOMPLETE ABILITY IMPLEMENTATION in /code/proj/Main.roc ────────────────────
+    ── INCOMPLETE ABILITY IMPLEMENTATION [R0220] in /code/proj/Main.roc ────────────
 
     This type does not fully implement the `MEq` ability:
 
@@ -8787,7 +8867,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r"
-    ── UNUSED DEFINITION in /code/proj/Main.roc ────────────────────────────────────
+    ── UNUSED DEFINITION [R0255] in /code/proj/Main.roc ────────────────────────────
 
     `hash` is not used anywhere in your code.
 
@@ -8816,7 +8896,7 @@ In roc, functions are always written as a lambda, like{}
         ),
         // TODO: the error message here could be seriously improved!
         @r"
-    ── OVERLOADED SPECIALIZATION in /code/proj/Main.roc ────────────────────────────
+    ── OVERLOADED SPECIALIZATION [R0241] in /code/proj/Main.roc ────────────────────
 
     This ability member specialization is already claimed to specialize
     another opaque type:
@@ -8829,7 +8909,7 @@ In roc, functions are always written as a lambda, like{}
     Ability specializations can only provide implementations for one
     opaque type, since all opaque types are different!
 
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0408] in /code/proj/Main.roc ────────────────────────────────
 
     This specialization of `hash` is overly general:
 
@@ -8869,7 +8949,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r"
-    ── OVERLOADED SPECIALIZATION in /code/proj/Main.roc ────────────────────────────
+    ── OVERLOADED SPECIALIZATION [R0241] in /code/proj/Main.roc ────────────────────
 
     This ability member specialization is already claimed to specialize
     another opaque type:
@@ -8900,7 +8980,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0408] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with this specialization of `eq`:
 
@@ -8935,7 +9015,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0412] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the body of the `hash` definition:
 
@@ -8976,7 +9056,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0414] in /code/proj/Main.roc ────────────────────────────────
 
     This expression has a type that does not implement the abilities it's expected to:
 
@@ -8989,7 +9069,7 @@ In roc, functions are always written as a lambda, like{}
 
     Only builtin abilities can have generated implementations!
 
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0414] in /code/proj/Main.roc ────────────────────────────────
 
     This expression has a type that does not implement the abilities it's expected to:
 
@@ -9014,7 +9094,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r"
-        ── ABILITY NOT ON TOP-LEVEL in /code/proj/Main.roc ─────────────────────────────
+        ── ABILITY NOT ON TOP-LEVEL [R0204] in /code/proj/Main.roc ─────────────────────
 
         This ability definition is not on the top-level of a module:
 
@@ -9042,7 +9122,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0412] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the body of the `hashable` definition:
 
@@ -9088,7 +9168,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r"
-    ── ABILITY USED AS TYPE in /code/proj/Main.roc ─────────────────────────────────
+    ── ABILITY USED AS TYPE [R0205] in /code/proj/Main.roc ─────────────────────────
 
     You are attempting to use the ability `MHash` as a type directly:
 
@@ -9102,7 +9182,7 @@ In roc, functions are always written as a lambda, like{}
 
         a implements MHash
 
-    ── ABILITY USED AS TYPE in /code/proj/Main.roc ─────────────────────────────────
+    ── ABILITY USED AS TYPE [R0205] in /code/proj/Main.roc ─────────────────────────
 
     You are attempting to use the ability `MHash` as a type directly:
 
@@ -9132,7 +9212,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0408] in /code/proj/Main.roc ────────────────────────────────
 
     The branches of this `when` expression don't match the condition:
 
@@ -9185,7 +9265,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r#"
-        ── WEIRD IMPORTS in tmp/imports_missing_comma/Test.roc ─────────────────────────
+        ── WEIRD IMPORTS [R0071] in tmp/imports_missing_comma/Test.roc ─────────────────
 
         I am partway through parsing a imports list, but I got stuck here:
 
@@ -9210,7 +9290,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r#"
-        ── UNSAFE PATTERN in /code/proj/Main.roc ───────────────────────────────────────
+        ── UNSAFE PATTERN [R0410] in /code/proj/Main.roc ───────────────────────────────
 
         This `when` does not cover all the possibilities:
 
@@ -9257,7 +9337,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r"
-        ── SPECIALIZATION NOT ON TOP-LEVEL in /code/proj/Main.roc ──────────────────────
+        ── SPECIALIZATION NOT ON TOP-LEVEL [R0243] in /code/proj/Main.roc ──────────────
 
         This specialization of the `default` ability member is in a nested
         scope:
@@ -9282,7 +9362,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0408] in /code/proj/Main.roc ────────────────────────────────
 
     This 2nd argument to == has an unexpected type:
 
@@ -9318,7 +9398,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0408] in /code/proj/Main.roc ────────────────────────────────
 
     This 1st argument to `remove` has an unexpected type:
 
@@ -9339,7 +9419,7 @@ In roc, functions are always written as a lambda, like{}
     change the type annotation to be more specific? Maybe change the code
     to be more general?
 
-    ── CIRCULAR TYPE in /code/proj/Main.roc ────────────────────────────────────────
+    ── CIRCULAR TYPE [R0400] in /code/proj/Main.roc ────────────────────────────────
 
     I'm inferring a weird self-referential type for `new`:
 
@@ -9352,7 +9432,7 @@ In roc, functions are always written as a lambda, like{}
 
         { set : Set ∞ }
 
-    ── CIRCULAR TYPE in /code/proj/Main.roc ────────────────────────────────────────
+    ── CIRCULAR TYPE [R0400] in /code/proj/Main.roc ────────────────────────────────
 
     I'm inferring a weird self-referential type for `goal`:
 
@@ -9382,7 +9462,7 @@ In roc, functions are always written as a lambda, like{}
             "
         ),
         @r"
-        ── CIRCULAR DEFINITION in /code/proj/Main.roc ──────────────────────────────────
+        ── CIRCULAR DEFINITION [R0207] in /code/proj/Main.roc ──────────────────────────
 
         The `t1` definition is causing a very tricky infinite loop:
 
@@ -9410,7 +9490,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0414] in /code/proj/Main.roc ────────────────────────────────
 
     This expression has a type that does not implement the abilities it's expected to:
 
@@ -9438,7 +9518,7 @@ In roc, functions are always written as a lambda, like{}
         // TODO: this error message is quite unfortunate. We should remove the duplication, and
         // also support regions that point to things in other modules. See also https://github.com/roc-lang/roc/issues/3056.
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0414] in /code/proj/Main.roc ────────────────────────────────
 
     This expression has a type that does not implement the abilities it's expected to:
 
@@ -9475,7 +9555,7 @@ In roc, functions are always written as a lambda, like{}
                 "#
         ),
         @r"
-            ── CIRCULAR DEFINITION in /code/proj/Main.roc ──────────────────────────────────
+            ── CIRCULAR DEFINITION [R0207] in /code/proj/Main.roc ──────────────────────────
 
             The `t1` definition is causing a very tricky infinite loop:
 
@@ -9505,7 +9585,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r"
-    ── IMPLEMENTATION NOT FOUND in /code/proj/Main.roc ─────────────────────────────
+    ── IMPLEMENTATION NOT FOUND [R0218] in /code/proj/Main.roc ─────────────────────
 
     An implementation of `eq` could not be found in this scope:
 
@@ -9516,7 +9596,7 @@ In roc, functions are always written as a lambda, like{}
     another variable that implements this ability member, like
     { eq: myeq }
 
-    ── INCOMPLETE ABILITY IMPLEMENTATION in /code/proj/Main.roc ────────────────────
+    ── INCOMPLETE ABILITY IMPLEMENTATION [R0220] in /code/proj/Main.roc ────────────
 
     This type does not fully implement the `MEq` ability:
 
@@ -9543,7 +9623,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r"
-    ── UNRECOGNIZED NAME in /code/proj/Main.roc ────────────────────────────────────
+    ── UNRECOGNIZED NAME [R0253] in /code/proj/Main.roc ────────────────────────────
 
     Nothing is named `aMEq` in this scope.
 
@@ -9557,7 +9637,7 @@ In roc, functions are always written as a lambda, like{}
         myMEq
         eq
 
-    ── INCOMPLETE ABILITY IMPLEMENTATION in /code/proj/Main.roc ────────────────────
+    ── INCOMPLETE ABILITY IMPLEMENTATION [R0220] in /code/proj/Main.roc ────────────
 
     This type does not fully implement the `MEq` ability:
 
@@ -9584,7 +9664,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r"
-    ── OPTIONAL ABILITY IMPLEMENTATION in /code/proj/Main.roc ──────────────────────
+    ── OPTIONAL ABILITY IMPLEMENTATION [R0239] in /code/proj/Main.roc ──────────────
 
     Ability implementations cannot be optional:
 
@@ -9595,7 +9675,7 @@ In roc, functions are always written as a lambda, like{}
 
 
 
-    ── INCOMPLETE ABILITY IMPLEMENTATION in /code/proj/Main.roc ────────────────────
+    ── INCOMPLETE ABILITY IMPLEMENTATION [R0220] in /code/proj/Main.roc ────────────
 
     This type does not fully implement the `MEq` ability:
 
@@ -9622,7 +9702,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r"
-    ── OPTIONAL ABILITY IMPLEMENTATION in /code/proj/Main.roc ──────────────────────
+    ── OPTIONAL ABILITY IMPLEMENTATION [R0239] in /code/proj/Main.roc ──────────────
 
     Ability implementations cannot be optional:
 
@@ -9635,7 +9715,7 @@ In roc, functions are always written as a lambda, like{}
     record of implementations. For example,    implements [Encoding] will
     attempt to derive `Encoding`
 
-    ── INCOMPLETE ABILITY IMPLEMENTATION in /code/proj/Main.roc ────────────────────
+    ── INCOMPLETE ABILITY IMPLEMENTATION [R0220] in /code/proj/Main.roc ────────────
 
     This type does not fully implement the `Encoding` ability:
 
@@ -9660,7 +9740,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r"
-    ── QUALIFIED ABILITY IMPLEMENTATION in /code/proj/Main.roc ─────────────────────
+    ── QUALIFIED ABILITY IMPLEMENTATION [R0242] in /code/proj/Main.roc ─────────────
 
     This ability implementation is qualified:
 
@@ -9670,7 +9750,7 @@ In roc, functions are always written as a lambda, like{}
     Custom implementations must be defined in the local scope, and
     unqualified.
 
-    ── INCOMPLETE ABILITY IMPLEMENTATION in /code/proj/Main.roc ────────────────────
+    ── INCOMPLETE ABILITY IMPLEMENTATION [R0220] in /code/proj/Main.roc ────────────
 
     This type does not fully implement the `MEq` ability:
 
@@ -9695,7 +9775,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r"
-    ── ABILITY IMPLEMENTATION NOT IDENTIFIER in /code/proj/Main.roc ────────────────
+    ── ABILITY IMPLEMENTATION NOT IDENTIFIER [R0201] in /code/proj/Main.roc ────────
 
     This ability implementation is not an identifier:
 
@@ -9707,7 +9787,7 @@ In roc, functions are always written as a lambda, like{}
 
     Tip: consider defining this expression as a variable.
 
-    ── INCOMPLETE ABILITY IMPLEMENTATION in /code/proj/Main.roc ────────────────────
+    ── INCOMPLETE ABILITY IMPLEMENTATION [R0220] in /code/proj/Main.roc ────────────
 
     This type does not fully implement the `MEq` ability:
 
@@ -9734,7 +9814,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r"
-    ── DUPLICATE IMPLEMENTATION in /code/proj/Main.roc ─────────────────────────────
+    ── DUPLICATE IMPLEMENTATION [R0213] in /code/proj/Main.roc ─────────────────────
 
     This ability member implementation is duplicate:
 
@@ -9762,7 +9842,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r"
-    ── NOT AN ABILITY in /code/proj/Main.roc ───────────────────────────────────────
+    ── NOT AN ABILITY [R0230] in /code/proj/Main.roc ───────────────────────────────
 
     This identifier is not an ability in scope:
 
@@ -9785,7 +9865,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r"
-    ── ILLEGAL DERIVE in /code/proj/Main.roc ───────────────────────────────────────
+    ── ILLEGAL DERIVE [R0216] in /code/proj/Main.roc ───────────────────────────────
 
     This ability cannot be derived:
 
@@ -9808,7 +9888,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r"
-    ── INCOMPLETE ABILITY IMPLEMENTATION in /code/proj/Main.roc ────────────────────
+    ── INCOMPLETE ABILITY IMPLEMENTATION [R0220] in /code/proj/Main.roc ────────────
 
     I can't derive an implementation of the `Encoding` ability for `A`:
 
@@ -9833,7 +9913,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r"
-    ── INCOMPLETE ABILITY IMPLEMENTATION in /code/proj/Main.roc ────────────────────
+    ── INCOMPLETE ABILITY IMPLEMENTATION [R0220] in /code/proj/Main.roc ────────────
 
     I can't derive an implementation of the `Encoding` ability for `A`:
 
@@ -9885,7 +9965,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r"
-    ── DUPLICATE NAME in /code/proj/Main.roc ───────────────────────────────────────
+    ── DUPLICATE NAME [R0214] in /code/proj/Main.roc ───────────────────────────────
 
     The `main` name is first defined here:
 
@@ -9900,7 +9980,7 @@ In roc, functions are always written as a lambda, like{}
     Since these variables have the same name, it's easy to use the wrong
     one by accident. Give one of them a new name.
 
-    ── UNNECESSARY DEFINITION in /code/proj/Main.roc ───────────────────────────────
+    ── UNNECESSARY DEFINITION [R0250] in /code/proj/Main.roc ───────────────────────
 
     This destructure assignment doesn't introduce any new variables:
 
@@ -9934,7 +10014,7 @@ In roc, functions are always written as a lambda, like{}
             "
         ),
         @r"
-        ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+        ── TYPE MISMATCH [R0412] in /code/proj/Main.roc ────────────────────────────────
 
         Something is off with the body of the `withOpen` definition:
 
@@ -9969,7 +10049,7 @@ In roc, functions are always written as a lambda, like{}
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0408] in /code/proj/Main.roc ────────────────────────────────
 
     This expression is used in an unexpected way:
 
@@ -10016,7 +10096,7 @@ In roc, functions are always written as a lambda, like{}
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0408] in /code/proj/Main.roc ────────────────────────────────
 
     This 1st argument to `foo` has an unexpected type:
 
@@ -10061,7 +10141,7 @@ In roc, functions are always written as a lambda, like{}
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0408] in /code/proj/Main.roc ────────────────────────────────
 
     This 2nd argument to == has an unexpected type:
 
@@ -10086,7 +10166,7 @@ In roc, functions are always written as a lambda, like{}
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0408] in /code/proj/Main.roc ────────────────────────────────
 
     This 2nd argument to == has an unexpected type:
 
@@ -10123,7 +10203,7 @@ In roc, functions are always written as a lambda, like{}
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0412] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the 2nd branch of this `when` expression:
 
@@ -10160,7 +10240,7 @@ In roc, functions are always written as a lambda, like{}
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0408] in /code/proj/Main.roc ────────────────────────────────
 
     This 2nd argument to `map` has an unexpected type:
 
@@ -10186,7 +10266,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r#"
-        ── NAME NOT BOUND IN ALL PATTERNS in /code/proj/Main.roc ───────────────────────
+        ── NAME NOT BOUND IN ALL PATTERNS [R0227] in /code/proj/Main.roc ───────────────
 
         `x` is not bound in all patterns of this `when` branch
 
@@ -10197,7 +10277,7 @@ In roc, functions are always written as a lambda, like{}
         of the branch. Otherwise, the program would crash when it tries to use
         an identifier that wasn't bound!
 
-        ── NAME NOT BOUND IN ALL PATTERNS in /code/proj/Main.roc ───────────────────────
+        ── NAME NOT BOUND IN ALL PATTERNS [R0227] in /code/proj/Main.roc ───────────────
 
         `y` is not bound in all patterns of this `when` branch
 
@@ -10208,7 +10288,7 @@ In roc, functions are always written as a lambda, like{}
         of the branch. Otherwise, the program would crash when it tries to use
         an identifier that wasn't bound!
 
-        ── UNUSED DEFINITION in /code/proj/Main.roc ────────────────────────────────────
+        ── UNUSED DEFINITION [R0255] in /code/proj/Main.roc ────────────────────────────
 
         `y` is not used in this `when` branch.
 
@@ -10229,7 +10309,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r###"
-        ── NAME NOT BOUND IN ALL PATTERNS in /code/proj/Main.roc ───────────────────────
+        ── NAME NOT BOUND IN ALL PATTERNS [R0227] in /code/proj/Main.roc ───────────────
 
         `x` is not bound in all patterns of this `when` branch
 
@@ -10252,7 +10332,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r#"
-    ── UNSAFE PATTERN in /code/proj/Main.roc ───────────────────────────────────────
+    ── UNSAFE PATTERN [R0410] in /code/proj/Main.roc ───────────────────────────────
 
     This `when` does not cover all the possibilities:
 
@@ -10278,7 +10358,7 @@ In roc, functions are always written as a lambda, like{}
         |golden| pretty_assertions::assert_eq!(
             golden,
             indoc!(
-                r"── UNRECOGNIZED NAME in /code/proj/Main.roc ────────────────────────────────────
+                r"── UNRECOGNIZED NAME [R0253] in /code/proj/Main.roc ────────────────────────────
 
                 Nothing is named `foo` in this scope.
 
@@ -10315,7 +10395,7 @@ In roc, functions are always written as a lambda, like{}
         |golden| pretty_assertions::assert_eq!(
             golden,
             indoc!(
-                r"── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+                r"── SYNTAX PROBLEM [R0269] in /code/proj/Main.roc ───────────────────────────────
 
                 An underscore is being used as a variable here:
 
@@ -10341,7 +10421,7 @@ In roc, functions are always written as a lambda, like{}
         |golden| pretty_assertions::assert_eq!(
             golden,
             indoc!(
-                r"── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+                r"── SYNTAX PROBLEM [R0269] in /code/proj/Main.roc ───────────────────────────────
 
                 This variable's name starts with an underscore:
 
@@ -10374,7 +10454,7 @@ In roc, functions are always written as a lambda, like{}
             golden,
             indoc!(
                 r"
-                ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+                ── SYNTAX PROBLEM [R0269] in /code/proj/Main.roc ───────────────────────────────
 
                 This variable's name starts with an underscore:
 
@@ -10401,7 +10481,7 @@ In roc, functions are always written as a lambda, like{}
             golden,
             indoc!(
                 r"
-                ── SYNTAX PROBLEM in /code/proj/Main.roc ───────────────────────────────────────
+                ── SYNTAX PROBLEM [R0269] in /code/proj/Main.roc ───────────────────────────────
 
                 Underscores are not allowed in identifier names:
 
@@ -10428,7 +10508,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r#"
-    ── BAD RECORD BUILDER in tmp/optional_field_in_record_builder/Test.roc ─────────
+    ── BAD RECORD BUILDER [R0004] in tmp/optional_field_in_record_builder/Test.roc ─
 
     I am partway through parsing a record builder, and I found an optional
     field:
@@ -10457,7 +10537,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r#"
-    ── BAD RECORD UPDATE in tmp/record_update_builder/Test.roc ─────────────────────
+    ── BAD RECORD UPDATE [R0005] in tmp/record_update_builder/Test.roc ─────────────
 
     I am partway through parsing a record update, and I found a record
     builder field:
@@ -10483,7 +10563,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r#"
-    ── MULTIPLE RECORD BUILDERS in /code/proj/Main.roc ─────────────────────────────
+    ── MULTIPLE RECORD BUILDERS [R0226] in /code/proj/Main.roc ─────────────────────
 
     This function is applied to multiple record builders:
 
@@ -10506,7 +10586,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r#"
-    ── UNAPPLIED RECORD BUILDER in /code/proj/Main.roc ─────────────────────────────
+    ── UNAPPLIED RECORD BUILDER [R0247] in /code/proj/Main.roc ─────────────────────
 
     This record builder was not applied to a function:
 
@@ -10531,7 +10611,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r#"
-    ── TOO MANY ARGS in /code/proj/Main.roc ────────────────────────────────────────
+    ── TOO MANY ARGS [R0407] in /code/proj/Main.roc ────────────────────────────────
 
     This value is not a function, but it was given 1 argument:
 
@@ -10563,7 +10643,7 @@ In roc, functions are always written as a lambda, like{}
     //         "#
     //     ),
     //     @r#"
-    // ── TOO MANY ARGS in /code/proj/Main.roc ────────────────────────────────────────
+    // ── TOO MANY ARGS [R0407] in /code/proj/Main.roc ────────────────────────────────
 
     // This value is an opaque type, so it cannot be called with an argument:
 
@@ -10590,7 +10670,7 @@ In roc, functions are always written as a lambda, like{}
             "
         ),
         @r"
-    ── UNNECESSARY DEFINITION in /code/proj/Main.roc ───────────────────────────────
+    ── UNNECESSARY DEFINITION [R0250] in /code/proj/Main.roc ───────────────────────
 
     This destructure assignment doesn't introduce any new variables:
 
@@ -10602,7 +10682,7 @@ In roc, functions are always written as a lambda, like{}
     functional, assignments that don't introduce variables cannot affect a
     program's behavior!
 
-    ── UNNECESSARY DEFINITION in /code/proj/Main.roc ───────────────────────────────
+    ── UNNECESSARY DEFINITION [R0250] in /code/proj/Main.roc ───────────────────────
 
     This destructure assignment doesn't introduce any new variables:
 
@@ -10614,7 +10694,7 @@ In roc, functions are always written as a lambda, like{}
     functional, assignments that don't introduce variables cannot affect a
     program's behavior!
 
-    ── UNNECESSARY DEFINITION in /code/proj/Main.roc ───────────────────────────────
+    ── UNNECESSARY DEFINITION [R0250] in /code/proj/Main.roc ───────────────────────
 
     This destructure assignment doesn't introduce any new variables:
 
@@ -10626,7 +10706,7 @@ In roc, functions are always written as a lambda, like{}
     functional, assignments that don't introduce variables cannot affect a
     program's behavior!
 
-    ── UNNECESSARY DEFINITION in /code/proj/Main.roc ───────────────────────────────
+    ── UNNECESSARY DEFINITION [R0250] in /code/proj/Main.roc ───────────────────────
 
     This destructure assignment doesn't introduce any new variables:
 
@@ -10656,7 +10736,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r"
-    ── UNNECESSARY DEFINITION in /code/proj/Main.roc ───────────────────────────────
+    ── UNNECESSARY DEFINITION [R0250] in /code/proj/Main.roc ───────────────────────
 
     This destructure assignment doesn't introduce any new variables:
 
@@ -10668,7 +10748,7 @@ In roc, functions are always written as a lambda, like{}
     functional, assignments that don't introduce variables cannot affect a
     program's behavior!
 
-    ── UNNECESSARY DEFINITION in /code/proj/Main.roc ───────────────────────────────
+    ── UNNECESSARY DEFINITION [R0250] in /code/proj/Main.roc ───────────────────────
 
     This destructure assignment doesn't introduce any new variables:
 
@@ -10680,7 +10760,7 @@ In roc, functions are always written as a lambda, like{}
     functional, assignments that don't introduce variables cannot affect a
     program's behavior!
 
-    ── UNNECESSARY DEFINITION in /code/proj/Main.roc ───────────────────────────────
+    ── UNNECESSARY DEFINITION [R0250] in /code/proj/Main.roc ───────────────────────
 
     This destructure assignment doesn't introduce any new variables:
 
@@ -10692,7 +10772,7 @@ In roc, functions are always written as a lambda, like{}
     functional, assignments that don't introduce variables cannot affect a
     program's behavior!
 
-    ── UNNECESSARY DEFINITION in /code/proj/Main.roc ───────────────────────────────
+    ── UNNECESSARY DEFINITION [R0250] in /code/proj/Main.roc ───────────────────────
 
     This destructure assignment doesn't introduce any new variables:
 
@@ -10720,7 +10800,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r"
-    ── UNUSED DEFINITION in /code/proj/Main.roc ────────────────────────────────────
+    ── UNUSED DEFINITION [R0255] in /code/proj/Main.roc ────────────────────────────
 
     `hash` is not used anywhere in your code.
 
@@ -10747,7 +10827,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r"
-    ── WRONG SPECIALIZATION TYPE in /code/proj/Main.roc ────────────────────────────
+    ── WRONG SPECIALIZATION TYPE [R0411] in /code/proj/Main.roc ────────────────────
 
     This specialization of `hash` is not for the expected type:
 
@@ -10770,7 +10850,7 @@ In roc, functions are always written as a lambda, like{}
                 "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0412] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the body of the `x` definition:
 
@@ -10800,7 +10880,7 @@ In roc, functions are always written as a lambda, like{}
             "
         ),
         @r"
-    ── CYCLIC ALIAS in /code/proj/Main.roc ─────────────────────────────────────────
+    ── CYCLIC ALIAS [R0401] in /code/proj/Main.roc ─────────────────────────────────
 
     The `Recursive` opaque is self-recursive in an invalid way:
 
@@ -10822,7 +10902,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r"
-    ── INCOMPLETE ABILITY IMPLEMENTATION in /code/proj/Main.roc ────────────────────
+    ── INCOMPLETE ABILITY IMPLEMENTATION [R0220] in /code/proj/Main.roc ────────────
 
     I can't derive an implementation of the `Decoding` ability for `A`:
 
@@ -10847,7 +10927,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r"
-    ── INCOMPLETE ABILITY IMPLEMENTATION in /code/proj/Main.roc ────────────────────
+    ── INCOMPLETE ABILITY IMPLEMENTATION [R0220] in /code/proj/Main.roc ────────────
 
     I can't derive an implementation of the `Decoding` ability for `A`:
 
@@ -10902,7 +10982,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0414] in /code/proj/Main.roc ────────────────────────────────
 
     This expression has a type that does not implement the abilities it's expected to:
 
@@ -10933,7 +11013,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0414] in /code/proj/Main.roc ────────────────────────────────
 
     This expression has a type that does not implement the abilities it's expected to:
 
@@ -10963,7 +11043,7 @@ In roc, functions are always written as a lambda, like{}
             "
         ),
     @r#"
-    ── UNUSED ARGUMENT in /code/proj/Main.roc ──────────────────────────────────────
+    ── UNUSED ARGUMENT [R0254] in /code/proj/Main.roc ──────────────────────────────
 
     This function doesn't use `x`.
 
@@ -10995,7 +11075,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0408] in /code/proj/Main.roc ────────────────────────────────
 
     The branches of this `when` expression don't match the condition:
 
@@ -11028,7 +11108,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0412] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the body of the `f` definition:
 
@@ -11065,7 +11145,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0412] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the `then` branch of this `if` expression:
 
@@ -11097,7 +11177,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
     @r#"
-    ── UNUSED DEFINITION in /code/proj/Main.roc ────────────────────────────────────
+    ── UNUSED DEFINITION [R0255] in /code/proj/Main.roc ────────────────────────────
 
     `foo` is not used in this `when` branch.
 
@@ -11123,7 +11203,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
     @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0414] in /code/proj/Main.roc ────────────────────────────────
 
     This expression has a type that does not implement the abilities it's expected to:
 
@@ -11152,7 +11232,7 @@ In roc, functions are always written as a lambda, like{}
              "#
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0414] in /code/proj/Main.roc ────────────────────────────────
 
     This expression has a type that does not implement the abilities it's expected to:
 
@@ -11218,7 +11298,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
     @r#"
-    ── UNSAFE PATTERN in /code/proj/Main.roc ───────────────────────────────────────
+    ── UNSAFE PATTERN [R0410] in /code/proj/Main.roc ───────────────────────────────
 
     This `when` does not cover all the possibilities:
 
@@ -11246,7 +11326,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
     @r#"
-    ── UNSAFE PATTERN in /code/proj/Main.roc ───────────────────────────────────────
+    ── UNSAFE PATTERN [R0410] in /code/proj/Main.roc ───────────────────────────────
 
     This `when` does not cover all the possibilities:
 
@@ -11273,7 +11353,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
     @r#"
-    ── UNMATCHABLE PATTERN in /code/proj/Main.roc ──────────────────────────────────
+    ── UNMATCHABLE PATTERN [R0409] in /code/proj/Main.roc ──────────────────────────
 
     The 2nd pattern will never be matched:
 
@@ -11300,7 +11380,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
     @r#"
-    ── UNMATCHABLE PATTERN in /code/proj/Main.roc ──────────────────────────────────
+    ── UNMATCHABLE PATTERN [R0409] in /code/proj/Main.roc ──────────────────────────
 
     The 2nd pattern will never be matched:
 
@@ -11312,7 +11392,7 @@ In roc, functions are always written as a lambda, like{}
     It's impossible to create a value of this shape, so this pattern can
     be safely removed!
 
-    ── UNMATCHABLE PATTERN in /code/proj/Main.roc ──────────────────────────────────
+    ── UNMATCHABLE PATTERN [R0409] in /code/proj/Main.roc ──────────────────────────
 
     The 3rd pattern will never be matched:
 
@@ -11337,7 +11417,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
     @r"
-    ── DUPLICATE NAME in /code/proj/Main.roc ───────────────────────────────────────
+    ── DUPLICATE NAME [R0214] in /code/proj/Main.roc ───────────────────────────────
 
     This opaque type has the same name as a builtin:
 
@@ -11359,7 +11439,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
     @r#"
-    ── UNUSED IMPORT in /code/proj/Main.roc ────────────────────────────────────────
+    ── UNUSED IMPORT [R0256] in /code/proj/Main.roc ────────────────────────────────
 
     `List.concat` is not used in this module.
 
@@ -11411,7 +11491,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
     @r"
-    ── CIRCULAR DEFINITION in /code/proj/Main.roc ──────────────────────────────────
+    ── CIRCULAR DEFINITION [R0207] in /code/proj/Main.roc ──────────────────────────
 
     `main` is defined directly in terms of itself:
 
@@ -11433,7 +11513,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
     @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0408] in /code/proj/Main.roc ────────────────────────────────
 
     This `if` condition needs to be a Bool:
 
@@ -11459,7 +11539,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
     @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0408] in /code/proj/Main.roc ────────────────────────────────
 
     This `if` condition needs to be a Bool:
 
@@ -11487,7 +11567,7 @@ In roc, functions are always written as a lambda, like{}
              "#
         ),
         @r"
-    ── INCOMPLETE ABILITY IMPLEMENTATION in /code/proj/Main.roc ────────────────────
+    ── INCOMPLETE ABILITY IMPLEMENTATION [R0220] in /code/proj/Main.roc ────────────
 
     I can't derive an implementation of the `Hash` ability for `A`:
 
@@ -11512,7 +11592,7 @@ In roc, functions are always written as a lambda, like{}
              "#
         ),
         @r"
-    ── INCOMPLETE ABILITY IMPLEMENTATION in /code/proj/Main.roc ────────────────────
+    ── INCOMPLETE ABILITY IMPLEMENTATION [R0220] in /code/proj/Main.roc ────────────
 
     I can't derive an implementation of the `Hash` ability for `A`:
 
@@ -11595,7 +11675,7 @@ In roc, functions are always written as a lambda, like{}
              "#
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0414] in /code/proj/Main.roc ────────────────────────────────
 
     This expression has a type that does not implement the abilities it's expected to:
 
@@ -11622,7 +11702,7 @@ In roc, functions are always written as a lambda, like{}
              "#
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0414] in /code/proj/Main.roc ────────────────────────────────
 
     This expression has a type that does not implement the abilities it's expected to:
 
@@ -11668,7 +11748,7 @@ In roc, functions are always written as a lambda, like{}
              "#
         ),
         @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0414] in /code/proj/Main.roc ────────────────────────────────
 
     This expression has a type that does not implement the abilities it's expected to:
 
@@ -11704,7 +11784,7 @@ In roc, functions are always written as a lambda, like{}
             "
         ),
     @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0408] in /code/proj/Main.roc ────────────────────────────────
 
     This 2nd argument to `shiftRightZfBy` has an unexpected type:
 
@@ -11719,7 +11799,7 @@ In roc, functions are always written as a lambda, like{}
 
         U8
 
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0408] in /code/proj/Main.roc ────────────────────────────────
 
     This 2nd argument to `shiftRightBy` has an unexpected type:
 
@@ -11734,7 +11814,7 @@ In roc, functions are always written as a lambda, like{}
 
         U8
 
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0408] in /code/proj/Main.roc ────────────────────────────────
 
     This 2nd argument to `shiftLeftBy` has an unexpected type:
 
@@ -11762,7 +11842,7 @@ In roc, functions are always written as a lambda, like{}
             "
         ),
     @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0408] in /code/proj/Main.roc ────────────────────────────────
 
     This 2nd argument to `contains` has an unexpected type:
 
@@ -11789,7 +11869,7 @@ In roc, functions are always written as a lambda, like{}
              "#
         ),
         @r"
-    ── INCOMPLETE ABILITY IMPLEMENTATION in /code/proj/Main.roc ────────────────────
+    ── INCOMPLETE ABILITY IMPLEMENTATION [R0220] in /code/proj/Main.roc ────────────
 
     I can't derive an implementation of the `Eq` ability for `A`:
 
@@ -11814,7 +11894,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
     @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0408] in /code/proj/Main.roc ────────────────────────────────
 
     The branches of this `when` expression don't match the condition:
 
@@ -11844,7 +11924,7 @@ In roc, functions are always written as a lambda, like{}
              "#
         ),
         @r"
-    ── INCOMPLETE ABILITY IMPLEMENTATION in /code/proj/Main.roc ────────────────────
+    ── INCOMPLETE ABILITY IMPLEMENTATION [R0220] in /code/proj/Main.roc ────────────
 
     I can't derive an implementation of the `Eq` ability for `A`:
 
@@ -11870,7 +11950,7 @@ In roc, functions are always written as a lambda, like{}
              "#
         ),
         @r"
-    ── INCOMPLETE ABILITY IMPLEMENTATION in /code/proj/Main.roc ────────────────────
+    ── INCOMPLETE ABILITY IMPLEMENTATION [R0220] in /code/proj/Main.roc ────────────
 
     I can't derive an implementation of the `Eq` ability for `A`:
 
@@ -11898,7 +11978,7 @@ In roc, functions are always written as a lambda, like{}
              "#
         ),
         @r"
-    ── INCOMPLETE ABILITY IMPLEMENTATION in /code/proj/Main.roc ────────────────────
+    ── INCOMPLETE ABILITY IMPLEMENTATION [R0220] in /code/proj/Main.roc ────────────
 
     I can't derive an implementation of the `Eq` ability for `A`:
 
@@ -11981,7 +12061,7 @@ In roc, functions are always written as a lambda, like{}
              "#
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0414] in /code/proj/Main.roc ────────────────────────────────
 
     This expression has a type that does not implement the abilities it's expected to:
 
@@ -12008,7 +12088,7 @@ In roc, functions are always written as a lambda, like{}
              "#
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0414] in /code/proj/Main.roc ────────────────────────────────
 
     This expression has a type that does not implement the abilities it's expected to:
 
@@ -12037,7 +12117,7 @@ In roc, functions are always written as a lambda, like{}
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0414] in /code/proj/Main.roc ────────────────────────────────
 
     This expression has a type that does not implement the abilities it's expected to:
 
@@ -12060,7 +12140,7 @@ In roc, functions are always written as a lambda, like{}
             "
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0414] in /code/proj/Main.roc ────────────────────────────────
 
     This expression has a type that does not implement the abilities it's expected to:
 
@@ -12100,7 +12180,7 @@ In roc, functions are always written as a lambda, like{}
              "#
         ),
         @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0414] in /code/proj/Main.roc ────────────────────────────────
 
     This expression has a type that does not implement the abilities it's expected to:
 
@@ -12138,7 +12218,7 @@ In roc, functions are always written as a lambda, like{}
             "
         ),
         @r"
-    ── NOT EXPOSED in /code/proj/Main.roc ──────────────────────────────────────────
+    ── NOT EXPOSED [R0232] in /code/proj/Main.roc ──────────────────────────────────
 
     The Bool module does not expose `structuralEq`:
 
@@ -12152,7 +12232,7 @@ In roc, functions are always written as a lambda, like{}
         Bool.false
         Bool.isEq
 
-    ── NOT EXPOSED in /code/proj/Main.roc ──────────────────────────────────────────
+    ── NOT EXPOSED [R0232] in /code/proj/Main.roc ──────────────────────────────────
 
     The Bool module does not expose `structuralNotEq`:
 
@@ -12180,7 +12260,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
     @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0414] in /code/proj/Main.roc ────────────────────────────────
 
     This expression has a type that does not implement the abilities it's expected to:
 
@@ -12205,7 +12285,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
     @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0413] in /code/proj/Main.roc ────────────────────────────────
 
     The 1st argument to `foo` is weird:
 
@@ -12234,7 +12314,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
     @r"
-    ── INCOMPLETE ABILITY IMPLEMENTATION in /code/proj/Main.roc ────────────────────
+    ── INCOMPLETE ABILITY IMPLEMENTATION [R0220] in /code/proj/Main.roc ────────────
 
     I can't derive an implementation of the `Hash` ability for `F`:
 
@@ -12245,7 +12325,7 @@ In roc, functions are always written as a lambda, like{}
 
     Tip: You can define a custom implementation of `Hash` for `F`.
 
-    ── INCOMPLETE ABILITY IMPLEMENTATION in /code/proj/Main.roc ────────────────────
+    ── INCOMPLETE ABILITY IMPLEMENTATION [R0220] in /code/proj/Main.roc ────────────
 
     I can't derive an implementation of the `Eq` ability for `F`:
 
@@ -12256,7 +12336,7 @@ In roc, functions are always written as a lambda, like{}
 
     Tip: You can define a custom implementation of `Eq` for `F`.
 
-    ── INCOMPLETE ABILITY IMPLEMENTATION in /code/proj/Main.roc ────────────────────
+    ── INCOMPLETE ABILITY IMPLEMENTATION [R0220] in /code/proj/Main.roc ────────────
 
     I can't derive an implementation of the `Encoding` ability for `F`:
 
@@ -12279,7 +12359,7 @@ In roc, functions are always written as a lambda, like{}
             "
         ),
     @r"
-    ── DUPLICATE BOUND ABILITY in /code/proj/Main.roc ──────────────────────────────
+    ── DUPLICATE BOUND ABILITY [R0211] in /code/proj/Main.roc ──────────────────────
 
     I already saw that this type variable is bound to the `Hash` ability
     once before:
@@ -12305,7 +12385,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
     @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0408] in /code/proj/Main.roc ────────────────────────────────
 
     This 1st argument to `g` has an unexpected type:
 
@@ -12342,7 +12422,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
     @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0408] in /code/proj/Main.roc ────────────────────────────────
 
     This 1st argument to `g` has an unexpected type:
 
@@ -12380,7 +12460,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
     @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0408] in /code/proj/Main.roc ────────────────────────────────
 
     This 1st argument to `g` has an unexpected type:
 
@@ -12413,7 +12493,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
     @r#"
-    ── UNFINISHED LIST PATTERN in tmp/list_pattern_not_terminated/Test.roc ─────────
+    ── UNFINISHED LIST PATTERN [R0054] in tmp/list_pattern_not_terminated/Test.roc ─
 
     I am partway through parsing a list pattern, but I got stuck here:
 
@@ -12434,7 +12514,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
     @r#"
-    ── INCORRECT REST PATTERN in tmp/list_pattern_weird_rest_pattern/Test.roc ──────
+    ── INCORRECT REST PATTERN [R0019] in ...st_pattern_weird_rest_pattern/Test.roc ─
 
     It looks like you may trying to write a list rest pattern, but it's
     not the form I expect:
@@ -12456,7 +12536,7 @@ In roc, functions are always written as a lambda, like{}
             "
         ),
     @r"
-    ── UNNECESSARY WILDCARD in /code/proj/Main.roc ─────────────────────────────────
+    ── UNNECESSARY WILDCARD [R0252] in /code/proj/Main.roc ─────────────────────────
 
     This type annotation has a wildcard type variable (`*`) that isn't
     needed.
@@ -12483,7 +12563,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
     @r#"
-    ── MULTIPLE LIST REST PATTERNS in /code/proj/Main.roc ──────────────────────────
+    ── MULTIPLE LIST REST PATTERNS [R0225] in /code/proj/Main.roc ──────────────────
 
     This list pattern match has multiple rest patterns:
 
@@ -12493,7 +12573,7 @@ In roc, functions are always written as a lambda, like{}
     I only support compiling list patterns with one .. pattern! Can you
     remove this additional one?
 
-    ── UNSAFE PATTERN in /code/proj/Main.roc ───────────────────────────────────────
+    ── UNSAFE PATTERN [R0410] in /code/proj/Main.roc ───────────────────────────────
 
     This `when` does not cover all the possibilities:
 
@@ -12517,7 +12597,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
     @r#"
-    ── MULTIPLE LIST REST PATTERNS in /code/proj/Main.roc ──────────────────────────
+    ── MULTIPLE LIST REST PATTERNS [R0225] in /code/proj/Main.roc ──────────────────
 
     This list pattern match has multiple rest patterns:
 
@@ -12527,7 +12607,7 @@ In roc, functions are always written as a lambda, like{}
     I only support compiling list patterns with one .. pattern! Can you
     remove this additional one?
 
-    ── UNSAFE PATTERN in /code/proj/Main.roc ───────────────────────────────────────
+    ── UNSAFE PATTERN [R0410] in /code/proj/Main.roc ───────────────────────────────
 
     This `when` does not cover all the possibilities:
 
@@ -12551,7 +12631,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
     @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0413] in /code/proj/Main.roc ────────────────────────────────
 
     This list element doesn't match the types of other elements in the
     pattern:
@@ -12578,7 +12658,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
     @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0408] in /code/proj/Main.roc ────────────────────────────────
 
     The branches of this `when` expression don't match the condition:
 
@@ -12611,7 +12691,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
     @r#"
-    ── UNSAFE PATTERN in /code/proj/Main.roc ───────────────────────────────────────
+    ── UNSAFE PATTERN [R0410] in /code/proj/Main.roc ───────────────────────────────
 
     This `when` does not cover all the possibilities:
 
@@ -12651,7 +12731,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
     @r#"
-    ── UNSAFE PATTERN in /code/proj/Main.roc ───────────────────────────────────────
+    ── UNSAFE PATTERN [R0410] in /code/proj/Main.roc ───────────────────────────────
 
     This `when` does not cover all the possibilities:
 
@@ -12695,7 +12775,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
     @r#"
-    ── REDUNDANT PATTERN in /code/proj/Main.roc ────────────────────────────────────
+    ── REDUNDANT PATTERN [R0405] in /code/proj/Main.roc ────────────────────────────
 
     The 2nd pattern is redundant:
 
@@ -12761,7 +12841,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
     @r#"
-    ── UNSAFE PATTERN in /code/proj/Main.roc ───────────────────────────────────────
+    ── UNSAFE PATTERN [R0410] in /code/proj/Main.roc ───────────────────────────────
 
     This `when` does not cover all the possibilities:
 
@@ -12788,7 +12868,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
     @r#"
-    ── UNSAFE PATTERN in /code/proj/Main.roc ───────────────────────────────────────
+    ── UNSAFE PATTERN [R0410] in /code/proj/Main.roc ───────────────────────────────
 
     This `when` does not cover all the possibilities:
 
@@ -12831,7 +12911,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
     @r#"
-    ── UNSAFE PATTERN in /code/proj/Main.roc ───────────────────────────────────────
+    ── UNSAFE PATTERN [R0410] in /code/proj/Main.roc ───────────────────────────────
 
     This `when` does not cover all the possibilities:
 
@@ -12858,7 +12938,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
     @r#"
-    ── UNSAFE PATTERN in /code/proj/Main.roc ───────────────────────────────────────
+    ── UNSAFE PATTERN [R0410] in /code/proj/Main.roc ───────────────────────────────
 
     This `when` does not cover all the possibilities:
 
@@ -12907,7 +12987,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
     @r#"
-    ── UNSAFE PATTERN in /code/proj/Main.roc ───────────────────────────────────────
+    ── UNSAFE PATTERN [R0410] in /code/proj/Main.roc ───────────────────────────────
 
     This `when` does not cover all the possibilities:
 
@@ -12938,7 +13018,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
     @r#"
-    ── UNSAFE PATTERN in /code/proj/Main.roc ───────────────────────────────────────
+    ── UNSAFE PATTERN [R0410] in /code/proj/Main.roc ───────────────────────────────
 
     This `when` does not cover all the possibilities:
 
@@ -12970,7 +13050,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
     @r#"
-    ── UNSAFE PATTERN in /code/proj/Main.roc ───────────────────────────────────────
+    ── UNSAFE PATTERN [R0410] in /code/proj/Main.roc ───────────────────────────────
 
     This `when` does not cover all the possibilities:
 
@@ -13019,7 +13099,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
     @r#"
-    ── UNSAFE PATTERN in /code/proj/Main.roc ───────────────────────────────────────
+    ── UNSAFE PATTERN [R0410] in /code/proj/Main.roc ───────────────────────────────
 
     This `when` does not cover all the possibilities:
 
@@ -13053,7 +13133,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
     @r#"
-    ── REDUNDANT PATTERN in /code/proj/Main.roc ────────────────────────────────────
+    ── REDUNDANT PATTERN [R0405] in /code/proj/Main.roc ────────────────────────────
 
     The 3rd pattern is redundant:
 
@@ -13081,7 +13161,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
     @r#"
-    ── REDUNDANT PATTERN in /code/proj/Main.roc ────────────────────────────────────
+    ── REDUNDANT PATTERN [R0405] in /code/proj/Main.roc ────────────────────────────
 
     The 3rd pattern is redundant:
 
@@ -13109,7 +13189,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
     @r#"
-    ── REDUNDANT PATTERN in /code/proj/Main.roc ────────────────────────────────────
+    ── REDUNDANT PATTERN [R0405] in /code/proj/Main.roc ────────────────────────────
 
     The 3rd pattern is redundant:
 
@@ -13137,7 +13217,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
     @r#"
-    ── REDUNDANT PATTERN in /code/proj/Main.roc ────────────────────────────────────
+    ── REDUNDANT PATTERN [R0405] in /code/proj/Main.roc ────────────────────────────
 
     The 2nd pattern is redundant:
 
@@ -13176,7 +13256,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
     @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0414] in /code/proj/Main.roc ────────────────────────────────
 
     This expression has a type that does not implement the abilities it's expected to:
 
@@ -13207,7 +13287,7 @@ In roc, functions are always written as a lambda, like{}
             "
         ),
     @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0408] in /code/proj/Main.roc ────────────────────────────────
 
     This value passed to `crash` is not a string:
 
@@ -13232,7 +13312,7 @@ In roc, functions are always written as a lambda, like{}
             "
         ),
     @r"
-    ── UNAPPLIED CRASH in /code/proj/Main.roc ──────────────────────────────────────
+    ── UNAPPLIED CRASH [R0246] in /code/proj/Main.roc ──────────────────────────────
 
     This `crash` doesn't have a message given to it:
 
@@ -13253,7 +13333,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
     @r#"
-    ── OVERAPPLIED CRASH in /code/proj/Main.roc ────────────────────────────────────
+    ── OVERAPPLIED CRASH [R0240] in /code/proj/Main.roc ────────────────────────────
 
     This `crash` has too many values given to it:
 
@@ -13289,7 +13369,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
     @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0414] in /code/proj/Main.roc ────────────────────────────────
 
     This expression has a type that does not implement the abilities it's expected to:
 
@@ -13331,7 +13411,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
     @r#"
-    ── DEFINITION ONLY USED IN RECURSION in /code/proj/Main.roc ────────────────────
+    ── DEFINITION ONLY USED IN RECURSION [R0209] in /code/proj/Main.roc ────────────
 
     This definition is only used in recursion with itself:
 
@@ -13364,7 +13444,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
     @r#"
-    ── DEFINITIONs ONLY USED IN RECURSION in /code/proj/Main.roc ───────────────────
+    ── DEFINITIONs ONLY USED IN RECURSION [R0209] in /code/proj/Main.roc ───────────
 
     These 2 definitions are only used in mutual recursion with themselves:
 
@@ -13400,7 +13480,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
     @r#"
-    ── DEFINITION ONLY USED IN RECURSION in /code/proj/Main.roc ────────────────────
+    ── DEFINITION ONLY USED IN RECURSION [R0209] in /code/proj/Main.roc ────────────
 
     This definition is only used in recursion with itself:
 
@@ -13436,7 +13516,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
     @r#"
-    ── DEFINITIONs ONLY USED IN RECURSION in /code/proj/Main.roc ───────────────────
+    ── DEFINITIONs ONLY USED IN RECURSION [R0209] in /code/proj/Main.roc ───────────
 
     These 2 definitions are only used in mutual recursion with themselves:
 
@@ -13475,7 +13555,7 @@ In roc, functions are always written as a lambda, like{}
             {one, str}
         "#),
     @r#"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0408] in /code/proj/Main.roc ────────────────────────────────
 
     This 2nd argument to `concat` has an unexpected type:
 
@@ -13506,7 +13586,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
     @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0412] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the `else` branch of this `if` expression:
 
@@ -13543,7 +13623,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
     @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0412] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the `else` branch of this `if` expression:
 
@@ -13579,7 +13659,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
     @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0412] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the body of the `main` definition:
 
@@ -13617,7 +13697,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
     @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0412] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the body of the `main` definition:
 
@@ -13683,7 +13763,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0414] in /code/proj/Main.roc ────────────────────────────────
 
     This expression has a type that does not implement the abilities it's expected to:
 
@@ -13723,7 +13803,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0414] in /code/proj/Main.roc ────────────────────────────────
 
     This expression has a type that does not implement the abilities it's expected to:
 
@@ -13763,7 +13843,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r#"
-    ── UNSAFE PATTERN in /code/proj/Main.roc ───────────────────────────────────────
+    ── UNSAFE PATTERN [R0410] in /code/proj/Main.roc ───────────────────────────────
 
     This `when` does not cover all the possibilities:
 
@@ -13811,7 +13891,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r#"
-    ── TOO MANY ARGS in /code/proj/Main.roc ────────────────────────────────────────
+    ── TOO MANY ARGS [R0407] in /code/proj/Main.roc ────────────────────────────────
 
     The `parser` value is an opaque type, so it cannot be called with an
     argument:
@@ -13835,7 +13915,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0412] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the body of the `f` definition:
 
@@ -13866,7 +13946,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0412] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the body of the `f` definition:
 
@@ -13900,7 +13980,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0412] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the body of the `f` definition:
 
@@ -13934,7 +14014,7 @@ In roc, functions are always written as a lambda, like{}
             "#
         ),
         @r"
-    ── TYPE MISMATCH in /code/proj/Main.roc ────────────────────────────────────────
+    ── TYPE MISMATCH [R0412] in /code/proj/Main.roc ────────────────────────────────
 
     Something is off with the body of the `f` definition:
 
@@ -13962,7 +14042,7 @@ In roc, functions are always written as a lambda, like{}
             "
         ),
         @r"
-    ── TOO FEW ARGS in /code/proj/Main.roc ─────────────────────────────────────────
+    ── TOO FEW ARGS [R0406] in /code/proj/Main.roc ─────────────────────────────────
 
     The `sub` function expects 2 arguments, but it got only 1:
 
@@ -13982,7 +14062,7 @@ In roc, functions are always written as a lambda, like{}
             "
         ),
         @r"
-    ── TOO FEW ARGS in /code/proj/Main.roc ─────────────────────────────────────────
+    ── TOO FEW ARGS [R0406] in /code/proj/Main.roc ─────────────────────────────────
 
     The `sub` function expects 2 arguments, but it got only 1:
 
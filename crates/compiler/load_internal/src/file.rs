@@ -4,7 +4,8 @@ use crate::docs::ModuleDocumentation;
 use crate::module::{
     CheckedModule, ConstrainedModule, EntryPoint, Expectations, ExposedToHost,
     FoundSpecializationsModule, LateSpecializationsModule, LoadedModule, ModuleHeader,
-    ModuleTiming, MonomorphizedModule, ParsedModule, ToplevelExpects, TypeCheckedModule,
+    ModuleTiming, MonomorphizedModule, ParsedModule, ReusableModule, ToplevelExpects,
+    TypeCheckedModule,
 };
 use crate::module_cache::ModuleCache;
 use bumpalo::{collections::CollectIn, Bump};
@@ -46,7 +47,7 @@ use roc_mono::layout::{
 };
 use roc_mono::reset_reuse;
 use roc_mono::{drop_specialization, inc_dec};
-use roc_packaging::cache::RocCacheDir;
+use roc_packaging::cache::{is_package_url, RocCacheDir};
 use roc_parse::ast::{
     self, CommentOrNewline, Expr, ExtractSpaces, Pattern, Spaced, StrLiteral, ValueDef,
 };
@@ -80,6 +81,7 @@ use std::{env, fs};
 use {
     roc_packaging::cache::{self},
     roc_packaging::https::{PackageMetadata, Problem},
+    roc_packaging::vendor::VENDOR_DIR_NAME,
 };
 
 pub use crate::work::Phase;
@@ -140,6 +142,38 @@ impl ExecutionMode {
 
 type SharedIdentIdsByModule = Arc<Mutex<roc_module::symbol::IdentIdsByModule>>;
 
+/// Whether the types of a module from an earlier load still hold: the module must be the same,
+/// and so must everything it imports, so those have to have been reused too.
+fn can_reuse_types(
+    state: &State,
+    module_id: ModuleId,
+    ident_ids: &IdentIds,
+    reusable: &ReusableModule,
+) -> bool {
+    let same_source = state
+        .module_cache
+        .sources
+        .get(&module_id)
+        .map_or(false, |(path, source)| {
+            *path == reusable.path && *source == &*reusable.source
+        });
+
+    let imports: MutSet<_> = state
+        .module_cache
+        .imports
+        .get(&module_id)
+        .into_iter()
+        .flatten()
+        .copied()
+        .filter(|id| !id.is_builtin())
+        .collect();
+
+    same_source
+        && *ident_ids == reusable.ident_ids
+        && imports == reusable.imports
+        && imports.iter().all(|id| state.reused_modules.contains(id))
+}
+
 fn start_phase<'a>(
     module_id: ModuleId,
     phase: Phase,
@@ -324,6 +358,24 @@ fn start_phase<'a>(
                     ..
                 } = constrained;
 
+                let reusable = state
+                    .reusable_modules
+                    .as_mut()
+                    .and_then(|reusable_modules| reusable_modules.remove(&module_id));
+
+                if let Some(reusable) = reusable {
+                    if can_reuse_types(state, module_id, &ident_ids, &reusable) {
+                        let ReusableModule {
+                            types, problems, ..
+                        } = reusable;
+
+                        // Solving takes the types from the cache instead, like it does for builtins
+                        state.cached_types.lock().insert(module_id, types);
+                        state.module_cache.type_problems.insert(module_id, problems);
+                        state.reused_modules.insert(module_id);
+                    }
+                }
+
                 let derived_module = SharedDerivedModule::clone(&state.derived_module);
 
                 #[cfg(debug_assertions)]
@@ -738,9 +790,15 @@ struct State<'a> {
 
     make_specializations_pass: MakeSpecializationsPass,
 
-    // cached types (used for builtin modules, could include packages in the future too)
+    // cached types (used for builtin modules and reused modules, could include packages in the future too)
     cached_types: CachedTypeState,
 
+    /// Type checking results from an earlier load, used for modules that haven't changed since.
+    /// `None` unless the caller asked to reuse them, in which case we also keep what the next
+    /// load needs to reuse this one's results.
+    reusable_modules: Option<MutMap<ModuleId, ReusableModule>>,
+    reused_modules: MutSet<ModuleId>,
+
     layout_interner: GlobalLayoutInterner<'a>,
 }
 
@@ -761,6 +819,7 @@ impl<'a> State<'a> {
         arc_modules: Arc<Mutex<PackageModuleIds<'a>>>,
         ident_ids_by_module: SharedIdentIdsByModule,
         cached_types: MutMap<ModuleId, TypeState>,
+        reusable_modules: Option<MutMap<ModuleId, ReusableModule>>,
         render: RenderTarget,
         palette: Palette,
         number_of_workers: usize,
@@ -770,6 +829,10 @@ impl<'a> State<'a> {
         let cache_dir = roc_packaging::cache::roc_cache_dir();
         let dependencies = Dependencies::new(exec_mode.goal_phase());
 
+        // Builds solve every module from scratch; reusing types is only for type checking
+        let reusable_modules =
+            reusable_modules.filter(|_| matches!(exec_mode, ExecutionMode::Check));
+
         Self {
             root_id,
             root_path,
@@ -798,6 +861,8 @@ impl<'a> State<'a> {
             timings: MutMap::default(),
             layout_caches: std::vec::Vec::with_capacity(number_of_workers),
             cached_types: Arc::new(Mutex::new(cached_types)),
+            reusable_modules,
+            reused_modules: MutSet::default(),
             render,
             palette,
             exec_mode,
@@ -1084,6 +1149,7 @@ pub struct LoadStart<'a> {
     root_msg: Msg<'a>,
     opt_platform_shorthand: Option<&'a str>,
     src_dir: PathBuf,
+    reusable_modules: Option<MutMap<ModuleId, ReusableModule>>,
 }
 
 impl<'a> LoadStart<'a> {
@@ -1143,6 +1209,7 @@ impl<'a> LoadStart<'a> {
             root_path: filename,
             root_msg: header_output.msg,
             opt_platform_shorthand: header_output.opt_platform_shorthand,
+            reusable_modules: None,
         })
     }
 
@@ -1186,8 +1253,22 @@ impl<'a> LoadStart<'a> {
             root_path: filename,
             root_msg,
             opt_platform_shorthand: opt_platform_id,
+            reusable_modules: None,
         })
     }
+
+    /// Use the type checking results of an earlier load (see [LoadedModule::reusable_modules])
+    /// for the modules that haven't changed since, instead of solving them again. Pass an empty
+    /// map for the first load, so that its results can be reused in turn.
+    ///
+    /// This only has an effect with [ExecutionMode::Check], e.g. in the language server.
+    pub fn with_reusable_modules(
+        mut self,
+        reusable_modules: MutMap<ModuleId, ReusableModule>,
+    ) -> Self {
+        self.reusable_modules = Some(reusable_modules);
+        self
+    }
 }
 
 fn adjust_header_paths<'a>(
@@ -1362,7 +1443,7 @@ pub fn load_single_threaded<'a>(
         root_msg,
         src_dir,
         opt_platform_shorthand,
-        ..
+        reusable_modules,
     } = load_start;
 
     let (msg_tx, msg_rx) = bounded(1024);
@@ -1382,6 +1463,7 @@ pub fn load_single_threaded<'a>(
         arc_modules,
         ident_ids_by_module,
         cached_types,
+        reusable_modules,
         render,
         palette,
         number_of_workers,
@@ -1689,7 +1771,7 @@ fn load_multi_threaded<'a>(
         root_msg,
         src_dir,
         opt_platform_shorthand,
-        ..
+        reusable_modules,
     } = load_start;
 
     let (msg_tx, msg_rx) = bounded(1024);
@@ -1724,6 +1806,7 @@ fn load_multi_threaded<'a>(
         arc_modules,
         ident_ids_by_module,
         cached_types,
+        reusable_modules,
         render,
         palette,
         num_workers,
@@ -2256,7 +2339,7 @@ fn update<'a>(
 
                 for (shorthand, package_name) in header.packages.iter() {
                     let package_str = package_name.as_str();
-                    let shorthand_path = if package_str.starts_with("https://") {
+                    let shorthand_path = if is_package_url(package_str) {
                         #[cfg(not(target_family = "wasm"))]
                         {
                             let url = package_str;
@@ -2583,10 +2666,12 @@ fn update<'a>(
             log!("solved types for {:?}", module_id);
             module_timing.end_time = Instant::now();
 
+            // A reused module already has the problems from when it was solved
             state
                 .module_cache
                 .type_problems
-                .insert(module_id, solved_module.problems);
+                .entry(module_id)
+                .or_insert(solved_module.problems);
             state
                 .module_cache
                 .exposes
@@ -2710,6 +2795,12 @@ fn update<'a>(
                 // the originally requested module, we're all done!
                 return Ok(state);
             } else {
+                // Only needed to reuse this module's types in a later load
+                let solved_implementations = state
+                    .reusable_modules
+                    .is_some()
+                    .then(|| solved_module.solved_implementations.clone());
+
                 state.exposed_types.insert(
                     module_id,
                     ExposedModuleTypes {
@@ -2748,6 +2839,7 @@ fn update<'a>(
                             solved_subs,
                             decls,
                             abilities_store,
+                            solved_implementations,
                         },
                     );
                     state.constrained_ident_ids.insert(module_id, ident_ids);
@@ -3401,6 +3493,7 @@ fn finish(
         exposed_imports: state.module_cache.exposed_imports,
         imports: state.module_cache.imports,
         exposes: state.module_cache.exposes,
+        reused_modules: state.reused_modules,
     }
 }
 
@@ -4181,7 +4274,7 @@ fn load_packages<'a>(
 
         // find the `package` or `platform` module on disk,
         // downloading it into a cache dir first if necessary.
-        let root_module_path = if src.starts_with("https://") {
+        let root_module_path = if is_package_url(src) {
             #[cfg(not(target_family = "wasm"))]
             {
                 // If this is a HTTPS package, synchronously download it
@@ -4190,7 +4283,9 @@ fn load_packages<'a>(
                 // TODO we should do this async; however, with the current
                 // architecture of file.rs (which doesn't use async/await),
                 // this would be very difficult!
-                match cache::install_package(roc_cache_dir, src) {
+                let vendor_dir = cwd.join(VENDOR_DIR_NAME);
+
+                match cache::install_package(roc_cache_dir, src, Some(&vendor_dir)) {
                     Ok((package_dir, opt_root_module)) => {
                         // You can optionally specify the root module using the URL fragment,
                         // e.g. #foo.roc
@@ -5097,40 +5192,10 @@ fn run_solve<'a>(
     let module = module;
 
     let solve_result = {
-        if module_id.is_builtin() {
-            match cached_types.lock().remove(&module_id) {
-                None => run_solve_solve(
-                    exposed_for_module,
-                    types,
-                    constraints,
-                    constraint,
-                    function_kind,
-                    pending_derives,
-                    var_store,
-                    module,
-                    derived_module,
-                    //
-                    #[cfg(debug_assertions)]
-                    checkmate,
-                ),
-                Some(TypeState {
-                    subs,
-                    exposed_vars_by_symbol,
-                    abilities,
-                    solved_implementations,
-                }) => SolveResult {
-                    solved: Solved(subs),
-                    solved_implementations,
-                    exposed_vars_by_symbol,
-                    problems: vec![],
-                    abilities_store: abilities,
+        let cached = cached_types.lock().remove(&module_id);
 
-                    #[cfg(debug_assertions)]
-                    checkmate: None,
-                },
-            }
-        } else {
-            run_solve_solve(
+        match cached {
+            None => run_solve_solve(
                 exposed_for_module,
                 types,
                 constraints,
@@ -5143,7 +5208,22 @@ fn run_solve<'a>(
                 //
                 #[cfg(debug_assertions)]
                 checkmate,
-            )
+            ),
+            Some(TypeState {
+                subs,
+                exposed_vars_by_symbol,
+                abilities,
+                solved_implementations,
+            }) => SolveResult {
+                solved: Solved(subs),
+                solved_implementations,
+                exposed_vars_by_symbol,
+                problems: vec![],
+                abilities_store: abilities,
+
+                #[cfg(debug_assertions)]
+                checkmate: None,
+            },
         }
     };
 
@@ -6545,6 +6625,7 @@ fn to_import_cycle_report(
         filename,
         doc,
         title: "IMPORT CYCLE".to_string(),
+        code: roc_reporting::codes::IMPORT_CYCLE,
         severity: Severity::RuntimeError,
     };
 
@@ -6594,6 +6675,7 @@ fn to_incorrect_module_name_report<'a>(
         filename,
         doc,
         title: "INCORRECT MODULE NAME".to_string(),
+        code: roc_reporting::codes::INCORRECT_MODULE_NAME,
         severity: Severity::RuntimeError,
     };
 
@@ -6677,6 +6759,7 @@ fn report_cannot_run(
                     filename,
                     doc,
                     title: "NO PLATFORM".to_string(),
+                    code: roc_reporting::codes::NO_PLATFORM,
                     severity: Severity::RuntimeError,
                 }
             }
@@ -6692,6 +6775,7 @@ fn report_cannot_run(
                     filename,
                     doc,
                     title: "NO PLATFORM".to_string(),
+                    code: roc_reporting::codes::NO_PLATFORM,
                     severity: Severity::RuntimeError,
                 }
             }
@@ -6707,6 +6791,7 @@ fn report_cannot_run(
                     filename,
                     doc,
                     title: "NO PLATFORM".to_string(),
+                    code: roc_reporting::codes::NO_PLATFORM,
                     severity: Severity::RuntimeError,
                 }
             }
@@ -6722,6 +6807,7 @@ fn report_cannot_run(
                     filename,
                     doc,
                     title: "NO PLATFORM".to_string(),
+                    code: roc_reporting::codes::NO_PLATFORM,
                     severity: Severity::RuntimeError,
                 }
             }
@@ -4,7 +4,7 @@ use roc_can::expr::{DbgLookup, ExpectLookup};
 use roc_can::{
     abilities::AbilitiesStore,
     expr::{Declarations, PendingDerives},
-    module::{Module, ResolvedImplementations},
+    module::{Module, ResolvedImplementations, TypeState},
 };
 use roc_collections::{MutMap, MutSet, VecMap};
 use roc_module::ident::Ident;
@@ -51,6 +51,9 @@ pub struct LoadedModule {
     pub imports: MutMap<ModuleId, MutSet<ModuleId>>,
     pub exposed_imports: MutMap<ModuleId, MutMap<Symbol, Region>>,
     pub exposes: MutMap<ModuleId, Vec<(Symbol, Variable)>>,
+
+    /// The modules whose types were taken from a [ReusableModule] instead of being solved again.
+    pub reused_modules: MutSet<ModuleId>,
 }
 
 impl LoadedModule {
@@ -81,6 +84,96 @@ impl LoadedModule {
             .map(|symbol| symbol.as_str(&self.interns))
             .collect()
     }
+
+    /// The type checking results of every module in this load that a later load can reuse.
+    /// Only the root module can be reused unless this load used
+    /// [LoadStart::with_reusable_modules](crate::file::LoadStart::with_reusable_modules).
+    pub fn reusable_modules(&self) -> MutMap<ModuleId, ReusableModule> {
+        let mut reusable = MutMap::default();
+
+        for (module_id, (path, source)) in self.sources.iter() {
+            let (subs, abilities, solved_implementations) = if *module_id == self.module_id {
+                (
+                    &self.solved,
+                    &self.abilities_store,
+                    &self.resolved_implementations,
+                )
+            } else {
+                match self.typechecked.get(module_id) {
+                    Some(CheckedModule {
+                        solved_subs,
+                        abilities_store,
+                        solved_implementations: Some(solved_implementations),
+                        ..
+                    }) => (solved_subs, abilities_store, solved_implementations),
+                    _ => continue,
+                }
+            };
+
+            let Some(ident_ids) = self.interns.all_ident_ids.get(module_id) else {
+                continue;
+            };
+            if module_id.is_builtin() || uses_derived_lambdas(subs.inner()) {
+                continue;
+            }
+
+            let imports = self
+                .imports
+                .get(module_id)
+                .into_iter()
+                .flatten()
+                .copied()
+                .filter(|id| !id.is_builtin())
+                .collect();
+
+            let types = TypeState {
+                subs: subs.inner().clone(),
+                exposed_vars_by_symbol: self.exposes.get(module_id).cloned().unwrap_or_default(),
+                abilities: abilities.clone(),
+                solved_implementations: solved_implementations.clone(),
+            };
+
+            let module = ReusableModule {
+                path: path.clone(),
+                source: source.clone(),
+                ident_ids: ident_ids.clone(),
+                imports,
+                types,
+                problems: self
+                    .type_problems
+                    .get(module_id)
+                    .cloned()
+                    .unwrap_or_default(),
+            };
+            reusable.insert(*module_id, module);
+        }
+
+        reusable
+    }
+}
+
+/// Whether any lambda in `subs` might come from the derived module. Those are made while solving,
+/// so they only exist in the load that solved the module.
+///
+/// The names of lambdas are among the symbols in `subs`, so this errs on the side of not reusing
+/// a module if any symbol is from the derived module.
+fn uses_derived_lambdas(subs: &Subs) -> bool {
+    subs.symbol_names
+        .iter()
+        .any(|symbol| symbol.module_id() == ModuleId::DERIVED_SYNTH)
+}
+
+/// The results of type checking a module, which a later load can use instead of solving the module
+/// again, as long as the module, its module and ident IDs, and everything it imports are the same.
+#[derive(Debug, Clone)]
+pub struct ReusableModule {
+    pub(crate) path: PathBuf,
+    pub(crate) source: Box<str>,
+    pub(crate) ident_ids: IdentIds,
+    /// The modules this one imports, other than the builtins.
+    pub(crate) imports: MutSet<ModuleId>,
+    pub(crate) types: TypeState,
+    pub(crate) problems: Vec<TypeError>,
 }
 
 #[derive(Debug)]
@@ -140,6 +233,9 @@ pub struct CheckedModule {
     pub solved_subs: Solved<Subs>,
     pub decls: Declarations,
     pub abilities_store: AbilitiesStore,
+    /// Only kept when the load was asked to make its results reusable, see
+    /// [LoadStart::with_reusable_modules](crate::file::LoadStart::with_reusable_modules).
+    pub solved_implementations: Option<ResolvedImplementations>,
 }
 
 #[derive(Debug)]
@@ -17,11 +17,12 @@ mod helpers;
 use crate::helpers::fixtures_dir;
 use bumpalo::Bump;
 use roc_can::module::ExposedByModule;
+use roc_collections::{MutMap, MutSet};
 use roc_load_internal::docs::DocDef;
 use roc_load_internal::file::{
     ExecutionMode, LoadConfig, LoadResult, LoadStart, LoadingProblem, Threading,
 };
-use roc_load_internal::module::LoadedModule;
+use roc_load_internal::module::{LoadedModule, ReusableModule};
 use roc_module::ident::ModuleName;
 use roc_module::symbol::{Interns, ModuleId};
 use roc_packaging::cache::RocCacheDir;
@@ -689,7 +690,7 @@ fn parse_problem() {
             report,
             indoc!(
                 "
-                    ── UNFINISHED LIST in tmp/parse_problem/Main.roc ───────────────────────────────
+                    ── UNFINISHED LIST [R0053] in tmp/parse_problem/Main.roc ───────────────────────
 
                     I am partway through started parsing a list, but I got stuck here:
 
@@ -895,7 +896,7 @@ fn opaque_wrapped_unwrapped_outside_defining_module() {
         err,
         indoc!(
             r"
-                ── OPAQUE TYPE DECLARED OUTSIDE SCOPE in ...d_outside_defining_module/Main.roc ─
+                ── OPAQUE TYPE DECLARED OUTSIDE SCOPE [R0236] in ...e_defining_module/Main.roc ─
 
                 The unwrapped opaque type Age referenced here:
 
@@ -909,7 +910,7 @@ fn opaque_wrapped_unwrapped_outside_defining_module() {
 
                 Note: Opaque types can only be wrapped and unwrapped in the module they are defined in!
 
-                ── OPAQUE TYPE DECLARED OUTSIDE SCOPE in ...d_outside_defining_module/Main.roc ─
+                ── OPAQUE TYPE DECLARED OUTSIDE SCOPE [R0236] in ...e_defining_module/Main.roc ─
 
                 The unwrapped opaque type Age referenced here:
 
@@ -923,7 +924,7 @@ fn opaque_wrapped_unwrapped_outside_defining_module() {
 
                 Note: Opaque types can only be wrapped and unwrapped in the module they are defined in!
 
-                ── UNUSED IMPORT in ...aque_wrapped_unwrapped_outside_defining_module/Main.roc ─
+                ── UNUSED IMPORT [R0256] in ...pped_unwrapped_outside_defining_module/Main.roc ─
 
                 Nothing from Age is used in this module.
 
@@ -978,7 +979,7 @@ fn issue_2863_module_type_does_not_exist() {
                 report,
                 indoc!(
                     "
-                        ── UNRECOGNIZED NAME in tmp/issue_2863_module_type_does_not_exist/main.roc ─────
+                        ── UNRECOGNIZED NAME [R0253] in ...ue_2863_module_type_does_not_exist/main.roc ─
 
                         Nothing is named `DoesNotExist` in this scope.
 
@@ -1054,7 +1055,7 @@ fn module_doesnt_match_file_path() {
         err,
         indoc!(
             r"
-            ── WEIRD MODULE NAME in tmp/module_doesnt_match_file_path/Age.roc ──────────────
+            ── WEIRD MODULE NAME [R0072] in tmp/module_doesnt_match_file_path/Age.roc ──────
 
             This module name does not correspond with the file path it is defined
             in:
@@ -1087,7 +1088,7 @@ fn module_cyclic_import_itself() {
         err,
         indoc!(
             r"
-            ── IMPORT CYCLE in tmp/module_cyclic_import_itself/Age.roc ─────────────────────
+            ── IMPORT CYCLE [R0600] in tmp/module_cyclic_import_itself/Age.roc ─────────────
 
             I can't compile Age because it depends on itself through the following
             chain of module imports:
@@ -1132,7 +1133,7 @@ fn module_cyclic_import_transitive() {
         err,
         indoc!(
             r"
-            ── IMPORT CYCLE in tmp/module_cyclic_import_transitive/Age.roc ─────────────────
+            ── IMPORT CYCLE [R0600] in tmp/module_cyclic_import_transitive/Age.roc ─────────
 
             I can't compile Age because it depends on itself through the following
             chain of module imports:
@@ -1180,7 +1181,7 @@ fn nested_module_has_incorrect_name() {
         err,
         indoc!(
             r"
-            ── INCORRECT MODULE NAME in tmp/nested_module_has_incorrect_name/Dep/Foo.roc ───
+            ── INCORRECT MODULE NAME [R0601] in ...d_module_has_incorrect_name/Dep/Foo.roc ─
 
             This module has a different name than I expected:
 
@@ -1261,7 +1262,7 @@ fn non_roc_file_extension() {
 
     let expected = indoc!(
         r"
-        ── NOT A ROC FILE in tmp/non_roc_file_extension/main.md ────────────────────────
+        ── NOT A ROC FILE [R0605] in tmp/non_roc_file_extension/main.md ────────────────
 
         I expected a file with extension `.roc` or without extension.
         Instead I received a file with extension `.md`."
@@ -1291,7 +1292,7 @@ fn roc_file_no_extension() {
 
     let expected = indoc!(
         r"
-        ── NOT A ROC FILE in tmp/roc_file_no_extension/main ────────────────────────────
+        ── NOT A ROC FILE [R0605] in tmp/roc_file_no_extension/main ────────────────────
 
         I expected a file with either:
         - extension `.roc`
@@ -1306,3 +1307,179 @@ fn roc_file_no_extension() {
 
     assert_eq!(err, expected, "\n{}", err);
 }
+
+fn load_reusing(
+    arena: &Bump,
+    filename: PathBuf,
+    reusable_modules: MutMap<ModuleId, ReusableModule>,
+) -> LoadedModule {
+    let load_start = LoadStart::from_path(
+        arena,
+        filename,
+        RenderTarget::Generic,
+        RocCacheDir::Disallowed,
+        DEFAULT_PALETTE,
+    )
+    .unwrap()
+    .with_reusable_modules(reusable_modules);
+    let load_config = LoadConfig {
+        target: TARGET,
+        function_kind: FunctionKind::LambdaSet,
+        render: RenderTarget::Generic,
+        palette: DEFAULT_PALETTE,
+        threading: Threading::Single,
+        exec_mode: ExecutionMode::Check,
+    };
+
+    match roc_load_internal::file::load(
+        arena,
+        load_start,
+        Default::default(),
+        Default::default(),
+        RocCacheDir::Disallowed,
+        load_config,
+    )
+    .unwrap()
+    {
+        LoadResult::Monomorphized(_) => unreachable!(""),
+        LoadResult::TypeChecked(module) => module,
+    }
+}
+
+/// The types of everything a module exposes, and the implementations of abilities it solved
+fn solved_module_summary(loaded: &LoadedModule, module_id: ModuleId) -> (Vec<String>, String) {
+    let (mut subs, implementations) = if module_id == loaded.module_id {
+        (
+            loaded.solved.inner().clone(),
+            format!("{:?}", loaded.resolved_implementations),
+        )
+    } else {
+        let checked = &loaded.typechecked[&module_id];
+        (
+            checked.solved_subs.inner().clone(),
+            format!("{:?}", checked.solved_implementations),
+        )
+    };
+
+    let types = loaded.exposes[&module_id]
+        .iter()
+        .map(|(symbol, var)| {
+            let var_str = name_and_print_var(
+                *var,
+                &mut subs,
+                module_id,
+                &loaded.interns,
+                DebugPrint::NOTHING,
+            );
+            format!("{}: {var_str}", symbol.as_str(&loaded.interns))
+        })
+        .collect();
+
+    (types, implementations)
+}
+
+const REUSE_DEP: &str = indoc!(
+    r"
+    interface Dep exposes [Id, fromNum, same] imports []
+
+    Id := U64 implements [Eq { isEq: idEq }]
+
+    idEq : Id, Id -> Bool
+    idEq = \@Id a, @Id b -> a == b
+
+    fromNum : U64 -> Id
+    fromNum = \n -> @Id n
+
+    same : Id, Id -> Bool
+    same = \a, b -> a == b
+    "
+);
+
+const REUSE_MAIN: &str = indoc!(
+    r"
+    interface Main exposes [main] imports [Dep]
+
+    main = Dep.same (Dep.fromNum 1) (Dep.fromNum 2)
+    "
+);
+
+#[test]
+fn reused_module_matches_fresh_solve() {
+    let tmp = roc_test_utils::TmpDir::new("tmp/reused_module_matches_fresh_solve");
+    std::fs::write(tmp.path().join("Dep.roc"), REUSE_DEP).unwrap();
+    std::fs::write(tmp.path().join("Main.roc"), REUSE_MAIN).unwrap();
+    let main_path = tmp.path().join("Main.roc");
+
+    let arena = Bump::new();
+    let fresh = load_reusing(&arena, main_path.clone(), MutMap::default());
+    assert!(fresh.reused_modules.is_empty());
+
+    let reusable = fresh.reusable_modules();
+    let dep_id = *fresh
+        .sources
+        .iter()
+        .find(|(_, (path, _))| path.ends_with("Dep.roc"))
+        .unwrap()
+        .0;
+    assert!(reusable.contains_key(&dep_id));
+
+    let reused = load_reusing(&arena, main_path, reusable);
+    assert_eq!(
+        reused.reused_modules,
+        MutSet::from_iter([dep_id, fresh.module_id])
+    );
+
+    for module_id in [dep_id, fresh.module_id] {
+        assert_eq!(
+            solved_module_summary(&reused, module_id),
+            solved_module_summary(&fresh, module_id)
+        );
+    }
+}
+
+#[test]
+fn changed_module_is_solved_again() {
+    let tmp = roc_test_utils::TmpDir::new("tmp/changed_module_is_solved_again");
+    std::fs::write(tmp.path().join("Dep.roc"), REUSE_DEP).unwrap();
+    std::fs::write(tmp.path().join("Main.roc"), REUSE_MAIN).unwrap();
+    let main_path = tmp.path().join("Main.roc");
+
+    let arena = Bump::new();
+    let first = load_reusing(&arena, main_path.clone(), MutMap::default());
+
+    std::fs::write(
+        tmp.path().join("Dep.roc"),
+        REUSE_DEP.replace("fromNum = \\n -> @Id n", "fromNum = \\n -> @Id (n + 1)"),
+    )
+    .unwrap();
+
+    let second = load_reusing(&arena, main_path, first.reusable_modules());
+    assert!(second.reused_modules.is_empty());
+}
+
+#[test]
+fn builds_do_not_keep_reusable_modules() {
+    let tmp = roc_test_utils::TmpDir::new("tmp/builds_do_not_keep_reusable_modules");
+    std::fs::write(tmp.path().join("Dep.roc"), REUSE_DEP).unwrap();
+    std::fs::write(tmp.path().join("Main.roc"), REUSE_MAIN).unwrap();
+
+    let arena = Bump::new();
+    let loaded = load_and_typecheck(
+        &arena,
+        tmp.path().join("Main.roc"),
+        Default::default(),
+        TARGET,
+        FunctionKind::LambdaSet,
+    )
+    .unwrap();
+
+    // Without LoadStart::with_reusable_modules, dependencies don't keep what reusing them needs
+    assert!(loaded
+        .typechecked
+        .values()
+        .all(|checked| checked.solved_implementations.is_none()));
+    assert_eq!(
+        loaded.reusable_modules().keys().collect::<Vec<_>>(),
+        vec![&loaded.module_id]
+    );
+}
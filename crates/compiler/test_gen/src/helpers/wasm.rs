@@ -167,6 +167,7 @@ fn compile_roc_to_wasm_bytes<'a, T: Wasm32Result>(
     });
 
     module.eliminate_dead_code(env.arena, called_fns);
+    roc_gen_wasm::dump_final_wat(&module);
 
     let mut app_module_bytes = std::vec::Vec::with_capacity(module.size());
     module.serialize(&mut app_module_bytes);
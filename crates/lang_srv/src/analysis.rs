@@ -9,7 +9,10 @@ use bumpalo::Bump;
 use parking_lot::Mutex;
 use roc_can::{abilities::AbilitiesStore, expr::Declarations};
 use roc_collections::{MutMap, MutSet, VecMap};
-use roc_load::{docs::ModuleDocumentation, CheckedModule, LoadedModule};
+use roc_load::{
+    docs::ModuleDocumentation, CheckedModule, ExecutionMode, LoadResult, LoadStart, LoadedModule,
+    ReusableModule,
+};
 use roc_module::symbol::{Interns, ModuleId, Symbol};
 use roc_packaging::cache::{self, RocCacheDir};
 use roc_region::all::LineInfo;
@@ -20,15 +23,21 @@ use roc_types::subs::{Subs, Variable};
 use tower_lsp::lsp_types::{Diagnostic, SemanticTokenType, Url};
 
 mod analysed_doc;
+mod code_actions;
 mod completion;
+mod inlay_hints;
 mod parse_ast;
+mod references;
 mod semantic_tokens;
+mod signature_help;
+mod symbols;
 mod tokens;
 mod utils;
 
 use crate::convert::diag::{IntoLspDiagnostic, ProblemFmt};
 
 pub(crate) use self::analysed_doc::{AnalyzedDocument, DocInfo};
+pub(crate) use self::references::{Reference, ReferenceTarget};
 use self::{analysed_doc::ModuleIdToUrl, tokens::Token};
 
 pub const HIGHLIGHT_TOKENS_LEGEND: &[SemanticTokenType] = Token::LEGEND;
@@ -92,6 +101,8 @@ pub(super) struct AnalyzedModule {
     abilities: AbilitiesStore,
     declarations: Declarations,
     modules_info: Arc<ModulesInfo>,
+    /// Whether the types of this module were reused from an earlier analysis.
+    types_reused: bool,
     // We need this because ModuleIds are not stable between compilations, so a ModuleId visible to
     // one module may not be true global to the language server.
     module_id_to_url: ModuleIdToUrl,
@@ -100,24 +111,44 @@ pub(super) struct AnalyzedModule {
 pub struct AnalysisResult {
     module: Option<AnalyzedModule>,
     diagnostics: Vec<Diagnostic>,
+    /// Kept alongside their diagnostics so we can offer quick fixes for them.
+    can_problems: Vec<(roc_problem::can::Problem, Diagnostic)>,
 }
 
-pub(crate) fn global_analysis(doc_info: DocInfo) -> Vec<AnalyzedDocument> {
+pub(crate) type ReusableModules = MutMap<ModuleId, ReusableModule>;
+
+/// Analyses a document and every module it imports. The modules that haven't changed since the
+/// analysis `reusable_modules` came from, and don't import any that have, keep their types
+/// instead of being type checked again.
+///
+/// Also returns what the next analysis of this document can reuse, unless loading failed.
+pub(crate) fn global_analysis(
+    doc_info: DocInfo,
+    reusable_modules: ReusableModules,
+) -> (Vec<AnalyzedDocument>, Option<ReusableModules>) {
     let fi = doc_info.url.to_file_path().unwrap();
     let src_dir = find_src_dir(&fi).to_path_buf();
+    let cache_dir = cache::roc_cache_dir();
+    let roc_cache_dir = RocCacheDir::Persistent(cache_dir.as_path());
 
     let arena = Bump::new();
-    let loaded = roc_load::load_and_typecheck_str(
-        &arena,
-        fi,
-        &doc_info.source,
-        src_dir,
-        roc_target::Target::LinuxX64,
-        roc_load::FunctionKind::LambdaSet,
-        roc_reporting::report::RenderTarget::Generic,
-        RocCacheDir::Persistent(cache::roc_cache_dir().as_path()),
-        roc_reporting::report::DEFAULT_PALETTE,
-    );
+    let loaded = LoadStart::from_str(&arena, fi, &doc_info.source, roc_cache_dir, src_dir)
+        .and_then(|load_start| {
+            roc_load::load_single_threaded(
+                &arena,
+                load_start.with_reusable_modules(reusable_modules),
+                roc_target::Target::LinuxX64,
+                roc_load::FunctionKind::LambdaSet,
+                roc_reporting::report::RenderTarget::Generic,
+                roc_reporting::report::DEFAULT_PALETTE,
+                roc_cache_dir,
+                ExecutionMode::Check,
+            )
+        })
+        .map(|result| match result {
+            LoadResult::TypeChecked(module) => module,
+            LoadResult::Monomorphized(_) => unreachable!("we only type check"),
+        });
 
     let module = match loaded {
         Ok(module) => module,
@@ -132,13 +163,15 @@ pub(crate) fn global_analysis(doc_info: DocInfo) -> Vec<AnalyzedDocument> {
                 analysis_result: AnalysisResult {
                     module: None,
                     diagnostics: all_problems,
+                    can_problems: vec![],
                 },
             };
 
-            return vec![analyzed_document];
+            return (vec![analyzed_document], None);
         }
     };
 
+    let reusable_modules = module.reusable_modules();
     let mut documents = vec![];
 
     let LoadedModule {
@@ -154,6 +187,7 @@ pub(crate) fn global_analysis(doc_info: DocInfo) -> Vec<AnalyzedDocument> {
         mut imports,
         exposes,
         docs_by_module,
+        reused_modules,
         ..
     } = module;
 
@@ -181,6 +215,7 @@ pub(crate) fn global_analysis(doc_info: DocInfo) -> Vec<AnalyzedDocument> {
         exposed_imports,
         imports: &mut imports,
         modules_info,
+        reused_modules: &reused_modules,
     };
 
     for (module_id, (path, source)) in sources {
@@ -188,7 +223,7 @@ pub(crate) fn global_analysis(doc_info: DocInfo) -> Vec<AnalyzedDocument> {
         documents.push(doc);
     }
 
-    documents
+    (documents, Some(reusable_modules))
 }
 
 /// Take the exposed imports from each module, lookup the symbol within that module's list of
@@ -271,6 +306,7 @@ struct AnalyzedDocumentBuilder<'a> {
     imports: &'a mut MutMap<ModuleId, MutSet<ModuleId>>,
     exposed_imports: HashMap<ModuleId, Vec<(Symbol, Variable)>>,
     modules_info: Arc<ModulesInfo>,
+    reused_modules: &'a MutSet<ModuleId>,
 }
 
 impl<'a> AnalyzedDocumentBuilder<'a> {
@@ -310,12 +346,14 @@ impl<'a> AnalyzedDocumentBuilder<'a> {
             declarations,
             module_id,
             modules_info: self.modules_info.clone(),
+            types_reused: self.reused_modules.contains(&module_id),
             interns: self.interns.clone(),
             module_id_to_url: self.module_id_to_url.clone(),
         };
 
         let line_info = LineInfo::new(&source);
-        let diagnostics = self.build_diagnostics(&path, &source, &line_info, module_id);
+        let (diagnostics, can_problems) =
+            self.build_diagnostics(&path, &source, &line_info, module_id);
 
         AnalyzedDocument {
             doc_info: DocInfo {
@@ -327,6 +365,7 @@ impl<'a> AnalyzedDocumentBuilder<'a> {
             analysis_result: AnalysisResult {
                 module: Some(analyzed_module),
                 diagnostics,
+                can_problems,
             },
         }
     }
@@ -357,7 +396,10 @@ impl<'a> AnalyzedDocumentBuilder<'a> {
         source: &str,
         line_info: &LineInfo,
         module_id: ModuleId,
-    ) -> Vec<Diagnostic> {
+    ) -> (
+        Vec<Diagnostic>,
+        Vec<(roc_problem::can::Problem, Diagnostic)>,
+    ) {
         let lines: Vec<_> = source.lines().collect();
 
         let alloc = RocDocAllocator::new(&lines, module_id, self.interns);
@@ -373,9 +415,11 @@ impl<'a> AnalyzedDocumentBuilder<'a> {
 
         let type_problems = self.type_problems.remove(&module_id).unwrap_or_default();
 
+        let mut diagnosed_can_problems = Vec::new();
         for can_problem in can_problems {
-            if let Some(diag) = can_problem.into_lsp_diagnostic(&fmt) {
-                all_problems.push(diag);
+            if let Some(diag) = can_problem.clone().into_lsp_diagnostic(&fmt) {
+                all_problems.push(diag.clone());
+                diagnosed_can_problems.push((can_problem, diag));
             }
         }
 
@@ -385,6 +429,6 @@ impl<'a> AnalyzedDocumentBuilder<'a> {
             }
         }
 
-        all_problems
+        (all_problems, diagnosed_can_problems)
     }
 }
@@ -8,18 +8,25 @@ use roc_module::symbol::{ModuleId, Symbol};
 use roc_region::all::LineInfo;
 
 use tower_lsp::lsp_types::{
-    CompletionItem, Diagnostic, GotoDefinitionResponse, Hover, HoverContents, LanguageString,
-    Location, MarkedString, Position, Range, SemanticTokens, SemanticTokensResult, TextEdit, Url,
+    CodeActionOrCommand, CompletionItem, Diagnostic, DocumentSymbolResponse, FoldingRange,
+    GotoDefinitionResponse, Hover, HoverContents, InlayHint, LanguageString, Location,
+    MarkedString, Position, Range, SemanticTokens, SemanticTokensResult, SignatureHelp,
+    SymbolInformation, TextDocumentContentChangeEvent, TextEdit, Url,
 };
 
 use crate::{
     analysis::completion::{field_completion, get_completion_items, get_module_completion_items},
-    convert::{ToRange, ToRocPosition},
+    convert::{ToRange, ToRegion, ToRocPosition},
 };
 
 use super::{
+    code_actions::code_actions,
+    inlay_hints::inlay_hints,
     parse_ast::Ast,
+    references::{self, Reference, ReferenceTarget},
     semantic_tokens::arrange_semantic_tokens,
+    signature_help::signature_help,
+    symbols,
     utils::{format_var_type, is_roc_identifier_char},
     AnalysisResult, AnalyzedModule,
 };
@@ -49,6 +56,29 @@ impl DocInfo {
         }
     }
 
+    /// Applies the changes of a `textDocument/didChange` notification in order, each one to the
+    /// result of those before it.
+    pub fn apply_changes(
+        &self,
+        changes: Vec<TextDocumentContentChangeEvent>,
+        version: i32,
+    ) -> Self {
+        let mut source = self.source.clone();
+
+        for change in changes {
+            match change.range {
+                Some(range) => {
+                    let start = byte_offset(&source, range.start);
+                    let end = byte_offset(&source, range.end).max(start);
+                    source.replace_range(start..end, &change.text);
+                }
+                None => source = change.text,
+            }
+        }
+
+        Self::new(self.url.clone(), source, version)
+    }
+
     #[cfg(debug_assertions)]
     #[allow(unused)]
     fn debug_log_prefix(&self, offset: usize) {
@@ -112,9 +142,78 @@ impl DocInfo {
             data,
         }))
     }
+
+    pub fn document_symbols(&self) -> Option<DocumentSymbolResponse> {
+        let arena = &Bump::new();
+        let ast = Ast::parse(arena, &self.source).ok()?;
+
+        let symbols = ast
+            .outline()
+            .into_iter()
+            .map(|item| symbols::document_symbol(item, &self.line_info))
+            .collect();
+
+        Some(DocumentSymbolResponse::Nested(symbols))
+    }
+
+    pub fn workspace_symbols(&self, query: &str) -> Vec<SymbolInformation> {
+        let arena = &Bump::new();
+        match Ast::parse(arena, &self.source) {
+            Ok(ast) => symbols::workspace_symbols(ast.outline(), &self.url, &self.line_info, query),
+            Err(_) => vec![],
+        }
+    }
+
+    pub fn folding_ranges(&self) -> Option<Vec<FoldingRange>> {
+        let arena = &Bump::new();
+        let ast = Ast::parse(arena, &self.source).ok()?;
+
+        Some(symbols::folding_ranges(
+            &ast.outline(),
+            &self.source,
+            &self.line_info,
+        ))
+    }
+}
+
+/// The byte offset of an LSP position, whose character is counted in UTF-16 code units. Positions
+/// past the end of a line, or of the document, are moved back to its end.
+fn byte_offset(source: &str, position: Position) -> usize {
+    let mut line_start = 0;
+    for _ in 0..position.line {
+        match source[line_start..].find('\n') {
+            Some(newline) => line_start += newline + 1,
+            None => return source.len(),
+        }
+    }
+
+    let line = &source[line_start..];
+    let line = &line[..line.find('\n').unwrap_or(line.len())];
+
+    let mut units = 0;
+    for (index, c) in line.char_indices() {
+        if units >= position.character {
+            return line_start + index;
+        }
+        units += c.len_utf16() as u32;
+    }
+
+    line_start + line.len()
 }
 
 impl AnalyzedDocument {
+    /// Stands in for a document that has been opened but not analysed yet.
+    pub fn unanalysed(doc_info: DocInfo) -> Self {
+        Self {
+            doc_info,
+            analysis_result: AnalysisResult {
+                module: None,
+                diagnostics: vec![],
+                can_problems: vec![],
+            },
+        }
+    }
+
     pub fn url(&self) -> &Url {
         &self.doc_info.url
     }
@@ -138,10 +237,27 @@ impl AnalyzedDocument {
         self.analysis_result.module.is_some()
     }
 
+    pub fn is_builtin(&self) -> bool {
+        self.module()
+            .map_or(false, |module| module.module_id.is_builtin())
+    }
+
     pub fn diagnostics(&self) -> Vec<Diagnostic> {
         self.analysis_result.diagnostics.clone()
     }
 
+    pub fn code_actions(&self, range: Range) -> Vec<CodeActionOrCommand> {
+        match self.module() {
+            Some(module) => code_actions(
+                &self.doc_info,
+                module,
+                &self.analysis_result.can_problems,
+                range,
+            ),
+            None => vec![],
+        }
+    }
+
     pub fn symbol_at(&self, position: Position) -> Option<Symbol> {
         let line_info = self.line_info();
 
@@ -200,6 +316,19 @@ impl AnalyzedDocument {
         })
     }
 
+    pub fn signature_help(&self, position: Position) -> Option<SignatureHelp> {
+        let position = position.to_roc_position(self.line_info());
+
+        signature_help(&self.doc_info, self.module()?, position)
+    }
+
+    pub fn inlay_hints(&self, range: Range) -> Vec<InlayHint> {
+        match self.module() {
+            Some(module) => inlay_hints(&self.doc_info, module, range),
+            None => vec![],
+        }
+    }
+
     pub fn definition(&self, symbol: Symbol) -> Option<GotoDefinitionResponse> {
         let AnalyzedModule { declarations, .. } = self.module()?;
 
@@ -210,10 +339,80 @@ impl AnalyzedDocument {
         Some(GotoDefinitionResponse::Scalar(self.location(range)))
     }
 
+    /// Finds the definition of a top level value of this module by name, for symbols that come
+    /// from another document's analysis and so may be numbered differently.
+    pub fn definition_by_name(&self, name: &str) -> Option<GotoDefinitionResponse> {
+        let AnalyzedModule {
+            module_id, interns, ..
+        } = self.module()?;
+
+        let ident_id = interns.all_ident_ids.get(module_id)?.get_id(name)?;
+
+        self.definition(Symbol::new(*module_id, ident_id))
+    }
+
+    pub(crate) fn symbol_name(&self, symbol: Symbol) -> Option<&str> {
+        Some(symbol.as_str(&self.module()?.interns))
+    }
+
+    /// Whether this module's types were reused from an earlier analysis, rather than checked again.
+    pub(crate) fn types_reused(&self) -> bool {
+        self.module().map_or(false, |module| module.types_reused)
+    }
+
     pub(crate) fn module_url(&self, module_id: ModuleId) -> Option<Url> {
         self.module()?.module_id_to_url.get(&module_id).cloned()
     }
 
+    pub(crate) fn module_name(&self) -> Option<String> {
+        let AnalyzedModule {
+            module_id, interns, ..
+        } = self.module()?;
+
+        Some(module_id.to_ident_str(interns).to_string())
+    }
+
+    pub(crate) fn imports_module(&self, module_name: &str) -> bool {
+        self.module().map_or(false, |module| {
+            references::imports_module(module, module_name)
+        })
+    }
+
+    /// Whether renaming `target` to `new_name` would shadow a name in this module.
+    pub(crate) fn rename_would_shadow(&self, target: &ReferenceTarget, new_name: &str) -> bool {
+        self.module().map_or(false, |module| {
+            references::brings_into_scope(&self.doc_info, module, target)
+                && references::name_in_scope(&self.doc_info, module, new_name)
+        })
+    }
+
+    /// Whether renaming the record field `target` here could touch the wrong records.
+    pub(crate) fn field_rename_is_ambiguous(&self, target: &ReferenceTarget) -> bool {
+        self.module().map_or(false, |module| {
+            references::field_rename_is_ambiguous(&self.doc_info, module, target)
+        })
+    }
+
+    /// Finds what the name at `position` refers to, and the range of that name.
+    pub(crate) fn reference_target(&self, position: Position) -> Option<(Range, ReferenceTarget)> {
+        references::target_at(&self.doc_info, self.module()?, position)
+    }
+
+    pub(crate) fn references(&self, target: &ReferenceTarget) -> Vec<Reference> {
+        match self.module() {
+            Some(module) => references::find_references(&self.doc_info, module, target),
+            None => vec![],
+        }
+    }
+
+    /// The source text within `range`.
+    pub(crate) fn text_at(&self, range: Range) -> Option<&str> {
+        let region = range.to_region(self.line_info());
+        let source = &self.doc_info.source;
+
+        source.get(region.start().offset as usize..region.end().offset as usize)
+    }
+
     pub fn completion_items(
         &self,
         position: Position,
@@ -0,0 +1,343 @@
+use std::collections::HashMap;
+
+use bumpalo::Bump;
+use roc_can::expr::DeclarationTag;
+use roc_module::symbol::ModuleId;
+use roc_problem::can::{Problem, RuntimeError};
+use roc_region::all::{Loc, Position as RocPosition, Region};
+use roc_types::subs::Content;
+use tower_lsp::lsp_types::{
+    CodeAction, CodeActionKind, CodeActionOrCommand, Diagnostic, Position, Range, TextEdit,
+    WorkspaceEdit,
+};
+
+use crate::convert::{ToRange, ToRocPosition};
+
+use super::{
+    parse_ast::{Ast, ImportEntry},
+    utils::format_var_type,
+    AnalyzedModule, DocInfo,
+};
+
+/// Finds the code actions available within `range`: quick fixes for the `problems` whose
+/// diagnostics overlap it, and adding the inferred annotation to an unannotated top-level def.
+pub(super) fn code_actions(
+    doc: &DocInfo,
+    module: &AnalyzedModule,
+    problems: &[(Problem, Diagnostic)],
+    range: Range,
+) -> Vec<CodeActionOrCommand> {
+    let arena = Bump::new();
+    let imports = Ast::parse(&arena, &doc.source)
+        .ok()
+        .and_then(|ast| ast.imports());
+
+    let fixer = Fixer {
+        doc,
+        module,
+        imports: imports.as_deref(),
+    };
+
+    let quick_fixes = problems
+        .iter()
+        .filter(|(_, diagnostic)| overlaps(diagnostic.range, range))
+        .flat_map(|(problem, diagnostic)| {
+            fixer
+                .fixes(problem)
+                .into_iter()
+                .map(|(title, edits)| CodeAction {
+                    title,
+                    kind: Some(CodeActionKind::QUICKFIX),
+                    diagnostics: Some(vec![diagnostic.clone()]),
+                    edit: Some(fixer.workspace_edit(edits)),
+                    ..CodeAction::default()
+                })
+        });
+
+    quick_fixes
+        .chain(fixer.add_annotation(range))
+        .map(CodeActionOrCommand::CodeAction)
+        .collect()
+}
+
+/// An empty region, for inserting text at `position`.
+fn at(position: RocPosition) -> Region {
+    Region::new(position, position)
+}
+
+fn overlaps(a: Range, b: Range) -> bool {
+    a.start <= b.end && b.start <= a.end
+}
+
+/// The region to delete to remove `items[index]` from a comma-separated list, separator included.
+fn list_item_removal(items: &[Region], index: usize) -> Region {
+    let item = items[index];
+
+    if let Some(next) = items.get(index + 1) {
+        Region::new(item.start(), next.start())
+    } else if index > 0 {
+        Region::new(items[index - 1].end(), item.end())
+    } else {
+        item
+    }
+}
+
+struct Fixer<'a> {
+    doc: &'a DocInfo,
+    module: &'a AnalyzedModule,
+    /// The header's `imports` list, if the document parses and has one.
+    imports: Option<&'a [ImportEntry<'a>]>,
+}
+
+impl Fixer<'_> {
+    /// The quick fixes for `problem`, as titles and the edits that make them.
+    fn fixes(&self, problem: &Problem) -> Vec<(String, Vec<TextEdit>)> {
+        let interns = &self.module.interns;
+
+        match problem {
+            Problem::UnusedImport(symbol, region) => {
+                let name = symbol.as_str(interns);
+                self.remove_exposed_import(*region)
+                    .map(|edit| (format!("Remove unused import `{name}`"), vec![edit]))
+                    .into_iter()
+                    .collect()
+            }
+            Problem::UnusedModuleImport(module_id, region) => {
+                let name = module_id.to_ident_str(interns);
+                self.remove_module_import(*region)
+                    .map(|edit| (format!("Remove unused import `{name}`"), vec![edit]))
+                    .into_iter()
+                    .collect()
+            }
+            Problem::UnusedArgument(_, _, argument, region) => {
+                let name = argument.as_str(interns);
+
+                // Only plain identifiers can be prefixed; `_` in front of a destructured field
+                // would change which field it refers to.
+                if self.text(*region) != Some(name) {
+                    return vec![];
+                }
+
+                let edit = self.edit(at(region.start()), "_".to_string());
+                vec![(format!("Prefix `{name}` with an underscore"), vec![edit])]
+            }
+            Problem::RuntimeError(RuntimeError::LookupNotInScope { loc_name, .. }) => {
+                let name = loc_name.value.as_str();
+
+                self.project_modules_exposing(name)
+                    .into_iter()
+                    .filter_map(|module| {
+                        let edit = self.add_exposed_import(module, name)?;
+                        Some((format!("Import `{name}` from `{module}`"), vec![edit]))
+                    })
+                    .collect()
+            }
+            Problem::RuntimeError(RuntimeError::ModuleNotImported { module_name, .. }) => {
+                let module = module_name.as_str();
+                let is_project_module =
+                    self.module.module_id_to_url.keys().any(|id| {
+                        id.to_ident_str(interns).as_str() == module && self.in_project(*id)
+                    });
+
+                if !is_project_module {
+                    return vec![];
+                }
+
+                self.insert_import_entry(module)
+                    .map(|edit| (format!("Import `{module}`"), vec![edit]))
+                    .into_iter()
+                    .collect()
+            }
+            _ => vec![],
+        }
+    }
+
+    fn add_annotation(&self, range: Range) -> Option<CodeAction> {
+        let AnalyzedModule {
+            declarations,
+            subs,
+            module_id,
+            interns,
+            ..
+        } = self.module;
+
+        let position = range.start.to_roc_position(&self.doc.line_info);
+
+        let index = (0..declarations.len()).find(|&index| {
+            let is_value = matches!(
+                declarations.declarations[index],
+                DeclarationTag::Value
+                    | DeclarationTag::Function(_)
+                    | DeclarationTag::Recursive(_)
+                    | DeclarationTag::TailRecursive(_)
+            );
+
+            is_value
+                && declarations.annotations[index].is_none()
+                && declarations.symbols[index].region.contains_pos(position)
+        })?;
+
+        let Loc { region, value } = declarations.symbols[index];
+        let var = declarations.variables[index];
+
+        let mut subs = subs.clone();
+        if matches!(subs.get_content_without_compacting(var), Content::Error) {
+            return None;
+        }
+        let type_str = format_var_type(var, &mut subs, module_id, interns);
+
+        let name = value.as_str(interns);
+        let line = region.to_range(&self.doc.line_info).start.line;
+        let edit = TextEdit {
+            range: Range::new(Position::new(line, 0), Position::new(line, 0)),
+            new_text: format!("{name} : {type_str}\n"),
+        };
+
+        Some(CodeAction {
+            title: format!("Add type annotation to `{name}`"),
+            kind: Some(CodeActionKind::REFACTOR_REWRITE),
+            edit: Some(self.workspace_edit(vec![edit])),
+            ..CodeAction::default()
+        })
+    }
+
+    fn workspace_edit(&self, edits: Vec<TextEdit>) -> WorkspaceEdit {
+        WorkspaceEdit::new(HashMap::from([(self.doc.url.clone(), edits)]))
+    }
+
+    fn edit(&self, region: Region, new_text: String) -> TextEdit {
+        TextEdit {
+            range: region.to_range(&self.doc.line_info),
+            new_text,
+        }
+    }
+
+    fn text(&self, region: Region) -> Option<&str> {
+        self.doc
+            .source
+            .get(region.start().offset as usize..region.end().offset as usize)
+    }
+
+    fn imports(&self) -> &[ImportEntry<'_>] {
+        self.imports.unwrap_or_default()
+    }
+
+    /// Whether the module is part of this project, rather than a builtin or package. Unqualified
+    /// imports are looked up relative to the importing module, so those are the ones we can add.
+    fn in_project(&self, module_id: ModuleId) -> bool {
+        let dir = self
+            .doc
+            .url
+            .to_file_path()
+            .ok()
+            .and_then(|path| Some(path.parent()?.to_path_buf()));
+
+        let path = self
+            .module
+            .module_id_to_url
+            .get(&module_id)
+            .and_then(|url| url.to_file_path().ok());
+
+        match (dir, path) {
+            (Some(dir), Some(path)) => !module_id.is_builtin() && path.starts_with(dir),
+            _ => false,
+        }
+    }
+
+    /// The names of the other modules in the project that expose a value called `name`.
+    fn project_modules_exposing(&self, name: &str) -> Vec<&str> {
+        let AnalyzedModule {
+            module_id: home,
+            interns,
+            modules_info,
+            ..
+        } = self.module;
+
+        let mut modules: Vec<_> = modules_info
+            .exposed
+            .iter()
+            .filter(|(id, exposed)| {
+                *id != home
+                    && self.in_project(**id)
+                    && exposed
+                        .iter()
+                        .any(|(symbol, _)| symbol.as_str(interns) == name)
+            })
+            .map(|(id, _)| id.to_ident_str(interns).as_str())
+            .collect();
+
+        modules.sort_unstable();
+        modules
+    }
+
+    fn remove_module_import(&self, region: Region) -> Option<TextEdit> {
+        let entries = self.imports();
+        let index = entries
+            .iter()
+            .position(|entry| entry.region.contains(&region))?;
+
+        let regions: Vec<_> = entries.iter().map(|entry| entry.region).collect();
+        Some(self.edit(list_item_removal(&regions, index), String::new()))
+    }
+
+    fn remove_exposed_import(&self, region: Region) -> Option<TextEdit> {
+        let entry = self
+            .imports()
+            .iter()
+            .find(|entry| entry.region.contains(&region))?;
+        let index = entry
+            .exposed
+            .iter()
+            .position(|name| name.region.contains(&region))?;
+
+        if entry.exposed.len() > 1 {
+            let regions: Vec<_> = entry.exposed.iter().map(|name| name.region).collect();
+            return Some(self.edit(list_item_removal(&regions, index), String::new()));
+        }
+
+        // `Foo.{ bar }` becomes `Foo`, since values from the module may still be used qualified
+        let braces = self.text(entry.region)?.find(".{")?;
+        let start = RocPosition::new(entry.region.start().offset + braces as u32);
+        Some(self.edit(Region::new(start, entry.region.end()), String::new()))
+    }
+
+    /// Makes `name` available unqualified by adding it to the import of `module`.
+    fn add_exposed_import(&self, module: &str, name: &str) -> Option<TextEdit> {
+        let Some(entry) = self
+            .imports()
+            .iter()
+            .find(|entry| entry.module == Some(module))
+        else {
+            return self.insert_import_entry(&format!("{module}.{{ {name} }}"));
+        };
+
+        match entry.exposed.last() {
+            Some(last) => Some(self.edit(at(last.region.end()), format!(", {name}"))),
+            None => Some(self.edit(at(entry.region.end()), format!(".{{ {name} }}"))),
+        }
+    }
+
+    fn insert_import_entry(&self, entry: &str) -> Option<TextEdit> {
+        let entries = self.imports?;
+
+        if let Some(last) = entries.last() {
+            return Some(self.edit(at(last.region.end()), format!(", {entry}")));
+        }
+
+        // An empty list has no regions to go by, so look for its opening bracket in the source
+        let source = &self.doc.source;
+        let bracket = source
+            .match_indices("imports")
+            .find_map(|(start, keyword)| {
+                let after = start + keyword.len();
+                let rest = &source[after..];
+                let trimmed = rest.trim_start();
+
+                let is_keyword = !source[..start].ends_with(|c: char| c.is_alphanumeric());
+                (is_keyword && trimmed.starts_with('[')).then(|| after + rest.len() - trimmed.len())
+            })?;
+
+        let position = RocPosition::new(bracket as u32 + 1);
+        Some(self.edit(at(position), entry.to_string()))
+    }
+}
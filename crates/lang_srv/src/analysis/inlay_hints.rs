@@ -0,0 +1,144 @@
+use roc_can::{
+    def::Def,
+    expr::{DeclarationTag, Expr},
+    pattern::Pattern,
+    traverse::{walk_closure, walk_def, walk_expr, Visitor},
+};
+use roc_region::all::{Loc, Region};
+use roc_types::subs::{Content, Subs, Variable};
+use tower_lsp::lsp_types::{InlayHint, InlayHintKind, InlayHintLabel, Range};
+
+use crate::convert::{ToRange, ToRegion};
+
+use super::{utils::format_var_type, AnalyzedModule, DocInfo};
+
+/// Hints at the inferred types of the unannotated defs within `range`, and of the arguments of
+/// closures that are not the body of a def, like lambdas passed to functions and backpassing.
+pub(super) fn inlay_hints(doc: &DocInfo, module: &AnalyzedModule, range: Range) -> Vec<InlayHint> {
+    let declarations = &module.declarations;
+
+    let mut collector = HintCollector {
+        doc,
+        module,
+        subs: module.subs.clone(),
+        region: range.to_region(&doc.line_info),
+        hints: Vec::new(),
+    };
+
+    for index in 0..declarations.len() {
+        let is_value = matches!(
+            declarations.declarations[index],
+            DeclarationTag::Value
+                | DeclarationTag::Function(_)
+                | DeclarationTag::Recursive(_)
+                | DeclarationTag::TailRecursive(_)
+        );
+
+        if is_value && declarations.annotations[index].is_none() {
+            let symbol = declarations.symbols[index];
+            let name = symbol.value.as_str(&module.interns);
+            collector.hint(Loc::at(symbol.region, name), declarations.variables[index]);
+        }
+    }
+
+    // The arguments of top-level functions are covered by the type of the function itself
+    collector.visit_decls(declarations);
+
+    collector.hints.sort_by_key(|hint| hint.position);
+    collector.hints
+}
+
+struct HintCollector<'a> {
+    doc: &'a DocInfo,
+    module: &'a AnalyzedModule,
+    subs: Subs,
+    /// Where hints were asked for.
+    region: Region,
+    hints: Vec<InlayHint>,
+}
+
+impl HintCollector<'_> {
+    /// Adds a hint after the identifier `name`, unless the compiler made it up while desugaring.
+    fn hint(&mut self, name: Loc<&str>, var: Variable) {
+        let Loc { region, value } = name;
+
+        let written = self
+            .doc
+            .source
+            .get(region.start().offset as usize..region.end().offset as usize);
+
+        if written != Some(value) || !self.overlaps(region) {
+            return;
+        }
+
+        if matches!(
+            self.subs.get_content_without_compacting(var),
+            Content::Error
+        ) {
+            return;
+        }
+
+        let AnalyzedModule {
+            module_id, interns, ..
+        } = self.module;
+        let type_str = format_var_type(var, &mut self.subs, module_id, interns);
+
+        self.hints.push(InlayHint {
+            position: region.to_range(&self.doc.line_info).end,
+            label: InlayHintLabel::String(format!(": {type_str}")),
+            kind: Some(InlayHintKind::TYPE),
+            text_edits: None,
+            tooltip: None,
+            padding_left: None,
+            padding_right: None,
+            data: None,
+        });
+    }
+
+    fn hint_pattern(&mut self, pattern: &Loc<Pattern>, var: Variable) {
+        if let Pattern::Identifier(symbol) = pattern.value {
+            let name = symbol.as_str(&self.module.interns);
+            self.hint(Loc::at(pattern.region, name), var);
+        }
+    }
+
+    fn overlaps(&self, region: Region) -> bool {
+        region.start() <= self.region.end() && self.region.start() <= region.end()
+    }
+}
+
+impl Visitor for HintCollector<'_> {
+    fn should_visit(&mut self, region: Region) -> bool {
+        self.overlaps(region)
+    }
+
+    fn visit_def(&mut self, def: &Def) {
+        if !self.should_visit(def.region()) {
+            return;
+        }
+
+        if def.annotation.is_none() {
+            self.hint_pattern(&def.loc_pattern, def.expr_var);
+        }
+
+        match &def.loc_expr.value {
+            // Like at the top level, the type of the def already gives the argument types
+            Expr::Closure(closure) => walk_closure(self, closure),
+            _ => walk_def(self, def),
+        }
+    }
+
+    fn visit_expr(&mut self, expr: &Expr, region: Region, var: Variable) {
+        if !self.should_visit(region) {
+            return;
+        }
+
+        if let Expr::Closure(closure) = expr {
+            for (var, _, argument) in closure.arguments.iter() {
+                self.hint_pattern(argument, *var);
+            }
+        }
+
+        walk_expr(self, expr, var);
+    }
+}
@@ -4,17 +4,27 @@ use roc_parse::{
     ast::{Defs, Module},
     parser::SyntaxError,
 };
-use roc_region::all::Loc;
+use roc_region::all::{Loc, Position, Region};
 
 use self::format::FormattedAst;
 
 use super::tokens::{IterTokens, Token};
 
+pub use self::imports::ImportEntry;
+pub use self::occurrences::{find_ident_in_region, NameOccurrence, RecordAnnotation};
+pub use self::outline::{OutlineItem, OutlineKind};
+
 mod format;
+mod imports;
+mod occurrences;
+mod outline;
 
 pub struct Ast<'a> {
     arena: &'a Bump,
+    src: &'a str,
     module: Module<'a>,
+    /// From the start of the source to the end of the header, not including trailing whitespace.
+    header_region: Region,
     defs: Defs<'a>,
 }
 
@@ -29,12 +39,17 @@ impl<'a> Ast<'a> {
         let (module, state) = parse_header(arena, State::new(src.as_bytes()))
             .map_err(|e| SyntaxError::Header(e.problem))?;
 
+        let header_end = src[..state.pos().offset as usize].trim_end().len();
+        let header_region = Region::new(Position::new(0), Position::new(header_end as u32));
+
         let (_, defs, _) = module_defs().parse(arena, state, 0).map_err(|(_, e)| e)?;
 
         Ok(Ast {
             module,
+            header_region,
             defs,
             arena,
+            src,
         })
     }
 
@@ -56,4 +71,27 @@ impl<'a> Ast<'a> {
 
         header_tokens.into_iter().chain(body_tokens)
     }
+
+    /// The entries of the header's `imports` list, if it has one.
+    pub fn imports(&self) -> Option<Vec<ImportEntry<'a>>> {
+        imports::header_imports(&self.module.header)
+    }
+
+    pub fn outline(&self) -> Vec<OutlineItem> {
+        outline::outline(
+            self.src,
+            &self.module.header,
+            self.header_region,
+            &self.defs,
+        )
+    }
+
+    pub fn name_occurrences(&self) -> Vec<Loc<NameOccurrence<'a>>> {
+        occurrences::collect_occurrences(&self.module.header, &self.defs)
+    }
+
+    /// The record types written in annotations and type definitions.
+    pub fn record_annotations(&self) -> Vec<RecordAnnotation<'a>> {
+        occurrences::collect_record_annotations(&self.module.header, &self.defs)
+    }
 }
@@ -0,0 +1,55 @@
+use roc_parse::{ast::Header, header::ImportsEntry};
+use roc_region::all::{Loc, Region};
+
+/// An entry in a module header's `imports` list, like `pf.Task.{ Task, await }`.
+#[derive(Debug)]
+pub struct ImportEntry<'a> {
+    pub region: Region,
+    /// The module name without its package shorthand, e.g. `Task`. Ingested files, like
+    /// `"data.json" as data : Str`, have none.
+    pub module: Option<&'a str>,
+    /// The names exposed in braces after the module name.
+    pub exposed: Vec<Loc<&'a str>>,
+}
+
+/// Lists every entry of the header's `imports`, or `None` if the header has no `imports` list.
+pub(super) fn header_imports<'a>(header: &Header<'a>) -> Option<Vec<ImportEntry<'a>>> {
+    let imports = match header {
+        Header::Interface(h) => &h.imports.item,
+        Header::App(h) => &h.imports.as_ref()?.item,
+        Header::Platform(h) => &h.imports.item,
+        Header::Hosted(h) => &h.imports.item,
+        Header::Package(_) => return None,
+    };
+
+    let entries = imports
+        .items
+        .iter()
+        .map(|entry| {
+            let (module, exposed) = match entry.value.item() {
+                ImportsEntry::Module(module, exposed)
+                | ImportsEntry::Package(_, module, exposed) => (module.as_str(), exposed.items),
+                ImportsEntry::IngestedFile(..) => {
+                    return ImportEntry {
+                        region: entry.region,
+                        module: None,
+                        exposed: Vec::new(),
+                    }
+                }
+            };
+
+            let exposed = exposed
+                .iter()
+                .map(|name| Loc::at(name.region, name.value.item().as_str()))
+                .collect();
+
+            ImportEntry {
+                region: entry.region,
+                module: Some(module),
+                exposed,
+            }
+        })
+        .collect();
+
+    Some(entries)
+}
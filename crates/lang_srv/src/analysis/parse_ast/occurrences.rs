@@ -0,0 +1,611 @@
+use roc_parse::{
+    ast::{
+        AssignedField, Collection, Defs, Expr, Header, ImplementsAbilities, ImplementsAbility,
+        Pattern, RecordBuilderField, Spaced, StrLiteral, StrSegment, Tag, TypeAnnotation, TypeDef,
+        TypeHeader, ValueDef, WhenBranch,
+    },
+    header::{ExposedName, ImportsEntry},
+    ident::Accessor,
+};
+use roc_region::all::{Loc, Position, Region};
+
+/// A name written in the source that the canonical AST does not give us a precise region for.
+///
+/// Value lookups and bindings are not included here; those are resolved to symbols by
+/// canonicalization, and their regions come from there.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NameOccurrence<'a> {
+    /// An entry in this module's own `exposes` or `provides` list.
+    Exposed(&'a str),
+    /// An entry in an imports list, e.g. the `after` in `Task.{ after }`.
+    Imported { module: &'a str, name: &'a str },
+    /// A type alias, opaque type or ability. `module` is empty if the name is unqualified.
+    Type {
+        module: &'a str,
+        name: &'a str,
+        is_definition: bool,
+    },
+    /// A record field label. A punned label like the `x` in `{ x }` also stands for a value.
+    Field { name: &'a str, punned: bool },
+    /// The name in a value annotation like `foo : Str`, and the region of the pattern in the
+    /// body it annotates.
+    Annotation { body: Region },
+}
+
+/// A record type written in an annotation, like `{ x : I64, y : I64 }`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordAnnotation<'a> {
+    pub labels: Vec<Loc<&'a str>>,
+    /// Whether the record has an extension, like the `a` in `{ x : I64 }a`.
+    pub is_open: bool,
+}
+
+pub(super) fn collect_occurrences<'a>(
+    header: &Header<'a>,
+    defs: &Defs<'a>,
+) -> Vec<Loc<NameOccurrence<'a>>> {
+    collect(header, defs).found
+}
+
+pub(super) fn collect_record_annotations<'a>(
+    header: &Header<'a>,
+    defs: &Defs<'a>,
+) -> Vec<RecordAnnotation<'a>> {
+    collect(header, defs).records
+}
+
+fn collect<'a>(header: &Header<'a>, defs: &Defs<'a>) -> Collector<'a> {
+    let mut collector = Collector {
+        found: Vec::new(),
+        records: Vec::new(),
+    };
+    collector.header(header);
+    collector.defs(defs);
+
+    collector
+}
+
+struct Collector<'a> {
+    found: Vec<Loc<NameOccurrence<'a>>>,
+    records: Vec<RecordAnnotation<'a>>,
+}
+
+/// The region of `name` when it is written at the very end of `region`.
+fn suffix_region(region: Region, name: &str) -> Region {
+    Region::new(region.end().sub(name.len() as u32), region.end())
+}
+
+/// The region of `name` when it is written `skip` bytes after the start of `region`.
+fn region_at(region: Region, skip: usize, name: &str) -> Region {
+    let start = region.start().bump_column(skip as u32);
+    Region::new(start, start.bump_column(name.len() as u32))
+}
+
+fn is_identifier(pattern: &Pattern) -> bool {
+    match pattern {
+        Pattern::Identifier(_) => true,
+        Pattern::SpaceBefore(p, _) | Pattern::SpaceAfter(p, _) => is_identifier(p),
+        _ => false,
+    }
+}
+
+fn assigned_label<'a, T>(field: &AssignedField<'a, T>) -> Option<Loc<&'a str>> {
+    match field {
+        AssignedField::RequiredValue(label, _, _)
+        | AssignedField::OptionalValue(label, _, _)
+        | AssignedField::LabelOnly(label) => Some(*label),
+        AssignedField::SpaceBefore(f, _) | AssignedField::SpaceAfter(f, _) => assigned_label(f),
+        AssignedField::Malformed(_) => None,
+    }
+}
+
+fn qualifier_len(module: &str) -> usize {
+    if module.is_empty() {
+        0
+    } else {
+        module.len() + 1
+    }
+}
+
+impl<'a> Collector<'a> {
+    fn push(&mut self, region: Region, occurrence: NameOccurrence<'a>) {
+        self.found.push(Loc::at(region, occurrence));
+    }
+
+    fn header(&mut self, header: &Header<'a>) {
+        match header {
+            Header::Interface(h) => {
+                self.exposes(&h.exposes.item);
+                self.imports(&h.imports.item);
+            }
+            Header::App(h) => {
+                if let Some(imports) = &h.imports {
+                    self.imports(&imports.item);
+                }
+                self.exposes(&h.provides.entries);
+            }
+            Header::Platform(h) => {
+                self.imports(&h.imports.item);
+                self.exposes(&h.provides.item);
+            }
+            Header::Hosted(h) => {
+                self.exposes(&h.exposes.item);
+                self.imports(&h.imports.item);
+            }
+            Header::Package(_) => {}
+        }
+    }
+
+    fn exposes(&mut self, exposes: &Collection<'a, Loc<Spaced<'a, ExposedName<'a>>>>) {
+        for name in exposes.items {
+            self.push(
+                name.region,
+                NameOccurrence::Exposed(name.value.item().as_str()),
+            );
+        }
+    }
+
+    fn imports(&mut self, imports: &Collection<'a, Loc<Spaced<'a, ImportsEntry<'a>>>>) {
+        for entry in imports.items {
+            let (module, names) = match entry.value.item() {
+                ImportsEntry::Module(module, names) => (module.as_str(), names),
+                ImportsEntry::Package(_, module, names) => (module.as_str(), names),
+                ImportsEntry::IngestedFile(..) => continue,
+            };
+
+            for name in names.items {
+                let name_str = name.value.item().as_str();
+                self.push(
+                    name.region,
+                    NameOccurrence::Imported {
+                        module,
+                        name: name_str,
+                    },
+                );
+            }
+        }
+    }
+
+    fn defs(&mut self, defs: &Defs<'a>) {
+        for def in defs.defs() {
+            match def {
+                Ok(type_def) => self.type_def(type_def),
+                Err(value_def) => self.value_def(value_def),
+            }
+        }
+    }
+
+    fn type_header(&mut self, header: &TypeHeader<'a>) {
+        self.push(
+            header.name.region,
+            NameOccurrence::Type {
+                module: "",
+                name: header.name.value,
+                is_definition: true,
+            },
+        );
+    }
+
+    fn type_def(&mut self, type_def: &TypeDef<'a>) {
+        match type_def {
+            TypeDef::Alias { header, ann } => {
+                self.type_header(header);
+                self.type_annotation(ann);
+            }
+            TypeDef::Opaque {
+                header,
+                typ,
+                derived,
+            } => {
+                self.type_header(header);
+                self.type_annotation(typ);
+                if let Some(derived) = derived {
+                    self.implements_abilities(&derived.value);
+                }
+            }
+            TypeDef::Ability {
+                header, members, ..
+            } => {
+                self.type_header(header);
+                for member in members.iter() {
+                    self.type_annotation(&member.typ);
+                }
+            }
+        }
+    }
+
+    fn implements_abilities(&mut self, abilities: &ImplementsAbilities<'a>) {
+        match abilities {
+            ImplementsAbilities::Implements(abilities) => {
+                for ability in abilities.items {
+                    self.implements_ability(&ability.value);
+                }
+            }
+            ImplementsAbilities::SpaceBefore(a, _) | ImplementsAbilities::SpaceAfter(a, _) => {
+                self.implements_abilities(a)
+            }
+        }
+    }
+
+    fn implements_ability(&mut self, ability: &ImplementsAbility<'a>) {
+        match ability {
+            // The labels of an ability's implementations name its members, and the
+            // implementations themselves are value lookups, so only the ability is of interest.
+            ImplementsAbility::ImplementsAbility { ability, .. } => self.type_annotation(ability),
+            ImplementsAbility::SpaceBefore(a, _) | ImplementsAbility::SpaceAfter(a, _) => {
+                self.implements_ability(a)
+            }
+        }
+    }
+
+    fn value_def(&mut self, value_def: &ValueDef<'a>) {
+        match value_def {
+            ValueDef::Annotation(pattern, ann) => {
+                self.pattern(pattern);
+                self.type_annotation(ann);
+            }
+            ValueDef::Body(pattern, body) => {
+                self.pattern(pattern);
+                self.expr(body);
+            }
+            ValueDef::AnnotatedBody {
+                ann_pattern,
+                ann_type,
+                comment: _,
+                body_pattern,
+                body_expr,
+            } => {
+                if is_identifier(&ann_pattern.value) {
+                    self.push(
+                        ann_pattern.region,
+                        NameOccurrence::Annotation {
+                            body: body_pattern.region,
+                        },
+                    );
+                }
+                self.type_annotation(ann_type);
+                self.pattern(body_pattern);
+                self.expr(body_expr);
+            }
+            ValueDef::Dbg { condition, .. }
+            | ValueDef::Expect { condition, .. }
+            | ValueDef::ExpectFx { condition, .. } => self.expr(condition),
+        }
+    }
+
+    fn type_annotation(&mut self, ann: &Loc<TypeAnnotation<'a>>) {
+        match ann.value {
+            TypeAnnotation::Function(args, ret) => {
+                args.iter().for_each(|arg| self.type_annotation(arg));
+                self.type_annotation(ret);
+            }
+            TypeAnnotation::Apply(module, name, args) => {
+                self.push(
+                    region_at(ann.region, qualifier_len(module), name),
+                    NameOccurrence::Type {
+                        module,
+                        name,
+                        is_definition: false,
+                    },
+                );
+                args.iter().for_each(|arg| self.type_annotation(arg));
+            }
+            TypeAnnotation::As(ty, _, header) => {
+                self.type_annotation(ty);
+                self.type_header(&header);
+            }
+            TypeAnnotation::Record { fields, ext } => {
+                self.records.push(RecordAnnotation {
+                    labels: fields
+                        .items
+                        .iter()
+                        .filter_map(|field| assigned_label(&field.value))
+                        .collect(),
+                    is_open: ext.is_some(),
+                });
+                for field in fields.items {
+                    self.assigned_field(&field.value, Self::type_annotation);
+                }
+                ext.iter().for_each(|ext| self.type_annotation(ext));
+            }
+            TypeAnnotation::Tuple { elems, ext } => {
+                elems
+                    .items
+                    .iter()
+                    .for_each(|elem| self.type_annotation(elem));
+                ext.iter().for_each(|ext| self.type_annotation(ext));
+            }
+            TypeAnnotation::TagUnion { ext, tags } => {
+                for tag in tags.items {
+                    self.tag(&tag.value);
+                }
+                ext.iter().for_each(|ext| self.type_annotation(ext));
+            }
+            TypeAnnotation::Where(ty, clauses) => {
+                self.type_annotation(ty);
+                for clause in clauses.iter() {
+                    clause
+                        .value
+                        .abilities
+                        .iter()
+                        .for_each(|ability| self.type_annotation(ability));
+                }
+            }
+            TypeAnnotation::SpaceBefore(ty, _) | TypeAnnotation::SpaceAfter(ty, _) => {
+                self.type_annotation(&Loc::at(ann.region, *ty))
+            }
+            TypeAnnotation::BoundVariable(_)
+            | TypeAnnotation::Inferred
+            | TypeAnnotation::Wildcard
+            | TypeAnnotation::Malformed(_) => {}
+        }
+    }
+
+    fn tag(&mut self, tag: &Tag<'a>) {
+        match tag {
+            Tag::Apply { args, .. } => args.iter().for_each(|arg| self.type_annotation(arg)),
+            Tag::SpaceBefore(t, _) | Tag::SpaceAfter(t, _) => self.tag(t),
+            Tag::Malformed(_) => {}
+        }
+    }
+
+    fn assigned_field<T>(
+        &mut self,
+        field: &AssignedField<'a, T>,
+        mut value: impl FnMut(&mut Self, &Loc<T>),
+    ) {
+        match field {
+            AssignedField::RequiredValue(label, _, val)
+            | AssignedField::OptionalValue(label, _, val) => {
+                self.field(label, false);
+                value(self, val);
+            }
+            AssignedField::LabelOnly(label) => self.field(label, true),
+            AssignedField::SpaceBefore(f, _) | AssignedField::SpaceAfter(f, _) => {
+                self.assigned_field(f, value)
+            }
+            AssignedField::Malformed(_) => {}
+        }
+    }
+
+    fn field(&mut self, label: &Loc<&'a str>, punned: bool) {
+        self.push(
+            label.region,
+            NameOccurrence::Field {
+                name: label.value,
+                punned,
+            },
+        );
+    }
+
+    fn record_builder_field(&mut self, field: &RecordBuilderField<'a>) {
+        match field {
+            RecordBuilderField::Value(label, _, value)
+            | RecordBuilderField::ApplyValue(label, _, _, value) => {
+                self.field(label, false);
+                self.expr(value);
+            }
+            RecordBuilderField::LabelOnly(label) => self.field(label, true),
+            RecordBuilderField::SpaceBefore(f, _) | RecordBuilderField::SpaceAfter(f, _) => {
+                self.record_builder_field(f)
+            }
+            RecordBuilderField::Malformed(_) => {}
+        }
+    }
+
+    fn str_literal(&mut self, literal: &StrLiteral<'a>) {
+        let mut segments = |segments: &[StrSegment<'a>]| {
+            for segment in segments {
+                if let StrSegment::Interpolated(e) | StrSegment::DeprecatedInterpolated(e) = segment
+                {
+                    self.expr(&Loc::at(e.region, *e.value));
+                }
+            }
+        };
+
+        match literal {
+            StrLiteral::PlainLine(_) => {}
+            StrLiteral::Line(line) => segments(line),
+            StrLiteral::Block(lines) => lines.iter().for_each(|line| segments(line)),
+        }
+    }
+
+    fn expr(&mut self, expr: &Loc<Expr<'a>>) {
+        let region = expr.region;
+        match expr.value {
+            Expr::Str(literal) => self.str_literal(&literal),
+            Expr::RecordAccess(record, field) => {
+                self.expr(&Loc::at(region, *record));
+                self.push(
+                    suffix_region(region, field),
+                    NameOccurrence::Field {
+                        name: field,
+                        punned: false,
+                    },
+                );
+            }
+            Expr::AccessorFunction(Accessor::RecordField(field)) => self.push(
+                region_at(region, 1, field),
+                NameOccurrence::Field {
+                    name: field,
+                    punned: false,
+                },
+            ),
+            Expr::TupleAccess(tuple, _) => self.expr(&Loc::at(region, *tuple)),
+            Expr::List(items) | Expr::Tuple(items) => {
+                items.items.iter().for_each(|item| self.expr(item))
+            }
+            Expr::RecordUpdate { update, fields } => {
+                self.expr(update);
+                for field in fields.items {
+                    self.assigned_field(&field.value, Self::expr);
+                }
+            }
+            Expr::Record(fields) => {
+                for field in fields.items {
+                    self.assigned_field(&field.value, Self::expr);
+                }
+            }
+            Expr::RecordBuilder(fields) => {
+                for field in fields.items {
+                    self.record_builder_field(&field.value);
+                }
+            }
+            Expr::IngestedFile(_, ann) => self.type_annotation(ann),
+            Expr::OpaqueRef(name) => self.opaque_ref(region, name),
+            Expr::Closure(patterns, body) => {
+                patterns.iter().for_each(|p| self.pattern(p));
+                self.expr(body);
+            }
+            Expr::Defs(defs, body) => {
+                self.defs(defs);
+                self.expr(body);
+            }
+            Expr::Backpassing(patterns, e1, e2) => {
+                patterns.iter().for_each(|p| self.pattern(p));
+                self.expr(e1);
+                self.expr(e2);
+            }
+            Expr::Expect(e1, e2) | Expr::Dbg(e1, e2) | Expr::LowLevelDbg(_, e1, e2) => {
+                self.expr(e1);
+                self.expr(e2);
+            }
+            Expr::Apply(f, args, _) => {
+                self.expr(f);
+                args.iter().for_each(|arg| self.expr(arg));
+            }
+            Expr::BinOps(operands, last) => {
+                operands.iter().for_each(|(operand, _)| self.expr(operand));
+                self.expr(last);
+            }
+            Expr::UnaryOp(e, _) => self.expr(e),
+            Expr::If(branches, final_else) => {
+                for (cond, then) in branches.iter() {
+                    self.expr(cond);
+                    self.expr(then);
+                }
+                self.expr(final_else);
+            }
+            Expr::When(cond, branches) => {
+                self.expr(cond);
+                branches.iter().for_each(|branch| self.when_branch(branch));
+            }
+            Expr::SpaceBefore(e, _)
+            | Expr::SpaceAfter(e, _)
+            | Expr::ParensAround(e)
+            | Expr::Suffixed(e) => self.expr(&Loc::at(region, *e)),
+            Expr::MultipleRecordBuilders(e) | Expr::UnappliedRecordBuilder(e) => self.expr(e),
+            Expr::Float(_)
+            | Expr::Num(_)
+            | Expr::NonBase10Int { .. }
+            | Expr::SingleQuote(_)
+            | Expr::AccessorFunction(Accessor::TupleIndex(_))
+            | Expr::Var { .. }
+            | Expr::Underscore(_)
+            | Expr::Crash
+            | Expr::Tag(_)
+            | Expr::MalformedIdent(..)
+            | Expr::MalformedClosure
+            | Expr::PrecedenceConflict(_) => {}
+        }
+    }
+
+    fn when_branch(&mut self, branch: &WhenBranch<'a>) {
+        branch.patterns.iter().for_each(|p| self.pattern(p));
+        self.expr(&branch.value);
+        branch.guard.iter().for_each(|guard| self.expr(guard));
+    }
+
+    fn opaque_ref(&mut self, region: Region, name: &'a str) {
+        // The region of an opaque reference includes its leading `@`.
+        let name = name.trim_start_matches('@');
+        self.push(
+            suffix_region(region, name),
+            NameOccurrence::Type {
+                module: "",
+                name,
+                is_definition: false,
+            },
+        );
+    }
+
+    fn pattern(&mut self, pattern: &Loc<Pattern<'a>>) {
+        let region = pattern.region;
+        match pattern.value {
+            Pattern::OpaqueRef(name) => self.opaque_ref(region, name),
+            Pattern::Apply(p, args) => {
+                self.pattern(p);
+                args.iter().for_each(|arg| self.pattern(arg));
+            }
+            Pattern::RecordDestructure(fields) => {
+                for field in fields.items {
+                    self.record_destruct(field);
+                }
+            }
+            Pattern::Tuple(patterns) | Pattern::List(patterns) => {
+                patterns.items.iter().for_each(|p| self.pattern(p))
+            }
+            Pattern::As(p, _) => self.pattern(p),
+            Pattern::SpaceBefore(p, _) | Pattern::SpaceAfter(p, _) => {
+                self.pattern(&Loc::at(region, *p))
+            }
+            Pattern::Identifier(_)
+            | Pattern::Tag(_)
+            | Pattern::RequiredField(..)
+            | Pattern::OptionalField(..)
+            | Pattern::NumLiteral(_)
+            | Pattern::NonBase10Literal { .. }
+            | Pattern::FloatLiteral(_)
+            | Pattern::StrLiteral(_)
+            | Pattern::Underscore(_)
+            | Pattern::SingleQuote(_)
+            | Pattern::ListRest(_)
+            | Pattern::Malformed(_)
+            | Pattern::MalformedIdent(..)
+            | Pattern::QualifiedIdentifier { .. } => {}
+        }
+    }
+
+    fn record_destruct(&mut self, field: &Loc<Pattern<'a>>) {
+        let region = field.region;
+        match field.value {
+            Pattern::Identifier(name) => self.field(&Loc::at(region, name), true),
+            Pattern::RequiredField(name, guard) => {
+                self.field(&Loc::at(region_at(region, 0, name), name), false);
+                self.pattern(guard);
+            }
+            Pattern::OptionalField(name, default) => {
+                self.field(&Loc::at(region_at(region, 0, name), name), false);
+                self.expr(default);
+            }
+            Pattern::SpaceBefore(p, _) | Pattern::SpaceAfter(p, _) => {
+                self.record_destruct(&Loc::at(region, *p))
+            }
+            _ => self.pattern(field),
+        }
+    }
+}
+
+/// Returns the region of the first whole-word occurrence of `ident` in `region` of `src`.
+///
+/// Canonicalization gives us the region of a whole pattern or expression, like `Foo.bar` or
+/// `{ bar & x: 1 }`; this narrows it down to just the identifier.
+pub fn find_ident_in_region(src: &str, region: Region, ident: &str) -> Option<Region> {
+    let start = region.start().offset as usize;
+    let end = (region.end().offset as usize).min(src.len());
+    let haystack = src.get(start..end)?;
+    let is_ident_byte = |b: u8| b.is_ascii_alphanumeric() || b == b'_';
+
+    haystack.match_indices(ident).find_map(|(index, _)| {
+        let before = haystack.as_bytes().get(index.wrapping_sub(1)).copied();
+        let after = haystack.as_bytes().get(index + ident.len()).copied();
+
+        let is_whole_word =
+            !before.map_or(false, is_ident_byte) && !after.map_or(false, is_ident_byte);
+
+        is_whole_word.then(|| {
+            let start = Position::new((start + index) as u32);
+            Region::new(start, start.bump_column(ident.len() as u32))
+        })
+    })
+}
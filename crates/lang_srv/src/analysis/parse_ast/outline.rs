@@ -0,0 +1,348 @@
+use roc_parse::ast::{
+    AssignedField, Defs, Expr, Header, RecordBuilderField, StrLiteral, StrSegment, TypeAnnotation,
+    TypeDef, ValueDef, WhenBranch,
+};
+use roc_region::all::{Loc, Region};
+
+/// What an [OutlineItem] is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutlineKind {
+    Module,
+    Value,
+    Function,
+    Alias,
+    Opaque,
+    Ability,
+    AbilityMember,
+    When,
+    WhenBranch,
+}
+
+/// Something a module defines, along with what it defines in turn.
+#[derive(Debug)]
+pub struct OutlineItem {
+    pub name: String,
+    pub kind: OutlineKind,
+    /// The whole of the item, e.g. a def including its annotation.
+    pub region: Region,
+    /// The part of the item that names it.
+    pub name_region: Region,
+    /// The item's type, if the source gives one.
+    pub detail: Option<String>,
+    pub children: Vec<OutlineItem>,
+}
+
+/// Builds the outline of a module, with its header first and then its defs in order.
+pub(super) fn outline(
+    src: &str,
+    header: &Header,
+    header_region: Region,
+    defs: &Defs,
+) -> Vec<OutlineItem> {
+    let builder = OutlineBuilder { src };
+
+    let mut items = Vec::new();
+    items.push(builder.header(header, header_region));
+    builder.defs(defs, &mut items);
+    items
+}
+
+struct OutlineBuilder<'s> {
+    src: &'s str,
+}
+
+impl OutlineBuilder<'_> {
+    fn text(&self, region: Region) -> &str {
+        self.src
+            .get(region.start().offset as usize..region.end().offset as usize)
+            .unwrap_or_default()
+    }
+
+    /// The source text of `region` on a single line, for names and details.
+    fn one_line(&self, region: Region) -> String {
+        self.text(region)
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    fn header(&self, header: &Header, region: Region) -> OutlineItem {
+        let name_region = match header {
+            Header::Interface(h) => h.name.region,
+            Header::App(h) => h.name.region,
+            Header::Package(h) => h.name.region,
+            Header::Platform(h) => h.name.region,
+            Header::Hosted(h) => h.name.region,
+        };
+
+        OutlineItem {
+            name: self.text(name_region).trim_matches('"').to_string(),
+            kind: OutlineKind::Module,
+            region,
+            name_region,
+            detail: None,
+            children: vec![],
+        }
+    }
+
+    fn defs(&self, defs: &Defs, items: &mut Vec<OutlineItem>) {
+        for (index, def) in defs.defs().enumerate() {
+            let region = defs.regions[index];
+            match def {
+                Ok(type_def) => items.push(self.type_def(type_def, region)),
+                Err(value_def) => self.value_def(value_def, region, items),
+            }
+        }
+    }
+
+    fn type_def(&self, type_def: &TypeDef, region: Region) -> OutlineItem {
+        let (header, kind, detail, children) = match type_def {
+            TypeDef::Alias { header, ann } => {
+                (header, OutlineKind::Alias, Some(ann.region), vec![])
+            }
+            TypeDef::Opaque { header, typ, .. } => {
+                (header, OutlineKind::Opaque, Some(typ.region), vec![])
+            }
+            TypeDef::Ability {
+                header, members, ..
+            } => {
+                let members = members
+                    .iter()
+                    .map(|member| OutlineItem {
+                        name: member.name.value.item().to_string(),
+                        kind: OutlineKind::AbilityMember,
+                        region: Region::span_across(&member.name.region, &member.typ.region),
+                        name_region: member.name.region,
+                        detail: Some(self.one_line(member.typ.region)),
+                        children: vec![],
+                    })
+                    .collect();
+
+                (header, OutlineKind::Ability, None, members)
+            }
+        };
+
+        OutlineItem {
+            name: header.name.value.to_string(),
+            kind,
+            region,
+            name_region: header.name.region,
+            detail: detail.map(|ann| self.one_line(ann)),
+            children,
+        }
+    }
+
+    fn value_def(&self, value_def: &ValueDef, region: Region, items: &mut Vec<OutlineItem>) {
+        let (pattern, annotation, body) = match value_def {
+            ValueDef::Annotation(pattern, ann) => (pattern, Some(ann), None),
+            ValueDef::Body(pattern, body) => (*pattern, None, Some(*body)),
+            ValueDef::AnnotatedBody {
+                ann_type,
+                body_pattern,
+                body_expr,
+                ..
+            } => (*body_pattern, Some(*ann_type), Some(*body_expr)),
+            ValueDef::Dbg { condition, .. }
+            | ValueDef::Expect { condition, .. }
+            | ValueDef::ExpectFx { condition, .. } => {
+                // These don't define anything, but may still contain `when`s
+                self.expr(&condition.value, condition.region, items);
+                return;
+            }
+        };
+
+        let is_function = annotation.map_or(false, |ann| is_function_type(&ann.value))
+            || body.map_or(false, |body| is_closure(&body.value));
+
+        let mut children = Vec::new();
+        if let Some(body) = body {
+            self.expr(&body.value, body.region, &mut children);
+        }
+
+        items.push(OutlineItem {
+            name: self.one_line(pattern.region),
+            kind: if is_function {
+                OutlineKind::Function
+            } else {
+                OutlineKind::Value
+            },
+            region,
+            name_region: pattern.region,
+            detail: annotation.map(|ann| self.one_line(ann.region)),
+            children,
+        });
+    }
+
+    fn when(&self, condition: &Loc<Expr>, branches: &[&WhenBranch], region: Region) -> OutlineItem {
+        let children = branches
+            .iter()
+            .filter_map(|branch| {
+                let first = branch.patterns.first()?;
+                let last = branch.patterns.last()?;
+                let name_region = Region::span_across(&first.region, &last.region);
+
+                let mut children = Vec::new();
+                if let Some(guard) = &branch.guard {
+                    self.expr(&guard.value, guard.region, &mut children);
+                }
+                self.expr(&branch.value.value, branch.value.region, &mut children);
+
+                Some(OutlineItem {
+                    name: self.one_line(name_region),
+                    kind: OutlineKind::WhenBranch,
+                    region: Region::span_across(&name_region, &branch.value.region),
+                    name_region,
+                    detail: None,
+                    children,
+                })
+            })
+            .collect();
+
+        OutlineItem {
+            name: format!("when {}", self.one_line(condition.region)),
+            kind: OutlineKind::When,
+            region,
+            name_region: condition.region,
+            detail: None,
+            children,
+        }
+    }
+
+    fn loc_expr(&self, expr: &Loc<Expr>, items: &mut Vec<OutlineItem>) {
+        self.expr(&expr.value, expr.region, items)
+    }
+
+    /// Finds the items defined within an expression; `region` is the expression's region, or
+    /// that of the closest enclosing expression that has one.
+    fn expr(&self, expr: &Expr, region: Region, items: &mut Vec<OutlineItem>) {
+        match expr {
+            Expr::When(condition, branches) => {
+                items.push(self.when(condition, branches, region));
+            }
+            Expr::Defs(defs, final_expr) => {
+                self.defs(defs, items);
+                self.loc_expr(final_expr, items);
+            }
+            Expr::RecordAccess(e, _)
+            | Expr::TupleAccess(e, _)
+            | Expr::Suffixed(e)
+            | Expr::SpaceBefore(e, _)
+            | Expr::SpaceAfter(e, _)
+            | Expr::ParensAround(e) => self.expr(e, region, items),
+            Expr::List(elems) | Expr::Tuple(elems) => {
+                elems.iter().for_each(|e| self.loc_expr(e, items))
+            }
+            Expr::Record(fields) => fields
+                .iter()
+                .for_each(|field| self.assigned_field(&field.value, items)),
+            Expr::RecordUpdate { update, fields } => {
+                self.loc_expr(update, items);
+                fields
+                    .iter()
+                    .for_each(|field| self.assigned_field(&field.value, items));
+            }
+            Expr::RecordBuilder(fields) => fields
+                .iter()
+                .for_each(|field| self.record_builder_field(&field.value, items)),
+            Expr::Str(literal) => self.str_literal(literal, items),
+            Expr::Closure(_, body) => self.loc_expr(body, items),
+            Expr::Backpassing(_, e1, e2)
+            | Expr::Expect(e1, e2)
+            | Expr::Dbg(e1, e2)
+            | Expr::LowLevelDbg(_, e1, e2) => {
+                self.loc_expr(e1, items);
+                self.loc_expr(e2, items);
+            }
+            Expr::Apply(function, args, _) => {
+                self.loc_expr(function, items);
+                args.iter().for_each(|arg| self.loc_expr(arg, items));
+            }
+            Expr::BinOps(operands, last) => {
+                operands.iter().for_each(|(e, _)| self.loc_expr(e, items));
+                self.loc_expr(last, items);
+            }
+            Expr::UnaryOp(e, _)
+            | Expr::MultipleRecordBuilders(e)
+            | Expr::UnappliedRecordBuilder(e) => self.loc_expr(e, items),
+            Expr::If(branches, final_else) => {
+                for (condition, then) in branches.iter() {
+                    self.loc_expr(condition, items);
+                    self.loc_expr(then, items);
+                }
+                self.loc_expr(final_else, items);
+            }
+            Expr::PrecedenceConflict(conflict) => self.loc_expr(conflict.expr, items),
+            Expr::Float(_)
+            | Expr::Num(_)
+            | Expr::NonBase10Int { .. }
+            | Expr::SingleQuote(_)
+            | Expr::AccessorFunction(_)
+            | Expr::IngestedFile(..)
+            | Expr::Var { .. }
+            | Expr::Underscore(_)
+            | Expr::Crash
+            | Expr::Tag(_)
+            | Expr::OpaqueRef(_)
+            | Expr::MalformedIdent(..)
+            | Expr::MalformedClosure => {}
+        }
+    }
+
+    fn assigned_field(&self, field: &AssignedField<Expr>, items: &mut Vec<OutlineItem>) {
+        match field {
+            AssignedField::RequiredValue(_, _, value)
+            | AssignedField::OptionalValue(_, _, value) => self.loc_expr(value, items),
+            AssignedField::SpaceBefore(f, _) | AssignedField::SpaceAfter(f, _) => {
+                self.assigned_field(f, items)
+            }
+            AssignedField::LabelOnly(_) | AssignedField::Malformed(_) => {}
+        }
+    }
+
+    fn record_builder_field(&self, field: &RecordBuilderField, items: &mut Vec<OutlineItem>) {
+        match field {
+            RecordBuilderField::Value(_, _, value)
+            | RecordBuilderField::ApplyValue(_, _, _, value) => self.loc_expr(value, items),
+            RecordBuilderField::SpaceBefore(f, _) | RecordBuilderField::SpaceAfter(f, _) => {
+                self.record_builder_field(f, items)
+            }
+            RecordBuilderField::LabelOnly(_) | RecordBuilderField::Malformed(_) => {}
+        }
+    }
+
+    fn str_literal(&self, literal: &StrLiteral, items: &mut Vec<OutlineItem>) {
+        let mut segments = |segments: &[StrSegment]| {
+            for segment in segments {
+                if let StrSegment::Interpolated(e) | StrSegment::DeprecatedInterpolated(e) = segment
+                {
+                    self.expr(e.value, e.region, items);
+                }
+            }
+        };
+
+        match literal {
+            StrLiteral::PlainLine(_) => {}
+            StrLiteral::Line(line) => segments(line),
+            StrLiteral::Block(lines) => lines.iter().for_each(|line| segments(line)),
+        }
+    }
+}
+
+fn is_function_type(ann: &TypeAnnotation) -> bool {
+    match ann {
+        TypeAnnotation::Function(..) => true,
+        TypeAnnotation::Where(ann, _) => is_function_type(&ann.value),
+        TypeAnnotation::SpaceBefore(ann, _) | TypeAnnotation::SpaceAfter(ann, _) => {
+            is_function_type(ann)
+        }
+        _ => false,
+    }
+}
+
+fn is_closure(expr: &Expr) -> bool {
+    match expr {
+        Expr::Closure(..) => true,
+        Expr::SpaceBefore(e, _) | Expr::SpaceAfter(e, _) | Expr::ParensAround(e) => is_closure(e),
+        _ => false,
+    }
+}
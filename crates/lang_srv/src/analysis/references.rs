@@ -0,0 +1,707 @@
+use std::collections::HashMap;
+
+use bumpalo::Bump;
+use roc_can::{
+    expr::{Expr, StructAccessorData},
+    pattern::Pattern,
+    traverse::{walk_expr, walk_pattern, Visitor},
+};
+use roc_module::symbol::Symbol;
+use roc_region::all::{Loc, Region};
+use roc_types::{
+    subs::{Content, FlatType, Subs, Variable},
+    types::IndexOrField,
+};
+use tower_lsp::lsp_types::{Position, Range, Url};
+
+use crate::convert::{ToRange, ToRocPosition};
+
+use super::{
+    parse_ast::{find_ident_in_region, Ast, NameOccurrence},
+    AnalyzedModule, DocInfo,
+};
+
+/// What a find-references or rename request is about.
+///
+/// Modules and identifiers are named by strings rather than by `ModuleId`s and `Symbol`s, because
+/// those are not stable between the analyses that produced different documents.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum ReferenceTarget {
+    /// A value defined at the top level of `module`, which importing modules can refer to.
+    TopLevel { module: String, ident: String },
+    /// A value that is local to the document at `url`.
+    Local { url: Url, symbol: Symbol },
+    /// A type alias, opaque type or ability defined in `module`.
+    Type { module: String, name: String },
+    /// A record field label, along with the type of the record it belongs to if that is known.
+    Field {
+        name: String,
+        record: Option<RecordShape>,
+    },
+}
+
+/// The labels of a record type. Record types are structural, so this is what tells a field apart
+/// from a field with the same label in an unrelated record.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct RecordShape {
+    labels: Vec<String>,
+    /// Whether the record can have more fields than `labels`, like the argument of `\r -> r.x`.
+    is_open: bool,
+}
+
+impl RecordShape {
+    fn new(mut labels: Vec<String>, is_open: bool) -> Self {
+        labels.sort();
+        labels.dedup();
+
+        Self { labels, is_open }
+    }
+
+    /// Whether a record could have both this shape and `other`.
+    fn is_compatible(&self, other: &RecordShape) -> bool {
+        let has_all = |shape: &RecordShape, of: &RecordShape| {
+            of.labels.iter().all(|label| shape.labels.contains(label))
+        };
+
+        match (self.is_open, other.is_open) {
+            (false, false) => self.labels == other.labels,
+            (true, false) => has_all(other, self),
+            (false, true) => has_all(self, other),
+            (true, true) => true,
+        }
+    }
+}
+
+impl ReferenceTarget {
+    /// The module a rename of this target has to start from, if it belongs to one.
+    pub(crate) fn home_module(&self) -> Option<&str> {
+        match self {
+            ReferenceTarget::TopLevel { module, .. } | ReferenceTarget::Type { module, .. } => {
+                Some(module)
+            }
+            ReferenceTarget::Local { .. } | ReferenceTarget::Field { .. } => None,
+        }
+    }
+
+    fn is_type(&self) -> bool {
+        matches!(self, ReferenceTarget::Type { .. })
+    }
+
+    /// Checks that `new_name` is syntactically a valid name for this target.
+    pub(crate) fn check_new_name(&self, new_name: &str) -> Result<(), String> {
+        let mut chars = new_name.chars();
+        let first_ok = match chars.next() {
+            Some(c) if self.is_type() => c.is_ascii_uppercase(),
+            Some(c) => c.is_ascii_lowercase(),
+            None => false,
+        };
+
+        let is_keyword = roc_parse::keyword::KEYWORDS.contains(&new_name)
+            || [roc_parse::keyword::IMPLEMENTS, roc_parse::keyword::WHERE].contains(&new_name);
+
+        if first_ok && chars.all(|c| c.is_ascii_alphanumeric()) && !is_keyword {
+            Ok(())
+        } else if self.is_type() {
+            Err(format!(
+                "`{new_name}` is not a valid type name. Type names start with an uppercase letter and contain only letters and digits."
+            ))
+        } else {
+            Err(format!(
+                "`{new_name}` is not a valid name. Names start with a lowercase letter, contain only letters and digits, and cannot be keywords."
+            ))
+        }
+    }
+
+    /// The text that replaces a reference when renaming this target to `new_name`.
+    pub(crate) fn replacement(&self, reference: &Reference, new_name: &str) -> String {
+        match (&reference.punned, self) {
+            // `{ x }` is short for `{ x: x }`; only one of the two gets renamed.
+            (Some(label), ReferenceTarget::Field { .. }) => format!("{new_name}: {label}"),
+            (Some(label), _) => format!("{label}: {new_name}"),
+            (None, _) => new_name.to_string(),
+        }
+    }
+}
+
+/// A place a [ReferenceTarget] is mentioned in a document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Reference {
+    pub range: Range,
+    pub is_declaration: bool,
+    /// Set to the label of a punned record field like `{ x }`, which stands for both a field and
+    /// a value.
+    pub punned: Option<String>,
+}
+
+fn module_name(module: &AnalyzedModule) -> String {
+    module.module_id.to_ident_str(&module.interns).to_string()
+}
+
+fn is_type_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_uppercase())
+}
+
+/// Whether this module imports the module called `name`.
+pub(super) fn imports_module(module: &AnalyzedModule, name: &str) -> bool {
+    module
+        .imports
+        .keys()
+        .any(|id| id.to_ident_str(&module.interns).as_str() == name)
+}
+
+/// Whether `name` is already bound anywhere in this module, or imported into it unqualified.
+/// Since Roc does not allow shadowing, a rename to such a name would not compile.
+pub(super) fn name_in_scope(doc: &DocInfo, module: &AnalyzedModule, name: &str) -> bool {
+    let bound_here = module
+        .interns
+        .all_ident_ids
+        .get(&module.module_id)
+        .map_or(false, |ident_ids| ident_ids.get_id(name).is_some());
+
+    let arena = Bump::new();
+    let imported = Ast::parse(&arena, &doc.source).map_or(false, |ast| {
+        ast.name_occurrences().iter().any(|occurrence| {
+            matches!(occurrence.value, NameOccurrence::Imported { name: imported, .. } if imported == name)
+        })
+    });
+
+    bound_here || imported
+}
+
+/// Whether the name of `target` can be used unqualified in this module.
+pub(super) fn brings_into_scope(
+    doc: &DocInfo,
+    module: &AnalyzedModule,
+    target: &ReferenceTarget,
+) -> bool {
+    let name = match target {
+        ReferenceTarget::TopLevel { module: home, .. }
+        | ReferenceTarget::Type { module: home, .. }
+            if module_name(module) == *home =>
+        {
+            return true;
+        }
+        ReferenceTarget::TopLevel { ident: name, .. } | ReferenceTarget::Type { name, .. } => name,
+        ReferenceTarget::Local { url, .. } => return url == &doc.url,
+        ReferenceTarget::Field { .. } => return false,
+    };
+    let home = target.home_module().unwrap_or_default();
+
+    let arena = Bump::new();
+    Ast::parse(&arena, &doc.source).map_or(false, |ast| {
+        ast.name_occurrences().iter().any(|occurrence| {
+            matches!(occurrence.value, NameOccurrence::Imported { module, name: imported } if module == home && imported == name)
+        })
+    })
+}
+
+/// Finds what the name at `position` refers to, along with the range of the name.
+pub(super) fn target_at(
+    doc: &DocInfo,
+    module: &AnalyzedModule,
+    position: Position,
+) -> Option<(Range, ReferenceTarget)> {
+    let arena = Bump::new();
+    let ast = Ast::parse(&arena, &doc.source).ok()?;
+    let occurrences = ast.name_occurrences();
+
+    let roc_position = position.to_roc_position(&doc.line_info);
+    let own_module = module_name(module);
+
+    let found = occurrences
+        .iter()
+        .find(|occurrence| occurrence.region.contains_pos(roc_position));
+
+    let (region, target) = match found.map(|occurrence| (occurrence.region, occurrence.value)) {
+        Some((
+            region,
+            NameOccurrence::Type {
+                module: qualifier,
+                name,
+                ..
+            },
+        )) => {
+            let home = if qualifier.is_empty() {
+                resolve_unqualified_type(&occurrences, name, &own_module)?
+            } else {
+                qualifier.to_string()
+            };
+
+            let target = ReferenceTarget::Type {
+                module: home,
+                name: name.to_string(),
+            };
+            (region, target)
+        }
+        Some((
+            region,
+            NameOccurrence::Field {
+                name,
+                punned: false,
+            },
+        )) => {
+            let target = ReferenceTarget::Field {
+                name: name.to_string(),
+                record: field_shapes(module, &ast).remove(&region.start()),
+            };
+            (region, target)
+        }
+        Some((region, NameOccurrence::Exposed(name))) => (region, header_target(own_module, name)),
+        Some((region, NameOccurrence::Imported { module, name })) => {
+            (region, header_target(module.to_string(), name))
+        }
+        Some((region, NameOccurrence::Annotation { body })) => {
+            let symbol = symbol_at(module, body)?;
+            (region, value_target(doc, module, symbol))
+        }
+        Some((_, NameOccurrence::Field { punned: true, .. })) | None => {
+            let found = roc_can::traverse::find_closest_symbol_at(
+                roc_position,
+                &module.declarations,
+                &module.abilities,
+            )?;
+            let symbol = found.implementation_symbol();
+            let ident = symbol.as_str(&module.interns);
+
+            let references = roc_can::traverse::find_references(symbol, &module.declarations);
+            let region = references
+                .bindings
+                .iter()
+                .chain(references.lookups.iter())
+                .filter_map(|region| find_ident_in_region(&doc.source, *region, ident))
+                .find(|region| region.contains_pos(roc_position))?;
+
+            (region, value_target(doc, module, symbol))
+        }
+    };
+
+    Some((region.to_range(&doc.line_info), target))
+}
+
+fn symbol_at(module: &AnalyzedModule, region: Region) -> Option<Symbol> {
+    let found = roc_can::traverse::find_closest_symbol_at(
+        region.start(),
+        &module.declarations,
+        &module.abilities,
+    )?;
+
+    Some(found.implementation_symbol())
+}
+
+fn header_target(module: String, name: &str) -> ReferenceTarget {
+    if is_type_name(name) {
+        ReferenceTarget::Type {
+            module,
+            name: name.to_string(),
+        }
+    } else {
+        ReferenceTarget::TopLevel {
+            module,
+            ident: name.to_string(),
+        }
+    }
+}
+
+/// Finds which module an unqualified type name comes from. Types defined outside the project,
+/// like the builtins, resolve to nothing.
+fn resolve_unqualified_type(
+    occurrences: &[Loc<NameOccurrence<'_>>],
+    name: &str,
+    own_module: &str,
+) -> Option<String> {
+    occurrences
+        .iter()
+        .find_map(|occurrence| match occurrence.value {
+            NameOccurrence::Imported {
+                module,
+                name: imported,
+            } if imported == name => Some(module.to_string()),
+            NameOccurrence::Type {
+                module: "",
+                name: defined,
+                is_definition: true,
+            } if defined == name => Some(own_module.to_string()),
+            _ => None,
+        })
+}
+
+fn is_top_level(module: &AnalyzedModule, symbol: Symbol) -> bool {
+    module
+        .declarations
+        .symbols
+        .iter()
+        .any(|loc_symbol| loc_symbol.value == symbol)
+}
+
+fn value_target(doc: &DocInfo, module: &AnalyzedModule, symbol: Symbol) -> ReferenceTarget {
+    let ident = symbol.as_str(&module.interns).to_string();
+
+    if symbol.module_id() != module.module_id {
+        ReferenceTarget::TopLevel {
+            module: symbol.module_id().to_ident_str(&module.interns).to_string(),
+            ident,
+        }
+    } else if is_top_level(module, symbol) {
+        ReferenceTarget::TopLevel {
+            module: module_name(module),
+            ident,
+        }
+    } else {
+        ReferenceTarget::Local {
+            url: doc.url.clone(),
+            symbol,
+        }
+    }
+}
+
+/// Finds the symbol this module uses for the top-level value `ident` of `home`.
+fn resolve_top_level(module: &AnalyzedModule, home: &str, ident: &str) -> Option<Symbol> {
+    let is_wanted = |symbol: &Symbol| symbol.as_str(&module.interns) == ident;
+
+    if module_name(module) == home {
+        module
+            .declarations
+            .symbols
+            .iter()
+            .map(|loc_symbol| loc_symbol.value)
+            .find(|symbol| symbol.module_id() == module.module_id && is_wanted(symbol))
+    } else {
+        module
+            .imports
+            .iter()
+            .find(|(id, _)| id.to_ident_str(&module.interns).as_str() == home)?
+            .1
+            .iter()
+            .map(|(symbol, _)| *symbol)
+            .find(is_wanted)
+    }
+}
+
+/// Finds every reference to `target` in this document.
+pub(super) fn find_references(
+    doc: &DocInfo,
+    module: &AnalyzedModule,
+    target: &ReferenceTarget,
+) -> Vec<Reference> {
+    let arena = Bump::new();
+    let occurrences = Ast::parse(&arena, &doc.source)
+        .map(|ast| ast.name_occurrences())
+        .unwrap_or_default();
+
+    let own_module = module_name(module);
+
+    let mut found: Vec<(Region, bool)> = Vec::new();
+
+    match target {
+        ReferenceTarget::TopLevel {
+            module: home,
+            ident,
+        } => {
+            if let Some(symbol) = resolve_top_level(module, home, ident) {
+                found.extend(symbol_references(doc, module, &occurrences, symbol));
+            }
+
+            for occurrence in occurrences.iter() {
+                let in_header = match occurrence.value {
+                    NameOccurrence::Exposed(name) => own_module == *home && name == ident,
+                    NameOccurrence::Imported { module, name } => module == home && name == ident,
+                    _ => false,
+                };
+
+                if in_header {
+                    found.push((occurrence.region, false));
+                }
+            }
+        }
+        ReferenceTarget::Local { url, symbol } => {
+            if url == &doc.url {
+                found.extend(symbol_references(doc, module, &occurrences, *symbol));
+            }
+        }
+        ReferenceTarget::Type { module: home, name } => {
+            let is_home = own_module == *home;
+            let imported_unqualified = occurrences.iter().any(|occurrence| {
+                matches!(occurrence.value, NameOccurrence::Imported { module, name: imported } if module == home && imported == name)
+            });
+
+            for occurrence in occurrences.iter() {
+                let is_reference = match occurrence.value {
+                    NameOccurrence::Type {
+                        module: qualifier,
+                        name: found_name,
+                        is_definition,
+                    } if found_name == name => {
+                        if qualifier.is_empty() {
+                            is_home || (imported_unqualified && !is_definition)
+                        } else {
+                            qualifier == home
+                        }
+                    }
+                    NameOccurrence::Exposed(exposed) => is_home && exposed == name,
+                    NameOccurrence::Imported {
+                        module,
+                        name: imported,
+                    } => module == home && imported == name,
+                    _ => false,
+                };
+
+                if is_reference {
+                    let is_definition = matches!(
+                        occurrence.value,
+                        NameOccurrence::Type {
+                            is_definition: true,
+                            ..
+                        }
+                    );
+                    found.push((occurrence.region, is_definition));
+                }
+            }
+        }
+        ReferenceTarget::Field {
+            name: field,
+            record,
+        } => {
+            for (region, shape) in field_occurrences(doc, module, field) {
+                let could_match = match (record, shape) {
+                    (Some(record), Some(shape)) => record.is_compatible(&shape),
+                    _ => true,
+                };
+
+                if could_match {
+                    found.push((region, false));
+                }
+            }
+        }
+    }
+
+    found.sort_by_key(|(region, _)| region.start());
+    found.dedup_by_key(|(region, _)| region.start());
+
+    found
+        .into_iter()
+        .map(|(region, is_declaration)| Reference {
+            range: region.to_range(&doc.line_info),
+            is_declaration,
+            punned: punned_label(&occurrences, region),
+        })
+        .collect()
+}
+
+/// Whether a rename of the field `target` could miss some of its references, or rename a field of
+/// an unrelated record, because the type of a record it is used with is not known precisely.
+pub(super) fn field_rename_is_ambiguous(
+    doc: &DocInfo,
+    module: &AnalyzedModule,
+    target: &ReferenceTarget,
+) -> bool {
+    match target {
+        ReferenceTarget::Field {
+            name,
+            record: Some(record),
+        } if !record.is_open => {
+            field_occurrences(doc, module, name)
+                .into_iter()
+                .any(|(_, shape)| match shape {
+                    Some(shape) => shape != *record && shape.is_compatible(record),
+                    None => true,
+                })
+        }
+        ReferenceTarget::Field { .. } => true,
+        _ => false,
+    }
+}
+
+/// The regions of the record field labels called `name` in this document, each with the type of
+/// its record if that is known.
+fn field_occurrences(
+    doc: &DocInfo,
+    module: &AnalyzedModule,
+    name: &str,
+) -> Vec<(Region, Option<RecordShape>)> {
+    let arena = Bump::new();
+    let ast = match Ast::parse(&arena, &doc.source) {
+        Ok(ast) => ast,
+        Err(_) => return Vec::new(),
+    };
+    let mut shapes = field_shapes(module, &ast);
+
+    ast.name_occurrences()
+        .into_iter()
+        .filter(|occurrence| {
+            matches!(occurrence.value, NameOccurrence::Field { name: found, .. } if found == name)
+        })
+        .map(|occurrence| (occurrence.region, shapes.remove(&occurrence.region.start())))
+        .collect()
+}
+
+/// Finds the record type of every field label in this module whose record type is known, by the
+/// start of the label.
+fn field_shapes(
+    module: &AnalyzedModule,
+    ast: &Ast<'_>,
+) -> HashMap<roc_region::all::Position, RecordShape> {
+    let mut collector = FieldShapeCollector {
+        subs: &module.subs,
+        shapes: HashMap::new(),
+    };
+    collector.visit_decls(&module.declarations);
+
+    // Annotations are not part of the canonical AST, but they spell out their record types.
+    for record in ast.record_annotations() {
+        let shape = RecordShape::new(
+            record
+                .labels
+                .iter()
+                .map(|label| label.value.to_string())
+                .collect(),
+            record.is_open,
+        );
+
+        for label in record.labels.iter() {
+            collector.shapes.insert(label.region.start(), shape.clone());
+        }
+    }
+
+    collector.shapes
+}
+
+/// The shape of the record type `var`, if it is a record.
+fn record_shape(subs: &Subs, mut var: Variable) -> Option<RecordShape> {
+    let mut labels = Vec::new();
+
+    loop {
+        if var == Variable::EMPTY_RECORD {
+            return Some(RecordShape::new(labels, false));
+        }
+
+        match subs.get_content_without_compacting(var) {
+            Content::Structure(FlatType::Record(fields, ext)) => {
+                labels.extend(
+                    fields
+                        .iter_all()
+                        .map(|(label, _, _)| subs[label].as_str().to_string()),
+                );
+                var = *ext;
+            }
+            Content::Structure(FlatType::EmptyRecord) => {
+                return Some(RecordShape::new(labels, false));
+            }
+            Content::Alias(_, _, actual, _) => var = *actual,
+            Content::FlexVar(_)
+            | Content::RigidVar(_)
+            | Content::FlexAbleVar(..)
+            | Content::RigidAbleVar(..) => return Some(RecordShape::new(labels, true)),
+            _ => return None,
+        }
+    }
+}
+
+struct FieldShapeCollector<'a> {
+    subs: &'a Subs,
+    shapes: HashMap<roc_region::all::Position, RecordShape>,
+}
+
+impl FieldShapeCollector<'_> {
+    fn add(&mut self, label_start: roc_region::all::Position, record_var: Variable) {
+        if let Some(shape) = record_shape(self.subs, record_var) {
+            self.shapes.insert(label_start, shape);
+        }
+    }
+}
+
+impl Visitor for FieldShapeCollector<'_> {
+    fn visit_expr(&mut self, expr: &Expr, region: Region, var: Variable) {
+        match expr {
+            Expr::Record { record_var, fields }
+            | Expr::RecordUpdate {
+                record_var,
+                updates: fields,
+                ..
+            } => {
+                for (_, field) in fields.iter() {
+                    self.add(field.region.start(), *record_var);
+                }
+            }
+            Expr::RecordAccess {
+                record_var, field, ..
+            } => self.add(region.end().sub(field.as_str().len() as u32), *record_var),
+            Expr::RecordAccessor(StructAccessorData {
+                record_var,
+                field: IndexOrField::Field(_),
+                ..
+            }) => self.add(region.start().bump_column(1), *record_var),
+            _ => {}
+        }
+
+        walk_expr(self, expr, var);
+    }
+
+    fn visit_pattern(&mut self, pattern: &Pattern, region: Region, _opt_var: Option<Variable>) {
+        if let Pattern::RecordDestructure {
+            whole_var,
+            destructs,
+            ..
+        } = pattern
+        {
+            for destruct in destructs.iter() {
+                self.add(destruct.region.start(), *whole_var);
+            }
+        }
+
+        if self.should_visit(region) {
+            walk_pattern(self, pattern);
+        }
+    }
+}
+
+fn punned_label(occurrences: &[Loc<NameOccurrence<'_>>], region: Region) -> Option<String> {
+    occurrences
+        .iter()
+        .find_map(|occurrence| match occurrence.value {
+            NameOccurrence::Field { name, punned: true } if occurrence.region == region => {
+                Some(name.to_string())
+            }
+            _ => None,
+        })
+}
+
+/// The regions of the name `symbol` wherever it is bound or looked up, including the annotations
+/// of the defs that bind it. Declarations are marked with `true`.
+fn symbol_references(
+    doc: &DocInfo,
+    module: &AnalyzedModule,
+    occurrences: &[Loc<NameOccurrence<'_>>],
+    symbol: Symbol,
+) -> Vec<(Region, bool)> {
+    let ident = symbol.as_str(&module.interns);
+    let references = roc_can::traverse::find_references(symbol, &module.declarations);
+
+    let bindings = references
+        .bindings
+        .iter()
+        .filter_map(|region| find_ident_in_region(&doc.source, *region, ident))
+        .collect::<Vec<_>>();
+
+    let annotations = occurrences
+        .iter()
+        .filter_map(|occurrence| match occurrence.value {
+            NameOccurrence::Annotation { body }
+                if bindings
+                    .iter()
+                    .any(|binding| binding.start() == body.start()) =>
+            {
+                Some(occurrence.region)
+            }
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    let lookups = references
+        .lookups
+        .iter()
+        .filter_map(|region| find_ident_in_region(&doc.source, *region, ident));
+
+    (bindings.into_iter().chain(annotations).map(|r| (r, true)))
+        .chain(lookups.map(|r| (r, false)))
+        .collect()
+}
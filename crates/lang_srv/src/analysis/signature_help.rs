@@ -0,0 +1,240 @@
+use roc_can::{
+    expr::Expr,
+    traverse::{walk_expr, Visitor},
+};
+use roc_module::{
+    called_via::{BinOp, CalledVia},
+    symbol::Symbol,
+};
+use roc_region::all::{Position, Region};
+use roc_types::subs::{Content, Variable};
+use tower_lsp::lsp_types::{
+    Documentation, ParameterInformation, ParameterLabel, SignatureHelp, SignatureInformation,
+};
+
+use super::{utils::format_var_type, AnalyzedModule, DocInfo};
+
+/// The signature of the innermost call around `position`, with the argument at `position` active.
+pub(super) fn signature_help(
+    doc: &DocInfo,
+    module: &AnalyzedModule,
+    position: Position,
+) -> Option<SignatureHelp> {
+    let AnalyzedModule {
+        declarations,
+        module_id,
+        interns,
+        modules_info,
+        ..
+    } = module;
+
+    let mut visitor = CallVisitor {
+        source: &doc.source,
+        position,
+        call: None,
+    };
+    visitor.visit_decls(declarations);
+    let call = visitor.call?;
+
+    let type_str = function_type(module, &call)?;
+
+    let (prefix, documentation) = match call.symbol {
+        Some(symbol) => {
+            let docs = modules_info
+                .docs
+                .get(&symbol.module_id())
+                .and_then(|docs| docs.get_doc_for_symbol(&symbol));
+
+            let name = symbol.fully_qualified(interns, *module_id);
+            (format!("{} : ", name.as_str()), docs)
+        }
+        None => (String::new(), None),
+    };
+
+    let params = parameter_offsets(&type_str);
+    if params.is_empty() {
+        return None;
+    }
+
+    let label = format!("{prefix}{type_str}");
+    let prefix_len = utf16_len(&prefix);
+    let parameters = params
+        .iter()
+        .map(|&(start, end)| ParameterInformation {
+            label: ParameterLabel::LabelOffsets([
+                prefix_len + utf16_len(&type_str[..start]),
+                prefix_len + utf16_len(&type_str[..end]),
+            ]),
+            documentation: None,
+        })
+        .collect();
+
+    let active_parameter = Some(call.active_arg.min(params.len() - 1) as u32);
+
+    Some(SignatureHelp {
+        signatures: vec![SignatureInformation {
+            label,
+            documentation: documentation.map(Documentation::String),
+            parameters: Some(parameters),
+            active_parameter,
+        }],
+        active_signature: Some(0),
+        active_parameter,
+    })
+}
+
+/// The printed type of the function in `call`.
+fn function_type(module: &AnalyzedModule, call: &FoundCall) -> Option<String> {
+    let AnalyzedModule {
+        declarations,
+        subs,
+        module_id,
+        interns,
+        modules_info,
+        ..
+    } = module;
+
+    let mut subs = subs.clone();
+    if !matches!(
+        subs.get_content_without_compacting(call.fn_var),
+        Content::Error
+    ) {
+        return Some(format_var_type(call.fn_var, &mut subs, module_id, interns));
+    }
+
+    // A call that does not type check, like one that is still missing arguments, leaves an error
+    // in place of the function's type at the call, so fall back to the type it was defined with.
+    let symbol = call.symbol?;
+    let home = symbol.module_id();
+
+    if home == *module_id {
+        let index = declarations
+            .symbols
+            .iter()
+            .position(|loc_symbol| loc_symbol.value == symbol)?;
+
+        return Some(format_var_type(
+            declarations.variables[index],
+            &mut subs,
+            module_id,
+            interns,
+        ));
+    }
+
+    let (_, var) = modules_info
+        .exposed
+        .get(&home)?
+        .iter()
+        .find(|(exposed, _)| *exposed == symbol)?;
+
+    modules_info.with_subs(&home, |subs| format_var_type(*var, subs, &home, interns))
+}
+
+fn utf16_len(s: &str) -> u32 {
+    s.encode_utf16().count() as u32
+}
+
+/// The byte ranges of the argument types in a printed function type, like `a` and `List b` in
+/// `a, List b -> c`. Nested function types are only split at their own top level.
+fn parameter_offsets(type_str: &str) -> Vec<(usize, usize)> {
+    let mut params = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+
+    for (index, c) in type_str.char_indices() {
+        match c {
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth -= 1,
+            ',' if depth == 0 => {
+                params.push((start, index));
+                start = index + ", ".len();
+            }
+            '-' if depth == 0 && type_str[index..].starts_with("->") => {
+                params.push((start, index.saturating_sub(1).max(start)));
+                return params;
+            }
+            _ => {}
+        }
+    }
+
+    // Not a function type after all
+    vec![]
+}
+
+struct FoundCall {
+    fn_var: Variable,
+    /// The function being called, if it is called by name.
+    symbol: Option<Symbol>,
+    active_arg: usize,
+}
+
+/// Finds the innermost call whose arguments `position` is in, or which `position` directly follows
+/// on the same line, so that help is still given while the next argument is being typed.
+struct CallVisitor<'a> {
+    source: &'a str,
+    position: Position,
+    call: Option<FoundCall>,
+}
+
+impl CallVisitor<'_> {
+    fn reaches_position(&self, region: Region) -> bool {
+        if region.start() > self.position {
+            return false;
+        }
+
+        let end = region.end().offset as usize;
+        let position = self.position.offset as usize;
+
+        end >= position
+            || self.source.get(end..position).map_or(false, |between| {
+                between.chars().all(|c| c == ' ' || c == '\t')
+            })
+    }
+}
+
+impl Visitor for CallVisitor<'_> {
+    fn should_visit(&mut self, region: Region) -> bool {
+        self.reaches_position(region)
+    }
+
+    fn visit_expr(&mut self, expr: &Expr, region: Region, var: Variable) {
+        if !self.reaches_position(region) {
+            return;
+        }
+
+        if let Expr::Call(boxed, args, called_via) = expr {
+            let (fn_var, fn_expr, _, _) = &**boxed;
+
+            let is_written_call = matches!(
+                called_via,
+                CalledVia::Space | CalledVia::BinOp(BinOp::Pizza)
+            );
+
+            if is_written_call && fn_expr.region.end() < self.position {
+                // Arguments are in the order the function takes them, which for a pipe is not
+                // the order they are written in.
+                let active_arg = args
+                    .iter()
+                    .position(|(_, arg)| arg.region.contains_pos(self.position))
+                    .unwrap_or_else(|| {
+                        args.iter()
+                            .filter(|(_, arg)| arg.region.end() < self.position)
+                            .count()
+                    });
+
+                let symbol = match fn_expr.value {
+                    Expr::Var(symbol, _) | Expr::AbilityMember(symbol, _, _) => Some(symbol),
+                    _ => None,
+                };
+
+                self.call = Some(FoundCall {
+                    fn_var: *fn_var,
+                    symbol,
+                    active_arg,
+                });
+            }
+        }
+
+        walk_expr(self, expr, var);
+    }
+}
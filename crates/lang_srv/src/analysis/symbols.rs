@@ -0,0 +1,156 @@
+use roc_region::all::LineInfo;
+use tower_lsp::lsp_types::{
+    DocumentSymbol, FoldingRange, FoldingRangeKind, Location, SymbolInformation, SymbolKind, Url,
+};
+
+use crate::convert::ToRange;
+
+use super::parse_ast::{OutlineItem, OutlineKind};
+
+fn symbol_kind(kind: OutlineKind) -> SymbolKind {
+    match kind {
+        OutlineKind::Module => SymbolKind::MODULE,
+        OutlineKind::Value => SymbolKind::CONSTANT,
+        OutlineKind::Function => SymbolKind::FUNCTION,
+        OutlineKind::Alias => SymbolKind::TYPE_PARAMETER,
+        OutlineKind::Opaque => SymbolKind::STRUCT,
+        OutlineKind::Ability => SymbolKind::INTERFACE,
+        OutlineKind::AbilityMember => SymbolKind::METHOD,
+        OutlineKind::When => SymbolKind::ENUM,
+        OutlineKind::WhenBranch => SymbolKind::ENUM_MEMBER,
+    }
+}
+
+// `deprecated` has been superseded by `tags`, but still has to be given.
+#[allow(deprecated)]
+pub(super) fn document_symbol(item: OutlineItem, line_info: &LineInfo) -> DocumentSymbol {
+    let children = item
+        .children
+        .into_iter()
+        .map(|child| document_symbol(child, line_info))
+        .collect::<Vec<_>>();
+
+    DocumentSymbol {
+        name: item.name,
+        detail: item.detail,
+        kind: symbol_kind(item.kind),
+        tags: None,
+        deprecated: None,
+        range: item.region.to_range(line_info),
+        selection_range: item.name_region.to_range(line_info),
+        children: (!children.is_empty()).then_some(children),
+    }
+}
+
+/// Whether the characters of `query` appear in order in `name`, ignoring case.
+fn matches_query(name: &str, query: &str) -> bool {
+    let mut name = name.chars().flat_map(char::to_lowercase);
+
+    query
+        .chars()
+        .flat_map(char::to_lowercase)
+        .all(|q| name.any(|c| c == q))
+}
+
+/// The module-level items of an outline that match `query`, as found by `workspace/symbol`.
+#[allow(deprecated)]
+pub(super) fn workspace_symbols(
+    items: Vec<OutlineItem>,
+    url: &Url,
+    line_info: &LineInfo,
+    query: &str,
+) -> Vec<SymbolInformation> {
+    let module_name = items
+        .iter()
+        .find(|item| item.kind == OutlineKind::Module)
+        .map(|item| item.name.clone());
+
+    let mut symbols = Vec::new();
+    let mut push = |item: &OutlineItem, container_name: Option<&String>| {
+        if matches_query(&item.name, query) {
+            symbols.push(SymbolInformation {
+                name: item.name.clone(),
+                kind: symbol_kind(item.kind),
+                tags: None,
+                deprecated: None,
+                location: Location::new(url.clone(), item.name_region.to_range(line_info)),
+                container_name: container_name.cloned(),
+            });
+        }
+    };
+
+    for item in items.iter() {
+        match item.kind {
+            OutlineKind::Module => push(item, None),
+            OutlineKind::When | OutlineKind::WhenBranch => {}
+            _ => {
+                push(item, module_name.as_ref());
+
+                // Ability members can be called from anywhere the ability is visible
+                for member in item.children.iter() {
+                    if member.kind == OutlineKind::AbilityMember {
+                        push(member, Some(&item.name));
+                    }
+                }
+            }
+        }
+    }
+
+    symbols
+}
+
+/// Folds every item of an outline that spans more than one line, and blocks of comment lines.
+pub(super) fn folding_ranges(
+    items: &[OutlineItem],
+    source: &str,
+    line_info: &LineInfo,
+) -> Vec<FoldingRange> {
+    let mut ranges = Vec::new();
+    outline_folding_ranges(items, line_info, &mut ranges);
+
+    let mut comment_start = None;
+    for (line, text) in source.lines().chain([""]).enumerate() {
+        let line = line as u32;
+        let is_comment = text.trim_start().starts_with('#');
+
+        match (is_comment, comment_start) {
+            (true, None) => comment_start = Some(line),
+            (false, Some(start)) => {
+                if line - 1 > start {
+                    ranges.push(fold(start, line - 1, Some(FoldingRangeKind::Comment)));
+                }
+                comment_start = None;
+            }
+            _ => {}
+        }
+    }
+
+    ranges.sort_by_key(|range| (range.start_line, range.end_line));
+    ranges
+}
+
+fn fold(start_line: u32, end_line: u32, kind: Option<FoldingRangeKind>) -> FoldingRange {
+    FoldingRange {
+        start_line,
+        end_line,
+        kind,
+        ..FoldingRange::default()
+    }
+}
+
+fn outline_folding_ranges(
+    items: &[OutlineItem],
+    line_info: &LineInfo,
+    ranges: &mut Vec<FoldingRange>,
+) {
+    for item in items {
+        let range = item.region.to_range(line_info);
+
+        if range.end.line > range.start.line {
+            let kind = (item.kind == OutlineKind::Module).then_some(FoldingRangeKind::Imports);
+            ranges.push(fold(range.start.line, range.end.line, kind));
+        }
+
+        outline_folding_ranges(&item.children, line_info, ranges);
+    }
+}
@@ -42,7 +42,7 @@ impl ToRegion for Range {
             },
             end: LineColumn {
                 line: self.end.line,
-                column: self.end.line,
+                column: self.end.character,
             },
         };
 
@@ -77,7 +77,7 @@ pub(crate) mod diag {
 
     use roc_problem::Severity;
     use roc_reporting::report::RocDocAllocator;
-    use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity, Position, Range};
+    use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity, NumberOrString, Position, Range};
 
     use super::ToRange;
 
@@ -190,6 +190,7 @@ pub(crate) mod diag {
             );
 
             let severity = report.severity.into_lsp_severity();
+            let code = NumberOrString::String(report.code.code.to_string());
 
             let mut msg = String::new();
             report.render_ci(&mut msg, fmt.alloc);
@@ -197,7 +198,7 @@ pub(crate) mod diag {
             Some(Diagnostic {
                 range,
                 severity: Some(severity),
-                code: None,
+                code: Some(code),
                 code_description: None,
                 source: None,
                 message: msg,
@@ -225,6 +226,7 @@ pub(crate) mod diag {
             )?;
 
             let severity = report.severity.into_lsp_severity();
+            let code = NumberOrString::String(report.code.code.to_string());
 
             let mut msg = String::new();
             report.render_ci(&mut msg, fmt.alloc);
@@ -232,7 +234,7 @@ pub(crate) mod diag {
             Some(Diagnostic {
                 range,
                 severity: Some(severity),
-                code: None,
+                code: Some(code),
                 code_description: None,
                 source: None,
                 message: msg,
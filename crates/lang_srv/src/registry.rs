@@ -9,11 +9,13 @@ use std::{
 use tokio::sync::{Mutex, MutexGuard};
 
 use tower_lsp::lsp_types::{
-    CompletionResponse, Diagnostic, GotoDefinitionResponse, Hover, Position, SemanticTokensResult,
-    TextEdit, Url,
+    CodeActionResponse, CompletionResponse, Diagnostic, DocumentSymbolResponse, FoldingRange,
+    GotoDefinitionResponse, Hover, InlayHint, Location, Position, PrepareRenameResponse, Range,
+    SemanticTokensResult, SignatureHelp, SymbolInformation, TextDocumentContentChangeEvent,
+    TextEdit, Url, WorkspaceEdit,
 };
 
-use crate::analysis::{AnalyzedDocument, DocInfo};
+use crate::analysis::{AnalyzedDocument, DocInfo, Reference, ReferenceTarget, ReusableModules};
 
 #[derive(Debug)]
 pub(crate) struct DocumentPair {
@@ -51,6 +53,8 @@ impl Default for RegistryConfig {
 #[derive(Debug, Default)]
 pub(crate) struct Registry {
     documents: Mutex<HashMap<Url, DocumentPair>>,
+    /// What the next analysis of each document can reuse from its last one.
+    reusable_modules: Mutex<HashMap<Url, ReusableModules>>,
     config: RegistryConfig,
 }
 
@@ -58,10 +62,18 @@ impl Registry {
     pub(crate) fn new(config: RegistryConfig) -> Self {
         Self {
             documents: Default::default(),
+            reusable_modules: Default::default(),
             config,
         }
     }
 
+    pub async fn reusable_modules(&self, url: &Url) -> ReusableModules {
+        (self.reusable_modules.lock().await)
+            .get(url)
+            .cloned()
+            .unwrap_or_default()
+    }
+
     pub async fn get_latest_version(&self, url: &Url) -> Option<i32> {
         self.documents.lock().await.get(url).map(|x| x.info.version)
     }
@@ -74,7 +86,9 @@ impl Registry {
         if &document.doc_info.url == updating_url {
             //Write the newly analysed document into the oncelock that any request requiring the latest document will be waiting on
             if let Some(a) = documents.get_mut(updating_url) {
-                a.latest_document.set(document.clone()).unwrap()
+                if a.latest_document.set(document.clone()).is_err() {
+                    debug!("The latest document was already set by an unchanged edit");
+                }
             }
         }
 
@@ -106,7 +120,14 @@ impl Registry {
         }
     }
 
-    pub async fn apply_changes<'a>(&self, analysed_docs: Vec<AnalyzedDocument>, updating_url: Url) {
+    /// Stores every document from one analysis, so that the documents of modules it analysed
+    /// together all share the same symbols and types.
+    pub async fn apply_changes<'a>(
+        &self,
+        analysed_docs: Vec<AnalyzedDocument>,
+        reusable_modules: Option<ReusableModules>,
+        updating_url: Url,
+    ) {
         let mut documents = self.documents.lock().await;
         debug!(
             "Finished doc analysis for doc: {}",
@@ -114,31 +135,95 @@ impl Registry {
         );
 
         for document in analysed_docs {
+            if document.types_reused() {
+                trace!("Reused the types of {}", document.url());
+            }
+
             let document = Arc::new(document);
             Registry::update_document(&mut documents, document, &updating_url);
         }
+
+        //A failed load has nothing to reuse, so we keep what the last one had for the next
+        if let Some(reusable_modules) = reusable_modules {
+            (self.reusable_modules.lock().await).insert(updating_url, reusable_modules);
+        }
     }
 
-    pub async fn apply_doc_info_changes(&self, url: Url, info: DocInfo) {
+    /// Records the latest contents of a document, returning whether they need to be analysed.
+    pub async fn apply_doc_info_changes(&self, url: Url, info: DocInfo) -> bool {
         let mut documents_lock = self.documents.lock().await;
-        let doc = documents_lock.get_mut(&url);
-        match doc {
-            Some(a) => {
+        match documents_lock.get_mut(&url) {
+            Some(pair) => Registry::set_doc_info(pair, info),
+            None => {
                 debug!(
-                    "Set the docInfo for {:?} to version:{:?}",
+                    "Set the docInfo for new document {:?} to version:{:?}",
                     url.as_str(),
                     info.version
                 );
-                *a = DocumentPair {
+                let unanalysed = Arc::new(AnalyzedDocument::unanalysed(info.clone()));
+                let pair = DocumentPair {
                     info,
-                    last_good_document: a.last_good_document.clone(),
                     latest_document: OnceLock::new(),
+                    last_good_document: unanalysed,
                 };
+                documents_lock.insert(url, pair);
+                true
             }
-            None => debug!("So existing docinfo for {:?} ", url.as_str()),
         }
     }
 
+    /// Applies the changes of a `textDocument/didChange` notification to the latest contents of a
+    /// document. Returns the new contents and whether they need to be analysed, or `None` if the
+    /// document hasn't been opened or `version` is not newer than the contents we have.
+    pub async fn apply_text_changes(
+        &self,
+        url: &Url,
+        changes: Vec<TextDocumentContentChangeEvent>,
+        version: i32,
+    ) -> Option<(DocInfo, bool)> {
+        let mut documents_lock = self.documents.lock().await;
+        let pair = documents_lock.get_mut(url)?;
+
+        if pair.info.version >= version {
+            debug!(
+                "Ignoring changes to {:?} at version:{:?}, already at version:{:?}",
+                url.as_str(),
+                version,
+                pair.info.version
+            );
+            return None;
+        }
+
+        let info = pair.info.apply_changes(changes, version);
+        let needs_analysis = Registry::set_doc_info(pair, info.clone());
+        Some((info, needs_analysis))
+    }
+
+    /// The latest analysis still holds if the source is the same as what it analysed, like after
+    /// an edit is undone, so it is kept instead of analysing the document again.
+    fn set_doc_info(pair: &mut DocumentPair, info: DocInfo) -> bool {
+        debug!(
+            "Set the docInfo for {:?} to version:{:?}",
+            info.url.as_str(),
+            info.version
+        );
+
+        let still_latest = pair
+            .latest_document
+            .get()
+            .filter(|latest| latest.doc_info.source == info.source)
+            .cloned();
+        let needs_analysis = still_latest.is_none();
+
+        *pair = DocumentPair {
+            info,
+            last_good_document: pair.last_good_document.clone(),
+            latest_document: still_latest.map_or_else(OnceLock::new, OnceLock::from),
+        };
+
+        needs_analysis
+    }
+
     async fn document_info_by_url(&self, url: &Url) -> Option<DocInfo> {
         self.documents.lock().await.get(url).map(|a| a.info.clone())
     }
@@ -163,6 +248,11 @@ impl Registry {
         .ok()
     }
 
+    #[cfg(test)]
+    pub(crate) async fn types_reused(&self, url: &Url) -> Option<bool> {
+        Some(self.latest_document_by_url(url).await?.types_reused())
+    }
+
     pub async fn diagnostics(&self, url: &Url) -> Vec<Diagnostic> {
         let Some(document) = self.latest_document_by_url(url).await else {
             return vec![];
@@ -174,6 +264,22 @@ impl Registry {
         self.latest_document_by_url(url).await?.hover(position)
     }
 
+    pub async fn signature_help(&self, url: &Url, position: Position) -> Option<SignatureHelp> {
+        self.latest_document_by_url(url)
+            .await?
+            .signature_help(position)
+    }
+
+    pub async fn inlay_hints(&self, url: &Url, range: Range) -> Option<Vec<InlayHint>> {
+        let hints = self.latest_document_by_url(url).await?.inlay_hints(range);
+        Some(hints)
+    }
+
+    pub async fn code_actions(&self, url: &Url, range: Range) -> Option<CodeActionResponse> {
+        let actions = self.latest_document_by_url(url).await?.code_actions(range);
+        Some(actions)
+    }
+
     pub async fn goto_definition(
         &self,
         url: &Url,
@@ -182,8 +288,14 @@ impl Registry {
         let document = self.latest_document_by_url(url).await?;
         let symbol = document.symbol_at(position)?;
         let def_document_url = document.module_url(symbol.module_id())?;
+        if &def_document_url == url {
+            return document.definition(symbol);
+        }
+
+        //The other document may come from a different analysis, which numbers its symbols differently
+        let name = document.symbol_name(symbol)?;
         let def_document = self.latest_document_by_url(&def_document_url).await?;
-        def_document.definition(symbol)
+        def_document.definition_by_name(name)
     }
 
     pub async fn formatting(&self, url: &Url) -> Option<Vec<TextEdit>> {
@@ -195,6 +307,30 @@ impl Registry {
         let document = self.document_info_by_url(url).await?;
         document.semantic_tokens()
     }
+    pub async fn document_symbols(&self, url: &Url) -> Option<DocumentSymbolResponse> {
+        let document = self.document_info_by_url(url).await?;
+        document.document_symbols()
+    }
+
+    pub async fn folding_ranges(&self, url: &Url) -> Option<Vec<FoldingRange>> {
+        let document = self.document_info_by_url(url).await?;
+        document.folding_ranges()
+    }
+
+    pub async fn workspace_symbols(&self, query: &str) -> Vec<SymbolInformation> {
+        //Builtins are analysed from sources embedded in the compiler, so there is no file to go to
+        let infos: Vec<_> = (self.documents.lock().await)
+            .values()
+            .filter(|pair| !pair.last_good_document.is_builtin())
+            .map(|pair| pair.info.clone())
+            .collect();
+
+        infos
+            .iter()
+            .flat_map(|info| info.workspace_symbols(query))
+            .collect()
+    }
+
     pub async fn completion_items(
         &self,
         url: &Url,
@@ -216,4 +352,185 @@ impl Registry {
 
         Some(CompletionResponse::Array(completions))
     }
+
+    ///Gets the most recent document for every url that type checked, using `current` in place of the registry's copy of its own url.
+    async fn type_checked_documents(
+        &self,
+        current: &Arc<AnalyzedDocument>,
+    ) -> Vec<Arc<AnalyzedDocument>> {
+        let documents = self.documents.lock().await;
+        let mut type_checked: Vec<_> = documents
+            .iter()
+            .filter(|(url, _)| *url != current.url())
+            .map(|(_, pair)| match pair.latest_document.get() {
+                Some(latest) if latest.type_checked() => latest.clone(),
+                _ => pair.last_good_document.clone(),
+            })
+            .collect();
+        type_checked.push(current.clone());
+        type_checked
+    }
+
+    ///Finds the references to `target` in every document that could mention it.
+    async fn find_references(
+        &self,
+        current: &Arc<AnalyzedDocument>,
+        target: &ReferenceTarget,
+    ) -> Vec<(Arc<AnalyzedDocument>, Vec<Reference>)> {
+        let current_module = current.module_name();
+
+        self.type_checked_documents(current)
+            .await
+            .into_iter()
+            .filter(|document| match target {
+                ReferenceTarget::TopLevel { module, .. } | ReferenceTarget::Type { module, .. } => {
+                    document.module_name().as_ref() == Some(module)
+                        || document.imports_module(module)
+                }
+                ReferenceTarget::Local { url, .. } => document.url() == url,
+                // Record types are structural, so a record can only end up in the modules that can
+                // see values from the current one.
+                ReferenceTarget::Field { .. } => {
+                    document.url() == current.url()
+                        || current_module
+                            .as_ref()
+                            .map_or(false, |name| document.imports_module(name))
+                }
+            })
+            .map(|document| {
+                let references = document.references(target);
+                (document, references)
+            })
+            .filter(|(_, references)| !references.is_empty())
+            .collect()
+    }
+
+    pub async fn references(
+        &self,
+        url: &Url,
+        position: Position,
+        include_declaration: bool,
+    ) -> Option<Vec<Location>> {
+        let document = self.latest_document_by_url(url).await?;
+        let (_, target) = document.reference_target(position)?;
+
+        let locations = self
+            .find_references(&document, &target)
+            .await
+            .into_iter()
+            .flat_map(|(document, references)| {
+                references
+                    .into_iter()
+                    .filter(|reference| include_declaration || !reference.is_declaration)
+                    .map(move |reference| Location::new(document.url().clone(), reference.range))
+            })
+            .collect();
+
+        Some(locations)
+    }
+
+    ///Checks that the definition of `target` is part of the project, rather than a builtin or package.
+    async fn check_renameable(
+        &self,
+        current: &Arc<AnalyzedDocument>,
+        target: &ReferenceTarget,
+    ) -> Result<(), String> {
+        let Some(home) = target.home_module() else {
+            return Ok(());
+        };
+
+        let defined_here = self
+            .type_checked_documents(current)
+            .await
+            .iter()
+            .any(|document| document.module_name().as_deref() == Some(home));
+
+        if defined_here {
+            Ok(())
+        } else {
+            Err(format!(
+                "This is defined in `{home}`, which is not part of this project, so it can't be renamed."
+            ))
+        }
+    }
+
+    pub async fn prepare_rename(
+        &self,
+        url: &Url,
+        position: Position,
+    ) -> Result<Option<PrepareRenameResponse>, String> {
+        let Some(document) = self.latest_document_by_url(url).await else {
+            return Ok(None);
+        };
+        let Some((range, target)) = document.reference_target(position) else {
+            return Ok(None);
+        };
+        self.check_renameable(&document, &target).await?;
+
+        let placeholder = document.text_at(range).unwrap_or_default().to_string();
+        Ok(Some(PrepareRenameResponse::RangeWithPlaceholder {
+            range,
+            placeholder,
+        }))
+    }
+
+    pub async fn rename(
+        &self,
+        url: &Url,
+        position: Position,
+        new_name: &str,
+    ) -> Result<Option<WorkspaceEdit>, String> {
+        let Some(document) = self.latest_document_by_url(url).await else {
+            return Ok(None);
+        };
+        let Some((range, target)) = document.reference_target(position) else {
+            return Ok(None);
+        };
+        if document.text_at(range) == Some(new_name) {
+            return Ok(Some(WorkspaceEdit::default()));
+        }
+
+        target.check_new_name(new_name)?;
+        self.check_renameable(&document, &target).await?;
+
+        let found = self.find_references(&document, &target).await;
+
+        //Roc doesn't allow shadowing, so we refuse renames that would introduce it rather than produce code that doesn't compile
+        if let Some((shadowing, _)) = found
+            .iter()
+            .find(|(document, _)| document.rename_would_shadow(&target, new_name))
+        {
+            return Err(format!(
+                "`{new_name}` is already in scope in {}, so renaming to it would shadow that name.",
+                shadowing.url()
+            ));
+        }
+
+        //Fields are told apart by the type of their record; if that isn't known everywhere, we can't be sure which labels belong to the same field
+        if let Some((ambiguous, _)) = found
+            .iter()
+            .find(|(document, _)| document.field_rename_is_ambiguous(&target))
+        {
+            return Err(format!(
+                "Can't tell which records in {} have this field, so renaming it could change unrelated records. Try adding type annotations.",
+                ambiguous.url()
+            ));
+        }
+
+        let changes: HashMap<Url, Vec<TextEdit>> = found
+            .into_iter()
+            .map(|(document, references)| {
+                let edits = references
+                    .iter()
+                    .map(|reference| TextEdit {
+                        range: reference.range,
+                        new_text: target.replacement(reference, new_name),
+                    })
+                    .collect();
+                (document.url().clone(), edits)
+            })
+            .collect();
+
+        Ok(Some(WorkspaceEdit::new(changes)))
+    }
 }
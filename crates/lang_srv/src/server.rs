@@ -64,14 +64,11 @@ impl RocServer {
     }
 
     pub fn capabilities() -> ServerCapabilities {
-        let text_document_sync = TextDocumentSyncCapability::Options(
-            // TODO: later on make this incremental
-            TextDocumentSyncOptions {
-                open_close: Some(true),
-                change: Some(TextDocumentSyncKind::FULL),
-                ..TextDocumentSyncOptions::default()
-            },
-        );
+        let text_document_sync = TextDocumentSyncCapability::Options(TextDocumentSyncOptions {
+            open_close: Some(true),
+            change: Some(TextDocumentSyncKind::INCREMENTAL),
+            ..TextDocumentSyncOptions::default()
+        });
         let hover_provider = HoverProviderCapability::Simple(true);
         let definition_provider = DefinitionOptions {
             work_done_progress_options: WorkDoneProgressOptions {
@@ -103,6 +100,29 @@ impl RocServer {
                 work_done_progress: None,
             },
         };
+        let code_action_provider = CodeActionOptions {
+            code_action_kinds: Some(vec![
+                CodeActionKind::QUICKFIX,
+                CodeActionKind::REFACTOR_REWRITE,
+            ]),
+            work_done_progress_options: WorkDoneProgressOptions {
+                work_done_progress: None,
+            },
+            resolve_provider: None,
+        };
+        let signature_help_provider = SignatureHelpOptions {
+            trigger_characters: Some(vec![" ".to_string()]),
+            retrigger_characters: None,
+            work_done_progress_options: WorkDoneProgressOptions {
+                work_done_progress: None,
+            },
+        };
+        let rename_provider = RenameOptions {
+            prepare_provider: Some(true),
+            work_done_progress_options: WorkDoneProgressOptions {
+                work_done_progress: None,
+            },
+        };
         ServerCapabilities {
             text_document_sync: Some(text_document_sync),
             hover_provider: Some(hover_provider),
@@ -110,6 +130,14 @@ impl RocServer {
             document_formatting_provider: Some(OneOf::Right(document_formatting_provider)),
             semantic_tokens_provider: Some(semantic_tokens_provider),
             completion_provider: Some(completion_provider),
+            signature_help_provider: Some(signature_help_provider),
+            inlay_hint_provider: Some(OneOf::Left(true)),
+            document_symbol_provider: Some(OneOf::Left(true)),
+            workspace_symbol_provider: Some(OneOf::Left(true)),
+            folding_range_provider: Some(FoldingRangeProviderCapability::Simple(true)),
+            code_action_provider: Some(CodeActionProviderCapability::Options(code_action_provider)),
+            references_provider: Some(OneOf::Left(true)),
+            rename_provider: Some(OneOf::Right(rename_provider)),
             ..ServerCapabilities::default()
         }
     }
@@ -117,7 +145,21 @@ impl RocServer {
     /// Records a document content change.
     async fn change(&self, fi: Url, text: String, version: i32) {
         let updating_result = self.state.change(&fi, text, version).await;
+        self.publish_diagnostics(fi, version, updating_result).await;
+    }
 
+    /// Records the changes from a `textDocument/didChange` notification.
+    async fn edit(&self, fi: Url, changes: Vec<TextDocumentContentChangeEvent>, version: i32) {
+        let updating_result = self.state.edit(&fi, changes, version).await;
+        self.publish_diagnostics(fi, version, updating_result).await;
+    }
+
+    async fn publish_diagnostics(
+        &self,
+        fi: Url,
+        version: i32,
+        updating_result: std::result::Result<(), String>,
+    ) {
         //The analysis task can be cancelled by another change coming in which will update the watched variable
         if let Err(e) = updating_result {
             debug!("Cancelled change. Reason:{:?}", e);
@@ -150,10 +192,43 @@ impl RocServerState {
         debug!("V{:?}:starting change", version);
         let doc_info = DocInfo::new(fi.clone(), text, version);
 
-        self.registry
+        let needs_analysis = self
+            .registry
             .apply_doc_info_changes(fi.clone(), doc_info.clone())
             .await;
 
+        self.analyse(fi, doc_info, needs_analysis).await
+    }
+
+    /// Applies incremental changes to the latest contents of a document.
+    pub async fn edit(
+        &self,
+        fi: &Url,
+        changes: Vec<TextDocumentContentChangeEvent>,
+        version: i32,
+    ) -> std::result::Result<(), String> {
+        debug!("V{:?}:starting edit", version);
+        let (doc_info, needs_analysis) = self
+            .registry
+            .apply_text_changes(fi, changes, version)
+            .await
+            .ok_or_else(|| format!("Could not apply the changes for version {}", version))?;
+
+        self.analyse(fi, doc_info, needs_analysis).await
+    }
+
+    async fn analyse(
+        &self,
+        fi: &Url,
+        doc_info: DocInfo,
+        needs_analysis: bool,
+    ) -> std::result::Result<(), String> {
+        let version = doc_info.version;
+        if !needs_analysis {
+            debug!("V{:?}:source is unchanged, keeping analysis", version);
+            return Ok(());
+        }
+
         debug!(
             "V{:?}:finished updating docinfo, starting analysis ",
             version
@@ -173,10 +248,14 @@ impl RocServerState {
                 return Err("Not latest version skipping analysis".to_string());
             }
 
-            let results = match tokio::task::spawn_blocking(|| global_analysis(doc_info)).await {
-                Err(e) => return Err(format!("Document analysis failed. reason:{:?}", e)),
-                Ok(a) => a,
-            };
+            let reusable_modules = inner_ref.registry.reusable_modules(fi).await;
+            let (results, reusable_modules) =
+                match tokio::task::spawn_blocking(|| global_analysis(doc_info, reusable_modules))
+                    .await
+                {
+                    Err(e) => return Err(format!("Document analysis failed. reason:{:?}", e)),
+                    Ok(a) => a,
+                };
             let latest_version = inner_ref.registry.get_latest_version(fi).await;
 
             //if this version is not the latest another change must have come in and this analysis is useless
@@ -194,7 +273,10 @@ impl RocServerState {
                 version
             );
 
-            inner_ref.registry.apply_changes(results, fi.clone()).await;
+            inner_ref
+                .registry
+                .apply_changes(results, reusable_modules, fi.clone())
+                .await;
             Ok(())
         }
         .await;
@@ -228,12 +310,7 @@ impl LanguageServer for RocServer {
     async fn did_change(&self, params: DidChangeTextDocumentParams) {
         let VersionedTextDocumentIdentifier { uri, version, .. } = params.text_document;
 
-        // NOTE: We specify that we expect full-content syncs in the server capabilities,
-        // so here we assume the only change passed is a change of the entire document's content.
-        let TextDocumentContentChangeEvent { text, .. } =
-            params.content_changes.into_iter().next().unwrap();
-
-        self.change(uri, text, version).await;
+        self.edit(uri, params.content_changes, version).await;
     }
 
     async fn did_close(&self, params: DidCloseTextDocumentParams) {
@@ -258,6 +335,35 @@ impl LanguageServer for RocServer {
         unwind_async(self.state.registry.hover(&text_document.uri, position)).await
     }
 
+    async fn signature_help(&self, params: SignatureHelpParams) -> Result<Option<SignatureHelp>> {
+        let SignatureHelpParams {
+            text_document_position_params:
+                TextDocumentPositionParams {
+                    text_document,
+                    position,
+                },
+            context: _,
+            work_done_progress_params: _,
+        } = params;
+
+        unwind_async(
+            self.state
+                .registry
+                .signature_help(&text_document.uri, position),
+        )
+        .await
+    }
+
+    async fn inlay_hint(&self, params: InlayHintParams) -> Result<Option<Vec<InlayHint>>> {
+        let InlayHintParams {
+            text_document,
+            range,
+            work_done_progress_params: _,
+        } = params;
+
+        unwind_async(self.state.registry.inlay_hints(&text_document.uri, range)).await
+    }
+
     async fn goto_definition(
         &self,
         params: GotoDefinitionParams,
@@ -314,6 +420,121 @@ impl LanguageServer for RocServer {
         )
         .await
     }
+
+    async fn document_symbol(
+        &self,
+        params: DocumentSymbolParams,
+    ) -> Result<Option<DocumentSymbolResponse>> {
+        let DocumentSymbolParams {
+            text_document,
+            work_done_progress_params: _,
+            partial_result_params: _,
+        } = params;
+
+        unwind_async(self.state.registry.document_symbols(&text_document.uri)).await
+    }
+
+    async fn symbol(
+        &self,
+        params: WorkspaceSymbolParams,
+    ) -> Result<Option<Vec<SymbolInformation>>> {
+        let WorkspaceSymbolParams {
+            query,
+            work_done_progress_params: _,
+            partial_result_params: _,
+        } = params;
+
+        unwind_async(async { Some(self.state.registry.workspace_symbols(&query).await) }).await
+    }
+
+    async fn folding_range(&self, params: FoldingRangeParams) -> Result<Option<Vec<FoldingRange>>> {
+        let FoldingRangeParams {
+            text_document,
+            work_done_progress_params: _,
+            partial_result_params: _,
+        } = params;
+
+        unwind_async(self.state.registry.folding_ranges(&text_document.uri)).await
+    }
+
+    async fn code_action(&self, params: CodeActionParams) -> Result<Option<CodeActionResponse>> {
+        let CodeActionParams {
+            text_document,
+            range,
+            context: _,
+            work_done_progress_params: _,
+            partial_result_params: _,
+        } = params;
+
+        unwind_async(self.state.registry.code_actions(&text_document.uri, range)).await
+    }
+
+    async fn references(&self, params: ReferenceParams) -> Result<Option<Vec<Location>>> {
+        let ReferenceParams {
+            text_document_position:
+                TextDocumentPositionParams {
+                    text_document,
+                    position,
+                },
+            context,
+            work_done_progress_params: _,
+            partial_result_params: _,
+        } = params;
+
+        unwind_async(self.state.registry.references(
+            &text_document.uri,
+            position,
+            context.include_declaration,
+        ))
+        .await
+    }
+
+    async fn prepare_rename(
+        &self,
+        params: TextDocumentPositionParams,
+    ) -> Result<Option<PrepareRenameResponse>> {
+        let TextDocumentPositionParams {
+            text_document,
+            position,
+        } = params;
+
+        unwind_async(
+            self.state
+                .registry
+                .prepare_rename(&text_document.uri, position),
+        )
+        .await?
+        .map_err(invalid_params)
+    }
+
+    async fn rename(&self, params: RenameParams) -> Result<Option<WorkspaceEdit>> {
+        let RenameParams {
+            text_document_position:
+                TextDocumentPositionParams {
+                    text_document,
+                    position,
+                },
+            new_name,
+            work_done_progress_params: _,
+        } = params;
+
+        unwind_async(
+            self.state
+                .registry
+                .rename(&text_document.uri, position, &new_name),
+        )
+        .await?
+        .map_err(invalid_params)
+    }
+}
+
+///Reports a request we understood but refuse to carry out, such as a rename to an invalid name.
+fn invalid_params(message: String) -> jsonrpc::Error {
+    jsonrpc::Error {
+        code: jsonrpc::ErrorCode::InvalidParams,
+        message,
+        data: None,
+    }
 }
 
 async fn unwind_async<Fut, T>(future: Fut) -> tower_lsp::jsonrpc::Result<T>
@@ -351,6 +572,7 @@ mod tests {
     use expect_test::expect;
     use indoc::indoc;
     use log::info;
+    use tempfile::TempDir;
 
     use super::*;
 
@@ -407,6 +629,36 @@ mod tests {
         (inner, url)
     }
 
+    /// Like [test_setup], but with `Test.roc` in a temporary directory of its own, next to the
+    /// given files. The directory gets deleted once the returned [TempDir] is dropped.
+    async fn test_setup_with_files(
+        doc: &str,
+        files: &[(&str, &str)],
+    ) -> (RocServerState, Url, TempDir) {
+        let dir = tempfile::tempdir().unwrap();
+        for (name, contents) in files {
+            std::fs::write(dir.path().join(name), contents).unwrap();
+        }
+
+        let url = Url::from_file_path(dir.path().join("Test.roc")).unwrap();
+        let inner = RocServerState::new(RocServerConfig::default(), Registry::default());
+        inner.change(&url, doc.to_string(), 0).await.unwrap();
+        (inner, url, dir)
+    }
+
+    const OTHER_FILE: (&str, &str) = (
+        "Other.roc",
+        indoc! {r"
+            interface Other
+              exposes [double, triple]
+              imports []
+
+            double = \num -> num * 2
+
+            triple = \num -> num * 3
+        "},
+    );
+
     /// Runs a basic completion and returns the response
     async fn completion_test(
         initial: &str,
@@ -594,4 +846,670 @@ mod tests {
         "#]]
         .assert_debug_eq(&actual);
     }
+
+    const RENAME_DOC: &str = indoc! {r"
+        double = \num -> num * 2
+
+        quadruple = \num -> double (double num)
+        "};
+
+    fn range_string(range: Range) -> String {
+        format!(
+            "{}:{}-{}:{}",
+            range.start.line, range.start.character, range.end.line, range.end.character
+        )
+    }
+
+    /// Runs a rename and returns each edit as its range and new text
+    async fn rename_test(
+        doc: &str,
+        position: Position,
+        new_name: &str,
+    ) -> std::result::Result<Vec<String>, String> {
+        let (inner, url) = test_setup(DOC_LIT.to_string() + doc).await;
+
+        let edit = inner.registry.rename(&url, position, new_name).await?;
+        let mut edits: Vec<_> = edit
+            .and_then(|edit| edit.changes)
+            .and_then(|mut changes| changes.remove(&url))
+            .unwrap_or_default()
+            .into_iter()
+            .map(|edit| format!("{} {}", range_string(edit.range), edit.new_text))
+            .collect();
+        edits.sort();
+        Ok(edits)
+    }
+
+    #[tokio::test]
+    async fn test_rename_top_level() {
+        let actual = rename_test(RENAME_DOC, Position::new(3, 2), "twice").await;
+
+        expect![[r#"
+            Ok(
+                [
+                    "3:0-3:6 twice",
+                    "5:20-5:26 twice",
+                    "5:28-5:34 twice",
+                ],
+            )
+        "#]]
+        .assert_debug_eq(&actual);
+    }
+
+    #[tokio::test]
+    async fn test_rename_refuses_shadowing() {
+        let actual = rename_test(RENAME_DOC, Position::new(3, 2), "quadruple").await;
+
+        assert!(actual.is_err(), "{actual:?}");
+    }
+
+    #[tokio::test]
+    async fn test_rename_across_modules() {
+        let dir = std::env::temp_dir().join("roc_lang_srv_test_rename_across_modules");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("Other.roc"),
+            indoc! {r"
+                interface Other
+                  exposes [double]
+                  imports []
+
+                double = \num -> num * 2
+            "},
+        )
+        .unwrap();
+
+        let doc = indoc! {r"
+            interface Test
+              exposes [quadruple]
+              imports [Other.{ double }]
+
+            quadruple = \num -> double (double num)
+        "};
+        let url = Url::from_file_path(dir.join("Test.roc")).unwrap();
+        let inner = RocServerState::new(RocServerConfig::default(), Registry::default());
+        inner.change(&url, doc.to_string(), 0).await.unwrap();
+
+        let edit = inner
+            .registry
+            .rename(&url, Position::new(4, 21), "twice")
+            .await;
+        let mut edits: Vec<_> = edit
+            .unwrap()
+            .and_then(|edit| edit.changes)
+            .unwrap_or_default()
+            .into_iter()
+            .flat_map(|(url, edits)| {
+                let file = url
+                    .path_segments()
+                    .unwrap()
+                    .next_back()
+                    .unwrap()
+                    .to_string();
+                edits.into_iter().map(move |edit| {
+                    format!("{file} {} {}", range_string(edit.range), edit.new_text)
+                })
+            })
+            .collect();
+        edits.sort();
+
+        expect![[r#"
+            [
+                "Other.roc 1:11-1:17 twice",
+                "Other.roc 4:0-4:6 twice",
+                "Test.roc 2:19-2:25 twice",
+                "Test.roc 4:20-4:26 twice",
+                "Test.roc 4:28-4:34 twice",
+            ]
+        "#]]
+        .assert_debug_eq(&edits);
+    }
+
+    #[tokio::test]
+    async fn test_rename_field_of_one_record() {
+        let doc = indoc! {r"
+            point = { x: 1, y: 2 }
+
+            size = { x: 3 }
+
+            left = point.x
+        "};
+        let actual = rename_test(doc, Position::new(3, 10), "left").await;
+
+        expect![[r#"
+            Ok(
+                [
+                    "3:10-3:11 left",
+                    "7:13-7:14 left",
+                ],
+            )
+        "#]]
+        .assert_debug_eq(&actual);
+    }
+
+    #[tokio::test]
+    async fn test_rename_field_refuses_unknown_record() {
+        let doc = indoc! {r"
+            point = { x: 1, y: 2 }
+
+            getX = \record -> record.x
+        "};
+        let actual = rename_test(doc, Position::new(3, 10), "left").await;
+
+        assert!(actual.is_err(), "{actual:?}");
+    }
+
+    #[tokio::test]
+    async fn test_references_local() {
+        let (inner, url) = test_setup(DOC_LIT.to_string() + RENAME_DOC).await;
+
+        let actual = inner
+            .registry
+            .references(&url, Position::new(5, 36), true)
+            .await
+            .map(|locations| {
+                locations
+                    .into_iter()
+                    .map(|location| range_string(location.range))
+                    .collect::<Vec<_>>()
+            });
+
+        expect![[r#"
+            Some(
+                [
+                    "5:13-5:16",
+                    "5:35-5:38",
+                ],
+            )
+        "#]]
+        .assert_debug_eq(&actual);
+    }
+
+    /// Gets the code actions at `position` and returns their titles and edits
+    async fn code_action_test(doc: String, position: Position) -> Vec<(String, Vec<String>)> {
+        let (inner, url) = test_setup(doc).await;
+        code_actions_at(&inner, &url, position).await
+    }
+
+    async fn code_actions_at(
+        inner: &RocServerState,
+        url: &Url,
+        position: Position,
+    ) -> Vec<(String, Vec<String>)> {
+        let actions = inner
+            .registry
+            .code_actions(url, Range::new(position, position))
+            .await
+            .unwrap_or_default();
+
+        actions
+            .into_iter()
+            .filter_map(|action| match action {
+                CodeActionOrCommand::CodeAction(action) => Some(action),
+                CodeActionOrCommand::Command(_) => None,
+            })
+            .map(|action| {
+                let edits = action
+                    .edit
+                    .and_then(|edit| edit.changes)
+                    .and_then(|mut changes| changes.remove(url))
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|edit| format!("{} {:?}", range_string(edit.range), edit.new_text))
+                    .collect();
+                (action.title, edits)
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_code_action_unused_argument() {
+        let doc = DOC_LIT.to_string()
+            + indoc! {r"
+                f = \x -> 1
+            "};
+        let actual = code_action_test(doc, Position::new(3, 5)).await;
+
+        expect![[r#"
+            [
+                (
+                    "Prefix `x` with an underscore",
+                    [
+                        "3:5-3:5 \"_\"",
+                    ],
+                ),
+            ]
+        "#]]
+        .assert_debug_eq(&actual);
+    }
+
+    #[tokio::test]
+    async fn test_code_action_unused_import() {
+        let doc = indoc! {r"
+            interface Test
+              exposes []
+              imports [List.{ map, len }]
+
+            x = len [1]
+        "};
+        let actual = code_action_test(doc.to_string(), Position::new(2, 19)).await;
+
+        expect![[r#"
+            [
+                (
+                    "Remove unused import `map`",
+                    [
+                        "2:18-2:23 \"\"",
+                    ],
+                ),
+            ]
+        "#]]
+        .assert_debug_eq(&actual);
+    }
+
+    #[tokio::test]
+    async fn test_code_action_unused_import_before_ingested_file() {
+        let doc = indoc! {r#"
+            interface Test
+              exposes [x]
+              imports [Other, "data.txt" as data : Str, Set]
+
+            x = (data, Set.empty {})
+        "#};
+        let (inner, url, _dir) =
+            test_setup_with_files(doc, &[OTHER_FILE, ("data.txt", "hello")]).await;
+        let actual = code_actions_at(&inner, &url, Position::new(2, 12)).await;
+
+        expect![[r#"
+            [
+                (
+                    "Remove unused import `Other`",
+                    [
+                        "2:11-2:18 \"\"",
+                    ],
+                ),
+            ]
+        "#]]
+        .assert_debug_eq(&actual);
+    }
+
+    #[tokio::test]
+    async fn test_code_action_add_missing_import() {
+        let doc = indoc! {r"
+            interface Test
+              exposes [sextuple]
+              imports [Other.{ triple }]
+
+            sextuple = \num -> double (triple num)
+        "};
+        let (inner, url, _dir) = test_setup_with_files(doc, &[OTHER_FILE]).await;
+        let actual = code_actions_at(&inner, &url, Position::new(4, 20)).await;
+
+        expect![[r#"
+            [
+                (
+                    "Import `double` from `Other`",
+                    [
+                        "2:25-2:25 \", double\"",
+                    ],
+                ),
+            ]
+        "#]]
+        .assert_debug_eq(&actual);
+    }
+
+    #[tokio::test]
+    async fn test_code_action_add_annotation() {
+        let doc = DOC_LIT.to_string() + RENAME_DOC;
+        let actual = code_action_test(doc, Position::new(3, 1)).await;
+
+        expect![[r#"
+            [
+                (
+                    "Add type annotation to `double`",
+                    [
+                        "3:0-3:0 \"double : Num a -> Num a\\n\"",
+                    ],
+                ),
+            ]
+        "#]]
+        .assert_debug_eq(&actual);
+    }
+
+    const OUTLINE_DOC: &str = indoc! {r#"
+        interface Test
+          exposes []
+          imports []
+
+        Color : [Red, Green]
+
+        describe : Color -> Str
+        describe = \color ->
+            when color is
+                Red -> "red"
+                Green -> "green"
+        "#};
+
+    fn outline_strings(symbols: Vec<DocumentSymbol>, depth: usize, lines: &mut Vec<String>) {
+        for symbol in symbols {
+            lines.push(format!(
+                "{}{} {:?} {} {}",
+                "  ".repeat(depth),
+                symbol.name,
+                symbol.kind,
+                range_string(symbol.range),
+                range_string(symbol.selection_range),
+            ));
+            outline_strings(symbol.children.unwrap_or_default(), depth + 1, lines);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_document_symbols() {
+        let (inner, url) = test_setup(OUTLINE_DOC.to_string()).await;
+
+        let mut actual = Vec::new();
+        if let Some(DocumentSymbolResponse::Nested(symbols)) =
+            inner.registry.document_symbols(&url).await
+        {
+            outline_strings(symbols, 0, &mut actual);
+        }
+
+        expect![[r#"
+            [
+                "Test Module 0:0-2:12 0:10-0:14",
+                "Color TypeParameter 4:0-4:20 4:0-4:5",
+                "describe Function 6:0-10:24 7:0-7:8",
+                "  when color Enum 8:4-10:24 8:9-8:14",
+                "    Red EnumMember 9:8-9:20 9:8-9:11",
+                "    Green EnumMember 10:8-10:24 10:8-10:13",
+            ]
+        "#]]
+        .assert_debug_eq(&actual);
+    }
+
+    #[tokio::test]
+    async fn test_workspace_symbols() {
+        let (inner, _) = test_setup(OUTLINE_DOC.to_string()).await;
+
+        let actual: Vec<_> = inner
+            .registry
+            .workspace_symbols("desc")
+            .await
+            .into_iter()
+            .map(|symbol| (symbol.name, symbol.container_name))
+            .collect();
+
+        expect![[r#"
+            [
+                (
+                    "describe",
+                    Some(
+                        "Test",
+                    ),
+                ),
+            ]
+        "#]]
+        .assert_debug_eq(&actual);
+    }
+
+    #[tokio::test]
+    async fn test_folding_ranges() {
+        let (inner, url) = test_setup(OUTLINE_DOC.to_string()).await;
+
+        let actual: Vec<_> = inner
+            .registry
+            .folding_ranges(&url)
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .map(|range| (range.start_line, range.end_line, range.kind))
+            .collect();
+
+        expect![[r#"
+            [
+                (
+                    0,
+                    2,
+                    Some(
+                        Imports,
+                    ),
+                ),
+                (
+                    6,
+                    10,
+                    None,
+                ),
+                (
+                    8,
+                    10,
+                    None,
+                ),
+            ]
+        "#]]
+        .assert_debug_eq(&actual);
+    }
+
+    /// Gets the signature help at `position`, as the signature's label, the text of each of its
+    /// parameters, and which one is active
+    async fn signature_help_test(
+        doc: &str,
+        position: Position,
+    ) -> Option<(String, Vec<String>, Option<u32>)> {
+        let (inner, url) = test_setup(DOC_LIT.to_string() + doc).await;
+
+        let help = inner.registry.signature_help(&url, position).await?;
+        let signature = help.signatures.into_iter().next()?;
+
+        let label: Vec<u16> = signature.label.encode_utf16().collect();
+        let params = signature
+            .parameters
+            .unwrap_or_default()
+            .into_iter()
+            .map(|param| match param.label {
+                ParameterLabel::LabelOffsets([start, end]) => {
+                    String::from_utf16_lossy(&label[start as usize..end as usize])
+                }
+                ParameterLabel::Simple(text) => text,
+            })
+            .collect();
+
+        Some((signature.label, params, help.active_parameter))
+    }
+
+    #[tokio::test]
+    async fn test_signature_help() {
+        let doc = indoc! {r"
+            total = List.walk [1, 2] 0 \sum, n -> sum + n
+            "};
+
+        let in_argument = signature_help_test(doc, Position::new(3, 25)).await;
+        let on_function = signature_help_test(doc, Position::new(3, 12)).await;
+
+        expect![[r#"
+            (
+                Some(
+                    (
+                        "List.walk : List (Num a), Num a, (Num a, Num a -> Num a) -> Num a",
+                        [
+                            "List (Num a)",
+                            "Num a",
+                            "(Num a, Num a -> Num a)",
+                        ],
+                        Some(
+                            1,
+                        ),
+                    ),
+                ),
+                None,
+            )
+        "#]]
+        .assert_debug_eq(&(in_argument, on_function));
+    }
+
+    #[tokio::test]
+    async fn test_signature_help_next_argument() {
+        let actual = signature_help_test("pairs = List.map2 [1] ", Position::new(3, 22)).await;
+
+        expect![[r#"
+            Some(
+                (
+                    "List.map2 : List a, List b, (a, b -> c) -> List c",
+                    [
+                        "List a",
+                        "List b",
+                        "(a, b -> c)",
+                    ],
+                    Some(
+                        1,
+                    ),
+                ),
+            )
+        "#]]
+        .assert_debug_eq(&actual);
+    }
+
+    #[tokio::test]
+    async fn test_inlay_hints() {
+        let doc = DOC_LIT.to_string()
+            + indoc! {r"
+            double = \num -> num * 2
+
+            main : List I64
+            main =
+                answer = double 21
+                List.map [answer] \x -> x + 1
+            "};
+
+        let (inner, url) = test_setup(doc).await;
+
+        let range = Range::new(Position::new(0, 0), Position::new(9, 0));
+        let actual: Vec<_> = inner
+            .registry
+            .inlay_hints(&url, range)
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .map(|hint| {
+                let label = match hint.label {
+                    InlayHintLabel::String(label) => label,
+                    InlayHintLabel::LabelParts(parts) => {
+                        parts.into_iter().map(|part| part.value).collect()
+                    }
+                };
+                let Position { line, character } = hint.position;
+                format!("{line}:{character} {label}")
+            })
+            .collect();
+
+        expect![[r#"
+            [
+                "3:6 : Num a -> Num a",
+                "7:10 : I64",
+                "8:24 : I64",
+            ]
+        "#]]
+        .assert_debug_eq(&actual);
+    }
+
+    #[tokio::test]
+    async fn test_incremental_change() {
+        let doc = DOC_LIT.to_string() + "main = (\"é\", \"x\")\n";
+        let (inner, url) = test_setup(doc).await;
+
+        // Positions count UTF-16 code units, so `"x"` starts at 13 even though `é` is two bytes
+        let change = TextDocumentContentChangeEvent {
+            range: Some(Range::new(Position::new(3, 13), Position::new(3, 16))),
+            range_length: None,
+            text: "42".to_string(),
+        };
+        inner.edit(&url, vec![change.clone()], 1).await.unwrap();
+
+        // Changes for a version we already have are stale
+        let stale = inner.edit(&url, vec![change], 1).await;
+
+        let hover = inner.registry.hover(&url, Position::new(3, 1)).await;
+        let type_str = hover.and_then(|hover| match hover.contents {
+            HoverContents::Array(contents) => {
+                contents.into_iter().find_map(|content| match content {
+                    MarkedString::LanguageString(LanguageString { value, .. }) => Some(value),
+                    MarkedString::String(_) => None,
+                })
+            }
+            _ => None,
+        });
+
+        expect![[r#"
+            (
+                Some(
+                    "( Str, Num * )*",
+                ),
+                Err(
+                    "Could not apply the changes for version 1",
+                ),
+            )
+        "#]]
+        .assert_debug_eq(&(type_str, stale));
+    }
+    #[tokio::test]
+    async fn test_incremental_change_reuses_unaffected_modules() {
+        let doc = indoc! {r"
+            interface Test
+              exposes [quadruple]
+              imports [Other.{ double }]
+
+            quadruple = \num -> double (double num)
+        "};
+        let (inner, url, dir) = test_setup_with_files(doc, &[OTHER_FILE]).await;
+        let other_url = Url::from_file_path(dir.path().join("Other.roc")).unwrap();
+        let registry = &inner.registry;
+
+        let first = (
+            registry.types_reused(&url).await,
+            registry.types_reused(&other_url).await,
+        );
+
+        // Only `Test` changed, so `Other` keeps the types it was checked with the first time
+        let change = doc.replace("double num", "double (num + 1)");
+        inner.change(&url, change, 1).await.unwrap();
+        let second = (
+            registry.types_reused(&url).await,
+            registry.types_reused(&other_url).await,
+        );
+
+        let definition = match registry.goto_definition(&url, Position::new(4, 21)).await {
+            Some(GotoDefinitionResponse::Scalar(location)) => {
+                Some((location.uri == other_url, range_string(location.range)))
+            }
+            _ => None,
+        };
+
+        expect![[r#"
+            (
+                (
+                    Some(
+                        false,
+                    ),
+                    Some(
+                        false,
+                    ),
+                ),
+                (
+                    Some(
+                        false,
+                    ),
+                    Some(
+                        true,
+                    ),
+                ),
+                Some(
+                    (
+                        true,
+                        "4:0-4:24",
+                    ),
+                ),
+            )
+        "#]]
+        .assert_debug_eq(&(first, second, definition));
+    }
 }
@@ -0,0 +1,228 @@
+//! Reading and patching AArch64 instructions, for surgical linking of arm64 executables.
+//!
+//! Every AArch64 instruction is 4 bytes. Addresses are encoded in bit fields inside the
+//! instruction, usually as an offset from the instruction itself, so relocations and
+//! surgery have to rewrite those bit fields rather than just overwriting some bytes.
+
+pub(crate) const INSTRUCTION_SIZE: usize = 4;
+
+/// Size of the pages that `adrp` works with. It is always 4KiB, whatever the OS page size is.
+pub(crate) const ADRP_PAGE_SIZE: u64 = 0x1000;
+
+const NOP: u32 = 0xd503_201f;
+
+/// The scratch register that the PLT and linker-generated stubs are allowed to clobber
+const IP0: u32 = 16;
+
+/// The ways an AArch64 instruction can refer to an address
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Fixup {
+    /// `b` or `bl`: 26-bit word offset from the instruction (+/-128MiB)
+    Branch26,
+    /// `b.cond`, `cbz`, `cbnz`, or `ldr` (literal): 19-bit word offset from the instruction (+/-1MiB)
+    Branch19,
+    /// `tbz` or `tbnz`: 14-bit word offset from the instruction (+/-32KiB)
+    Branch14,
+    /// `adr`: 21-bit byte offset from the instruction (+/-1MiB)
+    Adr,
+    /// `adrp`: 21-bit offset from the instruction's 4KiB page to the target's page (+/-4GiB)
+    Adrp,
+    /// `add` (immediate): the low 12 bits of the target address
+    AddLo12,
+    /// `ldr` or `str` (unsigned immediate): the low 12 bits of the target address,
+    /// divided by the access size, which is `1 << scale` bytes
+    LoadStoreLo12 { scale: u32 },
+    /// `ldr` of the low 12 bits of a GOT entry's address. We know the target's address, so we
+    /// skip the GOT and turn the `ldr` into an `add` of the target address.
+    /// The `adrp` that goes with it must point at the target's page instead of the GOT's.
+    GotLoadLo12,
+}
+
+impl Fixup {
+    /// Patch the instruction `inst` at address `pc` so that it refers to `target`.
+    /// Returns `None` if the target is out of range or misaligned for this kind of instruction.
+    pub(crate) fn apply(self, inst: u32, pc: u64, target: u64) -> Option<u32> {
+        let offset = target.wrapping_sub(pc) as i64;
+        match self {
+            Fixup::Branch26 => set_word_offset(inst, offset, 26, 0),
+            Fixup::Branch19 => set_word_offset(inst, offset, 19, 5),
+            Fixup::Branch14 => set_word_offset(inst, offset, 14, 5),
+            Fixup::Adr => set_adr_immediate(inst, offset),
+            Fixup::Adrp => {
+                let page_offset = page(target).wrapping_sub(page(pc)) as i64;
+                set_adr_immediate(inst, page_offset >> 12)
+            }
+            Fixup::AddLo12 => Some(set_imm12(inst, target & 0xfff)),
+            Fixup::LoadStoreLo12 { scale } => {
+                let lo12 = target & 0xfff;
+                if lo12 & ((1 << scale) - 1) != 0 {
+                    return None;
+                }
+                Some(set_imm12(inst, lo12 >> scale))
+            }
+            Fixup::GotLoadLo12 => {
+                // ldr xT, [xN, #imm] => add xT, xN, #imm
+                const LDR_X_UNSIGNED_IMM: u32 = 0xf940_0000;
+                const ADD_X_IMM: u32 = 0x9100_0000;
+                if inst & 0xffc0_0000 != LDR_X_UNSIGNED_IMM {
+                    return None;
+                }
+                let registers = inst & 0x3ff;
+                Some(set_imm12(ADD_X_IMM | registers, target & 0xfff))
+            }
+        }
+    }
+}
+
+/// If `inst` is a `b` or `bl` instruction, get the byte offset it branches to
+pub(crate) fn branch26_offset(inst: u32) -> Option<i64> {
+    const B: u32 = 0x1400_0000;
+    const BL: u32 = 0x9400_0000;
+    match inst & 0xfc00_0000 {
+        B | BL => Some(sign_extend(inst & 0x03ff_ffff, 26) << 2),
+        _ => None,
+    }
+}
+
+/// Instructions for a PLT entry that jumps straight to `target`, without going through the GOT.
+/// A PLT entry is 16 bytes, so this fills the space after the jump with `nop`s.
+pub(crate) fn plt_entry_jump(plt_entry_address: u64, target: u64) -> Option<[u32; 4]> {
+    let adrp = Fixup::Adrp.apply(0x9000_0000 | IP0, plt_entry_address, target)?;
+    let add = Fixup::AddLo12.apply(
+        0x9100_0000 | (IP0 << 5) | IP0,
+        plt_entry_address + 4,
+        target,
+    )?;
+    let br = 0xd61f_0000 | (IP0 << 5);
+    Some([adrp, add, br, NOP])
+}
+
+fn page(address: u64) -> u64 {
+    address & !(ADRP_PAGE_SIZE - 1)
+}
+
+fn sign_extend(value: u32, bits: u32) -> i64 {
+    let shift = 64 - bits;
+    ((value as i64) << shift) >> shift
+}
+
+fn fits_signed(value: i64, bits: u32) -> bool {
+    let limit = 1i64 << (bits - 1);
+    (-limit..limit).contains(&value)
+}
+
+/// Set a field that holds a PC-relative offset in words
+fn set_word_offset(inst: u32, byte_offset: i64, bits: u32, lsb: u32) -> Option<u32> {
+    if byte_offset % INSTRUCTION_SIZE as i64 != 0 {
+        return None;
+    }
+    let words = byte_offset / INSTRUCTION_SIZE as i64;
+    if !fits_signed(words, bits) {
+        return None;
+    }
+    let mask = ((1u32 << bits) - 1) << lsb;
+    Some((inst & !mask) | (((words as u32) << lsb) & mask))
+}
+
+/// `adr` and `adrp` split their 21-bit immediate into 2 low bits and 19 high bits
+fn set_adr_immediate(inst: u32, imm: i64) -> Option<u32> {
+    if !fits_signed(imm, 21) {
+        return None;
+    }
+    let imm = imm as u32;
+    let immlo = (imm & 0x3) << 29;
+    let immhi = ((imm >> 2) & 0x7ffff) << 5;
+    Some((inst & !0x60ff_ffe0) | immlo | immhi)
+}
+
+fn set_imm12(inst: u32, imm: u64) -> u32 {
+    (inst & !0x003f_fc00) | ((imm as u32 & 0xfff) << 10)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Expected encodings are from `llvm-mc --triple=aarch64 --show-encoding`
+
+    #[test]
+    fn branches() {
+        // bl #0x1000
+        assert_eq!(
+            Fixup::Branch26.apply(0x9400_0000, 0x2000, 0x3000),
+            Some(0x9400_0400)
+        );
+        // b #-0x8
+        assert_eq!(
+            Fixup::Branch26.apply(0x1400_0000, 0x2000, 0x1ff8),
+            Some(0x17ff_fffe)
+        );
+        assert_eq!(branch26_offset(0x17ff_fffe), Some(-8));
+        assert_eq!(branch26_offset(0x9400_0400), Some(0x1000));
+        assert_eq!(branch26_offset(NOP), None);
+
+        // cbz x1, #0x40
+        assert_eq!(
+            Fixup::Branch19.apply(0xb400_0001, 0x1000, 0x1040),
+            Some(0xb400_0201)
+        );
+        // tbnz w2, #3, #-0x10
+        assert_eq!(
+            Fixup::Branch14.apply(0x3718_0002, 0x1000, 0xff0),
+            Some(0x371f_ff82)
+        );
+
+        // out of range or misaligned
+        assert_eq!(Fixup::Branch26.apply(0x9400_0000, 0, 1 << 27), None);
+        assert_eq!(Fixup::Branch14.apply(0x3718_0002, 0, 1 << 15), None);
+        assert_eq!(Fixup::Branch26.apply(0x9400_0000, 0, 2), None);
+    }
+
+    #[test]
+    fn addresses() {
+        // adrp x0, #0x12344000
+        assert_eq!(
+            Fixup::Adrp.apply(0x9000_0000, 0x1ffc, 0x1234_5678),
+            Some(0x9009_1a20)
+        );
+        // adrp x3, #-0x2000
+        assert_eq!(
+            Fixup::Adrp.apply(0x9000_0003, 0x5000, 0x3fff),
+            Some(0xd0ff_ffe3)
+        );
+        // adr x1, #-4
+        assert_eq!(
+            Fixup::Adr.apply(0x1000_0001, 0x100, 0xfc),
+            Some(0x10ff_ffe1)
+        );
+        // add x0, x0, #0x678
+        assert_eq!(
+            Fixup::AddLo12.apply(0x9100_0000, 0, 0x1234_5678),
+            Some(0x9119_e000)
+        );
+        // ldr x1, [x2, #0x670] (the offset must be a multiple of 8)
+        assert_eq!(
+            Fixup::LoadStoreLo12 { scale: 3 }.apply(0xf940_0041, 0, 0x1234_567c),
+            None
+        );
+        assert_eq!(
+            Fixup::LoadStoreLo12 { scale: 3 }.apply(0xf940_0041, 0, 0x1234_5670),
+            Some(0xf943_3841)
+        );
+        // ldr x1, [x2, :got_lo12:sym] => add x1, x2, #0x678
+        assert_eq!(
+            Fixup::GotLoadLo12.apply(0xf940_0041, 0, 0x1234_5678),
+            Some(0x9119_e041)
+        );
+        assert_eq!(Fixup::GotLoadLo12.apply(NOP, 0, 0), None);
+    }
+
+    #[test]
+    fn plt_entry() {
+        // adrp x16, 0x3000 ; add x16, x16, #0x10 ; br x16 ; nop
+        assert_eq!(
+            plt_entry_jump(0x1020, 0x3010),
+            Some([0xd000_0010, 0x9100_4210, 0xd61f_0200, NOP])
+        );
+    }
+}
@@ -4,8 +4,8 @@ use memmap2::MmapMut;
 use object::{elf, endian};
 use object::{
     CompressedFileRange, CompressionFormat, LittleEndian as LE, Object, ObjectSection,
-    ObjectSymbol, RelocationKind, RelocationTarget, Section, SectionIndex, SectionKind, Symbol,
-    SymbolIndex, SymbolSection,
+    ObjectSymbol, RelocationEncoding, RelocationKind, RelocationTarget, Section, SectionIndex,
+    SectionKind, Symbol, SymbolIndex, SymbolKind, SymbolSection,
 };
 use roc_collections::all::MutMap;
 use roc_error_macros::{internal_error, user_error};
@@ -19,7 +19,7 @@ use std::{
 };
 
 use crate::{
-    align_by_constraint, align_to_offset_by_constraint, load_struct_inplace,
+    aarch64, align_by_constraint, align_to_offset_by_constraint, load_struct_inplace,
     load_struct_inplace_mut, load_structs_inplace_mut, open_mmap, open_mmap_mut,
 };
 
@@ -28,6 +28,76 @@ const MIN_SECTION_ALIGNMENT: usize = 0x40;
 // TODO: Analyze if this offset is always correct.
 const PLT_ADDRESS_OFFSET: u64 = 0x10;
 
+// On AArch64, the PLT starts with a 32 byte header, followed by a 16 byte entry per function.
+const AARCH64_PLT_HEADER_SIZE: u64 = 0x20;
+const AARCH64_PLT_ENTRY_SIZE: u64 = 0x10;
+
+/// The instruction sets we can do surgery on.
+/// They differ in relocation types, PLT layout, and how calls are encoded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ElfArch {
+    X86_64,
+    Aarch64,
+}
+
+impl ElfArch {
+    fn from_machine(e_machine: u16) -> Self {
+        match e_machine {
+            elf::EM_X86_64 => ElfArch::X86_64,
+            elf::EM_AARCH64 => ElfArch::Aarch64,
+            other => {
+                internal_error!("Surgical linking does not support ELF machine type {other}");
+            }
+        }
+    }
+
+    fn r_none(self) -> u32 {
+        match self {
+            ElfArch::X86_64 => elf::R_X86_64_NONE,
+            ElfArch::Aarch64 => elf::R_AARCH64_NONE,
+        }
+    }
+
+    fn r_relative(self) -> u32 {
+        match self {
+            ElfArch::X86_64 => elf::R_X86_64_RELATIVE,
+            ElfArch::Aarch64 => elf::R_AARCH64_RELATIVE,
+        }
+    }
+
+    fn r_glob_dat(self) -> u32 {
+        match self {
+            ElfArch::X86_64 => elf::R_X86_64_GLOB_DAT,
+            ElfArch::Aarch64 => elf::R_AARCH64_GLOB_DAT,
+        }
+    }
+
+    fn r_jump_slot(self) -> u32 {
+        match self {
+            ElfArch::X86_64 => elf::R_X86_64_JUMP_SLOT,
+            ElfArch::Aarch64 => elf::R_AARCH64_JUMP_SLOT,
+        }
+    }
+
+    /// Offset from the start of the PLT to the entry for the `i`th JUMP_SLOT relocation
+    fn plt_entry_offset(self, i: u64) -> u64 {
+        match self {
+            ElfArch::X86_64 => (i + 1) * PLT_ADDRESS_OFFSET,
+            ElfArch::Aarch64 => AARCH64_PLT_HEADER_SIZE + i * AARCH64_PLT_ENTRY_SIZE,
+        }
+    }
+
+    /// Alignment of the bytes we insert after the program headers.
+    /// On AArch64, `adrp` instructions find addresses relative to the 4KiB page they're on,
+    /// so the code has to move by whole pages for them to keep working.
+    fn shift_alignment(self) -> u64 {
+        match self {
+            ElfArch::X86_64 => MIN_SECTION_ALIGNMENT as u64,
+            ElfArch::Aarch64 => aarch64::ADRP_PAGE_SIZE,
+        }
+    }
+}
+
 struct ElfDynamicDeps {
     got_app_syms: Vec<(String, usize)>,
     got_sections: Vec<(usize, usize)>,
@@ -42,11 +112,20 @@ enum VirtualOffset {
     Relative(u64),
 }
 
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug)]
+enum SurgeryKind {
+    /// Overwrite `size` bytes with the app function's address, relative to `virtual_offset`
+    Value,
+    /// Overwrite the offset in an AArch64 `b` or `bl` instruction
+    Aarch64Branch,
+}
+
 #[derive(Serialize, Deserialize, PartialEq, Eq, Debug)]
 struct SurgeryEntry {
     file_offset: u64,
     virtual_offset: VirtualOffset,
     size: u8,
+    kind: SurgeryKind,
 }
 
 // TODO: Reanalyze each piece of data in this struct.
@@ -189,6 +268,7 @@ impl<'a> Surgeries<'a> {
         &mut self,
         object_bytes: &[u8],
         object: &object::File<'a, &'a [u8]>,
+        arch: ElfArch,
         verbose: bool,
     ) {
         let text_sections: Vec<Section> = object
@@ -212,7 +292,12 @@ impl<'a> Surgeries<'a> {
         }
 
         for text_section in text_sections {
-            self.append_text_section(object_bytes, &text_section, verbose)
+            match arch {
+                ElfArch::X86_64 => self.append_text_section(object_bytes, &text_section, verbose),
+                ElfArch::Aarch64 => {
+                    self.append_text_section_aarch64(object, &text_section, verbose)
+                }
+            }
         }
     }
 
@@ -299,6 +384,7 @@ impl<'a> Surgeries<'a> {
                                 file_offset: offset,
                                 virtual_offset: VirtualOffset::Relative(inst.next_ip()),
                                 size: op_size,
+                                kind: SurgeryKind::Value,
                             });
                     }
                 }
@@ -329,6 +415,163 @@ impl<'a> Surgeries<'a> {
             }
         }
     }
+
+    /// AArch64 instructions are all the same size, so we don't need to disassemble.
+    /// We just look for `b` and `bl` instructions that go to the PLT entry of an app function.
+    /// Any other calls to the app, like through function pointers, still reach the PLT, and we patch that too.
+    /// So do calls from code we skip because the symbol table doesn't say it's a function.
+    fn append_text_section_aarch64(
+        &mut self,
+        object: &object::File<'a, &'a [u8]>,
+        sec: &Section,
+        verbose: bool,
+    ) {
+        let (file_offset, compressed) = match sec.compressed_file_range() {
+            Ok(CompressedFileRange {
+                format: CompressionFormat::None,
+                offset,
+                ..
+            }) => (offset, false),
+            Ok(range) => (range.offset, true),
+            Err(err) => {
+                internal_error!(
+                    "Issues dealing with section compression for {:+x?}: {}",
+                    sec,
+                    err
+                );
+            }
+        };
+
+        let data = match sec.uncompressed_data() {
+            Ok(data) => data,
+            Err(err) => {
+                internal_error!("Failed to load text section, {:+x?}: {}", sec, err);
+            }
+        };
+
+        for address in aarch64_code_addresses(object, sec) {
+            let inst_offset = address - sec.address();
+            let bytes = &data[inst_offset as usize..][..aarch64::INSTRUCTION_SIZE];
+            let inst = u32::from_le_bytes(bytes.try_into().unwrap());
+            let branch_offset = match aarch64::branch26_offset(inst) {
+                Some(branch_offset) => branch_offset,
+                None => continue,
+            };
+            let target = address.wrapping_add(branch_offset as u64);
+            if let Some(func_name) = self.app_func_addresses.get(&target) {
+                if compressed {
+                    internal_error!(
+                        "Surgical linking does not work with compressed text sections: {:+x?}",
+                        sec
+                    );
+                }
+
+                let offset = file_offset + inst_offset;
+                if verbose {
+                    println!("Found branch from {address:+x} to {target:+x}({func_name})");
+                    println!(
+                        "\tNeed to surgically replace the instruction at file offset {offset:+x}"
+                    );
+                }
+                self.surgeries
+                    .get_mut(*func_name)
+                    .unwrap()
+                    .push(SurgeryEntry {
+                        file_offset: offset,
+                        virtual_offset: VirtualOffset::Relative(address),
+                        size: aarch64::INSTRUCTION_SIZE as u8,
+                        kind: SurgeryKind::Aarch64Branch,
+                    });
+            }
+        }
+    }
+}
+
+/// The addresses of the instructions in `sec` that belong to a function in the symbol table.
+/// Literal pools and other data can be in a text section too, and some of those words look
+/// like branches, so we also skip everything from a `$d` mapping symbol up to the next `$x`.
+fn aarch64_code_addresses(object: &object::File, sec: &Section) -> Vec<u64> {
+    let section_symbols = || {
+        object
+            .symbols()
+            .chain(object.dynamic_symbols())
+            .filter(|sym| sym.section_index() == Some(sec.index()))
+    };
+
+    let mut functions: Vec<(u64, u64)> = section_symbols()
+        .filter(|sym| sym.kind() == SymbolKind::Text && sym.size() > 0)
+        .map(|sym| (sym.address(), sym.address() + sym.size()))
+        .collect();
+    functions.sort_unstable();
+
+    // Each mapping symbol's address, and whether data (rather than code) starts there
+    let mut mapping_symbols: Vec<(u64, bool)> = section_symbols()
+        .filter_map(|sym| match sym.name() {
+            Ok("$d") => Some((sym.address(), true)),
+            Ok("$x") => Some((sym.address(), false)),
+            Ok(name) if name.starts_with("$d.") => Some((sym.address(), true)),
+            Ok(name) if name.starts_with("$x.") => Some((sym.address(), false)),
+            _ => None,
+        })
+        .collect();
+    mapping_symbols.sort_unstable();
+    let is_data =
+        |address: u64| match mapping_symbols.partition_point(|(start, _)| *start <= address) {
+            0 => false,
+            i => mapping_symbols[i - 1].1,
+        };
+
+    let inst_size = aarch64::INSTRUCTION_SIZE as u64;
+    let section_end = sec.address() + sec.size();
+    let mut addresses = Vec::new();
+    // Functions can overlap, e.g. when one symbol is an alias for another
+    let mut scanned_up_to = sec.address();
+    for (start, end) in functions {
+        let end = end.min(section_end);
+        let mut address = start.max(scanned_up_to);
+        while address + inst_size <= end {
+            if !is_data(address) {
+                addresses.push(address);
+            }
+            address += inst_size;
+        }
+        scanned_up_to = scanned_up_to.max(end);
+    }
+
+    addresses
+}
+
+/// Most AArch64 relocations patch a bit field in an instruction.
+/// `object` only has a generic `RelocationKind` for a few of them, so we look at the ELF type.
+fn aarch64_fixup(reloc: &object::Relocation) -> Option<aarch64::Fixup> {
+    use aarch64::Fixup;
+
+    let fixup = match reloc.kind() {
+        // R_AARCH64_CALL26
+        RelocationKind::PltRelative if reloc.encoding() == RelocationEncoding::AArch64Call => {
+            Fixup::Branch26
+        }
+        RelocationKind::Elf(r_type) => match r_type {
+            elf::R_AARCH64_JUMP26 | elf::R_AARCH64_CALL26 => Fixup::Branch26,
+            elf::R_AARCH64_CONDBR19 | elf::R_AARCH64_LD_PREL_LO19 => Fixup::Branch19,
+            elf::R_AARCH64_TSTBR14 => Fixup::Branch14,
+            elf::R_AARCH64_ADR_PREL_LO21 => Fixup::Adr,
+            elf::R_AARCH64_ADR_PREL_PG_HI21 | elf::R_AARCH64_ADR_PREL_PG_HI21_NC => Fixup::Adrp,
+            elf::R_AARCH64_ADD_ABS_LO12_NC => Fixup::AddLo12,
+            elf::R_AARCH64_LDST8_ABS_LO12_NC => Fixup::LoadStoreLo12 { scale: 0 },
+            elf::R_AARCH64_LDST16_ABS_LO12_NC => Fixup::LoadStoreLo12 { scale: 1 },
+            elf::R_AARCH64_LDST32_ABS_LO12_NC => Fixup::LoadStoreLo12 { scale: 2 },
+            elf::R_AARCH64_LDST64_ABS_LO12_NC => Fixup::LoadStoreLo12 { scale: 3 },
+            elf::R_AARCH64_LDST128_ABS_LO12_NC => Fixup::LoadStoreLo12 { scale: 4 },
+            // We don't make a GOT for the app. Everything it refers to is at a known address.
+            elf::R_AARCH64_ADR_GOT_PAGE => Fixup::Adrp,
+            elf::R_AARCH64_LD64_GOT_LO12_NC => Fixup::GotLoadLo12,
+            _ => return None,
+        },
+        _ => return None,
+    };
+
+    Some(fixup)
 }
 
 /// Constructs a `Metadata` from a host executable binary, and writes it to disk
@@ -350,6 +593,9 @@ pub(crate) fn preprocess_elf_le(
         }
     };
 
+    let exec_header = load_struct_inplace::<elf::FileHeader64<LE>>(exec_data, 0);
+    let arch = ElfArch::from_machine(exec_header.e_machine.get(LE));
+
     let mut md = Metadata {
         roc_symbol_vaddresses: collect_roc_definitions(&exec_obj),
         ..Default::default()
@@ -426,17 +672,16 @@ pub(crate) fn preprocess_elf_le(
                 }
             })
             .filter_map(|(_, reloc)| {
-                if let RelocationKind::Elf(7) = reloc.kind() {
-                    Some(reloc)
-                } else {
-                    None
+                match reloc.kind() {
+                    RelocationKind::Elf(r_type) if r_type == arch.r_jump_slot() => Some(reloc),
+                    _ => None,
                 }
             });
     for (i, reloc) in plt_relocs.enumerate() {
         for symbol in app_syms.iter() {
             if reloc.target() == RelocationTarget::Symbol(symbol.index()) {
-                let func_address = (i as u64 + 1) * PLT_ADDRESS_OFFSET + plt_address;
-                let func_offset = (i as u64 + 1) * PLT_ADDRESS_OFFSET + plt_offset;
+                let func_address = arch.plt_entry_offset(i as u64) + plt_address;
+                let func_offset = arch.plt_entry_offset(i as u64) + plt_offset;
                 app_func_addresses.insert(func_address, symbol.name().unwrap());
                 md.plt_addresses.insert(
                     symbol.name().unwrap().to_string(),
@@ -473,7 +718,7 @@ pub(crate) fn preprocess_elf_le(
     let text_disassembly_start = Instant::now();
 
     let mut surgeries = Surgeries::new(&app_syms, app_func_addresses);
-    surgeries.append_text_sections(exec_data, &exec_obj, verbose);
+    surgeries.append_text_sections(exec_data, &exec_obj, arch, verbose);
     md.surgeries = surgeries.surgeries;
 
     let text_disassembly_duration = text_disassembly_start.elapsed();
@@ -487,7 +732,7 @@ pub(crate) fn preprocess_elf_le(
         dynamic_lib_count,
         shared_lib_index,
     } = scan_elf_dynamic_deps(
-        &exec_obj, &mut md, &app_syms, shared_lib, exec_data, verbose,
+        &exec_obj, &mut md, &app_syms, shared_lib, exec_data, arch, verbose,
     );
 
     let scanning_dynamic_deps_duration = scanning_dynamic_deps_start.elapsed();
@@ -568,6 +813,7 @@ fn gen_elf_le(
     verbose: bool,
 ) -> MmapMut {
     let exec_header = load_struct_inplace::<elf::FileHeader64<LE>>(exec_data, 0);
+    let arch = ElfArch::from_machine(exec_header.e_machine.get(LE));
     let ph_offset = exec_header.e_phoff.get(LE);
     let ph_ent_size = exec_header.e_phentsize.get(LE);
     let ph_num = exec_header.e_phnum.get(LE);
@@ -587,9 +833,10 @@ fn gen_elf_le(
 
     // Copy header and shift everything to enable more program sections.
     let added_header_count = 3;
+    let shift_alignment = arch.shift_alignment();
     md.added_byte_count = ph_ent_size as u64 * added_header_count;
-    md.added_byte_count = md.added_byte_count
-        + (MIN_SECTION_ALIGNMENT as u64 - md.added_byte_count % MIN_SECTION_ALIGNMENT as u64);
+    md.added_byte_count =
+        md.added_byte_count + (shift_alignment - md.added_byte_count % shift_alignment);
     let ph_end = ph_offset as usize + ph_num as usize * ph_ent_size as usize;
     let physical_shift_start = ph_end as u64;
 
@@ -727,18 +974,18 @@ fn gen_elf_le(
                 rel.r_offset.set(LE, r_offset + md.added_byte_count);
                 // Deal with potential adjusts to absolute jumps.
                 // TODO: Verify other relocation types.
-                if rel.r_type(LE, false) == elf::R_X86_64_RELATIVE {
+                if rel.r_type(LE, false) == arch.r_relative() {
                     let r_addend = rel.r_addend.get(LE);
                     rel.r_addend.set(LE, r_addend + md.added_byte_count as i64);
                 }
             }
             // If the relocation goes to a roc function, we need to surgically link it and change it to relative.
             let r_type = rel.r_type(LE, false);
-            if r_type == elf::R_X86_64_GLOB_DAT {
+            if r_type == arch.r_glob_dat() {
                 let r_sym = rel.r_sym(LE, false);
                 for (name, index) in got_app_syms.iter() {
                     if *index as u32 == r_sym {
-                        rel.set_r_info(LE, false, 0, elf::R_X86_64_RELATIVE);
+                        rel.set_r_info(LE, false, 0, arch.r_relative());
                         let addend_addr = sec_offset as usize
                             + i * mem::size_of::<elf::Rela64<LE>>()
                             // This 16 skips the first 2 fields and gets to the addend field.
@@ -747,6 +994,7 @@ fn gen_elf_le(
                             file_offset: addend_addr as u64,
                             virtual_offset: VirtualOffset::Absolute,
                             size: 8,
+                            kind: SurgeryKind::Value,
                         });
                     }
                 }
@@ -763,8 +1011,7 @@ fn gen_elf_le(
             .filter_map(|(i, rel)| {
                 let r_type = rel.r_type(LE, false);
                 let r_sym = rel.r_sym(LE, false);
-                if r_type == elf::R_X86_64_JUMP_SLOT && app_sym_indices.contains(&(r_sym as usize))
-                {
+                if r_type == arch.r_jump_slot() && app_sym_indices.contains(&(r_sym as usize)) {
                     Some(i)
                 } else {
                     None
@@ -780,7 +1027,7 @@ fn gen_elf_le(
         for i in to_remove.iter() {
             relocations.swap(*i, j);
             let r_sym = relocations[j].r_sym(LE, false);
-            relocations[j].set_r_info(LE, false, r_sym, elf::R_X86_64_NONE);
+            relocations[j].set_r_info(LE, false, r_sym, arch.r_none());
             j -= 1;
         }
 
@@ -949,6 +1196,7 @@ fn scan_elf_dynamic_deps(
     app_syms: &[Symbol],
     shared_lib: &Path,
     exec_data: &[u8],
+    arch: ElfArch,
     verbose: bool,
 ) -> ElfDynamicDeps {
     let dyn_sec = match exec_obj.section_by_name(".dynamic") {
@@ -1088,7 +1336,7 @@ fn scan_elf_dynamic_deps(
         }
     })
     .filter_map(|(_, reloc)| {
-        if let RelocationKind::Elf(elf::R_X86_64_GLOB_DAT) = reloc.kind() {
+        if reloc.kind() == RelocationKind::Elf(arch.r_glob_dat()) {
             for symbol in app_syms.iter() {
                 if reloc.target() == RelocationTarget::Symbol(symbol.index()) {
                     return Some((symbol.name().unwrap().to_string(), symbol.index().0));
@@ -1107,7 +1355,7 @@ fn scan_elf_dynamic_deps(
         }
     })
     .filter_map(|(_, reloc)| {
-        if let RelocationKind::Elf(elf::R_X86_64_JUMP_SLOT) = reloc.kind() {
+        if reloc.kind() == RelocationKind::Elf(arch.r_jump_slot()) {
             for symbol in app_syms.iter() {
                 if reloc.target() == RelocationTarget::Symbol(symbol.index()) {
                     return Some(symbol.index().0);
@@ -1228,6 +1476,7 @@ fn surgery_elf_help(
         internal_error!("Only 64bit little endian elf currently supported for surgery");
     }
     let exec_header = load_struct_inplace::<elf::FileHeader64<LE>>(exec_mmap, 0);
+    let arch = ElfArch::from_machine(exec_header.e_machine.get(LE));
 
     let ph_offset = exec_header.e_phoff.get(LE);
     let ph_ent_size = exec_header.e_phentsize.get(LE);
@@ -1440,6 +1689,36 @@ fn surgery_elf_help(
                     if let Some(target_offset) = target_offset {
                         let virt_base = section_virtual_offset + rel.0 as usize;
                         let base = section_offset + rel.0 as usize;
+
+                        let aarch64_fixup = match arch {
+                            ElfArch::X86_64 => None,
+                            ElfArch::Aarch64 => aarch64_fixup(&rel.1),
+                        };
+                        if let Some(fixup) = aarch64_fixup {
+                            let target = (target_offset + rel.1.addend()) as u64;
+                            if verbose {
+                                println!(
+                                    "\t\tRelocation base location: {base:+x} (virt: {virt_base:+x})",
+                                );
+                                println!("\t\tFinal relocation target address: {target:+x}");
+                            }
+                            let inst_bytes = &mut exec_mmap[base..][..aarch64::INSTRUCTION_SIZE];
+                            let inst = u32::from_le_bytes((*inst_bytes).try_into().unwrap());
+                            match fixup.apply(inst, virt_base as u64, target) {
+                                Some(inst) => inst_bytes.copy_from_slice(&inst.to_le_bytes()),
+                                None => {
+                                    internal_error!(
+                                        "Relocation target {:+x} is out of range for {:?} at {:+x}: {:+x?}",
+                                        target,
+                                        fixup,
+                                        virt_base,
+                                        rel
+                                    );
+                                }
+                            }
+                            continue;
+                        }
+
                         let target: i64 = match rel.1.kind() {
                             RelocationKind::Relative | RelocationKind::PltRelative => {
                                 target_offset - virt_base as i64 + rel.1.addend()
@@ -1639,6 +1918,22 @@ fn surgery_elf_help(
                 VirtualOffset::Relative(vs) => (vs + md.added_byte_count) as i64,
                 VirtualOffset::Absolute => 0,
             };
+            if s.kind == SurgeryKind::Aarch64Branch {
+                let inst_bytes = &mut exec_mmap[(s.file_offset + md.added_byte_count) as usize..]
+                    [..aarch64::INSTRUCTION_SIZE];
+                let inst = u32::from_le_bytes((*inst_bytes).try_into().unwrap());
+                let fixup = aarch64::Fixup::Branch26;
+                match fixup.apply(inst, surgery_virt_offset as u64, func_virt_offset) {
+                    Some(inst) => inst_bytes.copy_from_slice(&inst.to_le_bytes()),
+                    None => {
+                        // Too far for a direct branch. Keep calling the PLT, which jumps to the app.
+                        if verbose {
+                            println!("\tApp function is out of range, so the branch stays pointed at the PLT");
+                        }
+                    }
+                }
+                continue;
+            }
             match s.size {
                 4 => {
                     let target = (func_virt_offset as i64 - surgery_virt_offset) as i32;
@@ -1669,18 +1964,39 @@ fn surgery_elf_help(
         if let Some((plt_off, plt_vaddr)) = md.plt_addresses.get(func_name) {
             let plt_off = (*plt_off + md.added_byte_count) as usize;
             let plt_vaddr = *plt_vaddr + md.added_byte_count;
-            let jmp_inst_len = 5;
-            let target =
-                (func_virt_offset as i64 - (plt_vaddr as i64 + jmp_inst_len as i64)) as i32;
             if verbose {
                 println!("\tPLT: {plt_off:+x}, {plt_vaddr:+x}");
-                println!("\tTarget Jump: {target:+x}");
             }
-            let data = target.to_le_bytes();
-            exec_mmap[plt_off] = 0xE9;
-            exec_mmap[plt_off + 1..plt_off + jmp_inst_len].copy_from_slice(&data);
-            for i in jmp_inst_len..PLT_ADDRESS_OFFSET as usize {
-                exec_mmap[plt_off + i] = 0x90;
+            match arch {
+                ElfArch::X86_64 => {
+                    let jmp_inst_len = 5;
+                    let target =
+                        (func_virt_offset as i64 - (plt_vaddr as i64 + jmp_inst_len as i64)) as i32;
+                    if verbose {
+                        println!("\tTarget Jump: {target:+x}");
+                    }
+                    let data = target.to_le_bytes();
+                    exec_mmap[plt_off] = 0xE9;
+                    exec_mmap[plt_off + 1..plt_off + jmp_inst_len].copy_from_slice(&data);
+                    for i in jmp_inst_len..PLT_ADDRESS_OFFSET as usize {
+                        exec_mmap[plt_off + i] = 0x90;
+                    }
+                }
+                ElfArch::Aarch64 => {
+                    let instructions = aarch64::plt_entry_jump(plt_vaddr, func_virt_offset)
+                        .unwrap_or_else(|| {
+                            internal_error!(
+                                "App function {func_name} is out of range of its PLT entry"
+                            )
+                        });
+                    let plt_entry = &mut exec_mmap[plt_off..][..AARCH64_PLT_ENTRY_SIZE as usize];
+                    for (inst, bytes) in instructions
+                        .iter()
+                        .zip(plt_entry.chunks_exact_mut(aarch64::INSTRUCTION_SIZE))
+                    {
+                        bytes.copy_from_slice(&inst.to_le_bytes());
+                    }
+                }
             }
         }
 
@@ -1841,7 +2157,7 @@ mod tests {
                 .collect()
         };
 
-        let dylib_bytes = crate::generate_dylib::create_dylib_elf64(&names).unwrap();
+        let dylib_bytes = crate::generate_dylib::create_dylib_elf64(&names, target).unwrap();
         std::fs::write(dir.join("libapp.so"), dylib_bytes).unwrap();
 
         // now we can compile the host (it uses libapp.so, hence the order here)
@@ -1915,4 +2231,601 @@ mod tests {
 
         assert_eq!("Hello foo\n", output);
     }
+
+    /// A small position independent aarch64 executable, laid out the way a linker would:
+    /// `main` calls `roc__mainForHost_1_exposed` through the PLT, and the GOT has the address
+    /// of `roc__mainForHost_size`. Both of those come from libapp.so. The PLT also has an
+    /// entry for `puts` from libc, which should be left alone, and so should the two words
+    /// of data in .text that happen to look like calls to the app.
+    fn aarch64_host() -> Vec<u8> {
+        use object::write::elf::{FileHeader, ProgramHeader, Rel, SectionHeader, Sym, Writer};
+
+        let mut out_data = Vec::new();
+        let mut writer = Writer::new(object::Endianness::Little, true, &mut out_data);
+
+        writer.reserve_null_section_index();
+        let dynsym_index = writer.reserve_dynsym_section_index();
+        writer.reserve_dynstr_section_index();
+        let rela_dyn_name = writer.add_section_name(b".rela.dyn");
+        writer.reserve_section_index();
+        let rela_plt_name = writer.add_section_name(b".rela.plt");
+        writer.reserve_section_index();
+        let plt_name = writer.add_section_name(b".plt");
+        let plt_index = writer.reserve_section_index();
+        let text_name = writer.add_section_name(b".text");
+        let text_index = writer.reserve_section_index();
+        let got_name = writer.add_section_name(b".got");
+        writer.reserve_section_index();
+        let got_plt_name = writer.add_section_name(b".got.plt");
+        writer.reserve_section_index();
+        writer.reserve_dynamic_section_index();
+        writer.reserve_symtab_section_index();
+        writer.reserve_strtab_section_index();
+        writer.reserve_shstrtab_section_index();
+
+        let libapp = writer.add_dynamic_string(b"libapp.so");
+        let libc = writer.add_dynamic_string(b"libc.so.6");
+        let app_names = ["roc__mainForHost_1_exposed", "roc__mainForHost_size"];
+        writer.reserve_null_dynamic_symbol_index();
+        let dynsyms: Vec<_> = ["puts"]
+            .iter()
+            .chain(app_names.iter())
+            .map(|name| {
+                writer.reserve_dynamic_symbol_index();
+                writer.add_dynamic_string(name.as_bytes())
+            })
+            .collect();
+
+        writer.reserve_null_symbol_index();
+        writer.reserve_symbol_index(Some(text_index));
+        let data_mapping_sym = writer.add_string(b"$d");
+        let app_syms: Vec<_> = app_names
+            .iter()
+            .map(|name| {
+                writer.reserve_symbol_index(None);
+                writer.add_string(name.as_bytes())
+            })
+            .collect();
+        let host_funcs = [("main", 0, 8), ("roc_alloc", 8, 8)];
+        let host_syms: Vec<_> = host_funcs
+            .iter()
+            .map(|(name, _, _)| {
+                writer.reserve_symbol_index(Some(text_index));
+                writer.add_string(name.as_bytes())
+            })
+            .collect();
+
+        // Everything is in one segment, with the same file offsets and virtual addresses.
+        writer.reserve_file_header();
+        writer.reserve_program_headers(3);
+        let dynsym_address = writer.reserved_len() as u64;
+        writer.reserve_dynsym();
+        let dynstr_address = writer.reserved_len() as u64;
+        writer.reserve_dynstr();
+        let dynstr_size = writer.reserved_len() as u64 - dynstr_address;
+        let rela_size = mem::size_of::<elf::Rela64<LE>>() as u64;
+        let rela_dyn_address = writer.reserve_relocations(1, true) as u64;
+        let rela_plt_address = writer.reserve_relocations(2, true) as u64;
+        let plt_size = AARCH64_PLT_HEADER_SIZE + 2 * AARCH64_PLT_ENTRY_SIZE;
+        let plt_address = writer.reserve(plt_size as usize, 16) as u64;
+        let text_size = 20;
+        let text_address = writer.reserve(text_size, 4) as u64;
+        let got_address = writer.reserve(8, 8) as u64;
+        let got_plt_size = 5 * 8;
+        let got_plt_address = writer.reserve(got_plt_size, 8) as u64;
+
+        let dynamic = [
+            (elf::DT_NEEDED, 0, Some(libapp)),
+            (elf::DT_NEEDED, 0, Some(libc)),
+            (elf::DT_SYMTAB, dynsym_address, None),
+            (elf::DT_STRTAB, dynstr_address, None),
+            (elf::DT_STRSZ, dynstr_size, None),
+            (
+                elf::DT_SYMENT,
+                mem::size_of::<elf::Sym64<LE>>() as u64,
+                None,
+            ),
+            (elf::DT_RELA, rela_dyn_address, None),
+            (elf::DT_RELASZ, rela_size, None),
+            (elf::DT_RELAENT, rela_size, None),
+            (elf::DT_JMPREL, rela_plt_address, None),
+            (elf::DT_PLTRELSZ, 2 * rela_size, None),
+            (elf::DT_PLTREL, elf::DT_RELA as u64, None),
+            (elf::DT_PLTGOT, got_plt_address, None),
+            (elf::DT_NULL, 0, None),
+        ];
+        let dynamic_address = crate::pe::next_multiple_of(writer.reserved_len(), 8) as u64;
+        writer.reserve_dynamic(dynamic.len());
+        let load_size = writer.reserved_len() as u64;
+
+        writer.reserve_symtab();
+        writer.reserve_strtab();
+        writer.reserve_shstrtab();
+        writer.reserve_section_headers();
+
+        writer
+            .write_file_header(&FileHeader {
+                os_abi: 0,
+                abi_version: 0,
+                e_type: elf::ET_DYN,
+                e_machine: elf::EM_AARCH64,
+                e_entry: text_address,
+                e_flags: 0,
+            })
+            .unwrap();
+
+        writer.write_align_program_headers();
+        let ph_size = 3 * mem::size_of::<elf::ProgramHeader64<LE>>() as u64;
+        let dynamic_size = (dynamic.len() * mem::size_of::<elf::Dyn64<LE>>()) as u64;
+        for (p_type, p_flags, p_offset, p_size, p_align) in [
+            (elf::PT_PHDR, elf::PF_R, 0x40, ph_size, 8),
+            (
+                elf::PT_LOAD,
+                elf::PF_R | elf::PF_W | elf::PF_X,
+                0,
+                load_size,
+                0x10000,
+            ),
+            (
+                elf::PT_DYNAMIC,
+                elf::PF_R | elf::PF_W,
+                dynamic_address,
+                dynamic_size,
+                8,
+            ),
+        ] {
+            writer.write_program_header(&ProgramHeader {
+                p_type,
+                p_flags,
+                p_offset,
+                p_vaddr: p_offset,
+                p_paddr: p_offset,
+                p_filesz: p_size,
+                p_memsz: p_size,
+                p_align,
+            });
+        }
+
+        writer.write_null_dynamic_symbol();
+        for name in dynsyms.iter() {
+            writer.write_dynamic_symbol(&Sym {
+                name: Some(*name),
+                section: None,
+                st_info: (elf::STB_GLOBAL << 4) | elf::STT_FUNC,
+                st_other: 0,
+                st_shndx: 0,
+                st_value: 0,
+                st_size: 0,
+            });
+        }
+        writer.write_dynstr();
+
+        writer.write_align_relocation();
+        writer.write_relocation(
+            true,
+            &Rel {
+                r_offset: got_address,
+                r_sym: 3,
+                r_type: elf::R_AARCH64_GLOB_DAT,
+                r_addend: 0,
+            },
+        );
+        writer.write_align_relocation();
+        for (slot, r_sym) in [(3, 1), (4, 2)] {
+            writer.write_relocation(
+                true,
+                &Rel {
+                    r_offset: got_plt_address + slot * 8,
+                    r_sym,
+                    r_type: elf::R_AARCH64_JUMP_SLOT,
+                    r_addend: 0,
+                },
+            );
+        }
+
+        writer.write_align(16);
+        for _ in 0..plt_size / aarch64::INSTRUCTION_SIZE as u64 {
+            writer.write(&0xd503_201f_u32.to_le_bytes());
+        }
+
+        // main: bl roc__mainForHost_1_exposed@plt ; ret
+        // roc_alloc: ret ; a literal pool (after the $d mapping symbol)
+        // and then a word of data that isn't in any function
+        let plt_entry_address = plt_address + ElfArch::Aarch64.plt_entry_offset(1);
+        let bl_from = |offset: u64| {
+            aarch64::Fixup::Branch26
+                .apply(0x9400_0000, text_address + offset, plt_entry_address)
+                .unwrap()
+        };
+        writer.write_align(4);
+        for inst in [
+            bl_from(0),
+            0xd65f_03c0,
+            0xd65f_03c0,
+            bl_from(12),
+            bl_from(16),
+        ] {
+            writer.write(&u32::to_le_bytes(inst));
+        }
+
+        writer.write_align(8);
+        writer.write(&0u64.to_le_bytes());
+        for value in [dynamic_address, 0, 0, plt_address, plt_address] {
+            writer.write(&value.to_le_bytes());
+        }
+
+        writer.write_align_dynamic();
+        for (tag, val, string) in dynamic {
+            match string {
+                Some(string) => writer.write_dynamic_string(tag, string),
+                None => writer.write_dynamic(tag, val),
+            }
+        }
+
+        writer.write_null_symbol();
+        writer.write_symbol(&Sym {
+            name: Some(data_mapping_sym),
+            section: Some(text_index),
+            st_info: (elf::STB_LOCAL << 4) | elf::STT_NOTYPE,
+            st_other: 0,
+            st_shndx: 0,
+            st_value: text_address + 12,
+            st_size: 0,
+        });
+        for name in app_syms {
+            writer.write_symbol(&Sym {
+                name: Some(name),
+                section: None,
+                st_info: (elf::STB_GLOBAL << 4) | elf::STT_FUNC,
+                st_other: 0,
+                st_shndx: 0,
+                st_value: 0,
+                st_size: 0,
+            });
+        }
+        for (name, (_, offset, size)) in host_syms.into_iter().zip(host_funcs) {
+            writer.write_symbol(&Sym {
+                name: Some(name),
+                section: Some(text_index),
+                st_info: (elf::STB_GLOBAL << 4) | elf::STT_FUNC,
+                st_other: 0,
+                st_shndx: 0,
+                st_value: text_address + offset,
+                st_size: size,
+            });
+        }
+        writer.write_strtab();
+        writer.write_shstrtab();
+
+        writer.write_null_section_header();
+        writer.write_dynsym_section_header(dynsym_address, 1);
+        writer.write_dynstr_section_header(dynstr_address);
+        for (name, address, count) in [
+            (rela_dyn_name, rela_dyn_address, 1),
+            (rela_plt_name, rela_plt_address, 2),
+        ] {
+            writer.write_section_header(&SectionHeader {
+                name: Some(name),
+                sh_type: elf::SHT_RELA,
+                sh_flags: elf::SHF_ALLOC as u64,
+                sh_addr: address,
+                sh_offset: address,
+                sh_size: count * rela_size,
+                sh_link: dynsym_index.0,
+                sh_info: if name == rela_plt_name {
+                    plt_index.0
+                } else {
+                    0
+                },
+                sh_addralign: 8,
+                sh_entsize: rela_size,
+            });
+        }
+        for (name, address, size, flags, align) in [
+            (plt_name, plt_address, plt_size, elf::SHF_EXECINSTR, 16),
+            (
+                text_name,
+                text_address,
+                text_size as u64,
+                elf::SHF_EXECINSTR,
+                4,
+            ),
+            (got_name, got_address, 8, elf::SHF_WRITE, 8),
+            (
+                got_plt_name,
+                got_plt_address,
+                got_plt_size as u64,
+                elf::SHF_WRITE,
+                8,
+            ),
+        ] {
+            writer.write_section_header(&SectionHeader {
+                name: Some(name),
+                sh_type: elf::SHT_PROGBITS,
+                sh_flags: (elf::SHF_ALLOC | flags) as u64,
+                sh_addr: address,
+                sh_offset: address,
+                sh_size: size,
+                sh_link: 0,
+                sh_info: 0,
+                sh_addralign: align,
+                sh_entsize: 0,
+            });
+        }
+        writer.write_dynamic_section_header(dynamic_address);
+        writer.write_symtab_section_header(2);
+        writer.write_strtab_section_header();
+        writer.write_shstrtab_section_header();
+
+        debug_assert_eq!(writer.reserved_len(), writer.len());
+
+        out_data
+    }
+
+    /// `roc__mainForHost_1_exposed` uses each kind of instruction fixup we expect from
+    /// an aarch64 Roc app: data in .rodata, a call to the host, and a GOT lookup of a host symbol.
+    fn aarch64_app() -> Vec<u8> {
+        use object::write;
+
+        let mut app = write::Object::new(
+            object::BinaryFormat::Elf,
+            object::Architecture::Aarch64,
+            object::Endianness::Little,
+        );
+
+        let rodata = app.section_id(write::StandardSection::ReadOnlyData);
+        app.append_section_data(rodata, &[0; 16], 16);
+        let rodata_symbol = app.section_symbol(rodata);
+
+        let text = app.section_id(write::StandardSection::Text);
+        let code: Vec<u8> = APP_CODE
+            .iter()
+            .flat_map(|inst| inst.to_le_bytes())
+            .collect();
+        app.append_section_data(text, &code, 4);
+
+        for (name, value, size) in [
+            ("roc__mainForHost_1_exposed", 0, 28),
+            ("roc__mainForHost_size", 28, 4),
+        ] {
+            app.add_symbol(write::Symbol {
+                name: name.as_bytes().to_vec(),
+                value,
+                size,
+                kind: object::SymbolKind::Text,
+                scope: object::SymbolScope::Dynamic,
+                weak: false,
+                section: write::SymbolSection::Section(text),
+                flags: object::SymbolFlags::None,
+            });
+        }
+        let roc_alloc = app.add_symbol(write::Symbol {
+            name: b"roc_alloc".to_vec(),
+            value: 0,
+            size: 0,
+            kind: object::SymbolKind::Text,
+            scope: object::SymbolScope::Dynamic,
+            weak: false,
+            section: write::SymbolSection::Undefined,
+            flags: object::SymbolFlags::None,
+        });
+
+        let relocations = [
+            (0, elf::R_AARCH64_ADR_PREL_PG_HI21, rodata_symbol, 8),
+            (4, elf::R_AARCH64_ADD_ABS_LO12_NC, rodata_symbol, 8),
+            (8, elf::R_AARCH64_LDST64_ABS_LO12_NC, rodata_symbol, 8),
+            (12, elf::R_AARCH64_ADR_GOT_PAGE, roc_alloc, 0),
+            (16, elf::R_AARCH64_LD64_GOT_LO12_NC, roc_alloc, 0),
+        ];
+        for (offset, r_type, symbol, addend) in relocations {
+            app.add_relocation(
+                text,
+                write::Relocation {
+                    offset,
+                    size: 0,
+                    kind: RelocationKind::Elf(r_type),
+                    encoding: RelocationEncoding::Generic,
+                    symbol,
+                    addend,
+                },
+            )
+            .unwrap();
+        }
+        app.add_relocation(
+            text,
+            write::Relocation {
+                offset: 20,
+                size: 26,
+                kind: RelocationKind::PltRelative,
+                encoding: RelocationEncoding::AArch64Call,
+                symbol: roc_alloc,
+                addend: 0,
+            },
+        )
+        .unwrap();
+
+        app.write().unwrap()
+    }
+
+    // adrp x0, 0 ; add x0, x0, #0 ; ldr x1, [x0] ; adrp x2, 0 ; ldr x2, [x2] ; bl 0 ; ret ; ret
+    const APP_CODE: [u32; 8] = [
+        0x9000_0000,
+        0x9100_0000,
+        0xf940_0001,
+        0x9000_0002,
+        0xf940_0042,
+        0x9400_0000,
+        0xd65f_03c0,
+        0xd65f_03c0,
+    ];
+
+    #[test]
+    fn aarch64_host_app() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path();
+
+        let host = aarch64_host();
+        std::fs::write(dir.join("host"), &host).unwrap();
+
+        let preprocessed_host_filename = dir.join(preprocessed_host_filename(Target::LinuxArm64));
+
+        preprocess_elf_le(
+            &dir.join("host"),
+            &dir.join("metadata"),
+            &preprocessed_host_filename,
+            &dir.join("libapp.so"),
+            false,
+            false,
+        );
+
+        std::fs::copy(&preprocessed_host_filename, dir.join("final")).unwrap();
+
+        surgery_elf(
+            &aarch64_app(),
+            &dir.join("metadata"),
+            &dir.join("final"),
+            false,
+            false,
+        );
+
+        let final_bytes = std::fs::read(dir.join("final")).unwrap();
+        let final_obj = object::File::parse(&*final_bytes).unwrap();
+        assert_eq!(final_obj.architecture(), object::Architecture::Aarch64);
+
+        let host_obj = object::File::parse(&*host).unwrap();
+        let shift = aarch64::ADRP_PAGE_SIZE;
+        let section_address = |name: &str| {
+            let before = host_obj.section_by_name(name).unwrap().address();
+            let after = final_obj.section_by_name(name).unwrap().address();
+            assert_eq!(after, before + shift, "{name} should move by a whole page");
+            after
+        };
+        let symbol_address = |name: &str| {
+            final_obj
+                .dynamic_symbols()
+                .chain(final_obj.symbols())
+                .find(|sym| sym.name() == Ok(name))
+                .unwrap()
+                .address()
+        };
+
+        let exposed = symbol_address("roc__mainForHost_1_exposed");
+        let size = symbol_address("roc__mainForHost_size");
+        let roc_alloc = symbol_address("roc_alloc");
+        assert_eq!(
+            roc_alloc,
+            host_obj
+                .symbols()
+                .find(|sym| sym.name() == Ok("roc_alloc"))
+                .unwrap()
+                .address()
+                + shift
+        );
+
+        // The app is appended in new sections: rodata, then bss, then text
+        let sections: Vec<_> = final_obj.sections().collect();
+        let rodata = &sections[sections.len() - 3];
+        let text = &sections[sections.len() - 1];
+        assert_eq!(size, exposed + 28);
+        assert!(text.address() <= exposed && exposed + 32 <= text.address() + text.size());
+
+        // The host calls the app directly
+        let host_text = final_obj.section_by_name(".text").unwrap();
+        let bl = u32::from_le_bytes(host_text.data().unwrap()[..4].try_into().unwrap());
+        let text_address = section_address(".text");
+        assert_eq!(
+            text_address.wrapping_add(aarch64::branch26_offset(bl).unwrap() as u64),
+            exposed
+        );
+
+        // Data that looks like a call to the app is left alone
+        let original_text = host_obj.section_by_name(".text").unwrap();
+        assert_eq!(
+            host_text.data().unwrap()[12..],
+            original_text.data().unwrap()[12..]
+        );
+
+        // The PLT entry also jumps to the app, and the one for puts is unchanged
+        let plt_entry_offset = ElfArch::Aarch64.plt_entry_offset(1);
+        let plt_entry_address = section_address(".plt") + plt_entry_offset;
+        let plt = final_obj.section_by_name(".plt").unwrap();
+        let host_plt = host_obj.section_by_name(".plt").unwrap();
+        assert_eq!(
+            plt.data().unwrap()[..plt_entry_offset as usize],
+            host_plt.data().unwrap()[..plt_entry_offset as usize]
+        );
+        let plt_entry: Vec<u32> = plt.data().unwrap()[plt_entry_offset as usize..]
+            .chunks_exact(aarch64::INSTRUCTION_SIZE)
+            .map(|bytes| u32::from_le_bytes(bytes.try_into().unwrap()))
+            .collect();
+        assert_eq!(
+            plt_entry,
+            aarch64::plt_entry_jump(plt_entry_address, exposed).unwrap()
+        );
+
+        // The app's instructions refer to the right addresses
+        let data = rodata.address() + 8;
+        let app_code: Vec<u32> = text.data().unwrap()[(exposed - text.address()) as usize..][..32]
+            .chunks_exact(aarch64::INSTRUCTION_SIZE)
+            .map(|bytes| u32::from_le_bytes(bytes.try_into().unwrap()))
+            .collect();
+        let fixups = [
+            (aarch64::Fixup::Adrp, data),
+            (aarch64::Fixup::AddLo12, data),
+            (aarch64::Fixup::LoadStoreLo12 { scale: 3 }, data),
+            (aarch64::Fixup::Adrp, roc_alloc),
+            (aarch64::Fixup::GotLoadLo12, roc_alloc),
+            (aarch64::Fixup::Branch26, roc_alloc),
+        ];
+        for (i, (fixup, target)) in fixups.into_iter().enumerate() {
+            let pc = exposed + (i * aarch64::INSTRUCTION_SIZE) as u64;
+            let expected = fixup.apply(APP_CODE[i], pc, target).unwrap();
+            assert_eq!(app_code[i], expected, "instruction {i} ({fixup:?})");
+        }
+        assert_eq!(app_code[6..], APP_CODE[6..]);
+
+        // The GOT entry is now relative to the load address, and only puts has a JUMP_SLOT
+        let got_address = section_address(".got");
+        let got_plt_address = section_address(".got.plt");
+        let dynamic_relocations: Vec<_> = final_obj
+            .dynamic_relocations()
+            .unwrap()
+            .map(|(offset, reloc)| (offset, reloc.kind(), reloc.addend()))
+            .collect();
+        assert_eq!(
+            dynamic_relocations,
+            [
+                (
+                    got_address,
+                    RelocationKind::Elf(elf::R_AARCH64_RELATIVE),
+                    size as i64
+                ),
+                (
+                    got_plt_address + 3 * 8,
+                    RelocationKind::Elf(elf::R_AARCH64_JUMP_SLOT),
+                    0
+                ),
+            ]
+        );
+
+        // libapp.so is no longer needed
+        let dynstr = final_obj.section_by_name(".dynstr").unwrap();
+        let dynstr = dynstr.data().unwrap();
+        let dynamic = final_obj.section_by_name(".dynamic").unwrap();
+        let mut needed = vec![];
+        for entry in dynamic.data().unwrap().chunks_exact(16) {
+            let tag = u64::from_le_bytes(entry[..8].try_into().unwrap());
+            let val = u64::from_le_bytes(entry[8..].try_into().unwrap());
+            match tag as u32 {
+                elf::DT_NULL => break,
+                elf::DT_NEEDED => {
+                    let name = &dynstr[val as usize..];
+                    needed.push(CStr::from_bytes_until_nul(name).unwrap().to_str().unwrap());
+                }
+                elf::DT_PLTRELSZ => assert_eq!(val, mem::size_of::<elf::Rela64<LE>>() as u64),
+                _ => {}
+            }
+        }
+        assert_eq!(needed, ["libc.so.6"]);
+    }
 }
@@ -1,10 +1,23 @@
 use object::{elf, Endianness};
+use roc_target::Target;
 
 use crate::pe::next_multiple_of;
 
-pub fn create_dylib_elf64(custom_names: &[String]) -> object::read::Result<Vec<u8>> {
+pub fn create_dylib_elf64(
+    custom_names: &[String],
+    target: Target,
+) -> object::read::Result<Vec<u8>> {
     let endian = Endianness::Little;
 
+    let e_machine = match target.architecture() {
+        roc_target::Architecture::X86_64 => elf::EM_X86_64,
+        roc_target::Architecture::Aarch64 => elf::EM_AARCH64,
+        _ => {
+            // We should have verified this via supported() before calling this function
+            unreachable!()
+        }
+    };
+
     let mut out_data = Vec::new();
     let mut writer = object::write::elf::Writer::new(endian, true, &mut out_data);
 
@@ -145,7 +158,7 @@ pub fn create_dylib_elf64(custom_names: &[String]) -> object::read::Result<Vec<u
             os_abi: 0,
             abi_version: 0,
             e_type: 3,
-            e_machine: 62,
+            e_machine,
             e_entry: 0x1000,
             e_flags: 0,
         })
@@ -5,6 +5,7 @@
 //! practical to use a regular linker.
 use memmap2::{Mmap, MmapMut};
 use object::Object;
+use roc_builtins::bitcode;
 use roc_error_macros::internal_error;
 use roc_load::{EntryPoint, ExecutionMode, ExposedToHost, LoadConfig, Threading};
 use roc_module::symbol::Interns;
@@ -16,6 +17,7 @@ use std::cmp::Ordering;
 use std::mem;
 use std::path::{Path, PathBuf};
 
+mod aarch64;
 mod elf;
 mod macho;
 mod pe;
@@ -34,6 +36,7 @@ pub fn supported(link_type: LinkType, target: Target) -> bool {
     if let LinkType::Executable = link_type {
         match target {
             Target::LinuxX64 => true,
+            Target::LinuxArm64 => true,
             Target::WinX64 => true,
             // macho support is incomplete
             Target::MacX64 => false,
@@ -197,6 +200,9 @@ impl ExposedSymbols {
             custom_names.extend([format!("roc__{sym}_{id}_caller")]);
         }
 
+        // Hosts that build Dicts (e.g. with roc_std's RocDict) need the seed Roc hashes keys with
+        custom_names.push(bitcode::UTILS_DICT_PSEUDO_SEED.to_string());
+
         // on windows (PE) binary search is used on the symbols,
         // so they must be in alphabetical order
         custom_names.sort_unstable();
@@ -223,6 +229,9 @@ impl ExposedSymbols {
             }
         }
 
+        // Hosts that build Dicts (e.g. with roc_std's RocDict) need the seed Roc hashes keys with
+        custom_names.push(bitcode::UTILS_DICT_PSEUDO_SEED.to_string());
+
         // on windows (PE) binary search is used on the symbols,
         // so they must be in alphabetical order
         custom_names.sort_unstable();
@@ -316,23 +325,20 @@ fn generate_def_file(custom_names: &[String]) -> Result<String, std::fmt::Error>
 }
 
 fn object_matches_target<'a>(target: Target, object: &object::File<'a, &'a [u8]>) -> bool {
-    match target.architecture() {
-        Architecture::X86_64 => {
-            if object.architecture() != object::Architecture::X86_64 {
-                return false;
-            }
+    let target_architecture = match target.architecture() {
+        Architecture::X86_64 => object::Architecture::X86_64,
+        Architecture::Aarch64 => object::Architecture::Aarch64,
+        _ => return false,
+    };
 
-            let target_format = match target.operating_system() {
-                OperatingSystem::Linux => object::BinaryFormat::Elf,
-                OperatingSystem::Windows => object::BinaryFormat::Pe,
-                _ => todo!("surgical linker does not support target {:?}", target),
-            };
+    let target_format = match target.operating_system() {
+        OperatingSystem::Linux => object::BinaryFormat::Elf,
+        OperatingSystem::Mac => object::BinaryFormat::MachO,
+        OperatingSystem::Windows => object::BinaryFormat::Pe,
+        OperatingSystem::Freestanding => return false,
+    };
 
-            object.format() == target_format
-        }
-        Architecture::Aarch64 => object.architecture() == object::Architecture::Aarch64,
-        _ => todo!("surgical linker does not support target {:?}", target),
-    }
+    object.architecture() == target_architecture && object.format() == target_format
 }
 
 /// Checks whether the stub `.dll/.so` is up to date, in other words that it exports exactly the
@@ -1,11 +1,13 @@
 #[cfg(not(target_family = "wasm"))]
 use {
+    crate::cached,
     crate::https::{self, PackageMetadata, Problem},
+    crate::mirror::Mirrors,
     roc_error_macros::internal_error,
     std::fs,
 };
 #[cfg(not(target_family = "wasm"))]
-const MAX_DOWNLOAD_BYTES: u64 = 32 * 1_000_000_000; // GB
+pub(crate) const MAX_DOWNLOAD_BYTES: u64 = 32 * 1_000_000_000; // GB
 
 use std::path::{Path, PathBuf};
 
@@ -59,7 +61,9 @@ fn nixos_error_if_dynamic(url: &str, dest_dir: &Path) {
 /// into that dir. If the cache dir on the filesystem, then look into it to see if we already
 /// have an entry for the given URL. If we do, return its info. If we don't already have it, then:
 ///
-/// - Download and decompress the compressed tarball from the given URL
+/// - Download and decompress the compressed tarball from the given URL, or from the mirror that
+///   the configured [Mirrors] rewrite it to. If `vendor_dir` has a tarball for it that
+///   `roc vendor` saved, decompress that one instead
 /// - Verify its bytes against the hash in the URL
 /// - Extract the tarball's contents into the appropriate cache directory
 ///
@@ -69,6 +73,7 @@ fn nixos_error_if_dynamic(url: &str, dest_dir: &Path) {
 pub fn install_package<'a>(
     roc_cache_dir: RocCacheDir<'_>,
     url: &'a str,
+    vendor_dir: Option<&Path>,
 ) -> Result<(PathBuf, Option<&'a str>), Problem> {
     use std::io::ErrorKind;
 
@@ -88,23 +93,56 @@ pub fn install_package<'a>(
             if dest_dir.exists() {
                 // If the cache dir exists already, we assume it has the correct contents
                 // (it's a cache, after all!) and return without downloading anything.
+                // `roc cache verify` can check that assumption.
                 //
                 #[cfg(target_os = "linux")]
                 {
                     nixos_error_if_dynamic(url, &dest_dir);
                 }
 
+                cached::record_use(&dest_dir);
+
                 Ok((dest_dir, root_module_filename))
             } else {
                 // Download into a tempdir; only move it to dest_dir if hash verification passes.
-                println!(
-                    "Downloading \u{001b}[36m{url}\u{001b}[0m\n    into {}\n",
-                    cache_dir.display()
-                );
                 let tempdir = tempfile::tempdir().map_err(Problem::IoErr)?;
                 let tempdir_path = tempdir.path();
-                let downloaded_hash =
-                    https::download_and_hash(url, tempdir_path, MAX_DOWNLOAD_BYTES)?;
+                let saved_tarball =
+                    vendor_dir.and_then(|dir| https::find_saved_tarball(dir, content_hash));
+
+                let (downloaded_hash, index) = match saved_tarball {
+                    Some(tarball) => {
+                        // Vendored tarballs get verified just like downloaded ones, since they
+                        // could have been changed since they were vendored.
+                        println!(
+                            "Installing \u{001b}[36m{url}\u{001b}[0m\n    from {}\n    into {}\n",
+                            tarball.display(),
+                            cache_dir.display()
+                        );
+                        https::unpack_and_hash(&tarball, tempdir_path)?
+                    }
+                    None => {
+                        let source = Mirrors::load()?.rewrite(url);
+
+                        if source == url {
+                            println!(
+                                "Downloading \u{001b}[36m{url}\u{001b}[0m\n    into {}\n",
+                                cache_dir.display()
+                            );
+                        } else {
+                            // The mirror gets the same scrutiny as the original URL would have.
+                            PackageMetadata::try_from(source.as_ref())
+                                .map_err(Problem::InvalidUrl)?;
+
+                            println!(
+                                "Downloading \u{001b}[36m{url}\u{001b}[0m\n    from {source}\n    into {}\n",
+                                cache_dir.display()
+                            );
+                        }
+
+                        https::download_and_hash(&source, tempdir_path, MAX_DOWNLOAD_BYTES)?
+                    }
+                };
 
                 // Download the tarball into memory and verify it.
                 // The tarball name is the hash of its contents.
@@ -165,6 +203,12 @@ pub fn install_package<'a>(
                         nixos_error_if_dynamic(url, &dest_dir);
                     }
 
+                    if let Some(index) = index {
+                        cached::save_index(&dest_dir, &index).map_err(Problem::IoErr)?;
+                    }
+
+                    cached::record_use(&dest_dir);
+
                     // The package's files are now in the cache. We're done!
                     Ok((dest_dir, root_module_filename))
                 } else {
@@ -186,6 +230,12 @@ pub fn install_package<'a>(
     }
 }
 
+/// Whether a package name in a header is a URL to install into the cache, as opposed to a path on
+/// the filesystem.
+pub fn is_package_url(package_name: &str) -> bool {
+    package_name.starts_with("https://") || package_name.starts_with("file://")
+}
+
 #[cfg(windows)]
 // e.g. the "Roc" in %APPDATA%\\Roc
 const ROC_CACHE_DIR_NAME: &str = "Roc";
@@ -0,0 +1,292 @@
+//! Looking after the packages that [install_package](crate::cache::install_package) puts in the
+//! cache: listing them, checking that their files still match their hashes, and removing the
+//! ones that aren't needed anymore.
+//!
+//! Next to each package's directory (which is named after its content hash) the cache keeps a
+//! `.index` file, which [index::verify] uses to re-hash the package, and a `.last-used` file with
+//! the last time it was installed or used, in seconds since the Unix epoch.
+
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::cache::is_package_url;
+use crate::https::PackageMetadata;
+use crate::index::{self, Corruption, PackageIndex};
+use crate::vendor::package_names;
+
+const INDEX_EXT: &str = "index";
+const LAST_USED_EXT: &str = "last-used";
+
+/// The length of a base64url-encoded BLAKE3 hash, without padding.
+const CONTENT_HASH_LEN: usize = 43;
+
+#[derive(Debug)]
+pub struct CachedPackage {
+    /// e.g. ~/.cache/roc/packages/example.com/roc-packages/jDRlAFAA3738vu3-vMpLUoyxtA86Z7CaZneoOKrihbE
+    pub dir: PathBuf,
+    /// The total size of the package's files, in bytes.
+    pub size: u64,
+    /// None if the package has never been used, and it's not known when it was installed either.
+    pub last_used: Option<SystemTime>,
+}
+
+#[derive(Debug)]
+pub enum UsageProblem {
+    /// Reading this file or directory failed.
+    IoErr(PathBuf, io::Error),
+    /// The header of the module at this path could not be parsed.
+    InvalidHeader(PathBuf),
+}
+
+impl CachedPackage {
+    pub fn content_hash(&self) -> &str {
+        self.dir
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or_default()
+    }
+
+    /// Re-hash the package's files, and return everything that's different from when it was
+    /// installed. Returns None if it was installed without an index (e.g. by an older roc), and
+    /// so can't be verified.
+    pub fn verify(&self) -> io::Result<Option<Vec<Corruption>>> {
+        match PackageIndex::read(&sidecar(&self.dir, INDEX_EXT))? {
+            Some(index) => index::verify(&index, &self.dir, self.content_hash()).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    pub fn remove(&self) -> io::Result<()> {
+        fs::remove_dir_all(&self.dir)?;
+
+        for ext in [INDEX_EXT, LAST_USED_EXT] {
+            match fs::remove_file(sidecar(&self.dir, ext)) {
+                Err(err) if err.kind() != io::ErrorKind::NotFound => return Err(err),
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Whether the package hasn't been used since `duration` ago.
+    pub fn unused_for(&self, duration: Duration) -> bool {
+        match self.last_used {
+            Some(last_used) => SystemTime::now()
+                .duration_since(last_used)
+                .is_ok_and(|elapsed| elapsed >= duration),
+            None => true,
+        }
+    }
+}
+
+/// Every package in `cache_dir`, in order of their paths.
+pub fn cached_packages(cache_dir: &Path) -> io::Result<Vec<CachedPackage>> {
+    let mut packages = Vec::new();
+
+    if !cache_dir.exists() {
+        return Ok(packages);
+    }
+
+    let mut entries = walkdir::WalkDir::new(cache_dir)
+        .min_depth(1)
+        .sort_by_file_name()
+        .into_iter();
+
+    while let Some(entry) = entries.next() {
+        let entry = entry.map_err(io::Error::from)?;
+        let is_package =
+            entry.file_type().is_dir() && entry.file_name().to_str().is_some_and(is_content_hash);
+
+        if is_package {
+            entries.skip_current_dir();
+
+            let dir = entry.into_path();
+
+            packages.push(CachedPackage {
+                size: dir_size(&dir)?,
+                last_used: last_used(&dir),
+                dir,
+            });
+        }
+    }
+
+    Ok(packages)
+}
+
+/// The directories in `cache_dir` of the packages that the Roc files in `paths` use, including
+/// the packages those packages use in turn. Each path can be a .roc file, or a directory to look
+/// for .roc files in.
+pub fn packages_used_by(
+    cache_dir: &Path,
+    paths: &[PathBuf],
+) -> Result<HashSet<PathBuf>, UsageProblem> {
+    let mut stack = Vec::new();
+
+    for path in paths {
+        if path.is_dir() {
+            for entry in walkdir::WalkDir::new(path) {
+                let entry = entry.map_err(|err| UsageProblem::IoErr(path.clone(), err.into()))?;
+
+                if entry.path().extension().is_some_and(|ext| ext == "roc") {
+                    stack.push(entry.into_path());
+                }
+            }
+        } else {
+            stack.push(path.clone());
+        }
+    }
+
+    let mut used = HashSet::new();
+    let mut visited = HashSet::new();
+
+    while let Some(module_path) = stack.pop() {
+        if !visited.insert(module_path.clone()) {
+            continue;
+        }
+
+        let names = package_names(&module_path)
+            .map_err(|err| UsageProblem::IoErr(module_path.clone(), err))?
+            .ok_or_else(|| UsageProblem::InvalidHeader(module_path.clone()))?;
+        let module_dir = module_path.parent().unwrap_or_else(|| Path::new("."));
+
+        for name in names {
+            if !is_package_url(&name) {
+                stack.push(module_dir.join(name));
+                continue;
+            }
+
+            // A package with an invalid URL can't have been installed.
+            if let Ok(PackageMetadata {
+                cache_subdir,
+                content_hash,
+                root_module_filename,
+            }) = PackageMetadata::try_from(name.as_str())
+            {
+                let package_dir = cache_dir.join(cache_subdir).join(content_hash);
+                let root_module = package_dir.join(root_module_filename.unwrap_or("main.roc"));
+
+                if root_module.exists() {
+                    stack.push(root_module);
+                }
+
+                used.insert(package_dir);
+            }
+        }
+    }
+
+    Ok(used)
+}
+
+/// Save the index of a package that was just installed into `package_dir`.
+pub(crate) fn save_index(package_dir: &Path, index: &PackageIndex) -> io::Result<()> {
+    index.write(&sidecar(package_dir, INDEX_EXT))
+}
+
+/// Note that the package in `package_dir` was used just now. This is only for telling which
+/// packages can be removed, so it's fine if it fails - e.g. because the cache is read-only.
+pub(crate) fn record_use(package_dir: &Path) {
+    if let Ok(now) = SystemTime::now().duration_since(UNIX_EPOCH) {
+        let _ = fs::write(
+            sidecar(package_dir, LAST_USED_EXT),
+            now.as_secs().to_string(),
+        );
+    }
+}
+
+fn last_used(package_dir: &Path) -> Option<SystemTime> {
+    let recorded = fs::read_to_string(sidecar(package_dir, LAST_USED_EXT))
+        .ok()
+        .and_then(|secs| secs.trim().parse().ok())
+        .map(|secs| UNIX_EPOCH + Duration::from_secs(secs));
+
+    // Packages installed before uses were recorded have at least been used since they were
+    // installed, which is about when their directory was last modified.
+    recorded.or_else(|| {
+        fs::metadata(package_dir)
+            .and_then(|meta| meta.modified())
+            .ok()
+    })
+}
+
+/// e.g. the jDRlAFAA3738vu3-vMpLUoyxtA86Z7CaZneoOKrihbE.index next to a package's directory
+fn sidecar(package_dir: &Path, ext: &str) -> PathBuf {
+    let mut name = package_dir.file_name().unwrap_or_default().to_os_string();
+    name.push(".");
+    name.push(ext);
+
+    package_dir.with_file_name(name)
+}
+
+fn is_content_hash(name: &str) -> bool {
+    name.len() == CONTENT_HASH_LEN
+        && name
+            .bytes()
+            .all(|byte| byte.is_ascii_alphanumeric() || byte == b'-' || byte == b'_')
+}
+
+fn dir_size(dir: &Path) -> io::Result<u64> {
+    let mut size = 0;
+
+    for entry in walkdir::WalkDir::new(dir) {
+        let entry = entry.map_err(io::Error::from)?;
+
+        if entry.file_type().is_file() {
+            size += entry.metadata().map_err(io::Error::from)?.len();
+        }
+    }
+
+    Ok(size)
+}
+
+#[test]
+fn list_and_remove_cached_packages() {
+    const PACKAGE_MAIN: &str = "package \"pkg\"\n    exposes []\n    packages {}\n";
+    let cache_dir = tempfile::tempdir().unwrap();
+    let used_hash = "c7hZdtfwvnzPgwzZD1dxNHQ_H4qzBPe7EOpNOtDsHLY";
+    let unused_hash = "jDRlAFAA3738vu3-vMpLUoyxtA86Z7CaZneoOKrihbE";
+    let subdir = cache_dir.path().join("example.com").join("roc-packages");
+
+    for hash in [used_hash, unused_hash] {
+        fs::create_dir_all(subdir.join(hash)).unwrap();
+        fs::write(subdir.join(hash).join("main.roc"), PACKAGE_MAIN).unwrap();
+    }
+
+    record_use(&subdir.join(used_hash));
+
+    let app_dir = tempfile::tempdir().unwrap();
+    fs::write(
+        app_dir.path().join("main.roc"),
+        format!(
+            "app \"example\"\n    packages {{ pf: \"https://example.com/roc-packages/{used_hash}.tar.br\" }}\n    imports []\n    provides [main] to pf\n"
+        ),
+    )
+    .unwrap();
+
+    let packages = cached_packages(cache_dir.path()).unwrap();
+    let hashes: Vec<&str> = packages.iter().map(CachedPackage::content_hash).collect();
+
+    assert_eq!(hashes, [used_hash, unused_hash]);
+    assert_eq!(packages[0].size, PACKAGE_MAIN.len() as u64);
+    assert!(!packages[0].unused_for(Duration::from_secs(60)));
+    assert_eq!(packages[0].verify().unwrap(), None);
+
+    let used = packages_used_by(cache_dir.path(), &[app_dir.path().to_path_buf()]).unwrap();
+
+    for package in packages.iter() {
+        if !used.contains(&package.dir) {
+            package.remove().unwrap();
+        }
+    }
+
+    let remaining = cached_packages(cache_dir.path()).unwrap();
+
+    assert_eq!(remaining.len(), 1);
+    assert_eq!(remaining[0].content_hash(), used_hash);
+    assert!(!subdir
+        .join(format!("{unused_hash}.{LAST_USED_EXT}"))
+        .exists());
+}
@@ -1,12 +1,15 @@
 use std::{
+    fs::File,
     io::{self, Read, Write},
-    path::Path,
+    path::{Path, PathBuf},
 };
 
+use crate::index::{self, PackageIndex};
 use crate::tarball::Compression;
 
 // gzip should be the most widely supported, and brotli offers the highest compression.
 // flate2 gets us both gzip and deflate, so there's no harm in offering deflate too.
+// zstd compresses almost as well as brotli, but much faster, which matters for big platforms.
 //
 // Here are all the officially supported options: https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Accept-Encoding
 // We can consider supporting more, but that would bloat the `roc` binary more, so
@@ -28,7 +31,8 @@ pub struct PackageMetadata<'a> {
 /// - .tar
 /// - .tar.gz
 /// - .tar.br
-const VALID_EXTENSION_SUFFIXES: [&str; 2] = [".gz", ".br"];
+/// - .tar.zst
+const VALID_EXTENSION_SUFFIXES: [&str; 3] = [".gz", ".br", ".zst"];
 
 /// Since the TLD (top level domain) `.zip` is now available, there is a new attack
 /// vector where malicous URLs can be used to confuse the reader.
@@ -45,6 +49,11 @@ const MISLEADING_CHARACTERS_IN_URL: [char; 5] = [
     '\u{29F8}', // U+29F8 == ⧸ Big Solidus
 ];
 
+const FILE_URL_PREFIX: &str = "file://";
+
+/// The cache subdirectory for packages from file:// URLs
+const FILE_CACHE_SUBDIR: &str = "file";
+
 #[derive(Debug, PartialEq, Eq)]
 pub enum UrlProblem {
     InvalidExtensionSuffix(String),
@@ -65,12 +74,15 @@ impl<'a> TryFrom<&'a str> for PackageMetadata<'a> {
 
 impl<'a> PackageMetadata<'a> {
     fn new(url: &'a str) -> Result<Self, UrlProblem> {
-        // First, verify that the URL starts with https://
-        let without_protocol = match url.split_once("https://") {
-            Some((_, without_protocol)) => without_protocol,
-            None => {
-                return Err(UrlProblem::MissingHttps);
-            }
+        // First, verify that the URL starts with https:// - or file://, for tarballs on this machine
+        let (without_protocol, is_file) = match url.split_once("https://") {
+            Some((_, without_protocol)) => (without_protocol, false),
+            None => match url.strip_prefix(FILE_URL_PREFIX) {
+                Some(without_protocol) => (without_protocol, true),
+                None => {
+                    return Err(UrlProblem::MissingHttps);
+                }
+            },
         };
 
         // Next, check if there are misleading characters in the URL
@@ -119,8 +131,12 @@ impl<'a> PackageMetadata<'a> {
             }
         };
 
+        // Local paths can't be used as cache subdirectories the way hosts can, since they may be
+        // absolute. Packages are content-addressed, so they don't need to be kept apart anyway.
+        let cache_subdir = if is_file { FILE_CACHE_SUBDIR } else { path };
+
         Ok(PackageMetadata {
-            cache_subdir: path,
+            cache_subdir,
             content_hash: tarball_name,
             root_module_filename: fragment,
         })
@@ -226,6 +242,28 @@ fn url_with_fragment() {
     );
 }
 
+#[test]
+fn file_url() {
+    let expected = Ok(PackageMetadata {
+        cache_subdir: "file",
+        content_hash: "hash",
+        root_module_filename: Some("filename.roc"),
+    });
+    assert_eq!(
+        PackageMetadata::try_from("file:///srv/tarballs/hash.tar.br#filename.roc"),
+        expected
+    );
+}
+
+#[test]
+fn url_problem_file_url_missing_hash() {
+    let expected = Err(UrlProblem::MissingHash);
+    assert_eq!(
+        PackageMetadata::try_from("file:///srv/tarballs/.tar"),
+        expected
+    );
+}
+
 #[derive(Debug)]
 pub enum Problem {
     UnsupportedEncoding(String),
@@ -241,13 +279,97 @@ pub enum Problem {
     /// The Content-Length header of the response exceeded max_download_bytes
     DownloadTooBig(u64),
     NotFound,
+    /// A package mirror rule from `source` (a config file or environment variable) was malformed
+    InvalidMirror {
+        source: String,
+        rule: String,
+    },
 }
 
+/// Download the tarball at `url` and unpack it into `dest_dir`. Returns the base64url-encoded
+/// BLAKE3 hash of its contents, along with its [PackageIndex] if it could be indexed.
 pub fn download_and_hash(
     url: &str,
     dest_dir: &Path,
     max_download_bytes: u64,
-) -> Result<String, Problem> {
+) -> Result<(String, Option<PackageIndex>), Problem> {
+    let (encoding, resp) = download(url, max_download_bytes)?;
+
+    decompress_into(dest_dir, encoding, resp)
+}
+
+/// Like [download_and_hash], but also writes the response body to `tarball` exactly as it was
+/// downloaded, so that it can be unpacked again later without the network by [unpack_and_hash].
+/// Returns the hash along with the extension that the saved tarball's filename should end in.
+pub fn download_hash_and_save(
+    url: &str,
+    dest_dir: &Path,
+    tarball: impl Write,
+    max_download_bytes: u64,
+) -> Result<(String, &'static str), Problem> {
+    let (encoding, resp) = download(url, max_download_bytes)?;
+    let mut tee = TeeReader {
+        reader: resp,
+        writer: tarball,
+    };
+
+    let (hash, _) = decompress_into(dest_dir, encoding, &mut tee)?;
+
+    // Decompressors can stop reading before the end, e.g. of a gzip trailer, but the saved
+    // tarball needs every byte.
+    io::copy(&mut tee, &mut io::sink()).map_err(Problem::IoErr)?;
+
+    Ok((hash, encoding.saved_tarball_ext()))
+}
+
+/// Returns the tarball that was saved in `dir` for the package with the given content hash,
+/// if there is one.
+pub fn find_saved_tarball(dir: &Path, content_hash: &str) -> Option<PathBuf> {
+    SAVED_TARBALL_EXTENSIONS
+        .iter()
+        .map(|(ext, _)| dir.join(format!("{content_hash}{ext}")))
+        .find(|path| path.is_file())
+}
+
+/// Whether `filename` is the name of a tarball that [download_hash_and_save] saved.
+pub fn is_saved_tarball_name(filename: &str) -> bool {
+    Encoding::from_saved_tarball_name(filename).is_some()
+}
+
+/// Unpack a tarball saved by [download_hash_and_save] into `dest_dir`, and return the
+/// base64url-encoded BLAKE3 hash of its contents - the same hash and index [download_and_hash]
+/// returns.
+pub fn unpack_and_hash(
+    tarball: &Path,
+    dest_dir: &Path,
+) -> Result<(String, Option<PackageIndex>), Problem> {
+    let filename = tarball
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or_default();
+    let encoding = Encoding::from_saved_tarball_name(filename)
+        .ok_or_else(|| Problem::UnsupportedEncoding(filename.to_string()))?;
+    let file = File::open(tarball).map_err(Problem::IoErr)?;
+
+    decompress_into(dest_dir, encoding, io::BufReader::new(file))
+}
+
+/// Send the request, and check the response before anything gets read from it. A file:// URL is
+/// read from the filesystem instead.
+fn download(url: &str, max_download_bytes: u64) -> Result<(Encoding, Box<dyn Read>), Problem> {
+    if let Some(path) = file_url_path(url) {
+        let file = File::open(path).map_err(|err| match err.kind() {
+            io::ErrorKind::NotFound => Problem::NotFound,
+            _ => Problem::IoErr(err),
+        })?;
+        let encoding = Encoding::new("", url)?;
+
+        return Ok((
+            encoding,
+            Box::new(io::BufReader::new(file).take(max_download_bytes)),
+        ));
+    }
+
     // TODO apparently it really improves performance to construct a Client once and then reuse it,
     // instead of making a new Client for every request.
     // Per https://github.com/seanmonstar/reqwest/issues/1454#issuecomment-1026076701
@@ -285,7 +407,23 @@ pub fn download_and_hash(
     // Use .take to prevent a malicious server from sending back bytes
     // until system resources are exhausted!
     let resp = ProgressReporter::new(resp.take(max_download_bytes), content_length);
-    decompress_into(dest_dir, encoding, resp)
+
+    Ok((encoding, Box::new(resp)))
+}
+
+/// The path a file:// URL refers to, without its fragment.
+fn file_url_path(url: &str) -> Option<PathBuf> {
+    let path = url.strip_prefix(FILE_URL_PREFIX)?;
+    let path = path.split_once('#').map_or(path, |(path, _)| path);
+
+    // file:///C:/tarballs refers to C:/tarballs, not /C:/tarballs
+    #[cfg(windows)]
+    let path = match path.strip_prefix('/') {
+        Some(without_slash) if without_slash.get(1..2) == Some(":") => without_slash,
+        _ => path,
+    };
+
+    Some(PathBuf::from(path))
 }
 
 /// The content encodings we support
@@ -294,10 +432,39 @@ enum Encoding {
     Gzip,
     Brotli,
     Deflate,
+    Zstd,
     Uncompressed,
 }
 
+/// What the filenames of saved tarballs end in, depending on how they were encoded when downloaded.
+const SAVED_TARBALL_EXTENSIONS: [(&str, Encoding); 5] = [
+    (".tar.br", Encoding::Brotli),
+    (".tar.gz", Encoding::Gzip),
+    (".tar.deflate", Encoding::Deflate),
+    (".tar.zst", Encoding::Zstd),
+    (".tar", Encoding::Uncompressed),
+];
+
 impl Encoding {
+    fn saved_tarball_ext(self) -> &'static str {
+        SAVED_TARBALL_EXTENSIONS
+            .iter()
+            .find(|(_, encoding)| *encoding == self)
+            .map(|(ext, _)| *ext)
+            .unwrap()
+    }
+
+    fn from_saved_tarball_name(filename: &str) -> Option<Self> {
+        SAVED_TARBALL_EXTENSIONS
+            .iter()
+            .find(|(ext, _)| {
+                filename
+                    .strip_suffix(ext)
+                    .is_some_and(|hash| !hash.is_empty())
+            })
+            .map(|(_, encoding)| *encoding)
+    }
+
     pub fn new(content_encoding: &str, url: &str) -> Result<Self, Problem> {
         use Encoding::*;
 
@@ -306,6 +473,7 @@ impl Encoding {
             "br" => Ok(Brotli),
             "gzip" => Ok(Gzip),
             "deflate" => Ok(Deflate),
+            "zstd" => Ok(Zstd),
             "" => {
                 // There was no Content-Encoding header, but we can infer the encoding
                 // from the file extension in the URL.
@@ -316,6 +484,7 @@ impl Encoding {
                     Some((_, after_dot)) => match Compression::from_file_ext(after_dot) {
                         Some(Compression::Brotli) => Ok(Self::Brotli),
                         Some(Compression::Gzip) => Ok(Self::Gzip),
+                        Some(Compression::Zstd) => Ok(Self::Zstd),
                         Some(Compression::Uncompressed) | None => Ok(Self::Uncompressed),
                     },
                     None => Ok(Uncompressed),
@@ -346,31 +515,115 @@ fn encoding_from_tar_br() {
     assert_eq!(Encoding::Brotli, actual);
 }
 
-fn hash_and_unpack(dest_dir: &Path, reader: impl Read) -> Result<String, Problem> {
-    let mut hash_reader = HashReader::new(reader);
+#[test]
+fn encoding_from_tar_zst() {
+    let actual = Encoding::new(
+        "",
+        "https://example.com/jDRlAFAA3738vu3-vMpLUoyxtA86Z7CaZneoOKrihbE.tar.zst",
+    )
+    .unwrap();
+
+    assert_eq!(Encoding::Zstd, actual);
+}
+
+#[test]
+fn saved_tarball_round_trip() {
+    let mut archive = tar::Builder::new(Vec::new());
+    let contents = b"package \"example\" exposes [] packages {}";
+    let mut header = tar::Header::new_gnu();
+    header.set_size(contents.len() as u64);
+    header.set_cksum();
+    archive
+        .append_data(&mut header, "main.roc", &contents[..])
+        .unwrap();
+    let archive = archive.into_inner().unwrap();
+    let expected_hash = base64_url::encode(blake3::hash(&archive).as_bytes());
+
+    let vendor_dir = tempfile::tempdir().unwrap();
+    let mut encoder = flate2::write::GzEncoder::new(
+        File::create(vendor_dir.path().join(format!("{expected_hash}.tar.gz"))).unwrap(),
+        flate2::Compression::fast(),
+    );
+    encoder.write_all(&archive).unwrap();
+    encoder.finish().unwrap();
+
+    let tarball = find_saved_tarball(vendor_dir.path(), &expected_hash).unwrap();
+    assert!(find_saved_tarball(vendor_dir.path(), "other").is_none());
+
+    let dest_dir = tempfile::tempdir().unwrap();
+    let (actual_hash, _) = unpack_and_hash(&tarball, dest_dir.path()).unwrap();
+
+    assert_eq!(expected_hash, actual_hash);
+    assert_eq!(
+        std::fs::read(dest_dir.path().join("main.roc")).unwrap(),
+        contents
+    );
+}
+
+#[test]
+fn hash_is_the_same_for_every_compression() {
+    let package_dir = tempfile::tempdir().unwrap();
+    let main = package_dir.path().join("main.roc");
+    std::fs::write(
+        &main,
+        "package \"pkg\"\n    exposes [Thing]\n    packages {}\n",
+    )
+    .unwrap();
+    std::fs::write(
+        package_dir.path().join("Thing.roc"),
+        "interface Thing\n    exposes [thing]\n    imports []\n\nthing = 42\n",
+    )
+    .unwrap();
+
+    let mut hashes = Vec::new();
+
+    for compression in [
+        Compression::Uncompressed,
+        Compression::Gzip,
+        Compression::Brotli,
+        Compression::Zstd,
+    ] {
+        let filename = crate::tarball::build(&main, compression).unwrap();
+        let dest_dir = tempfile::tempdir().unwrap();
+        let (hash, _) = unpack_and_hash(&main.with_file_name(&filename), dest_dir.path()).unwrap();
+
+        assert!(
+            filename.starts_with(&hash),
+            "{filename} is not named {hash}"
+        );
+        assert_eq!(
+            std::fs::read(dest_dir.path().join("Thing.roc")).unwrap(),
+            std::fs::read(package_dir.path().join("Thing.roc")).unwrap()
+        );
+
+        hashes.push(hash);
+    }
 
-    tar::Archive::new(&mut hash_reader)
-        .unpack(dest_dir)
-        .map_err(Problem::IoErr)?;
+    hashes.dedup();
 
-    let mut buf = Vec::with_capacity(1024);
+    assert_eq!(hashes.len(), 1);
+}
 
-    // Archive::new() doesn't always read all the bytes, but we need to read them all
-    // in order to get the correct hash!
-    hash_reader.read_to_end(&mut buf).map_err(Problem::IoErr)?;
+fn hash_and_unpack(
+    dest_dir: &Path,
+    reader: impl Read,
+) -> Result<(String, Option<PackageIndex>), Problem> {
+    let mut hash_reader = HashReader::new(reader);
+    let index = index::unpack(&mut hash_reader, dest_dir).map_err(Problem::IoErr)?;
 
-    Ok(base64_url::encode(hash_reader.finalize().as_bytes()))
+    Ok((base64_url::encode(hash_reader.finalize().as_bytes()), index))
 }
 
 /// Read from the given reader, decompress the bytes using the given Content-Encoding string,
-/// write them to the given writer, and return the base64url-encoded BLAKE3 hash of what was written.
+/// write them to the given writer, and return the base64url-encoded BLAKE3 hash of what was written,
+/// along with the index of what was unpacked (if it could be indexed).
 /// This both writes and hashes incrementally as it reads, so the only extra work that's done
 /// at the end is base64url-encoding the final hash.
 fn decompress_into(
     dest_dir: &Path,
     encoding: Encoding,
     reader: impl Read,
-) -> Result<String, Problem> {
+) -> Result<(String, Option<PackageIndex>), Problem> {
     match encoding {
         Encoding::Brotli => hash_and_unpack(
             dest_dir,
@@ -381,10 +634,30 @@ fn decompress_into(
             hash_and_unpack(dest_dir, flate2::read::GzDecoder::new(reader))
         }
         Encoding::Deflate => hash_and_unpack(dest_dir, flate2::read::DeflateDecoder::new(reader)),
+        Encoding::Zstd => hash_and_unpack(
+            dest_dir,
+            zstd::stream::read::Decoder::new(reader).map_err(Problem::IoErr)?,
+        ),
         Encoding::Uncompressed => hash_and_unpack(dest_dir, reader),
     }
 }
 
+/// Read something while writing everything that was read to `writer`
+struct TeeReader<R: Read, W: Write> {
+    reader: R,
+    writer: W,
+}
+
+impl<R: Read, W: Write> Read for TeeReader<R, W> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let bytes_read = self.reader.read(buf)?;
+
+        self.writer.write_all(&buf[0..bytes_read])?;
+
+        Ok(bytes_read)
+    }
+}
+
 /// Read something while calculating its BLAKE3 hash
 struct HashReader<R: Read> {
     reader: R,
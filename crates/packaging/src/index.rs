@@ -0,0 +1,507 @@
+//! A record of how an installed package was unpacked, which lets [verify] check the package's
+//! files against the hash in its name long after its tarball is gone.
+//!
+//! A package's hash is the BLAKE3 hash of its uncompressed tarball, and a tarball is mostly the
+//! contents of the files in it. The rest - headers with names, modes and timestamps, padding, and
+//! the end-of-archive blocks - can't be recovered from the unpacked files, so the index keeps
+//! those bytes (the "skeleton") along with where each file's contents went. Putting the files back
+//! into the skeleton gives the exact tarball that was hashed, so if anything in the package or in
+//! the index is changed, the hash will no longer match.
+
+use std::cell::RefCell;
+use std::collections::{HashSet, VecDeque};
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::path::{Component, Path, PathBuf};
+use std::rc::Rc;
+
+const HEADER: &str = "roc package index 1\n";
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct PackageIndex {
+    entries: Vec<IndexEntry>,
+    skeleton: Vec<u8>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum IndexEntry {
+    /// A regular file, whose contents were at `offset` in the uncompressed tarball.
+    File {
+        path: PathBuf,
+        offset: u64,
+        size: u64,
+        hash: String,
+    },
+    /// Anything else that was unpacked and isn't a directory, like a symlink.
+    Other { path: PathBuf },
+}
+
+impl IndexEntry {
+    fn path(&self) -> &Path {
+        match self {
+            IndexEntry::File { path, .. } | IndexEntry::Other { path } => path,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Corruption {
+    /// A file from the tarball is no longer there.
+    Missing(PathBuf),
+    /// A file from the tarball has different contents now.
+    Changed(PathBuf),
+    /// A file which wasn't in the tarball was added.
+    Added(PathBuf),
+    /// All the files are as the index says, but the index doesn't match the package's hash.
+    Index,
+}
+
+impl PackageIndex {
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        let mut out = HEADER.to_string();
+
+        for entry in self.entries.iter() {
+            // Paths were checked to be UTF-8 without newlines when the entry was added.
+            let path = entry.path().to_str().unwrap_or_default();
+
+            match entry {
+                IndexEntry::File {
+                    offset, size, hash, ..
+                } => out.push_str(&format!("file {offset} {size} {hash} {path}\n")),
+                IndexEntry::Other { .. } => out.push_str(&format!("other {path}\n")),
+            }
+        }
+
+        out.push('\n');
+
+        let mut bytes = out.into_bytes();
+        bytes.extend_from_slice(&self.skeleton);
+
+        fs::write(path, bytes)
+    }
+
+    /// Returns None if there's no index at `path`.
+    pub fn read(path: &Path) -> io::Result<Option<Self>> {
+        let bytes = match fs::read(path) {
+            Ok(bytes) => bytes,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err),
+        };
+
+        Self::parse(&bytes)
+            .map(Some)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid package index"))
+    }
+
+    fn parse(bytes: &[u8]) -> Option<Self> {
+        let rest = bytes.strip_prefix(HEADER.as_bytes())?;
+        let mut entries = Vec::new();
+        let mut remaining = rest;
+
+        loop {
+            let newline = remaining.iter().position(|&byte| byte == b'\n')?;
+            let line = std::str::from_utf8(&remaining[..newline]).ok()?;
+            remaining = &remaining[newline + 1..];
+
+            if line.is_empty() {
+                break;
+            }
+
+            let entry = match line.split_once(' ')? {
+                ("file", fields) => {
+                    let mut fields = fields.splitn(4, ' ');
+
+                    IndexEntry::File {
+                        offset: fields.next()?.parse().ok()?,
+                        size: fields.next()?.parse().ok()?,
+                        hash: fields.next()?.to_string(),
+                        path: PathBuf::from(fields.next()?),
+                    }
+                }
+                ("other", path) => IndexEntry::Other {
+                    path: PathBuf::from(path),
+                },
+                _ => return None,
+            };
+
+            entries.push(entry);
+        }
+
+        Some(Self {
+            entries,
+            skeleton: remaining.to_vec(),
+        })
+    }
+}
+
+/// Unpack the uncompressed tarball in `reader` into `dest_dir`, reading it all the way to the end.
+/// Returns the package's index, or None if this tarball can't be indexed - for example, because
+/// it has a path which isn't UTF-8, or the same path twice.
+pub(crate) fn unpack(reader: impl Read, dest_dir: &Path) -> io::Result<Option<PackageIndex>> {
+    let state = Rc::new(RefCell::new(SkeletonState::default()));
+    let mut reader = SkeletonReader {
+        reader,
+        state: Rc::clone(&state),
+    };
+    let mut entries = Vec::new();
+    let mut paths = HashSet::new();
+    let mut indexable = true;
+
+    {
+        let mut archive = tar::Archive::new(&mut reader);
+
+        // Like tar::Archive::unpack, leave directories until the end so that their permissions
+        // don't get in the way of unpacking the files in them.
+        let mut directories = Vec::new();
+
+        for entry in archive.entries()? {
+            let mut entry = entry?;
+
+            if entry.header().entry_type().is_dir() {
+                directories.push(entry);
+                continue;
+            }
+
+            let path = match index_path(&entry.path()?) {
+                Some(path) if paths.insert(path.clone()) => path,
+                _ => {
+                    indexable = false;
+                    entry.unpack_in(dest_dir)?;
+                    continue;
+                }
+            };
+
+            if entry.header().entry_type().is_file() {
+                let size = entry.size();
+                let offset = entry.raw_file_position();
+                let mut hash = String::new();
+
+                if size == 0 {
+                    hash = base64_url::encode(blake3::hash(&[]).as_bytes());
+                } else {
+                    state.borrow_mut().files.push_back(FileInProgress {
+                        entry: entries.len(),
+                        end: offset + size,
+                        hasher: blake3::Hasher::new(),
+                    });
+                }
+
+                entries.push(IndexEntry::File {
+                    path,
+                    offset,
+                    size,
+                    hash,
+                });
+            } else {
+                entries.push(IndexEntry::Other { path });
+            }
+
+            indexable &= entry.unpack_in(dest_dir)?;
+        }
+
+        for mut directory in directories {
+            directory.unpack_in(dest_dir)?;
+        }
+    }
+
+    // tar::Archive doesn't always read all the bytes, but they all need to be read in order to
+    // get the correct hash!
+    io::copy(&mut reader, &mut io::sink())?;
+
+    drop(reader);
+
+    let SkeletonState {
+        skeleton, hashes, ..
+    } = Rc::try_unwrap(state)
+        .map(RefCell::into_inner)
+        .unwrap_or_default();
+
+    for (index, hash) in hashes {
+        if let IndexEntry::File {
+            hash: file_hash, ..
+        } = &mut entries[index]
+        {
+            *file_hash = hash;
+        }
+    }
+
+    Ok(indexable.then_some(PackageIndex { entries, skeleton }))
+}
+
+/// The path that [tar::Entry::unpack_in] unpacks an entry to, relative to the destination dir.
+/// None if it won't be unpacked, or its path can't be written in an index.
+fn index_path(path: &Path) -> Option<PathBuf> {
+    let mut index_path = PathBuf::new();
+
+    for component in path.components() {
+        match component {
+            Component::Prefix(_) | Component::RootDir | Component::CurDir => {}
+            Component::ParentDir => return None,
+            Component::Normal(part) => index_path.push(part),
+        }
+    }
+
+    let path_str = index_path.to_str()?;
+
+    if path_str.is_empty() || path_str.contains('\n') {
+        None
+    } else {
+        Some(index_path)
+    }
+}
+
+/// Put the files in `package_dir` back into the skeleton in `index`, and check the result against
+/// the hash the package was installed under. Returns everything that's wrong, if anything.
+pub fn verify(
+    index: &PackageIndex,
+    package_dir: &Path,
+    content_hash: &str,
+) -> io::Result<Vec<Corruption>> {
+    let mut corruptions = Vec::new();
+    let mut hasher = blake3::Hasher::new();
+    let mut skeleton = &index.skeleton[..];
+    let mut position = 0;
+
+    for entry in index.entries.iter() {
+        let path = entry.path();
+        let full_path = package_dir.join(path);
+
+        let (offset, size, hash) = match entry {
+            IndexEntry::File {
+                offset, size, hash, ..
+            } => (*offset, *size, hash),
+            IndexEntry::Other { .. } => {
+                if full_path.symlink_metadata().is_err() {
+                    corruptions.push(Corruption::Missing(path.to_path_buf()));
+                }
+
+                continue;
+            }
+        };
+
+        // Everything between the previous file and this one comes from the skeleton.
+        let (before, after) = offset
+            .checked_sub(position)
+            .and_then(|len| usize::try_from(len).ok())
+            .filter(|&len| len <= skeleton.len())
+            .map(|len| skeleton.split_at(len))
+            .unwrap_or((skeleton, &[]));
+
+        hasher.update(before);
+        skeleton = after;
+        position = offset + size;
+
+        let file = match File::open(&full_path) {
+            Ok(file) => file,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                corruptions.push(Corruption::Missing(path.to_path_buf()));
+                continue;
+            }
+            Err(err) => return Err(err),
+        };
+
+        let mut file_hasher = blake3::Hasher::new();
+        let mut file_len = 0;
+        let mut buf = [0; 8192];
+        let mut reader = file.take(size + 1);
+
+        loop {
+            let bytes_read = reader.read(&mut buf)?;
+
+            if bytes_read == 0 {
+                break;
+            }
+
+            hasher.update(&buf[..bytes_read]);
+            file_hasher.update(&buf[..bytes_read]);
+            file_len += bytes_read as u64;
+        }
+
+        if file_len != size || base64_url::encode(file_hasher.finalize().as_bytes()) != *hash {
+            corruptions.push(Corruption::Changed(path.to_path_buf()));
+        }
+    }
+
+    hasher.update(skeleton);
+
+    let indexed: HashSet<&Path> = index.entries.iter().map(IndexEntry::path).collect();
+
+    for entry in walkdir::WalkDir::new(package_dir).min_depth(1) {
+        let entry = entry.map_err(io::Error::from)?;
+
+        if !entry.file_type().is_dir() {
+            let path = entry
+                .path()
+                .strip_prefix(package_dir)
+                .unwrap_or(entry.path());
+
+            if !indexed.contains(path) {
+                corruptions.push(Corruption::Added(path.to_path_buf()));
+            }
+        }
+    }
+
+    let actual_hash = base64_url::encode(hasher.finalize().as_bytes());
+
+    if actual_hash != content_hash && corruptions.is_empty() {
+        corruptions.push(Corruption::Index);
+    }
+
+    Ok(corruptions)
+}
+
+#[derive(Default)]
+struct SkeletonState {
+    /// How far into the tarball has been read.
+    position: u64,
+    skeleton: Vec<u8>,
+    /// The files whose contents haven't all been read yet, in the order they're in the tarball.
+    files: VecDeque<FileInProgress>,
+    /// The hash of each file whose contents have all been read, by its index in the entries.
+    hashes: Vec<(usize, String)>,
+}
+
+struct FileInProgress {
+    entry: usize,
+    end: u64,
+    hasher: blake3::Hasher,
+}
+
+/// Read a tarball while keeping everything in it except the contents of the files which are
+/// registered in the shared state before their contents get read.
+struct SkeletonReader<R: Read> {
+    reader: R,
+    state: Rc<RefCell<SkeletonState>>,
+}
+
+impl<R: Read> Read for SkeletonReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let bytes_read = self.reader.read(buf)?;
+        let mut state = self.state.borrow_mut();
+        let mut remaining = &buf[..bytes_read];
+
+        while !remaining.is_empty() {
+            let position = state.position;
+
+            // Files are registered right before their contents get read, so anything read while
+            // there are none in progress belongs in the skeleton.
+            let len = match state.files.front_mut() {
+                Some(file) => {
+                    let len = remaining.len().min((file.end - position) as usize);
+
+                    file.hasher.update(&remaining[..len]);
+
+                    len
+                }
+                None => {
+                    let len = remaining.len();
+
+                    state.skeleton.extend_from_slice(remaining);
+
+                    len
+                }
+            };
+
+            remaining = &remaining[len..];
+            state.position += len as u64;
+
+            if state
+                .files
+                .front()
+                .is_some_and(|file| file.end == state.position)
+            {
+                let file = state.files.pop_front().unwrap();
+                let hash = base64_url::encode(file.hasher.finalize().as_bytes());
+
+                state.hashes.push((file.entry, hash));
+            }
+        }
+
+        Ok(bytes_read)
+    }
+}
+
+#[test]
+fn verify_unpacked_package() {
+    let mut tarball = Vec::new();
+
+    {
+        let mut builder = tar::Builder::new(&mut tarball);
+
+        for (path, contents) in [
+            (
+                "main.roc",
+                &b"package \"example\" exposes [] packages {}\n"[..],
+            ),
+            ("Empty.roc", &b""[..]),
+            (
+                "src/Thing.roc",
+                &b"interface Thing exposes [] imports []\n"[..],
+            ),
+        ] {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(contents.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+
+            builder.append_data(&mut header, path, contents).unwrap();
+        }
+
+        builder.finish().unwrap();
+    }
+
+    let content_hash = base64_url::encode(blake3::hash(&tarball).as_bytes());
+    let dest_dir = tempfile::tempdir().unwrap();
+    let index = unpack(&tarball[..], dest_dir.path()).unwrap().unwrap();
+
+    let index_path = dest_dir.path().with_extension("index");
+    index.write(&index_path).unwrap();
+    let index = PackageIndex::read(&index_path).unwrap().unwrap();
+    fs::remove_file(index_path).unwrap();
+
+    assert_eq!(verify(&index, dest_dir.path(), &content_hash).unwrap(), []);
+
+    fs::write(dest_dir.path().join("src/Thing.roc"), "interface Thing\n").unwrap();
+    fs::write(dest_dir.path().join("Extra.roc"), "").unwrap();
+    fs::remove_file(dest_dir.path().join("Empty.roc")).unwrap();
+
+    let mut corruptions = verify(&index, dest_dir.path(), &content_hash).unwrap();
+    corruptions.sort_by_key(|corruption| format!("{corruption:?}"));
+
+    assert_eq!(
+        corruptions,
+        [
+            Corruption::Added(PathBuf::from("Extra.roc")),
+            Corruption::Changed(PathBuf::from("src/Thing.roc")),
+            Corruption::Missing(PathBuf::from("Empty.roc")),
+        ]
+    );
+}
+
+#[test]
+fn verify_changed_index() {
+    let mut tarball = Vec::new();
+
+    {
+        let mut builder = tar::Builder::new(&mut tarball);
+        let mut header = tar::Header::new_gnu();
+        header.set_size(4);
+        header.set_cksum();
+
+        builder
+            .append_data(&mut header, "main.roc", &b"main"[..])
+            .unwrap();
+        builder.finish().unwrap();
+    }
+
+    let content_hash = base64_url::encode(blake3::hash(&tarball).as_bytes());
+    let dest_dir = tempfile::tempdir().unwrap();
+    let mut index = unpack(&tarball[..], dest_dir.path()).unwrap().unwrap();
+
+    index.skeleton[0] = b'x';
+
+    assert_eq!(
+        verify(&index, dest_dir.path(), &content_hash).unwrap(),
+        [Corruption::Index]
+    );
+}
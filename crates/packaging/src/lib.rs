@@ -1,4 +1,12 @@
 pub mod cache;
 #[cfg(not(target_family = "wasm"))]
+pub mod cached;
+#[cfg(not(target_family = "wasm"))]
 pub mod https;
+#[cfg(not(target_family = "wasm"))]
+pub mod index;
+#[cfg(not(target_family = "wasm"))]
+pub mod mirror;
 pub mod tarball;
+#[cfg(not(target_family = "wasm"))]
+pub mod vendor;
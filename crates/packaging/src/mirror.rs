@@ -0,0 +1,150 @@
+//! Fetching packages from somewhere other than their URL, like an internal artifact server or a
+//! local directory of tarballs, by rewriting the start of their URLs.
+//!
+//! Rules are read from the `ROC_PACKAGE_MIRRORS` environment variable, and then from a `mirrors`
+//! file in the Roc config directory (e.g. `~/.config/roc/mirrors`). Each rule is a URL prefix and
+//! what to replace it with, separated by whitespace. In the file, rules go on separate lines and
+//! `#` starts a comment; in the environment variable, they can also be separated by `;`. The first
+//! rule whose prefix matches wins.
+//!
+//! Packages are still verified against the hash in their original URL, wherever they come from.
+
+use std::borrow::Cow;
+use std::path::PathBuf;
+
+use crate::https::Problem;
+
+pub const MIRRORS_ENV_VAR: &str = "ROC_PACKAGE_MIRRORS";
+const MIRRORS_FILE_NAME: &str = "mirrors";
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Mirrors {
+    /// Each URL prefix, along with what to replace it with.
+    rules: Vec<(String, String)>,
+}
+
+impl Mirrors {
+    /// The rules in [MIRRORS_ENV_VAR], followed by the ones in the mirrors file, if they exist.
+    pub fn load() -> Result<Self, Problem> {
+        let mut mirrors = match std::env::var(MIRRORS_ENV_VAR) {
+            Ok(config) => Self::parse(&config.replace(';', "\n"), MIRRORS_ENV_VAR)?,
+            Err(_) => Self::default(),
+        };
+
+        if let Some(path) = mirrors_file() {
+            match std::fs::read_to_string(&path) {
+                Ok(config) => {
+                    let from_file = Self::parse(&config, &path.display().to_string())?;
+                    mirrors.rules.extend(from_file.rules);
+                }
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+                Err(err) => return Err(Problem::IoErr(err)),
+            }
+        }
+
+        Ok(mirrors)
+    }
+
+    /// Parses one rule per line. `source` says where the rules came from, for error messages.
+    pub fn parse(config: &str, source: &str) -> Result<Self, Problem> {
+        let mut rules = Vec::new();
+
+        for line in config.lines() {
+            let rule = match line.split_once('#') {
+                Some((before_comment, _)) => before_comment,
+                None => line,
+            };
+
+            let mut parts = rule.split_whitespace();
+
+            match (parts.next(), parts.next(), parts.next()) {
+                (None, _, _) => {}
+                (Some(prefix), Some(replacement), None) => {
+                    rules.push((prefix.to_string(), replacement.to_string()));
+                }
+                _ => {
+                    return Err(Problem::InvalidMirror {
+                        source: source.to_string(),
+                        rule: rule.trim().to_string(),
+                    })
+                }
+            }
+        }
+
+        Ok(Self { rules })
+    }
+
+    /// Where to fetch the package at `url` from.
+    pub fn rewrite<'a>(&self, url: &'a str) -> Cow<'a, str> {
+        for (prefix, replacement) in self.rules.iter() {
+            if let Some(rest) = url.strip_prefix(prefix.as_str()) {
+                return Cow::Owned(format!("{replacement}{rest}"));
+            }
+        }
+
+        Cow::Borrowed(url)
+    }
+}
+
+/// e.g. ~/.config/roc/mirrors, or None if there's no home directory to find it in.
+fn mirrors_file() -> Option<PathBuf> {
+    use std::env;
+
+    let config_dir = match env::var_os("XDG_CONFIG_HOME") {
+        Some(xdg_config_home) => PathBuf::from(xdg_config_home),
+        None => {
+            #[cfg(windows)]
+            {
+                PathBuf::from(env::var_os("APPDATA")?)
+            }
+
+            #[cfg(not(windows))]
+            {
+                PathBuf::from(env::var_os("HOME")?).join(".config")
+            }
+        }
+    };
+
+    #[cfg(windows)]
+    let roc_dir = "Roc";
+    #[cfg(not(windows))]
+    let roc_dir = "roc";
+
+    Some(config_dir.join(roc_dir).join(MIRRORS_FILE_NAME))
+}
+
+#[test]
+fn rewrite_uses_first_matching_rule() {
+    let mirrors = Mirrors::parse(
+        "# internal artifact server\n\
+         https://github.com/roc-lang/  https://artifacts.example.com/roc-lang/\n\
+         \n\
+         https://github.com/  file:///srv/tarballs/  # everything else from GitHub\n",
+        "test",
+    )
+    .unwrap();
+
+    assert_eq!(
+        mirrors
+            .rewrite("https://github.com/roc-lang/basic-cli/releases/download/0.7.0/hash.tar.br"),
+        "https://artifacts.example.com/roc-lang/basic-cli/releases/download/0.7.0/hash.tar.br"
+    );
+    assert_eq!(
+        mirrors.rewrite("https://github.com/someone/hash.tar.gz"),
+        "file:///srv/tarballs/someone/hash.tar.gz"
+    );
+    assert_eq!(
+        mirrors.rewrite("https://example.com/hash.tar"),
+        "https://example.com/hash.tar"
+    );
+}
+
+#[test]
+fn rule_without_replacement() {
+    let problem = Mirrors::parse("https://github.com/\n", "test").unwrap_err();
+
+    assert!(matches!(
+        problem,
+        Problem::InvalidMirror { rule, .. } if rule == "https://github.com/"
+    ));
+}
@@ -8,14 +8,24 @@ use roc_parse::state::State;
 use std::ffi::OsStr;
 use std::fs::File;
 use std::io::{self, Read, Write};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use tar;
 use walkdir::WalkDir;
 
+/// zstd's levels go up to 19 (or 22 with a lot more memory), and the higher levels are where
+/// it gets close to Brotli's compression ratio while still being several times faster.
+#[cfg(not(target_family = "wasm"))]
+const ZSTD_LEVEL: i32 = 19;
+
+/// The modification time of every file in a bundle. Some tools mishandle a zero timestamp, so
+/// this is 2000-01-01T00:00:00Z instead.
+const NORMALIZED_MTIME: u64 = 946_684_800;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Compression {
     Brotli,
     Gzip,
+    Zstd,
     Uncompressed,
 }
 
@@ -24,6 +34,7 @@ impl Compression {
         match self {
             Compression::Brotli => ".tar.br",
             Compression::Gzip => ".tar.gz",
+            Compression::Zstd => ".tar.zst",
             Compression::Uncompressed => ".tar",
         }
     }
@@ -33,6 +44,7 @@ impl Compression {
             "tar" => Some(Self::Uncompressed),
             "gz" => Some(Self::Gzip),
             "br" => Some(Self::Brotli),
+            "zst" => Some(Self::Zstd),
             _ => None,
         }
     }
@@ -46,6 +58,8 @@ impl<'a> TryFrom<&'a str> for Compression {
             Ok(Compression::Brotli)
         } else if extension.ends_with(".gz") {
             Ok(Compression::Gzip)
+        } else if extension.ends_with(".zst") {
+            Ok(Compression::Zstd)
         } else if extension.ends_with(".tar") {
             Ok(Compression::Uncompressed)
         } else {
@@ -103,6 +117,24 @@ pub fn build(path_to_main: &Path, compression: Compression) -> io::Result<String
                 encoder.write_all(&archive_bytes)?;
                 encoder.finish()?;
             }
+            #[cfg(not(target_family = "wasm"))]
+            Compression::Zstd => {
+                let mut encoder = zstd::stream::write::Encoder::new(&mut file, ZSTD_LEVEL)?;
+
+                // Unlike Brotli, zstd can use every core, which makes a big difference for
+                // platforms with large prebuilt host binaries in them.
+                let workers = std::thread::available_parallelism().map_or(1, |n| n.get());
+                encoder.multithread(workers as u32)?;
+                encoder.write_all(&archive_bytes)?;
+                encoder.finish()?;
+            }
+            #[cfg(target_family = "wasm")]
+            Compression::Zstd => {
+                return Err(io::Error::new(
+                    io::ErrorKind::Unsupported,
+                    "zstd compression is not available when targeting wasm",
+                ));
+            }
             Compression::Uncompressed => file.write_all(&archive_bytes)?,
         };
     }
@@ -111,6 +143,10 @@ pub fn build(path_to_main: &Path, compression: Compression) -> io::Result<String
 }
 
 /// Write an uncompressed tar archive to the given writer.
+///
+/// The archive only depends on the names and contents of the files that go in it, so that
+/// building the same package on any machine gives the same hash: entries are sorted by name, and
+/// their timestamps, owners and permissions are all the same.
 fn write_archive<W: Write>(path: &Path, writer: W) -> io::Result<()> {
     let root_dir = if let Some(parent) = path.parent() {
         parent
@@ -124,6 +160,7 @@ fn write_archive<W: Write>(path: &Path, writer: W) -> io::Result<()> {
     let mut builder = tar::Builder::new(writer);
     let arena = Bump::new();
     let mut buf = Vec::new();
+    let mut files = Vec::new();
 
     // TODO use this when finding .roc files by discovering them from the root module.
     // let other_modules: &[Module<'_>] =
@@ -141,7 +178,7 @@ fn write_archive<W: Write>(path: &Path, writer: W) -> io::Result<()> {
             // TODO report error
         }
         Header::Package(_) => {
-            add_dot_roc_files(root_dir, &mut builder)?;
+            add_dot_roc_files(root_dir, &mut files)?;
         }
         Header::Platform(PlatformHeader { imports: _, .. }) => {
             // Add all the prebuilt host files to the archive.
@@ -164,21 +201,43 @@ fn write_archive<W: Write>(path: &Path, writer: W) -> io::Result<()> {
                     Some("zig"),
                 ]
                 .contains(&path.extension().and_then(OsStr::to_str))
+                    && path.is_file()
                 {
-                    builder.append_path_with_name(
-                        &path,
-                        // Store it without the root path, so that (for example) we don't store
-                        // `examples/cli/main.roc` and therefore end up with the root of the tarball
-                        // being an `examples/cli/` dir instead of having `main.roc` in the root.
-                        path.strip_prefix(root_dir).unwrap(),
-                    )?;
+                    files.push(path);
                 }
             }
 
-            add_dot_roc_files(root_dir, &mut builder)?;
+            add_dot_roc_files(root_dir, &mut files)?;
         }
     };
 
+    // Store each file without the root path, so that (for example) we don't store
+    // `examples/cli/main.roc` and therefore end up with the root of the tarball
+    // being an `examples/cli/` dir instead of having `main.roc` in the root.
+    let mut entries: Vec<(String, PathBuf)> = files
+        .into_iter()
+        .map(|path| (archive_name(path.strip_prefix(root_dir).unwrap()), path))
+        .collect();
+
+    entries.sort();
+    entries.dedup_by(|(a, _), (b, _)| a == b);
+
+    for (name, path) in entries {
+        let mut file = File::open(&path)?;
+        let mut header = tar::Header::new_gnu();
+
+        header.set_entry_type(tar::EntryType::Regular);
+        header.set_size(file.metadata()?.len());
+        header.set_mode(0o644);
+        header.set_mtime(NORMALIZED_MTIME);
+        header.set_uid(0);
+        header.set_gid(0);
+
+        // append_data sets the path (using a GNU long name entry if it's too long for the header)
+        // and the checksum.
+        builder.append_data(&mut header, name, &mut file)?;
+    }
+
     // TODO: This will be necessary when bundling packages (not platforms, since platforms just
     // slurp up the whole directory at the moment) and also platforms in a future where they
     // have precompiled hosts, and we only need to grab the .roc files and the precompiled hostfiles!
@@ -220,10 +279,16 @@ fn write_archive<W: Write>(path: &Path, writer: W) -> io::Result<()> {
     builder.finish()
 }
 
-fn add_dot_roc_files<W: Write>(
-    root_dir: &Path,
-    builder: &mut tar::Builder<W>,
-) -> Result<(), io::Error> {
+/// The name of a file in the archive, with `/` separators on every OS.
+fn archive_name(relative_path: &Path) -> String {
+    relative_path
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn add_dot_roc_files(root_dir: &Path, files: &mut Vec<PathBuf>) -> Result<(), io::Error> {
     for entry in WalkDir::new(root_dir).into_iter().filter_entry(|entry| {
         let path = entry.path();
 
@@ -238,13 +303,7 @@ fn add_dot_roc_files<W: Write>(
         // added based on the paths of the files inside anyway. (In fact, if we don't
         // filter out directories in this step, then empty ones can sometimes be added!)
         if path.is_file() {
-            builder.append_path_with_name(
-                path,
-                // Store it without the root path, so that (for example) we don't store
-                // `examples/cli/main.roc` and therefore end up with the root of the tarball
-                // being an `examples/cli/` dir instead of having `main.roc` in the root.
-                path.strip_prefix(root_dir).unwrap(),
-            )?;
+            files.push(path.to_path_buf());
         }
     }
 
@@ -275,3 +334,276 @@ fn read_header<'a>(
 
     Ok(module)
 }
+
+/// What's in a bundle that [build] made, so it can be checked before its URL is published.
+#[derive(Debug)]
+pub struct BundleContents {
+    /// The base64url-encoded BLAKE3 hash of the uncompressed tarball, which is what the bundle
+    /// should be named after.
+    pub content_hash: String,
+    /// In the order they're in the tarball.
+    pub files: Vec<BundledFile>,
+    /// The header of every .roc file, in the same order.
+    pub modules: Vec<BundledModule>,
+}
+
+#[derive(Debug)]
+pub struct BundledFile {
+    pub path: String,
+    pub size: u64,
+    /// The base64url-encoded BLAKE3 hash of the file's contents.
+    pub hash: String,
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct BundledModule {
+    pub path: String,
+    /// e.g. `interface Json` or `platform "cli"`, or None if the header couldn't be parsed.
+    pub header: Option<String>,
+    /// e.g. `json: "https://example.com/…"`
+    pub packages: Vec<String>,
+    /// The modules a package or platform exposes.
+    pub exposes: Vec<String>,
+    /// e.g. `Json` or `pf.Task`
+    pub imports: Vec<String>,
+}
+
+/// Decompress the bundle at `path_to_bundle` (based on its extension), and describe what's in it.
+pub fn inspect(path_to_bundle: &Path) -> io::Result<BundleContents> {
+    let filename = path_to_bundle
+        .file_name()
+        .map(|name| name.to_string_lossy())
+        .unwrap_or_default();
+    let compression = Compression::try_from(filename.as_ref()).map_err(|()| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("{filename} doesn't end in .tar, .tar.gz, .tar.br or .tar.zst"),
+        )
+    })?;
+    let archive_bytes = decompress(compression, io::BufReader::new(File::open(path_to_bundle)?))?;
+    let mut files = Vec::new();
+    let mut modules = Vec::new();
+
+    for entry in tar::Archive::new(archive_bytes.as_slice()).entries()? {
+        let mut entry = entry?;
+
+        if entry.header().entry_type().is_dir() {
+            continue;
+        }
+
+        let path = entry.path()?.to_string_lossy().into_owned();
+        let mut contents = Vec::with_capacity(entry.size() as usize);
+        entry.read_to_end(&mut contents)?;
+
+        if path.ends_with(".roc") {
+            modules.push(describe_module(path.clone(), &contents));
+        }
+
+        files.push(BundledFile {
+            path,
+            size: contents.len() as u64,
+            hash: base64_url::encode(blake3::hash(&contents).as_bytes()),
+        });
+    }
+
+    Ok(BundleContents {
+        content_hash: base64_url::encode(blake3::hash(&archive_bytes).as_bytes()),
+        files,
+        modules,
+    })
+}
+
+fn decompress(compression: Compression, mut reader: impl Read) -> io::Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+
+    match compression {
+        Compression::Brotli => {
+            brotli::Decompressor::new(reader, 4096).read_to_end(&mut bytes)?;
+        }
+        Compression::Gzip => {
+            flate2::read::GzDecoder::new(reader).read_to_end(&mut bytes)?;
+        }
+        #[cfg(not(target_family = "wasm"))]
+        Compression::Zstd => {
+            zstd::stream::read::Decoder::new(reader)?.read_to_end(&mut bytes)?;
+        }
+        #[cfg(target_family = "wasm")]
+        Compression::Zstd => {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "zstd compression is not available when targeting wasm",
+            ));
+        }
+        Compression::Uncompressed => {
+            reader.read_to_end(&mut bytes)?;
+        }
+    }
+
+    Ok(bytes)
+}
+
+fn describe_module(path: String, contents: &[u8]) -> BundledModule {
+    use roc_parse::ast::{Collection, Spaced, StrLiteral};
+    use roc_parse::header::{ImportsEntry, ModuleName, PackageEntry};
+    use roc_region::all::Loc;
+
+    fn names<'a, T: Copy + Into<&'a str>>(items: &[Loc<Spaced<'a, T>>]) -> Vec<String> {
+        items
+            .iter()
+            .map(|item| (*item.value.item()).into().to_string())
+            .collect()
+    }
+
+    fn packages(items: &[Loc<Spaced<'_, PackageEntry<'_>>>]) -> Vec<String> {
+        items
+            .iter()
+            .map(|item| {
+                let entry = item.value.item();
+
+                format!(
+                    "{}: \"{}\"",
+                    entry.shorthand,
+                    entry.package_name.value.as_str()
+                )
+            })
+            .collect()
+    }
+
+    fn imports(items: &Collection<'_, Loc<Spaced<'_, ImportsEntry<'_>>>>) -> Vec<String> {
+        items
+            .iter()
+            .map(|item| match item.value.item() {
+                ImportsEntry::Module(name, _) => <&str>::from(*name).to_string(),
+                ImportsEntry::Package(shorthand, name, _) => {
+                    format!("{shorthand}.{}", <&str>::from(*name))
+                }
+                ImportsEntry::IngestedFile(StrLiteral::PlainLine(file), _) => {
+                    format!("\"{file}\"")
+                }
+                ImportsEntry::IngestedFile(..) => "(ingested file)".to_string(),
+            })
+            .collect()
+    }
+
+    let arena = Bump::new();
+    let mut module = BundledModule {
+        path,
+        ..Default::default()
+    };
+
+    let header = match parse_header(&arena, State::new(contents)) {
+        Ok((parsed, _)) => parsed.header,
+        Err(_) => return module,
+    };
+
+    module.header = Some(match header {
+        Header::Interface(header) => {
+            module.imports = imports(&header.imports.item);
+
+            format!("interface {}", <&str>::from(header.name.value))
+        }
+        Header::Hosted(header) => {
+            module.imports = imports(&header.imports.item);
+
+            format!("hosted {}", <&str>::from(header.name.value))
+        }
+        Header::App(header) => {
+            if let Some(packages_item) = header.packages {
+                module.packages = packages(packages_item.item.items);
+            }
+
+            if let Some(imports_item) = header.imports {
+                module.imports = imports(&imports_item.item);
+            }
+
+            match header.name.value {
+                StrLiteral::PlainLine(name) => format!("app \"{name}\""),
+                _ => "app".to_string(),
+            }
+        }
+        Header::Package(header) => {
+            module.exposes = names::<ModuleName>(header.exposes.item.items);
+            module.packages = packages(header.packages.item.items);
+
+            format!("package \"{}\"", header.name.value.as_str())
+        }
+        Header::Platform(header) => {
+            module.exposes = names::<ModuleName>(header.exposes.item.items);
+            module.packages = packages(header.packages.item.items);
+            module.imports = imports(&header.imports.item);
+
+            format!("platform \"{}\"", header.name.value.as_str())
+        }
+    });
+
+    module
+}
+
+#[test]
+fn bundles_are_reproducible() {
+    use std::fs;
+
+    let bundles: Vec<(tempfile::TempDir, String)> = (0..2)
+        .map(|attempt| {
+            let dir = tempfile::tempdir().unwrap();
+
+            // Write the files in a different order each time, so their timestamps differ too.
+            let mut modules = [
+                (
+                    "main.roc",
+                    "package \"pkg\"\n    exposes [Thing]\n    packages {}\n",
+                ),
+                (
+                    "Thing.roc",
+                    "interface Thing\n    exposes [thing]\n    imports [Other]\n\nthing = 42\n",
+                ),
+                (
+                    "Other.roc",
+                    "interface Other\n    exposes []\n    imports []\n",
+                ),
+            ];
+
+            if attempt == 1 {
+                modules.reverse();
+            }
+
+            for (name, contents) in modules {
+                fs::write(dir.path().join(name), contents).unwrap();
+            }
+
+            #[cfg(unix)]
+            if attempt == 1 {
+                use std::os::unix::fs::PermissionsExt;
+
+                let path = dir.path().join("Thing.roc");
+                fs::set_permissions(path, fs::Permissions::from_mode(0o600)).unwrap();
+            }
+
+            let filename = build(&dir.path().join("main.roc"), Compression::Gzip).unwrap();
+
+            (dir, filename)
+        })
+        .collect();
+
+    assert_eq!(bundles[0].1, bundles[1].1);
+
+    let contents = inspect(&bundles[0].0.path().join(&bundles[0].1)).unwrap();
+    let paths: Vec<&str> = contents
+        .files
+        .iter()
+        .map(|file| file.path.as_str())
+        .collect();
+
+    assert!(bundles[0].1.starts_with(&contents.content_hash));
+    assert_eq!(paths, ["Other.roc", "Thing.roc", "main.roc"]);
+    assert_eq!(
+        contents.modules[1],
+        BundledModule {
+            path: "Thing.roc".to_string(),
+            header: Some("interface Thing".to_string()),
+            imports: vec!["Other".to_string()],
+            ..Default::default()
+        }
+    );
+    assert_eq!(contents.modules[2].exposes, ["Thing"]);
+}
@@ -0,0 +1,233 @@
+//! Saving the packages an app depends on next to it, so that it can be built without network
+//! access. `install_package` installs packages from the saved tarballs rather than downloading
+//! them, but verifies their hashes all the same.
+
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use bumpalo::Bump;
+use roc_parse::ast::Header;
+use roc_parse::header::To;
+use roc_parse::state::State;
+
+use crate::cache::{is_package_url, MAX_DOWNLOAD_BYTES};
+use crate::https::{self, PackageMetadata, Problem};
+use crate::mirror::Mirrors;
+
+/// The directory next to an app's main module that `roc vendor` saves tarballs into.
+pub const VENDOR_DIR_NAME: &str = "roc-vendor";
+
+#[derive(Debug)]
+pub enum VendorProblem {
+    /// Reading or writing this file or directory failed.
+    IoErr(PathBuf, io::Error),
+    /// The header of the module at this path could not be parsed.
+    InvalidHeader(PathBuf),
+    /// The package mirror configuration could not be loaded.
+    Mirrors(Problem),
+    /// A package in the header of `module_path` could not be vendored.
+    Package {
+        url: String,
+        module_path: PathBuf,
+        problem: Problem,
+    },
+}
+
+#[derive(Debug)]
+pub struct VendoredPackage {
+    pub url: String,
+    pub tarball: PathBuf,
+    /// False if the tarball was already vendored, and only had to be verified.
+    pub downloaded: bool,
+}
+
+/// Finds every package and platform URL reachable from the app at `path_to_app` - including the
+/// ones in the headers of those packages and platforms - and saves a verified tarball for each of
+/// them in the app's [VENDOR_DIR_NAME] directory. Tarballs in that directory which are no longer
+/// needed are deleted.
+pub fn vendor(path_to_app: &Path) -> Result<Vec<VendoredPackage>, VendorProblem> {
+    let app_dir = path_to_app.parent().unwrap_or_else(|| Path::new("."));
+    let vendor_dir = app_dir.join(VENDOR_DIR_NAME);
+
+    fs::create_dir_all(&vendor_dir).map_err(|err| VendorProblem::IoErr(vendor_dir.clone(), err))?;
+
+    // Packages get unpacked here to verify them, and to find the packages they depend on in turn.
+    let unpack_dir =
+        tempfile::tempdir().map_err(|err| VendorProblem::IoErr(vendor_dir.clone(), err))?;
+
+    let mirrors = Mirrors::load().map_err(VendorProblem::Mirrors)?;
+
+    let mut vendored: Vec<VendoredPackage> = Vec::new();
+    let mut vendored_hashes = HashSet::new();
+    let mut visited_modules = HashSet::new();
+
+    // Each module to look for packages in, along with the path to mention when reporting problems
+    // with it, which for a downloaded package is its URL rather than where it was unpacked to.
+    let mut stack = vec![(path_to_app.to_path_buf(), path_to_app.to_path_buf())];
+
+    while let Some((module_path, display_path)) = stack.pop() {
+        if !visited_modules.insert(module_path.clone()) {
+            continue;
+        }
+
+        let module_dir = module_path.parent().unwrap_or_else(|| Path::new("."));
+
+        let names = package_names(&module_path)
+            .map_err(|err| VendorProblem::IoErr(display_path.clone(), err))?
+            .ok_or_else(|| VendorProblem::InvalidHeader(display_path.clone()))?;
+
+        for package in names {
+            if !is_package_url(&package) {
+                // A package on the filesystem doesn't need vendoring, but the ones it uses might.
+                let local_path = module_dir.join(&package);
+                let display_path = if module_path == display_path {
+                    local_path.clone()
+                } else {
+                    display_path.clone()
+                };
+
+                stack.push((local_path, display_path));
+                continue;
+            }
+
+            let to_problem = |problem| VendorProblem::Package {
+                url: package.clone(),
+                module_path: display_path.clone(),
+                problem,
+            };
+
+            let PackageMetadata {
+                content_hash,
+                root_module_filename,
+                ..
+            } = PackageMetadata::try_from(package.as_str())
+                .map_err(|err| to_problem(Problem::InvalidUrl(err)))?;
+
+            if !vendored_hashes.insert(content_hash.to_string()) {
+                continue;
+            }
+
+            let dest_dir = unpack_dir.path().join(content_hash);
+            fs::create_dir(&dest_dir).map_err(|err| to_problem(Problem::IoErr(err)))?;
+
+            let (tarball, downloaded) = match https::find_saved_tarball(&vendor_dir, content_hash) {
+                Some(tarball) => {
+                    let (hash, _) =
+                        https::unpack_and_hash(&tarball, &dest_dir).map_err(to_problem)?;
+                    verify_hash(content_hash, hash).map_err(to_problem)?;
+
+                    (tarball, false)
+                }
+                None => {
+                    let source = mirrors.rewrite(&package);
+                    PackageMetadata::try_from(source.as_ref())
+                        .map_err(|err| to_problem(Problem::InvalidUrl(err)))?;
+
+                    println!("Downloading \u{001b}[36m{source}\u{001b}[0m");
+
+                    // Only give the tarball its real name once its hash has been verified.
+                    let mut file = tempfile::NamedTempFile::new_in(&vendor_dir)
+                        .map_err(|err| to_problem(Problem::IoErr(err)))?;
+                    let (hash, ext) = https::download_hash_and_save(
+                        &source,
+                        &dest_dir,
+                        &mut file,
+                        MAX_DOWNLOAD_BYTES,
+                    )
+                    .map_err(to_problem)?;
+                    verify_hash(content_hash, hash).map_err(to_problem)?;
+
+                    let tarball = vendor_dir.join(format!("{content_hash}{ext}"));
+                    file.persist(&tarball)
+                        .map_err(|err| to_problem(Problem::IoErr(err.error)))?;
+
+                    (tarball, true)
+                }
+            };
+
+            let root_module = dest_dir.join(root_module_filename.unwrap_or("main.roc"));
+            stack.push((root_module, PathBuf::from(&package)));
+
+            vendored.push(VendoredPackage {
+                url: package,
+                tarball,
+                downloaded,
+            });
+        }
+    }
+
+    remove_unused_tarballs(&vendor_dir, &vendored)?;
+
+    Ok(vendored)
+}
+
+fn verify_hash(expected: &str, actual: String) -> Result<(), Problem> {
+    if actual == expected {
+        Ok(())
+    } else {
+        Err(Problem::InvalidContentHash {
+            expected: expected.to_string(),
+            actual,
+        })
+    }
+}
+
+/// The names of the packages in the header of the module at `path`, including the platform an
+/// app provides to if it's named there rather than in `packages`. Returns None if the header
+/// couldn't be parsed.
+pub(crate) fn package_names(path: &Path) -> io::Result<Option<Vec<String>>> {
+    let bytes = fs::read(path)?;
+    let arena = Bump::new();
+
+    let module = match roc_parse::module::parse_header(&arena, State::new(&bytes)) {
+        Ok((module, _)) => module,
+        Err(_) => return Ok(None),
+    };
+
+    let entries = match &module.header {
+        Header::App(header) => header
+            .packages
+            .as_ref()
+            .map_or(&[][..], |packages| packages.item.items),
+        Header::Package(header) => header.packages.item.items,
+        Header::Platform(header) => header.packages.item.items,
+        Header::Interface(_) | Header::Hosted(_) => &[],
+    };
+
+    let mut names: Vec<String> = entries
+        .iter()
+        .map(|entry| entry.value.item().package_name.value.to_str().to_string())
+        .collect();
+
+    if let Header::App(header) = &module.header {
+        if let To::NewPackage(package_name) = header.provides.to.value {
+            names.push(package_name.to_str().to_string());
+        }
+    }
+
+    Ok(Some(names))
+}
+
+fn remove_unused_tarballs(
+    vendor_dir: &Path,
+    vendored: &[VendoredPackage],
+) -> Result<(), VendorProblem> {
+    let io_err = |err| VendorProblem::IoErr(vendor_dir.to_path_buf(), err);
+
+    for entry in fs::read_dir(vendor_dir).map_err(io_err)? {
+        let path = entry.map_err(io_err)?.path();
+        let is_tarball = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(https::is_saved_tarball_name);
+
+        if is_tarball && !vendored.iter().any(|package| package.tarball == path) {
+            println!("Removing {}, which is no longer used", path.display());
+            fs::remove_file(&path).map_err(io_err)?;
+        }
+    }
+
+    Ok(())
+}
@@ -94,15 +94,17 @@ mod test {
     use indoc::indoc;
     use pretty_assertions::assert_eq;
     use roc_error_macros::internal_error;
-    use roc_gen_llvm::{llvm::build::LlvmBackendMode, run_roc::RocCallResult, run_roc_dylib};
+    use roc_gen_llvm::llvm::build::LlvmBackendMode;
     use roc_load::{ExecutionMode, FunctionKind, LoadConfig, LoadMonomorphizedError, Threading};
     use roc_packaging::cache::RocCacheDir;
     use roc_reporting::report::{RenderTarget, DEFAULT_PALETTE};
+    use std::time::Duration;
     use target_lexicon::Triple;
 
-    use crate::run::expect_mono_module_to_dylib;
+    use crate::run::{expect_mono_module_to_dylib, ExpectResult};
 
-    fn run_expect_test(source: &str, expected: &str) {
+    /// Runs the expects in `source`, returning their results and everything they rendered.
+    fn run_expects(source: &str, timeout: Option<Duration>) -> (String, Vec<ExpectResult>) {
         let arena = bumpalo::Bump::new();
         let arena = &arena;
 
@@ -153,21 +155,14 @@ mod test {
         let arena = &bumpalo::Bump::new();
         let interns = arena.alloc(interns);
 
-        const BUFFER_SIZE: usize = 1024;
-
-        let mut shared_buffer = [0u8; BUFFER_SIZE];
-        let mut memory = crate::run::ExpectMemory::from_slice(&mut shared_buffer);
-
-        // communicate the mmapped name to zig/roc
-        let set_shared_buffer = run_roc_dylib!(dy_lib, "set_shared_buffer", (*mut u8, usize), ());
-        let mut result = RocCallResult::default();
-        unsafe { set_shared_buffer((shared_buffer.as_mut_ptr(), BUFFER_SIZE), &mut result) };
+        let mut memory = crate::run::ExpectMemory::anonymous();
 
         let mut writer = Vec::with_capacity(1024);
+        let mut results = Vec::new();
 
         let global_layout_interner = layout_interner.into_global();
         for (_, expect_funcs) in expects_by_module {
-            let (_failed, _passed) = crate::run::run_expects_with_memory(
+            let module_results = crate::run::run_expects_with_memory(
                 &mut writer,
                 RenderTarget::ColorTerminal,
                 arena,
@@ -177,8 +172,11 @@ mod test {
                 &mut expectations,
                 expect_funcs,
                 &mut memory,
+                timeout,
             )
             .unwrap();
+
+            results.extend(module_results);
         }
 
         // Remove ANSI escape codes from the answer - for example:
@@ -186,7 +184,12 @@ mod test {
         //     Before: "42 \u{1b}[35m:\u{1b}[0m Num *"
         //     After:  "42 : Num *"
         let bytes = strip_ansi_escapes::strip(writer).unwrap();
-        let actual = String::from_utf8(bytes).unwrap();
+
+        (String::from_utf8(bytes).unwrap(), results)
+    }
+
+    fn run_expect_test(source: &str, expected: &str) {
+        let (actual, _) = run_expects(source, None);
 
         if !actual.is_empty() {
             // trim off the first line; it contains a path in a tempdir that
@@ -243,6 +246,103 @@ mod test {
         );
     }
 
+    #[test]
+    fn timeout_fails_only_its_expect() {
+        let (output, results) = run_expects(
+            indoc!(
+                r#"
+                app "test" provides [main] to "./platform"
+
+                main = 0
+
+                spin = \n -> spin (n + 1)
+
+                expect spin 0 == 0
+
+                expect 1 == 1
+                "#
+            ),
+            Some(Duration::from_millis(500)),
+        );
+
+        assert!(output.contains("EXPECT TIMED OUT"), "{output}");
+        assert!(output.contains("7│  expect spin 0 == 0"), "{output}");
+
+        let passed: Vec<_> = results.iter().map(|result| result.passed).collect();
+        assert_eq!(passed.len(), 2);
+        assert_eq!(passed.iter().filter(|passed| **passed).count(), 1);
+    }
+
+    #[test]
+    fn panic_fails_only_its_expect() {
+        let (output, results) = run_expects(
+            indoc!(
+                r#"
+                app "test" provides [main] to "./platform"
+
+                main = 0
+
+                expect
+                    crash "the expect crashed"
+
+                expect 1 == 1
+
+                expect 1 == 2
+                "#
+            ),
+            None,
+        );
+
+        assert!(output.contains("EXPECT PANICKED"), "{output}");
+        assert!(output.contains("the expect crashed"), "{output}");
+        assert!(output.contains("This expectation failed:"), "{output}");
+        assert!(output.contains("10│  expect 1 == 2"), "{output}");
+
+        let passed: Vec<_> = results.iter().map(|result| result.passed).collect();
+        assert_eq!(passed.len(), 3);
+        assert_eq!(passed.iter().filter(|passed| **passed).count(), 1);
+    }
+
+    #[test]
+    fn segfault_fails_only_its_expect() {
+        // The recursive call isn't in tail position, so this runs until it hits the guard page
+        // at the end of the stack
+        let (output, results) = run_expects(
+            indoc!(
+                r#"
+                app "test" provides [main] to "./platform"
+
+                main = 0
+
+                overflow = \n ->
+                    if n == 0 then [] else List.append (overflow (n + 1)) n
+
+                expect List.len (overflow 1) == 0
+
+                expect 1 == 1
+
+                expect 1 == 2
+                "#
+            ),
+            None,
+        );
+
+        assert!(
+            output.contains("The process running this expectation was killed by"),
+            "{output}"
+        );
+        assert!(
+            output.contains("8│  expect List.len (overflow 1) == 0"),
+            "{output}"
+        );
+        assert!(output.contains("This expectation failed:"), "{output}");
+        assert!(output.contains("12│  expect 1 == 2"), "{output}");
+
+        let passed: Vec<_> = results.iter().map(|result| result.passed).collect();
+        assert_eq!(passed.len(), 3);
+        assert_eq!(passed.iter().filter(|passed| **passed).count(), 1);
+    }
+
     #[test]
     fn lookup_integer() {
         run_expect_test(
@@ -1,9 +1,12 @@
 use std::{
-    os::unix::process::parent_id,
+    fs::File,
+    io::Read,
+    os::{fd::FromRawFd, raw::c_int},
     sync::{
         atomic::{AtomicBool, AtomicU32},
         Arc,
     },
+    time::{Duration, Instant},
 };
 
 use bumpalo::collections::Vec as BumpVec;
@@ -32,19 +35,35 @@ use roc_types::subs::Subs;
 pub struct ExpectMemory<'a> {
     ptr: *mut u8,
     length: usize,
-    shm_name: Option<std::ffi::CString>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 
 impl<'a> ExpectMemory<'a> {
     const SHM_SIZE: usize = 1024;
 
+    /// Memory shared with the child processes that expects run in, but no other process.
     #[cfg(test)]
-    pub(crate) fn from_slice(slice: &mut [u8]) -> Self {
+    pub(crate) fn anonymous() -> Self {
+        let ptr = unsafe {
+            libc::mmap(
+                std::ptr::null_mut(),
+                Self::SHM_SIZE,
+                libc::PROT_WRITE | libc::PROT_READ,
+                libc::MAP_SHARED | libc::MAP_ANONYMOUS,
+                -1,
+                0,
+            )
+        };
+
+        if ptr == libc::MAP_FAILED {
+            internal_error!("failed to mmap anonymous shared memory")
+        }
+
+        let _ = ExpectSequence::new(ptr.cast());
+
         Self {
-            ptr: slice.as_mut_ptr(),
-            length: slice.len(),
-            shm_name: None,
+            ptr: ptr.cast(),
+            length: Self::SHM_SIZE,
             _marker: std::marker::PhantomData,
         }
     }
@@ -54,11 +73,6 @@ impl<'a> ExpectMemory<'a> {
         Self::mmap_help(cstring, libc::O_RDWR | libc::O_CREAT)
     }
 
-    fn reuse_mmap(&mut self) -> Option<Self> {
-        let shm_name = self.shm_name.as_ref()?.clone();
-        Some(Self::mmap_help(shm_name, libc::O_RDWR))
-    }
-
     fn mmap_help(cstring: std::ffi::CString, shm_flags: i32) -> Self {
         let ptr = unsafe {
             let shared_fd = libc::shm_open(cstring.as_ptr().cast(), shm_flags, 0o666);
@@ -102,7 +116,6 @@ impl<'a> ExpectMemory<'a> {
         Self {
             ptr: ptr.cast(),
             length: Self::SHM_SIZE,
-            shm_name: Some(cstring),
             _marker: std::marker::PhantomData,
         }
     }
@@ -134,7 +147,8 @@ pub fn run_inline_expects<'a, W: std::io::Write>(
     lib: &libloading::Library,
     expectations: &mut VecMap<ModuleId, Expectations>,
     expects: ExpectFunctions<'_>,
-) -> std::io::Result<(usize, usize)> {
+    timeout: Option<Duration>,
+) -> std::io::Result<Vec<ExpectResult>> {
     let shm_name = format!("/roc_expect_buffer_{}", std::process::id());
     let mut memory = ExpectMemory::create_or_reuse_mmap(&shm_name);
 
@@ -148,6 +162,7 @@ pub fn run_inline_expects<'a, W: std::io::Write>(
         expectations,
         expects,
         &mut memory,
+        timeout,
     )
 }
 
@@ -161,7 +176,8 @@ pub fn run_toplevel_expects<'a, W: std::io::Write>(
     lib: &libloading::Library,
     expectations: &mut VecMap<ModuleId, Expectations>,
     expects: ExpectFunctions<'_>,
-) -> std::io::Result<(usize, usize)> {
+    timeout: Option<Duration>,
+) -> std::io::Result<Vec<ExpectResult>> {
     let shm_name = format!("/roc_expect_buffer_{}", std::process::id());
     let mut memory = ExpectMemory::create_or_reuse_mmap(&shm_name);
 
@@ -175,9 +191,12 @@ pub fn run_toplevel_expects<'a, W: std::io::Write>(
         expectations,
         expects,
         &mut memory,
+        timeout,
     )
 }
 
+/// Runs each `expect` in a child process of its own, so that one that crashes, or that doesn't
+/// finish within `timeout`, fails without stopping the others from running.
 #[allow(clippy::too_many_arguments)]
 pub(crate) fn run_expects_with_memory<'a, W: std::io::Write>(
     writer: &mut W,
@@ -189,34 +208,16 @@ pub(crate) fn run_expects_with_memory<'a, W: std::io::Write>(
     expectations: &mut VecMap<ModuleId, Expectations>,
     expects: ExpectFunctions<'_>,
     memory: &mut ExpectMemory,
-) -> std::io::Result<(usize, usize)> {
-    let mut failed = 0;
-    let mut passed = 0;
+    timeout: Option<Duration>,
+) -> std::io::Result<Vec<ExpectResult>> {
+    let mut results = Vec::with_capacity(expects.fx.len() + expects.pure.len());
 
-    for expect in expects.fx {
-        let result = run_expect_fx(
-            writer,
-            render_target,
-            arena,
-            interns,
-            layout_interner,
-            lib,
-            expectations,
-            memory,
-            expect,
-        )?;
-
-        match result {
-            true => passed += 1,
-            false => failed += 1,
-        }
-    }
+    for expect in expects.fx.into_iter().chain(expects.pure) {
+        let mut report = Vec::new();
+        let start = Instant::now();
 
-    memory.set_shared_buffer(lib);
-
-    for expect in expects.pure {
-        let result = run_expect_pure(
-            writer,
+        let passed = run_expect(
+            &mut report,
             render_target,
             arena,
             interns,
@@ -225,19 +226,32 @@ pub(crate) fn run_expects_with_memory<'a, W: std::io::Write>(
             expectations,
             memory,
             expect,
+            timeout,
         )?;
 
-        match result {
-            true => passed += 1,
-            false => failed += 1,
-        }
+        results.push(ExpectResult::new(expect, passed, start.elapsed(), &report));
+        writer.write_all(&report)?;
     }
 
-    Ok((failed, passed))
+    Ok(results)
+}
+
+/// The exit code of a child process whose `expect` ran to the end, whether or not it failed.
+const CHILD_FINISHED: c_int = 0;
+/// The exit code of a child process whose `expect` called `crash`, or otherwise panicked.
+const CHILD_PANICKED: c_int = 3;
+/// Panic messages get sent through a pipe, which must not fill up before the child exits.
+const MAX_PANIC_MESSAGE_LEN: usize = 4096;
+
+/// How the child process that ran an `expect` ended.
+enum ChildOutcome {
+    Exited(c_int),
+    Signaled(c_int),
+    TimedOut,
 }
 
 #[allow(clippy::too_many_arguments)]
-fn run_expect_pure<'a, W: std::io::Write>(
+fn run_expect<'a, W: std::io::Write>(
     writer: &mut W,
     render_target: RenderTarget,
     arena: &'a Bump,
@@ -245,154 +259,193 @@ fn run_expect_pure<'a, W: std::io::Write>(
     layout_interner: &GlobalLayoutInterner<'a>,
     lib: &libloading::Library,
     expectations: &mut VecMap<ModuleId, Expectations>,
-    shared_memory: &mut ExpectMemory,
+    memory: &mut ExpectMemory,
     expect: ToplevelExpect<'_>,
+    timeout: Option<Duration>,
 ) -> std::io::Result<bool> {
-    use roc_gen_llvm::try_run_jit_function;
+    let sequence = ExpectSequence::new(memory.ptr);
+
+    let mut pipe_fds = [0 as c_int; 2];
+    if unsafe { libc::pipe(pipe_fds.as_mut_ptr()) } == -1 {
+        return Err(std::io::Error::last_os_error());
+    }
+    let [panic_read_fd, panic_write_fd] = pipe_fds;
+
+    let outcome = match unsafe { libc::fork() } {
+        0 => unsafe {
+            // we are the child; the memory is mapped as shared, so the parent sees what we write.
+            // We leave with `_exit`, so that the at-exit handlers and buffered output we inherited
+            // from the parent don't run or get flushed a second time.
+            use roc_gen_llvm::try_run_jit_function;
+
+            libc::close(panic_read_fd);
+            memory.set_shared_buffer(lib);
+
+            let result: Result<(), (String, _)> =
+                try_run_jit_function!(lib, expect.name, (), |v: ()| v);
 
-    let sequence = ExpectSequence::new(shared_memory.ptr.cast());
+            if let Err((message, _)) = result {
+                let bytes = &message.as_bytes()[..message.len().min(MAX_PANIC_MESSAGE_LEN)];
+                libc::write(panic_write_fd, bytes.as_ptr().cast(), bytes.len());
 
-    let result: Result<(), (String, _)> = try_run_jit_function!(lib, expect.name, (), |v: ()| v);
+                libc::_exit(CHILD_PANICKED)
+            }
 
-    let shared_memory_ptr: *const u8 = shared_memory.ptr.cast();
+            libc::_exit(CHILD_FINISHED)
+        },
+        -1 => {
+            let error = std::io::Error::last_os_error();
 
-    if result.is_err() || sequence.count_failures() > 0 {
-        let module_id = expect.symbol.module_id();
-        let data = expectations.get_mut(&module_id).unwrap();
+            unsafe {
+                libc::close(panic_read_fd);
+                libc::close(panic_write_fd);
+            }
+
+            return Err(error);
+        }
+        pid => {
+            unsafe { libc::close(panic_write_fd) };
+
+            wait_for_expect_child(pid, timeout)?
+        }
+    };
+
+    // Reading to the end only stops once the child is gone, and with it the write end of the pipe
+    let mut panic_message = String::new();
+    let mut panic_pipe = unsafe { File::from_raw_fd(panic_read_fd) };
+    panic_pipe.read_to_string(&mut panic_message)?;
+
+    let crash_message = match outcome {
+        ChildOutcome::Exited(CHILD_FINISHED) if sequence.count_failures() == 0 => return Ok(true),
+        ChildOutcome::Exited(CHILD_FINISHED) => None,
+        ChildOutcome::Exited(CHILD_PANICKED) => Some(panic_message),
+        ChildOutcome::Exited(code) => Some(format!(
+            "The process running this expectation exited unexpectedly with code {code}."
+        )),
+        ChildOutcome::Signaled(signal) => Some(describe_signal(signal)),
+        ChildOutcome::TimedOut => {
+            let home = expect.symbol.module_id();
+            let (filename, source) = module_source(expectations, home);
+            let renderer = Renderer::new(arena, interns, render_target, home, filename, &source);
+
+            renderer.render_timeout(writer, timeout.unwrap_or_default(), expect.region)?;
+            writeln!(writer)?;
+
+            return Ok(false);
+        }
+    };
 
-        let path = &data.path;
-        let filename = data.path.to_owned();
-        let source = std::fs::read_to_string(path).unwrap();
+    let home = expect.symbol.module_id();
 
-        let renderer = Renderer::new(arena, interns, render_target, module_id, filename, &source);
+    match crash_message {
+        Some(message) => {
+            let (filename, source) = module_source(expectations, home);
+            let renderer = Renderer::new(arena, interns, render_target, home, filename, &source);
 
-        if let Err((roc_panic_message, _roc_panic_tag)) = result {
-            renderer.render_panic(writer, &roc_panic_message, expect.region)?;
-        } else {
+            renderer.render_panic(writer, &message, expect.region)?;
+        }
+        None => {
             let mut offset = ExpectSequence::START_OFFSET;
 
             for _ in 0..sequence.count_failures() {
+                // An `expect` inside a function can fail while running an `expect` in a
+                // different module
+                let module_id = ExpectFrame::at_offset(memory.ptr, offset).module_id;
+
+                let (filename, source) = module_source(expectations, module_id);
+                let renderer =
+                    Renderer::new(arena, interns, render_target, module_id, filename, &source);
+
                 offset = render_expect_failure(
                     writer,
                     &renderer,
                     arena,
-                    Some(expect),
+                    (module_id == home).then_some(expect),
                     expectations,
                     interns,
                     layout_interner,
-                    shared_memory_ptr,
+                    memory.ptr,
                     offset,
                 )?;
             }
         }
-
-        writeln!(writer)?;
-
-        Ok(false)
-    } else {
-        Ok(true)
     }
-}
 
-#[allow(clippy::too_many_arguments)]
-fn run_expect_fx<'a, W: std::io::Write>(
-    writer: &mut W,
-    render_target: RenderTarget,
-    arena: &'a Bump,
-    interns: &'a Interns,
-    layout_interner: &GlobalLayoutInterner<'a>,
-    lib: &libloading::Library,
-    expectations: &mut VecMap<ModuleId, Expectations>,
-    parent_memory: &mut ExpectMemory,
-    expect: ToplevelExpect<'_>,
-) -> std::io::Result<bool> {
-    use signal_hook::{consts::signal::SIGCHLD, consts::signal::SIGUSR1, iterator::Signals};
+    writeln!(writer)?;
 
-    let mut signals = Signals::new([SIGCHLD, SIGUSR1]).unwrap();
+    Ok(false)
+}
 
-    match unsafe { libc::fork() } {
-        0 => unsafe {
-            // we are the child
+/// Waits for the child process running an `expect` to exit, killing it once `timeout` is up.
+fn wait_for_expect_child(
+    pid: libc::pid_t,
+    timeout: Option<Duration>,
+) -> std::io::Result<ChildOutcome> {
+    let deadline = timeout.map(|timeout| Instant::now() + timeout);
 
-            use roc_gen_llvm::try_run_jit_function;
+    // Most expects finish almost immediately, so start out checking often
+    let mut pause = Duration::from_micros(50);
 
-            let mut child_memory = parent_memory.reuse_mmap().unwrap();
+    loop {
+        let mut status = 0;
 
-            let sequence = ExpectSequence::new(child_memory.ptr);
+        match unsafe { libc::waitpid(pid, &mut status, libc::WNOHANG) } {
+            -1 => {
+                let error = std::io::Error::last_os_error();
 
-            child_memory.set_shared_buffer(lib);
+                if error.kind() != std::io::ErrorKind::Interrupted {
+                    return Err(error);
+                }
+            }
+            0 => {
+                if deadline.map_or(false, |deadline| Instant::now() >= deadline) {
+                    unsafe {
+                        libc::kill(pid, libc::SIGKILL);
+                        libc::waitpid(pid, &mut status, 0);
+                    }
 
-            let result: Result<(), (String, _)> =
-                try_run_jit_function!(lib, expect.name, (), |v: ()| v);
+                    return Ok(ChildOutcome::TimedOut);
+                }
 
-            if let Err((msg, _)) = result {
-                internal_error!("roc panic {msg}");
+                std::thread::sleep(pause);
+                pause = (pause * 2).min(Duration::from_millis(10));
             }
-
-            if sequence.count_failures() > 0 {
-                libc::kill(parent_id() as _, SIGUSR1);
+            _ if libc::WIFSIGNALED(status) => {
+                return Ok(ChildOutcome::Signaled(libc::WTERMSIG(status)));
             }
+            _ => return Ok(ChildOutcome::Exited(libc::WEXITSTATUS(status))),
+        }
+    }
+}
 
-            std::process::exit(0)
-        },
-        -1 => {
-            // something failed
+fn describe_signal(signal: c_int) -> String {
+    let (name, could_be_stack_overflow) = match signal {
+        libc::SIGSEGV => ("a segmentation fault (SIGSEGV)", true),
+        libc::SIGBUS => ("a bus error (SIGBUS)", true),
+        // Rust aborts when it notices the stack overflowing
+        libc::SIGABRT => ("an abort (SIGABRT)", true),
+        libc::SIGILL => ("an illegal instruction (SIGILL)", false),
+        libc::SIGFPE => ("an arithmetic error (SIGFPE)", false),
+        _ => return format!("The process running this expectation was killed by signal {signal}."),
+    };
 
-            // Display a human-friendly error message
-            println!("Error {:?}", std::io::Error::last_os_error());
+    let mut message = format!("The process running this expectation was killed by {name}.");
+    if could_be_stack_overflow {
+        message
+            .push_str(" This can happen when the stack overflows, e.g. from unbounded recursion.");
+    }
 
-            std::process::exit(1)
-        }
-        1.. => {
-            let mut has_succeeded = true;
-
-            for sig in &mut signals {
-                match sig {
-                    SIGCHLD => {
-                        // done!
-                        return Ok(has_succeeded);
-                    }
-                    SIGUSR1 => {
-                        // this is the signal we use for an expect failure. Let's see what the child told us
-                        has_succeeded = false;
-
-                        let frame =
-                            ExpectFrame::at_offset(parent_memory.ptr, ExpectSequence::START_OFFSET);
-                        let module_id = frame.module_id;
-
-                        let data = expectations.get_mut(&module_id).unwrap();
-                        let filename = data.path.to_owned();
-                        let source = std::fs::read_to_string(&data.path).unwrap();
-
-                        let renderer = Renderer::new(
-                            arena,
-                            interns,
-                            render_target,
-                            module_id,
-                            filename,
-                            &source,
-                        );
-
-                        render_expect_failure(
-                            writer,
-                            &renderer,
-                            arena,
-                            None,
-                            expectations,
-                            interns,
-                            layout_interner,
-                            parent_memory.ptr,
-                            ExpectSequence::START_OFFSET,
-                        )?;
-                    }
-                    _ => println!("received signal {sig}"),
-                }
-            }
+    message
+}
 
-            Ok(true)
-        }
-        _ => unreachable!(),
-    }
+fn module_source(
+    expectations: &VecMap<ModuleId, Expectations>,
+    module_id: ModuleId,
+) -> (std::path::PathBuf, String) {
+    let data = expectations.get(&module_id).unwrap();
+    let source = std::fs::read_to_string(&data.path).unwrap();
+
+    (data.path.to_owned(), source)
 }
 
 pub fn render_expects_in_memory<'a>(
@@ -604,6 +657,30 @@ pub struct ToplevelExpect<'a> {
     pub region: Region,
 }
 
+/// The outcome of running one top-level `expect`.
+#[derive(Debug, Clone)]
+pub struct ExpectResult {
+    pub symbol: Symbol,
+    pub region: Region,
+    pub passed: bool,
+    pub duration: Duration,
+    /// What was rendered about the `expect`: for a failure, the failing condition along with the
+    /// values of the variables it looks at. Empty when the `expect` passed.
+    pub report: String,
+}
+
+impl ExpectResult {
+    fn new(expect: ToplevelExpect<'_>, passed: bool, duration: Duration, report: &[u8]) -> Self {
+        Self {
+            symbol: expect.symbol,
+            region: expect.region,
+            passed,
+            duration,
+            report: String::from_utf8_lossy(report).into_owned(),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct ExpectFunctions<'a> {
     pub pure: BumpVec<'a, ToplevelExpect<'a>>,
@@ -61,7 +61,7 @@ fn exhaustiveness_problem() {
 
         let expected_error: &str = indoc!(
             r#"
-            ── UNSAFE PATTERN ──────────────────────────────────────────────────────────────
+            ── UNSAFE PATTERN [R0410] ──────────────────────────────────────────────────────
 
             This when does not cover all the possibilities:
 
@@ -107,7 +107,7 @@ fn partial_record_definition() {
         input.push('\n');
         let expected_error: &str = indoc!(
             r#"
-            ── RECORD PARSE PROBLEM ────────────────────────────────────────────────────────
+            ── RECORD PARSE PROBLEM [R0040] ────────────────────────────────────────────────
 
             I am partway through parsing a record, but I got stuck here:
 
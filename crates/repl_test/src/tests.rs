@@ -643,7 +643,7 @@ fn too_few_args() {
         "Num.add 2",
         indoc!(
             r"
-                ── TOO FEW ARGS ────────────────────────────────────────────────────────────────
+                ── TOO FEW ARGS [R0406] ────────────────────────────────────────────────────────
 
                 The add function expects 2 arguments, but it got only 1:
 
@@ -664,7 +664,7 @@ fn type_problem_function() {
         "Num.add 1 \"not a num\"",
         indoc!(
             r#"
-                ── TYPE MISMATCH ───────────────────────────────────────────────────────────────
+                ── TYPE MISMATCH [R0408] ───────────────────────────────────────────────────────
 
                 This 2nd argument to add has an unexpected type:
 
@@ -690,7 +690,7 @@ fn type_problem_binary_operator() {
         "1 + \"\"",
         indoc!(
             r#"
-                ── TYPE MISMATCH ───────────────────────────────────────────────────────────────
+                ── TYPE MISMATCH [R0408] ───────────────────────────────────────────────────────
 
                 This 2nd argument to + has an unexpected type:
 
@@ -716,7 +716,7 @@ fn type_problem_unary_operator() {
         "!\"not a bool\"",
         indoc!(
             r#"
-                ── TYPE MISMATCH ───────────────────────────────────────────────────────────────
+                ── TYPE MISMATCH [R0408] ───────────────────────────────────────────────────────
 
                 This 1st argument to ! has an unexpected type:
 
@@ -742,7 +742,7 @@ fn type_problem_string_interpolation() {
         "\"This is not a string -> $(1)\"",
         indoc!(
             r#"
-                ── TYPE MISMATCH ───────────────────────────────────────────────────────────────
+                ── TYPE MISMATCH [R0408] ───────────────────────────────────────────────────────
 
                 This argument to this string interpolation has an unexpected type:
 
@@ -768,7 +768,7 @@ fn list_drop_at_negative_index() {
         "List.dropAt [1, 2, 3] -1",
         indoc!(
             r#"
-            ── TYPE MISMATCH ───────────────────────────────────────────────────────────────
+            ── TYPE MISMATCH [R0408] ───────────────────────────────────────────────────────
 
             This 2nd argument to dropAt has an unexpected type:
 
@@ -794,7 +794,7 @@ fn list_get_negative_index() {
         "List.get [1, 2, 3] -1",
         indoc!(
             r#"
-            ── TYPE MISMATCH ───────────────────────────────────────────────────────────────
+            ── TYPE MISMATCH [R0408] ───────────────────────────────────────────────────────
 
             This 2nd argument to get has an unexpected type:
 
@@ -820,7 +820,7 @@ fn invalid_string_interpolation() {
         "\"$(123)\"",
         indoc!(
             r#"
-            ── TYPE MISMATCH ───────────────────────────────────────────────────────────────
+            ── TYPE MISMATCH [R0408] ───────────────────────────────────────────────────────
 
             This argument to this string interpolation has an unexpected type:
 
@@ -1118,7 +1118,7 @@ fn parse_problem() {
         "add m n = m + n",
         indoc!(
             r#"
-                ── ARGUMENTS BEFORE EQUALS ─────────────────────────────────────────────────────
+                ── ARGUMENTS BEFORE EQUALS [R0001] ─────────────────────────────────────────────
 
                 I am partway through parsing a definition, but I got stuck here:
 
@@ -1152,7 +1152,7 @@ fn issue_2343_complete_mono_with_shadowed_vars() {
         ),
         indoc!(
             r"
-                ── DUPLICATE NAME ──────────────────────────────────────────────────────────────
+                ── DUPLICATE NAME [R0214] ──────────────────────────────────────────────────────
 
                 The b name is first defined here:
 
@@ -1547,7 +1547,7 @@ fn interpolation_with_nested_interpolation() {
         ),
         indoc!(
             r#"
-                ── SYNTAX PROBLEM ──────────────────────────────────────────────────────────────
+                ── SYNTAX PROBLEM [R0261] ──────────────────────────────────────────────────────
 
                 This string interpolation is invalid:
 
@@ -6,7 +6,8 @@ use roc_problem::can::Problem;
 use roc_region::all::LineInfo;
 use roc_solve_problem::TypeError;
 
-use crate::report::ANSI_STYLE_CODES;
+use crate::report::{RenderTarget, ANSI_STYLE_CODES};
+use crate::structured::{sarif_log, Diagnostic};
 
 #[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
 pub struct Problems {
@@ -65,6 +66,7 @@ pub fn report_problems(
     interns: &Interns,
     can_problems: &mut MutMap<ModuleId, Vec<roc_problem::can::Problem>>,
     type_problems: &mut MutMap<ModuleId, Vec<TypeError>>,
+    render: RenderTarget,
 ) -> Problems {
     use crate::report::{can_problem, type_problem, Report, RocDocAllocator, DEFAULT_PALETTE};
     use roc_problem::Severity::*;
@@ -86,6 +88,9 @@ pub fn report_problems(
     let mut errors = Vec::with_capacity(total_problems);
     let mut fatally_errored = false;
 
+    // Structured targets get every problem, in the order they were found
+    let mut diagnostics: Vec<Diagnostic> = Vec::new();
+
     for (home, (module_path, src)) in sources.iter() {
         let mut src_lines: Vec<&str> = Vec::new();
 
@@ -99,11 +104,17 @@ pub fn report_problems(
         let problems = type_problems.remove(home).unwrap_or_default();
 
         for problem in problems {
+            alloc.take_highlight();
+
             if let Some(report) = type_problem(&alloc, &lines, module_path.clone(), problem) {
                 let severity = report.severity;
                 let mut buf = String::new();
 
-                report.render_color_terminal(&mut buf, &alloc, &palette);
+                if render.is_structured() {
+                    diagnostics.push(report.to_diagnostic(&alloc));
+                } else {
+                    report.render_color_terminal(&mut buf, &alloc, &palette);
+                }
 
                 match severity {
                     Warning => {
@@ -137,11 +148,17 @@ pub fn report_problems(
         ordered.extend(shadowing_errs);
 
         for problem in ordered.into_iter() {
+            alloc.take_highlight();
+
             let report = can_problem(&alloc, &lines, module_path.clone(), problem);
             let severity = report.severity;
             let mut buf = String::new();
 
-            report.render_color_terminal(&mut buf, &alloc, &palette);
+            if render.is_structured() {
+                diagnostics.push(report.to_diagnostic(&alloc));
+            } else {
+                report.render_color_terminal(&mut buf, &alloc, &palette);
+            }
 
             match severity {
                 Warning => {
@@ -163,8 +180,19 @@ pub fn report_problems(
 
     let problems_reported;
 
-    // Only print warnings if there are no errors
-    if errors.is_empty() {
+    if render.is_structured() {
+        // Tools can filter out warnings themselves, so they get all of them, and no rule.
+        problems_reported = 0;
+
+        if let RenderTarget::Json = render {
+            for diagnostic in diagnostics.iter() {
+                println!("{}", diagnostic.to_json());
+            }
+        } else {
+            println!("{}", sarif_log(&diagnostics));
+        }
+    } else if errors.is_empty() {
+        // Only print warnings if there are no errors
         problems_reported = warnings.len();
 
         for warning in warnings.iter() {
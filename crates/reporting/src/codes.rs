@@ -0,0 +1,235 @@
+//! Stable codes for the kinds of reports in [crate::error]. Every [crate::report::Report] is built
+//! with the code for its kind, so unlike titles, codes stay the same when a report is reworded,
+//! and reports that share a generic title like "TYPE MISMATCH" still get different codes.
+//! Problems can be searched for and counted by code. `roc explain` prints the longer explanation
+//! that goes with each code.
+
+/// A kind of report.
+#[derive(Debug, Clone, Copy)]
+pub struct ReportCode {
+    pub code: &'static str,
+    /// The titles of the reports of this kind; most kinds have just one.
+    pub titles: &'static [&'static str],
+    /// What the problem is and how to fix it, with an example.
+    pub explanation: &'static str,
+}
+
+macro_rules! report_codes {
+    ($($code:ident $name:ident => [$($title:literal),+],)*) => {
+        $(
+            #[doc = concat!("`", stringify!($code), "`")]
+            pub const $name: ReportCode = ReportCode {
+                code: stringify!($code),
+                titles: &[$($title),+],
+                explanation: include_str!(concat!("codes/", stringify!($code), ".md")),
+            };
+        )*
+
+        /// Every code, in order. Codes are never reused or renumbered. New kinds of reports get
+        /// the next code in their range: parsing problems are R0001-R0199, canonicalization
+        /// problems R0200-R0399, type checking problems R0400-R0599, problems loading modules and
+        /// packages R0600-R0799, and problems running expects R0800 onwards.
+        pub const REPORT_CODES: &[ReportCode] = &[$($name),*];
+    };
+}
+
+report_codes! {
+    // Parsing
+    R0001 ARGUMENTS_BEFORE_EQUALS => ["ARGUMENTS BEFORE EQUALS"],
+    R0002 ASCII_CONTROL_CHARACTER => ["ASCII CONTROL CHARACTER"],
+    R0003 BAD_BACKPASSING_ARROW => ["BAD BACKPASSING ARROW"],
+    R0004 BAD_RECORD_BUILDER => ["BAD RECORD BUILDER"],
+    R0005 BAD_RECORD_UPDATE => ["BAD RECORD UPDATE"],
+    R0006 BAD_REQUIRES => ["BAD REQUIRES"],
+    R0007 BAD_REQUIRES_RIGIDS => ["BAD REQUIRES RIGIDS"],
+    R0008 BAD_TYPE_VARIABLE => ["BAD TYPE VARIABLE"],
+    R0009 DOUBLE_COMMA => ["DOUBLE COMMA"],
+    R0010 DOUBLE_DOT => ["DOUBLE DOT"],
+    R0011 EMPTY_PARENTHESES => ["EMPTY PARENTHESES"],
+    R0012 END_OF_FILE => ["END OF FILE"],
+    R0013 ENDLESS_FORMAT => ["ENDLESS FORMAT"],
+    R0014 ENDLESS_SCALAR => ["ENDLESS SCALAR"],
+    R0015 ENDLESS_STRING => ["ENDLESS STRING"],
+    R0016 EXPECTED_STRING => ["EXPECTED STRING"],
+    R0017 IF_GUARD_NO_CONDITION => ["IF GUARD NO CONDITION"],
+    R0018 INCOMPLETE_HEADER => ["INCOMPLETE HEADER"],
+    R0019 INCORRECT_REST_PATTERN => ["INCORRECT REST PATTERN"],
+    R0020 INDENT_ENDS_AFTER_EXPRESSION => ["INDENT ENDS AFTER EXPRESSION"],
+    R0021 INSUFFICIENT_INDENT_IN_MULTI_LINE_STRING => ["INSUFFICIENT INDENT IN MULTI-LINE STRING"],
+    R0022 INVALID_NUMBER_LITERAL => ["INVALID NUMBER LITERAL"],
+    R0023 INVALID_PACKAGE_NAME => ["INVALID PACKAGE NAME"],
+    R0024 INVALID_PLATFORM_NAME => ["INVALID PLATFORM NAME"],
+    R0025 INVALID_SCALAR => ["INVALID SCALAR"],
+    R0026 MISPLACED_CARRIAGE_RETURN => ["MISPLACED CARRIAGE RETURN"],
+    R0027 MISSING_ARROW => ["MISSING ARROW"],
+    R0028 MISSING_EXPRESSION => ["MISSING EXPRESSION"],
+    R0029 MISSING_FINAL_EXPRESSION => ["MISSING FINAL EXPRESSION"],
+    R0030 MISSING_HEADER => ["MISSING HEADER"],
+    R0031 MISSING_PACKAGES => ["MISSING PACKAGES"],
+    R0032 MISSING_REQUIRES => ["MISSING REQUIRES"],
+    R0033 NEED_MORE_INDENTATION => ["NEED MORE INDENTATION"],
+    R0034 NOT_AN_INLINE_ALIAS => ["NOT AN INLINE ALIAS"],
+    R0035 NOT_END_OF_FILE => ["NOT END OF FILE"],
+    R0036 PARSE_PROBLEM => ["PARSE PROBLEM"],
+    R0037 PROBLEM_IN_RECORD_PATTERN => ["PROBLEM IN RECORD PATTERN"],
+    R0038 PROBLEM_IN_RECORD_TYPE => ["PROBLEM IN RECORD TYPE"],
+    R0039 QUALIFIED_ALIAS_NAME => ["QUALIFIED ALIAS NAME"],
+    R0040 RECORD_PARSE_PROBLEM => ["RECORD PARSE PROBLEM"],
+    R0041 BAD_EXPRESSION_END => ["SYNTAX PROBLEM"],
+    R0042 TAB_CHARACTER => ["TAB CHARACTER"],
+    R0043 TRAILING_DOT => ["TRAILING DOT"],
+    R0044 TRAILING_OPERATOR => ["TRAILING OPERATOR"],
+    R0045 TYPE_ARGUMENT_NOT_LOWERCASE => ["TYPE ARGUMENT NOT LOWERCASE"],
+    R0046 UNEXPECTED_ARROW => ["UNEXPECTED ARROW"],
+    R0047 UNEXPECTED_COMMA => ["UNEXPECTED COMMA"],
+    R0048 UNFINISHED_ABILITY => ["UNFINISHED ABILITY"],
+    R0049 UNFINISHED_ARGUMENT_LIST => ["UNFINISHED ARGUMENT LIST"],
+    R0050 UNFINISHED_FUNCTION => ["UNFINISHED FUNCTION"],
+    R0051 UNFINISHED_IF => ["UNFINISHED IF"],
+    R0052 UNFINISHED_INLINE_ALIAS => ["UNFINISHED INLINE ALIAS"],
+    R0053 UNFINISHED_LIST => ["UNFINISHED LIST"],
+    R0054 UNFINISHED_LIST_PATTERN => ["UNFINISHED LIST PATTERN"],
+    R0055 UNFINISHED_PARENTHESES => ["UNFINISHED PARENTHESES"],
+    R0056 UNFINISHED_PATTERN => ["UNFINISHED PATTERN"],
+    R0057 UNFINISHED_RECORD_PATTERN => ["UNFINISHED RECORD PATTERN"],
+    R0058 UNFINISHED_RECORD_TYPE => ["UNFINISHED RECORD TYPE"],
+    R0059 UNFINISHED_TAG_UNION_TYPE => ["UNFINISHED TAG UNION TYPE"],
+    R0060 UNFINISHED_TYPE => ["UNFINISHED TYPE"],
+    R0061 UNFINISHED_WHEN => ["UNFINISHED WHEN"],
+    R0062 UNKNOWN_OPERATOR => ["UNKNOWN OPERATOR"],
+    R0063 WEIRD_APP_NAME => ["WEIRD APP NAME"],
+    R0064 WEIRD_ARROW => ["WEIRD ARROW"],
+    R0065 WEIRD_CODE_POINT => ["WEIRD CODE POINT"],
+    R0066 WEIRD_ESCAPE => ["WEIRD ESCAPE"],
+    R0067 WEIRD_EXPOSES => ["WEIRD EXPOSES"],
+    R0068 WEIRD_GENERATED_TYPE_NAME => ["WEIRD GENERATED TYPE NAME"],
+    R0069 WEIRD_GENERATES => ["WEIRD GENERATES"],
+    R0070 WEIRD_IDENTIFIER => ["WEIRD IDENTIFIER"],
+    R0071 WEIRD_IMPORTS => ["WEIRD IMPORTS"],
+    R0072 WEIRD_MODULE_NAME => ["WEIRD MODULE NAME"],
+    R0073 WEIRD_PACKAGES_LIST => ["WEIRD PACKAGES LIST"],
+    R0074 WEIRD_PROVIDES => ["WEIRD PROVIDES"],
+    R0075 WEIRD_QUALIFIED_NAME => ["WEIRD QUALIFIED NAME"],
+    R0076 WEIRD_TAG_NAME => ["WEIRD TAG NAME"],
+    // Canonicalization
+    R0200 ABILITY_HAS_TYPE_VARIABLES => ["ABILITY HAS TYPE VARIABLES"],
+    R0201 ABILITY_IMPLEMENTATION_NOT_IDENTIFIER => ["ABILITY IMPLEMENTATION NOT IDENTIFIER"],
+    R0202 ABILITY_MEMBER_BINDS_MULTIPLE_VARIABLES => ["ABILITY MEMBER BINDS MULTIPLE VARIABLES"],
+    R0203 ABILITY_MEMBER_MISSING_IMPLEMENTS_CLAUSE => ["ABILITY MEMBER MISSING IMPLEMENTS CLAUSE"],
+    R0204 ABILITY_NOT_ON_TOP_LEVEL => ["ABILITY NOT ON TOP-LEVEL"],
+    R0205 ABILITY_USED_AS_TYPE => ["ABILITY USED AS TYPE"],
+    R0206 BAD_OPTIONAL_VALUE => ["BAD OPTIONAL VALUE", "SYNTAX PROBLEM"],
+    R0207 CIRCULAR_DEFINITION => ["CIRCULAR DEFINITION"],
+    R0208 CONFLICTING_NUMBER_SUFFIX => ["CONFLICTING NUMBER SUFFIX"],
+    R0209 DEFINITION_ONLY_USED_IN_RECURSION => ["DEFINITION ONLY USED IN RECURSION", "DEFINITIONs ONLY USED IN RECURSION"],
+    R0210 DEGENERATE_BRANCH => ["DEGENERATE BRANCH"],
+    R0211 DUPLICATE_BOUND_ABILITY => ["DUPLICATE BOUND ABILITY"],
+    R0212 DUPLICATE_FIELD_NAME => ["DUPLICATE FIELD NAME"],
+    R0213 DUPLICATE_IMPLEMENTATION => ["DUPLICATE IMPLEMENTATION"],
+    R0214 DUPLICATE_NAME => ["DUPLICATE NAME"],
+    R0215 DUPLICATE_TAG_NAME => ["DUPLICATE TAG NAME"],
+    R0216 ILLEGAL_DERIVE => ["ILLEGAL DERIVE"],
+    R0217 ILLEGAL_IMPLEMENTS_CLAUSE => ["ILLEGAL IMPLEMENTS CLAUSE"],
+    R0218 IMPLEMENTATION_NOT_FOUND => ["IMPLEMENTATION NOT FOUND"],
+    R0219 IMPLEMENTS_CLAUSE_IS_NOT_AN_ABILITY => ["IMPLEMENTS CLAUSE IS NOT AN ABILITY"],
+    R0220 INCOMPLETE_ABILITY_IMPLEMENTATION => ["INCOMPLETE ABILITY IMPLEMENTATION"],
+    R0221 INVALID_UNICODE => ["INVALID UNICODE"],
+    R0222 INVALID_EXTENSION_TYPE => ["INVALID_EXTENSION_TYPE"],
+    R0223 MISSING_DEFINITION => ["MISSING DEFINITION"],
+    R0224 MODULE_NOT_IMPORTED => ["MODULE NOT IMPORTED"],
+    R0225 MULTIPLE_LIST_REST_PATTERNS => ["MULTIPLE LIST REST PATTERNS"],
+    R0226 MULTIPLE_RECORD_BUILDERS => ["MULTIPLE RECORD BUILDERS"],
+    R0227 NAME_NOT_BOUND_IN_ALL_PATTERNS => ["NAME NOT BOUND IN ALL PATTERNS"],
+    R0228 BAD_PATTERN_IDENTIFIER => ["NAMING PROBLEM"],
+    R0229 NESTED_DATATYPE => ["NESTED DATATYPE"],
+    R0230 NOT_AN_ABILITY => ["NOT AN ABILITY"],
+    R0231 NOT_AN_ABILITY_MEMBER => ["NOT AN ABILITY MEMBER"],
+    R0232 NOT_EXPOSED => ["NOT EXPOSED"],
+    R0233 NUMBER_OVERFLOWS_SUFFIX => ["NUMBER OVERFLOWS SUFFIX"],
+    R0234 NUMBER_UNDERFLOWS_SUFFIX => ["NUMBER UNDERFLOWS SUFFIX"],
+    R0235 OPAQUE_TYPE_APPLIED_TO_TOO_MANY_ARGS => ["OPAQUE TYPE APPLIED TO TOO MANY ARGS"],
+    R0236 OPAQUE_TYPE_DECLARED_OUTSIDE_SCOPE => ["OPAQUE TYPE DECLARED OUTSIDE SCOPE"],
+    R0237 OPAQUE_TYPE_NOT_APPLIED => ["OPAQUE TYPE NOT APPLIED"],
+    R0238 OPAQUE_TYPE_NOT_DEFINED => ["OPAQUE TYPE NOT DEFINED"],
+    R0239 OPTIONAL_ABILITY_IMPLEMENTATION => ["OPTIONAL ABILITY IMPLEMENTATION"],
+    R0240 OVERAPPLIED_CRASH => ["OVERAPPLIED CRASH"],
+    R0241 OVERLOADED_SPECIALIZATION => ["OVERLOADED SPECIALIZATION"],
+    R0242 QUALIFIED_ABILITY_IMPLEMENTATION => ["QUALIFIED ABILITY IMPLEMENTATION"],
+    R0243 SPECIALIZATION_NOT_ON_TOP_LEVEL => ["SPECIALIZATION NOT ON TOP-LEVEL"],
+    R0244 TOO_FEW_TYPE_ARGUMENTS => ["TOO FEW TYPE ARGUMENTS"],
+    R0245 TOO_MANY_TYPE_ARGUMENTS => ["TOO MANY TYPE ARGUMENTS"],
+    R0246 UNAPPLIED_CRASH => ["UNAPPLIED CRASH"],
+    R0247 UNAPPLIED_RECORD_BUILDER => ["UNAPPLIED RECORD BUILDER"],
+    R0248 UNBOUND_TYPE_VARIABLE => ["UNBOUND TYPE VARIABLE"],
+    R0249 UNKNOWN_GENERATES_FUNCTION => ["UNKNOWN GENERATES FUNCTION"],
+    R0250 UNNECESSARY_DEFINITION => ["UNNECESSARY DEFINITION"],
+    R0251 UNNECESSARY_IMPLEMENTATIONS => ["UNNECESSARY IMPLEMENTATIONS"],
+    R0252 UNNECESSARY_WILDCARD => ["UNNECESSARY WILDCARD"],
+    R0253 UNRECOGNIZED_NAME => ["UNRECOGNIZED NAME"],
+    R0254 UNUSED_ARGUMENT => ["UNUSED ARGUMENT"],
+    R0255 UNUSED_DEFINITION => ["UNUSED DEFINITION"],
+    R0256 UNUSED_IMPORT => ["UNUSED IMPORT"],
+    R0257 UNUSED_TYPE_ALIAS_PARAMETER => ["UNUSED TYPE ALIAS PARAMETER"],
+    R0258 OPERATORS_NEED_PARENTHESES => ["SYNTAX PROBLEM"],
+    R0259 PATTERN_NOT_ALLOWED => ["SYNTAX PROBLEM"],
+    R0260 INVALID_ALIAS_VARIABLE => ["SYNTAX PROBLEM"],
+    R0261 INVALID_INTERPOLATION => ["SYNTAX PROBLEM"],
+    R0262 SIGNATURE_NAME_MISMATCH => ["NAMING PROBLEM"],
+    R0263 MALFORMED_PATTERN => ["SYNTAX PROBLEM"],
+    R0264 MALFORMED_TYPE_NAME => ["SYNTAX PROBLEM"],
+    R0265 INVALID_FLOAT => ["SYNTAX PROBLEM"],
+    R0266 INVALID_INTEGER => ["SYNTAX PROBLEM"],
+    R0267 INVALID_RECORD_UPDATE => ["SYNTAX PROBLEM"],
+    R0268 INVALID_CHARACTER_LITERAL => ["SYNTAX PROBLEM"],
+    R0269 MALFORMED_IDENTIFIER => ["SYNTAX PROBLEM"],
+    // Type checking
+    R0400 CIRCULAR_TYPE => ["CIRCULAR TYPE"],
+    R0401 CYCLIC_ALIAS => ["CYCLIC ALIAS"],
+    R0402 ILLEGAL_SPECIALIZATION => ["ILLEGAL SPECIALIZATION"],
+    R0403 INVALID_TYPE_FOR_INGESTED_FILE => ["INVALID TYPE FOR INGESTED FILE"],
+    R0404 INVALID_UTF_8 => ["INVALID UTF-8"],
+    R0405 REDUNDANT_PATTERN => ["REDUNDANT PATTERN"],
+    R0406 TOO_FEW_ARGS => ["TOO FEW ARGS"],
+    R0407 TOO_MANY_ARGS => ["TOO MANY ARGS"],
+    R0408 TYPE_MISMATCH => ["TYPE MISMATCH"],
+    R0409 UNMATCHABLE_PATTERN => ["UNMATCHABLE PATTERN"],
+    R0410 UNSAFE_PATTERN => ["UNSAFE PATTERN"],
+    R0411 WRONG_SPECIALIZATION_TYPE => ["WRONG SPECIALIZATION TYPE"],
+    R0412 ANNOTATION_MISMATCH => ["TYPE MISMATCH"],
+    R0413 PATTERN_TYPE_MISMATCH => ["TYPE MISMATCH"],
+    R0414 MISSING_ABILITY => ["TYPE MISMATCH"],
+    R0415 MISSING_RECORD_FIELD => ["TYPE MISMATCH"],
+    // Loading modules and packages
+    R0600 IMPORT_CYCLE => ["IMPORT CYCLE"],
+    R0601 INCORRECT_MODULE_NAME => ["INCORRECT MODULE NAME"],
+    R0602 NO_PLATFORM => ["NO PLATFORM"],
+    R0603 FILE_NOT_FOUND => ["FILE NOT FOUND"],
+    R0604 FILE_PERMISSION_DENIED => ["FILE PERMISSION DENIED"],
+    R0605 NOT_A_ROC_FILE => ["NOT A ROC FILE"],
+    R0606 FILE_PROBLEM => ["FILE PROBLEM"],
+    R0607 UNSUPPORTED_ENCODING => ["UNSUPPORTED ENCODING"],
+    R0608 MULTIPLE_ENCODINGS => ["MULTIPLE ENCODINGS"],
+    R0609 INVALID_CONTENT_HASH => ["INVALID CONTENT HASH"],
+    R0610 PACKAGE_NOT_FOUND => ["NOTFOUND"],
+    R0611 PACKAGE_IO_ERROR => ["IO ERROR"],
+    R0612 HTTP_ERROR => ["HTTP ERROR"],
+    R0613 INVALID_EXTENSION_SUFFIX => ["INVALID EXTENSION SUFFIX"],
+    R0614 INVALID_EXTENSION => ["INVALID EXTENSION"],
+    R0615 INVALID_FRAGMENT => ["INVALID FRAGMENT"],
+    R0616 MISSING_PACKAGE_HASH => ["MISSING PACKAGE HASH"],
+    R0617 HTTPS_MANDATORY => ["HTTPS MANDATORY"],
+    R0618 MISLEADING_CHARACTERS => ["MISLEADING CHARACTERS"],
+    R0619 INVALID_PACKAGE_MIRROR => ["INVALID PACKAGE MIRROR"],
+    R0620 FILE_TOO_LARGE => ["FILE TOO LARGE"],
+    // Running expects
+    R0800 EXPECT_FAILED => ["EXPECT FAILED"],
+    R0801 EXPECT_PANICKED => ["EXPECT PANICKED"],
+    R0802 EXPECT_TIMED_OUT => ["EXPECT TIMED OUT"],
+}
+
+/// Looks up a code like `R0123`, ignoring case.
+pub fn find_code(code: &str) -> Option<&'static ReportCode> {
+    REPORT_CODES
+        .iter()
+        .find(|report_code| report_code.code.eq_ignore_ascii_case(code.trim()))
+}
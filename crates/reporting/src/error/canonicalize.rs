@@ -11,6 +11,7 @@ use roc_region::all::{LineColumn, LineColumnRegion, LineInfo, Loc, Region};
 use roc_types::types::AliasKind;
 use std::path::PathBuf;
 
+use crate::codes::{self, ReportCode};
 use crate::error::r#type::suggest;
 use crate::report::{to_file_problem_report, Annotation, Report, RocDocAllocator, RocDocBuilder};
 use ven_pretty::{text, DocAllocator};
@@ -68,6 +69,7 @@ pub fn can_problem<'b>(
 ) -> Report<'b> {
     let doc;
     let title;
+    let code;
     let severity = problem.severity();
 
     match problem {
@@ -87,6 +89,7 @@ pub fn can_problem<'b>(
             ]);
 
             title = UNUSED_DEF.to_string();
+            code = codes::UNUSED_DEFINITION;
         }
         Problem::UnusedImport(symbol, region) => {
             doc = alloc.stack([
@@ -103,6 +106,7 @@ pub fn can_problem<'b>(
             ]);
 
             title = UNUSED_IMPORT.to_string();
+            code = codes::UNUSED_IMPORT;
         }
         Problem::UnusedModuleImport(module_id, region) => {
             doc = alloc.stack([
@@ -120,6 +124,7 @@ pub fn can_problem<'b>(
             ]);
 
             title = UNUSED_IMPORT.to_string();
+            code = codes::UNUSED_IMPORT;
         }
         Problem::DefsOnlyUsedInRecursion(1, region) => {
             doc = alloc.stack([
@@ -131,6 +136,7 @@ pub fn can_problem<'b>(
             ]);
 
             title = "DEFINITION ONLY USED IN RECURSION".to_string();
+            code = codes::DEFINITION_ONLY_USED_IN_RECURSION;
         }
         Problem::DefsOnlyUsedInRecursion(n, region) => {
             doc = alloc.stack([
@@ -146,6 +152,7 @@ pub fn can_problem<'b>(
             ]);
 
             title = "DEFINITIONs ONLY USED IN RECURSION".to_string();
+            code = codes::DEFINITION_ONLY_USED_IN_RECURSION;
         }
         Problem::ExposedButNotDefined(symbol) => {
             doc = alloc.stack([
@@ -161,6 +168,7 @@ pub fn can_problem<'b>(
             ]);
 
             title = MISSING_DEFINITION.to_string();
+            code = codes::MISSING_DEFINITION;
         }
         Problem::UnknownGeneratesWith(loc_ident) => {
             doc = alloc.stack([
@@ -175,6 +183,7 @@ pub fn can_problem<'b>(
             ]);
 
             title = UNKNOWN_GENERATES_WITH.to_string();
+            code = codes::UNKNOWN_GENERATES_FUNCTION;
         }
         Problem::UnusedArgument(closure_symbol, is_anonymous, argument_symbol, region) => {
             let line = "\". Adding an underscore at the start of a variable name is a way of saying that the variable is not used.";
@@ -209,6 +218,7 @@ pub fn can_problem<'b>(
             ]);
 
             title = UNUSED_ARG.to_string();
+            code = codes::UNUSED_ARGUMENT;
         }
         Problem::UnusedBranchDef(symbol, region) => {
             doc = alloc.stack([
@@ -229,6 +239,7 @@ pub fn can_problem<'b>(
             ]);
 
             title = UNUSED_DEF.to_string();
+            code = codes::UNUSED_DEFINITION;
         }
         Problem::PrecedenceProblem(BothNonAssociative(region, left_bin_op, right_bin_op)) => {
             doc = alloc.stack([
@@ -257,6 +268,7 @@ pub fn can_problem<'b>(
             ]);
 
             title = SYNTAX_PROBLEM.to_string();
+            code = codes::OPERATORS_NEED_PARENTHESES;
         }
         Problem::UnsupportedPattern(BadPattern::Unsupported(pattern_type), region) => {
             use roc_parse::pattern::PatternType::*;
@@ -287,6 +299,7 @@ pub fn can_problem<'b>(
             ]);
 
             title = SYNTAX_PROBLEM.to_string();
+            code = codes::PATTERN_NOT_ALLOWED;
         }
         Problem::Shadowing {
             original_region,
@@ -298,6 +311,7 @@ pub fn can_problem<'b>(
 
             doc = res_doc;
             title = res_title.to_string();
+            code = codes::DUPLICATE_NAME;
         }
         Problem::CyclicAlias(symbol, region, others, alias_kind) => {
             let answer = crate::error::r#type::cyclic_alias(
@@ -306,6 +320,7 @@ pub fn can_problem<'b>(
 
             doc = answer.0;
             title = answer.1;
+            code = codes::CYCLIC_ALIAS;
         }
         Problem::PhantomTypeArgument {
             typ: alias,
@@ -333,6 +348,7 @@ pub fn can_problem<'b>(
             ]);
 
             title = UNUSED_ALIAS_PARAM.to_string();
+            code = codes::UNUSED_TYPE_ALIAS_PARAMETER;
         }
         Problem::UnboundTypeVariable {
             typ: alias,
@@ -369,10 +385,12 @@ pub fn can_problem<'b>(
             doc = alloc.stack(stack);
 
             title = UNBOUND_TYPE_VARIABLE.to_string();
+            code = codes::UNBOUND_TYPE_VARIABLE;
         }
         Problem::BadRecursion(entries) => {
             doc = to_circular_def_doc(alloc, lines, &entries);
             title = CIRCULAR_DEF.to_string();
+            code = codes::CIRCULAR_DEFINITION;
         }
         Problem::DuplicateRecordFieldValue {
             field_name,
@@ -407,6 +425,7 @@ pub fn can_problem<'b>(
             ]);
 
             title = DUPLICATE_FIELD_NAME.to_string();
+            code = codes::DUPLICATE_FIELD_NAME;
         }
         Problem::InvalidOptionalValue {
             field_name,
@@ -455,6 +474,7 @@ pub fn can_problem<'b>(
             ]);
 
             title = DUPLICATE_FIELD_NAME.to_string();
+            code = codes::DUPLICATE_FIELD_NAME;
         }
         Problem::DuplicateTag {
             tag_name,
@@ -489,6 +509,7 @@ pub fn can_problem<'b>(
             ]);
 
             title = DUPLICATE_TAG_NAME.to_string();
+            code = codes::DUPLICATE_TAG_NAME;
         }
         Problem::SignatureDefMismatch {
             ref annotation_pattern,
@@ -505,6 +526,7 @@ pub fn can_problem<'b>(
             ]);
 
             title = NAMING_PROBLEM.to_string();
+            code = codes::SIGNATURE_NAME_MISMATCH;
         }
         Problem::InvalidAliasRigid {
             alias_name: type_name,
@@ -527,6 +549,7 @@ pub fn can_problem<'b>(
             ]);
 
             title = SYNTAX_PROBLEM.to_string();
+            code = codes::INVALID_ALIAS_VARIABLE;
         }
         Problem::InvalidHexadecimal(region) => {
             doc = alloc.stack([
@@ -543,6 +566,7 @@ pub fn can_problem<'b>(
             ]);
 
             title = INVALID_UNICODE.to_string();
+            code = codes::INVALID_UNICODE;
         }
         Problem::InvalidUnicodeCodePt(region) => {
             doc = alloc.stack([
@@ -552,6 +576,7 @@ pub fn can_problem<'b>(
             ]);
 
             title = INVALID_UNICODE.to_string();
+            code = codes::INVALID_UNICODE;
         }
         Problem::InvalidInterpolation(region) => {
             doc = alloc.stack([
@@ -562,12 +587,14 @@ pub fn can_problem<'b>(
             ]);
 
             title = SYNTAX_PROBLEM.to_string();
+            code = codes::INVALID_INTERPOLATION;
         }
         Problem::RuntimeError(runtime_error) => {
             let answer = pretty_runtime_error(alloc, lines, runtime_error);
 
             doc = answer.0;
             title = answer.1.to_string();
+            code = answer.2;
         }
         Problem::NestedDatatype {
             alias,
@@ -595,6 +622,7 @@ pub fn can_problem<'b>(
             ]);
 
             title = NESTED_DATATYPE.to_string();
+            code = codes::NESTED_DATATYPE;
         }
 
         Problem::InvalidExtensionType { region, kind } => {
@@ -622,6 +650,7 @@ pub fn can_problem<'b>(
             ]);
 
             title = INVALID_EXTENSION_TYPE.to_string();
+            code = codes::INVALID_EXTENSION_TYPE;
         }
 
         Problem::AbilityHasTypeVariables {
@@ -640,6 +669,7 @@ pub fn can_problem<'b>(
                 ),
             ]);
             title = ABILITY_HAS_TYPE_VARIABLES.to_string();
+            code = codes::ABILITY_HAS_TYPE_VARIABLES;
         }
 
         Problem::ImplementsClauseIsNotAbility {
@@ -652,6 +682,7 @@ pub fn can_problem<'b>(
                 alloc.region(lines.convert_region(clause_region)),
             ]);
             title = IMPLEMENTS_CLAUSE_IS_NOT_AN_ABILITY.to_string();
+            code = codes::IMPLEMENTS_CLAUSE_IS_NOT_AN_ABILITY;
         }
 
         Problem::IllegalImplementsClause { region } => {
@@ -670,6 +701,7 @@ pub fn can_problem<'b>(
                 ]),
             ]);
             title = ILLEGAL_IMPLEMENTS_CLAUSE.to_string();
+            code = codes::ILLEGAL_IMPLEMENTS_CLAUSE;
         }
 
         Problem::DuplicateImplementsAbility { ability, region } => {
@@ -687,6 +719,7 @@ pub fn can_problem<'b>(
                 ]),
             ]);
             title = "DUPLICATE BOUND ABILITY".to_string();
+            code = codes::DUPLICATE_BOUND_ABILITY;
         }
 
         Problem::AbilityMemberMissingImplementsClause {
@@ -721,6 +754,7 @@ pub fn can_problem<'b>(
                     .reflow("Otherwise, the function does not need to be part of the ability!")]),
             ]);
             title = ABILITY_MEMBER_MISSING_IMPLEMENTS_CLAUSE.to_string();
+            code = codes::ABILITY_MEMBER_MISSING_IMPLEMENTS_CLAUSE;
         }
 
         Problem::AbilityMemberMultipleBoundVars {
@@ -748,6 +782,7 @@ pub fn can_problem<'b>(
                 ])
             ]);
             title = ABILITY_MEMBER_BINDS_MULTIPLE_VARIABLES.to_string();
+            code = codes::ABILITY_MEMBER_BINDS_MULTIPLE_VARIABLES;
         }
 
         Problem::AbilityNotOnToplevel { region } => {
@@ -759,6 +794,7 @@ pub fn can_problem<'b>(
                 alloc.reflow("Abilities can only be defined on the top-level of a Roc module."),
             ]);
             title = ABILITY_NOT_ON_TOPLEVEL.to_string();
+            code = codes::ABILITY_NOT_ON_TOP_LEVEL;
         }
 
         Problem::AbilityUsedAsType(suggested_var_name, ability, region) => {
@@ -786,6 +822,7 @@ pub fn can_problem<'b>(
                 ])),
             ]);
             title = ABILITY_USED_AS_TYPE.to_string();
+            code = codes::ABILITY_USED_AS_TYPE;
         }
         Problem::NestedSpecialization(member, region) => {
             doc = alloc.stack([
@@ -798,6 +835,7 @@ pub fn can_problem<'b>(
                 alloc.reflow("Specializations can only be defined on the top-level of a module."),
             ]);
             title = SPECIALIZATION_NOT_ON_TOPLEVEL.to_string();
+            code = codes::SPECIALIZATION_NOT_ON_TOP_LEVEL;
         }
         Problem::IllegalDerivedAbility(region) => {
             doc = alloc.stack([
@@ -809,6 +847,7 @@ pub fn can_problem<'b>(
                     .append(list_builtin_abilities(alloc)),
             ]);
             title = ILLEGAL_DERIVE.to_string();
+            code = codes::ILLEGAL_DERIVE;
         }
         Problem::NotAnAbility(region) => {
             doc = alloc.stack([
@@ -817,6 +856,7 @@ pub fn can_problem<'b>(
                 alloc.reflow("Only abilities can be implemented."),
             ]);
             title = NOT_AN_ABILITY.to_string();
+            code = codes::NOT_AN_ABILITY;
         }
         Problem::NotAnAbilityMember {
             ability,
@@ -831,6 +871,7 @@ pub fn can_problem<'b>(
                 alloc.reflow("Only implementations for members an ability has can be specified in this location.")
             ]);
             title = NOT_AN_ABILITY_MEMBER.to_string();
+            code = codes::NOT_AN_ABILITY_MEMBER;
         }
         Problem::ImplementationNotFound { member, region } => {
             let member_str = member.as_str(alloc.interns);
@@ -842,6 +883,7 @@ pub fn can_problem<'b>(
                 alloc.tip().append(alloc.concat([alloc.reflow("consider adding a value of name "), alloc.symbol_unqualified(member), alloc.reflow(" in this scope, or using another variable that implements this ability member, like "), alloc.type_str(&format!("{{ {member_str}: my{member_str} }}"))]))
             ]);
             title = IMPLEMENTATION_NOT_FOUND.to_string();
+            code = codes::IMPLEMENTATION_NOT_FOUND;
         }
         Problem::OptionalAbilityImpl { ability, region } => {
             let hint = if ability.is_builtin() {
@@ -860,6 +902,7 @@ pub fn can_problem<'b>(
                 hint,
             ]);
             title = OPTIONAL_ABILITY_IMPLEMENTATION.to_string();
+            code = codes::OPTIONAL_ABILITY_IMPLEMENTATION;
         }
         Problem::QualifiedAbilityImpl { region } => {
             doc = alloc.stack([
@@ -870,6 +913,7 @@ pub fn can_problem<'b>(
                 ),
             ]);
             title = QUALIFIED_ABILITY_IMPLEMENTATION.to_string();
+            code = codes::QUALIFIED_ABILITY_IMPLEMENTATION;
         }
         Problem::AbilityImplNotIdent { region } => {
             doc = alloc.stack([
@@ -881,6 +925,7 @@ pub fn can_problem<'b>(
                 alloc.tip().append(alloc.reflow("consider defining this expression as a variable."))
             ]);
             title = ABILITY_IMPLEMENTATION_NOT_IDENTIFIER.to_string();
+            code = codes::ABILITY_IMPLEMENTATION_NOT_IDENTIFIER;
         }
         Problem::DuplicateImpl {
             original,
@@ -895,6 +940,7 @@ pub fn can_problem<'b>(
                     .reflow("Only one custom implementation can be defined for an ability member."),
             ]);
             title = DUPLICATE_IMPLEMENTATION.to_string();
+            code = codes::DUPLICATE_IMPLEMENTATION;
         }
         Problem::ImplementsNonRequired {
             region,
@@ -919,6 +965,7 @@ pub fn can_problem<'b>(
                 ),
             ]);
             title = UNNECESSARY_IMPLEMENTATIONS.to_string();
+            code = codes::UNNECESSARY_IMPLEMENTATIONS;
         }
         Problem::DoesNotImplementAbility {
             region,
@@ -943,6 +990,7 @@ pub fn can_problem<'b>(
                 ),
             ]);
             title = INCOMPLETE_ABILITY_IMPLEMENTATION.to_string();
+            code = codes::INCOMPLETE_ABILITY_IMPLEMENTATION;
         }
         Problem::NotBoundInAllPatterns {
             unbound_symbol,
@@ -963,6 +1011,7 @@ pub fn can_problem<'b>(
                 ]),
             ]);
             title = "NAME NOT BOUND IN ALL PATTERNS".to_string();
+            code = codes::NAME_NOT_BOUND_IN_ALL_PATTERNS;
         }
         Problem::NoIdentifiersIntroduced(region) => {
             doc = alloc.stack([
@@ -971,6 +1020,7 @@ pub fn can_problem<'b>(
                 alloc.reflow("If you don't need to use the value on the right-hand-side of this assignment, consider removing the assignment. Since Roc is purely functional, assignments that don't introduce variables cannot affect a program's behavior!"),
             ]);
             title = "UNNECESSARY DEFINITION".to_string();
+            code = codes::UNNECESSARY_DEFINITION;
         }
         Problem::OverloadedSpecialization {
             ability_member,
@@ -990,6 +1040,7 @@ pub fn can_problem<'b>(
                 alloc.reflow("Ability specializations can only provide implementations for one opaque type, since all opaque types are different!"),
             ]);
             title = "OVERLOADED SPECIALIZATION".to_string();
+            code = codes::OVERLOADED_SPECIALIZATION;
         }
         Problem::UnnecessaryOutputWildcard { region } => {
             doc = alloc.stack([
@@ -1009,6 +1060,7 @@ pub fn can_problem<'b>(
                 alloc.reflow("You can safely remove this to make the code more concise without changing what it means."),
             ]);
             title = "UNNECESSARY WILDCARD".to_string();
+            code = codes::UNNECESSARY_WILDCARD;
         }
         Problem::MultipleListRestPattern { region } => {
             doc = alloc.stack([
@@ -1021,6 +1073,7 @@ pub fn can_problem<'b>(
                 ]),
             ]);
             title = "MULTIPLE LIST REST PATTERNS".to_string();
+            code = codes::MULTIPLE_LIST_REST_PATTERNS;
         }
         Problem::BadTypeArguments {
             symbol,
@@ -1055,10 +1108,16 @@ pub fn can_problem<'b>(
                 alloc.reflow("Are there missing parentheses?"),
             ]);
 
-            title = if type_got > alias_needs {
-                "TOO MANY TYPE ARGUMENTS".to_string()
+            (title, code) = if type_got > alias_needs {
+                (
+                    "TOO MANY TYPE ARGUMENTS".to_string(),
+                    codes::TOO_MANY_TYPE_ARGUMENTS,
+                )
             } else {
-                "TOO FEW TYPE ARGUMENTS".to_string()
+                (
+                    "TOO FEW TYPE ARGUMENTS".to_string(),
+                    codes::TOO_FEW_TYPE_ARGUMENTS,
+                )
             };
         }
         Problem::UnappliedCrash { region } => {
@@ -1073,6 +1132,7 @@ pub fn can_problem<'b>(
                 ])
             ]);
             title = "UNAPPLIED CRASH".to_string();
+            code = codes::UNAPPLIED_CRASH;
         }
         Problem::OverAppliedCrash { region } => {
             doc = alloc.stack([
@@ -1088,16 +1148,19 @@ pub fn can_problem<'b>(
                 ]),
             ]);
             title = "OVERAPPLIED CRASH".to_string();
+            code = codes::OVERAPPLIED_CRASH;
         }
         Problem::FileProblem { filename, error } => {
             let report = to_file_problem_report(alloc, filename, error);
             doc = report.doc;
             title = report.title;
+            code = report.code;
         }
     };
 
     Report {
         title,
+        code,
         filename,
         doc,
         severity,
@@ -1131,6 +1194,7 @@ fn to_invalid_optional_value_report<'b>(
 
     Report {
         title: "BAD OPTIONAL VALUE".to_string(),
+        code: codes::BAD_OPTIONAL_VALUE,
         filename,
         doc,
         severity: Severity::RuntimeError,
@@ -1588,9 +1652,10 @@ fn pretty_runtime_error<'b>(
     alloc: &'b RocDocAllocator<'b>,
     lines: &LineInfo,
     runtime_error: RuntimeError,
-) -> (RocDocBuilder<'b>, &'static str) {
+) -> (RocDocBuilder<'b>, &'static str, ReportCode) {
     let doc;
     let title;
+    let code;
 
     match runtime_error {
         RuntimeError::VoidValue => {
@@ -1610,6 +1675,7 @@ fn pretty_runtime_error<'b>(
             kind,
         } => {
             (title, doc) = report_shadowing(alloc, lines, original_region, shadow, kind);
+            code = codes::DUPLICATE_NAME;
         }
 
         RuntimeError::LookupNotInScope {
@@ -1626,10 +1692,12 @@ fn pretty_runtime_error<'b>(
                 underscored_suggestion_region,
             );
             title = UNRECOGNIZED_NAME;
+            code = codes::UNRECOGNIZED_NAME;
         }
         RuntimeError::CircularDef(entries) => {
             doc = to_circular_def_doc(alloc, lines, &entries);
             title = CIRCULAR_DEF;
+            code = codes::CIRCULAR_DEFINITION;
         }
         RuntimeError::MalformedPattern(problem, region) => {
             use roc_parse::ast::Base;
@@ -1644,9 +1712,10 @@ fn pretty_runtime_error<'b>(
                 MalformedBase(Base::Decimal) => " integer ",
                 BadIdent(bad_ident) => {
                     title = NAMING_PROBLEM;
+                    code = codes::BAD_PATTERN_IDENTIFIER;
                     doc = to_bad_ident_pattern_report(alloc, lines, bad_ident, region);
 
-                    return (doc, title);
+                    return (doc, title, code);
                 }
                 Unknown => " ",
                 QualifiedIdentifier => " qualified ",
@@ -1681,6 +1750,7 @@ fn pretty_runtime_error<'b>(
             ]);
 
             title = SYNTAX_PROBLEM;
+            code = codes::MALFORMED_PATTERN;
         }
         RuntimeError::UnsupportedPattern(_) => {
             todo!("unsupported patterns are currently not parsed!")
@@ -1722,6 +1792,7 @@ fn pretty_runtime_error<'b>(
             ]);
 
             title = VALUE_NOT_EXPOSED;
+            code = codes::NOT_EXPOSED;
         }
 
         RuntimeError::ModuleNotImported {
@@ -1740,6 +1811,7 @@ fn pretty_runtime_error<'b>(
             );
 
             title = MODULE_NOT_IMPORTED;
+            code = codes::MODULE_NOT_IMPORTED;
         }
         RuntimeError::InvalidPrecedence(_, _) => {
             // do nothing, reported with PrecedenceProblem
@@ -1749,6 +1821,7 @@ fn pretty_runtime_error<'b>(
             doc = to_bad_ident_expr_report(alloc, lines, bad_ident, surroundings);
 
             title = SYNTAX_PROBLEM;
+            code = codes::MALFORMED_IDENTIFIER;
         }
         RuntimeError::MalformedTypeName(_box_str, surroundings) => {
             doc = alloc.stack([
@@ -1765,6 +1838,7 @@ fn pretty_runtime_error<'b>(
             ]);
 
             title = SYNTAX_PROBLEM;
+            code = codes::MALFORMED_TYPE_NAME;
         }
         RuntimeError::MalformedClosure(_) => {
             todo!("");
@@ -1799,6 +1873,7 @@ fn pretty_runtime_error<'b>(
             ]);
 
             title = SYNTAX_PROBLEM;
+            code = codes::INVALID_FLOAT;
         }
         RuntimeError::InvalidFloat(FloatErrorKind::Error, region, _raw_str) => {
             let tip = alloc
@@ -1817,6 +1892,7 @@ fn pretty_runtime_error<'b>(
             ]);
 
             title = SYNTAX_PROBLEM;
+            code = codes::INVALID_FLOAT;
         }
         RuntimeError::InvalidFloat(FloatErrorKind::IntSuffix, region, _raw_str) => {
             doc = alloc.stack([
@@ -1827,6 +1903,7 @@ fn pretty_runtime_error<'b>(
             ]);
 
             title = CONFLICTING_NUMBER_SUFFIX;
+            code = codes::CONFLICTING_NUMBER_SUFFIX;
         }
         RuntimeError::InvalidInt(error @ IntErrorKind::InvalidDigit, base, region, _raw_str)
         | RuntimeError::InvalidInt(error @ IntErrorKind::Empty, base, region, _raw_str) => {
@@ -1888,6 +1965,7 @@ fn pretty_runtime_error<'b>(
             ]);
 
             title = SYNTAX_PROBLEM;
+            code = codes::INVALID_INTEGER;
         }
         RuntimeError::InvalidInt(error_kind @ IntErrorKind::Underflow, _base, region, _raw_str)
         | RuntimeError::InvalidInt(error_kind @ IntErrorKind::Overflow, _base, region, _raw_str) => {
@@ -1931,6 +2009,7 @@ fn pretty_runtime_error<'b>(
             ]);
 
             title = SYNTAX_PROBLEM;
+            code = codes::INVALID_INTEGER;
         }
         RuntimeError::InvalidInt(IntErrorKind::FloatSuffix, _base, region, _raw_str) => {
             doc = alloc.stack([
@@ -1941,6 +2020,7 @@ fn pretty_runtime_error<'b>(
             ]);
 
             title = CONFLICTING_NUMBER_SUFFIX;
+            code = codes::CONFLICTING_NUMBER_SUFFIX;
         }
         RuntimeError::InvalidInt(
             IntErrorKind::OverflowsSuffix {
@@ -1965,6 +2045,7 @@ fn pretty_runtime_error<'b>(
             ]);
 
             title = NUMBER_OVERFLOWS_SUFFIX;
+            code = codes::NUMBER_OVERFLOWS_SUFFIX;
         }
         RuntimeError::InvalidInt(
             IntErrorKind::UnderflowsSuffix {
@@ -1989,6 +2070,7 @@ fn pretty_runtime_error<'b>(
             ]);
 
             title = NUMBER_UNDERFLOWS_SUFFIX;
+            code = codes::NUMBER_UNDERFLOWS_SUFFIX;
         }
         RuntimeError::InvalidOptionalValue {
             field_name,
@@ -2004,6 +2086,7 @@ fn pretty_runtime_error<'b>(
             );
 
             title = SYNTAX_PROBLEM;
+            code = codes::BAD_OPTIONAL_VALUE;
         }
         RuntimeError::InvalidRecordUpdate { region } => {
             doc = alloc.stack([
@@ -2016,6 +2099,7 @@ fn pretty_runtime_error<'b>(
             ]);
 
             title = SYNTAX_PROBLEM;
+            code = codes::INVALID_RECORD_UPDATE;
         }
         RuntimeError::InvalidHexadecimal(region) => {
             todo!(
@@ -2049,6 +2133,7 @@ fn pretty_runtime_error<'b>(
                 .append(alloc.reflow(", but it was not defined anywhere in that module."))]);
 
             title = MISSING_DEFINITION;
+            code = codes::MISSING_DEFINITION;
         }
         RuntimeError::EmptySingleQuote(region) => {
             let tip = alloc
@@ -2062,6 +2147,7 @@ fn pretty_runtime_error<'b>(
             ]);
 
             title = SYNTAX_PROBLEM;
+            code = codes::INVALID_CHARACTER_LITERAL;
         }
         RuntimeError::MultipleCharsInSingleQuote(region) => {
             let tip = alloc
@@ -2078,6 +2164,7 @@ fn pretty_runtime_error<'b>(
             ]);
 
             title = SYNTAX_PROBLEM;
+            code = codes::INVALID_CHARACTER_LITERAL;
         }
         RuntimeError::OpaqueNotDefined {
             usage:
@@ -2128,6 +2215,7 @@ fn pretty_runtime_error<'b>(
             doc = alloc.stack(stack);
 
             title = OPAQUE_NOT_DEFINED;
+            code = codes::OPAQUE_TYPE_NOT_DEFINED;
         }
         RuntimeError::OpaqueOutsideScope {
             opaque,
@@ -2149,6 +2237,7 @@ fn pretty_runtime_error<'b>(
             ]);
 
             title = OPAQUE_DECLARED_OUTSIDE_SCOPE;
+            code = codes::OPAQUE_TYPE_DECLARED_OUTSIDE_SCOPE;
         }
         RuntimeError::OpaqueNotApplied(loc_ident) => {
             doc = alloc.stack([
@@ -2158,6 +2247,7 @@ fn pretty_runtime_error<'b>(
             ]);
 
             title = OPAQUE_NOT_APPLIED;
+            code = codes::OPAQUE_TYPE_NOT_APPLIED;
         }
         RuntimeError::OpaqueAppliedToMultipleArgs(region) => {
             doc = alloc.stack([
@@ -2167,6 +2257,7 @@ fn pretty_runtime_error<'b>(
             ]);
 
             title = OPAQUE_OVER_APPLIED;
+            code = codes::OPAQUE_TYPE_APPLIED_TO_TOO_MANY_ARGS;
         }
         RuntimeError::DegenerateBranch(region) => {
             doc = alloc.stack([
@@ -2175,6 +2266,7 @@ fn pretty_runtime_error<'b>(
             ]);
 
             title = "DEGENERATE BRANCH";
+            code = codes::DEGENERATE_BRANCH;
         }
         RuntimeError::MultipleRecordBuilders(region) => {
             let tip = alloc
@@ -2189,6 +2281,7 @@ fn pretty_runtime_error<'b>(
             ]);
 
             title = "MULTIPLE RECORD BUILDERS";
+            code = codes::MULTIPLE_RECORD_BUILDERS;
         }
         RuntimeError::UnappliedRecordBuilder(region) => {
             doc = alloc.stack([
@@ -2201,10 +2294,11 @@ fn pretty_runtime_error<'b>(
             ]);
 
             title = "UNAPPLIED RECORD BUILDER";
+            code = codes::UNAPPLIED_RECORD_BUILDER;
         }
     }
 
-    (doc, title)
+    (doc, title, code)
 }
 
 pub fn to_circular_def_doc<'b>(
@@ -2270,6 +2364,9 @@ fn not_found<'b>(
     );
     suggestions.truncate(4);
 
+    // Built first, so that structured reports point at the name rather than at the similar one
+    let snippet = alloc.region(lines.convert_region(region));
+
     let default_no = alloc.concat([
         alloc.reflow("Is there an "),
         alloc.keyword("import"),
@@ -2295,7 +2392,11 @@ fn not_found<'b>(
             alloc.stack([
                 yes_suggestion_details,
                 alloc
-                    .vcat(suggestions.into_iter().map(|v| alloc.string(v.to_string())))
+                    .vcat(suggestions.into_iter().map(|v| {
+                        alloc
+                            .string(v.to_string())
+                            .annotate(Annotation::Replacement)
+                    }))
                     .indent(4),
             ])
         }
@@ -2307,7 +2408,7 @@ fn not_found<'b>(
             alloc.string(name.to_string()),
             alloc.reflow("` in this scope."),
         ]),
-        alloc.region(lines.convert_region(region)),
+        snippet,
         to_details(default_no, default_yes),
     ])
 }
@@ -10,6 +10,7 @@ use roc_types::{
     types::{ErrorType, Polarity},
 };
 
+use crate::codes;
 use crate::report::{RenderTarget, RocDocAllocator, RocDocBuilder};
 
 pub struct Renderer<'a> {
@@ -153,6 +154,7 @@ impl<'a> Renderer<'a> {
 
         let report = Report {
             title: "EXPECT FAILED".into(),
+            code: codes::EXPECT_FAILED,
             doc,
             filename: self.filename.clone(),
             severity: Severity::RuntimeError,
@@ -224,6 +226,55 @@ impl<'a> Renderer<'a> {
 
         let report = Report {
             title: "EXPECT PANICKED".into(),
+            code: codes::EXPECT_PANICKED,
+            doc,
+            filename: self.filename.clone(),
+            severity: Severity::RuntimeError,
+        };
+
+        let mut buf = String::new();
+
+        report.render(
+            self.render_target,
+            &mut buf,
+            &self.alloc,
+            &crate::report::DEFAULT_PALETTE,
+        );
+
+        write!(writer, "{buf}")
+    }
+
+    pub fn render_timeout<W>(
+        &self,
+        writer: &mut W,
+        timeout: std::time::Duration,
+        expect_region: Region,
+    ) -> std::io::Result<()>
+    where
+        W: std::io::Write,
+    {
+        use crate::report::Report;
+        use ven_pretty::DocAllocator;
+
+        let line_col_region = self.line_info.convert_region(expect_region);
+
+        let doc = self.alloc.stack([
+            self.alloc.concat([
+                self.alloc.reflow("This expectation was stopped because it was still running after "),
+                self.alloc.text(format!("{:?}", timeout)),
+                self.alloc.reflow(":"),
+            ]),
+            self.alloc.region(line_col_region),
+            self.alloc.concat([
+                self.alloc.reflow("It may be stuck in an infinite loop. If it just needs more time, raise the limit with "),
+                self.alloc.parser_suggestion("roc test --timeout"),
+                self.alloc.reflow("."),
+            ]),
+        ]);
+
+        let report = Report {
+            title: "EXPECT TIMED OUT".into(),
+            code: codes::EXPECT_TIMED_OUT,
             doc,
             filename: self.filename.clone(),
             severity: Severity::RuntimeError,
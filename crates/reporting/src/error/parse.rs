@@ -3,6 +3,7 @@ use roc_problem::Severity;
 use roc_region::all::{LineColumn, LineColumnRegion, LineInfo, Position, Region};
 use std::path::PathBuf;
 
+use crate::codes;
 use crate::report::{Report, RocDocAllocator, RocDocBuilder};
 use ven_pretty::DocAllocator;
 
@@ -64,6 +65,7 @@ fn to_syntax_report<'a>(
         filename: filename.clone(),
         doc,
         title: "PARSE PROBLEM".to_string(),
+        code: codes::PARSE_PROBLEM,
         severity: Severity::RuntimeError,
     };
 
@@ -78,6 +80,7 @@ fn to_syntax_report<'a>(
                 filename,
                 doc,
                 title: "PARSE PROBLEM".to_string(),
+                code: codes::PARSE_PROBLEM,
                 severity: Severity::RuntimeError,
             }
         }
@@ -110,6 +113,7 @@ fn to_syntax_report<'a>(
                 filename,
                 doc,
                 title: "NOT END OF FILE".to_string(),
+                code: codes::NOT_END_OF_FILE,
                 severity: Severity::RuntimeError,
             }
         }
@@ -123,6 +127,7 @@ fn to_syntax_report<'a>(
                 filename,
                 doc,
                 title: "PARSE PROBLEM".to_string(),
+                code: codes::PARSE_PROBLEM,
                 severity: Severity::RuntimeError,
             }
         }
@@ -133,6 +138,7 @@ fn to_syntax_report<'a>(
                 filename,
                 doc,
                 title: "PARSE PROBLEM".to_string(),
+                code: codes::PARSE_PROBLEM,
                 severity: Severity::RuntimeError,
             }
         }
@@ -214,6 +220,7 @@ fn to_expr_report<'a>(
                 filename,
                 doc,
                 title: "ARGUMENTS BEFORE EQUALS".to_string(),
+                code: codes::ARGUMENTS_BEFORE_EQUALS,
                 severity: Severity::RuntimeError,
             }
         }
@@ -328,6 +335,7 @@ fn to_expr_report<'a>(
                 filename,
                 doc,
                 title: "UNKNOWN OPERATOR".to_string(),
+                code: codes::UNKNOWN_OPERATOR,
                 severity: Severity::RuntimeError,
             }
         }
@@ -354,14 +362,16 @@ fn to_expr_report<'a>(
                 filename,
                 doc,
                 title: "WEIRD IDENTIFIER".to_string(),
+                code: codes::WEIRD_IDENTIFIER,
                 severity: Severity::RuntimeError,
             }
         }
 
         EExpr::Start(pos) | EExpr::IndentStart(pos) => {
-            let (title, expecting) = match &context {
+            let (title, code, expecting) = match &context {
                 Context::InNode { .. } | Context::InDef { .. } => (
                     "MISSING EXPRESSION",
+                    codes::MISSING_EXPRESSION,
                     alloc.concat([
                         alloc.reflow("I was expecting to see an expression like "),
                         alloc.parser_suggestion("42"),
@@ -372,6 +382,7 @@ fn to_expr_report<'a>(
                 ),
                 Context::InDefFinalExpr { .. } => (
                     "MISSING FINAL EXPRESSION",
+                    codes::MISSING_FINAL_EXPRESSION,
                     alloc.stack([
                         alloc.concat([
                             alloc.reflow("This definition is missing a final expression."),
@@ -436,6 +447,7 @@ fn to_expr_report<'a>(
                 filename,
                 doc,
                 title: title.to_string(),
+                code,
                 severity: Severity::RuntimeError,
             }
         }
@@ -464,6 +476,7 @@ fn to_expr_report<'a>(
                 filename,
                 doc,
                 title: "MISSING FINAL EXPRESSION".to_string(),
+                code: codes::MISSING_FINAL_EXPRESSION,
                 severity: Severity::RuntimeError,
             }
         }
@@ -495,6 +508,7 @@ fn to_expr_report<'a>(
                 filename,
                 doc,
                 title: "SYNTAX PROBLEM".to_string(),
+                code: codes::BAD_EXPRESSION_END,
                 severity: Severity::RuntimeError,
             }
         }
@@ -518,6 +532,7 @@ fn to_expr_report<'a>(
                 filename,
                 doc,
                 title: "ARGUMENTS BEFORE EQUALS".to_string(),
+                code: codes::ARGUMENTS_BEFORE_EQUALS,
                 severity: Severity::RuntimeError,
             }
         }
@@ -536,6 +551,7 @@ fn to_expr_report<'a>(
                 filename,
                 doc,
                 title: "BAD BACKPASSING ARROW".to_string(),
+                code: codes::BAD_BACKPASSING_ARROW,
                 severity: Severity::RuntimeError,
             }
         }
@@ -554,6 +570,7 @@ fn to_expr_report<'a>(
                 filename,
                 doc,
                 title: "RECORD PARSE PROBLEM".to_string(),
+                code: codes::RECORD_PARSE_PROBLEM,
                 severity: Severity::RuntimeError,
             }
         }
@@ -574,6 +591,7 @@ fn to_expr_report<'a>(
                 filename,
                 doc,
                 title: "BAD RECORD BUILDER".to_string(),
+                code: codes::BAD_RECORD_BUILDER,
                 severity: Severity::RuntimeError,
             }
         }
@@ -594,6 +612,7 @@ fn to_expr_report<'a>(
                 filename,
                 doc,
                 title: "BAD RECORD UPDATE".to_string(),
+                code: codes::BAD_RECORD_UPDATE,
                 severity: Severity::RuntimeError,
             }
         }
@@ -660,6 +679,7 @@ fn to_expr_report<'a>(
                 filename,
                 doc,
                 title: "INDENT ENDS AFTER EXPRESSION".to_string(),
+                code: codes::INDENT_ENDS_AFTER_EXPRESSION,
                 severity: Severity::RuntimeError,
             }
         }
@@ -684,6 +704,7 @@ fn to_expr_report<'a>(
                 filename,
                 doc,
                 title: "TRAILING OPERATOR".to_string(),
+                code: codes::TRAILING_OPERATOR,
                 severity: Severity::RuntimeError,
             }
         }
@@ -701,6 +722,7 @@ fn to_expr_report<'a>(
                 filename,
                 doc,
                 title: "UNEXPECTED COMMA".to_string(),
+                code: codes::UNEXPECTED_COMMA,
                 severity: Severity::RuntimeError,
             }
         }
@@ -739,6 +761,7 @@ fn to_lambda_report<'a>(
                     filename,
                     doc,
                     title: "WEIRD ARROW".to_string(),
+                    code: codes::WEIRD_ARROW,
                     severity: Severity::RuntimeError,
                 }
             }
@@ -761,6 +784,7 @@ fn to_lambda_report<'a>(
                     filename,
                     doc,
                     title: "MISSING ARROW".to_string(),
+                    code: codes::MISSING_ARROW,
                     severity: Severity::RuntimeError,
                 }
             }
@@ -786,6 +810,7 @@ fn to_lambda_report<'a>(
                     filename,
                     doc,
                     title: "WEIRD ARROW".to_string(),
+                    code: codes::WEIRD_ARROW,
                     severity: Severity::RuntimeError,
                 }
             }
@@ -808,6 +833,7 @@ fn to_lambda_report<'a>(
                     filename,
                     doc,
                     title: "MISSING ARROW".to_string(),
+                    code: codes::MISSING_ARROW,
                     severity: Severity::RuntimeError,
                 }
             }
@@ -832,6 +858,7 @@ fn to_lambda_report<'a>(
                     filename,
                     doc,
                     title: "UNFINISHED ARGUMENT LIST".to_string(),
+                    code: codes::UNFINISHED_ARGUMENT_LIST,
                     severity: Severity::RuntimeError,
                 }
             }
@@ -853,6 +880,7 @@ fn to_lambda_report<'a>(
                     filename,
                     doc,
                     title: "MISSING ARROW".to_string(),
+                    code: codes::MISSING_ARROW,
                     severity: Severity::RuntimeError,
                 }
             }
@@ -934,6 +962,7 @@ fn to_unfinished_lambda_report<'a>(
         filename,
         doc,
         title: "UNFINISHED FUNCTION".to_string(),
+        code: codes::UNFINISHED_FUNCTION,
         severity: Severity::RuntimeError,
     }
 }
@@ -1000,6 +1029,7 @@ fn to_str_report<'a>(
                 filename,
                 doc,
                 title: "WEIRD ESCAPE".to_string(),
+                code: codes::WEIRD_ESCAPE,
                 severity: Severity::RuntimeError,
             }
         }
@@ -1026,6 +1056,7 @@ fn to_str_report<'a>(
                 filename,
                 doc,
                 title: "WEIRD CODE POINT".to_string(),
+                code: codes::WEIRD_CODE_POINT,
                 severity: Severity::RuntimeError,
             }
         }
@@ -1047,6 +1078,7 @@ fn to_str_report<'a>(
                 filename,
                 doc,
                 title: "ENDLESS FORMAT".to_string(),
+                code: codes::ENDLESS_FORMAT,
                 severity: Severity::RuntimeError,
             }
         }
@@ -1070,6 +1102,7 @@ fn to_str_report<'a>(
                 filename,
                 doc,
                 title: "ENDLESS SCALAR".to_string(),
+                code: codes::ENDLESS_SCALAR,
                 severity: Severity::RuntimeError,
             }
         }
@@ -1136,6 +1169,7 @@ fn to_str_report<'a>(
                 filename,
                 doc,
                 title: "INVALID SCALAR".to_string(),
+                code: codes::INVALID_SCALAR,
                 severity: Severity::RuntimeError,
             }
         }
@@ -1159,6 +1193,7 @@ fn to_str_report<'a>(
                 filename,
                 doc,
                 title: "ENDLESS STRING".to_string(),
+                code: codes::ENDLESS_STRING,
                 severity: Severity::RuntimeError,
             }
         }
@@ -1183,6 +1218,7 @@ fn to_str_report<'a>(
                 filename,
                 doc,
                 title: "EXPECTED STRING".to_string(),
+                code: codes::EXPECTED_STRING,
                 severity: Severity::RuntimeError,
             }
         }
@@ -1206,6 +1242,7 @@ fn to_str_report<'a>(
                 filename,
                 doc,
                 title: "ENDLESS STRING".to_string(),
+                code: codes::ENDLESS_STRING,
                 severity: Severity::RuntimeError,
             }
         }
@@ -1227,6 +1264,7 @@ fn to_str_report<'a>(
                 filename,
                 doc,
                 title: "INSUFFICIENT INDENT IN MULTI-LINE STRING".to_string(),
+                code: codes::INSUFFICIENT_INDENT_IN_MULTI_LINE_STRING,
                 severity: Severity::RuntimeError,
             }
         }
@@ -1269,6 +1307,7 @@ fn to_expr_in_parens_report<'a>(
                 filename,
                 doc,
                 title: "EMPTY PARENTHESES".to_string(),
+                code: codes::EMPTY_PARENTHESES,
                 severity: Severity::RuntimeError,
             }
         }
@@ -1293,6 +1332,7 @@ fn to_expr_in_parens_report<'a>(
                 filename,
                 doc,
                 title: "UNFINISHED PARENTHESES".to_string(),
+                code: codes::UNFINISHED_PARENTHESES,
                 severity: Severity::RuntimeError,
             }
         }
@@ -1318,6 +1358,7 @@ fn to_expr_in_parens_report<'a>(
                 filename,
                 doc,
                 title: "UNFINISHED PARENTHESES".to_string(),
+                code: codes::UNFINISHED_PARENTHESES,
                 severity: Severity::RuntimeError,
             }
         }
@@ -1368,6 +1409,7 @@ fn to_list_report<'a>(
                         filename,
                         doc,
                         title: "UNFINISHED LIST".to_string(),
+                        code: codes::UNFINISHED_LIST,
                         severity: Severity::RuntimeError,
                     }
                 }
@@ -1401,6 +1443,7 @@ fn to_list_report<'a>(
                         filename,
                         doc,
                         title: "UNFINISHED LIST".to_string(),
+                        code: codes::UNFINISHED_LIST,
                         severity: Severity::RuntimeError,
                     }
                 }
@@ -1548,6 +1591,7 @@ fn to_unfinished_if_report<'a>(
         filename,
         doc,
         title: "UNFINISHED IF".to_string(),
+        code: codes::UNFINISHED_IF,
         severity: Severity::RuntimeError,
     }
 }
@@ -1581,6 +1625,7 @@ fn to_when_report<'a>(
                         filename,
                         doc,
                         title: "IF GUARD NO CONDITION".to_string(),
+                        code: codes::IF_GUARD_NO_CONDITION,
                         severity: Severity::RuntimeError,
                     }
                 }
@@ -1613,6 +1658,7 @@ fn to_when_report<'a>(
                 filename,
                 doc,
                 title: "MISSING ARROW".to_string(),
+                code: codes::MISSING_ARROW,
                 severity: Severity::RuntimeError,
             }
         }
@@ -1768,6 +1814,7 @@ fn to_unfinished_when_report<'a>(
                 filename,
                 doc,
                 title: "UNFINISHED WHEN".to_string(),
+                code: codes::UNFINISHED_WHEN,
                 severity: Severity::RuntimeError,
             }
         }
@@ -1808,6 +1855,7 @@ fn to_unexpected_arrow_report<'a>(
         filename,
         doc,
         title: "UNEXPECTED ARROW".to_string(),
+        code: codes::UNEXPECTED_ARROW,
         severity: Severity::RuntimeError,
     }
 }
@@ -1884,6 +1932,7 @@ fn to_pattern_report<'a>(
                 filename,
                 doc,
                 title: "UNFINISHED PATTERN".to_string(),
+                code: codes::UNFINISHED_PATTERN,
                 severity: Severity::RuntimeError,
             }
         }
@@ -1928,6 +1977,7 @@ fn to_precord_report<'a>(
                     filename,
                     doc,
                     title: "UNFINISHED RECORD PATTERN".to_string(),
+                    code: codes::UNFINISHED_RECORD_PATTERN,
                     severity: Severity::RuntimeError,
                 }
             }
@@ -1945,6 +1995,7 @@ fn to_precord_report<'a>(
                     filename,
                     doc,
                     title: "UNFINISHED RECORD PATTERN".to_string(),
+                    code: codes::UNFINISHED_RECORD_PATTERN,
                     severity: Severity::RuntimeError,
                 }
             }
@@ -1970,6 +2021,7 @@ fn to_precord_report<'a>(
                         filename,
                         doc,
                         title: "UNFINISHED RECORD PATTERN".to_string(),
+                        code: codes::UNFINISHED_RECORD_PATTERN,
                         severity: Severity::RuntimeError,
                     }
                 }
@@ -1990,6 +2042,7 @@ fn to_precord_report<'a>(
                         filename,
                         doc,
                         title: "UNFINISHED RECORD PATTERN".to_string(),
+                        code: codes::UNFINISHED_RECORD_PATTERN,
                         severity: Severity::RuntimeError,
                     }
                 }
@@ -2015,6 +2068,7 @@ fn to_precord_report<'a>(
                     filename,
                     doc,
                     title: "UNFINISHED RECORD PATTERN".to_string(),
+                    code: codes::UNFINISHED_RECORD_PATTERN,
                     severity: Severity::RuntimeError,
                 }
             }
@@ -2040,6 +2094,7 @@ fn to_precord_report<'a>(
                     filename,
                     doc,
                     title: "PROBLEM IN RECORD PATTERN".to_string(),
+                    code: codes::PROBLEM_IN_RECORD_PATTERN,
                     severity: Severity::RuntimeError,
                 }
             }
@@ -2093,6 +2148,7 @@ fn to_plist_report<'a>(
                 filename,
                 doc,
                 title: "UNFINISHED LIST PATTERN".to_string(),
+                code: codes::UNFINISHED_LIST_PATTERN,
                 severity: Severity::RuntimeError,
             }
         }
@@ -2115,6 +2171,7 @@ fn to_plist_report<'a>(
                 filename,
                 doc,
                 title: "UNFINISHED LIST PATTERN".to_string(),
+                code: codes::UNFINISHED_LIST_PATTERN,
                 severity: Severity::RuntimeError,
             }
         }
@@ -2137,6 +2194,7 @@ fn to_plist_report<'a>(
                 filename,
                 doc,
                 title: "INCORRECT REST PATTERN".to_string(),
+                code: codes::INCORRECT_REST_PATTERN,
                 severity: Severity::RuntimeError,
             }
         }
@@ -2180,6 +2238,7 @@ fn to_pattern_in_parens_report<'a>(
                 filename,
                 doc,
                 title: "UNFINISHED PARENTHESES".to_string(),
+                code: codes::UNFINISHED_PARENTHESES,
                 severity: Severity::RuntimeError,
             }
         }
@@ -2201,6 +2260,7 @@ fn to_pattern_in_parens_report<'a>(
                 filename,
                 doc,
                 title: "EMPTY PARENTHESES".to_string(),
+                code: codes::EMPTY_PARENTHESES,
                 severity: Severity::RuntimeError,
             }
         }
@@ -2225,6 +2285,7 @@ fn to_pattern_in_parens_report<'a>(
                 filename,
                 doc,
                 title: "UNFINISHED PARENTHESES".to_string(),
+                code: codes::UNFINISHED_PARENTHESES,
                 severity: Severity::RuntimeError,
             }
         }
@@ -2253,6 +2314,7 @@ fn to_malformed_number_literal_report<'a>(
         filename,
         doc,
         title: "INVALID NUMBER LITERAL".to_string(),
+        code: codes::INVALID_NUMBER_LITERAL,
         severity: Severity::RuntimeError,
     }
 }
@@ -2293,6 +2355,7 @@ fn to_type_report<'a>(
                         filename,
                         doc,
                         title: "DOUBLE COMMA".to_string(),
+                        code: codes::DOUBLE_COMMA,
                         severity: Severity::RuntimeError,
                     }
                 }
@@ -2320,6 +2383,7 @@ fn to_type_report<'a>(
                 filename,
                 doc,
                 title: "UNFINISHED TYPE".to_string(),
+                code: codes::UNFINISHED_TYPE,
                 severity: Severity::RuntimeError,
             }
         }
@@ -2338,6 +2402,7 @@ fn to_type_report<'a>(
                 filename,
                 doc,
                 title: "UNFINISHED TYPE".to_string(),
+                code: codes::UNFINISHED_TYPE,
                 severity: Severity::RuntimeError,
             }
         }
@@ -2356,6 +2421,7 @@ fn to_type_report<'a>(
                 filename,
                 doc,
                 title: "UNFINISHED TYPE".to_string(),
+                code: codes::UNFINISHED_TYPE,
                 severity: Severity::RuntimeError,
             }
         }
@@ -2374,6 +2440,7 @@ fn to_type_report<'a>(
                 filename,
                 doc,
                 title: "UNFINISHED INLINE ALIAS".to_string(),
+                code: codes::UNFINISHED_INLINE_ALIAS,
                 severity: Severity::RuntimeError,
             }
         }
@@ -2391,6 +2458,7 @@ fn to_type_report<'a>(
                 filename,
                 doc,
                 title: "BAD TYPE VARIABLE".to_string(),
+                code: codes::BAD_TYPE_VARIABLE,
                 severity: Severity::RuntimeError,
             }
         }
@@ -2428,6 +2496,7 @@ fn to_trecord_report<'a>(
                     filename,
                     doc,
                     title: "UNFINISHED RECORD TYPE".to_string(),
+                    code: codes::UNFINISHED_RECORD_TYPE,
                     severity: Severity::RuntimeError,
                 }
             }
@@ -2449,6 +2518,7 @@ fn to_trecord_report<'a>(
                     filename,
                     doc,
                     title: "UNFINISHED RECORD TYPE".to_string(),
+                    code: codes::UNFINISHED_RECORD_TYPE,
                     severity: Severity::RuntimeError,
                 }
             }
@@ -2474,6 +2544,7 @@ fn to_trecord_report<'a>(
                         filename,
                         doc,
                         title: "UNFINISHED RECORD TYPE".to_string(),
+                        code: codes::UNFINISHED_RECORD_TYPE,
                         severity: Severity::RuntimeError,
                     }
                 }
@@ -2494,6 +2565,7 @@ fn to_trecord_report<'a>(
                         filename,
                         doc,
                         title: "UNFINISHED RECORD TYPE".to_string(),
+                        code: codes::UNFINISHED_RECORD_TYPE,
                         severity: Severity::RuntimeError,
                     }
                 }
@@ -2519,6 +2591,7 @@ fn to_trecord_report<'a>(
                     filename,
                     doc,
                     title: "UNFINISHED RECORD TYPE".to_string(),
+                    code: codes::UNFINISHED_RECORD_TYPE,
                     severity: Severity::RuntimeError,
                 }
             }
@@ -2544,6 +2617,7 @@ fn to_trecord_report<'a>(
                     filename,
                     doc,
                     title: "PROBLEM IN RECORD TYPE".to_string(),
+                    code: codes::PROBLEM_IN_RECORD_TYPE,
                     severity: Severity::RuntimeError,
                 }
             }
@@ -2577,6 +2651,7 @@ fn to_trecord_report<'a>(
                 filename,
                 doc,
                 title: "UNFINISHED RECORD TYPE".to_string(),
+                code: codes::UNFINISHED_RECORD_TYPE,
                 severity: Severity::RuntimeError,
             }
         }
@@ -2601,6 +2676,7 @@ fn to_trecord_report<'a>(
                         filename,
                         doc,
                         title: "NEED MORE INDENTATION".to_string(),
+                        code: codes::NEED_MORE_INDENTATION,
                         severity: Severity::RuntimeError,
                     }
                 }
@@ -2626,6 +2702,7 @@ fn to_trecord_report<'a>(
                         filename,
                         doc,
                         title: "UNFINISHED RECORD TYPE".to_string(),
+                        code: codes::UNFINISHED_RECORD_TYPE,
                         severity: Severity::RuntimeError,
                     }
                 }
@@ -2673,6 +2750,7 @@ fn to_ttag_union_report<'a>(
                     filename,
                     doc,
                     title: "UNFINISHED TAG UNION TYPE".to_string(),
+                    code: codes::UNFINISHED_TAG_UNION_TYPE,
                     severity: Severity::RuntimeError,
                 }
             }
@@ -2695,6 +2773,7 @@ fn to_ttag_union_report<'a>(
                     filename,
                     doc,
                     title: "WEIRD TAG NAME".to_string(),
+                    code: codes::WEIRD_TAG_NAME,
                     severity: Severity::RuntimeError,
                 }
             }
@@ -2716,6 +2795,7 @@ fn to_ttag_union_report<'a>(
                     filename,
                     doc,
                     title: "UNFINISHED TAG UNION TYPE".to_string(),
+                    code: codes::UNFINISHED_TAG_UNION_TYPE,
                     severity: Severity::RuntimeError,
                 }
             }
@@ -2742,6 +2822,7 @@ fn to_ttag_union_report<'a>(
                         filename,
                         doc,
                         title: "WEIRD TAG NAME".to_string(),
+                        code: codes::WEIRD_TAG_NAME,
                         severity: Severity::RuntimeError,
                     }
                 }
@@ -2762,6 +2843,7 @@ fn to_ttag_union_report<'a>(
                         filename,
                         doc,
                         title: "UNFINISHED TAG UNION TYPE".to_string(),
+                        code: codes::UNFINISHED_TAG_UNION_TYPE,
                         severity: Severity::RuntimeError,
                     }
                 }
@@ -2805,6 +2887,7 @@ fn to_tinparens_report<'a>(
                         filename,
                         doc,
                         title: "UNFINISHED PARENTHESES".to_string(),
+                        code: codes::UNFINISHED_PARENTHESES,
                         severity: Severity::RuntimeError,
                     }
                 }
@@ -2827,6 +2910,7 @@ fn to_tinparens_report<'a>(
                         filename,
                         doc,
                         title: "WEIRD TAG NAME".to_string(),
+                        code: codes::WEIRD_TAG_NAME,
                         severity: Severity::RuntimeError,
                     }
                 }
@@ -2850,6 +2934,7 @@ fn to_tinparens_report<'a>(
                         filename,
                         doc,
                         title: "UNFINISHED PARENTHESES".to_string(),
+                        code: codes::UNFINISHED_PARENTHESES,
                         severity: Severity::RuntimeError,
                     }
                 }
@@ -2873,6 +2958,7 @@ fn to_tinparens_report<'a>(
                 filename,
                 doc,
                 title: "EMPTY PARENTHESES".to_string(),
+                code: codes::EMPTY_PARENTHESES,
                 severity: Severity::RuntimeError,
             }
         }
@@ -2899,6 +2985,7 @@ fn to_tinparens_report<'a>(
                         filename,
                         doc,
                         title: "WEIRD TAG NAME".to_string(),
+                        code: codes::WEIRD_TAG_NAME,
                         severity: Severity::RuntimeError,
                     }
                 }
@@ -2919,6 +3006,7 @@ fn to_tinparens_report<'a>(
                         filename,
                         doc,
                         title: "UNFINISHED PARENTHESES".to_string(),
+                        code: codes::UNFINISHED_PARENTHESES,
                         severity: Severity::RuntimeError,
                     }
                 }
@@ -2947,6 +3035,7 @@ fn to_tinparens_report<'a>(
                 filename,
                 doc,
                 title: "UNFINISHED PARENTHESES".to_string(),
+                code: codes::UNFINISHED_PARENTHESES,
                 severity: Severity::RuntimeError,
             }
         }
@@ -2971,6 +3060,7 @@ fn to_tinparens_report<'a>(
                         filename,
                         doc,
                         title: "NEED MORE INDENTATION".to_string(),
+                        code: codes::NEED_MORE_INDENTATION,
                         severity: Severity::RuntimeError,
                     }
                 }
@@ -2996,6 +3086,7 @@ fn to_tinparens_report<'a>(
                         filename,
                         doc,
                         title: "UNFINISHED PARENTHESES".to_string(),
+                        code: codes::UNFINISHED_PARENTHESES,
                         severity: Severity::RuntimeError,
                     }
                 }
@@ -3029,6 +3120,7 @@ fn to_tapply_report<'a>(
                 filename,
                 doc,
                 title: "DOUBLE DOT".to_string(),
+                code: codes::DOUBLE_DOT,
                 severity: Severity::RuntimeError,
             }
         }
@@ -3051,6 +3143,7 @@ fn to_tapply_report<'a>(
                 filename,
                 doc,
                 title: "TRAILING DOT".to_string(),
+                code: codes::TRAILING_DOT,
                 severity: Severity::RuntimeError,
             }
         }
@@ -3073,6 +3166,7 @@ fn to_tapply_report<'a>(
                 filename,
                 doc,
                 title: "WEIRD QUALIFIED NAME".to_string(),
+                code: codes::WEIRD_QUALIFIED_NAME,
                 severity: Severity::RuntimeError,
             }
         }
@@ -3095,6 +3189,7 @@ fn to_tapply_report<'a>(
                 filename,
                 doc,
                 title: "WEIRD QUALIFIED NAME".to_string(),
+                code: codes::WEIRD_QUALIFIED_NAME,
                 severity: Severity::RuntimeError,
             }
         }
@@ -3113,6 +3208,7 @@ fn to_tapply_report<'a>(
                 filename,
                 doc,
                 title: "END OF FILE".to_string(),
+                code: codes::END_OF_FILE,
                 severity: Severity::RuntimeError,
             }
         }
@@ -3153,6 +3249,7 @@ fn to_talias_report<'a>(
                 filename,
                 doc,
                 title: "NOT AN INLINE ALIAS".to_string(),
+                code: codes::NOT_AN_INLINE_ALIAS,
                 severity: Severity::RuntimeError,
             }
         }
@@ -3169,6 +3266,7 @@ fn to_talias_report<'a>(
                 filename,
                 doc,
                 title: "QUALIFIED ALIAS NAME".to_string(),
+                code: codes::QUALIFIED_ALIAS_NAME,
                 severity: Severity::RuntimeError,
             }
         }
@@ -3185,6 +3283,7 @@ fn to_talias_report<'a>(
                 filename,
                 doc,
                 title: "TYPE ARGUMENT NOT LOWERCASE".to_string(),
+                code: codes::TYPE_ARGUMENT_NOT_LOWERCASE,
                 severity: Severity::RuntimeError,
             }
         }
@@ -3231,6 +3330,7 @@ fn to_header_report<'a>(
                 filename,
                 doc,
                 title: "INCOMPLETE HEADER".to_string(),
+                code: codes::INCOMPLETE_HEADER,
                 severity: Severity::RuntimeError,
             }
         }
@@ -3269,6 +3369,7 @@ fn to_header_report<'a>(
                 filename,
                 doc,
                 title: "MISSING HEADER".to_string(),
+                code: codes::MISSING_HEADER,
                 severity: Severity::RuntimeError,
             }
         }
@@ -3293,6 +3394,7 @@ fn to_header_report<'a>(
                 filename,
                 doc,
                 title: "WEIRD MODULE NAME".to_string(),
+                code: codes::WEIRD_MODULE_NAME,
                 severity: Severity::RuntimeError,
             }
         }
@@ -3320,6 +3422,7 @@ fn to_header_report<'a>(
                 filename,
                 doc,
                 title: "WEIRD MODULE NAME".to_string(),
+                code: codes::WEIRD_MODULE_NAME,
                 severity: Severity::RuntimeError,
             }
         }
@@ -3344,6 +3447,7 @@ fn to_header_report<'a>(
                 filename,
                 doc,
                 title: "WEIRD APP NAME".to_string(),
+                code: codes::WEIRD_APP_NAME,
                 severity: Severity::RuntimeError,
             }
         }
@@ -3366,6 +3470,7 @@ fn to_header_report<'a>(
                 filename,
                 doc,
                 title: "INVALID PACKAGE NAME".to_string(),
+                code: codes::INVALID_PACKAGE_NAME,
                 severity: Severity::RuntimeError,
             }
         }
@@ -3389,6 +3494,7 @@ fn to_header_report<'a>(
                 filename,
                 doc,
                 title: "INVALID PLATFORM NAME".to_string(),
+                code: codes::INVALID_PLATFORM_NAME,
                 severity: Severity::RuntimeError,
             }
         }
@@ -3412,6 +3518,7 @@ fn to_header_report<'a>(
                 filename,
                 doc,
                 title: "WEIRD GENERATED TYPE NAME".to_string(),
+                code: codes::WEIRD_GENERATED_TYPE_NAME,
                 severity: Severity::RuntimeError,
             }
         }
@@ -3452,6 +3559,7 @@ fn to_generates_with_report<'a>(
                 filename,
                 doc,
                 title: "WEIRD GENERATES".to_string(),
+                code: codes::WEIRD_GENERATES,
                 severity: Severity::RuntimeError,
             }
         }
@@ -3477,6 +3585,7 @@ fn to_generates_with_report<'a>(
                 filename,
                 doc,
                 title: "WEIRD GENERATES".to_string(),
+                code: codes::WEIRD_GENERATES,
                 severity: Severity::RuntimeError,
             }
         }
@@ -3518,6 +3627,7 @@ fn to_provides_report<'a>(
                 filename,
                 doc,
                 title: "WEIRD PROVIDES".to_string(),
+                code: codes::WEIRD_PROVIDES,
                 severity: Severity::RuntimeError,
             }
         }
@@ -3543,6 +3653,7 @@ fn to_provides_report<'a>(
                 filename,
                 doc,
                 title: "WEIRD PROVIDES".to_string(),
+                code: codes::WEIRD_PROVIDES,
                 severity: Severity::RuntimeError,
             }
         }
@@ -3570,6 +3681,7 @@ fn to_provides_report<'a>(
                 filename,
                 doc,
                 title: "WEIRD PROVIDES".to_string(),
+                code: codes::WEIRD_PROVIDES,
                 severity: Severity::RuntimeError,
             }
         }
@@ -3591,6 +3703,7 @@ fn to_provides_report<'a>(
                 filename,
                 doc,
                 title: "WEIRD PROVIDES".to_string(),
+                code: codes::WEIRD_PROVIDES,
                 severity: Severity::RuntimeError,
             }
         }
@@ -3629,6 +3742,7 @@ fn to_exposes_report<'a>(
                 filename,
                 doc,
                 title: "WEIRD EXPOSES".to_string(),
+                code: codes::WEIRD_EXPOSES,
                 severity: Severity::RuntimeError,
             }
         }
@@ -3654,6 +3768,7 @@ fn to_exposes_report<'a>(
                 filename,
                 doc,
                 title: "WEIRD EXPOSES".to_string(),
+                code: codes::WEIRD_EXPOSES,
                 severity: Severity::RuntimeError,
             }
         }
@@ -3693,6 +3808,7 @@ fn to_imports_report<'a>(
                 filename,
                 doc,
                 title: "WEIRD IMPORTS".to_string(),
+                code: codes::WEIRD_IMPORTS,
                 severity: Severity::RuntimeError,
             }
         }
@@ -3718,6 +3834,7 @@ fn to_imports_report<'a>(
                 filename,
                 doc,
                 title: "WEIRD IMPORTS".to_string(),
+                code: codes::WEIRD_IMPORTS,
                 severity: Severity::RuntimeError,
             }
         }
@@ -3744,6 +3861,7 @@ fn to_imports_report<'a>(
                 filename,
                 doc,
                 title: "WEIRD MODULE NAME".to_string(),
+                code: codes::WEIRD_MODULE_NAME,
                 severity: Severity::RuntimeError,
             }
         }
@@ -3763,6 +3881,7 @@ fn to_imports_report<'a>(
                 filename,
                 doc,
                 title: "WEIRD IMPORTS".to_string(),
+                code: codes::WEIRD_IMPORTS,
                 severity: Severity::RuntimeError,
             }
         }
@@ -3802,6 +3921,7 @@ fn to_requires_report<'a>(
                 filename,
                 doc,
                 title: "MISSING REQUIRES".to_string(),
+                code: codes::MISSING_REQUIRES,
                 severity: Severity::RuntimeError,
             }
         }
@@ -3829,6 +3949,7 @@ fn to_requires_report<'a>(
                 filename,
                 doc,
                 title: "MISSING REQUIRES".to_string(),
+                code: codes::MISSING_REQUIRES,
                 severity: Severity::RuntimeError,
             }
         }
@@ -3858,6 +3979,7 @@ fn to_requires_report<'a>(
                 filename,
                 doc,
                 title: "BAD REQUIRES RIGIDS".to_string(),
+                code: codes::BAD_REQUIRES_RIGIDS,
                 severity: Severity::RuntimeError,
             }
         }
@@ -3887,6 +4009,7 @@ fn to_requires_report<'a>(
                 filename,
                 doc,
                 title: "BAD REQUIRES".to_string(),
+                code: codes::BAD_REQUIRES,
                 severity: Severity::RuntimeError,
             }
         }
@@ -3924,6 +4047,7 @@ fn to_packages_report<'a>(
                 filename,
                 doc,
                 title: "MISSING PACKAGES".to_string(),
+                code: codes::MISSING_PACKAGES,
                 severity: Severity::RuntimeError,
             }
         }
@@ -3946,6 +4070,7 @@ fn to_packages_report<'a>(
                 filename,
                 doc,
                 title: "WEIRD PACKAGES LIST".to_string(),
+                code: codes::WEIRD_PACKAGES_LIST,
                 severity: Severity::RuntimeError,
             }
         }
@@ -3981,6 +4106,7 @@ fn to_space_report<'a>(
                 filename,
                 doc,
                 title: "TAB CHARACTER".to_string(),
+                code: codes::TAB_CHARACTER,
                 severity: Severity::RuntimeError,
             }
         }
@@ -3998,6 +4124,7 @@ fn to_space_report<'a>(
                 filename,
                 doc,
                 title: "ASCII CONTROL CHARACTER".to_string(),
+                code: codes::ASCII_CONTROL_CHARACTER,
                 severity: Severity::RuntimeError,
             }
         }
@@ -4015,6 +4142,7 @@ fn to_space_report<'a>(
                 filename,
                 doc,
                 title: "MISPLACED CARRIAGE RETURN".to_string(),
+                code: codes::MISPLACED_CARRIAGE_RETURN,
                 severity: Severity::RuntimeError,
             }
         }
@@ -4096,6 +4224,7 @@ fn to_unfinished_ability_report<'a>(
         filename,
         doc,
         title: "UNFINISHED ABILITY".to_string(),
+        code: codes::UNFINISHED_ABILITY,
         severity: Severity::RuntimeError,
     }
 }
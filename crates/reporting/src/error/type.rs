@@ -1,5 +1,6 @@
 #![allow(clippy::too_many_arguments)]
 
+use crate::codes::{self, ReportCode};
 use crate::error::canonicalize::{to_circular_def_doc, CIRCULAR_DEF};
 use crate::report::{Annotation, Report, RocDocAllocator, RocDocBuilder};
 use itertools::EitherOrBoth;
@@ -44,15 +45,19 @@ pub fn type_problem<'b>(
 
     let severity = problem.severity();
 
-    let report =
-        move |title: String, doc: RocDocBuilder<'b>, filename: PathBuf| -> Option<Report<'b>> {
-            Some(Report {
-                title,
-                filename,
-                doc,
-                severity,
-            })
-        };
+    let report = move |title: String,
+                       code: ReportCode,
+                       doc: RocDocBuilder<'b>,
+                       filename: PathBuf|
+          -> Option<Report<'b>> {
+        Some(Report {
+            title,
+            code,
+            filename,
+            doc,
+            severity,
+        })
+    };
 
     match problem {
         BadExpr(region, category, found, expected) => Some(to_expr_report(
@@ -80,14 +85,19 @@ pub fn type_problem<'b>(
                     .append(alloc.symbol_unqualified(symbol))])
                 .append(alloc.reflow("."));
 
-            report(title, doc, filename)
+            report(title, codes::UNRECOGNIZED_NAME, doc, filename)
         }
         UnfulfilledAbility(incomplete) => {
             let title = "INCOMPLETE ABILITY IMPLEMENTATION".to_string();
 
             let doc = report_unfulfilled_ability(alloc, lines, incomplete);
 
-            report(title, doc, filename)
+            report(
+                title,
+                codes::INCOMPLETE_ABILITY_IMPLEMENTATION,
+                doc,
+                filename,
+            )
         }
         BadExprMissingAbility(region, _category, _found, incomplete) => {
             if region == roc_can::DERIVED_REGION {
@@ -109,6 +119,7 @@ pub fn type_problem<'b>(
 
             let report = Report {
                 title: "TYPE MISMATCH".to_string(),
+                code: codes::MISSING_ABILITY,
                 filename,
                 doc: alloc.stack(stack),
                 severity,
@@ -131,6 +142,7 @@ pub fn type_problem<'b>(
 
             let report = Report {
                 title: "TYPE MISMATCH".to_string(),
+                code: codes::MISSING_ABILITY,
                 filename,
                 doc: alloc.stack(stack),
                 severity,
@@ -144,6 +156,7 @@ pub fn type_problem<'b>(
 
             Some(Report {
                 title,
+                code: codes::CIRCULAR_DEFINITION,
                 filename,
                 doc,
                 severity,
@@ -174,6 +187,7 @@ pub fn type_problem<'b>(
 
             Some(Report {
                 title: "ILLEGAL SPECIALIZATION".to_string(),
+                code: codes::ILLEGAL_SPECIALIZATION,
                 filename,
                 doc: alloc.stack(stack),
                 severity,
@@ -203,6 +217,7 @@ pub fn type_problem<'b>(
 
             Some(Report {
                 title: "WRONG SPECIALIZATION TYPE".to_string(),
+                code: codes::WRONG_SPECIALIZATION_TYPE,
                 filename,
                 doc: alloc.stack(stack),
                 severity,
@@ -219,6 +234,7 @@ pub fn type_problem<'b>(
             ];
             Some(Report {
                 title: "INVALID UTF-8".to_string(),
+                code: codes::INVALID_UTF_8,
                 filename,
                 doc: alloc.stack(stack),
                 severity,
@@ -242,6 +258,7 @@ pub fn type_problem<'b>(
             ];
             Some(Report {
                 title: "INVALID TYPE FOR INGESTED FILE".to_string(),
+                code: codes::INVALID_TYPE_FOR_INGESTED_FILE,
                 filename,
                 doc: alloc.stack(stack),
                 severity,
@@ -535,6 +552,7 @@ fn report_mismatch<'b>(
 
     Report {
         title: "TYPE MISMATCH".to_string(),
+        code: codes::TYPE_MISMATCH,
         filename,
         doc: alloc.stack(lines),
         severity,
@@ -578,6 +596,7 @@ fn report_bad_type<'b>(
 
     Report {
         title: "TYPE MISMATCH".to_string(),
+        code: codes::TYPE_MISMATCH,
         filename,
         doc: alloc.stack(lines),
         severity,
@@ -662,6 +681,7 @@ fn to_expr_report<'b>(
             Report {
                 filename,
                 title: "TYPE MISMATCH".to_string(),
+                code: codes::TYPE_MISMATCH,
                 doc: alloc.stack([
                     alloc.text("This expression is used in an unexpected way:"),
                     alloc.region(lines.convert_region(expr_region)),
@@ -776,6 +796,7 @@ fn to_expr_report<'b>(
 
             Report {
                 title: "TYPE MISMATCH".to_string(),
+                code: codes::ANNOTATION_MISMATCH,
                 filename,
                 doc: alloc.stack([
                     alloc.text("Something is off with the ").append(thing),
@@ -1180,6 +1201,7 @@ fn to_expr_report<'b>(
                     Report {
                         filename,
                         title: "TOO MANY ARGS".to_string(),
+                        code: codes::TOO_MANY_ARGS,
                         doc,
                         severity,
                     }
@@ -1215,6 +1237,7 @@ fn to_expr_report<'b>(
                         Report {
                             filename,
                             title: "TOO MANY ARGS".to_string(),
+                            code: codes::TOO_MANY_ARGS,
                             doc: alloc.stack(lines),
                             severity,
                         }
@@ -1242,6 +1265,7 @@ fn to_expr_report<'b>(
                         Report {
                             filename,
                             title: "TOO FEW ARGS".to_string(),
+                            code: codes::TOO_FEW_ARGS,
                             doc: alloc.stack(lines),
                             severity,
                         }
@@ -1442,6 +1466,7 @@ fn to_expr_report<'b>(
 
                 Report {
                     title: "TYPE MISMATCH".to_string(),
+                    code: codes::TYPE_MISMATCH,
                     filename,
                     doc: alloc.stack(lines),
                     severity,
@@ -1480,6 +1505,7 @@ fn to_expr_report<'b>(
                 Report {
                     filename,
                     title: "TYPE MISMATCH".to_string(),
+                    code: codes::TYPE_MISMATCH,
                     doc,
                     severity,
                 }
@@ -1516,6 +1542,7 @@ fn to_expr_report<'b>(
                 Report {
                     filename,
                     title: "TYPE MISMATCH".to_string(),
+                    code: codes::TYPE_MISMATCH,
                     doc: alloc.stack(lines),
                     severity,
                 }
@@ -1929,6 +1956,7 @@ fn to_pattern_report<'b>(
             Report {
                 filename,
                 title: "TYPE MISMATCH".to_string(),
+                code: codes::PATTERN_TYPE_MISMATCH,
                 doc,
                 severity,
             }
@@ -1972,6 +2000,7 @@ fn to_pattern_report<'b>(
                 Report {
                     filename,
                     title: "TYPE MISMATCH".to_string(),
+                    code: codes::PATTERN_TYPE_MISMATCH,
                     doc,
                     severity,
                 }
@@ -2052,6 +2081,7 @@ fn to_pattern_report<'b>(
                 Report {
                     filename,
                     title: "TYPE MISMATCH".to_string(),
+                    code: codes::PATTERN_TYPE_MISMATCH,
                     doc,
                     severity,
                 }
@@ -2079,6 +2109,7 @@ fn to_pattern_report<'b>(
                 Report {
                     filename,
                     title: "TYPE MISMATCH".to_string(),
+                    code: codes::PATTERN_TYPE_MISMATCH,
                     doc,
                     severity,
                 }
@@ -2163,6 +2194,7 @@ fn to_circular_report<'b>(
 ) -> Report<'b> {
     Report {
         title: "CIRCULAR TYPE".to_string(),
+        code: codes::CIRCULAR_TYPE,
         filename,
         doc: {
             alloc.stack([
@@ -4854,6 +4886,7 @@ fn report_record_field_typo<'b>(
     Report {
         filename,
         title: "TYPE MISMATCH".to_string(),
+        code: codes::MISSING_RECORD_FIELD,
         doc,
         severity,
     }
@@ -4891,6 +4924,7 @@ fn exhaustive_problem<'a>(
                 Report {
                     filename,
                     title: "UNSAFE PATTERN".to_string(),
+                    code: codes::UNSAFE_PATTERN,
                     doc,
                     severity,
                 }
@@ -4915,6 +4949,7 @@ fn exhaustive_problem<'a>(
                 Report {
                     filename,
                     title: "UNSAFE PATTERN".to_string(),
+                    code: codes::UNSAFE_PATTERN,
                     doc,
                     severity,
                 }
@@ -4939,6 +4974,7 @@ fn exhaustive_problem<'a>(
                 Report {
                     filename,
                     title: "UNSAFE PATTERN".to_string(),
+                    code: codes::UNSAFE_PATTERN,
                     doc,
                     severity,
                 }
@@ -4968,6 +5004,7 @@ fn exhaustive_problem<'a>(
             Report {
                 filename,
                 title: "REDUNDANT PATTERN".to_string(),
+                code: codes::REDUNDANT_PATTERN,
                 doc,
                 severity,
             }
@@ -4996,6 +5033,7 @@ fn exhaustive_problem<'a>(
             Report {
                 filename,
                 title: "UNMATCHABLE PATTERN".to_string(),
+                code: codes::UNMATCHABLE_PATTERN,
                 doc,
                 severity,
             }
@@ -3,10 +3,14 @@ use roc_module::ident::{Lowercase, ModuleName, TagName, Uppercase};
 use roc_module::symbol::{Interns, ModuleId, ModuleIds, PQModuleName, PackageQualified, Symbol};
 use roc_problem::Severity;
 use roc_region::all::LineColumnRegion;
+use std::cell::Cell;
 use std::path::{Path, PathBuf};
 use std::{fmt, io};
 use ven_pretty::{text, BoxAllocator, DocAllocator, DocBuilder, Render, RenderAnnotated};
 
+use crate::codes::{self, ReportCode};
+use crate::structured::{sarif_log, Diagnostic};
+
 #[cfg(not(target_family = "wasm"))]
 use byte_unit::Byte;
 #[cfg(not(target_family = "wasm"))]
@@ -112,11 +116,24 @@ pub fn pretty_header_with_path(title: &str, path: &Path) -> String {
 pub enum RenderTarget {
     ColorTerminal,
     Generic,
+    /// A line of JSON per report, for tools to consume.
+    Json,
+    /// A SARIF 2.1 log, for code scanning tools to annotate pull requests with.
+    Sarif,
+}
+
+impl RenderTarget {
+    /// Whether this target is meant to be read by tools rather than people.
+    pub fn is_structured(self) -> bool {
+        matches!(self, RenderTarget::Json | RenderTarget::Sarif)
+    }
 }
 
 /// A textual report.
 pub struct Report<'b> {
     pub title: String,
+    /// The kind of report this is; `title` must be one of the code's titles.
+    pub code: ReportCode,
     pub filename: PathBuf,
     pub doc: RocDocBuilder<'b>,
     pub severity: Severity,
@@ -133,6 +150,39 @@ impl<'b> Report<'b> {
         match target {
             RenderTarget::Generic => self.render_ci(buf, alloc),
             RenderTarget::ColorTerminal => self.render_color_terminal(buf, alloc, palette),
+            RenderTarget::Json => {
+                let diagnostic = self.to_diagnostic(alloc);
+                buf.push_str(&diagnostic.to_json().to_string());
+                buf.push('\n');
+            }
+            RenderTarget::Sarif => {
+                let diagnostic = self.to_diagnostic(alloc);
+                buf.push_str(&sarif_log(&[diagnostic]).to_string());
+                buf.push('\n');
+            }
+        }
+    }
+
+    /// Takes the report apart for [RenderTarget::Json] and [RenderTarget::Sarif]. The region is
+    /// the first one that was highlighted while building the report, so `alloc` must not have
+    /// been used to build any other report since [RocDocAllocator::take_highlight] was last called.
+    pub fn to_diagnostic(self, alloc: &'b RocDocAllocator<'b>) -> Diagnostic {
+        let err_msg = "<buffer is not a utf-8 encoded string>";
+
+        let mut message = String::new();
+        let mut write = DiagnosticWrite::new(&mut message);
+        self.doc.1.render_raw(70, &mut write).expect(err_msg);
+        let mut suggestions = write.suggestions;
+        suggestions.dedup();
+
+        Diagnostic {
+            severity: self.severity,
+            code: self.code.code,
+            title: self.title,
+            filename: self.filename,
+            region: alloc.take_highlight(),
+            message: message.trim().to_string(),
+            suggestions,
         }
     }
 
@@ -166,10 +216,12 @@ impl<'b> Report<'b> {
         if self.title.is_empty() {
             self.doc
         } else {
+            let title = format!("{} [{}]", self.title, self.code.code);
+
             let header = if self.filename == PathBuf::from("replfile.roc") {
-                crate::report::pretty_header(&self.title)
+                crate::report::pretty_header(&title)
             } else {
-                crate::report::pretty_header_with_path(&self.title, &self.filename)
+                crate::report::pretty_header_with_path(&title, &self.filename)
             };
 
             alloc.stack([alloc.text(header).annotate(Annotation::Header), self.doc])
@@ -302,6 +354,8 @@ pub struct RocDocAllocator<'a> {
     pub src_lines: &'a [&'a str],
     pub home: ModuleId,
     pub interns: &'a Interns,
+    /// The first region an error was highlighted in, for structured reports.
+    highlight: Cell<Option<LineColumnRegion>>,
 }
 
 pub type RocDocBuilder<'b> = DocBuilder<'b, RocDocAllocator<'b>, Annotation>;
@@ -338,6 +392,18 @@ impl<'a> RocDocAllocator<'a> {
             home,
             src_lines,
             interns,
+            highlight: Cell::new(None),
+        }
+    }
+
+    /// The first region an error was highlighted in since this was last called.
+    pub fn take_highlight(&self) -> Option<LineColumnRegion> {
+        self.highlight.take()
+    }
+
+    fn record_highlight(&self, region: LineColumnRegion) {
+        if self.highlight.get().is_none() {
+            self.highlight.set(Some(region));
         }
     }
 
@@ -550,6 +616,8 @@ impl<'a> RocDocAllocator<'a> {
         debug_assert!(region.contains(&sub_region1));
         debug_assert!(region.contains(&sub_region2));
 
+        self.record_highlight(sub_region1);
+
         // if true, the final line of the snippet will be some ^^^ that point to the region where
         // the problem is. Otherwise, the snippet will have a > on the lines that are in the region
         // where the problem is.
@@ -664,6 +732,8 @@ impl<'a> RocDocAllocator<'a> {
     ) -> DocBuilder<'a, Self, Annotation> {
         // debug_assert!(region.contains(&sub_region));
 
+        self.record_highlight(sub_region);
+
         // If the outer region takes more than 1 full screen (~60 lines), only show the inner region
         if region.end().line.saturating_sub(region.start().line) > 60 {
             // If the inner region contains the outer region (or if they are the same),
@@ -870,6 +940,9 @@ pub enum Annotation {
     Tip,
     Header,
     ParserSuggestion,
+    /// Code that could replace the code a report highlights, like a name that a misspelled one
+    /// might have meant. Rendered as plain text.
+    Replacement,
 }
 
 /// Render with minimal formatting
@@ -995,6 +1068,66 @@ where
     }
 }
 
+/// Renders like [CiWrite], while also collecting the [Annotation::Replacement]s in a report.
+struct DiagnosticWrite<W> {
+    ci: CiWrite<W>,
+    /// The text of the suggestion being rendered, if one is.
+    suggestion: Option<String>,
+    suggestions: Vec<String>,
+}
+
+impl<W> DiagnosticWrite<W> {
+    fn new(upstream: W) -> Self {
+        DiagnosticWrite {
+            ci: CiWrite::new(upstream),
+            suggestion: None,
+            suggestions: Vec::new(),
+        }
+    }
+}
+
+impl<W> Render for DiagnosticWrite<W>
+where
+    W: fmt::Write,
+{
+    type Error = fmt::Error;
+
+    fn write_str(&mut self, s: &str) -> Result<usize, fmt::Error> {
+        self.write_str_all(s).map(|_| s.len())
+    }
+
+    fn write_str_all(&mut self, s: &str) -> fmt::Result {
+        if let Some(suggestion) = &mut self.suggestion {
+            suggestion.push_str(s);
+        }
+
+        self.ci.write_str_all(s)
+    }
+}
+
+impl<W> RenderAnnotated<Annotation> for DiagnosticWrite<W>
+where
+    W: fmt::Write,
+{
+    fn push_annotation(&mut self, annotation: &Annotation) -> Result<(), Self::Error> {
+        if matches!(annotation, Annotation::Replacement) {
+            self.suggestion = Some(String::new());
+        }
+
+        self.ci.push_annotation(annotation)
+    }
+
+    fn pop_annotation(&mut self) -> Result<(), Self::Error> {
+        if matches!(self.ci.style_stack.last(), Some(Annotation::Replacement)) {
+            if let Some(suggestion) = self.suggestion.take() {
+                self.suggestions.push(suggestion);
+            }
+        }
+
+        self.ci.pop_annotation()
+    }
+}
+
 impl<'a, W> Render for ColorWrite<'a, W>
 where
     W: fmt::Write,
@@ -1080,7 +1213,9 @@ where
             ParserSuggestion => {
                 self.write_str(self.palette.parser_suggestion)?;
             }
-            TypeBlock | InlineTypeBlock | Tag | RecordField | TupleElem => { /* nothing yet */ }
+            TypeBlock | InlineTypeBlock | Tag | RecordField | TupleElem | Replacement => {
+                /* nothing yet */
+            }
         }
         self.style_stack.push(*annotation);
         Ok(())
@@ -1098,8 +1233,8 @@ where
                     self.write_str(self.palette.reset)?;
                 }
 
-                TypeBlock | InlineTypeBlock | Tag | Opaque | RecordField | TupleElem => { /* nothing yet */
-                }
+                TypeBlock | InlineTypeBlock | Tag | Opaque | RecordField | TupleElem
+                | Replacement => { /* nothing yet */ }
             },
         }
         Ok(())
@@ -1168,6 +1303,7 @@ pub fn to_https_problem_report<'b>(
                 filename,
                 doc,
                 title: "UNSUPPORTED ENCODING".to_string(),
+                code: codes::UNSUPPORTED_ENCODING,
                 severity: Severity::Fatal,
             }
         }
@@ -1203,6 +1339,7 @@ pub fn to_https_problem_report<'b>(
                 filename,
                 doc,
                 title: "MULTIPLE ENCODINGS".to_string(),
+                code: codes::MULTIPLE_ENCODINGS,
                 severity: Severity::Fatal,
             }
         }
@@ -1238,6 +1375,7 @@ pub fn to_https_problem_report<'b>(
                 filename,
                 doc,
                 title: "INVALID CONTENT HASH".to_string(),
+                code: codes::INVALID_CONTENT_HASH,
                 severity: Severity::Fatal,
             }
         }
@@ -1255,6 +1393,7 @@ pub fn to_https_problem_report<'b>(
                 filename,
                 doc,
                 title: "NOTFOUND".to_string(),
+                code: codes::PACKAGE_NOT_FOUND,
                 severity: Severity::Fatal,
             }
         }
@@ -1282,6 +1421,7 @@ pub fn to_https_problem_report<'b>(
                 filename,
                 doc,
                 title: "IO ERROR".to_string(),
+                code: codes::PACKAGE_IO_ERROR,
                 severity: Severity::Fatal,
             }
         }
@@ -1309,6 +1449,7 @@ pub fn to_https_problem_report<'b>(
                 filename,
                 doc,
                 title: "IO ERROR".to_string(),
+                code: codes::PACKAGE_IO_ERROR,
                 severity: Severity::Fatal,
             }
         }
@@ -1338,6 +1479,7 @@ pub fn to_https_problem_report<'b>(
                 filename,
                 doc,
                 title: "HTTP ERROR".to_string(),
+                code: codes::HTTP_ERROR,
                 severity: Severity::Fatal,
             }
         }
@@ -1366,8 +1508,10 @@ pub fn to_https_problem_report<'b>(
                     alloc.keyword(r".tar"),
                     alloc.reflow(r", "),
                     alloc.keyword(r".tar.gz"),
-                    alloc.reflow(r" and "),
+                    alloc.reflow(r", "),
                     alloc.keyword(r".tar.br"),
+                    alloc.reflow(r" and "),
+                    alloc.keyword(r".tar.zst"),
                 ]),
                 alloc.concat([
                     alloc.tip(),
@@ -1379,6 +1523,7 @@ pub fn to_https_problem_report<'b>(
                 filename,
                 doc,
                 title: "INVALID EXTENSION SUFFIX".to_string(),
+                code: codes::INVALID_EXTENSION_SUFFIX,
                 severity: Severity::Fatal,
             }
         }
@@ -1399,8 +1544,10 @@ pub fn to_https_problem_report<'b>(
                     alloc.keyword(r".tar"),
                     alloc.reflow(r", "),
                     alloc.keyword(r".tar.gz"),
-                    alloc.reflow(r" and "),
+                    alloc.reflow(r", "),
                     alloc.keyword(r".tar.br"),
+                    alloc.reflow(r" and "),
+                    alloc.keyword(r".tar.zst"),
                 ]),
                 alloc.concat([
                     alloc.tip(),
@@ -1412,6 +1559,7 @@ pub fn to_https_problem_report<'b>(
                 filename,
                 doc,
                 title: "INVALID EXTENSION".to_string(),
+                code: codes::INVALID_EXTENSION,
                 severity: Severity::Fatal,
             }
         }
@@ -1450,6 +1598,7 @@ pub fn to_https_problem_report<'b>(
                 filename,
                 doc,
                 title: "INVALID FRAGMENT".to_string(),
+                code: codes::INVALID_FRAGMENT,
                 severity: Severity::Fatal,
             }
         }
@@ -1488,6 +1637,7 @@ pub fn to_https_problem_report<'b>(
                 filename,
                 doc,
                 title: "MISSING PACKAGE HASH".to_string(),
+                code: codes::MISSING_PACKAGE_HASH,
                 severity: Severity::Fatal,
             }
         }
@@ -1502,7 +1652,9 @@ pub fn to_https_problem_report<'b>(
                     alloc.reflow(r"For your security, I will only attempt to download "),
                     alloc.reflow(r"files from servers which use the "),
                     alloc.keyword(r"https"),
-                    alloc.reflow(r" protocol."),
+                    alloc.reflow(r" protocol, or to read them from this machine with a "),
+                    alloc.keyword(r"file://"),
+                    alloc.reflow(r" URL."),
                 ]),
                 alloc.concat([
                     alloc.tip(),
@@ -1514,6 +1666,7 @@ pub fn to_https_problem_report<'b>(
                 filename,
                 doc,
                 title: "HTTPS MANDATORY".to_string(),
+                code: codes::HTTPS_MANDATORY,
                 severity: Severity::Fatal,
             }
         }
@@ -1557,6 +1710,36 @@ pub fn to_https_problem_report<'b>(
                 filename,
                 doc,
                 title: "MISLEADING CHARACTERS".to_string(),
+                code: codes::MISLEADING_CHARACTERS,
+                severity: Severity::Fatal,
+            }
+        }
+        Problem::InvalidMirror { source, rule } => {
+            let doc = alloc.stack([
+                alloc.reflow(r"I tried to download from this URL:"),
+                alloc
+                    .string((&url).to_string())
+                    .annotate(Annotation::Url)
+                    .indent(4),
+                alloc.concat([
+                    alloc.reflow(r"But I could not understand this package mirror rule from "),
+                    alloc.string(source).annotate(Annotation::Keyword),
+                    alloc.reflow(r":"),
+                ]),
+                alloc.string(rule).annotate(Annotation::PlainText).indent(4),
+                alloc.concat([
+                    alloc.tip(),
+                    alloc.reflow(r"Each rule should be a URL prefix followed by what to "),
+                    alloc.reflow(r"replace it with, like "),
+                    alloc.parser_suggestion("https://github.com/ https://mirror.example.com/"),
+                ]),
+            ]);
+
+            Report {
+                filename,
+                doc,
+                title: "INVALID PACKAGE MIRROR".to_string(),
+                code: codes::INVALID_PACKAGE_MIRROR,
                 severity: Severity::Fatal,
             }
         }
@@ -1587,6 +1770,7 @@ pub fn to_https_problem_report<'b>(
                 filename,
                 doc,
                 title: "FILE TOO LARGE".to_string(),
+                code: codes::FILE_TOO_LARGE,
                 severity: Severity::Fatal,
             }
         }
@@ -1634,6 +1818,7 @@ pub fn to_file_problem_report<'b>(
                 filename,
                 doc,
                 title: "FILE NOT FOUND".to_string(),
+                code: codes::FILE_NOT_FOUND,
                 severity: Severity::Fatal,
             }
         }
@@ -1652,6 +1837,7 @@ pub fn to_file_problem_report<'b>(
                 filename,
                 doc,
                 title: "FILE PERMISSION DENIED".to_string(),
+                code: codes::FILE_PERMISSION_DENIED,
                 severity: Severity::Fatal,
             }
         }
@@ -1688,6 +1874,7 @@ pub fn to_file_problem_report<'b>(
                 filename,
                 doc,
                 title: "NOT A ROC FILE".to_string(),
+                code: codes::NOT_A_ROC_FILE,
                 severity: Severity::Fatal,
             }
         }
@@ -1708,6 +1895,7 @@ pub fn to_file_problem_report<'b>(
                 filename,
                 doc,
                 title: "FILE PROBLEM".to_string(),
+                code: codes::FILE_PROBLEM,
                 severity: Severity::Fatal,
             }
         }
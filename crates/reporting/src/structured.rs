@@ -0,0 +1,158 @@
+//! Reports as data rather than text, for tools like code review bots and editor plugins.
+//!
+//! Lines and columns are 1-based, and the end column of a region is the one just past it, as in
+//! SARIF.
+
+use std::path::{Path, PathBuf};
+
+use roc_problem::Severity;
+use roc_region::all::LineColumnRegion;
+use serde_json::{json, Value};
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const ROC_URL: &str = "https://www.roc-lang.org";
+
+/// A [Report](crate::report::Report) taken apart into the pieces tools need.
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// The stable code of the kind of report this is, like `R0253`.
+    pub code: &'static str,
+    pub title: String,
+    pub filename: PathBuf,
+    /// The code the report points at, if it points at any.
+    pub region: Option<LineColumnRegion>,
+    /// The body of the report as plain text.
+    pub message: String,
+    /// What the report suggests replacing the code at `region` with, e.g. the names that a
+    /// misspelled one might have meant.
+    pub suggestions: Vec<String>,
+}
+
+impl Diagnostic {
+    pub fn to_json(&self) -> Value {
+        json!({
+            "severity": match self.severity {
+                Severity::Fatal => "fatal",
+                Severity::RuntimeError => "error",
+                Severity::Warning => "warning",
+            },
+            "code": self.code,
+            "title": self.title,
+            "file": self.filename.display().to_string(),
+            "region": self.region.map(region_json),
+            "message": self.message,
+            "suggestions": self.suggestions,
+        })
+    }
+
+    /// The report as a SARIF `result` object.
+    pub fn to_sarif_result(&self) -> Value {
+        let uri = artifact_uri(&self.filename);
+
+        let mut physical_location = json!({ "artifactLocation": { "uri": uri } });
+        if let Some(region) = self.region {
+            physical_location["region"] = sarif_region(region);
+        }
+
+        let fixes: Vec<_> = match self.region {
+            Some(region) => self
+                .suggestions
+                .iter()
+                .map(|suggestion| {
+                    json!({
+                        "description": { "text": format!("Replace with `{suggestion}`") },
+                        "artifactChanges": [{
+                            "artifactLocation": { "uri": uri },
+                            "replacements": [{
+                                "deletedRegion": sarif_region(region),
+                                "insertedContent": { "text": suggestion },
+                            }],
+                        }],
+                    })
+                })
+                .collect(),
+            None => vec![],
+        };
+
+        let mut result = json!({
+            "ruleId": self.code,
+            "level": match self.severity {
+                Severity::Fatal | Severity::RuntimeError => "error",
+                Severity::Warning => "warning",
+            },
+            "message": { "text": self.message },
+            "locations": [{ "physicalLocation": physical_location }],
+        });
+
+        if !fixes.is_empty() {
+            result["fixes"] = Value::Array(fixes);
+        }
+
+        result
+    }
+}
+
+/// A SARIF 2.1 log with a single run of the compiler, which found `diagnostics`.
+pub fn sarif_log(diagnostics: &[Diagnostic]) -> Value {
+    let mut rules: Vec<Value> = Vec::new();
+    for diagnostic in diagnostics {
+        // Rules are identified by code, so that they keep their ids when a report is reworded.
+        let code = diagnostic.code;
+
+        if !rules.iter().any(|rule| rule["id"] == code) {
+            rules.push(json!({
+                "id": code,
+                "shortDescription": { "text": diagnostic.title },
+                "help": { "text": format!("Run `roc explain {code}` for more.") },
+            }));
+        }
+    }
+
+    let results: Vec<_> = diagnostics
+        .iter()
+        .map(Diagnostic::to_sarif_result)
+        .collect();
+
+    json!({
+        "$schema": SARIF_SCHEMA,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "roc",
+                    "informationUri": ROC_URL,
+                    "rules": rules,
+                },
+            },
+            "results": results,
+        }],
+    })
+}
+
+/// Paths are relative to the current directory where possible, since that is usually the root
+/// of the repository that the results get annotated on.
+fn artifact_uri(path: &Path) -> String {
+    let relative = std::env::current_dir()
+        .ok()
+        .and_then(|cwd| path.strip_prefix(cwd).ok())
+        .unwrap_or(path);
+
+    relative.to_string_lossy().replace('\\', "/")
+}
+
+fn region_json(region: LineColumnRegion) -> Value {
+    json!({
+        "start": { "line": region.start.line + 1, "column": region.start.column + 1 },
+        "end": { "line": region.end.line + 1, "column": region.end.column + 1 },
+    })
+}
+
+fn sarif_region(region: LineColumnRegion) -> Value {
+    json!({
+        "startLine": region.start.line + 1,
+        "startColumn": region.start.column + 1,
+        "endLine": region.end.line + 1,
+        "endColumn": region.end.column + 1,
+    })
+}
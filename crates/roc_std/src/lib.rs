@@ -8,11 +8,19 @@ use core::ffi::c_void;
 use core::fmt::{self, Debug};
 use core::hash::{Hash, Hasher};
 use core::mem::{ManuallyDrop, MaybeUninit};
-use core::ops::Drop;
+use core::ops::{Add, Div, Drop, Mul, Neg, Sub};
 use core::str;
 
+#[cfg(feature = "serde")]
+use serde::{
+    de::{Deserializer, Visitor},
+    ser::Serializer,
+    Deserialize, Serialize,
+};
+
 mod roc_box;
 mod roc_dict;
+mod roc_hash;
 mod roc_list;
 mod roc_set;
 mod roc_str;
@@ -20,6 +28,7 @@ mod storage;
 
 pub use roc_box::RocBox;
 pub use roc_dict::RocDict;
+pub use roc_hash::{RocHash, RocHasher};
 pub use roc_list::{RocList, SendSafeRocList};
 pub use roc_set::RocSet;
 pub use roc_str::{InteriorNulError, RocStr, SendSafeRocStr};
@@ -242,6 +251,34 @@ impl<T, E> From<Result<T, E>> for RocResult<T, E> {
     }
 }
 
+/// Serialized the same way as a [`Result`].
+#[cfg(feature = "serde")]
+impl<T: Serialize, E: Serialize> Serialize for RocResult<T, E> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.as_result_of_refs()
+            .map(|payload| &**payload)
+            .map_err(|payload| &**payload)
+            .serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T, E> Deserialize<'de> for RocResult<T, E>
+where
+    T: Deserialize<'de>,
+    E: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Result::deserialize(deserializer).map(RocResult::from)
+    }
+}
+
 #[repr(u8)]
 #[derive(Clone, Copy)]
 enum RocResultTag {
@@ -444,20 +481,346 @@ impl RocDec {
     pub fn to_str(&self) -> RocStr {
         RocStr::from(self.to_str_helper(&mut ArrayString::new()))
     }
+
+    /// Like the builtin, digits past the 18th decimal place are truncated. Returns None if the
+    /// number is NaN or out of range.
+    pub fn from_f64(value: f64) -> Option<Self> {
+        // 2^127; i128::MIN is exactly -2^127, and i128::MAX rounds up to 2^127 as an f64.
+        const LIMIT: f64 = 170141183460469231731687303715884105728.0;
+
+        let scaled = value * Self::ONE_POINT_ZERO as f64;
+
+        if (-LIMIT..LIMIT).contains(&scaled) {
+            Some(Self::new(scaled as i128))
+        } else {
+            None
+        }
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        self.as_i128() as f64 / Self::ONE_POINT_ZERO as f64
+    }
+
+    /// The Dec with the same whole number value, e.g. 5 becomes 5.0
+    pub fn checked_from_i128(value: i128) -> Option<Self> {
+        value.checked_mul(Self::ONE_POINT_ZERO).map(Self::new)
+    }
+
+    /// The Dec with the same whole number value, e.g. 5 becomes 5.0
+    pub fn checked_from_u128(value: u128) -> Option<Self> {
+        i128::try_from(value).ok().and_then(Self::checked_from_i128)
+    }
+
+    /// The whole number part, rounding toward zero. Use this after [round](Self::round),
+    /// [floor](Self::floor) or [ceiling](Self::ceiling) to get what the Roc functions of the
+    /// same names return.
+    pub fn whole_part(&self) -> i128 {
+        self.as_i128() / Self::ONE_POINT_ZERO
+    }
+
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        self.as_i128().checked_add(other.as_i128()).map(Self::new)
+    }
+
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        self.as_i128().checked_sub(other.as_i128()).map(Self::new)
+    }
+
+    pub fn checked_mul(self, other: Self) -> Option<Self> {
+        let (a, b) = (self.as_i128(), other.as_i128());
+        let is_answer_negative = (a < 0) != (b < 0);
+
+        // Like the builtin, Dec.MIN can only be multiplied by 0 or 1.
+        let (a_abs, b_abs) = match (a.checked_abs(), b.checked_abs()) {
+            (Some(a_abs), Some(b_abs)) => (a_abs as u128, b_abs as u128),
+            (None, _) => {
+                return match b {
+                    0 => Some(Self::new(0)),
+                    Self::ONE_POINT_ZERO => Some(self),
+                    _ => None,
+                }
+            }
+            (_, None) => {
+                return match a {
+                    0 => Some(Self::new(0)),
+                    Self::ONE_POINT_ZERO => Some(other),
+                    _ => None,
+                }
+            }
+        };
+
+        let (hi, lo) = mul_u128(a_abs, b_abs);
+        let answer = div_u256_by_u128(hi, lo, Self::ONE_POINT_ZERO as u128)?;
+        let answer = i128::try_from(answer).ok()?;
+
+        Some(Self::new(if is_answer_negative { -answer } else { answer }))
+    }
+
+    /// Returns None if `other` is zero or the result is out of range. Like the builtin, 0 / 0
+    /// is 0.
+    pub fn checked_div(self, other: Self) -> Option<Self> {
+        let (a, b) = (self.as_i128(), other.as_i128());
+
+        if a == 0 {
+            return Some(Self::new(0));
+        }
+
+        if b == 0 {
+            return None;
+        }
+
+        let is_answer_negative = (a < 0) != (b < 0);
+
+        // Like the builtin, Dec.MIN can only be divided by 1, and only 1 can be divided by it.
+        let (a_abs, b_abs) = match (a.checked_abs(), b.checked_abs()) {
+            (Some(a_abs), Some(b_abs)) => (a_abs as u128, b_abs as u128),
+            (None, _) => return (b == Self::ONE_POINT_ZERO).then_some(self),
+            (_, None) => return (a == Self::ONE_POINT_ZERO).then_some(other),
+        };
+
+        let (hi, lo) = mul_u128(a_abs, Self::ONE_POINT_ZERO as u128);
+        let answer = div_u256_by_u128(hi, lo, b_abs)?;
+        let answer = i128::try_from(answer).ok()?;
+
+        Some(Self::new(if is_answer_negative { -answer } else { answer }))
+    }
+
+    pub fn checked_neg(self) -> Option<Self> {
+        self.as_i128().checked_neg().map(Self::new)
+    }
+
+    /// The nearest whole number, rounding half-way cases away from zero.
+    ///
+    /// Panics if the result is out of range, like the builtin.
+    pub fn round(self) -> Self {
+        let trunc = self.trunc();
+        let fract = self.fract().as_i128();
+
+        if fract.abs() >= Self::ONE_POINT_ZERO / 2 {
+            trunc + Self::new(fract.signum() * Self::ONE_POINT_ZERO)
+        } else {
+            trunc
+        }
+    }
+
+    /// The largest whole number less than or equal to this one.
+    ///
+    /// Panics if the result is out of range, like the builtin.
+    pub fn floor(self) -> Self {
+        if self.fract().as_i128() < 0 {
+            self.trunc() - Self::from(1)
+        } else {
+            self.trunc()
+        }
+    }
+
+    /// The smallest whole number greater than or equal to this one.
+    ///
+    /// Panics if the result is out of range, like the builtin.
+    pub fn ceiling(self) -> Self {
+        if self.fract().as_i128() > 0 {
+            self.trunc() + Self::from(1)
+        } else {
+            self.trunc()
+        }
+    }
+
+    /// The whole number part, rounding toward zero.
+    pub fn trunc(self) -> Self {
+        Self::new(self.as_i128() - self.fract().as_i128())
+    }
+
+    /// The part after the decimal point, with the same sign as this number.
+    pub fn fract(self) -> Self {
+        Self::new(self.as_i128() % Self::ONE_POINT_ZERO)
+    }
+}
+
+macro_rules! dec_from_int {
+    ($($int:ty),*) => {
+        $(
+            impl From<$int> for RocDec {
+                fn from(value: $int) -> Self {
+                    RocDec::new(RocDec::ONE_POINT_ZERO * value as i128)
+                }
+            }
+        )*
+    };
+}
+
+// Every 64-bit integer fits in a Dec, so these can't overflow.
+dec_from_int!(i8, i16, i32, i64, u8, u16, u32, u64);
+
+impl From<RocDec> for f64 {
+    fn from(value: RocDec) -> Self {
+        value.to_f64()
+    }
 }
 
-impl From<i32> for RocDec {
-    fn from(value: i32) -> Self {
-        RocDec::from_ne_bytes((RocDec::ONE_POINT_ZERO * value as i128).to_ne_bytes())
+impl PartialOrd for RocDec {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for RocDec {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_i128().cmp(&other.as_i128())
     }
 }
 
+// These panic with the same messages as the builtins do.
+
+impl Add for RocDec {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        self.checked_add(other)
+            .expect("Decimal addition overflowed!")
+    }
+}
+
+impl Sub for RocDec {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self.checked_sub(other)
+            .expect("Decimal subtraction overflowed!")
+    }
+}
+
+impl Mul for RocDec {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        self.checked_mul(other)
+            .expect("Decimal multiplication overflowed!")
+    }
+}
+
+impl Div for RocDec {
+    type Output = Self;
+
+    fn div(self, other: Self) -> Self {
+        let (a, b) = (self.as_i128(), other.as_i128());
+
+        if a != 0 {
+            if b == 0 {
+                panic!("Decimal division by 0!");
+            } else if a == i128::MIN && b != Self::ONE_POINT_ZERO {
+                panic!("Decimal division overflow in numerator!");
+            } else if b == i128::MIN && a != Self::ONE_POINT_ZERO {
+                panic!("Decimal division overflow in denominator!");
+            }
+        }
+
+        self.checked_div(other).expect("Decimal division overflow!")
+    }
+}
+
+impl Neg for RocDec {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        self.checked_neg().expect("Decimal negation overflow!")
+    }
+}
+
+/// The full 256-bit product of two u128s, as (high bits, low bits).
+fn mul_u128(a: u128, b: u128) -> (u128, u128) {
+    const LOWER_MASK: u128 = u64::MAX as u128;
+
+    let (a_hi, a_lo) = (a >> 64, a & LOWER_MASK);
+    let (b_hi, b_lo) = (b >> 64, b & LOWER_MASK);
+
+    let lo_lo = a_lo * b_lo;
+    let hi_lo = a_hi * b_lo;
+    let lo_hi = a_lo * b_hi;
+    let hi_hi = a_hi * b_hi;
+
+    let middle = (lo_lo >> 64) + (hi_lo & LOWER_MASK) + (lo_hi & LOWER_MASK);
+
+    let lo = (middle << 64) | (lo_lo & LOWER_MASK);
+    let hi = hi_hi + (hi_lo >> 64) + (lo_hi >> 64) + (middle >> 64);
+
+    (hi, lo)
+}
+
+/// Divides the 256-bit number (hi, lo) by `denom`, rounding toward zero. Returns None if the
+/// quotient doesn't fit in a u128.
+fn div_u256_by_u128(hi: u128, lo: u128, denom: u128) -> Option<u128> {
+    if hi >= denom {
+        return None;
+    }
+
+    // Schoolbook long division, one bit at a time. Since hi < denom, the remainder always fits
+    // in 129 bits, and the top one is kept in `carry`.
+    let mut remainder = hi;
+    let mut quotient = 0;
+
+    for bit in (0..128).rev() {
+        let carry = remainder >> 127;
+
+        remainder = (remainder << 1) | ((lo >> bit) & 1);
+        quotient <<= 1;
+
+        if carry == 1 || remainder >= denom {
+            remainder = remainder.wrapping_sub(denom);
+            quotient |= 1;
+        }
+    }
+
+    Some(quotient)
+}
+
 impl fmt::Display for RocDec {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.write_str(self.to_str_helper(&mut ArrayString::new()))
     }
 }
 
+/// Serialized as a decimal string (e.g. `"-1.25"`), since most formats can't represent a `Dec`
+/// as a number without losing precision.
+#[cfg(feature = "serde")]
+impl Serialize for RocDec {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.to_str_helper(&mut ArrayString::new()))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for RocDec {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(RocDecVisitor {})
+    }
+}
+
+#[cfg(feature = "serde")]
+struct RocDecVisitor {}
+
+#[cfg(feature = "serde")]
+impl<'de> Visitor<'de> for RocDecVisitor {
+    type Value = RocDec;
+
+    fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(formatter, "a decimal number in a string")
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        RocDec::from_str(value)
+            .ok_or_else(|| E::invalid_value(serde::de::Unexpected::Str(value), &self))
+    }
+}
+
 #[repr(C, align(16))]
 #[derive(Clone, Copy, Eq, Default)]
 pub struct I128([u8; 16]);
@@ -510,6 +873,26 @@ impl Hash for I128 {
     }
 }
 
+#[cfg(feature = "serde")]
+impl Serialize for I128 {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_i128(i128::from(*self))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for I128 {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        i128::deserialize(deserializer).map(I128::from)
+    }
+}
+
 #[repr(C, align(16))]
 #[derive(Clone, Copy, Eq, Default)]
 pub struct U128([u8; 16]);
@@ -561,3 +944,114 @@ impl Hash for U128 {
         u128::from(*self).hash(state);
     }
 }
+
+#[cfg(feature = "serde")]
+impl Serialize for U128 {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_u128(u128::from(*self))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for U128 {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        u128::deserialize(deserializer).map(U128::from)
+    }
+}
+
+/// Arithmetic that panics on overflow and division by zero (with the same messages as the
+/// builtins), whether or not debug assertions are enabled, since that's what Roc does.
+macro_rules! int128_arithmetic {
+    ($ty:ident, $int:ty) => {
+        impl $ty {
+            pub fn checked_add(self, other: Self) -> Option<Self> {
+                <$int>::from(self)
+                    .checked_add(<$int>::from(other))
+                    .map(Self::from)
+            }
+
+            pub fn checked_sub(self, other: Self) -> Option<Self> {
+                <$int>::from(self)
+                    .checked_sub(<$int>::from(other))
+                    .map(Self::from)
+            }
+
+            pub fn checked_mul(self, other: Self) -> Option<Self> {
+                <$int>::from(self)
+                    .checked_mul(<$int>::from(other))
+                    .map(Self::from)
+            }
+
+            /// Rounds toward zero, like Roc's `//`.
+            pub fn checked_div(self, other: Self) -> Option<Self> {
+                <$int>::from(self)
+                    .checked_div(<$int>::from(other))
+                    .map(Self::from)
+            }
+        }
+
+        impl Add for $ty {
+            type Output = Self;
+
+            fn add(self, other: Self) -> Self {
+                self.checked_add(other)
+                    .expect("Integer addition overflowed!")
+            }
+        }
+
+        impl Sub for $ty {
+            type Output = Self;
+
+            fn sub(self, other: Self) -> Self {
+                self.checked_sub(other)
+                    .expect("Integer subtraction overflowed!")
+            }
+        }
+
+        impl Mul for $ty {
+            type Output = Self;
+
+            fn mul(self, other: Self) -> Self {
+                self.checked_mul(other)
+                    .expect("Integer multiplication overflowed!")
+            }
+        }
+
+        impl Div for $ty {
+            type Output = Self;
+
+            fn div(self, other: Self) -> Self {
+                if <$int>::from(other) == 0 {
+                    panic!("Integer division by 0!");
+                }
+
+                self.checked_div(other)
+                    .expect("Integer division overflowed!")
+            }
+        }
+    };
+}
+
+int128_arithmetic!(I128, i128);
+int128_arithmetic!(U128, u128);
+
+impl I128 {
+    pub fn checked_neg(self) -> Option<Self> {
+        i128::from(self).checked_neg().map(Self::from)
+    }
+}
+
+impl Neg for I128 {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        self.checked_neg()
+            .expect("Integer negation overflowed because its argument is the minimum value")
+    }
+}
@@ -10,6 +10,9 @@ use core::{
     ptr::{self, NonNull},
 };
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
 #[repr(C)]
 pub struct RocBox<T> {
     contents: NonNull<T>,
@@ -125,6 +128,26 @@ impl<T: core::hash::Hash> core::hash::Hash for RocBox<T> {
     }
 }
 
+#[cfg(feature = "serde")]
+impl<T: Serialize> Serialize for RocBox<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.deref().serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for RocBox<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        T::deserialize(deserializer).map(RocBox::new)
+    }
+}
+
 impl<T> Debug for RocBox<T>
 where
     T: Debug,
@@ -1,3 +1,4 @@
+use crate::roc_hash::{RocHash, RocHasher};
 use crate::roc_list::RocList;
 use core::{
     fmt::{self, Debug},
@@ -5,8 +6,22 @@ use core::{
     mem::{align_of, ManuallyDrop},
 };
 
-/// At the moment, Roc's Dict is just an association list. Its lookups are O(n) but
-/// we haven't grown such big programs that it's a problem yet!
+#[cfg(feature = "serde")]
+use core::marker::PhantomData;
+#[cfg(feature = "serde")]
+use serde::{
+    de::{Deserializer, MapAccess, Visitor},
+    ser::{SerializeMap, Serializer},
+    Deserialize, Serialize,
+};
+
+/// Roc's Dict: a list of key-value pairs in the order they were inserted, plus an index into
+/// that list which is a hash table with Robin Hood hashing. The implementation is almost the same
+/// as [ankerl::unordered_dense](https://github.com/martinus/unordered_dense).
+///
+/// Everything here has to work exactly like Dict.roc does, because hosts hand these to Roc (and
+/// Roc hands them to hosts), and each side expects to find the other's keys where it would have
+/// put them itself. That includes hashing the keys with Roc's hasher and seed; see [`RocHash`].
 ///
 /// We do some things in this data structure that only make sense because the
 /// memory is managed in Roc:
@@ -15,57 +30,454 @@ use core::{
 ///    since Roc owns the memory, not rust.
 /// 2. We use a union for [`RocDictItem`] instead of just a struct. See the
 ///    comment on that data structure for why.
-#[derive(Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[repr(transparent)]
-pub struct RocDict<K, V>(RocList<RocDictItem<K, V>>);
+///
+/// # Linking
+///
+/// Everything that hashes keys ([`RocDict::get`], [`RocDict::insert`], [`FromIterator`], and so
+/// on) calls [`RocHasher::for_dict`], which gets the seed from the Roc app the host is linked
+/// with. Binaries that aren't linked with a Roc app have to define the seed themselves; see
+/// [`RocHasher::for_dict`].
+///
+/// # Breaking change
+///
+/// This used to be a plain list of key-value pairs. Its layout now matches Dict.roc, and
+/// [`FromIterator`], [`Extend`], and `Deserialize` (with the `serde` feature) need
+/// `K: RocHash + Eq + Clone, V: Clone` instead of `K: Hash`, because they hash keys the way Roc
+/// does. Hosts that built a `RocDict` from an iterator need to implement [`RocHash`] for their
+/// keys.
+#[repr(C)]
+pub struct RocDict<K, V> {
+    // Record fields are sorted by alignment, and then by name. A U64 is more aligned than a List
+    // on these 32-bit targets.
+    #[cfg(any(target_arch = "arm", target_arch = "wasm32"))]
+    max_bucket_capacity: u64,
+    buckets: RocList<Bucket>,
+    data: RocList<RocDictItem<K, V>>,
+    #[cfg(not(any(target_arch = "arm", target_arch = "wasm32")))]
+    max_bucket_capacity: u64,
+    max_load_factor: f32,
+    shifts: u8,
+}
+
+/// A slot in the hash table, which says where in `data` to find its key.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(C)]
+struct Bucket {
+    data_index: u32,
+    /// The upper 3 bytes are the distance from the bucket the key hashes to, plus one (so an
+    /// empty bucket is all zeroes). The lower byte is the key's fingerprint: part of its hash.
+    dist_and_fingerprint: u32,
+}
+
+const EMPTY_BUCKET: Bucket = Bucket {
+    data_index: 0,
+    dist_and_fingerprint: 0,
+};
+const DIST_INC: u32 = 1 << 8;
+const FINGERPRINT_MASK: u32 = DIST_INC - 1;
+const DEFAULT_MAX_LOAD_FACTOR: f32 = 0.8;
+/// There are 2^(64 - shifts) buckets.
+const INITIAL_SHIFTS: u8 = 64 - 3;
+const MAX_BUCKET_COUNT: u64 = 1 << 32;
 
 impl<K, V> RocDict<K, V> {
     pub fn len(&self) -> usize {
-        self.0.len()
+        self.data.len()
     }
 
     pub fn is_empty(&self) -> bool {
-        self.0.is_empty()
+        self.data.is_empty()
     }
 
+    /// Like `Dict.withCapacity`. Note that this is the number of entries that fit before the
+    /// buckets have to grow, which is not the capacity of the underlying lists.
     pub fn with_capacity(capacity: usize) -> Self {
-        Self(RocList::with_capacity(capacity))
+        let size = (capacity as u64).min(MAX_BUCKET_COUNT);
+        let shifts = calc_shifts_for_size(size, DEFAULT_MAX_LOAD_FACTOR);
+        let (buckets, max_bucket_capacity) =
+            alloc_buckets_from_shift(shifts, DEFAULT_MAX_LOAD_FACTOR);
+
+        Self {
+            buckets,
+            data: RocList::with_capacity(size as usize),
+            max_bucket_capacity,
+            max_load_factor: DEFAULT_MAX_LOAD_FACTOR,
+            shifts,
+        }
+    }
+
+    /// The number of entries that fit before the buckets have to grow.
+    pub fn capacity(&self) -> usize {
+        self.max_bucket_capacity as usize
     }
 
     pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
-        self.0.iter().map(|item| (item.key(), item.value()))
+        self.data.iter().map(|item| (item.key(), item.value()))
     }
 
     pub fn iter_keys(&self) -> impl Iterator<Item = &K> {
-        self.0.iter().map(|item| item.key())
+        self.data.iter().map(|item| item.key())
     }
 
     pub fn iter_values(&self) -> impl Iterator<Item = &V> {
-        self.0.iter().map(|item| item.value())
+        self.data.iter().map(|item| item.value())
     }
 }
 
-impl<K: Hash, V> RocDict<K, V> {
-    unsafe fn insert_unchecked(&mut self, _key: K, _val: V) {
-        todo!();
+impl<K: RocHash + Eq, V> RocDict<K, V> {
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.get_key_value(key).map(|(_, value)| value)
+    }
+
+    pub fn get_key_value(&self, key: &K) -> Option<(&K, &V)> {
+        let item = &self.data[self.find(key)?];
+
+        Some((item.key(), item.value()))
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.find(key).is_some()
+    }
+
+    /// The index in `data` of the entry for `key`.
+    fn find(&self, key: &K) -> Option<usize> {
+        if self.data.is_empty() {
+            return None;
+        }
+
+        let hash = hash_key(key);
+        let mut dist_and_fingerprint = dist_and_fingerprint_from_hash(hash);
+        let mut bucket_index = bucket_index_from_hash(hash, self.shifts);
+
+        loop {
+            let bucket = self.buckets[bucket_index];
+
+            if dist_and_fingerprint == bucket.dist_and_fingerprint {
+                if self.data[bucket.data_index as usize].key() == key {
+                    return Some(bucket.data_index as usize);
+                }
+            } else if dist_and_fingerprint > bucket.dist_and_fingerprint {
+                // Every key that hashes to the same bucket as this one would have been placed
+                // before this bucket.
+                return None;
+            }
+
+            bucket_index = self.next_bucket_index(bucket_index);
+            dist_and_fingerprint += DIST_INC;
+        }
+    }
+
+    fn next_bucket_index(&self, bucket_index: usize) -> usize {
+        if bucket_index + 1 != self.buckets.len() {
+            bucket_index + 1
+        } else {
+            0
+        }
+    }
+
+    /// The first bucket, starting from the one `key` hashes to, whose entry is no further from
+    /// where it hashes to than `key` would be.
+    fn next_while_less(&self, key: &K) -> (usize, u32) {
+        let hash = hash_key(key);
+        let mut dist_and_fingerprint = dist_and_fingerprint_from_hash(hash);
+        let mut bucket_index = bucket_index_from_hash(hash, self.shifts);
+
+        while dist_and_fingerprint < self.buckets[bucket_index].dist_and_fingerprint {
+            bucket_index = self.next_bucket_index(bucket_index);
+            dist_and_fingerprint += DIST_INC;
+        }
+
+        (bucket_index, dist_and_fingerprint)
+    }
+
+    /// Put the bucket at `bucket_index`, moving the buckets after it along until there's an
+    /// empty one.
+    fn place_and_shift_up(&mut self, mut bucket: Bucket, mut bucket_index: usize) {
+        let buckets = self.buckets.as_mut_slice();
+
+        while buckets[bucket_index].dist_and_fingerprint != 0 {
+            bucket = core::mem::replace(&mut buckets[bucket_index], bucket);
+            bucket.dist_and_fingerprint += DIST_INC;
+            bucket_index = if bucket_index + 1 != buckets.len() {
+                bucket_index + 1
+            } else {
+                0
+            };
+        }
+
+        buckets[bucket_index] = bucket;
+    }
+
+    /// Rebuild the buckets with the given number of shifts.
+    fn rehash(&mut self, shifts: u8) {
+        let (buckets, max_bucket_capacity) = alloc_buckets_from_shift(shifts, self.max_load_factor);
+
+        self.buckets = buckets;
+        self.max_bucket_capacity = max_bucket_capacity;
+        self.shifts = shifts;
+
+        for data_index in 0..self.data.len() {
+            let (bucket_index, dist_and_fingerprint) =
+                self.next_while_less(self.data[data_index].key());
+            let bucket = Bucket {
+                data_index: data_index as u32,
+                dist_and_fingerprint,
+            };
+
+            self.place_and_shift_up(bucket, bucket_index);
+        }
     }
 }
 
-impl<K: Hash, V> FromIterator<(K, V)> for RocDict<K, V> {
-    fn from_iter<T: IntoIterator<Item = (K, V)>>(into_iter: T) -> Self {
-        let src = into_iter.into_iter();
-        let mut ret = Self::with_capacity(src.size_hint().0);
+impl<K: RocHash + Eq + Clone, V: Clone> RocDict<K, V> {
+    /// Insert the entry, and return the value that `key` had before, if any. Like `Dict.insert`,
+    /// this also replaces the key.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        self.make_unique();
+
+        if self.len() as u64 >= self.max_bucket_capacity {
+            self.increase_size();
+        }
+
+        let hash = hash_key(&key);
+        let mut dist_and_fingerprint = dist_and_fingerprint_from_hash(hash);
+        let mut bucket_index = bucket_index_from_hash(hash, self.shifts);
 
-        for (key, val) in src {
-            unsafe {
-                ret.insert_unchecked(key, val);
+        loop {
+            let loaded = self.buckets[bucket_index];
+
+            if dist_and_fingerprint == loaded.dist_and_fingerprint {
+                let item = &mut self.data.as_mut_slice()[loaded.data_index as usize];
+
+                if item.key() == &key {
+                    let (_, old_value) =
+                        core::mem::replace(item, RocDictItem::new(key, value)).into_parts();
+
+                    return Some(old_value);
+                }
+            } else if dist_and_fingerprint > loaded.dist_and_fingerprint {
+                let bucket = Bucket {
+                    data_index: self.data.len() as u32,
+                    dist_and_fingerprint,
+                };
+
+                self.data.push(RocDictItem::new(key, value));
+                self.place_and_shift_up(bucket, bucket_index);
+
+                return None;
             }
+
+            bucket_index = self.next_bucket_index(bucket_index);
+            dist_and_fingerprint += DIST_INC;
+        }
+    }
+
+    /// Remove the entry for `key`, and return its value. Like `Dict.remove`, this moves the last
+    /// entry into the removed entry's place.
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        if self.data.is_empty() {
+            return None;
+        }
+
+        let (mut bucket_index, mut dist_and_fingerprint) = self.next_while_less(key);
+
+        loop {
+            let bucket = self.buckets[bucket_index];
+
+            if dist_and_fingerprint != bucket.dist_and_fingerprint {
+                return None;
+            }
+
+            if self.data[bucket.data_index as usize].key() == key {
+                break;
+            }
+
+            bucket_index = self.next_bucket_index(bucket_index);
+            dist_and_fingerprint += DIST_INC;
+        }
+
+        self.make_unique();
+
+        Some(self.remove_bucket(bucket_index))
+    }
+
+    fn remove_bucket(&mut self, mut bucket_index: usize) -> V {
+        let data_index_to_remove = self.buckets[bucket_index].data_index;
+
+        // Shift the following buckets down, until one is empty or already where it hashes to.
+        loop {
+            let next_index = self.next_bucket_index(bucket_index);
+            let next_bucket = self.buckets[next_index];
+
+            if next_bucket.dist_and_fingerprint < DIST_INC * 2 {
+                break;
+            }
+
+            self.buckets.as_mut_slice()[bucket_index] = Bucket {
+                dist_and_fingerprint: next_bucket.dist_and_fingerprint - DIST_INC,
+                ..next_bucket
+            };
+            bucket_index = next_index;
+        }
+
+        self.buckets.as_mut_slice()[bucket_index] = EMPTY_BUCKET;
+
+        let last_data_index = self.data.len() as u32 - 1;
+
+        if data_index_to_remove != last_data_index {
+            // Swap the removed entry to the end, and point the last entry's bucket at its new
+            // place.
+            self.data
+                .as_mut_slice()
+                .swap(data_index_to_remove as usize, last_data_index as usize);
+
+            let hash = hash_key(self.data[data_index_to_remove as usize].key());
+            let mut bucket_index = bucket_index_from_hash(hash, self.shifts);
+
+            while self.buckets[bucket_index].data_index != last_data_index {
+                bucket_index = self.next_bucket_index(bucket_index);
+            }
+
+            self.buckets.as_mut_slice()[bucket_index].data_index = data_index_to_remove;
+        }
+
+        let (_, value) = self.data.pop().unwrap().into_parts();
+
+        value
+    }
+
+    fn increase_size(&mut self) {
+        if self.max_bucket_capacity == MAX_BUCKET_COUNT {
+            panic!("Dict hit limit of {MAX_BUCKET_COUNT} elements. Unable to grow more.");
         }
 
+        self.rehash(self.shifts.wrapping_sub(1));
+    }
+
+    /// Make sure that changing the dict won't change any other references to its lists.
+    fn make_unique(&mut self) {
+        self.buckets.make_unique();
+        self.data.make_unique();
+    }
+}
+
+fn hash_key<K: RocHash>(key: &K) -> u64 {
+    let mut hasher = RocHasher::for_dict();
+    key.roc_hash(&mut hasher);
+    hasher.complete()
+}
+
+fn dist_and_fingerprint_from_hash(hash: u64) -> u32 {
+    (hash as u32 & FINGERPRINT_MASK) | DIST_INC
+}
+
+fn bucket_index_from_hash(hash: u64, shifts: u8) -> usize {
+    (hash >> shifts) as usize
+}
+
+fn calc_num_buckets(shifts: u8) -> u64 {
+    1u64.checked_shl(64 - shifts as u32)
+        .unwrap_or(u64::MAX)
+        .min(MAX_BUCKET_COUNT)
+}
+
+fn calc_shifts_for_size(size: u64, max_load_factor: f32) -> u8 {
+    let mut shifts = INITIAL_SHIFTS;
+
+    while shifts > 0 && max_bucket_capacity(calc_num_buckets(shifts), max_load_factor) < size {
+        shifts -= 1;
+    }
+
+    shifts
+}
+
+fn max_bucket_capacity(bucket_count: u64, max_load_factor: f32) -> u64 {
+    (bucket_count as f32 * max_load_factor).floor() as u64
+}
+
+fn alloc_buckets_from_shift(shifts: u8, max_load_factor: f32) -> (RocList<Bucket>, u64) {
+    let bucket_count = calc_num_buckets(shifts);
+    let buckets = (0..bucket_count).map(|_| EMPTY_BUCKET).collect();
+
+    if bucket_count == MAX_BUCKET_COUNT {
+        // We've reached the maximum, so make sure we can use every bucket.
+        (buckets, MAX_BUCKET_COUNT)
+    } else {
+        (buckets, max_bucket_capacity(bucket_count, max_load_factor))
+    }
+}
+
+impl<K, V> Default for RocDict<K, V> {
+    /// Like `Dict.empty {}`, which doesn't allocate any buckets yet.
+    fn default() -> Self {
+        Self {
+            buckets: RocList::empty(),
+            data: RocList::empty(),
+            max_bucket_capacity: 0,
+            max_load_factor: DEFAULT_MAX_LOAD_FACTOR,
+            shifts: INITIAL_SHIFTS,
+        }
+    }
+}
+
+impl<K, V> Clone for RocDict<K, V> {
+    fn clone(&self) -> Self {
+        Self {
+            buckets: self.buckets.clone(),
+            data: self.data.clone(),
+            max_bucket_capacity: self.max_bucket_capacity,
+            max_load_factor: self.max_load_factor,
+            shifts: self.shifts,
+        }
+    }
+}
+
+// These compare the entries in the order they were inserted, unlike Roc's `Dict.isEq`.
+
+impl<K: PartialEq, V: PartialEq> PartialEq for RocDict<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.data == other.data
+    }
+}
+
+impl<K: Eq, V: Eq> Eq for RocDict<K, V> {}
+
+impl<K: PartialOrd, V: PartialOrd> PartialOrd for RocDict<K, V> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        self.data.partial_cmp(&other.data)
+    }
+}
+
+impl<K: Ord, V: Ord> Ord for RocDict<K, V> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.data.cmp(&other.data)
+    }
+}
+
+impl<K: Hash, V: Hash> Hash for RocDict<K, V> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.data.hash(state)
+    }
+}
+
+impl<K: RocHash + Eq + Clone, V: Clone> FromIterator<(K, V)> for RocDict<K, V> {
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(into_iter: T) -> Self {
+        let mut ret = Self::default();
+
+        ret.extend(into_iter);
+
         ret
     }
 }
 
+impl<K: RocHash + Eq + Clone, V: Clone> Extend<(K, V)> for RocDict<K, V> {
+    fn extend<T: IntoIterator<Item = (K, V)>>(&mut self, iter: T) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
 impl<'a, K, V> IntoIterator for &'a RocDict<K, V> {
     type Item = (&'a K, &'a V);
     type IntoIter = IntoIter<'a, K, V>;
@@ -73,7 +485,7 @@ impl<'a, K, V> IntoIterator for &'a RocDict<K, V> {
     fn into_iter(self) -> Self::IntoIter {
         IntoIter {
             index: 0,
-            items: self.0.as_slice(),
+            items: self.data.as_slice(),
         }
     }
 }
@@ -104,6 +516,68 @@ impl<'a, K, V> Iterator for IntoIter<'a, K, V> {
     }
 }
 
+#[cfg(feature = "serde")]
+impl<K: Serialize, V: Serialize> Serialize for RocDict<K, V> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(Some(self.len()))?;
+        for (key, value) in self {
+            map.serialize_entry(key, value)?;
+        }
+        map.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, K, V> Deserialize<'de> for RocDict<K, V>
+where
+    K: Deserialize<'de> + RocHash + Eq + Clone,
+    V: Deserialize<'de> + Clone,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(RocDictVisitor {
+            marker: PhantomData,
+        })
+    }
+}
+
+#[cfg(feature = "serde")]
+struct RocDictVisitor<K, V> {
+    marker: PhantomData<(K, V)>,
+}
+
+#[cfg(feature = "serde")]
+impl<'de, K, V> Visitor<'de> for RocDictVisitor<K, V>
+where
+    K: Deserialize<'de> + RocHash + Eq + Clone,
+    V: Deserialize<'de> + Clone,
+{
+    type Value = RocDict<K, V>;
+
+    fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(formatter, "a map")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut out = RocDict::with_capacity(map.size_hint().unwrap_or(0));
+
+        // Like a HashMap, later entries win if the same key appears more than once.
+        while let Some((key, value)) = map.next_entry()? {
+            out.insert(key, value);
+        }
+
+        Ok(out)
+    }
+}
+
 impl<K: Debug, V: Debug> Debug for RocDict<K, V> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.write_str("RocDict ")?;
@@ -150,6 +624,32 @@ struct ValueFirst<K, V> {
 }
 
 impl<K, V> RocDictItem<K, V> {
+    fn new(key: K, value: V) -> Self {
+        if align_of::<K>() >= align_of::<V>() {
+            Self {
+                key_first: ManuallyDrop::new(KeyFirst { key, value }),
+            }
+        } else {
+            Self {
+                value_first: ManuallyDrop::new(ValueFirst { value, key }),
+            }
+        }
+    }
+
+    fn into_parts(self) -> (K, V) {
+        let mut item = ManuallyDrop::new(self);
+
+        unsafe {
+            if align_of::<K>() >= align_of::<V>() {
+                let KeyFirst { key, value } = ManuallyDrop::take(&mut item.key_first);
+                (key, value)
+            } else {
+                let ValueFirst { value, key } = ManuallyDrop::take(&mut item.value_first);
+                (key, value)
+            }
+        }
+    }
+
     fn key(&self) -> &K {
         if align_of::<K>() >= align_of::<V>() {
             unsafe { &self.key_first.key }
@@ -177,6 +677,12 @@ impl<K, V> Drop for RocDictItem<K, V> {
     }
 }
 
+impl<K: Clone, V: Clone> Clone for RocDictItem<K, V> {
+    fn clone(&self) -> Self {
+        Self::new(self.key().clone(), self.value().clone())
+    }
+}
+
 impl<K: PartialEq, V: PartialEq> PartialEq for RocDictItem<K, V> {
     fn eq(&self, other: &Self) -> bool {
         self.key() == other.key() && self.value() == other.value()
@@ -0,0 +1,367 @@
+//! A port of the hasher that Roc's `Dict` and `Set` use, so that hosts can build dictionaries
+//! that Roc can look things up in.
+//!
+//! This has to match `LowLevelHasher` in Dict.roc, and the way the compiler derives `Hash` for
+//! each type, bit for bit. If either of those change, this has to change with them.
+
+use crate::{RocDec, RocList, RocResult, RocStr, I128, U128};
+
+extern "C" {
+    /// Roc hashes the keys of every `Dict` with the same seed, which is the address of this
+    /// builtin. It's different every time the program runs (because of ASLR), so the host has to
+    /// ask for it too.
+    #[link_name = "roc_builtins.utils.dict_pseudo_seed"]
+    fn roc_dict_pseudo_seed() -> u64;
+}
+
+const WYP0: u64 = 0xa0761d6478bd642f;
+const WYP1: u64 = 0xe7037ed1a0b428db;
+const WYP2: u64 = 0x8ebc6af09c88c6e3;
+const WYP3: u64 = 0x589965cc75374cc3;
+
+/// A value that can be hashed the same way Roc's `Hash` ability hashes it.
+///
+/// Records hash their fields in alphabetical order, tuples hash their elements in order, and tag
+/// unions hash the index of their tag (in alphabetical order) as a `u8`, followed by its payloads.
+/// A tag union with only one tag doesn't hash its tag index.
+pub trait RocHash {
+    fn roc_hash(&self, hasher: &mut RocHasher);
+}
+
+/// Roc's `LowLevelHasher`: a form of [wyhash final4](https://github.com/wangyi-fudan/wyhash).
+#[derive(Clone, Copy, Debug)]
+pub struct RocHasher {
+    initialized_seed: u64,
+    state: u64,
+}
+
+impl RocHasher {
+    pub fn with_seed(seed: u64) -> Self {
+        Self {
+            initialized_seed: wymix(seed ^ WYP0, WYP1) ^ seed,
+            state: seed,
+        }
+    }
+
+    /// A hasher with the seed that Roc uses for the keys of every `Dict`.
+    ///
+    /// This calls `roc_builtins.utils.dict_pseudo_seed`, which every Roc app exports to its host,
+    /// like `roc_alloc` and friends go the other way. Rust binaries that aren't linked with a Roc
+    /// app (e.g. tests) have to define it themselves, with
+    /// `#[export_name = "roc_builtins.utils.dict_pseudo_seed"]` on an `extern "C" fn() -> u64`.
+    #[inline]
+    pub fn for_dict() -> Self {
+        Self::with_seed(unsafe { roc_dict_pseudo_seed() })
+    }
+
+    pub fn complete(&self) -> u64 {
+        self.state
+    }
+
+    pub fn add_u8(&mut self, value: u8) {
+        let p0 = value as u64;
+        let a = (p0 << 16) | (p0 << 8) | p0;
+
+        self.combine(a, 0, self.initialized_seed, 1);
+    }
+
+    pub fn add_u16(&mut self, value: u16) {
+        let p0 = (value & 0xFF) as u64;
+        let p1 = (value >> 8) as u64;
+        let a = (p0 << 16) | (p1 << 8) | p1;
+
+        self.combine(a, 0, self.initialized_seed, 2);
+    }
+
+    pub fn add_u32(&mut self, value: u32) {
+        let p0 = value as u64;
+        let a = (p0 << 32) | p0;
+
+        self.combine(a, a, self.initialized_seed, 4);
+    }
+
+    pub fn add_u64(&mut self, value: u64) {
+        let p0 = value & 0xFFFF_FFFF;
+        let p1 = value >> 32;
+        let a = (p0 << 32) | p1;
+        let b = (p1 << 32) | p0;
+
+        self.combine(a, b, self.initialized_seed, 8);
+    }
+
+    pub fn add_u128(&mut self, value: u128) {
+        let lower = value as u64;
+        let upper = (value >> 64) as u64;
+        let p0 = lower & 0xFFFF_FFFF;
+        let p1 = lower >> 32;
+        let p2 = upper & 0xFFFF_FFFF;
+        let p3 = upper >> 32;
+        let a = (p0 << 32) | p2;
+        let b = (p3 << 32) | p1;
+
+        self.combine(a, b, self.initialized_seed, 16);
+    }
+
+    pub fn add_bytes(&mut self, bytes: &[u8]) {
+        let length = bytes.len();
+        let (a, b, seed) = if length <= 16 {
+            if length >= 4 {
+                let x = (length >> 3) << 2;
+                let a = (wyr4(bytes, 0) << 32) | wyr4(bytes, x);
+                let b = (wyr4(bytes, length - 4) << 32) | wyr4(bytes, length - 4 - x);
+
+                (a, b, self.initialized_seed)
+            } else if length > 0 {
+                (wyr3(bytes, 0, length), 0, self.initialized_seed)
+            } else {
+                (0, 0, self.initialized_seed)
+            }
+        } else if length <= 48 {
+            hash_bytes_16(self.initialized_seed, bytes, 0, length)
+        } else {
+            hash_bytes_48(bytes, self.initialized_seed)
+        };
+
+        self.combine(a, b, seed, length as u64);
+    }
+
+    fn combine(&mut self, a: u64, b: u64, seed: u64, length: u64) {
+        let (lower, upper) = wymum(a ^ WYP1, b ^ seed);
+        let hash = wymix(lower ^ WYP0 ^ length, upper ^ WYP1);
+
+        self.state = wymix(self.state, hash);
+    }
+}
+
+fn hash_bytes_48(bytes: &[u8], initialized_seed: u64) -> (u64, u64, u64) {
+    let mut seed = initialized_seed;
+    let mut see1 = initialized_seed;
+    let mut see2 = initialized_seed;
+    let mut index = 0;
+    let mut remaining = bytes.len();
+
+    loop {
+        seed = wymix(wyr8(bytes, index) ^ WYP1, wyr8(bytes, index + 8) ^ seed);
+        see1 = wymix(
+            wyr8(bytes, index + 16) ^ WYP2,
+            wyr8(bytes, index + 24) ^ see1,
+        );
+        see2 = wymix(
+            wyr8(bytes, index + 32) ^ WYP3,
+            wyr8(bytes, index + 40) ^ see2,
+        );
+        remaining -= 48;
+        index += 48;
+
+        if remaining <= 48 {
+            break;
+        }
+    }
+
+    let seed = see2 ^ see1 ^ seed;
+
+    if remaining > 16 {
+        hash_bytes_16(seed, bytes, index, remaining)
+    } else {
+        let end = index + remaining;
+
+        (wyr8(bytes, end - 16), wyr8(bytes, end - 8), seed)
+    }
+}
+
+fn hash_bytes_16(
+    mut seed: u64,
+    bytes: &[u8],
+    mut index: usize,
+    mut remaining: usize,
+) -> (u64, u64, u64) {
+    loop {
+        seed = wymix(wyr8(bytes, index) ^ WYP1, wyr8(bytes, index + 8) ^ seed);
+        remaining -= 16;
+        index += 16;
+
+        if remaining <= 16 {
+            let end = index + remaining;
+
+            return (wyr8(bytes, end - 16), wyr8(bytes, end - 8), seed);
+        }
+    }
+}
+
+fn wymix(a: u64, b: u64) -> u64 {
+    let (lower, upper) = wymum(a, b);
+
+    lower ^ upper
+}
+
+fn wymum(a: u64, b: u64) -> (u64, u64) {
+    let r = (a as u128).wrapping_mul(b as u128);
+
+    (r as u64, (r >> 64) as u64)
+}
+
+fn wyr8(bytes: &[u8], index: usize) -> u64 {
+    let mut le_bytes = [0; 8];
+    le_bytes.copy_from_slice(&bytes[index..index + 8]);
+
+    u64::from_le_bytes(le_bytes)
+}
+
+fn wyr4(bytes: &[u8], index: usize) -> u64 {
+    let mut le_bytes = [0; 4];
+    le_bytes.copy_from_slice(&bytes[index..index + 4]);
+
+    u32::from_le_bytes(le_bytes) as u64
+}
+
+fn wyr3(bytes: &[u8], index: usize, k: usize) -> u64 {
+    let p1 = bytes[index] as u64;
+    let p2 = bytes[index + (k >> 1)] as u64;
+    let p3 = bytes[index + k - 1] as u64;
+
+    (p1 << 16) | (p2 << 8) | p3
+}
+
+macro_rules! roc_hash_unsigned {
+    ($($ty:ty => $add:ident),*) => {
+        $(
+            impl RocHash for $ty {
+                fn roc_hash(&self, hasher: &mut RocHasher) {
+                    hasher.$add(*self)
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! roc_hash_signed {
+    ($($ty:ty => $unsigned:ty),*) => {
+        $(
+            impl RocHash for $ty {
+                fn roc_hash(&self, hasher: &mut RocHasher) {
+                    (*self as $unsigned).roc_hash(hasher)
+                }
+            }
+        )*
+    };
+}
+
+roc_hash_unsigned!(u8 => add_u8, u16 => add_u16, u32 => add_u32, u64 => add_u64, u128 => add_u128);
+roc_hash_signed!(i8 => u8, i16 => u16, i32 => u32, i64 => u64, i128 => u128);
+
+impl RocHash for bool {
+    fn roc_hash(&self, hasher: &mut RocHasher) {
+        hasher.add_u8(*self as u8)
+    }
+}
+
+/// The empty record, `{}`, e.g. the values of the `Dict` inside a `Set`.
+impl RocHash for () {
+    fn roc_hash(&self, _hasher: &mut RocHasher) {}
+}
+
+impl RocHash for U128 {
+    fn roc_hash(&self, hasher: &mut RocHasher) {
+        hasher.add_u128(u128::from(*self))
+    }
+}
+
+impl RocHash for I128 {
+    fn roc_hash(&self, hasher: &mut RocHasher) {
+        i128::from(*self).roc_hash(hasher)
+    }
+}
+
+impl RocHash for RocDec {
+    fn roc_hash(&self, hasher: &mut RocHasher) {
+        i128::from_ne_bytes(self.to_ne_bytes()).roc_hash(hasher)
+    }
+}
+
+impl RocHash for RocStr {
+    fn roc_hash(&self, hasher: &mut RocHasher) {
+        hasher.add_bytes(self.as_bytes())
+    }
+}
+
+impl<T: RocHash> RocHash for RocList<T> {
+    fn roc_hash(&self, hasher: &mut RocHasher) {
+        for elem in self.iter() {
+            elem.roc_hash(hasher);
+        }
+    }
+}
+
+/// A `Result` is a tag union, `[Err e, Ok a]`.
+impl<T: RocHash, E: RocHash> RocHash for RocResult<T, E> {
+    fn roc_hash(&self, hasher: &mut RocHasher) {
+        match self.as_result_of_refs() {
+            Err(payload) => {
+                hasher.add_u8(0);
+                payload.roc_hash(hasher);
+            }
+            Ok(payload) => {
+                hasher.add_u8(1);
+                payload.roc_hash(hasher);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_low_level_hasher() {
+        // These are the expects at the bottom of Dict.roc.
+        let hash = |add: &dyn Fn(&mut RocHasher)| {
+            let mut hasher = RocHasher::with_seed(0x526F_6352_616E_643F);
+            add(&mut hasher);
+            hasher.complete()
+        };
+
+        assert_eq!(hash(&|h| h.add_bytes(&[])), 0xD59C59757DBBE6B3);
+        assert_eq!(hash(&|h| h.add_bytes(&[0x42])), 0x38CE03D0E61AF963);
+        assert_eq!(hash(&|h| h.add_u8(0x42)), 0x38CE03D0E61AF963);
+        assert_eq!(hash(&|h| h.add_bytes(&[0xFF, 0xFF])), 0xE1CB2FA0D6A64113);
+        assert_eq!(hash(&|h| h.add_u16(0xFFFF)), 0xE1CB2FA0D6A64113);
+        assert_eq!(hash(&|h| h.add_bytes(&[0x36, 0xA7])), 0x26B8319EDAF81B15);
+        assert_eq!(hash(&|h| h.add_u16(0xA736)), 0x26B8319EDAF81B15);
+        assert_eq!(hash(&|h| h.add_bytes(&[0, 0, 0, 0])), 0xA187D7CA074F9EE7);
+        assert_eq!(hash(&|h| h.add_u32(0)), 0xA187D7CA074F9EE7);
+        assert_eq!(
+            hash(&|h| h.add_bytes(&[0xA9, 0x2F, 0xEE, 0x21])),
+            0xA499EFE4C1454D09
+        );
+        assert_eq!(hash(&|h| h.add_u32(0x21EE_2FA9)), 0xA499EFE4C1454D09);
+        assert_eq!(
+            hash(&|h| h.add_bytes(&[
+                0x5D, 0x66, 0xB1, 0x8F, 0x68, 0x44, 0xC7, 0x03, 0xE1, 0xDD, 0x23, 0x34, 0xBB, 0x9A,
+                0x42, 0xA7
+            ])),
+            0xDD39A206AED64C73
+        );
+        assert_eq!(
+            hash(&|h| h.add_u128(0xA742_9ABB_3423_DDE1_03C7_4468_8FB1_665D)),
+            0xDD39A206AED64C73
+        );
+        assert_eq!(
+            hash(&|h| h.add_bytes(b"abcdefghijklmnopqrstuvwxyz")),
+            0x51C59DF5B1D15F40
+        );
+        assert_eq!(
+            hash(
+                &|h| h.add_bytes(b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789")
+            ),
+            0xD8D0A129D97A4E95
+        );
+        assert_eq!(
+            hash(&|h| h.add_bytes(
+                b"1234567890123456789012345678901234567890123456789012345678901234567890"
+            )),
+            0x8188065B44FB4AAA
+        );
+        assert_eq!(hash(&|h| h.add_bytes(&[0x77; 100])), 0x47A2A606EADF3378);
+    }
+}
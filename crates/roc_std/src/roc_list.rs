@@ -265,6 +265,20 @@ impl<T> RocList<T> {
         self.length += 1;
     }
 
+    /// Remove the last element and return it. The list must be unique, or this would take the
+    /// element away from the list's other references too.
+    pub(crate) fn pop(&mut self) -> Option<T> {
+        debug_assert!(self.is_unique() && !self.is_seamless_slice());
+
+        let new_len = self.len().checked_sub(1)?;
+        let elements = self.elements?.as_ptr();
+
+        // Shorten the list first, so the element can't be dropped twice.
+        self.length = new_len;
+
+        Some(unsafe { ManuallyDrop::into_inner(ptr::read(elements.add(new_len))) })
+    }
+
     /// # Safety
     ///
     /// - `bytes` must be allocated for `cap` elements
@@ -290,6 +304,14 @@ where
         list
     }
 
+    /// Copy the elements into a new allocation, unless this is the only reference to them, so
+    /// that they can be changed in place without changing any other references' elements.
+    pub(crate) fn make_unique(&mut self) {
+        if !self.is_unique() || self.is_seamless_slice() {
+            *self = Self::from_slice(self);
+        }
+    }
+
     pub fn extend_from_slice(&mut self, slice: &[T]) {
         // TODO: Can we do better for ZSTs? Alignment might be a problem.
         if slice.is_empty() {
@@ -304,14 +326,14 @@ where
 
             if is_unique {
                 // If we have enough capacity, we can add to the existing elements in-place.
-                if self.capacity() >= slice.len() {
+                if self.capacity() >= new_len {
                     elements
                 } else {
                     // There wasn't enough capacity, so we need a new allocation.
                     // Since this is a unique RocList, we can use realloc here.
                     let new_ptr = unsafe {
                         roc_realloc(
-                            storage.as_ptr().cast(),
+                            self.ptr_to_allocation(),
                             Self::alloc_bytes(new_len),
                             Self::alloc_bytes(self.capacity()),
                             Self::alloc_alignment(),
@@ -331,8 +353,8 @@ where
                 }
 
                 // Allocate new memory.
-                self.capacity_or_ref_ptr = slice.len();
-                let new_elements = Self::elems_with_capacity(slice.len());
+                self.capacity_or_ref_ptr = new_len;
+                let new_elements = Self::elems_with_capacity(new_len);
 
                 // Copy the old elements to the new allocation.
                 unsafe {
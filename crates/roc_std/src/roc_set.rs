@@ -1,10 +1,25 @@
 use crate::roc_dict::RocDict;
+use crate::roc_hash::RocHash;
 use core::{
     fmt::{self, Debug},
     hash::Hash,
 };
 
+#[cfg(feature = "serde")]
+use core::marker::PhantomData;
+#[cfg(feature = "serde")]
+use serde::{
+    de::{Deserializer, SeqAccess, Visitor},
+    ser::{SerializeSeq, Serializer},
+    Deserialize, Serialize,
+};
+
+/// Roc's Set, which is a [`RocDict`] with `()` values.
+///
+/// Like [`RocDict`], everything that hashes values needs them to implement [`RocHash`] (not
+/// [`Hash`]), and gets the seed from the Roc app; see [`RocDict`] for details.
 #[derive(Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
 pub struct RocSet<T>(RocDict<T, ()>);
 
 impl<T> RocSet<T> {
@@ -27,7 +42,31 @@ impl<T> RocSet<T> {
     }
 }
 
-impl<T: Hash> FromIterator<T> for RocSet<T> {
+impl<T: RocHash + Eq> RocSet<T> {
+    pub fn contains(&self, value: &T) -> bool {
+        self.0.contains_key(value)
+    }
+
+    /// The set's value that's equal to `value`, if any.
+    pub fn get(&self, value: &T) -> Option<&T> {
+        self.0.get_key_value(value).map(|(key, _)| key)
+    }
+}
+
+impl<T: RocHash + Eq + Clone> RocSet<T> {
+    /// Returns whether the value was newly inserted. Like `Set.insert`, this replaces an equal
+    /// value that was already in the set.
+    pub fn insert(&mut self, value: T) -> bool {
+        self.0.insert(value, ()).is_none()
+    }
+
+    /// Returns whether the value was in the set.
+    pub fn remove(&mut self, value: &T) -> bool {
+        self.0.remove(value).is_some()
+    }
+}
+
+impl<T: RocHash + Eq + Clone> FromIterator<T> for RocSet<T> {
     fn from_iter<I: IntoIterator<Item = T>>(into_iter: I) -> Self {
         Self(RocDict::from_iter(
             into_iter.into_iter().map(|elem| (elem, ())),
@@ -35,6 +74,12 @@ impl<T: Hash> FromIterator<T> for RocSet<T> {
     }
 }
 
+impl<T: RocHash + Eq + Clone> Extend<T> for RocSet<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.0.extend(iter.into_iter().map(|elem| (elem, ())))
+    }
+}
+
 impl<T: Debug> Debug for RocSet<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.write_str("RocSet ")?;
@@ -42,3 +87,62 @@ impl<T: Debug> Debug for RocSet<T> {
         f.debug_set().entries(self.iter()).finish()
     }
 }
+
+#[cfg(feature = "serde")]
+impl<T: Serialize> Serialize for RocSet<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut seq = serializer.serialize_seq(Some(self.len()))?;
+        for item in self.iter() {
+            seq.serialize_element(item)?;
+        }
+        seq.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T> Deserialize<'de> for RocSet<T>
+where
+    T: Deserialize<'de> + RocHash + Eq + Clone,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(RocSetVisitor {
+            marker: PhantomData,
+        })
+    }
+}
+
+#[cfg(feature = "serde")]
+struct RocSetVisitor<T> {
+    marker: PhantomData<T>,
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T> Visitor<'de> for RocSetVisitor<T>
+where
+    T: Deserialize<'de> + RocHash + Eq + Clone,
+{
+    type Value = RocSet<T>;
+
+    fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(formatter, "a list of unique elements")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut out = RocDict::with_capacity(seq.size_hint().unwrap_or(0));
+
+        while let Some(elem) = seq.next_element()? {
+            out.insert(elem, ());
+        }
+
+        Ok(RocSet(out))
+    }
+}
@@ -52,9 +52,18 @@ pub unsafe extern "C" fn roc_memset(dst: *mut c_void, c: i32, n: usize) -> *mut
     libc::memset(dst, c, n)
 }
 
+#[cfg(test)]
+#[export_name = "roc_builtins.utils.dict_pseudo_seed"]
+pub extern "C" fn dict_pseudo_seed() -> u64 {
+    // In a Roc app, this is the address of the function; any value works for these tests.
+    0x526F_6352_616E_643F
+}
+
 #[cfg(test)]
 mod test_roc_std {
     use roc_std::{RocBox, RocDec, RocList, RocResult, RocStr, SendSafeRocStr};
+    use roc_std::{RocDict, RocSet};
+    use roc_std::{I128, U128};
 
     fn roc_str_byte_representation(string: &RocStr) -> [u8; RocStr::SIZE] {
         unsafe { core::mem::transmute_copy(string) }
@@ -174,6 +183,31 @@ mod test_roc_std {
         assert_eq!(roc_list.capacity(), 5000);
     }
 
+    #[test]
+    fn extend_unique_list_past_capacity() {
+        let mut roc_list = RocList::<i64>::empty();
+        roc_list.reserve(4);
+        roc_list.extend_from_slice(&[1, 2, 3]);
+
+        // The 2 new elements fit in the capacity, but not next to the 3 that are already there
+        roc_list.extend_from_slice(&[4, 5]);
+
+        assert_eq!(roc_list.as_slice(), &[1, 2, 3, 4, 5]);
+        assert!(roc_list.capacity() >= 5);
+    }
+
+    #[test]
+    fn extend_shared_list() {
+        let original = RocList::from_slice(&[1i64, 2, 3]);
+        let mut extended = original.clone();
+
+        extended.extend_from_slice(&[4, 5, 6, 7]);
+
+        assert_eq!(extended.as_slice(), &[1, 2, 3, 4, 5, 6, 7]);
+        assert!(extended.capacity() >= 7);
+        assert_eq!(original.as_slice(), &[1, 2, 3]);
+    }
+
     #[test]
     #[cfg(feature = "serde")]
     fn short_list_roundtrip() {
@@ -307,6 +341,295 @@ mod test_roc_std {
         assert_eq!(format!("{example}"), "3.141592653589793238");
     }
 
+    fn dec(string: &str) -> RocDec {
+        RocDec::from_str(string).unwrap()
+    }
+
+    #[test]
+    fn roc_dec_arithmetic() {
+        assert_eq!(dec("1.5") + dec("2.25"), dec("3.75"));
+        assert_eq!(dec("1.5") - dec("2.25"), dec("-0.75"));
+        assert_eq!(dec("1.5") * dec("1.5"), dec("2.25"));
+        assert_eq!(dec("-0.1") * dec("3"), dec("-0.3"));
+        assert_eq!(-dec("1.5"), dec("-1.5"));
+
+        // these are the cases from dec.zig
+        assert_eq!(RocDec::from(8) / RocDec::from(5), dec("1.6"));
+        assert_eq!(
+            RocDec::from(10) / RocDec::from(3),
+            dec("3.333333333333333333")
+        );
+        assert_eq!(
+            RocDec::from(342) / RocDec::from(343),
+            dec("0.997084548104956268")
+        );
+        assert_eq!(
+            RocDec::from(-10) / RocDec::from(3),
+            dec("-3.333333333333333333")
+        );
+
+        // digits past the 18th decimal place are truncated
+        assert_eq!(dec("0.000000000000000001") * dec("0.5"), RocDec::from(0));
+        assert_eq!(
+            dec("-0.000000000000000001") / RocDec::from(2),
+            RocDec::from(0)
+        );
+
+        assert_eq!(RocDec::MAX * RocDec::from(1), RocDec::MAX);
+        assert_eq!(RocDec::MIN * RocDec::from(1), RocDec::MIN);
+        assert_eq!(RocDec::MIN / RocDec::from(1), RocDec::MIN);
+        assert_eq!(RocDec::from(0) / RocDec::from(0), RocDec::from(0));
+
+        assert_eq!(RocDec::MAX.checked_add(dec("0.000000000000000001")), None);
+        assert_eq!(RocDec::MIN.checked_sub(dec("0.000000000000000001")), None);
+        assert_eq!(RocDec::MAX.checked_mul(RocDec::from(2)), None);
+        assert_eq!(RocDec::MIN.checked_mul(RocDec::from(-1)), None);
+        assert_eq!(RocDec::MAX.checked_div(dec("0.5")), None);
+        assert_eq!(RocDec::from(1).checked_div(RocDec::from(0)), None);
+        assert_eq!(RocDec::MIN.checked_neg(), None);
+
+        assert!(dec("-0.5") < dec("0.25"));
+        assert!(RocDec::MIN < RocDec::MAX);
+    }
+
+    #[test]
+    #[should_panic(expected = "Decimal division by 0!")]
+    fn roc_dec_division_by_zero() {
+        let _ = RocDec::from(1) / RocDec::from(0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Decimal division overflow in numerator!")]
+    fn roc_dec_division_overflow_in_numerator() {
+        let _ = RocDec::MIN / RocDec::from(2);
+    }
+
+    #[test]
+    #[should_panic(expected = "Decimal division overflow in denominator!")]
+    fn roc_dec_division_overflow_in_denominator() {
+        let _ = RocDec::from(2) / RocDec::MIN;
+    }
+
+    #[test]
+    fn roc_dec_division_of_min_by_one() {
+        assert_eq!(RocDec::MIN / RocDec::from(1), RocDec::MIN);
+        assert_eq!(RocDec::from(1) / RocDec::MIN, RocDec::MIN);
+    }
+
+    #[test]
+    fn roc_dec_conversions() {
+        assert_eq!(RocDec::from_f64(25.5), Some(dec("25.5")));
+        assert_eq!(RocDec::from_f64(1e308), None);
+        assert_eq!(RocDec::from_f64(f64::NAN), None);
+        assert_eq!(dec("-25.5").to_f64(), -25.5);
+        assert_eq!(f64::from(dec("0.25")), 0.25);
+
+        assert_eq!(RocDec::from(u64::MAX), dec("18446744073709551615"));
+        assert_eq!(RocDec::from(i64::MIN), dec("-9223372036854775808"));
+        assert_eq!(RocDec::checked_from_i128(-3), Some(dec("-3")));
+        assert_eq!(RocDec::checked_from_i128(i128::MAX), None);
+        assert_eq!(RocDec::checked_from_u128(u128::MAX), None);
+
+        assert_eq!(dec("-7.9").whole_part(), -7);
+    }
+
+    #[test]
+    fn roc_dec_rounding() {
+        let cases = [
+            // value, round, floor, ceiling, trunc, fract
+            ("123.45", "123", "123", "124", "123", "0.45"),
+            ("-123.45", "-123", "-124", "-123", "-123", "-0.45"),
+            ("0.5", "1", "0", "1", "0", "0.5"),
+            ("-0.5", "-1", "-1", "0", "0", "-0.5"),
+            ("2", "2", "2", "2", "2", "0"),
+        ];
+
+        for (value, round, floor, ceiling, trunc, fract) in cases {
+            let value = dec(value);
+
+            assert_eq!(value.round(), dec(round), "round {value}");
+            assert_eq!(value.floor(), dec(floor), "floor {value}");
+            assert_eq!(value.ceiling(), dec(ceiling), "ceiling {value}");
+            assert_eq!(value.trunc(), dec(trunc), "trunc {value}");
+            assert_eq!(value.fract(), dec(fract), "fract {value}");
+        }
+
+        assert_eq!(dec("-2.5").round().whole_part(), -3);
+    }
+
+    #[test]
+    fn int128_arithmetic() {
+        let (a, b) = (I128::from(-7), I128::from(2));
+
+        assert_eq!(a + b, I128::from(-5));
+        assert_eq!(a - b, I128::from(-9));
+        assert_eq!(a * b, I128::from(-14));
+        assert_eq!(a / b, I128::from(-3));
+        assert_eq!(-a, I128::from(7));
+        assert_eq!(I128::from(i128::MIN).checked_div(I128::from(-1)), None);
+        assert_eq!(I128::from(i128::MIN).checked_neg(), None);
+
+        let (a, b) = (U128::from(7), U128::from(2));
+
+        assert_eq!(a + b, U128::from(9));
+        assert_eq!(a / b, U128::from(3));
+        assert_eq!(b.checked_sub(a), None);
+        assert_eq!(U128::from(u128::MAX).checked_mul(b), None);
+        assert_eq!(a.checked_div(U128::from(0)), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Integer addition overflowed!")]
+    fn int128_overflow_panics() {
+        let _ = U128::from(u128::MAX) + U128::from(1);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn dec_serde_roundtrip() {
+        let orig = RocDec::from_str("-1234.5678").unwrap();
+
+        let serialized = serde_json::to_string(&orig).expect("failed to serialize dec");
+        assert_eq!(serialized, "\"-1234.5678\"");
+
+        let deserialized =
+            serde_json::from_str::<RocDec>(&serialized).expect("failed to deserialize dec");
+        assert_eq!(orig, deserialized);
+
+        assert!(serde_json::from_str::<RocDec>("\"1.2.3\"").is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn int128_serde_roundtrip() {
+        let orig = (I128::from(i128::MIN), U128::from(u128::MAX));
+
+        let serialized = serde_json::to_string(&orig).expect("failed to serialize ints");
+        let deserialized =
+            serde_json::from_str::<(I128, U128)>(&serialized).expect("failed to deserialize ints");
+
+        assert_eq!(orig, deserialized);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn dict_serde_roundtrip() {
+        let orig = RocDict::from_iter([
+            (RocStr::from("one"), 1u64),
+            (RocStr::from("two"), 2),
+            (RocStr::from("three"), 3),
+        ]);
+
+        let serialized = serde_json::to_string(&orig).expect("failed to serialize dict");
+        assert_eq!(serialized, r#"{"one":1,"two":2,"three":3}"#);
+
+        let deserialized = serde_json::from_str::<RocDict<RocStr, u64>>(&serialized)
+            .expect("failed to deserialize dict");
+        assert_eq!(orig, deserialized);
+
+        let duplicated = serde_json::from_str::<RocDict<RocStr, u64>>(r#"{"a":1,"a":2}"#)
+            .expect("failed to deserialize dict");
+        assert_eq!(duplicated, RocDict::from_iter([(RocStr::from("a"), 2)]));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn set_serde_roundtrip() {
+        let orig = RocSet::from_iter([3i32, 1, 2]);
+
+        let serialized = serde_json::to_string(&orig).expect("failed to serialize set");
+        assert_eq!(serialized, "[3,1,2]");
+
+        let deserialized =
+            serde_json::from_str::<RocSet<i32>>(&serialized).expect("failed to deserialize set");
+        assert_eq!(orig, deserialized);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn result_and_box_serde_roundtrip() {
+        let ok: RocResult<RocBox<u8>, RocStr> = RocResult::ok(RocBox::new(42));
+        let err: RocResult<RocBox<u8>, RocStr> = RocResult::err(RocStr::from("oops"));
+        let orig = RocList::from_slice(&[ok, err]);
+
+        let serialized = serde_json::to_string(&orig).expect("failed to serialize results");
+        assert_eq!(serialized, r#"[{"Ok":42},{"Err":"oops"}]"#);
+
+        let deserialized =
+            serde_json::from_str::<RocList<RocResult<RocBox<u8>, RocStr>>>(&serialized)
+                .expect("failed to deserialize results");
+        assert_eq!(orig, deserialized);
+    }
+
+    #[test]
+    fn dict_layout() {
+        // The same size as Dict.roc's record: 2 lists, a U64, an F32 and a U8.
+        assert_eq!(
+            core::mem::size_of::<RocDict<u64, u64>>(),
+            2 * core::mem::size_of::<RocList<u64>>() + 16
+        );
+    }
+
+    #[test]
+    fn dict_insert_get_remove() {
+        let mut dict = RocDict::default();
+
+        for i in 0..1000u64 {
+            assert_eq!(dict.insert(i, i * 2), None);
+        }
+
+        assert_eq!(dict.len(), 1000);
+        assert_eq!(dict.insert(7, 0), Some(14));
+        assert_eq!(dict.get(&7), Some(&0));
+        assert_eq!(dict.get(&1000), None);
+
+        for i in (0..1000u64).step_by(3) {
+            assert_eq!(dict.remove(&i), Some(if i == 7 { 0 } else { i * 2 }));
+        }
+
+        assert_eq!(dict.remove(&0), None);
+        assert_eq!(dict.len(), 666);
+
+        for i in 0..1000u64 {
+            assert_eq!(dict.contains_key(&i), i % 3 != 0, "key {i}");
+        }
+
+        // Removing swaps the last entry into the removed one's place, like Dict.roc does.
+        let mut dict = RocDict::from_iter([(0u8, ()), (1, ()), (2, ()), (3, ())]);
+        dict.remove(&0);
+
+        assert_eq!(dict.iter_keys().collect::<Vec<_>>(), [&3, &1, &2]);
+    }
+
+    #[test]
+    fn dict_copies_shared_lists() {
+        let mut dict = RocDict::from_iter([(RocStr::from("a"), 1u8), (RocStr::from("b"), 2)]);
+        let original = dict.clone();
+
+        dict.insert(RocStr::from("a"), 3);
+        dict.remove(&RocStr::from("b"));
+        dict.extend([(RocStr::from("c"), 4)]);
+
+        assert_eq!(original.get(&RocStr::from("a")), Some(&1));
+        assert_eq!(original.get(&RocStr::from("b")), Some(&2));
+        assert_eq!(dict.get(&RocStr::from("a")), Some(&3));
+        assert_eq!(dict.get(&RocStr::from("b")), None);
+        assert_eq!(dict.len(), 2);
+    }
+
+    #[test]
+    fn set_insert_remove() {
+        let mut set = RocSet::with_capacity(2);
+
+        assert!(set.insert(RocStr::from("a long string that is not small")));
+        assert!(!set.insert(RocStr::from("a long string that is not small")));
+        assert!(set.insert(RocStr::from("short")));
+        assert!(set.contains(&RocStr::from("short")));
+        assert!(set.remove(&RocStr::from("short")));
+        assert!(!set.remove(&RocStr::from("short")));
+        assert_eq!(set.len(), 1);
+    }
+
     #[test]
     fn safe_send_no_copy() {
         let x = RocStr::from("This is a long string but still unique. Yay!!!");
@@ -3,7 +3,7 @@ use std::iter::repeat;
 
 use crate::value_store::ValueStore;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Frame {
     /// The function this frame belongs to
     pub fn_index: usize,
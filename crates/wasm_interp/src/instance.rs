@@ -9,8 +9,13 @@ use roc_wasm_module::{ExportType, WasmModule};
 use roc_wasm_module::{Value, ValueType};
 
 use crate::frame::Frame;
+use crate::profiler::Profiler;
+use crate::snapshot::Snapshot;
 use crate::value_store::ValueStore;
-use crate::{Error, ImportDispatcher};
+use crate::{CallError, ImportDispatcher, StackFrame, Trap, TrapReport};
+
+/// Default for `Instance::max_call_depth`
+pub const DEFAULT_MAX_CALL_DEPTH: usize = 100_000;
 
 #[derive(Debug)]
 pub enum Action {
@@ -18,18 +23,18 @@ pub enum Action {
     Break,
 }
 
-#[derive(Debug, Clone, Copy)]
-enum BlockType {
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum BlockType {
     Loop(usize),         // Loop block, with start address to loop back to
     Normal,              // Block created by `block` instruction
     Locals(usize),       // Special "block" for locals. Holds function index for debug
     FunctionBody(usize), // Special block surrounding the function body. Holds function index for debug
 }
 
-#[derive(Debug, Clone, Copy)]
-struct Block {
-    ty: BlockType,
-    vstack: usize,
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Block {
+    pub ty: BlockType,
+    pub vstack: usize,
 }
 
 #[derive(Debug, Clone)]
@@ -66,6 +71,14 @@ pub struct Instance<'a, I: ImportDispatcher> {
     import_arguments: Vec<'a, Value>,
     /// temporary storage for output using the --debug option
     debug_string: Option<String>,
+    /// Number of instructions the program may still execute before trapping. `None` means no limit.
+    pub fuel: Option<u64>,
+    /// Memory size limit for `memory.grow`, in pages. This is on top of any maximum declared in the module.
+    pub max_memory_pages: Option<u32>,
+    /// Maximum number of nested function calls before trapping
+    pub max_call_depth: usize,
+    /// Instruction and call counts per function, if profiling is enabled
+    pub profiler: Option<Profiler>,
 }
 
 impl<'a, I: ImportDispatcher> Instance<'a, I> {
@@ -95,6 +108,10 @@ impl<'a, I: ImportDispatcher> Instance<'a, I> {
             import_dispatcher,
             import_arguments: Vec::new_in(arena),
             debug_string: Some(String::new()),
+            fuel: None,
+            max_memory_pages: None,
+            max_call_depth: DEFAULT_MAX_CALL_DEPTH,
+            profiler: None,
         }
     }
 
@@ -163,10 +180,18 @@ impl<'a, I: ImportDispatcher> Instance<'a, I> {
             import_dispatcher,
             import_arguments: Vec::new_in(arena),
             debug_string,
+            fuel: None,
+            max_memory_pages: None,
+            max_call_depth: DEFAULT_MAX_CALL_DEPTH,
+            profiler: None,
         })
     }
 
-    pub fn call_export<A>(&mut self, fn_name: &str, arg_values: A) -> Result<Option<Value>, String>
+    pub fn call_export<A>(
+        &mut self,
+        fn_name: &str,
+        arg_values: A,
+    ) -> Result<Option<Value>, CallError>
     where
         A: IntoIterator<Item = Value>,
     {
@@ -177,9 +202,9 @@ impl<'a, I: ImportDispatcher> Instance<'a, I> {
         for (i, (value, expected_type)) in arg_values.into_iter().zip(param_type_iter).enumerate() {
             let actual_type = ValueType::from(value);
             if actual_type != expected_type {
-                return Err(format!(
+                return Err(CallError::Invalid(format!(
                     "Type mismatch on argument {i} of {fn_name}. Expected {expected_type:?} but got {value:?}"
-                ));
+                )));
             }
             self.value_store.push(value);
         }
@@ -192,7 +217,7 @@ impl<'a, I: ImportDispatcher> Instance<'a, I> {
         module: &WasmModule<'a>,
         fn_name: &str,
         arg_strings: &'a [&'a [u8]],
-    ) -> Result<Option<Value>, String> {
+    ) -> Result<Option<Value>, CallError> {
         // We have two different mechanisms for handling CLI arguments!
         // 1. Basic numbers:
         //      e.g. `roc_wasm_interp fibonacci 12`
@@ -295,7 +320,7 @@ impl<'a, I: ImportDispatcher> Instance<'a, I> {
         fn_index: usize,
         n_args: usize,
         return_type: Option<ValueType>,
-    ) -> Result<Option<Value>, String> {
+    ) -> Result<Option<Value>, CallError> {
         self.previous_frames.clear();
         self.blocks.clear();
         self.blocks.push(Block {
@@ -316,18 +341,38 @@ impl<'a, I: ImportDispatcher> Instance<'a, I> {
             ty: BlockType::FunctionBody(fn_index),
             vstack: self.value_store.depth(),
         });
+        if let Some(profiler) = self.profiler.as_mut() {
+            profiler.enter(fn_index);
+        }
 
+        self.run(module)
+    }
+
+    /// Execute instructions until the outermost call returns or the program traps
+    fn run(&mut self, module: &WasmModule<'a>) -> Result<Option<Value>, CallError> {
         loop {
+            let instruction_addr = self.program_counter;
             match self.execute_next_instruction(module) {
                 Ok(Action::Continue) => {}
                 Ok(Action::Break) => {
                     break;
                 }
-                Err(e) => {
-                    let file_offset = self.program_counter + module.code.section_offset as usize;
-                    let mut message = e.to_string_at(file_offset);
-                    self.debug_stack_trace(&mut message).unwrap();
-                    return Err(message);
+                Err(trap) => {
+                    self.program_counter = instruction_addr;
+                    if let Some(profiler) = self.profiler.as_mut() {
+                        profiler.unwind();
+                    }
+                    let report = TrapReport {
+                        trap,
+                        file_offset: instruction_addr + module.code.section_offset as usize,
+                        call_stack: self.call_stack(module),
+                    };
+                    if self.debug_string.is_some() {
+                        let mut message = report.to_string();
+                        self.debug_stack_trace(&mut message).unwrap();
+                        eprintln!("{message}");
+                    }
+                    return Err(CallError::Trap(report));
                 }
             };
         }
@@ -341,6 +386,148 @@ impl<'a, I: ImportDispatcher> Instance<'a, I> {
         Ok(return_value)
     }
 
+    /// Copy the runtime state of the instance, including any call in progress
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            memory: self.memory.to_vec(),
+            globals: self.globals.to_vec(),
+            values: self.value_store.iter().copied().collect(),
+            current_frame: self.current_frame.clone(),
+            previous_frames: self.previous_frames.to_vec(),
+            blocks: self.blocks.to_vec(),
+            program_counter: self.program_counter,
+        }
+    }
+
+    /// Replace the runtime state of the instance with a snapshot taken from an instance of the same module.
+    /// If the snapshot has a call in progress, continue it with `resume`.
+    pub fn restore(&mut self, snapshot: &Snapshot) -> Result<(), String> {
+        let page_size = MemorySection::PAGE_SIZE as usize;
+        let pages = snapshot.memory.len() / page_size;
+        if pages * page_size != snapshot.memory.len() {
+            return Err(format!(
+                "Snapshot memory size {} is not a whole number of pages",
+                snapshot.memory.len()
+            ));
+        }
+        if snapshot.globals.len() != self.globals.len() {
+            return Err(format!(
+                "Snapshot has {} globals but the module has {}",
+                snapshot.globals.len(),
+                self.globals.len()
+            ));
+        }
+        for (i, (new, old)) in snapshot.globals.iter().zip(self.globals.iter()).enumerate() {
+            if ValueType::from(*new) != ValueType::from(*old) {
+                return Err(format!(
+                    "Type mismatch on global {i}. Expected {:?} but got {new:?}",
+                    ValueType::from(*old)
+                ));
+            }
+        }
+        if snapshot.is_call_in_progress() {
+            let code_len = self.module.code.bytes.len();
+            let stack_depth = snapshot.values.len();
+            let mut prev_vstack = 0;
+            for (i, block) in snapshot.blocks.iter().enumerate() {
+                if block.vstack < prev_vstack || block.vstack > stack_depth {
+                    return Err(format!(
+                        "Snapshot block {i} starts at value stack height {}, which is not between {prev_vstack} and the stack depth {stack_depth}",
+                        block.vstack
+                    ));
+                }
+                prev_vstack = block.vstack;
+                if let BlockType::Loop(start_addr) = block.ty {
+                    if start_addr >= code_len {
+                        return Err(format!(
+                            "Snapshot block {i} loops back to {start_addr:#x}, which is outside the code section"
+                        ));
+                    }
+                }
+            }
+
+            let fn_count = self.import_count + self.module.code.function_count as usize;
+            let frames = snapshot
+                .previous_frames
+                .iter()
+                .chain(once(&snapshot.current_frame));
+            for frame in frames {
+                if frame.fn_index < self.import_count || frame.fn_index >= fn_count {
+                    return Err(format!(
+                        "Snapshot has a call frame for invalid function index {}",
+                        frame.fn_index
+                    ));
+                }
+                // Returning from the frame drops its locals block and everything after it
+                let locals_block = frame
+                    .body_block_index
+                    .checked_sub(1)
+                    .and_then(|index| snapshot.blocks.get(index));
+                if frame.body_block_index >= snapshot.blocks.len()
+                    || !matches!(
+                        locals_block,
+                        Some(Block {
+                            ty: BlockType::Locals(_),
+                            ..
+                        })
+                    )
+                {
+                    return Err(format!(
+                        "Snapshot has a call frame for function {} with invalid body block index {}",
+                        frame.fn_index, frame.body_block_index
+                    ));
+                }
+                if frame.locals_start > stack_depth
+                    || frame.locals_count > stack_depth - frame.locals_start
+                {
+                    return Err(format!(
+                        "Snapshot has a call frame for function {} whose {} locals at {} don't fit in the value stack depth {stack_depth}",
+                        frame.fn_index, frame.locals_count, frame.locals_start
+                    ));
+                }
+            }
+            if snapshot.program_counter >= self.module.code.bytes.len() {
+                return Err(format!(
+                    "Snapshot program counter {:#x} is outside the code section",
+                    snapshot.program_counter
+                ));
+            }
+        }
+
+        self.memory.clear();
+        self.memory.extend_from_slice(&snapshot.memory);
+        self.globals.clear();
+        self.globals.extend_from_slice(&snapshot.globals);
+        self.value_store.truncate(0);
+        self.value_store.extend(snapshot.values.iter().copied());
+        self.current_frame = snapshot.current_frame.clone();
+        self.previous_frames.clear();
+        self.previous_frames
+            .extend(snapshot.previous_frames.iter().cloned());
+        self.blocks.clear();
+        self.blocks.extend_from_slice(&snapshot.blocks);
+        self.program_counter = snapshot.program_counter;
+        Ok(())
+    }
+
+    /// Continue a call that was interrupted, after adding more `fuel` or restoring a `Snapshot`.
+    /// Only traps that happen before an instruction starts executing can be resumed, like running out of fuel.
+    /// After other traps, the instruction that trapped may have been partly executed.
+    pub fn resume(&mut self) -> Result<Option<Value>, CallError> {
+        if self.blocks.is_empty() {
+            return Err(CallError::Invalid(
+                "There is no call in progress to resume".into(),
+            ));
+        }
+        if let Some(profiler) = self.profiler.as_mut() {
+            let frames = self.previous_frames.iter().chain(once(&self.current_frame));
+            for frame in frames {
+                profiler.enter(frame.fn_index);
+            }
+        }
+        self.run(self.module)
+    }
+
     fn fetch_immediate_u32(&mut self, module: &WasmModule<'a>) -> u32 {
         let x = u32::parse((), &module.code.bytes, &mut self.program_counter).unwrap();
         if let Some(debug_string) = self.debug_string.as_mut() {
@@ -371,6 +558,10 @@ impl<'a, I: ImportDispatcher> Instance<'a, I> {
         };
         self.value_store.truncate(new_stack_depth);
 
+        if let Some(profiler) = self.profiler.as_mut() {
+            profiler.exit();
+        }
+
         // Resume executing at the next instruction in the caller function
         let new_block_len = locals_block_index; // don't need a -1 because one is a length and the other is an index!
         self.blocks.truncate(new_block_len);
@@ -387,23 +578,21 @@ impl<'a, I: ImportDispatcher> Instance<'a, I> {
         }
     }
 
-    fn get_load_address(&mut self, module: &WasmModule<'a>) -> Result<u32, Error> {
+    fn get_load_address(&mut self, module: &WasmModule<'a>, size: u32) -> Result<usize, Trap> {
         // Alignment is not used in the execution steps from the spec! Maybe it's just an optimization hint?
         // https://webassembly.github.io/spec/core/exec/instructions.html#memory-instructions
         // Also note: in the text format we can specify the useless `align=` but not the useful `offset=`!
         let _alignment = self.fetch_immediate_u32(module);
         let offset = self.fetch_immediate_u32(module);
         let base_addr = self.value_store.pop_u32()?;
-        let addr = base_addr + offset;
-        let memory_size = self.memory.len() as u32;
-        if addr >= memory_size {
-            Err(Error::MemoryAccessOutOfBounds(addr, memory_size))
-        } else {
-            Ok(addr)
-        }
+        self.check_memory_access(base_addr as u64 + offset as u64, size)
     }
 
-    fn get_store_addr_value(&mut self, module: &WasmModule<'a>) -> Result<(usize, Value), Error> {
+    fn get_store_addr_value(
+        &mut self,
+        module: &WasmModule<'a>,
+        size: u32,
+    ) -> Result<(usize, Value), Trap> {
         // Alignment is not used in the execution steps from the spec! Maybe it's just an optimization hint?
         // https://webassembly.github.io/spec/core/exec/instructions.html#memory-instructions
         // Also note: in the text format we can specify the useless `align=` but not the useful `offset=`!
@@ -411,12 +600,16 @@ impl<'a, I: ImportDispatcher> Instance<'a, I> {
         let offset = self.fetch_immediate_u32(module);
         let value = self.value_store.pop();
         let base_addr = self.value_store.pop_u32()?;
-        let addr = base_addr + offset;
+        let addr = self.check_memory_access(base_addr as u64 + offset as u64, size)?;
+        Ok((addr, value))
+    }
+
+    fn check_memory_access(&self, addr: u64, size: u32) -> Result<usize, Trap> {
         let memory_size = self.memory.len() as u32;
-        if addr >= memory_size {
-            Err(Error::MemoryAccessOutOfBounds(addr, memory_size))
+        if addr + size as u64 > memory_size as u64 {
+            Err(Trap::MemoryAccessOutOfBounds(addr, size, memory_size))
         } else {
-            Ok((addr as usize, value))
+            Ok(addr as usize)
         }
     }
 
@@ -488,7 +681,7 @@ impl<'a, I: ImportDispatcher> Instance<'a, I> {
         expected_signature: Option<u32>,
         fn_index: usize,
         module: &WasmModule<'a>,
-    ) -> Result<(), Error> {
+    ) -> Result<(), Trap> {
         // self.debug_values_and_blocks(&format!("start do_call {}", fn_index));
 
         let (signature_index, opt_import) = if fn_index < self.import_count {
@@ -506,10 +699,9 @@ impl<'a, I: ImportDispatcher> Instance<'a, I> {
         };
 
         if let Some(expected) = expected_signature {
-            assert_eq!(
-                expected, signature_index,
-                "Indirect function call failed. Expected signature {expected} but found {signature_index}",
-            );
+            if expected != signature_index {
+                return Err(Trap::IndirectCallTypeMismatch(expected, signature_index));
+            }
         }
 
         let (arg_type_iter, ret_type) = module.types.look_up(signature_index);
@@ -526,11 +718,15 @@ impl<'a, I: ImportDispatcher> Instance<'a, I> {
                 let arg = self.value_store.pop();
                 let actual = ValueType::from(arg);
                 if actual != expected {
-                    return Err(Error::Type(expected, actual));
+                    return Err(Trap::Type(expected, actual));
                 }
                 self.import_arguments[i] = arg;
             }
 
+            if let Some(profiler) = self.profiler.as_mut() {
+                profiler.call_import(fn_index);
+            }
+
             let optional_return_val = self.import_dispatcher.dispatch(
                 import.module,
                 import.name,
@@ -544,6 +740,12 @@ impl<'a, I: ImportDispatcher> Instance<'a, I> {
                 write!(debug_string, " {}.{}", import.module, import.name).unwrap();
             }
         } else {
+            if self.previous_frames.len() >= self.max_call_depth {
+                return Err(Trap::StackExhausted);
+            }
+            if let Some(profiler) = self.profiler.as_mut() {
+                profiler.enter(fn_index);
+            }
             let return_addr = self.program_counter;
             // set PC to start of function bytes
             let internal_fn_index = fn_index - self.import_count;
@@ -603,9 +805,19 @@ impl<'a, I: ImportDispatcher> Instance<'a, I> {
     pub(crate) fn execute_next_instruction(
         &mut self,
         module: &WasmModule<'a>,
-    ) -> Result<Action, Error> {
+    ) -> Result<Action, Trap> {
         use OpCode::*;
 
+        if let Some(fuel) = self.fuel.as_mut() {
+            if *fuel == 0 {
+                return Err(Trap::FuelExhausted);
+            }
+            *fuel -= 1;
+        }
+        if let Some(profiler) = self.profiler.as_mut() {
+            profiler.instruction();
+        }
+
         let file_offset = self.program_counter as u32 + module.code.section_offset;
         let op_code = OpCode::from(module.code.bytes[self.program_counter]);
         self.program_counter += 1;
@@ -620,7 +832,7 @@ impl<'a, I: ImportDispatcher> Instance<'a, I> {
 
         match op_code {
             UNREACHABLE => {
-                return Err(Error::UnreachableOp);
+                return Err(Trap::UnreachableOp);
             }
             NOP => {}
             BLOCK => {
@@ -746,11 +958,10 @@ impl<'a, I: ImportDispatcher> Instance<'a, I> {
                 );
 
                 // Dereference the function pointer (look up the element index in the function table)
-                let fn_index = module.element.lookup(element_index).unwrap_or_else(|| {
-                    panic!(
-                        "Indirect function call failed. There is no function with element index {element_index}"
-                    )
-                });
+                let fn_index = module
+                    .element
+                    .lookup(element_index)
+                    .ok_or(Trap::UndefinedElement(element_index))?;
 
                 self.do_call(Some(expected_signature), fn_index as usize, module)?;
             }
@@ -764,7 +975,7 @@ impl<'a, I: ImportDispatcher> Instance<'a, I> {
                 let actual = ValueType::from(val2);
                 let expected = ValueType::from(val1);
                 if actual != expected {
-                    return Err(Error::Type(expected, actual));
+                    return Err(Trap::Type(expected, actual));
                 }
                 let result = if c != 0 { val1 } else { val2 };
                 self.value_store.push(result);
@@ -795,150 +1006,150 @@ impl<'a, I: ImportDispatcher> Instance<'a, I> {
                 self.globals[index as usize] = self.value_store.pop();
             }
             I32LOAD => {
-                let addr = self.get_load_address(module)? as usize;
+                let addr = self.get_load_address(module, 4)?;
                 let mut bytes = [0; 4];
                 bytes.copy_from_slice(&self.memory[addr..][..4]);
                 let value = i32::from_le_bytes(bytes);
                 self.value_store.push(Value::I32(value));
             }
             I64LOAD => {
-                let addr = self.get_load_address(module)? as usize;
+                let addr = self.get_load_address(module, 8)?;
                 let mut bytes = [0; 8];
                 bytes.copy_from_slice(&self.memory[addr..][..8]);
                 let value = i64::from_le_bytes(bytes);
                 self.value_store.push(Value::I64(value));
             }
             F32LOAD => {
-                let addr = self.get_load_address(module)? as usize;
+                let addr = self.get_load_address(module, 4)?;
                 let mut bytes = [0; 4];
                 bytes.copy_from_slice(&self.memory[addr..][..4]);
                 let value = f32::from_le_bytes(bytes);
                 self.value_store.push(Value::F32(value));
             }
             F64LOAD => {
-                let addr = self.get_load_address(module)? as usize;
+                let addr = self.get_load_address(module, 8)?;
                 let mut bytes = [0; 8];
                 bytes.copy_from_slice(&self.memory[addr..][..8]);
                 let value = f64::from_le_bytes(bytes);
                 self.value_store.push(Value::F64(value));
             }
             I32LOAD8S => {
-                let addr = self.get_load_address(module)? as usize;
+                let addr = self.get_load_address(module, 1)?;
                 let mut bytes = [0; 1];
                 bytes.copy_from_slice(&self.memory[addr..][..1]);
                 let value = i8::from_le_bytes(bytes);
                 self.value_store.push(Value::I32(value as i32));
             }
             I32LOAD8U => {
-                let addr = self.get_load_address(module)? as usize;
+                let addr = self.get_load_address(module, 1)?;
                 let value = self.memory[addr];
                 self.value_store.push(Value::I32(value as i32));
             }
             I32LOAD16S => {
-                let addr = self.get_load_address(module)? as usize;
+                let addr = self.get_load_address(module, 2)?;
                 let mut bytes = [0; 2];
                 bytes.copy_from_slice(&self.memory[addr..][..2]);
                 let value = i16::from_le_bytes(bytes);
                 self.value_store.push(Value::I32(value as i32));
             }
             I32LOAD16U => {
-                let addr = self.get_load_address(module)? as usize;
+                let addr = self.get_load_address(module, 2)?;
                 let mut bytes = [0; 2];
                 bytes.copy_from_slice(&self.memory[addr..][..2]);
                 let value = u16::from_le_bytes(bytes);
                 self.value_store.push(Value::I32(value as i32));
             }
             I64LOAD8S => {
-                let addr = self.get_load_address(module)? as usize;
+                let addr = self.get_load_address(module, 1)?;
                 let mut bytes = [0; 1];
                 bytes.copy_from_slice(&self.memory[addr..][..1]);
                 let value = i8::from_le_bytes(bytes);
                 self.value_store.push(Value::I64(value as i64));
             }
             I64LOAD8U => {
-                let addr = self.get_load_address(module)? as usize;
+                let addr = self.get_load_address(module, 1)?;
                 let value = self.memory[addr];
                 self.value_store.push(Value::I64(value as i64));
             }
             I64LOAD16S => {
-                let addr = self.get_load_address(module)? as usize;
+                let addr = self.get_load_address(module, 2)?;
                 let mut bytes = [0; 2];
                 bytes.copy_from_slice(&self.memory[addr..][..2]);
                 let value = i16::from_le_bytes(bytes);
                 self.value_store.push(Value::I64(value as i64));
             }
             I64LOAD16U => {
-                let addr = self.get_load_address(module)? as usize;
+                let addr = self.get_load_address(module, 2)?;
                 let mut bytes = [0; 2];
                 bytes.copy_from_slice(&self.memory[addr..][..2]);
                 let value = u16::from_le_bytes(bytes);
                 self.value_store.push(Value::I64(value as i64));
             }
             I64LOAD32S => {
-                let addr = self.get_load_address(module)? as usize;
+                let addr = self.get_load_address(module, 4)?;
                 let mut bytes = [0; 4];
                 bytes.copy_from_slice(&self.memory[addr..][..4]);
                 let value = i32::from_le_bytes(bytes);
                 self.value_store.push(Value::I64(value as i64));
             }
             I64LOAD32U => {
-                let addr = self.get_load_address(module)? as usize;
+                let addr = self.get_load_address(module, 4)?;
                 let mut bytes = [0; 4];
                 bytes.copy_from_slice(&self.memory[addr..][..4]);
                 let value = u32::from_le_bytes(bytes);
                 self.value_store.push(Value::I64(value as i64));
             }
             I32STORE => {
-                let (addr, value) = self.get_store_addr_value(module)?;
-                let unwrapped = value.expect_i32().map_err(Error::from)?;
+                let (addr, value) = self.get_store_addr_value(module, 4)?;
+                let unwrapped = value.expect_i32().map_err(Trap::from)?;
                 let target = &mut self.memory[addr..][..4];
                 target.copy_from_slice(&unwrapped.to_le_bytes());
             }
             I64STORE => {
-                let (addr, value) = self.get_store_addr_value(module)?;
-                let unwrapped = value.expect_i64().map_err(Error::from)?;
+                let (addr, value) = self.get_store_addr_value(module, 8)?;
+                let unwrapped = value.expect_i64().map_err(Trap::from)?;
                 let target = &mut self.memory[addr..][..8];
                 target.copy_from_slice(&unwrapped.to_le_bytes());
             }
             F32STORE => {
-                let (addr, value) = self.get_store_addr_value(module)?;
-                let unwrapped = value.expect_f32().map_err(Error::from)?;
+                let (addr, value) = self.get_store_addr_value(module, 4)?;
+                let unwrapped = value.expect_f32().map_err(Trap::from)?;
                 let target = &mut self.memory[addr..][..4];
                 target.copy_from_slice(&unwrapped.to_le_bytes());
             }
             F64STORE => {
-                let (addr, value) = self.get_store_addr_value(module)?;
-                let unwrapped = value.expect_f64().map_err(Error::from)?;
+                let (addr, value) = self.get_store_addr_value(module, 8)?;
+                let unwrapped = value.expect_f64().map_err(Trap::from)?;
                 let target = &mut self.memory[addr..][..8];
                 target.copy_from_slice(&unwrapped.to_le_bytes());
             }
             I32STORE8 => {
-                let (addr, value) = self.get_store_addr_value(module)?;
-                let unwrapped = value.expect_i32().map_err(Error::from)?;
+                let (addr, value) = self.get_store_addr_value(module, 1)?;
+                let unwrapped = value.expect_i32().map_err(Trap::from)?;
                 let target = &mut self.memory[addr..][..1];
                 target.copy_from_slice(&unwrapped.to_le_bytes()[..1]);
             }
             I32STORE16 => {
-                let (addr, value) = self.get_store_addr_value(module)?;
-                let unwrapped = value.expect_i32().map_err(Error::from)?;
+                let (addr, value) = self.get_store_addr_value(module, 2)?;
+                let unwrapped = value.expect_i32().map_err(Trap::from)?;
                 let target = &mut self.memory[addr..][..2];
                 target.copy_from_slice(&unwrapped.to_le_bytes()[..2]);
             }
             I64STORE8 => {
-                let (addr, value) = self.get_store_addr_value(module)?;
-                let unwrapped = value.expect_i64().map_err(Error::from)?;
+                let (addr, value) = self.get_store_addr_value(module, 1)?;
+                let unwrapped = value.expect_i64().map_err(Trap::from)?;
                 let target = &mut self.memory[addr..][..1];
                 target.copy_from_slice(&unwrapped.to_le_bytes()[..1]);
             }
             I64STORE16 => {
-                let (addr, value) = self.get_store_addr_value(module)?;
-                let unwrapped = value.expect_i64().map_err(Error::from)?;
+                let (addr, value) = self.get_store_addr_value(module, 2)?;
+                let unwrapped = value.expect_i64().map_err(Trap::from)?;
                 let target = &mut self.memory[addr..][..2];
                 target.copy_from_slice(&unwrapped.to_le_bytes()[..2]);
             }
             I64STORE32 => {
-                let (addr, value) = self.get_store_addr_value(module)?;
-                let unwrapped = value.expect_i64().map_err(Error::from)?;
+                let (addr, value) = self.get_store_addr_value(module, 4)?;
+                let unwrapped = value.expect_i64().map_err(Trap::from)?;
                 let target = &mut self.memory[addr..][..4];
                 target.copy_from_slice(&unwrapped.to_le_bytes()[..4]);
             }
@@ -951,16 +1162,20 @@ impl<'a, I: ImportDispatcher> Instance<'a, I> {
             GROWMEMORY => {
                 let memory_index = self.fetch_immediate_u32(module);
                 assert_eq!(memory_index, 0);
-                let old_bytes = self.memory.len() as u32;
-                let old_pages = old_bytes / MemorySection::PAGE_SIZE;
-                let grow_pages = self.value_store.pop_u32()?;
-                let grow_bytes = grow_pages * MemorySection::PAGE_SIZE;
+                let page_size = MemorySection::PAGE_SIZE as u64;
+                let old_bytes = self.memory.len() as u64;
+                let old_pages = old_bytes / page_size;
+                let grow_pages = self.value_store.pop_u32()? as u64;
+                let grow_bytes = grow_pages * page_size;
                 let new_bytes = old_bytes + grow_bytes;
 
-                let success = match module.memory.max_bytes().unwrap() {
-                    Some(max_bytes) => new_bytes <= max_bytes,
-                    None => true,
-                };
+                let module_max_bytes = module.memory.max_bytes().unwrap().map(u64::from);
+                let instance_max_bytes = self.max_memory_pages.map(|p| p as u64 * page_size);
+                let success = [module_max_bytes, instance_max_bytes]
+                    .into_iter()
+                    .flatten()
+                    .all(|max_bytes| new_bytes <= max_bytes)
+                    && new_bytes <= u32::MAX as u64;
                 if success {
                     self.memory
                         .extend(iter::repeat(0).take(grow_bytes as usize));
@@ -984,6 +1199,8 @@ impl<'a, I: ImportDispatcher> Instance<'a, I> {
                             // in future versions of WebAssembly this byte may be used to index additional memories
                             self.program_counter += 1 + 2;
 
+                            self.check_memory_access(source as u64, size as u32)?;
+                            self.check_memory_access(destination as u64, size as u32)?;
                             self.memory.copy_within(source..source + size, destination)
                         }
                         MemoryInstruction::MemoryFill => {
@@ -995,6 +1212,7 @@ impl<'a, I: ImportDispatcher> Instance<'a, I> {
                             // in future versions of WebAssembly this byte may be used to index additional memories
                             self.program_counter += 1 + 1;
 
+                            self.check_memory_access(destination as u64, size as u32)?;
                             self.memory[destination..][..size].fill(byte_value);
                         }
                     },
@@ -1264,21 +1482,34 @@ impl<'a, I: ImportDispatcher> Instance<'a, I> {
             I32DIVS => {
                 let arg2 = self.value_store.pop_i32()?;
                 let arg1 = self.value_store.pop_i32()?;
-                self.value_store.push(Value::from(arg1.wrapping_div(arg2)));
+                if arg2 == 0 {
+                    return Err(Trap::DivisionByZero);
+                }
+                let quotient = arg1.checked_div(arg2).ok_or(Trap::IntegerOverflow)?;
+                self.value_store.push(Value::from(quotient));
             }
             I32DIVU => {
                 let arg2 = self.value_store.pop_u32()?;
                 let arg1 = self.value_store.pop_u32()?;
+                if arg2 == 0 {
+                    return Err(Trap::DivisionByZero);
+                }
                 self.value_store.push(Value::from(arg1.wrapping_div(arg2)));
             }
             I32REMS => {
                 let arg2 = self.value_store.pop_i32()?;
                 let arg1 = self.value_store.pop_i32()?;
+                if arg2 == 0 {
+                    return Err(Trap::DivisionByZero);
+                }
                 self.value_store.push(Value::from(arg1.wrapping_rem(arg2)));
             }
             I32REMU => {
                 let arg2 = self.value_store.pop_u32()?;
                 let arg1 = self.value_store.pop_u32()?;
+                if arg2 == 0 {
+                    return Err(Trap::DivisionByZero);
+                }
                 self.value_store.push(Value::from(arg1.wrapping_rem(arg2)));
             }
             I32AND => {
@@ -1360,21 +1591,34 @@ impl<'a, I: ImportDispatcher> Instance<'a, I> {
             I64DIVS => {
                 let arg2 = self.value_store.pop_i64()?;
                 let arg1 = self.value_store.pop_i64()?;
-                self.value_store.push(Value::from(arg1.wrapping_div(arg2)));
+                if arg2 == 0 {
+                    return Err(Trap::DivisionByZero);
+                }
+                let quotient = arg1.checked_div(arg2).ok_or(Trap::IntegerOverflow)?;
+                self.value_store.push(Value::from(quotient));
             }
             I64DIVU => {
                 let arg2 = self.value_store.pop_u64()?;
                 let arg1 = self.value_store.pop_u64()?;
+                if arg2 == 0 {
+                    return Err(Trap::DivisionByZero);
+                }
                 self.value_store.push(Value::from(arg1.wrapping_div(arg2)));
             }
             I64REMS => {
                 let arg2 = self.value_store.pop_i64()?;
                 let arg1 = self.value_store.pop_i64()?;
+                if arg2 == 0 {
+                    return Err(Trap::DivisionByZero);
+                }
                 self.value_store.push(Value::from(arg1.wrapping_rem(arg2)));
             }
             I64REMU => {
                 let arg2 = self.value_store.pop_u64()?;
                 let arg1 = self.value_store.pop_u64()?;
+                if arg2 == 0 {
+                    return Err(Trap::DivisionByZero);
+                }
                 self.value_store.push(Value::from(arg1.wrapping_rem(arg2)));
             }
             I64AND => {
@@ -1605,29 +1849,29 @@ impl<'a, I: ImportDispatcher> Instance<'a, I> {
             }
             I32TRUNCSF32 => {
                 let arg = self.value_store.pop_f32()?;
-                if arg < i32::MIN as f32 || arg > i32::MAX as f32 {
-                    panic!("Cannot truncate {arg} from F32 to I32");
+                if !trunc_fits(arg as f64, -(2f64.powi(31))..2f64.powi(31)) {
+                    return Err(Trap::InvalidConversionToInteger);
                 }
                 self.value_store.push(Value::I32(arg as i32));
             }
             I32TRUNCUF32 => {
                 let arg = self.value_store.pop_f32()?;
-                if arg < u32::MIN as f32 || arg > u32::MAX as f32 {
-                    panic!("Cannot truncate {arg} from F32 to unsigned I32");
+                if !trunc_fits(arg as f64, 0.0..2f64.powi(32)) {
+                    return Err(Trap::InvalidConversionToInteger);
                 }
                 self.value_store.push(Value::from(arg as u32));
             }
             I32TRUNCSF64 => {
                 let arg = self.value_store.pop_f64()?;
-                if arg < i32::MIN as f64 || arg > i32::MAX as f64 {
-                    panic!("Cannot truncate {arg} from F64 to I32");
+                if !trunc_fits(arg, -(2f64.powi(31))..2f64.powi(31)) {
+                    return Err(Trap::InvalidConversionToInteger);
                 }
                 self.value_store.push(Value::I32(arg as i32));
             }
             I32TRUNCUF64 => {
                 let arg = self.value_store.pop_f64()?;
-                if arg < u32::MIN as f64 || arg > u32::MAX as f64 {
-                    panic!("Cannot truncate {arg} from F64 to unsigned I32");
+                if !trunc_fits(arg, 0.0..2f64.powi(32)) {
+                    return Err(Trap::InvalidConversionToInteger);
                 }
                 self.value_store.push(Value::from(arg as u32));
             }
@@ -1641,29 +1885,29 @@ impl<'a, I: ImportDispatcher> Instance<'a, I> {
             }
             I64TRUNCSF32 => {
                 let arg = self.value_store.pop_f32()?;
-                if arg < i64::MIN as f32 || arg > i64::MAX as f32 {
-                    panic!("Cannot truncate {arg} from F32 to I64");
+                if !trunc_fits(arg as f64, -(2f64.powi(63))..2f64.powi(63)) {
+                    return Err(Trap::InvalidConversionToInteger);
                 }
                 self.value_store.push(Value::I64(arg as i64));
             }
             I64TRUNCUF32 => {
                 let arg = self.value_store.pop_f32()?;
-                if arg < u64::MIN as f32 || arg > u64::MAX as f32 {
-                    panic!("Cannot truncate {arg} from F32 to unsigned I64");
+                if !trunc_fits(arg as f64, 0.0..2f64.powi(64)) {
+                    return Err(Trap::InvalidConversionToInteger);
                 }
                 self.value_store.push(Value::from(arg as u64));
             }
             I64TRUNCSF64 => {
                 let arg = self.value_store.pop_f64()?;
-                if arg < i64::MIN as f64 || arg > i64::MAX as f64 {
-                    panic!("Cannot truncate {arg} from F64 to I64");
+                if !trunc_fits(arg, -(2f64.powi(63))..2f64.powi(63)) {
+                    return Err(Trap::InvalidConversionToInteger);
                 }
                 self.value_store.push(Value::I64(arg as i64));
             }
             I64TRUNCUF64 => {
                 let arg = self.value_store.pop_f64()?;
-                if arg < u64::MIN as f64 || arg > u64::MAX as f64 {
-                    panic!("Cannot truncate {arg} from F64 to unsigned I64");
+                if !trunc_fits(arg, 0.0..2f64.powi(64)) {
+                    return Err(Trap::InvalidConversionToInteger);
                 }
                 self.value_store.push(Value::from(arg as u64));
             }
@@ -1896,6 +2140,33 @@ impl<'a, I: ImportDispatcher> Instance<'a, I> {
         Ok(())
     }
 
+    /// Wasm call stack for a trap report, innermost frame first
+    fn call_stack(&self, module: &WasmModule<'a>) -> std::vec::Vec<StackFrame> {
+        let section_offset = module.code.section_offset as usize;
+        let frames = self.previous_frames.iter().chain(once(&self.current_frame));
+
+        // Callers are at the CALL instruction before the return address of the next frame in
+        let mut next_addr = self.program_counter;
+        let mut call_stack = std::vec::Vec::with_capacity(self.previous_frames.len() + 1);
+        for frame in frames.rev() {
+            let fn_name = module
+                .names
+                .function_names
+                .iter()
+                .find(|(idx, _)| *idx == frame.fn_index as u32)
+                .map(|(_, name)| name.to_string());
+            call_stack.push(StackFrame {
+                fn_index: frame.fn_index,
+                fn_name,
+                file_offset: next_addr + section_offset,
+            });
+            if call_stack.len() <= self.previous_frames.len() {
+                next_addr = self.debug_return_addr_to_call_addr(frame.return_addr);
+            }
+        }
+        call_stack
+    }
+
     // Call address is more intuitive than the return address in the stack trace. Search backward for it.
     fn debug_return_addr_to_call_addr(&self, return_addr: usize) -> usize {
         // return_addr is pointing at the next instruction after the CALL/CALLINDIRECT.
@@ -1913,3 +2184,10 @@ impl<'a, I: ImportDispatcher> Instance<'a, I> {
         call_addr
     }
 }
+
+/// Whether a float truncated toward zero lands in `range`, as the Wasm spec requires for the
+/// trapping float-to-int conversions. `f32` arguments widen to `f64` exactly, and all the bounds
+/// are powers of two, so nothing gets rounded on the way. NaN is never in range.
+fn trunc_fits(arg: f64, range: std::ops::Range<f64>) -> bool {
+    range.contains(&arg.trunc())
+}
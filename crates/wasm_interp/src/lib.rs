@@ -1,5 +1,7 @@
 mod frame;
 mod instance;
+mod profiler;
+mod snapshot;
 #[cfg(test)]
 mod tests;
 
@@ -7,11 +9,14 @@ mod value_store;
 pub mod wasi;
 
 // Main external interface
-pub use instance::Instance;
-pub use wasi::{WasiDispatcher, WasiFile};
+pub use instance::{Instance, DEFAULT_MAX_CALL_DEPTH};
+pub use profiler::{FunctionProfile, Profiler};
+pub use snapshot::Snapshot;
+pub use wasi::{WasiClock, WasiDispatcher, WasiFile};
 
 pub use roc_wasm_module::Value;
 use roc_wasm_module::ValueType;
+use std::fmt;
 
 pub trait ImportDispatcher {
     /// Dispatch a call from WebAssembly to your own code, based on module and function name.
@@ -60,44 +65,149 @@ impl<'a> ImportDispatcher for DefaultImportDispatcher<'a> {
     }
 }
 
-/// Errors that can happen while interpreting the program
-/// All of these cause a WebAssembly stack trace to be dumped
-#[derive(Debug, PartialEq)]
-pub(crate) enum Error {
+/// Reasons the program can trap (stop abnormally) while it's being interpreted
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Trap {
+    /// Expected type, actual type
     Type(ValueType, ValueType),
     StackEmpty,
-    MemoryAccessOutOfBounds(u32, u32),
+    /// Address, number of bytes accessed, memory size
+    MemoryAccessOutOfBounds(u64, u32, u32),
     UnreachableOp,
+    DivisionByZero,
+    /// Signed division of the smallest integer by -1, whose result doesn't fit
+    IntegerOverflow,
+    /// A float to int conversion where the float was NaN or out of range
+    InvalidConversionToInteger,
+    /// Expected signature index, actual signature index
+    IndirectCallTypeMismatch(u32, u32),
+    /// Element index
+    UndefinedElement(u32),
+    /// The call depth exceeded `Instance::max_call_depth`
+    StackExhausted,
+    /// The program used up all of the fuel in `Instance::fuel`
+    FuelExhausted,
 }
 
-impl Error {
+impl Trap {
     pub fn to_string_at(&self, file_offset: usize) -> String {
         match self {
-            Error::Type(expected, actual) => {
+            Trap::Type(expected, actual) => {
                 format!(
                     "ERROR: I found a type mismatch at file offset {file_offset:#x}. Expected {expected:?}, but found {actual:?}.\n"
                 )
             }
-            Error::StackEmpty => {
+            Trap::StackEmpty => {
                 format!(
                     "ERROR: I tried to pop a value from the stack at file offset {file_offset:#x}, but it was empty.\n"
                 )
             }
-            Error::MemoryAccessOutOfBounds(addr, memory_size) => {
+            Trap::MemoryAccessOutOfBounds(addr, size, memory_size) => {
                 format!(
-                    "ERROR: A Wasm instruction at file offset {:#x} tried to access memory at {:#x} but the maximum address is {:#x}\n",
-                    file_offset, addr, memory_size-1
+                    "ERROR: A Wasm instruction at file offset {file_offset:#x} tried to access {size} bytes of memory at {addr:#x} but the memory size is {memory_size:#x}\n"
                 )
             }
-            Error::UnreachableOp => {
+            Trap::UnreachableOp => {
                 format!("WebAssembly `unreachable` instruction at file offset {file_offset:#x}.\n")
             }
+            Trap::DivisionByZero => {
+                format!("ERROR: Integer division by zero at file offset {file_offset:#x}.\n")
+            }
+            Trap::IntegerOverflow => {
+                format!("ERROR: Integer overflow in division at file offset {file_offset:#x}.\n")
+            }
+            Trap::InvalidConversionToInteger => {
+                format!(
+                    "ERROR: A float at file offset {file_offset:#x} was NaN or too big to convert to an integer.\n"
+                )
+            }
+            Trap::IndirectCallTypeMismatch(expected, actual) => {
+                format!(
+                    "ERROR: Indirect function call failed at file offset {file_offset:#x}. Expected signature {expected} but found {actual}.\n"
+                )
+            }
+            Trap::UndefinedElement(element_index) => {
+                format!(
+                    "ERROR: Indirect function call failed at file offset {file_offset:#x}. There is no function with element index {element_index}.\n"
+                )
+            }
+            Trap::StackExhausted => {
+                format!("ERROR: Call stack exhausted at file offset {file_offset:#x}.\n")
+            }
+            Trap::FuelExhausted => {
+                format!("ERROR: The program ran out of fuel at file offset {file_offset:#x}.\n")
+            }
         }
     }
 }
 
-impl From<(ValueType, ValueType)> for Error {
+impl From<(ValueType, ValueType)> for Trap {
     fn from((expected, actual): (ValueType, ValueType)) -> Self {
-        Error::Type(expected, actual)
+        Trap::Type(expected, actual)
+    }
+}
+
+/// One function call in the Wasm call stack at the point where the program trapped
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StackFrame {
+    pub fn_index: usize,
+    /// The function's name from the module's NameSection, if it has one
+    pub fn_name: Option<String>,
+    /// File offset of the instruction being executed. For callers, this is the call instruction.
+    pub file_offset: usize,
+}
+
+/// A trap, with the location where it happened
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrapReport {
+    pub trap: Trap,
+    /// File offset of the instruction that trapped
+    pub file_offset: usize,
+    /// Innermost frame first
+    pub call_stack: std::vec::Vec<StackFrame>,
+}
+
+impl fmt::Display for TrapReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.trap.to_string_at(self.file_offset))?;
+        writeln!(f, "Wasm call stack:")?;
+        for frame in self.call_stack.iter() {
+            let fn_name = frame.fn_name.as_deref().unwrap_or("");
+            writeln!(
+                f,
+                "  func[{}]  {}  at {:#x}",
+                frame.fn_index, fn_name, frame.file_offset
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Errors returned from calling a Wasm function
+#[derive(Debug, Clone, PartialEq)]
+pub enum CallError {
+    /// The function couldn't be called, e.g. it doesn't exist, or the arguments didn't match its signature
+    Invalid(String),
+    Trap(TrapReport),
+}
+
+impl fmt::Display for CallError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CallError::Invalid(message) => write!(f, "{message}"),
+            CallError::Trap(report) => write!(f, "{report}"),
+        }
+    }
+}
+
+impl From<String> for CallError {
+    fn from(message: String) -> Self {
+        CallError::Invalid(message)
+    }
+}
+
+impl From<CallError> for String {
+    fn from(e: CallError) -> Self {
+        e.to_string()
     }
 }
@@ -2,16 +2,27 @@ use bumpalo::{collections::Vec, Bump};
 use clap::ArgAction;
 use clap::{Arg, Command};
 use std::fs;
-use std::io;
+use std::io::{self, Write};
 use std::iter::once;
+use std::path::Path;
 use std::process;
 
-use roc_wasm_interp::{DefaultImportDispatcher, Instance};
-use roc_wasm_module::WasmModule;
+use roc_wasm_interp::{DefaultImportDispatcher, Instance, Profiler, Snapshot, WasiClock};
+use roc_wasm_module::{Serialize, WasmModule};
 
 pub const FLAG_FUNCTION: &str = "function";
 pub const FLAG_DEBUG: &str = "debug";
 pub const FLAG_HEX: &str = "hex";
+pub const FLAG_DIR: &str = "dir";
+pub const FLAG_DETERMINISTIC_CLOCK: &str = "deterministic-clock";
+pub const FLAG_FUEL: &str = "fuel";
+pub const FLAG_MAX_MEMORY_PAGES: &str = "max-memory-pages";
+pub const FLAG_PROFILE: &str = "profile";
+pub const FLAG_PROFILE_STACKS: &str = "profile-stacks";
+pub const FLAG_WAT: &str = "wat";
+pub const FLAG_LOAD_SNAPSHOT: &str = "load-snapshot";
+pub const FLAG_SAVE_SNAPSHOT: &str = "save-snapshot";
+pub const FLAG_WRITE_MODULE: &str = "write-module";
 pub const WASM_FILE: &str = "WASM_FILE";
 pub const ARGS_FOR_APP: &str = "ARGS_FOR_APP";
 
@@ -38,6 +49,69 @@ fn main() -> io::Result<()> {
         .action(ArgAction::SetTrue)
         .required(false);
 
+    let flag_dir = Arg::new(FLAG_DIR)
+        .long(FLAG_DIR)
+        .value_name("HOST_DIR[::GUEST_DIR]")
+        .help("Give the app access to a directory on the host, and everything inside it.\nThe app sees it at GUEST_DIR, or at HOST_DIR if there's no GUEST_DIR.\ne.g. `--dir .` or `--dir ./data::/data`")
+        .action(ArgAction::Append)
+        .required(false);
+
+    let flag_deterministic_clock = Arg::new(FLAG_DETERMINISTIC_CLOCK)
+        .long(FLAG_DETERMINISTIC_CLOCK)
+        .help("Use a fake clock that gives the same times on every run, and skips over sleeps.\nUseful for reproducible test runs.")
+        .action(ArgAction::SetTrue)
+        .required(false);
+
+    let flag_fuel = Arg::new(FLAG_FUEL)
+        .long(FLAG_FUEL)
+        .value_name("INSTRUCTIONS")
+        .help("Stop the app with an error after it executes this many instructions")
+        .value_parser(clap::value_parser!(u64))
+        .required(false);
+
+    let flag_max_memory_pages = Arg::new(FLAG_MAX_MEMORY_PAGES)
+        .long(FLAG_MAX_MEMORY_PAGES)
+        .value_name("PAGES")
+        .help("Don't let the app grow its memory beyond this many 64KiB pages")
+        .value_parser(clap::value_parser!(u32))
+        .required(false);
+
+    let flag_profile = Arg::new(FLAG_PROFILE)
+        .long(FLAG_PROFILE)
+        .help("Count instructions and calls for each function, and print a table of the results to stderr.")
+        .action(ArgAction::SetTrue)
+        .required(false);
+
+    let flag_profile_stacks = Arg::new(FLAG_PROFILE_STACKS)
+        .long(FLAG_PROFILE_STACKS)
+        .value_name("FILE")
+        .help("Write instruction counts for each call stack to FILE, in the collapsed stack format used by flamegraph tools.")
+        .required(false);
+
+    let flag_wat = Arg::new(FLAG_WAT)
+        .long(FLAG_WAT)
+        .help("Print the module in WebAssembly text format, instead of running it.")
+        .action(ArgAction::SetTrue)
+        .required(false);
+
+    let flag_load_snapshot = Arg::new(FLAG_LOAD_SNAPSHOT)
+        .long(FLAG_LOAD_SNAPSHOT)
+        .value_name("FILE")
+        .help("Restore the app's memory and globals from a snapshot file before running.\nIf the snapshot was saved in the middle of a call, continue that call instead of calling the function.")
+        .required(false);
+
+    let flag_save_snapshot = Arg::new(FLAG_SAVE_SNAPSHOT)
+        .long(FLAG_SAVE_SNAPSHOT)
+        .value_name("FILE")
+        .help("Save the app's memory, globals, and any call in progress to a snapshot file after running, even if the program trapped.\nCombine with --fuel to make a checkpoint.")
+        .required(false);
+
+    let flag_write_module = Arg::new(FLAG_WRITE_MODULE)
+        .long(FLAG_WRITE_MODULE)
+        .value_name("FILE")
+        .help("After running, write a copy of the module to FILE with the app's memory and globals as its initial state.\ne.g. `--function init --write-module app.init.wasm` to pre-initialize a module.")
+        .required(false);
+
     let wasm_file_to_run = Arg::new(WASM_FILE)
         .help("The .wasm file to run")
         .required(true);
@@ -51,6 +125,16 @@ fn main() -> io::Result<()> {
         .arg(flag_function)
         .arg(flag_debug)
         .arg(flag_hex)
+        .arg(flag_dir)
+        .arg(flag_deterministic_clock)
+        .arg(flag_fuel)
+        .arg(flag_max_memory_pages)
+        .arg(flag_profile)
+        .arg(flag_profile_stacks)
+        .arg(flag_wat)
+        .arg(flag_load_snapshot)
+        .arg(flag_save_snapshot)
+        .arg(flag_write_module)
         .arg(wasm_file_to_run)
         .arg(args_for_app);
 
@@ -60,6 +144,13 @@ fn main() -> io::Result<()> {
     let start_fn_name = matches.get_one::<String>(FLAG_FUNCTION).unwrap();
     let is_debug_mode = matches.get_flag(FLAG_DEBUG);
     let is_hex_format = matches.get_flag(FLAG_HEX);
+    let is_deterministic_clock = matches.get_flag(FLAG_DETERMINISTIC_CLOCK);
+    let is_profile = matches.get_flag(FLAG_PROFILE);
+    let profile_stacks_path = matches.get_one::<String>(FLAG_PROFILE_STACKS);
+    let is_wat = matches.get_flag(FLAG_WAT);
+    let load_snapshot_path = matches.get_one::<String>(FLAG_LOAD_SNAPSHOT);
+    let save_snapshot_path = matches.get_one::<String>(FLAG_SAVE_SNAPSHOT);
+    let write_module_path = matches.get_one::<String>(FLAG_WRITE_MODULE);
     let start_arg_strings = matches.get_many::<String>(ARGS_FOR_APP).unwrap_or_default();
     let wasm_path = matches.get_one::<String>(WASM_FILE).unwrap();
     // WASI expects the .wasm file to be argv[0]
@@ -75,7 +166,7 @@ fn main() -> io::Result<()> {
     // Parse the binary data
 
     let require_relocatable = false;
-    let module = match WasmModule::preload(&arena, &module_bytes, require_relocatable) {
+    let mut module = match WasmModule::preload(&arena, &module_bytes, require_relocatable) {
         Ok(m) => m,
         Err(e) => {
             eprintln!("I couldn't parse this WebAssembly module! There's something wrong at byte offset {:#x}.",  e.offset);
@@ -85,18 +176,106 @@ fn main() -> io::Result<()> {
         }
     };
 
+    if is_wat {
+        print!("{}", module.to_wat());
+        return Ok(());
+    }
+
     // Create an execution instance
 
-    let dispatcher = DefaultImportDispatcher::new(&wasi_argv);
+    let mut dispatcher = DefaultImportDispatcher::new(&wasi_argv);
+
+    if is_deterministic_clock {
+        dispatcher.wasi.clock = WasiClock::deterministic();
+    }
+
+    for dir in matches.get_many::<String>(FLAG_DIR).unwrap_or_default() {
+        let (host_dir, guest_dir) = dir.split_once("::").unwrap_or((dir, dir));
+
+        if let Err(e) = dispatcher.wasi.preopen_dir(Path::new(host_dir), guest_dir) {
+            eprintln!("I couldn't give the app access to the directory {host_dir}: {e}");
+            process::exit(1);
+        }
+    }
+
     let mut inst =
         Instance::for_module(&arena, &module, dispatcher, is_debug_mode).unwrap_or_else(|e| {
             eprintln!("{e}");
             process::exit(2);
         });
+    inst.fuel = matches.get_one::<u64>(FLAG_FUEL).copied();
+    inst.max_memory_pages = matches.get_one::<u32>(FLAG_MAX_MEMORY_PAGES).copied();
+    if is_profile || profile_stacks_path.is_some() {
+        inst.profiler = Some(Profiler::default());
+    }
+
+    let mut is_resuming = false;
+    if let Some(path) = load_snapshot_path {
+        let snapshot = Snapshot::from_bytes(&fs::read(path)?).unwrap_or_else(|e| {
+            eprintln!(
+                "I couldn't parse the snapshot file {path}. There's something wrong at byte offset {:#x}.",
+                e.offset
+            );
+            eprintln!("{}", e.message);
+            process::exit(1);
+        });
+        if let Err(e) = inst.restore(&snapshot) {
+            eprintln!("I couldn't restore the snapshot file {path}: {e}");
+            process::exit(1);
+        }
+        is_resuming = snapshot.is_call_in_progress();
+    }
 
     // Run
 
-    let result = inst.call_export_from_cli(&module, start_fn_name, &wasi_argv);
+    let result = if is_resuming {
+        inst.resume()
+    } else {
+        inst.call_export_from_cli(&module, start_fn_name, &wasi_argv)
+    };
+
+    // Write out the profile, even if the program trapped
+
+    if let Some(profiler) = &inst.profiler {
+        if is_profile {
+            profiler.write_table(&module, &mut io::stderr())?;
+        }
+        if let Some(path) = profile_stacks_path {
+            let mut file = io::BufWriter::new(fs::File::create(path)?);
+            profiler.write_collapsed_stacks(&module, &mut file)?;
+            file.flush()?;
+        }
+    }
+
+    // Write out the snapshot and the pre-initialized module, even if the program trapped
+
+    if save_snapshot_path.is_some() || write_module_path.is_some() {
+        let snapshot = inst.snapshot();
+        drop(inst);
+
+        if let Some(path) = save_snapshot_path {
+            let mut bytes = std::vec::Vec::with_capacity(snapshot.memory.len() + 1024);
+            snapshot.serialize(&mut bytes);
+            fs::write(path, bytes)?;
+        }
+
+        if let Some(path) = write_module_path {
+            match snapshot.apply_to_module(&arena, &mut module) {
+                Ok(()) => {
+                    let mut bytes = Vec::with_capacity_in(module.size(), &arena);
+                    module.serialize(&mut bytes);
+                    fs::write(path, bytes)?;
+                }
+                Err(e) => {
+                    eprintln!("I couldn't write the module to {path}: {e}");
+                    // If the program trapped, still report the trap below
+                    if result.is_ok() {
+                        process::exit(1);
+                    }
+                }
+            }
+        }
+    }
 
     // Print out return value, if any
 
@@ -0,0 +1,223 @@
+use std::collections::HashMap;
+use std::io::{self, Write};
+
+use roc_wasm_module::WasmModule;
+
+/// Counts instructions and calls per function, to find hot spots in a Wasm program.
+///
+/// Set `Instance::profiler` to `Some(Profiler::default())` before calling into the module,
+/// and read the results afterwards.
+#[derive(Debug)]
+pub struct Profiler {
+    /// Number of calls to each function, by function index
+    calls: Vec<u64>,
+    /// Instructions executed while each function was on the call stack
+    inclusive: Vec<u64>,
+    /// Number of calls to each function that are currently in progress.
+    /// Recursive calls only count towards `inclusive` once, at the outermost call.
+    active: Vec<u32>,
+    /// Calls in progress, with the total instruction count when they started
+    stack: Vec<(usize, u64)>,
+    /// Every distinct call stack we've seen, as a tree. Node 0 is the root, outside of any function.
+    nodes: Vec<StackNode>,
+    /// Look up a node's child by (parent node index, function index)
+    children: HashMap<(usize, usize), usize>,
+    current_node: usize,
+    total_instructions: u64,
+}
+
+#[derive(Debug)]
+struct StackNode {
+    fn_index: usize,
+    parent: usize,
+    /// Instructions executed in this function, with exactly this call stack
+    instructions: u64,
+}
+
+/// Profile results for one function
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FunctionProfile {
+    pub fn_index: usize,
+    /// Name from the module's NameSection, or `func[N]` if it doesn't have one
+    pub name: String,
+    pub calls: u64,
+    /// Instructions executed in the function itself
+    pub exclusive: u64,
+    /// Instructions executed in the function and everything it called
+    pub inclusive: u64,
+}
+
+impl Default for Profiler {
+    fn default() -> Self {
+        Profiler {
+            calls: Vec::new(),
+            inclusive: Vec::new(),
+            active: Vec::new(),
+            stack: Vec::new(),
+            nodes: vec![StackNode {
+                fn_index: usize::MAX,
+                parent: 0,
+                instructions: 0,
+            }],
+            children: HashMap::new(),
+            current_node: 0,
+            total_instructions: 0,
+        }
+    }
+}
+
+impl Profiler {
+    pub fn total_instructions(&self) -> u64 {
+        self.total_instructions
+    }
+
+    pub(crate) fn instruction(&mut self) {
+        self.total_instructions += 1;
+        self.nodes[self.current_node].instructions += 1;
+    }
+
+    /// Count a call to an imported function. It doesn't execute any Wasm instructions.
+    pub(crate) fn call_import(&mut self, fn_index: usize) {
+        self.grow(fn_index);
+        self.calls[fn_index] += 1;
+    }
+
+    pub(crate) fn enter(&mut self, fn_index: usize) {
+        self.grow(fn_index);
+        self.calls[fn_index] += 1;
+        self.active[fn_index] += 1;
+        self.stack.push((fn_index, self.total_instructions));
+
+        let next_node_index = self.nodes.len();
+        let parent = self.current_node;
+        self.current_node = *self
+            .children
+            .entry((parent, fn_index))
+            .or_insert(next_node_index);
+        if self.current_node == next_node_index {
+            self.nodes.push(StackNode {
+                fn_index,
+                parent,
+                instructions: 0,
+            });
+        }
+    }
+
+    pub(crate) fn exit(&mut self) {
+        if let Some((fn_index, start)) = self.stack.pop() {
+            self.active[fn_index] -= 1;
+            if self.active[fn_index] == 0 {
+                self.inclusive[fn_index] += self.total_instructions - start;
+            }
+            self.current_node = self.nodes[self.current_node].parent;
+        }
+    }
+
+    /// Exit all calls in progress, for when the program traps
+    pub(crate) fn unwind(&mut self) {
+        while !self.stack.is_empty() {
+            self.exit();
+        }
+    }
+
+    fn grow(&mut self, fn_index: usize) {
+        if fn_index >= self.calls.len() {
+            self.calls.resize(fn_index + 1, 0);
+            self.inclusive.resize(fn_index + 1, 0);
+            self.active.resize(fn_index + 1, 0);
+        }
+    }
+
+    /// Results for every function that was called, hottest first (by exclusive instruction count)
+    pub fn functions(&self, module: &WasmModule<'_>) -> Vec<FunctionProfile> {
+        let mut exclusive = vec![0; self.calls.len()];
+        for node in self.nodes.iter().skip(1) {
+            exclusive[node.fn_index] += node.instructions;
+        }
+
+        let mut functions: Vec<FunctionProfile> = (0..self.calls.len())
+            .filter(|fn_index| self.calls[*fn_index] > 0)
+            .map(|fn_index| FunctionProfile {
+                fn_index,
+                name: function_name(module, fn_index),
+                calls: self.calls[fn_index],
+                exclusive: exclusive[fn_index],
+                inclusive: self.inclusive[fn_index],
+            })
+            .collect();
+
+        functions.sort_by(|a, b| {
+            b.exclusive
+                .cmp(&a.exclusive)
+                .then(b.inclusive.cmp(&a.inclusive))
+                .then(a.fn_index.cmp(&b.fn_index))
+        });
+        functions
+    }
+
+    /// Write a table of results, hottest functions first
+    pub fn write_table<W: Write>(&self, module: &WasmModule<'_>, w: &mut W) -> io::Result<()> {
+        let total = self.total_instructions.max(1) as f64;
+        writeln!(
+            w,
+            "{:>12} {:>7} {:>12} {:>7} {:>10}  function",
+            "exclusive", "%", "inclusive", "%", "calls"
+        )?;
+        for f in self.functions(module) {
+            writeln!(
+                w,
+                "{:>12} {:>6.2}% {:>12} {:>6.2}% {:>10}  {}",
+                f.exclusive,
+                100.0 * f.exclusive as f64 / total,
+                f.inclusive,
+                100.0 * f.inclusive as f64 / total,
+                f.calls,
+                f.name
+            )?;
+        }
+        writeln!(w, "{:>12} instructions in total", self.total_instructions)
+    }
+
+    /// Write instruction counts per call stack, in the "collapsed stacks" format used by
+    /// flamegraph tools like `flamegraph.pl` and `inferno-flamegraph`. e.g. `_start;main;foo 1234`
+    pub fn write_collapsed_stacks<W: Write>(
+        &self,
+        module: &WasmModule<'_>,
+        w: &mut W,
+    ) -> io::Result<()> {
+        let mut lines: Vec<(String, u64)> = self
+            .nodes
+            .iter()
+            .enumerate()
+            .skip(1)
+            .filter(|(_, node)| node.instructions > 0)
+            .map(|(node_index, node)| {
+                let mut path = Vec::new();
+                let mut i = node_index;
+                while i != 0 {
+                    let name = function_name(module, self.nodes[i].fn_index);
+                    path.push(name.replace(';', ":"));
+                    i = self.nodes[i].parent;
+                }
+                path.reverse();
+                (path.join(";"), node.instructions)
+            })
+            .collect();
+        lines.sort();
+
+        for (path, count) in lines {
+            writeln!(w, "{path} {count}")?;
+        }
+        Ok(())
+    }
+}
+
+fn function_name(module: &WasmModule<'_>, fn_index: usize) -> String {
+    module
+        .names
+        .function_names
+        .iter()
+        .find(|(idx, _)| *idx == fn_index as u32)
+        .map(|(_, name)| name.to_string())
+        .unwrap_or_else(|| format!("func[{fn_index}]"))
+}
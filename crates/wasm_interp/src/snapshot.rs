@@ -0,0 +1,392 @@
+use bumpalo::{collections::Vec, Bump};
+
+use roc_wasm_module::parse::{Parse, ParseError, SkipBytes};
+use roc_wasm_module::sections::{
+    DataMode, DataSection, DataSegment, GlobalSection, Limits, MemorySection,
+};
+use roc_wasm_module::{
+    ConstExpr, Global, GlobalType, SerialBuffer, Serialize, Value, ValueType, WasmModule,
+};
+
+use crate::frame::Frame;
+use crate::instance::{Block, BlockType};
+
+/// A copy of the runtime state of an `Instance`: its memory, globals, and any call in progress.
+///
+/// Get one from `Instance::snapshot` and load it back with `Instance::restore`.
+/// It can be saved to bytes with `serialize` and loaded again with `from_bytes`.
+///
+/// It doesn't include the state of the import dispatcher (like open WASI files),
+/// or settings like `fuel` and `profiler`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Snapshot {
+    pub memory: std::vec::Vec<u8>,
+    pub globals: std::vec::Vec<Value>,
+    pub(crate) values: std::vec::Vec<Value>,
+    pub(crate) current_frame: Frame,
+    pub(crate) previous_frames: std::vec::Vec<Frame>,
+    pub(crate) blocks: std::vec::Vec<Block>,
+    pub(crate) program_counter: usize,
+}
+
+/// "Roc Wasm Interpreter Snapshot"
+const MAGIC: &[u8; 4] = b"RWIS";
+const VERSION: u32 = 1;
+
+/// Runs of zeros shorter than this don't split a data segment. A new segment would cost more bytes.
+const MIN_ZERO_GAP: usize = 16;
+
+impl Snapshot {
+    /// Whether the snapshot was taken in the middle of a call, for example when the program ran out of fuel.
+    /// If so, `Instance::resume` can continue the call after restoring the snapshot.
+    pub fn is_call_in_progress(&self) -> bool {
+        !self.blocks.is_empty()
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        Self::parse((), bytes, &mut 0)
+    }
+
+    /// Make the snapshot's memory and globals the initial state of the module.
+    /// This is useful for running a program's startup code ahead of time, then writing the module
+    /// back out with `WasmModule::serialize`, so that it starts up already initialized.
+    ///
+    /// All existing data segments are replaced. This only makes sense between calls.
+    pub fn apply_to_module<'a>(
+        &self,
+        arena: &'a Bump,
+        module: &mut WasmModule<'a>,
+    ) -> Result<(), String> {
+        if self.is_call_in_progress() {
+            return Err(
+                "I can't initialize a module from a snapshot with a call in progress".into(),
+            );
+        }
+
+        // Memory size. Keep the maximum, if there is one.
+        if module.memory.count == 0 && !self.memory.is_empty() {
+            return Err("I can't add memory from a snapshot to a module that has none".into());
+        }
+        let pages = self.memory.len() as u32 / MemorySection::PAGE_SIZE;
+        let max_pages = module
+            .memory
+            .max_bytes()
+            .map_err(|e| e.message)?
+            .map(|max_bytes| max_bytes / MemorySection::PAGE_SIZE);
+        let limits = match max_pages {
+            Some(max) => Limits::MinMax(pages, max),
+            None => Limits::Min(pages),
+        };
+        let mut memory_bytes = Vec::with_capacity_in(12, arena);
+        limits.serialize(&mut memory_bytes);
+        module.memory = MemorySection {
+            count: module.memory.count,
+            bytes: memory_bytes,
+        };
+
+        // Globals
+        let mut global = GlobalSection::new(arena);
+        let mut cursor = 0;
+        for value in self.globals.iter() {
+            let ty =
+                GlobalType::parse((), &module.global.bytes, &mut cursor).map_err(|e| e.message)?;
+            ConstExpr::skip_bytes(&module.global.bytes, &mut cursor).map_err(|e| e.message)?;
+            let init = match *value {
+                Value::I32(x) => ConstExpr::I32(x),
+                Value::I64(x) => ConstExpr::I64(x),
+                Value::F32(x) => ConstExpr::F32(x),
+                Value::F64(x) => ConstExpr::F64(x),
+            };
+            global.append(Global { ty, init });
+        }
+        module.global = global;
+
+        // Data segments for all of the non-zero memory
+        let mut data = DataSection::new(arena);
+        let mut addr = 0;
+        while let Some(start) = find_non_zero(&self.memory, addr) {
+            let mut end = start;
+            while let Some(next) = find_non_zero(&self.memory, end + 1) {
+                if next - end > MIN_ZERO_GAP {
+                    break;
+                }
+                end = next;
+            }
+            end += 1;
+            data.append_segment(DataSegment {
+                mode: DataMode::active_at(start as u32),
+                init: Vec::from_iter_in(self.memory[start..end].iter().copied(), arena),
+            });
+            data.end_addr = end as u32;
+            addr = end;
+        }
+        module.data = data;
+
+        Ok(())
+    }
+}
+
+fn find_non_zero(memory: &[u8], from: usize) -> Option<usize> {
+    let slice = memory.get(from..)?;
+    slice.iter().position(|byte| *byte != 0).map(|i| from + i)
+}
+
+impl Serialize for Snapshot {
+    fn serialize<T: SerialBuffer>(&self, buffer: &mut T) {
+        buffer.append_slice(MAGIC);
+        buffer.encode_u32(VERSION);
+
+        buffer.encode_u32(self.memory.len() as u32);
+        buffer.append_slice(&self.memory);
+        serialize_values(&self.globals, buffer);
+        serialize_values(&self.values, buffer);
+        self.current_frame.serialize(buffer);
+        self.previous_frames.serialize(buffer);
+        self.blocks.serialize(buffer);
+
+        // usize::MAX means the instance has never been called
+        buffer.encode_u32(self.program_counter.min(u32::MAX as usize) as u32);
+    }
+}
+
+impl Parse<()> for Snapshot {
+    fn parse(_: (), bytes: &[u8], cursor: &mut usize) -> Result<Self, ParseError> {
+        if bytes.get(*cursor..*cursor + MAGIC.len()) != Some(MAGIC) {
+            return Err(ParseError {
+                offset: *cursor,
+                message: "This is not a Wasm interpreter snapshot".into(),
+            });
+        }
+        *cursor += MAGIC.len();
+        let version = parse_u32(bytes, cursor)?;
+        if version != VERSION {
+            return Err(ParseError {
+                offset: *cursor,
+                message: format!("Unsupported snapshot version {version}. Expected {VERSION}."),
+            });
+        }
+
+        let memory = parse_bytes(bytes, cursor)?.to_vec();
+        let globals = parse_values(bytes, cursor)?;
+        let values = parse_values(bytes, cursor)?;
+        let current_frame = Frame::parse((), bytes, cursor)?;
+        let previous_frames = parse_items(bytes, cursor)?;
+        let blocks = parse_items(bytes, cursor)?;
+        let program_counter = match parse_u32(bytes, cursor)? {
+            u32::MAX => usize::MAX,
+            pc => pc as usize,
+        };
+
+        Ok(Snapshot {
+            memory,
+            globals,
+            values,
+            current_frame,
+            previous_frames,
+            blocks,
+            program_counter,
+        })
+    }
+}
+
+impl Serialize for Frame {
+    fn serialize<T: SerialBuffer>(&self, buffer: &mut T) {
+        buffer.encode_u32(self.fn_index as u32);
+        buffer.encode_u32(self.return_addr as u32);
+        buffer.encode_u32(self.body_block_index as u32);
+        buffer.encode_u32(self.locals_start as u32);
+        buffer.encode_u32(self.locals_count as u32);
+        match self.return_type {
+            Some(ty) => ty.serialize(buffer),
+            None => buffer.append_u8(ValueType::VOID),
+        }
+    }
+}
+
+impl Parse<()> for Frame {
+    fn parse(_: (), bytes: &[u8], cursor: &mut usize) -> Result<Self, ParseError> {
+        let fn_index = parse_u32(bytes, cursor)? as usize;
+        let return_addr = parse_u32(bytes, cursor)? as usize;
+        let body_block_index = parse_u32(bytes, cursor)? as usize;
+        let locals_start = parse_u32(bytes, cursor)? as usize;
+        let locals_count = parse_u32(bytes, cursor)? as usize;
+        let return_type = match parse_byte(bytes, cursor)? {
+            ValueType::VOID => None,
+            byte => Some(parse_value_type(byte, *cursor - 1)?),
+        };
+        Ok(Frame {
+            fn_index,
+            return_addr,
+            body_block_index,
+            locals_start,
+            locals_count,
+            return_type,
+        })
+    }
+}
+
+#[repr(u8)]
+enum BlockTypeId {
+    Loop = 0,
+    Normal = 1,
+    Locals = 2,
+    FunctionBody = 3,
+}
+
+impl Serialize for Block {
+    fn serialize<T: SerialBuffer>(&self, buffer: &mut T) {
+        let (id, data) = match self.ty {
+            BlockType::Loop(addr) => (BlockTypeId::Loop, addr),
+            BlockType::Normal => (BlockTypeId::Normal, 0),
+            BlockType::Locals(fn_index) => (BlockTypeId::Locals, fn_index),
+            BlockType::FunctionBody(fn_index) => (BlockTypeId::FunctionBody, fn_index),
+        };
+        buffer.append_u8(id as u8);
+        buffer.encode_u32(data as u32);
+        buffer.encode_u32(self.vstack as u32);
+    }
+}
+
+impl Parse<()> for Block {
+    fn parse(_: (), bytes: &[u8], cursor: &mut usize) -> Result<Self, ParseError> {
+        let id_offset = *cursor;
+        let id = parse_byte(bytes, cursor)?;
+        let data = parse_u32(bytes, cursor)? as usize;
+        let vstack = parse_u32(bytes, cursor)? as usize;
+        let ty = match id {
+            x if x == BlockTypeId::Loop as u8 => BlockType::Loop(data),
+            x if x == BlockTypeId::Normal as u8 => BlockType::Normal,
+            x if x == BlockTypeId::Locals as u8 => BlockType::Locals(data),
+            x if x == BlockTypeId::FunctionBody as u8 => BlockType::FunctionBody(data),
+            _ => {
+                return Err(ParseError {
+                    offset: id_offset,
+                    message: format!("Invalid block type {id}"),
+                })
+            }
+        };
+        Ok(Block { ty, vstack })
+    }
+}
+
+fn serialize_values<T: SerialBuffer>(values: &[Value], buffer: &mut T) {
+    buffer.encode_u32(values.len() as u32);
+    for value in values {
+        ValueType::from(*value).serialize(buffer);
+        match *value {
+            Value::I32(x) => {
+                buffer.encode_i32(x);
+            }
+            Value::I64(x) => {
+                buffer.encode_i64(x);
+            }
+            Value::F32(x) => buffer.encode_f32(x),
+            Value::F64(x) => buffer.encode_f64(x),
+        }
+    }
+}
+
+fn parse_values(bytes: &[u8], cursor: &mut usize) -> Result<std::vec::Vec<Value>, ParseError> {
+    let count = parse_u32(bytes, cursor)?;
+    let mut values = std::vec::Vec::with_capacity(count.min(1024) as usize);
+    for _ in 0..count {
+        let ty = parse_value_type(parse_byte(bytes, cursor)?, *cursor - 1)?;
+        let value = match ty {
+            ValueType::I32 => {
+                check_leb128(bytes, *cursor)?;
+                Value::I32(i32::parse((), bytes, cursor)?)
+            }
+            ValueType::I64 => {
+                check_leb128(bytes, *cursor)?;
+                Value::I64(i64::parse((), bytes, cursor)?)
+            }
+            ValueType::F32 => {
+                let mut b = [0; 4];
+                b.copy_from_slice(parse_fixed(bytes, cursor, 4)?);
+                Value::F32(f32::from_le_bytes(b))
+            }
+            ValueType::F64 => {
+                let mut b = [0; 8];
+                b.copy_from_slice(parse_fixed(bytes, cursor, 8)?);
+                Value::F64(f64::from_le_bytes(b))
+            }
+        };
+        values.push(value);
+    }
+    Ok(values)
+}
+
+fn parse_items<T: Parse<()>>(
+    bytes: &[u8],
+    cursor: &mut usize,
+) -> Result<std::vec::Vec<T>, ParseError> {
+    let count = parse_u32(bytes, cursor)?;
+    let mut items = std::vec::Vec::with_capacity(count.min(1024) as usize);
+    for _ in 0..count {
+        items.push(T::parse((), bytes, cursor)?);
+    }
+    Ok(items)
+}
+
+fn parse_value_type(byte: u8, offset: usize) -> Result<ValueType, ParseError> {
+    match byte {
+        0x7f => Ok(ValueType::I32),
+        0x7e => Ok(ValueType::I64),
+        0x7d => Ok(ValueType::F32),
+        0x7c => Ok(ValueType::F64),
+        _ => Err(ParseError {
+            offset,
+            message: format!("Invalid ValueType 0x{byte:02x}"),
+        }),
+    }
+}
+
+fn parse_u32(bytes: &[u8], cursor: &mut usize) -> Result<u32, ParseError> {
+    check_leb128(bytes, *cursor)?;
+    u32::parse((), bytes, cursor)
+}
+
+fn parse_byte(bytes: &[u8], cursor: &mut usize) -> Result<u8, ParseError> {
+    Ok(parse_fixed(bytes, cursor, 1)?[0])
+}
+
+/// A length-prefixed byte array
+fn parse_bytes<'b>(bytes: &'b [u8], cursor: &mut usize) -> Result<&'b [u8], ParseError> {
+    let len = parse_u32(bytes, cursor)? as usize;
+    parse_fixed(bytes, cursor, len)
+}
+
+fn parse_fixed<'b>(
+    bytes: &'b [u8],
+    cursor: &mut usize,
+    len: usize,
+) -> Result<&'b [u8], ParseError> {
+    check_remaining(bytes, *cursor, len)?;
+    let slice = &bytes[*cursor..][..len];
+    *cursor += len;
+    Ok(slice)
+}
+
+/// Check that a LEB-128 number ends before the end of the bytes, so that we can parse it without panicking
+fn check_leb128(bytes: &[u8], cursor: usize) -> Result<(), ParseError> {
+    let remaining = bytes.get(cursor..).unwrap_or_default();
+    if remaining.iter().any(|byte| byte & 0x80 == 0) {
+        Ok(())
+    } else {
+        Err(ParseError {
+            offset: cursor,
+            message: "Unexpected end of snapshot".into(),
+        })
+    }
+}
+
+fn check_remaining(bytes: &[u8], cursor: usize, len: usize) -> Result<(), ParseError> {
+    if cursor + len > bytes.len() {
+        Err(ParseError {
+            offset: cursor,
+            message: "Unexpected end of snapshot".into(),
+        })
+    } else {
+        Ok(())
+    }
+}
@@ -7,8 +7,12 @@ mod test_f64;
 mod test_i32;
 mod test_i64;
 mod test_mem;
+mod test_profiler;
+mod test_snapshot;
+mod test_traps;
+mod test_wasi;
 
-use crate::{DefaultImportDispatcher, Instance};
+use crate::{CallError, DefaultImportDispatcher, Instance, Trap};
 use bumpalo::{collections::Vec, Bump};
 use roc_wasm_module::{
     opcodes::OpCode, Export, ExportType, SerialBuffer, Serialize, Signature, Value, ValueType,
@@ -51,6 +55,27 @@ pub fn const_value(buf: &mut Vec<'_, u8>, value: Value) {
 }
 
 pub fn test_op_example<A>(op: OpCode, args: A, expected: Value)
+where
+    A: IntoIterator<Item = Value>,
+{
+    let return_val = call_op_example(op, args, ValueType::from(expected))
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(return_val, expected);
+}
+
+pub fn test_op_trap<A>(op: OpCode, args: A, ret_type: ValueType, expected: Trap)
+where
+    A: IntoIterator<Item = Value>,
+{
+    match call_op_example(op, args, ret_type) {
+        Err(CallError::Trap(report)) => assert_eq!(report.trap, expected),
+        other => panic!("Expected {expected:?} but got {other:?}"),
+    }
+}
+
+fn call_op_example<A>(op: OpCode, args: A, ret_type: ValueType) -> Result<Option<Value>, CallError>
 where
     A: IntoIterator<Item = Value>,
 {
@@ -74,7 +99,7 @@ where
         module.code.function_offsets.push(0);
         module.add_function_signature(Signature {
             param_types: Vec::new_in(&arena),
-            ret_type: Some(ValueType::from(expected)),
+            ret_type: Some(ret_type),
         });
         module.export.append(Export {
             name: "test",
@@ -95,9 +120,7 @@ where
     let mut inst =
         Instance::for_module(&arena, &module, DefaultImportDispatcher::default(), true).unwrap();
 
-    let return_val = inst.call_export("test", []).unwrap().unwrap();
-
-    assert_eq!(return_val, expected);
+    inst.call_export("test", [])
 }
 
 pub fn create_exported_function_no_locals<'a, F>(
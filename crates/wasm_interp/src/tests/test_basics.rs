@@ -5,7 +5,7 @@ use crate::tests::{
     const_value, create_exported_function_no_locals, create_exported_function_with_locals,
     default_state,
 };
-use crate::{DefaultImportDispatcher, ImportDispatcher, Instance};
+use crate::{CallError, DefaultImportDispatcher, ImportDispatcher, Instance, Trap, TrapReport};
 use bumpalo::{collections::Vec, Bump};
 use roc_wasm_module::sections::{Import, ImportDesc};
 use roc_wasm_module::{
@@ -702,29 +702,41 @@ fn test_call_return_with_args() {
 
 #[test]
 fn test_call_indirect_ok() {
-    let result = test_call_indirect_help(0, 0);
+    let result = test_call_indirect_help(0, 0).unwrap().unwrap();
     assert_eq!(result, Value::I32(111));
 }
 
 #[test]
-#[should_panic(expected = "Expected signature")]
 fn test_call_indirect_wrong_signature() {
-    test_call_indirect_help(0, 1);
+    let result = test_call_indirect_help(0, 1);
+    assert!(matches!(
+        result,
+        Err(CallError::Trap(TrapReport {
+            trap: Trap::IndirectCallTypeMismatch(0, 1),
+            ..
+        }))
+    ));
 }
 
 #[test]
-#[should_panic(expected = "element index")]
 fn test_call_indirect_index_out_of_bounds() {
-    test_call_indirect_help(0, 2);
+    let result = test_call_indirect_help(0, 2);
+    assert!(matches!(
+        result,
+        Err(CallError::Trap(TrapReport {
+            trap: Trap::UndefinedElement(2),
+            ..
+        }))
+    ));
 }
 
 #[test]
 #[should_panic(expected = "Table index")]
 fn test_call_indirect_unsupported_table() {
-    test_call_indirect_help(1, 0);
+    let _ = test_call_indirect_help(1, 0);
 }
 
-fn test_call_indirect_help(table_index: u32, elem_index: u32) -> Value {
+fn test_call_indirect_help(table_index: u32, elem_index: u32) -> Result<Option<Value>, CallError> {
     let arena = Bump::new();
     let mut module = WasmModule::new(&arena);
 
@@ -783,7 +795,7 @@ fn test_call_indirect_help(table_index: u32, elem_index: u32) -> Value {
         is_debug_mode,
     )
     .unwrap();
-    inst.call_export(start_fn_name, []).unwrap().unwrap()
+    inst.call_export(start_fn_name, [])
 }
 
 // #[test]
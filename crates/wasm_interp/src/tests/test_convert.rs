@@ -1,7 +1,8 @@
 #![cfg(test)]
 
-use super::test_op_example;
-use roc_wasm_module::{opcodes::OpCode::*, Value};
+use super::{test_op_example, test_op_trap};
+use crate::Trap;
+use roc_wasm_module::{opcodes::OpCode::*, Value, ValueType};
 
 #[test]
 fn test_i32wrapi64() {
@@ -18,12 +19,12 @@ fn test_i32truncsf32() {
 }
 
 #[test]
-#[should_panic(expected = "Cannot truncate")]
 fn test_i32truncsf32_oob() {
-    test_op_example(
+    test_op_trap(
         I32TRUNCSF32,
         [Value::F32(i32::MAX as f32 * 2.0)],
-        Value::I32(i32::MIN),
+        ValueType::I32,
+        Trap::InvalidConversionToInteger,
     );
 }
 
@@ -37,12 +38,12 @@ fn test_i32truncuf32() {
 }
 
 #[test]
-#[should_panic(expected = "Cannot truncate")]
 fn test_i32truncuf32_oob() {
-    test_op_example(
+    test_op_trap(
         I32TRUNCUF32,
         [Value::F32(u32::MAX as f32 * 2.0)],
-        Value::I32(0),
+        ValueType::I32,
+        Trap::InvalidConversionToInteger,
     );
 }
 
@@ -52,12 +53,12 @@ fn test_i32truncsf64() {
 }
 
 #[test]
-#[should_panic(expected = "Cannot truncate")]
 fn test_i32truncsf64_oob() {
-    test_op_example(
+    test_op_trap(
         I32TRUNCSF64,
         [Value::F64(i32::MAX as f64 * 2.0)],
-        Value::I32(i32::MIN),
+        ValueType::I32,
+        Trap::InvalidConversionToInteger,
     );
 }
 
@@ -71,12 +72,12 @@ fn test_i32truncuf64() {
 }
 
 #[test]
-#[should_panic(expected = "Cannot truncate")]
 fn test_i32truncuf64_oob() {
-    test_op_example(
+    test_op_trap(
         I32TRUNCUF64,
         [Value::F64(u32::MAX as f64 * 2.0)],
-        Value::I32(0),
+        ValueType::I32,
+        Trap::InvalidConversionToInteger,
     );
 }
 
@@ -96,12 +97,12 @@ fn test_i64truncsf32() {
 }
 
 #[test]
-#[should_panic(expected = "Cannot truncate")]
 fn test_i64truncsf32_oob() {
-    test_op_example(
+    test_op_trap(
         I64TRUNCSF32,
         [Value::F32(i64::MAX as f32 * 2.0)],
-        Value::I64(i64::MIN),
+        ValueType::I64,
+        Trap::InvalidConversionToInteger,
     );
 }
 
@@ -115,12 +116,12 @@ fn test_i64truncuf32() {
 }
 
 #[test]
-#[should_panic(expected = "Cannot truncate")]
 fn test_i64truncuf32_oob() {
-    test_op_example(
+    test_op_trap(
         I64TRUNCUF32,
         [Value::F32(u64::MAX as f32 * 2.0)],
-        Value::I64(0),
+        ValueType::I64,
+        Trap::InvalidConversionToInteger,
     );
 }
 
@@ -130,12 +131,12 @@ fn test_i64truncsf64() {
 }
 
 #[test]
-#[should_panic(expected = "Cannot truncate")]
 fn test_i64truncsf64_oob() {
-    test_op_example(
+    test_op_trap(
         I64TRUNCSF64,
         [Value::F64(i64::MAX as f64 * 2.0)],
-        Value::I64(i64::MIN),
+        ValueType::I64,
+        Trap::InvalidConversionToInteger,
     );
 }
 
@@ -149,15 +150,130 @@ fn test_i64truncuf64() {
 }
 
 #[test]
-#[should_panic(expected = "Cannot truncate")]
 fn test_i64truncuf64_oob() {
-    test_op_example(
+    test_op_trap(
         I32TRUNCUF64,
         [Value::F64(u64::MAX as f64 * 2.0)],
-        Value::I32(0),
+        ValueType::I32,
+        Trap::InvalidConversionToInteger,
     );
 }
 
+#[test]
+fn test_trunc_signed_bounds() {
+    let trap = Trap::InvalidConversionToInteger;
+
+    // The lower bound is inclusive, the upper bound isn't
+    test_op_example(
+        I32TRUNCSF32,
+        [Value::F32(-(2f32.powi(31)))],
+        Value::I32(i32::MIN),
+    );
+    test_op_trap(
+        I32TRUNCSF32,
+        [Value::F32(2f32.powi(31))],
+        ValueType::I32,
+        trap,
+    );
+
+    // Fractions get truncated before the check
+    test_op_example(
+        I32TRUNCSF64,
+        [Value::F64(2147483647.9)],
+        Value::I32(i32::MAX),
+    );
+    test_op_example(
+        I32TRUNCSF64,
+        [Value::F64(-2147483648.9)],
+        Value::I32(i32::MIN),
+    );
+    test_op_trap(
+        I32TRUNCSF64,
+        [Value::F64(2f64.powi(31))],
+        ValueType::I32,
+        trap,
+    );
+    test_op_trap(
+        I32TRUNCSF64,
+        [Value::F64(-2147483649.0)],
+        ValueType::I32,
+        trap,
+    );
+
+    test_op_example(
+        I64TRUNCSF32,
+        [Value::F32(-(2f32.powi(63)))],
+        Value::I64(i64::MIN),
+    );
+    test_op_trap(
+        I64TRUNCSF32,
+        [Value::F32(2f32.powi(63))],
+        ValueType::I64,
+        trap,
+    );
+
+    test_op_example(
+        I64TRUNCSF64,
+        [Value::F64(-(2f64.powi(63)))],
+        Value::I64(i64::MIN),
+    );
+    test_op_trap(
+        I64TRUNCSF64,
+        [Value::F64(2f64.powi(63))],
+        ValueType::I64,
+        trap,
+    );
+}
+
+#[test]
+fn test_trunc_unsigned_bounds() {
+    let trap = Trap::InvalidConversionToInteger;
+
+    // Anything above -1 truncates to zero
+    test_op_example(I32TRUNCUF32, [Value::F32(-0.5)], Value::I32(0));
+    test_op_example(I32TRUNCUF64, [Value::F64(-0.9)], Value::I32(0));
+    test_op_example(I64TRUNCUF32, [Value::F32(-0.5)], Value::I64(0));
+    test_op_example(I64TRUNCUF64, [Value::F64(-0.9)], Value::I64(0));
+    test_op_trap(I32TRUNCUF32, [Value::F32(-1.0)], ValueType::I32, trap);
+    test_op_trap(I64TRUNCUF64, [Value::F64(-1.0)], ValueType::I64, trap);
+
+    test_op_example(I32TRUNCUF64, [Value::F64(4294967295.9)], Value::I32(-1));
+    test_op_trap(
+        I32TRUNCUF32,
+        [Value::F32(2f32.powi(32))],
+        ValueType::I32,
+        trap,
+    );
+    test_op_trap(
+        I32TRUNCUF64,
+        [Value::F64(2f64.powi(32))],
+        ValueType::I32,
+        trap,
+    );
+    test_op_trap(
+        I64TRUNCUF32,
+        [Value::F32(2f32.powi(64))],
+        ValueType::I64,
+        trap,
+    );
+    test_op_trap(
+        I64TRUNCUF64,
+        [Value::F64(2f64.powi(64))],
+        ValueType::I64,
+        trap,
+    );
+}
+
+#[test]
+fn test_trunc_nan() {
+    let trap = Trap::InvalidConversionToInteger;
+
+    test_op_trap(I32TRUNCSF32, [Value::F32(f32::NAN)], ValueType::I32, trap);
+    test_op_trap(I32TRUNCUF64, [Value::F64(f64::NAN)], ValueType::I32, trap);
+    test_op_trap(I64TRUNCSF64, [Value::F64(f64::NAN)], ValueType::I64, trap);
+    test_op_trap(I64TRUNCUF32, [Value::F32(f32::NAN)], ValueType::I64, trap);
+}
+
 #[test]
 fn test_f32convertsi32() {
     test_op_example(F32CONVERTSI32, [Value::I32(-1)], Value::F32(-1.0));
@@ -1,7 +1,8 @@
 #![cfg(test)]
 
-use super::test_op_example;
-use roc_wasm_module::{opcodes::OpCode, opcodes::OpCode::*, Value};
+use super::{test_op_example, test_op_trap};
+use crate::Trap;
+use roc_wasm_module::{opcodes::OpCode, opcodes::OpCode::*, Value, ValueType};
 
 fn test_i32_binop(op: OpCode, arg1: i32, arg2: i32, expected: i32) {
     test_op_example(
@@ -190,13 +191,26 @@ fn test_i32divs() {
     let op = I32DIVS;
     test_i32_binop(op, -1, -1, 1);
     test_i32_binop(op, 6, 3, 2);
-    test_i32_binop(op, i32::MIN, -1, i32::MIN);
 }
 
 #[test]
-#[should_panic(expected = "divide by zero")]
+fn test_i32divs_overflow() {
+    test_op_trap(
+        I32DIVS,
+        [Value::I32(i32::MIN), Value::I32(-1)],
+        ValueType::I32,
+        Trap::IntegerOverflow,
+    );
+}
+
+#[test]
 fn test_i32divs_zero() {
-    test_i32_binop(I32DIVS, 1, 0, -1);
+    test_op_trap(
+        I32DIVS,
+        [Value::I32(1), Value::I32(0)],
+        ValueType::I32,
+        Trap::DivisionByZero,
+    );
 }
 
 #[test]
@@ -207,9 +221,13 @@ fn test_i32divu() {
 }
 
 #[test]
-#[should_panic(expected = "divide by zero")]
 fn test_i32divu_zero() {
-    test_i32_binop(I32DIVU, 1, 0, -1);
+    test_op_trap(
+        I32DIVU,
+        [Value::I32(1), Value::I32(0)],
+        ValueType::I32,
+        Trap::DivisionByZero,
+    );
 }
 
 #[test]
@@ -222,9 +240,13 @@ fn test_i32rems() {
 }
 
 #[test]
-#[should_panic(expected = "divisor of zero")]
 fn test_i32rems_zero() {
-    test_i32_binop(I32REMS, 1, 0, -1);
+    test_op_trap(
+        I32REMS,
+        [Value::I32(1), Value::I32(0)],
+        ValueType::I32,
+        Trap::DivisionByZero,
+    );
 }
 
 #[test]
@@ -234,9 +256,13 @@ fn test_i32remu() {
 }
 
 #[test]
-#[should_panic(expected = "divisor of zero")]
 fn test_i32remu_zero() {
-    test_i32_binop(I32REMU, 1, 0, -1);
+    test_op_trap(
+        I32REMU,
+        [Value::I32(1), Value::I32(0)],
+        ValueType::I32,
+        Trap::DivisionByZero,
+    );
 }
 
 #[test]
@@ -1,7 +1,8 @@
 #![cfg(test)]
 
-use super::test_op_example;
-use roc_wasm_module::{opcodes::OpCode, opcodes::OpCode::*, Value};
+use super::{test_op_example, test_op_trap};
+use crate::Trap;
+use roc_wasm_module::{opcodes::OpCode, opcodes::OpCode::*, Value, ValueType};
 
 fn test_i64_comparison(op: OpCode, arg1: i64, arg2: i64, expected: bool) {
     test_op_example(
@@ -206,13 +207,26 @@ fn test_i64divs() {
     let op = I64DIVS;
     test_i64_binop(op, -1, -1, 1);
     test_i64_binop(op, 6, 3, 2);
-    test_i64_binop(op, i64::MIN, -1, i64::MIN);
 }
 
 #[test]
-#[should_panic(expected = "divide by zero")]
+fn test_i64divs_overflow() {
+    test_op_trap(
+        I64DIVS,
+        [Value::I64(i64::MIN), Value::I64(-1)],
+        ValueType::I64,
+        Trap::IntegerOverflow,
+    );
+}
+
+#[test]
 fn test_i64divs_zero() {
-    test_i64_binop(I64DIVS, 1, 0, -1);
+    test_op_trap(
+        I64DIVS,
+        [Value::I64(1), Value::I64(0)],
+        ValueType::I64,
+        Trap::DivisionByZero,
+    );
 }
 
 #[test]
@@ -223,9 +237,13 @@ fn test_i64divu() {
 }
 
 #[test]
-#[should_panic(expected = "divide by zero")]
 fn test_i64divu_zero() {
-    test_i64_binop(I64DIVU, 1, 0, -1);
+    test_op_trap(
+        I64DIVU,
+        [Value::I64(1), Value::I64(0)],
+        ValueType::I64,
+        Trap::DivisionByZero,
+    );
 }
 
 #[test]
@@ -238,9 +256,13 @@ fn test_i64rems() {
 }
 
 #[test]
-#[should_panic(expected = "divisor of zero")]
 fn test_i64rems_zero() {
-    test_i64_binop(I64REMS, 1, 0, -1);
+    test_op_trap(
+        I64REMS,
+        [Value::I64(1), Value::I64(0)],
+        ValueType::I64,
+        Trap::DivisionByZero,
+    );
 }
 
 #[test]
@@ -250,9 +272,13 @@ fn test_i64remu() {
 }
 
 #[test]
-#[should_panic(expected = "divisor of zero")]
 fn test_i64remu_zero() {
-    test_i64_binop(I64REMU, 1, 0, -1);
+    test_op_trap(
+        I64REMU,
+        [Value::I64(1), Value::I64(0)],
+        ValueType::I64,
+        Trap::DivisionByZero,
+    );
 }
 
 #[test]
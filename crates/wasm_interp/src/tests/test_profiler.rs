@@ -0,0 +1,198 @@
+#![cfg(test)]
+
+use super::create_exported_function_no_locals;
+use crate::{DefaultImportDispatcher, FunctionProfile, Instance, Profiler};
+use bumpalo::Bump;
+use roc_wasm_module::{opcodes::OpCode, SerialBuffer, Signature, Value, ValueType, WasmModule};
+
+#[test]
+fn test_profile_calls() {
+    let arena = Bump::new();
+    let mut module = WasmModule::new(&arena);
+
+    // func[0]: square(square(3))
+    let signature = Signature {
+        param_types: bumpalo::vec![in &arena],
+        ret_type: Some(ValueType::I32),
+    };
+    create_exported_function_no_locals(&mut module, "main", signature, |buf| {
+        buf.append_u8(OpCode::I32CONST as u8);
+        buf.encode_i32(3);
+        buf.append_u8(OpCode::CALL as u8);
+        buf.encode_u32(1);
+        buf.append_u8(OpCode::CALL as u8);
+        buf.encode_u32(1);
+        buf.append_u8(OpCode::END as u8);
+    });
+
+    // func[1]: square
+    let signature = Signature {
+        param_types: bumpalo::vec![in &arena; ValueType::I32],
+        ret_type: Some(ValueType::I32),
+    };
+    create_exported_function_no_locals(&mut module, "square", signature, |buf| {
+        buf.append_u8(OpCode::GETLOCAL as u8);
+        buf.encode_u32(0);
+        buf.append_u8(OpCode::GETLOCAL as u8);
+        buf.encode_u32(0);
+        buf.append_u8(OpCode::I32MUL as u8);
+        buf.append_u8(OpCode::END as u8);
+    });
+
+    module.names.function_names.push((0, "main"));
+    module.names.function_names.push((1, "square"));
+
+    let mut inst =
+        Instance::for_module(&arena, &module, DefaultImportDispatcher::default(), false).unwrap();
+    inst.profiler = Some(Profiler::default());
+
+    let result = inst.call_export("main", []).unwrap();
+    assert_eq!(result, Some(Value::I32(81)));
+
+    let profiler = inst.profiler.as_ref().unwrap();
+    assert_eq!(profiler.total_instructions(), 12);
+    assert_eq!(
+        profiler.functions(&module),
+        [
+            FunctionProfile {
+                fn_index: 1,
+                name: "square".into(),
+                calls: 2,
+                exclusive: 8,
+                inclusive: 8,
+            },
+            FunctionProfile {
+                fn_index: 0,
+                name: "main".into(),
+                calls: 1,
+                exclusive: 4,
+                inclusive: 12,
+            },
+        ]
+    );
+
+    let mut stacks = Vec::new();
+    profiler
+        .write_collapsed_stacks(&module, &mut stacks)
+        .unwrap();
+    assert_eq!(
+        String::from_utf8(stacks).unwrap(),
+        "main 4\nmain;square 8\n"
+    );
+
+    let mut table = Vec::new();
+    profiler.write_table(&module, &mut table).unwrap();
+    let table = String::from_utf8(table).unwrap();
+    let square_line = table.find("square").unwrap();
+    let main_line = table.find("main").unwrap();
+    assert!(square_line < main_line, "{table}");
+}
+
+#[test]
+fn test_profile_recursion() {
+    let arena = Bump::new();
+    let mut module = WasmModule::new(&arena);
+
+    // func[0]: count down to zero, recursively. No name.
+    let signature = Signature {
+        param_types: bumpalo::vec![in &arena; ValueType::I32],
+        ret_type: None,
+    };
+    create_exported_function_no_locals(&mut module, "countdown", signature, |buf| {
+        buf.append_u8(OpCode::GETLOCAL as u8);
+        buf.encode_u32(0);
+        buf.append_u8(OpCode::IF as u8);
+        buf.append_u8(0x40); // blocktype: no value
+        buf.append_u8(OpCode::GETLOCAL as u8);
+        buf.encode_u32(0);
+        buf.append_u8(OpCode::I32CONST as u8);
+        buf.encode_i32(1);
+        buf.append_u8(OpCode::I32SUB as u8);
+        buf.append_u8(OpCode::CALL as u8);
+        buf.encode_u32(0);
+        buf.append_u8(OpCode::END as u8);
+        buf.append_u8(OpCode::END as u8);
+    });
+
+    let mut inst =
+        Instance::for_module(&arena, &module, DefaultImportDispatcher::default(), false).unwrap();
+    inst.profiler = Some(Profiler::default());
+
+    inst.call_export("countdown", [Value::I32(3)]).unwrap();
+
+    let profiler = inst.profiler.as_ref().unwrap();
+    let total = profiler.total_instructions();
+    assert_eq!(total, 3 * 8 + 3);
+    assert_eq!(
+        profiler.functions(&module),
+        [FunctionProfile {
+            fn_index: 0,
+            name: "func[0]".into(),
+            calls: 4,
+            exclusive: total,
+            inclusive: total,
+        }]
+    );
+
+    let mut stacks = Vec::new();
+    profiler
+        .write_collapsed_stacks(&module, &mut stacks)
+        .unwrap();
+    assert_eq!(
+        String::from_utf8(stacks).unwrap(),
+        [
+            "func[0] 8",
+            "func[0];func[0] 8",
+            "func[0];func[0];func[0] 8",
+            "func[0];func[0];func[0];func[0] 3",
+            "",
+        ]
+        .join("\n")
+    );
+}
+
+#[test]
+fn test_profile_trap() {
+    let arena = Bump::new();
+    let mut module = WasmModule::new(&arena);
+    let signature = || Signature {
+        param_types: bumpalo::vec![in &arena],
+        ret_type: None,
+    };
+    create_exported_function_no_locals(&mut module, "outer", signature(), |buf| {
+        buf.append_u8(OpCode::CALL as u8);
+        buf.encode_u32(1);
+        buf.append_u8(OpCode::END as u8);
+    });
+    create_exported_function_no_locals(&mut module, "inner", signature(), |buf| {
+        buf.append_u8(OpCode::NOP as u8);
+        buf.append_u8(OpCode::UNREACHABLE as u8);
+        buf.append_u8(OpCode::END as u8);
+    });
+
+    let mut inst =
+        Instance::for_module(&arena, &module, DefaultImportDispatcher::default(), false).unwrap();
+    inst.profiler = Some(Profiler::default());
+
+    assert!(inst.call_export("outer", []).is_err());
+
+    // The trap unwinds the profiler's call stack, so the next call starts from the root again
+    inst.call_export("inner", []).unwrap_err();
+
+    let profiler = inst.profiler.as_ref().unwrap();
+    let inclusive: Vec<(usize, u64)> = profiler
+        .functions(&module)
+        .iter()
+        .map(|f| (f.fn_index, f.inclusive))
+        .collect();
+    assert_eq!(inclusive, [(1, 4), (0, 3)]);
+
+    let mut stacks = Vec::new();
+    profiler
+        .write_collapsed_stacks(&module, &mut stacks)
+        .unwrap();
+    assert_eq!(
+        String::from_utf8(stacks).unwrap(),
+        "func[0] 1\nfunc[0];func[1] 2\nfunc[1] 2\n"
+    );
+}
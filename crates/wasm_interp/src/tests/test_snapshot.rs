@@ -0,0 +1,276 @@
+#![cfg(test)]
+
+use super::create_exported_function_no_locals;
+use crate::{CallError, DefaultImportDispatcher, Instance, Snapshot, Trap};
+use bumpalo::{collections::Vec, Bump};
+use roc_wasm_module::{
+    opcodes::OpCode,
+    sections::{GlobalSection, MemorySection},
+    ConstExpr, Global, GlobalType, SerialBuffer, Serialize, Signature, Value, ValueType,
+    WasmModule,
+};
+
+/// A module with a mutable i32 global, one page of memory, and these functions:
+/// - `sum(n)`: adds up the numbers from 1 to n in a loop, keeping the total in the global
+/// - `outer(n)`: calls `sum(n)` and adds 1000, so that there's more than one call frame
+/// - `init()`: stores 42 at address 100 and 7 at address 40000, and sets the global to 5
+/// - `get()`: returns the value at address 100 plus the global
+fn test_module(arena: &Bump) -> WasmModule<'_> {
+    let mut module = WasmModule::new(arena);
+    module.memory = MemorySection::new(arena, MemorySection::PAGE_SIZE);
+    module.global = GlobalSection::new(arena);
+    module.global.append(Global {
+        ty: GlobalType {
+            value_type: ValueType::I32,
+            is_mutable: true,
+        },
+        init: ConstExpr::I32(0),
+    });
+
+    let signature = Signature {
+        param_types: bumpalo::vec![in arena; ValueType::I32],
+        ret_type: Some(ValueType::I32),
+    };
+    create_exported_function_no_locals(&mut module, "sum", signature, |buf| {
+        buf.append_u8(OpCode::I32CONST as u8);
+        buf.encode_i32(0);
+        buf.append_u8(OpCode::SETGLOBAL as u8);
+        buf.encode_u32(0);
+
+        buf.append_u8(OpCode::LOOP as u8);
+        buf.append_u8(ValueType::VOID);
+        buf.append_u8(OpCode::GETGLOBAL as u8);
+        buf.encode_u32(0);
+        buf.append_u8(OpCode::GETLOCAL as u8);
+        buf.encode_u32(0);
+        buf.append_u8(OpCode::I32ADD as u8);
+        buf.append_u8(OpCode::SETGLOBAL as u8);
+        buf.encode_u32(0);
+        buf.append_u8(OpCode::GETLOCAL as u8);
+        buf.encode_u32(0);
+        buf.append_u8(OpCode::I32CONST as u8);
+        buf.encode_i32(1);
+        buf.append_u8(OpCode::I32SUB as u8);
+        buf.append_u8(OpCode::TEELOCAL as u8);
+        buf.encode_u32(0);
+        buf.append_u8(OpCode::BRIF as u8);
+        buf.encode_u32(0);
+        buf.append_u8(OpCode::END as u8);
+
+        buf.append_u8(OpCode::GETGLOBAL as u8);
+        buf.encode_u32(0);
+        buf.append_u8(OpCode::END as u8);
+    });
+
+    let signature = Signature {
+        param_types: bumpalo::vec![in arena; ValueType::I32],
+        ret_type: Some(ValueType::I32),
+    };
+    create_exported_function_no_locals(&mut module, "outer", signature, |buf| {
+        buf.append_u8(OpCode::GETLOCAL as u8);
+        buf.encode_u32(0);
+        buf.append_u8(OpCode::CALL as u8);
+        buf.encode_u32(0);
+        buf.append_u8(OpCode::I32CONST as u8);
+        buf.encode_i32(1000);
+        buf.append_u8(OpCode::I32ADD as u8);
+        buf.append_u8(OpCode::END as u8);
+    });
+
+    let signature = Signature {
+        param_types: bumpalo::vec![in arena],
+        ret_type: None,
+    };
+    create_exported_function_no_locals(&mut module, "init", signature, |buf| {
+        for (addr, value) in [(100, 42), (40000, 7)] {
+            buf.append_u8(OpCode::I32CONST as u8);
+            buf.encode_i32(addr);
+            buf.append_u8(OpCode::I32CONST as u8);
+            buf.encode_i32(value);
+            buf.append_u8(OpCode::I32STORE as u8);
+            buf.encode_u32(2);
+            buf.encode_u32(0);
+        }
+        buf.append_u8(OpCode::I32CONST as u8);
+        buf.encode_i32(5);
+        buf.append_u8(OpCode::SETGLOBAL as u8);
+        buf.encode_u32(0);
+        buf.append_u8(OpCode::END as u8);
+    });
+
+    let signature = Signature {
+        param_types: bumpalo::vec![in arena],
+        ret_type: Some(ValueType::I32),
+    };
+    create_exported_function_no_locals(&mut module, "get", signature, |buf| {
+        buf.append_u8(OpCode::I32CONST as u8);
+        buf.encode_i32(100);
+        buf.append_u8(OpCode::I32LOAD as u8);
+        buf.encode_u32(2);
+        buf.encode_u32(0);
+        buf.append_u8(OpCode::GETGLOBAL as u8);
+        buf.encode_u32(0);
+        buf.append_u8(OpCode::I32ADD as u8);
+        buf.append_u8(OpCode::END as u8);
+    });
+
+    module
+}
+
+fn round_trip(snapshot: &Snapshot) -> Snapshot {
+    let mut bytes = std::vec::Vec::new();
+    snapshot.serialize(&mut bytes);
+    Snapshot::from_bytes(&bytes).unwrap()
+}
+
+#[test]
+fn test_snapshot_restore() {
+    let arena = Bump::new();
+    let module = test_module(&arena);
+    let mut inst =
+        Instance::for_module(&arena, &module, DefaultImportDispatcher::default(), false).unwrap();
+
+    inst.call_export("init", []).unwrap();
+    let snapshot = inst.snapshot();
+    assert!(!snapshot.is_call_in_progress());
+    assert_eq!(round_trip(&snapshot), snapshot);
+
+    inst.call_export("sum", [Value::I32(10)]).unwrap();
+    inst.memory[100] = 0;
+    assert_eq!(inst.call_export("get", []).unwrap(), Some(Value::I32(55)));
+
+    inst.restore(&snapshot).unwrap();
+    assert_eq!(inst.call_export("get", []).unwrap(), Some(Value::I32(47)));
+}
+
+#[test]
+fn test_snapshot_resume() {
+    let arena = Bump::new();
+    let module = test_module(&arena);
+
+    let mut uninterrupted =
+        Instance::for_module(&arena, &module, DefaultImportDispatcher::default(), false).unwrap();
+    let expected = uninterrupted.call_export("outer", [Value::I32(100)]);
+    assert_eq!(expected, Ok(Some(Value::I32(6050))));
+
+    let mut inst =
+        Instance::for_module(&arena, &module, DefaultImportDispatcher::default(), false).unwrap();
+    inst.fuel = Some(123);
+    match inst.call_export("outer", [Value::I32(100)]) {
+        Err(CallError::Trap(report)) => {
+            assert_eq!(report.trap, Trap::FuelExhausted);
+            assert_eq!(report.call_stack.len(), 2);
+        }
+        other => panic!("Expected a trap but got {other:?}"),
+    }
+    let snapshot = round_trip(&inst.snapshot());
+    assert!(snapshot.is_call_in_progress());
+
+    // Resume in a fresh instance, as if reproducing the run from a checkpoint file
+    let mut resumed =
+        Instance::for_module(&arena, &module, DefaultImportDispatcher::default(), false).unwrap();
+    resumed.restore(&snapshot).unwrap();
+    assert_eq!(resumed.resume(), expected);
+
+    // Resume the original instance by adding more fuel
+    inst.fuel = None;
+    assert_eq!(inst.resume(), expected);
+    assert!(matches!(inst.resume(), Err(CallError::Invalid(_))));
+}
+
+#[test]
+fn test_snapshot_apply_to_module() {
+    let arena = Bump::new();
+    let module = test_module(&arena);
+    let mut inst =
+        Instance::for_module(&arena, &module, DefaultImportDispatcher::default(), false).unwrap();
+    inst.call_export("init", []).unwrap();
+    let snapshot = inst.snapshot();
+
+    let mut initialized = test_module(&arena);
+    snapshot.apply_to_module(&arena, &mut initialized).unwrap();
+    let mut bytes = Vec::with_capacity_in(initialized.size(), &arena);
+    initialized.serialize(&mut bytes);
+
+    let preloaded = WasmModule::preload(&arena, &bytes, false).unwrap();
+    let mut fresh = Instance::for_module(
+        &arena,
+        &preloaded,
+        DefaultImportDispatcher::default(),
+        false,
+    )
+    .unwrap();
+    assert_eq!(fresh.globals.as_slice(), &[Value::I32(5)]);
+    assert_eq!(fresh.memory.as_slice(), snapshot.memory.as_slice());
+    assert_eq!(fresh.call_export("get", []).unwrap(), Some(Value::I32(47)));
+
+    // The two stores are far apart, so the zeros between them should not be in the data section
+    assert!(initialized.data.size() < 100);
+}
+
+#[test]
+fn test_snapshot_invalid() {
+    let arena = Bump::new();
+    let module = test_module(&arena);
+    let mut inst =
+        Instance::for_module(&arena, &module, DefaultImportDispatcher::default(), false).unwrap();
+    inst.fuel = Some(50);
+    inst.call_export("sum", [Value::I32(100)]).unwrap_err();
+
+    let mut bytes = std::vec::Vec::new();
+    inst.snapshot().serialize(&mut bytes);
+    for len in 0..bytes.len() {
+        assert!(Snapshot::from_bytes(&bytes[..len]).is_err());
+    }
+    assert!(Snapshot::from_bytes(b"not a snapshot").is_err());
+
+    let mut snapshot = inst.snapshot();
+    snapshot.globals.push(Value::I64(1));
+    assert!(inst.restore(&snapshot).is_err());
+
+    let mut snapshot = inst.snapshot();
+    snapshot.globals[0] = Value::F64(1.0);
+    assert!(inst.restore(&snapshot).is_err());
+
+    let mut snapshot = inst.snapshot();
+    snapshot.memory.push(0);
+    assert!(inst.restore(&snapshot).is_err());
+
+    let mut snapshot = inst.snapshot();
+    snapshot.current_frame.fn_index = 99;
+    assert!(inst.restore(&snapshot).is_err());
+
+    // A truncated value stack, with blocks and locals that point past the end of it
+    let mut snapshot = inst.snapshot();
+    assert!(snapshot.is_call_in_progress());
+    snapshot.values.clear();
+    assert!(inst.restore(&snapshot).is_err());
+
+    let mut snapshot = inst.snapshot();
+    snapshot.blocks.last_mut().unwrap().vstack = snapshot.values.len() + 1;
+    assert!(inst.restore(&snapshot).is_err());
+
+    let mut snapshot = inst.snapshot();
+    snapshot.blocks.swap(0, 1);
+    assert!(inst.restore(&snapshot).is_err());
+
+    let mut snapshot = inst.snapshot();
+    snapshot.current_frame.locals_count = usize::MAX;
+    assert!(inst.restore(&snapshot).is_err());
+
+    let mut snapshot = inst.snapshot();
+    snapshot.current_frame.locals_start = snapshot.values.len();
+    assert!(inst.restore(&snapshot).is_err());
+
+    let mut snapshot = inst.snapshot();
+    snapshot.current_frame.body_block_index = 0;
+    assert!(inst.restore(&snapshot).is_err());
+
+    let mut snapshot = inst.snapshot();
+    snapshot.current_frame.body_block_index = snapshot.blocks.len();
+    assert!(inst.restore(&snapshot).is_err());
+
+    // Rejected snapshots leave the instance as it was, so it can still finish the call
+    inst.fuel = None;
+    assert_eq!(inst.resume(), Ok(Some(Value::I32(5050))));
+}
@@ -0,0 +1,213 @@
+#![cfg(test)]
+
+use super::create_exported_function_no_locals;
+use crate::{CallError, DefaultImportDispatcher, Instance, StackFrame, Trap, TrapReport};
+use bumpalo::Bump;
+use roc_wasm_module::{
+    opcodes::OpCode, sections::MemorySection, SerialBuffer, Signature, Value, ValueType, WasmModule,
+};
+
+fn expect_trap(result: Result<Option<Value>, CallError>) -> TrapReport {
+    match result {
+        Err(CallError::Trap(report)) => report,
+        other => panic!("Expected a trap but got {other:?}"),
+    }
+}
+
+fn infinite_loop_module(arena: &Bump) -> WasmModule<'_> {
+    let mut module = WasmModule::new(arena);
+    let signature = Signature {
+        param_types: bumpalo::vec![in arena],
+        ret_type: None,
+    };
+    create_exported_function_no_locals(&mut module, "test", signature, |buf| {
+        buf.append_u8(OpCode::LOOP as u8);
+        buf.append_u8(0x40); // blocktype: no value
+        buf.append_u8(OpCode::BR as u8);
+        buf.encode_u32(0);
+        buf.append_u8(OpCode::END as u8);
+        buf.append_u8(OpCode::END as u8);
+    });
+    module
+}
+
+#[test]
+fn test_fuel_exhausted() {
+    let arena = Bump::new();
+    let module = infinite_loop_module(&arena);
+
+    let mut inst =
+        Instance::for_module(&arena, &module, DefaultImportDispatcher::default(), false).unwrap();
+    inst.fuel = Some(1000);
+
+    let report = expect_trap(inst.call_export("test", []));
+    assert_eq!(report.trap, Trap::FuelExhausted);
+    assert_eq!(inst.fuel, Some(0));
+}
+
+#[test]
+fn test_fuel_remaining() {
+    let arena = Bump::new();
+    let mut module = WasmModule::new(&arena);
+    let signature = Signature {
+        param_types: bumpalo::vec![in &arena],
+        ret_type: Some(ValueType::I32),
+    };
+    create_exported_function_no_locals(&mut module, "test", signature, |buf| {
+        buf.append_u8(OpCode::I32CONST as u8);
+        buf.encode_i32(2);
+        buf.append_u8(OpCode::I32CONST as u8);
+        buf.encode_i32(2);
+        buf.append_u8(OpCode::I32ADD as u8);
+        buf.append_u8(OpCode::END as u8);
+    });
+
+    let mut inst =
+        Instance::for_module(&arena, &module, DefaultImportDispatcher::default(), false).unwrap();
+    inst.fuel = Some(10);
+
+    let result = inst.call_export("test", []).unwrap();
+    assert_eq!(result, Some(Value::I32(4)));
+    assert_eq!(inst.fuel, Some(6));
+}
+
+#[test]
+fn test_max_memory_pages() {
+    let arena = Bump::new();
+    let mut module = WasmModule::new(&arena);
+    module.memory = MemorySection::new(&arena, MemorySection::PAGE_SIZE);
+    let signature = Signature {
+        param_types: bumpalo::vec![in &arena; ValueType::I32],
+        ret_type: Some(ValueType::I32),
+    };
+    create_exported_function_no_locals(&mut module, "grow", signature, |buf| {
+        buf.append_u8(OpCode::GETLOCAL as u8);
+        buf.encode_u32(0);
+        buf.append_u8(OpCode::GROWMEMORY as u8);
+        buf.encode_u32(0);
+        buf.append_u8(OpCode::END as u8);
+    });
+
+    let mut inst =
+        Instance::for_module(&arena, &module, DefaultImportDispatcher::default(), false).unwrap();
+    inst.max_memory_pages = Some(3);
+
+    let result = inst.call_export("grow", [Value::I32(3)]).unwrap();
+    assert_eq!(result, Some(Value::I32(-1)));
+    assert_eq!(inst.memory.len(), MemorySection::PAGE_SIZE as usize);
+
+    let result = inst.call_export("grow", [Value::I32(2)]).unwrap();
+    assert_eq!(result, Some(Value::I32(1)));
+    assert_eq!(inst.memory.len(), 3 * MemorySection::PAGE_SIZE as usize);
+}
+
+#[test]
+fn test_load_out_of_bounds() {
+    let arena = Bump::new();
+    let mut module = WasmModule::new(&arena);
+    module.memory = MemorySection::new(&arena, MemorySection::PAGE_SIZE);
+    let signature = Signature {
+        param_types: bumpalo::vec![in &arena],
+        ret_type: Some(ValueType::I32),
+    };
+    let addr = MemorySection::PAGE_SIZE - 2;
+    create_exported_function_no_locals(&mut module, "test", signature, |buf| {
+        buf.append_u8(OpCode::I32CONST as u8);
+        buf.encode_i32(addr as i32);
+        buf.append_u8(OpCode::I32LOAD as u8);
+        buf.encode_u32(2); // alignment
+        buf.encode_u32(0); // offset
+        buf.append_u8(OpCode::END as u8);
+    });
+
+    let mut inst =
+        Instance::for_module(&arena, &module, DefaultImportDispatcher::default(), false).unwrap();
+
+    let report = expect_trap(inst.call_export("test", []));
+    assert_eq!(
+        report.trap,
+        Trap::MemoryAccessOutOfBounds(addr as u64, 4, MemorySection::PAGE_SIZE)
+    );
+}
+
+#[test]
+fn test_stack_exhausted() {
+    let arena = Bump::new();
+    let mut module = WasmModule::new(&arena);
+    let signature = Signature {
+        param_types: bumpalo::vec![in &arena],
+        ret_type: None,
+    };
+    create_exported_function_no_locals(&mut module, "recurse", signature, |buf| {
+        buf.append_u8(OpCode::CALL as u8);
+        buf.encode_u32(0);
+        buf.append_u8(OpCode::END as u8);
+    });
+    module.names.function_names.push((0, "recurse"));
+
+    let mut inst =
+        Instance::for_module(&arena, &module, DefaultImportDispatcher::default(), false).unwrap();
+    inst.max_call_depth = 100;
+
+    let report = expect_trap(inst.call_export("recurse", []));
+    assert_eq!(report.trap, Trap::StackExhausted);
+    assert_eq!(report.call_stack.len(), 101);
+    assert!(report
+        .call_stack
+        .iter()
+        .all(|frame| frame.fn_name.as_deref() == Some("recurse")));
+}
+
+#[test]
+fn test_trap_call_stack() {
+    let arena = Bump::new();
+    let mut module = WasmModule::new(&arena);
+    let signature = || Signature {
+        param_types: bumpalo::vec![in &arena],
+        ret_type: None,
+    };
+
+    // func[0]: calls func[1]
+    create_exported_function_no_locals(&mut module, "outer", signature(), |buf| {
+        buf.append_u8(OpCode::NOP as u8);
+        buf.append_u8(OpCode::CALL as u8);
+        buf.encode_u32(1);
+        buf.append_u8(OpCode::END as u8);
+    });
+    // func[1]: traps
+    create_exported_function_no_locals(&mut module, "inner", signature(), |buf| {
+        buf.append_u8(OpCode::UNREACHABLE as u8);
+        buf.append_u8(OpCode::END as u8);
+    });
+    module.names.function_names.push((0, "outer"));
+    module.names.function_names.push((1, "inner"));
+
+    let call_addr = module.code.function_offsets[0] as usize + 5 + 1 + 1; // padded length, locals, NOP
+    let unreachable_addr = module.code.function_offsets[1] as usize + 5 + 1; // padded length, locals
+
+    let mut inst =
+        Instance::for_module(&arena, &module, DefaultImportDispatcher::default(), false).unwrap();
+
+    let report = expect_trap(inst.call_export("outer", []));
+    assert_eq!(report.trap, Trap::UnreachableOp);
+    assert_eq!(report.file_offset, unreachable_addr);
+    assert_eq!(
+        report.call_stack,
+        [
+            StackFrame {
+                fn_index: 1,
+                fn_name: Some("inner".into()),
+                file_offset: unreachable_addr,
+            },
+            StackFrame {
+                fn_index: 0,
+                fn_name: Some("outer".into()),
+                file_offset: call_addr,
+            },
+        ]
+    );
+
+    let message = report.to_string();
+    assert!(message.contains("func[1]  inner  at"), "{message}");
+    assert!(message.contains("func[0]  outer  at"), "{message}");
+}
@@ -0,0 +1,609 @@
+use crate::wasi::{Errno, FileType, WasiClock, WasiDispatcher};
+use roc_wasm_module::Value;
+use std::fs;
+use std::time::{Duration, Instant};
+
+// Where the tests put things in the app's memory
+const OUT: usize = 0;
+const IOVS: usize = 64;
+const DATA: usize = 128;
+const PATH: usize = 1024;
+const PATH2: usize = 1536;
+
+const PREOPEN_FD: i32 = 3;
+const RIGHTS_READ: i64 = 1 << 1;
+const RIGHTS_READ_WRITE: i64 = (1 << 1) | (1 << 6);
+const OFLAGS_CREAT: i32 = 1;
+const OFLAGS_DIRECTORY: i32 = 2;
+const LOOKUPFLAGS_SYMLINK_FOLLOW: i32 = 1;
+
+fn call(wasi: &mut WasiDispatcher, memory: &mut [u8], name: &str, args: &[Value]) -> Errno {
+    match wasi.dispatch(name, args, memory) {
+        Some(Value::I32(code)) => errno(code),
+        other => panic!("{name} returned {other:?}"),
+    }
+}
+
+fn errno(code: i32) -> Errno {
+    let all = [
+        Errno::Success,
+        Errno::Badf,
+        Errno::Exist,
+        Errno::Inval,
+        Errno::Isdir,
+        Errno::Loop,
+        Errno::Noent,
+        Errno::Notcapable,
+        Errno::Notdir,
+        Errno::Notempty,
+    ];
+
+    *all.iter()
+        .find(|errno| **errno as i32 == code)
+        .unwrap_or_else(|| panic!("unexpected errno {code}"))
+}
+
+fn read_u32(memory: &[u8], addr: usize) -> u32 {
+    u32::from_le_bytes(memory[addr..][..4].try_into().unwrap())
+}
+
+fn read_u64(memory: &[u8], addr: usize) -> u64 {
+    u64::from_le_bytes(memory[addr..][..8].try_into().unwrap())
+}
+
+fn put_path(memory: &mut [u8], addr: usize, path: &str) -> [Value; 2] {
+    memory[addr..][..path.len()].copy_from_slice(path.as_bytes());
+    [Value::I32(addr as i32), Value::I32(path.len() as i32)]
+}
+
+fn open(
+    wasi: &mut WasiDispatcher,
+    memory: &mut [u8],
+    path: &str,
+    oflags: i32,
+    rights: i64,
+) -> Result<i32, Errno> {
+    open_with_lookup_flags(
+        wasi,
+        memory,
+        path,
+        LOOKUPFLAGS_SYMLINK_FOLLOW,
+        oflags,
+        rights,
+    )
+}
+
+fn open_with_lookup_flags(
+    wasi: &mut WasiDispatcher,
+    memory: &mut [u8],
+    path: &str,
+    lookup_flags: i32,
+    oflags: i32,
+    rights: i64,
+) -> Result<i32, Errno> {
+    let [ptr_path, path_len] = put_path(memory, PATH, path);
+    let args = [
+        Value::I32(PREOPEN_FD),
+        Value::I32(lookup_flags),
+        ptr_path,
+        path_len,
+        Value::I32(oflags),
+        Value::I64(rights),
+        Value::I64(rights),
+        Value::I32(0),
+        Value::I32(OUT as i32),
+    ];
+
+    match call(wasi, memory, "path_open", &args) {
+        Errno::Success => Ok(read_u32(memory, OUT) as i32),
+        errno => Err(errno),
+    }
+}
+
+fn read(wasi: &mut WasiDispatcher, memory: &mut [u8], fd: i32, len: usize) -> Vec<u8> {
+    memory[IOVS..][..4].copy_from_slice(&(DATA as u32).to_le_bytes());
+    memory[IOVS + 4..][..4].copy_from_slice(&(len as u32).to_le_bytes());
+    let args = [
+        Value::I32(fd),
+        Value::I32(IOVS as i32),
+        Value::I32(1),
+        Value::I32(OUT as i32),
+    ];
+
+    assert_eq!(call(wasi, memory, "fd_read", &args), Errno::Success);
+    let n_read = read_u32(memory, OUT) as usize;
+    memory[DATA..][..n_read].to_vec()
+}
+
+fn write(wasi: &mut WasiDispatcher, memory: &mut [u8], fd: i32, bytes: &[u8]) {
+    memory[DATA..][..bytes.len()].copy_from_slice(bytes);
+    memory[IOVS..][..4].copy_from_slice(&(DATA as u32).to_le_bytes());
+    memory[IOVS + 4..][..4].copy_from_slice(&(bytes.len() as u32).to_le_bytes());
+    let args = [
+        Value::I32(fd),
+        Value::I32(IOVS as i32),
+        Value::I32(1),
+        Value::I32(OUT as i32),
+    ];
+
+    assert_eq!(call(wasi, memory, "fd_write", &args), Errno::Success);
+    assert_eq!(read_u32(memory, OUT) as usize, bytes.len());
+}
+
+fn dir_entry_names(wasi: &mut WasiDispatcher, memory: &mut [u8], fd: i32) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut cookie = 0;
+
+    // A small buffer, to check that reading in batches works
+    let buf_len = 40;
+
+    loop {
+        let args = [
+            Value::I32(fd),
+            Value::I32(DATA as i32),
+            Value::I32(buf_len),
+            Value::I64(cookie),
+            Value::I32(OUT as i32),
+        ];
+        assert_eq!(call(wasi, memory, "fd_readdir", &args), Errno::Success);
+
+        let used = read_u32(memory, OUT) as usize;
+        let mut offset = DATA;
+
+        while offset + 24 <= DATA + used {
+            let name_len = read_u32(memory, offset + 16) as usize;
+            if offset + 24 + name_len > DATA + used {
+                break;
+            }
+
+            cookie = read_u64(memory, offset) as i64;
+            names.push(String::from_utf8(memory[offset + 24..][..name_len].to_vec()).unwrap());
+            offset += 24 + name_len;
+        }
+
+        if used < buf_len as usize {
+            return names;
+        }
+    }
+}
+
+#[test]
+fn test_preopened_dir() {
+    let host_dir = tempfile::tempdir().unwrap();
+    let mut wasi = WasiDispatcher::default();
+    let mut memory = vec![0; 2048];
+
+    wasi.preopen_dir(host_dir.path(), "/sandbox").unwrap();
+
+    let args = [Value::I32(PREOPEN_FD), Value::I32(OUT as i32)];
+    assert_eq!(
+        call(&mut wasi, &mut memory, "fd_prestat_get", &args),
+        Errno::Success
+    );
+    assert_eq!(read_u32(&memory, OUT + 4), 8);
+
+    let args = [
+        Value::I32(PREOPEN_FD),
+        Value::I32(DATA as i32),
+        Value::I32(8),
+    ];
+    assert_eq!(
+        call(&mut wasi, &mut memory, "fd_prestat_dir_name", &args),
+        Errno::Success
+    );
+    assert_eq!(&memory[DATA..][..8], b"/sandbox");
+
+    // WASI libc stops looking for preopens at the first file descriptor that isn't one
+    let args = [Value::I32(PREOPEN_FD + 1), Value::I32(OUT as i32)];
+    assert_eq!(
+        call(&mut wasi, &mut memory, "fd_prestat_get", &args),
+        Errno::Badf
+    );
+}
+
+#[test]
+fn test_files_and_directories() {
+    let host_dir = tempfile::tempdir().unwrap();
+    fs::write(host_dir.path().join("hello.txt"), "Hello, World!").unwrap();
+
+    let mut wasi = WasiDispatcher::default();
+    let mut memory = vec![0; 2048];
+    wasi.preopen_dir(host_dir.path(), ".").unwrap();
+
+    // Read part of a file, then seek and read the rest
+    let fd = open(&mut wasi, &mut memory, "hello.txt", 0, RIGHTS_READ).unwrap();
+    assert_eq!(fd, PREOPEN_FD + 1);
+    assert_eq!(read(&mut wasi, &mut memory, fd, 5), b"Hello");
+
+    let args = [
+        Value::I32(fd),
+        Value::I64(-6),
+        Value::I32(2), // from the end
+        Value::I32(OUT as i32),
+    ];
+    assert_eq!(
+        call(&mut wasi, &mut memory, "fd_seek", &args),
+        Errno::Success
+    );
+    assert_eq!(read_u64(&memory, OUT), 7);
+    assert_eq!(read(&mut wasi, &mut memory, fd, 100), b"World!");
+
+    let args = [Value::I32(fd), Value::I32(DATA as i32)];
+    assert_eq!(
+        call(&mut wasi, &mut memory, "fd_filestat_get", &args),
+        Errno::Success
+    );
+    assert_eq!(memory[DATA + 16], FileType::RegularFile as u8);
+    assert_eq!(read_u64(&memory, DATA + 32), 13);
+
+    assert_eq!(
+        call(&mut wasi, &mut memory, "fd_close", &[Value::I32(fd)]),
+        Errno::Success
+    );
+    assert_eq!(
+        call(&mut wasi, &mut memory, "fd_close", &[Value::I32(fd)]),
+        Errno::Badf
+    );
+
+    // Create a directory with a new file in it
+    let [ptr_path, path_len] = put_path(&mut memory, PATH, "sub");
+    let args = [Value::I32(PREOPEN_FD), ptr_path, path_len];
+    assert_eq!(
+        call(&mut wasi, &mut memory, "path_create_directory", &args),
+        Errno::Success
+    );
+    assert_eq!(
+        call(&mut wasi, &mut memory, "path_create_directory", &args),
+        Errno::Exist
+    );
+
+    let fd = open(
+        &mut wasi,
+        &mut memory,
+        "sub/new.txt",
+        OFLAGS_CREAT,
+        RIGHTS_READ_WRITE,
+    )
+    .unwrap();
+    // The file descriptor that was closed gets reused
+    assert_eq!(fd, PREOPEN_FD + 1);
+    write(&mut wasi, &mut memory, fd, b"new file");
+    assert_eq!(
+        call(&mut wasi, &mut memory, "fd_close", &[Value::I32(fd)]),
+        Errno::Success
+    );
+    assert_eq!(
+        fs::read_to_string(host_dir.path().join("sub").join("new.txt")).unwrap(),
+        "new file"
+    );
+
+    let dir_fd = open(&mut wasi, &mut memory, "sub", OFLAGS_DIRECTORY, RIGHTS_READ).unwrap();
+    assert_eq!(
+        dir_entry_names(&mut wasi, &mut memory, dir_fd),
+        [".", "..", "new.txt"]
+    );
+    assert_eq!(
+        dir_entry_names(&mut wasi, &mut memory, PREOPEN_FD),
+        [".", "..", "hello.txt", "sub"]
+    );
+    assert_eq!(
+        open(
+            &mut wasi,
+            &mut memory,
+            "hello.txt",
+            OFLAGS_DIRECTORY,
+            RIGHTS_READ
+        ),
+        Err(Errno::Notdir)
+    );
+
+    // Remove them again
+    let [ptr_path, path_len] = put_path(&mut memory, PATH, "sub");
+    let args = [Value::I32(PREOPEN_FD), ptr_path, path_len];
+    assert_eq!(
+        call(&mut wasi, &mut memory, "path_remove_directory", &args),
+        Errno::Notempty
+    );
+    assert_eq!(
+        call(&mut wasi, &mut memory, "path_unlink_file", &args),
+        Errno::Isdir
+    );
+
+    let [ptr_path, path_len] = put_path(&mut memory, PATH, "sub/new.txt");
+    let args = [Value::I32(PREOPEN_FD), ptr_path, path_len];
+    assert_eq!(
+        call(&mut wasi, &mut memory, "path_unlink_file", &args),
+        Errno::Success
+    );
+    assert_eq!(
+        call(&mut wasi, &mut memory, "path_unlink_file", &args),
+        Errno::Noent
+    );
+
+    let [ptr_path, path_len] = put_path(&mut memory, PATH, "sub");
+    let args = [Value::I32(PREOPEN_FD), ptr_path, path_len];
+    assert_eq!(
+        call(&mut wasi, &mut memory, "path_remove_directory", &args),
+        Errno::Success
+    );
+    assert!(!host_dir.path().join("sub").exists());
+}
+
+#[test]
+fn test_rename() {
+    let host_dir = tempfile::tempdir().unwrap();
+    fs::write(host_dir.path().join("old.txt"), "contents").unwrap();
+
+    let mut wasi = WasiDispatcher::default();
+    let mut memory = vec![0; 2048];
+    wasi.preopen_dir(host_dir.path(), ".").unwrap();
+
+    let [ptr_old, old_len] = put_path(&mut memory, PATH, "old.txt");
+    let [ptr_new, new_len] = put_path(&mut memory, PATH2, "./new.txt");
+    let args = [
+        Value::I32(PREOPEN_FD),
+        ptr_old,
+        old_len,
+        Value::I32(PREOPEN_FD),
+        ptr_new,
+        new_len,
+    ];
+    assert_eq!(
+        call(&mut wasi, &mut memory, "path_rename", &args),
+        Errno::Success
+    );
+    assert_eq!(
+        fs::read_to_string(host_dir.path().join("new.txt")).unwrap(),
+        "contents"
+    );
+}
+
+#[test]
+fn test_sandbox() {
+    let outer_dir = tempfile::tempdir().unwrap();
+    let host_dir = outer_dir.path().join("sandbox");
+    fs::create_dir(&host_dir).unwrap();
+    fs::write(outer_dir.path().join("secret.txt"), "secret").unwrap();
+
+    let mut wasi = WasiDispatcher::default();
+    let mut memory = vec![0; 2048];
+    wasi.preopen_dir(&host_dir, ".").unwrap();
+
+    for path in ["../secret.txt", "a/../../secret.txt", "/etc/passwd", ".."] {
+        assert_eq!(
+            open(&mut wasi, &mut memory, path, 0, RIGHTS_READ),
+            Err(Errno::Notcapable),
+            "{path}"
+        );
+    }
+
+    assert_eq!(
+        open(&mut wasi, &mut memory, "missing.txt", 0, RIGHTS_READ),
+        Err(Errno::Noent)
+    );
+
+    #[cfg(unix)]
+    {
+        std::os::unix::fs::symlink(outer_dir.path(), host_dir.join("escape")).unwrap();
+
+        assert_eq!(
+            open(&mut wasi, &mut memory, "escape/secret.txt", 0, RIGHTS_READ),
+            Err(Errno::Notcapable)
+        );
+        assert_eq!(
+            open(
+                &mut wasi,
+                &mut memory,
+                "escape/new.txt",
+                OFLAGS_CREAT,
+                RIGHTS_READ_WRITE
+            ),
+            Err(Errno::Notcapable)
+        );
+        assert!(!outer_dir.path().join("new.txt").exists());
+
+        // A link straight to a file outside, whether or not the app asks to follow it
+        std::os::unix::fs::symlink(
+            outer_dir.path().join("secret.txt"),
+            host_dir.join("leak.txt"),
+        )
+        .unwrap();
+
+        assert_eq!(
+            open_with_lookup_flags(&mut wasi, &mut memory, "leak.txt", 0, 0, RIGHTS_READ),
+            Err(Errno::Loop)
+        );
+        assert_eq!(
+            open(&mut wasi, &mut memory, "leak.txt", 0, RIGHTS_READ),
+            Err(Errno::Notcapable)
+        );
+
+        // A dangling link to a file outside mustn't let the app create that file
+        std::os::unix::fs::symlink(
+            outer_dir.path().join("created.txt"),
+            host_dir.join("dangling.txt"),
+        )
+        .unwrap();
+
+        assert_eq!(
+            open(
+                &mut wasi,
+                &mut memory,
+                "dangling.txt",
+                OFLAGS_CREAT,
+                RIGHTS_READ_WRITE
+            ),
+            Err(Errno::Notcapable)
+        );
+        assert!(!outer_dir.path().join("created.txt").exists());
+
+        // The app can't make links that point outside either
+        for target in ["../secret.txt", "a/../../secret.txt", "/etc/passwd"] {
+            let [ptr_target, target_len] = put_path(&mut memory, PATH2, target);
+            let [ptr_path, path_len] = put_path(&mut memory, PATH, "link.txt");
+            let args = [
+                ptr_target,
+                target_len,
+                Value::I32(PREOPEN_FD),
+                ptr_path,
+                path_len,
+            ];
+            assert_eq!(
+                call(&mut wasi, &mut memory, "path_symlink", &args),
+                Errno::Notcapable,
+                "{target}"
+            );
+        }
+        assert!(!host_dir.join("link.txt").exists());
+
+        // ...but links inside the sandbox are fine
+        fs::write(host_dir.join("inside.txt"), "inside").unwrap();
+        let [ptr_target, target_len] = put_path(&mut memory, PATH2, "./inside.txt");
+        let [ptr_path, path_len] = put_path(&mut memory, PATH, "link.txt");
+        let args = [
+            ptr_target,
+            target_len,
+            Value::I32(PREOPEN_FD),
+            ptr_path,
+            path_len,
+        ];
+        assert_eq!(
+            call(&mut wasi, &mut memory, "path_symlink", &args),
+            Errno::Success
+        );
+        let fd = open(&mut wasi, &mut memory, "link.txt", 0, RIGHTS_READ).unwrap();
+        assert_eq!(read(&mut wasi, &mut memory, fd, 6), b"inside");
+
+        // The link itself is inside the sandbox, so it can be removed
+        let [ptr_path, path_len] = put_path(&mut memory, PATH, "escape");
+        let args = [Value::I32(PREOPEN_FD), ptr_path, path_len];
+        assert_eq!(
+            call(&mut wasi, &mut memory, "path_unlink_file", &args),
+            Errno::Success
+        );
+        assert!(outer_dir.path().join("secret.txt").exists());
+    }
+}
+
+fn clock_time(wasi: &mut WasiDispatcher, memory: &mut [u8], clock_id: i32) -> u64 {
+    let args = [Value::I32(clock_id), Value::I64(0), Value::I32(OUT as i32)];
+    assert_eq!(call(wasi, memory, "clock_time_get", &args), Errno::Success);
+    read_u64(memory, OUT)
+}
+
+/// Write a poll_oneoff subscription at index `i` of the array at DATA
+fn subscribe(memory: &mut [u8], i: usize, userdata: u64, tag: u8, fd_or_clock: u32, timeout: u64) {
+    let subscription = &mut memory[DATA + 48 * i..][..48];
+    subscription.fill(0);
+    subscription[0..8].copy_from_slice(&userdata.to_le_bytes());
+    subscription[8] = tag;
+    subscription[16..20].copy_from_slice(&fd_or_clock.to_le_bytes());
+    subscription[24..32].copy_from_slice(&timeout.to_le_bytes());
+}
+
+/// Call poll_oneoff with the first `n` subscriptions, and return the (userdata, error, type) of
+/// each event
+fn poll(wasi: &mut WasiDispatcher, memory: &mut [u8], n: usize) -> Vec<(u64, u16, u8)> {
+    let args = [
+        Value::I32(DATA as i32),
+        Value::I32(PATH as i32),
+        Value::I32(n as i32),
+        Value::I32(OUT as i32),
+    ];
+    assert_eq!(call(wasi, memory, "poll_oneoff", &args), Errno::Success);
+
+    (0..read_u32(memory, OUT) as usize)
+        .map(|i| {
+            let event = &memory[PATH + 32 * i..][..32];
+            let userdata = u64::from_le_bytes(event[0..8].try_into().unwrap());
+            let error = u16::from_le_bytes(event[8..10].try_into().unwrap());
+            (userdata, error, event[10])
+        })
+        .collect()
+}
+
+#[test]
+fn test_deterministic_clock() {
+    const MONOTONIC: i32 = 1;
+    const REALTIME: i32 = 0;
+    const CLOCK: u8 = 0;
+    let step = WasiClock::DETERMINISTIC_STEP;
+
+    let mut wasi = WasiDispatcher::default();
+    let mut memory = vec![0; 2048];
+    wasi.clock = WasiClock::deterministic();
+
+    assert_eq!(clock_time(&mut wasi, &mut memory, MONOTONIC), step);
+    assert_eq!(clock_time(&mut wasi, &mut memory, MONOTONIC), 2 * step);
+    assert_eq!(
+        clock_time(&mut wasi, &mut memory, REALTIME),
+        WasiClock::DETERMINISTIC_START + 3 * step
+    );
+
+    let args = [Value::I32(MONOTONIC), Value::I32(OUT as i32)];
+    assert_eq!(
+        call(&mut wasi, &mut memory, "clock_res_get", &args),
+        Errno::Success
+    );
+    assert_eq!(read_u64(&memory, OUT), step);
+
+    let args = [Value::I32(99), Value::I64(0), Value::I32(OUT as i32)];
+    assert_eq!(
+        call(&mut wasi, &mut memory, "clock_time_get", &args),
+        Errno::Inval
+    );
+
+    // Sleeping for an hour skips ahead instead of actually sleeping
+    let hour = 3600 * 1_000_000_000;
+    let before = clock_time(&mut wasi, &mut memory, MONOTONIC);
+    subscribe(&mut memory, 0, 42, CLOCK, MONOTONIC as u32, hour);
+    assert_eq!(poll(&mut wasi, &mut memory, 1), [(42, 0, CLOCK)]);
+    assert!(clock_time(&mut wasi, &mut memory, MONOTONIC) >= before + hour);
+}
+
+#[test]
+fn test_poll_oneoff() {
+    const MONOTONIC: u32 = 1;
+    const CLOCK: u8 = 0;
+    const FD_READ: u8 = 1;
+
+    let mut wasi = WasiDispatcher::default();
+    let mut memory = vec![0; 2048];
+
+    let timeout = Duration::from_millis(5);
+    let start = Instant::now();
+    subscribe(
+        &mut memory,
+        0,
+        1,
+        CLOCK,
+        MONOTONIC,
+        timeout.as_nanos() as u64,
+    );
+    subscribe(
+        &mut memory,
+        1,
+        2,
+        CLOCK,
+        MONOTONIC,
+        10 * timeout.as_nanos() as u64,
+    );
+    assert_eq!(poll(&mut wasi, &mut memory, 2), [(1, 0, CLOCK)]);
+    assert!(start.elapsed() >= timeout);
+
+    // Files are always ready, so this doesn't wait for the timer
+    wasi.clock = WasiClock::deterministic();
+    subscribe(&mut memory, 0, 1, CLOCK, MONOTONIC, 3600 * 1_000_000_000);
+    subscribe(&mut memory, 1, 2, FD_READ, 0, 0);
+    subscribe(&mut memory, 2, 3, FD_READ, 99, 0);
+    assert_eq!(
+        poll(&mut wasi, &mut memory, 3),
+        [(2, 0, FD_READ), (3, Errno::Badf as u16, FD_READ)]
+    );
+    assert!(clock_time(&mut wasi, &mut memory, MONOTONIC as i32) < 1_000_000_000);
+
+    assert_eq!(
+        call(&mut wasi, &mut memory, "sched_yield", &[]),
+        Errno::Success
+    );
+}
@@ -2,7 +2,7 @@ use bumpalo::{collections::Vec, Bump};
 use roc_wasm_module::{Value, ValueType};
 use std::fmt::Debug;
 
-use crate::Error;
+use crate::Trap;
 
 /// Combined storage for the Wasm stack machine and local variables.
 ///
@@ -56,51 +56,51 @@ impl<'a> ValueStore<'a> {
     }
 
     /// Memory addresses etc
-    pub(crate) fn pop_u32(&mut self) -> Result<u32, Error> {
+    pub(crate) fn pop_u32(&mut self) -> Result<u32, Trap> {
         match self.values.pop() {
             Some(Value::I32(x)) => Ok(u32::from_ne_bytes(x.to_ne_bytes())),
-            Some(bad) => Err(Error::Type(ValueType::I32, ValueType::from(bad))),
-            None => Err(Error::StackEmpty),
+            Some(bad) => Err(Trap::Type(ValueType::I32, ValueType::from(bad))),
+            None => Err(Trap::StackEmpty),
         }
     }
 
-    pub(crate) fn pop_i32(&mut self) -> Result<i32, Error> {
+    pub(crate) fn pop_i32(&mut self) -> Result<i32, Trap> {
         match self.values.pop() {
             Some(Value::I32(x)) => Ok(x),
-            Some(bad) => Err(Error::Type(ValueType::I32, ValueType::from(bad))),
-            None => Err(Error::StackEmpty),
+            Some(bad) => Err(Trap::Type(ValueType::I32, ValueType::from(bad))),
+            None => Err(Trap::StackEmpty),
         }
     }
 
-    pub(crate) fn pop_u64(&mut self) -> Result<u64, Error> {
+    pub(crate) fn pop_u64(&mut self) -> Result<u64, Trap> {
         match self.values.pop() {
             Some(Value::I64(x)) => Ok(u64::from_ne_bytes(x.to_ne_bytes())),
-            Some(bad) => Err(Error::Type(ValueType::I64, ValueType::from(bad))),
-            None => Err(Error::StackEmpty),
+            Some(bad) => Err(Trap::Type(ValueType::I64, ValueType::from(bad))),
+            None => Err(Trap::StackEmpty),
         }
     }
 
-    pub(crate) fn pop_i64(&mut self) -> Result<i64, Error> {
+    pub(crate) fn pop_i64(&mut self) -> Result<i64, Trap> {
         match self.values.pop() {
             Some(Value::I64(x)) => Ok(x),
-            Some(bad) => Err(Error::Type(ValueType::I64, ValueType::from(bad))),
-            None => Err(Error::StackEmpty),
+            Some(bad) => Err(Trap::Type(ValueType::I64, ValueType::from(bad))),
+            None => Err(Trap::StackEmpty),
         }
     }
 
-    pub(crate) fn pop_f32(&mut self) -> Result<f32, Error> {
+    pub(crate) fn pop_f32(&mut self) -> Result<f32, Trap> {
         match self.values.pop() {
             Some(Value::F32(x)) => Ok(x),
-            Some(bad) => Err(Error::Type(ValueType::F32, ValueType::from(bad))),
-            None => Err(Error::StackEmpty),
+            Some(bad) => Err(Trap::Type(ValueType::F32, ValueType::from(bad))),
+            None => Err(Trap::StackEmpty),
         }
     }
 
-    pub(crate) fn pop_f64(&mut self) -> Result<f64, Error> {
+    pub(crate) fn pop_f64(&mut self) -> Result<f64, Trap> {
         match self.values.pop() {
             Some(Value::F64(x)) => Ok(x),
-            Some(bad) => Err(Error::Type(ValueType::F64, ValueType::from(bad))),
-            None => Err(Error::StackEmpty),
+            Some(bad) => Err(Trap::Type(ValueType::F64, ValueType::from(bad))),
+            None => Err(Trap::StackEmpty),
         }
     }
 
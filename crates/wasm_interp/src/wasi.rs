@@ -1,7 +1,11 @@
 use rand::prelude::*;
 use roc_wasm_module::Value;
-use std::io::{self, Read, StderrLock, StdoutLock, Write};
+use std::fs::{self, File, Metadata, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, StderrLock, StdoutLock, Write};
+use std::path::{Component, Path, PathBuf};
 use std::process::exit;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 pub const MODULE_NAME: &str = "wasi_snapshot_preview1";
 
@@ -9,6 +13,7 @@ pub struct WasiDispatcher<'a> {
     pub args: &'a [&'a [u8]],
     pub rng: ThreadRng,
     pub files: Vec<WasiFile>,
+    pub clock: WasiClock,
 }
 
 impl Default for WasiDispatcher<'_> {
@@ -22,12 +27,113 @@ pub enum WasiFile {
     WriteOnly(Vec<u8>),
     ReadWrite(Vec<u8>),
     HostSystemFile,
+    /// A host directory that the app was given access to on the command line.
+    /// The app can open paths inside it, but nothing outside of it.
+    PreopenedDir {
+        guest_path: String,
+        dir: HostDir,
+    },
+    /// A directory the app opened inside one of the preopened directories
+    HostDir(HostDir),
+    /// A file the app opened inside one of the preopened directories
+    HostFile(HostFile),
+    /// A file descriptor that the app closed. It can be reused by the next file the app opens.
+    Closed,
+}
+
+pub struct HostDir {
+    /// The canonical path of the preopened directory that this directory is inside of (or is)
+    pub root: PathBuf,
+    pub path: PathBuf,
+}
+
+pub struct HostFile {
+    pub file: File,
+    pub append: bool,
+}
+
+pub enum WasiClock {
+    /// The host's clocks
+    Host { start: Instant },
+    /// A fake clock for reproducible runs. It starts at the same time on every run, moves forward
+    /// by [WasiClock::DETERMINISTIC_STEP] nanoseconds every time the app reads it, and jumps
+    /// forward instead of sleeping.
+    Deterministic { elapsed: u64 },
+}
+
+impl WasiClock {
+    /// 2023-11-14T22:13:20Z, in nanoseconds since the Unix epoch
+    pub const DETERMINISTIC_START: u64 = 1_700_000_000_000_000_000;
+    pub const DETERMINISTIC_STEP: u64 = 1_000;
+
+    pub fn host() -> Self {
+        WasiClock::Host {
+            start: Instant::now(),
+        }
+    }
+
+    pub fn deterministic() -> Self {
+        WasiClock::Deterministic { elapsed: 0 }
+    }
+
+    /// The current time in nanoseconds. The realtime clock counts from the Unix epoch, and the
+    /// others count from when the clock was created.
+    fn now(&mut self, clock_id: u32) -> Result<u64, Errno> {
+        let since_start = match self {
+            WasiClock::Host { start } => {
+                if clock_id == CLOCKID_REALTIME {
+                    return SystemTime::now()
+                        .duration_since(UNIX_EPOCH)
+                        .map(|duration| duration.as_nanos() as u64)
+                        .map_err(|_| Errno::Overflow);
+                }
+
+                start.elapsed().as_nanos() as u64
+            }
+            WasiClock::Deterministic { elapsed } => {
+                *elapsed += Self::DETERMINISTIC_STEP;
+
+                if clock_id == CLOCKID_REALTIME {
+                    return Ok(Self::DETERMINISTIC_START + *elapsed);
+                }
+
+                *elapsed
+            }
+        };
+
+        // We don't measure CPU time, but the time since we started is an upper bound for it
+        match clock_id {
+            CLOCKID_MONOTONIC | CLOCKID_PROCESS_CPUTIME_ID | CLOCKID_THREAD_CPUTIME_ID => {
+                Ok(since_start)
+            }
+            _ => Err(Errno::Inval),
+        }
+    }
+
+    fn resolution(&self, clock_id: u32) -> Result<u64, Errno> {
+        if clock_id > CLOCKID_THREAD_CPUTIME_ID {
+            return Err(Errno::Inval);
+        }
+
+        match self {
+            WasiClock::Host { .. } => Ok(1),
+            WasiClock::Deterministic { .. } => Ok(Self::DETERMINISTIC_STEP),
+        }
+    }
+
+    fn sleep(&mut self, nanos: u64) {
+        match self {
+            WasiClock::Host { .. } => thread::sleep(Duration::from_nanos(nanos)),
+            WasiClock::Deterministic { elapsed } => *elapsed += nanos,
+        }
+    }
 }
 
 enum WriteLock<'a> {
     StdOut(StdoutLock<'a>),
     Stderr(StderrLock<'a>),
     RegularFile(&'a mut Vec<u8>),
+    HostFile(&'a mut File),
 }
 
 /// Implementation of WASI syscalls
@@ -44,7 +150,33 @@ impl<'a> WasiDispatcher<'a> {
                 WasiFile::HostSystemFile,
                 WasiFile::HostSystemFile,
             ],
+            clock: WasiClock::host(),
+        }
+    }
+
+    /// Give the app access to a host directory and everything inside it, at `guest_path`.
+    ///
+    /// WASI libc finds preopened directories by looking at the file descriptors right after
+    /// stdio, so if you replace `files`, do that before calling this.
+    pub fn preopen_dir(&mut self, host_path: &Path, guest_path: &str) -> io::Result<()> {
+        let root = host_path.canonicalize()?;
+
+        if !root.is_dir() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("{} is not a directory", host_path.display()),
+            ));
         }
+
+        self.files.push(WasiFile::PreopenedDir {
+            guest_path: guest_path.to_string(),
+            dir: HostDir {
+                path: root.clone(),
+                root,
+            },
+        });
+
+        Ok(())
     }
 
     pub fn dispatch(
@@ -107,12 +239,61 @@ impl<'a> WasiDispatcher<'a> {
 
                 success_code
             }
-            "clock_res_get" => success_code, // this dummy implementation seems to be good enough for some functions
-            "clock_time_get" => success_code,
-            "fd_advise" => todo!("WASI {}({:?})", function_name, arguments),
-            "fd_allocate" => todo!("WASI {}({:?})", function_name, arguments),
-            "fd_close" => todo!("WASI {}({:?})", function_name, arguments),
-            "fd_datasync" => todo!("WASI {}({:?})", function_name, arguments),
+            "clock_res_get" => {
+                let clock_id = arguments[0].expect_i32().unwrap() as u32;
+                // Out param: the resolution in nanoseconds
+                let ptr_resolution = arguments[1].expect_i32().unwrap() as usize;
+
+                errno_result(self.clock.resolution(clock_id).map(|resolution| {
+                    write_u64(memory, ptr_resolution, resolution);
+                }))
+            }
+            "clock_time_get" => {
+                let clock_id = arguments[0].expect_i32().unwrap() as u32;
+                // precision: the maximum lag, which we can ignore since we always give the exact time
+                // Out param: the time in nanoseconds
+                let ptr_time = arguments[2].expect_i32().unwrap() as usize;
+
+                errno_result(self.clock.now(clock_id).map(|time| {
+                    write_u64(memory, ptr_time, time);
+                }))
+            }
+            "fd_advise" => {
+                // The advice is only a hint, so we can ignore it
+                let fd = arguments[0].expect_i32().unwrap() as usize;
+                errno_result(self.host_file(fd).map(|_| ()))
+            }
+            "fd_allocate" => {
+                let fd = arguments[0].expect_i32().unwrap() as usize;
+                let offset = arguments[1].expect_i64().unwrap() as u64;
+                let len = arguments[2].expect_i64().unwrap() as u64;
+
+                errno_result(self.host_file(fd).and_then(|host_file| {
+                    let size = host_file.file.metadata().map_err(errno_from_io)?.len();
+                    let new_size = offset.checked_add(len).ok_or(Errno::Fbig)?;
+
+                    if new_size > size {
+                        host_file.file.set_len(new_size).map_err(errno_from_io)?;
+                    }
+
+                    Ok(())
+                }))
+            }
+            "fd_close" => {
+                let fd = arguments[0].expect_i32().unwrap() as usize;
+
+                match self.files.get_mut(fd) {
+                    Some(WasiFile::Closed) | None => Some(Value::I32(Errno::Badf as i32)),
+                    Some(file) => {
+                        *file = WasiFile::Closed;
+                        success_code
+                    }
+                }
+            }
+            "fd_datasync" => {
+                let fd = arguments[0].expect_i32().unwrap() as usize;
+                errno_result(self.sync(fd, File::sync_data))
+            }
             "fd_fdstat_get" => {
                 // (i32, i32) -> i32
 
@@ -121,9 +302,12 @@ impl<'a> WasiDispatcher<'a> {
                 // ptr to a wasi_fdstat_t
                 let stat_mut_ptr = arguments[1].expect_i32().unwrap() as usize;
 
-                match fd {
-                    1 => {
-                        // Tell WASI that stdout is a tty (no seek or tell)
+                let (filetype, flags, rights) = match self.files.get(fd) {
+                    Some(WasiFile::HostSystemFile)
+                    | Some(WasiFile::ReadOnly(_))
+                    | Some(WasiFile::WriteOnly(_))
+                    | Some(WasiFile::ReadWrite(_)) => {
+                        // Tell WASI that stdio is a tty (no seek or tell)
                         // https://github.com/WebAssembly/wasi-libc/blob/659ff414560721b1660a19685110e484a081c3d4/libc-bottom-half/sources/isatty.c
                         // *Not* a tty if:
                         //     (statbuf.fs_filetype != __WASI_FILETYPE_CHARACTER_DEVICE ||
@@ -131,25 +315,86 @@ impl<'a> WasiDispatcher<'a> {
                         // So it's sufficient to set:
                         //     .fs_filetype = __WASI_FILETYPE_CHARACTER_DEVICE
                         //     .fs_rights_base = 0
+                        (FileType::CharacterDevice, 0, 0)
+                    }
+                    Some(WasiFile::PreopenedDir { .. }) | Some(WasiFile::HostDir(_)) => {
+                        (FileType::Directory, 0, RIGHTS_ALL)
+                    }
+                    Some(WasiFile::HostFile(host_file)) => {
+                        let flags = if host_file.append { FDFLAGS_APPEND } else { 0 };
+                        (FileType::RegularFile, flags, RIGHTS_ALL)
+                    }
+                    Some(WasiFile::Closed) | None => return Some(Value::I32(Errno::Badf as i32)),
+                };
 
-                        const WASI_FILETYPE_CHARACTER_DEVICE: u8 = 2;
-                        memory[stat_mut_ptr] = WASI_FILETYPE_CHARACTER_DEVICE;
+                // struct fdstat {
+                //     filetype fs_filetype;   // u8 at offset 0
+                //     fdflags fs_flags;       // u16 at offset 2
+                //     rights fs_rights_base;  // u64 at offset 8
+                //     rights fs_rights_inheriting; // u64 at offset 16
+                // };
+                memory[stat_mut_ptr..][..24].fill(0);
+                memory[stat_mut_ptr] = filetype as u8;
+                memory[stat_mut_ptr + 2..][..2].copy_from_slice(&flags.to_le_bytes());
+                write_u64(memory, stat_mut_ptr + 8, rights);
+                write_u64(memory, stat_mut_ptr + 16, rights);
 
-                        for b in memory[stat_mut_ptr + 1..stat_mut_ptr + 24].iter_mut() {
-                            *b = 0;
-                        }
+                success_code
+            }
+            // We don't check rights, and we can't change the flags of a file that's already open
+            "fd_fdstat_set_flags" => Some(Value::I32(Errno::Nosys as i32)),
+            "fd_fdstat_set_rights" => Some(Value::I32(Errno::Nosys as i32)),
+            "fd_filestat_get" => {
+                let fd = arguments[0].expect_i32().unwrap() as usize;
+                // ptr to a wasi filestat
+                let ptr_buf = arguments[1].expect_i32().unwrap() as usize;
+
+                let metadata = match self.files.get(fd) {
+                    Some(WasiFile::HostFile(host_file)) => host_file.file.metadata(),
+                    Some(WasiFile::PreopenedDir { dir, .. }) | Some(WasiFile::HostDir(dir)) => {
+                        fs::metadata(&dir.path)
                     }
-                    _ => todo!("WASI {}({:?})", function_name, arguments),
-                }
+                    Some(WasiFile::Closed) | None => return Some(Value::I32(Errno::Badf as i32)),
+                    Some(_) => {
+                        // stdio
+                        memory[ptr_buf..][..FILESTAT_SIZE].fill(0);
+                        memory[ptr_buf + 16] = FileType::CharacterDevice as u8;
+                        return success_code;
+                    }
+                };
 
-                success_code
+                errno_result(
+                    metadata
+                        .map(|metadata| write_filestat(memory, ptr_buf, &metadata))
+                        .map_err(errno_from_io),
+                )
+            }
+            "fd_filestat_set_size" => {
+                let fd = arguments[0].expect_i32().unwrap() as usize;
+                let size = arguments[1].expect_i64().unwrap() as u64;
+
+                errno_result(
+                    self.host_file(fd)
+                        .and_then(|host_file| host_file.file.set_len(size).map_err(errno_from_io)),
+                )
+            }
+            // Setting file times needs a newer Rust than we support
+            "fd_filestat_set_times" => Some(Value::I32(Errno::Nosys as i32)),
+            "fd_pread" => {
+                let fd = arguments[0].expect_i32().unwrap() as usize;
+                let ptr_iovs = arguments[1].expect_i32().unwrap() as usize;
+                let iovs_len = arguments[2].expect_i32().unwrap() as usize;
+                let offset = arguments[3].expect_i64().unwrap() as u64;
+                let ptr_nread = arguments[4].expect_i32().unwrap() as usize;
+
+                let result = self.host_file(fd).and_then(|host_file| {
+                    at_offset(&mut host_file.file, offset, |file| {
+                        read_iovs(file, memory, ptr_iovs, iovs_len)
+                    })
+                });
+
+                errno_result(result.map(|n_read| write_u32(memory, ptr_nread, n_read as u32)))
             }
-            "fd_fdstat_set_flags" => todo!("WASI {}({:?})", function_name, arguments),
-            "fd_fdstat_set_rights" => todo!("WASI {}({:?})", function_name, arguments),
-            "fd_filestat_get" => todo!("WASI {}({:?})", function_name, arguments),
-            "fd_filestat_set_size" => todo!("WASI {}({:?})", function_name, arguments),
-            "fd_filestat_set_times" => todo!("WASI {}({:?})", function_name, arguments),
-            "fd_pread" => todo!("WASI {}({:?})", function_name, arguments),
             "fd_prestat_get" => {
                 // The preopened file descriptor to query
                 let fd = arguments[0].expect_i32().unwrap() as usize;
@@ -157,19 +402,52 @@ impl<'a> WasiDispatcher<'a> {
                 //  preopen type: 4 bytes, where 0=dir is the only one supported, it seems
                 //  preopen name length: 4 bytes
                 let ptr_buf = arguments[1].expect_i32().unwrap() as usize;
-                memory[ptr_buf..][..8].copy_from_slice(&0u64.to_le_bytes());
-                if fd < self.files.len() {
-                    success_code
-                } else {
-                    println!("WASI warning: file descriptor {fd} does not exist");
-                    Some(Value::I32(Errno::Badf as i32))
+
+                match self.files.get(fd) {
+                    Some(WasiFile::PreopenedDir { guest_path, .. }) => {
+                        write_u32(memory, ptr_buf, 0);
+                        write_u32(memory, ptr_buf + 4, guest_path.len() as u32);
+                        success_code
+                    }
+                    // WASI libc asks about every file descriptor after stdio until it gets Badf
+                    _ => Some(Value::I32(Errno::Badf as i32)),
                 }
             }
             "fd_prestat_dir_name" => {
-                // We're not giving names to any of our files so just return success
-                success_code
+                let fd = arguments[0].expect_i32().unwrap() as usize;
+                let ptr_path = arguments[1].expect_i32().unwrap() as usize;
+                let path_len = arguments[2].expect_i32().unwrap() as usize;
+
+                match self.files.get(fd) {
+                    Some(WasiFile::PreopenedDir { guest_path, .. }) => {
+                        if path_len < guest_path.len() {
+                            return Some(Value::I32(Errno::Nametoolong as i32));
+                        }
+
+                        memory[ptr_path..][..guest_path.len()]
+                            .copy_from_slice(guest_path.as_bytes());
+                        success_code
+                    }
+                    _ => Some(Value::I32(Errno::Badf as i32)),
+                }
+            }
+            "fd_pwrite" => {
+                let fd = arguments[0].expect_i32().unwrap() as usize;
+                let ptr_iovs = arguments[1].expect_i32().unwrap() as usize;
+                let iovs_len = arguments[2].expect_i32().unwrap() as usize;
+                let offset = arguments[3].expect_i64().unwrap() as u64;
+                let ptr_nwritten = arguments[4].expect_i32().unwrap() as usize;
+
+                let result = self.host_file(fd).and_then(|host_file| {
+                    at_offset(&mut host_file.file, offset, |file| {
+                        write_iovs(file, memory, ptr_iovs, iovs_len)
+                    })
+                });
+
+                errno_result(
+                    result.map(|n_written| write_u32(memory, ptr_nwritten, n_written as u32)),
+                )
             }
-            "fd_pwrite" => todo!("WASI {}({:?})", function_name, arguments),
             "fd_read" => {
                 use WasiFile::*;
 
@@ -189,7 +467,7 @@ impl<'a> WasiDispatcher<'a> {
                 // };
 
                 let mut n_read: usize = 0;
-                match self.files.get(fd) {
+                match self.files.get_mut(fd) {
                     Some(ReadOnly(content) | ReadWrite(content)) => {
                         for _ in 0..iovs_len {
                             let iov_base = read_u32(memory, ptr_iovs) as usize;
@@ -218,17 +496,97 @@ impl<'a> WasiDispatcher<'a> {
                             }
                         }
                     }
+                    Some(HostFile(host_file)) => {
+                        match read_iovs(&mut host_file.file, memory, ptr_iovs, iovs_len as usize) {
+                            Ok(n) => n_read = n,
+                            Err(errno) => return Some(Value::I32(errno as i32)),
+                        }
+                    }
+                    Some(PreopenedDir { .. } | HostDir(_)) => {
+                        return Some(Value::I32(Errno::Isdir as i32))
+                    }
                     _ => return Some(Value::I32(Errno::Badf as i32)),
                 };
 
                 memory[ptr_nread..][..4].copy_from_slice(&(n_read as u32).to_le_bytes());
                 success_code
             }
-            "fd_readdir" => todo!("WASI {}({:?})", function_name, arguments),
-            "fd_renumber" => todo!("WASI {}({:?})", function_name, arguments),
-            "fd_seek" => todo!("WASI {}({:?})", function_name, arguments),
-            "fd_sync" => todo!("WASI {}({:?})", function_name, arguments),
-            "fd_tell" => todo!("WASI {}({:?})", function_name, arguments),
+            "fd_readdir" => {
+                let fd = arguments[0].expect_i32().unwrap() as usize;
+                let ptr_buf = arguments[1].expect_i32().unwrap() as usize;
+                let buf_len = arguments[2].expect_i32().unwrap() as usize;
+                // The index of the first entry to read
+                let cookie = arguments[3].expect_i64().unwrap() as u64;
+                // Out param: number of bytes written to the buffer
+                let ptr_bufused = arguments[4].expect_i32().unwrap() as usize;
+
+                let dir_path = match self.files.get(fd) {
+                    Some(WasiFile::PreopenedDir { dir, .. }) | Some(WasiFile::HostDir(dir)) => {
+                        &dir.path
+                    }
+                    Some(WasiFile::Closed) | None => return Some(Value::I32(Errno::Badf as i32)),
+                    Some(_) => return Some(Value::I32(Errno::Notdir as i32)),
+                };
+
+                match read_dir_entries(dir_path, cookie, buf_len) {
+                    Ok(bytes) => {
+                        // If the buffer is full, the app will assume there are more entries
+                        // and call us again, starting after the last complete one.
+                        let used = bytes.len().min(buf_len);
+                        memory[ptr_buf..][..used].copy_from_slice(&bytes[..used]);
+                        write_u32(memory, ptr_bufused, used as u32);
+                        success_code
+                    }
+                    Err(errno) => Some(Value::I32(errno as i32)),
+                }
+            }
+            "fd_renumber" => {
+                let from = arguments[0].expect_i32().unwrap() as usize;
+                let to = arguments[1].expect_i32().unwrap() as usize;
+
+                let is_open =
+                    |fd: usize| !matches!(self.files.get(fd), Some(WasiFile::Closed) | None);
+                if !is_open(from) || !is_open(to) {
+                    return Some(Value::I32(Errno::Badf as i32));
+                }
+
+                if from != to {
+                    self.files[to] = std::mem::replace(&mut self.files[from], WasiFile::Closed);
+                }
+
+                success_code
+            }
+            "fd_seek" => {
+                let fd = arguments[0].expect_i32().unwrap() as usize;
+                let offset = arguments[1].expect_i64().unwrap();
+                let whence = arguments[2].expect_i32().unwrap();
+                // Out param: the new offset, relative to the start of the file
+                let ptr_newoffset = arguments[3].expect_i32().unwrap() as usize;
+
+                let seek_from = match whence {
+                    WHENCE_SET if offset >= 0 => SeekFrom::Start(offset as u64),
+                    WHENCE_CUR => SeekFrom::Current(offset),
+                    WHENCE_END => SeekFrom::End(offset),
+                    _ => return Some(Value::I32(Errno::Inval as i32)),
+                };
+
+                errno_result(self.seek(fd, seek_from).map(|new_offset| {
+                    write_u64(memory, ptr_newoffset, new_offset);
+                }))
+            }
+            "fd_sync" => {
+                let fd = arguments[0].expect_i32().unwrap() as usize;
+                errno_result(self.sync(fd, File::sync_all))
+            }
+            "fd_tell" => {
+                let fd = arguments[0].expect_i32().unwrap() as usize;
+                // Out param: the current offset, relative to the start of the file
+                let ptr_offset = arguments[1].expect_i32().unwrap() as usize;
+
+                errno_result(self.seek(fd, SeekFrom::Current(0)).map(|offset| {
+                    write_u64(memory, ptr_offset, offset);
+                }))
+            }
             "fd_write" => {
                 use WasiFile::*;
 
@@ -252,6 +610,10 @@ impl<'a> WasiDispatcher<'a> {
                     Some(WriteOnly(content) | ReadWrite(content)) => {
                         WriteLock::RegularFile(content)
                     }
+                    Some(HostFile(host_file)) => WriteLock::HostFile(&mut host_file.file),
+                    Some(PreopenedDir { .. } | HostDir(_)) => {
+                        return Some(Value::I32(Errno::Isdir as i32))
+                    }
                     _ => return Some(Value::I32(Errno::Badf as i32)),
                 };
 
@@ -280,6 +642,7 @@ impl<'a> WasiDispatcher<'a> {
                         WriteLock::StdOut(stdout) => stdout.write_all(bytes),
                         WriteLock::Stderr(stderr) => stderr.write_all(bytes),
                         WriteLock::RegularFile(content) => content.write_all(bytes),
+                        WriteLock::HostFile(file) => file.write_all(bytes),
                     };
                     if write_result.is_err() {
                         break;
@@ -300,23 +663,234 @@ impl<'a> WasiDispatcher<'a> {
                     Err(_) => Some(Value::I32(Errno::Io as i32)),
                 }
             }
-            "path_create_directory" => todo!("WASI {}({:?})", function_name, arguments),
-            "path_filestat_get" => todo!("WASI {}({:?})", function_name, arguments),
-            "path_filestat_set_times" => todo!("WASI {}({:?})", function_name, arguments),
-            "path_link" => todo!("WASI {}({:?})", function_name, arguments),
-            "path_open" => todo!("WASI {}({:?})", function_name, arguments),
-            "path_readlink" => todo!("WASI {}({:?})", function_name, arguments),
-            "path_remove_directory" => todo!("WASI {}({:?})", function_name, arguments),
-            "path_rename" => todo!("WASI {}({:?})", function_name, arguments),
-            "path_symlink" => todo!("WASI {}({:?})", function_name, arguments),
-            "path_unlink_file" => todo!("WASI {}({:?})", function_name, arguments),
-            "poll_oneoff" => todo!("WASI {}({:?})", function_name, arguments),
+            "path_create_directory" => {
+                let fd = arguments[0].expect_i32().unwrap() as usize;
+                let ptr_path = arguments[1].expect_i32().unwrap() as usize;
+                let path_len = arguments[2].expect_i32().unwrap() as usize;
+
+                errno_result(
+                    self.resolve_path(fd, memory, ptr_path, path_len, false)
+                        .and_then(|host_path| fs::create_dir(host_path).map_err(errno_from_io)),
+                )
+            }
+            "path_filestat_get" => {
+                let fd = arguments[0].expect_i32().unwrap() as usize;
+                let lookup_flags = arguments[1].expect_i32().unwrap() as u32;
+                let ptr_path = arguments[2].expect_i32().unwrap() as usize;
+                let path_len = arguments[3].expect_i32().unwrap() as usize;
+                // ptr to a wasi filestat
+                let ptr_buf = arguments[4].expect_i32().unwrap() as usize;
+
+                let follow_symlinks = lookup_flags & LOOKUPFLAGS_SYMLINK_FOLLOW != 0;
+
+                let result = self
+                    .resolve_path(fd, memory, ptr_path, path_len, follow_symlinks)
+                    .and_then(|host_path| {
+                        if follow_symlinks {
+                            fs::metadata(host_path)
+                        } else {
+                            fs::symlink_metadata(host_path)
+                        }
+                        .map_err(errno_from_io)
+                    });
+
+                errno_result(result.map(|metadata| write_filestat(memory, ptr_buf, &metadata)))
+            }
+            // Setting file times needs a newer Rust than we support
+            "path_filestat_set_times" => Some(Value::I32(Errno::Nosys as i32)),
+            "path_link" => {
+                let old_fd = arguments[0].expect_i32().unwrap() as usize;
+                let old_lookup_flags = arguments[1].expect_i32().unwrap() as u32;
+                let ptr_old_path = arguments[2].expect_i32().unwrap() as usize;
+                let old_path_len = arguments[3].expect_i32().unwrap() as usize;
+                let new_fd = arguments[4].expect_i32().unwrap() as usize;
+                let ptr_new_path = arguments[5].expect_i32().unwrap() as usize;
+                let new_path_len = arguments[6].expect_i32().unwrap() as usize;
+
+                let follow_symlinks = old_lookup_flags & LOOKUPFLAGS_SYMLINK_FOLLOW != 0;
+
+                let result = self
+                    .resolve_path(old_fd, memory, ptr_old_path, old_path_len, follow_symlinks)
+                    .and_then(|old_path| {
+                        let new_path =
+                            self.resolve_path(new_fd, memory, ptr_new_path, new_path_len, false)?;
+                        fs::hard_link(old_path, new_path).map_err(errno_from_io)
+                    });
+
+                errno_result(result)
+            }
+            "path_open" => {
+                // The directory that the path is relative to
+                let fd = arguments[0].expect_i32().unwrap() as usize;
+                let lookup_flags = arguments[1].expect_i32().unwrap() as u32;
+                let ptr_path = arguments[2].expect_i32().unwrap() as usize;
+                let path_len = arguments[3].expect_i32().unwrap() as usize;
+                let oflags = arguments[4].expect_i32().unwrap() as u16;
+                let rights_base = arguments[5].expect_i64().unwrap() as u64;
+                // rights_inheriting: we don't check rights
+                let fdflags = arguments[7].expect_i32().unwrap() as u16;
+                // Out param: the file descriptor of the opened file
+                let ptr_fd = arguments[8].expect_i32().unwrap() as usize;
+
+                let follow_symlinks = lookup_flags & LOOKUPFLAGS_SYMLINK_FOLLOW != 0;
+
+                let result = self
+                    .resolve_path(fd, memory, ptr_path, path_len, follow_symlinks)
+                    .and_then(|host_path| {
+                        let root = match &self.files[fd] {
+                            WasiFile::PreopenedDir { dir, .. } | WasiFile::HostDir(dir) => {
+                                dir.root.clone()
+                            }
+                            _ => unreachable!("resolve_path only accepts directories"),
+                        };
+
+                        open(
+                            root,
+                            host_path,
+                            follow_symlinks,
+                            oflags,
+                            rights_base,
+                            fdflags,
+                        )
+                    })
+                    .map(|file| self.insert_file(file));
+
+                errno_result(result.map(|new_fd| write_u32(memory, ptr_fd, new_fd as u32)))
+            }
+            "path_readlink" => {
+                let fd = arguments[0].expect_i32().unwrap() as usize;
+                let ptr_path = arguments[1].expect_i32().unwrap() as usize;
+                let path_len = arguments[2].expect_i32().unwrap() as usize;
+                let ptr_buf = arguments[3].expect_i32().unwrap() as usize;
+                let buf_len = arguments[4].expect_i32().unwrap() as usize;
+                // Out param: number of bytes written to the buffer
+                let ptr_bufused = arguments[5].expect_i32().unwrap() as usize;
+
+                let result = self
+                    .resolve_path(fd, memory, ptr_path, path_len, false)
+                    .and_then(|host_path| fs::read_link(host_path).map_err(errno_from_io));
+
+                errno_result(result.map(|target| {
+                    let target = target.to_string_lossy();
+                    let used = target.len().min(buf_len);
+                    memory[ptr_buf..][..used].copy_from_slice(&target.as_bytes()[..used]);
+                    write_u32(memory, ptr_bufused, used as u32);
+                }))
+            }
+            "path_remove_directory" => {
+                let fd = arguments[0].expect_i32().unwrap() as usize;
+                let ptr_path = arguments[1].expect_i32().unwrap() as usize;
+                let path_len = arguments[2].expect_i32().unwrap() as usize;
+
+                let result = self
+                    .resolve_path(fd, memory, ptr_path, path_len, false)
+                    .and_then(|host_path| {
+                        // io::ErrorKind doesn't have variants for these on the Rust we support
+                        let metadata = fs::symlink_metadata(&host_path).map_err(errno_from_io)?;
+                        if !metadata.is_dir() {
+                            return Err(Errno::Notdir);
+                        }
+
+                        let mut entries = fs::read_dir(&host_path).map_err(errno_from_io)?;
+                        if entries.next().is_some() {
+                            return Err(Errno::Notempty);
+                        }
+
+                        fs::remove_dir(host_path).map_err(errno_from_io)
+                    });
+
+                errno_result(result)
+            }
+            "path_rename" => {
+                let old_fd = arguments[0].expect_i32().unwrap() as usize;
+                let ptr_old_path = arguments[1].expect_i32().unwrap() as usize;
+                let old_path_len = arguments[2].expect_i32().unwrap() as usize;
+                let new_fd = arguments[3].expect_i32().unwrap() as usize;
+                let ptr_new_path = arguments[4].expect_i32().unwrap() as usize;
+                let new_path_len = arguments[5].expect_i32().unwrap() as usize;
+
+                let result = self
+                    .resolve_path(old_fd, memory, ptr_old_path, old_path_len, false)
+                    .and_then(|old_path| {
+                        let new_path =
+                            self.resolve_path(new_fd, memory, ptr_new_path, new_path_len, false)?;
+                        fs::rename(old_path, new_path).map_err(errno_from_io)
+                    });
+
+                errno_result(result)
+            }
+            "path_symlink" => {
+                let ptr_old_path = arguments[0].expect_i32().unwrap() as usize;
+                let old_path_len = arguments[1].expect_i32().unwrap() as usize;
+                let fd = arguments[2].expect_i32().unwrap() as usize;
+                let ptr_new_path = arguments[3].expect_i32().unwrap() as usize;
+                let new_path_len = arguments[4].expect_i32().unwrap() as usize;
+
+                let result = read_str(memory, ptr_old_path, old_path_len).and_then(|target| {
+                    let link = self.resolve_path(fd, memory, ptr_new_path, new_path_len, false)?;
+                    let root = match &self.files[fd] {
+                        WasiFile::PreopenedDir { dir, .. } | WasiFile::HostDir(dir) => &dir.root,
+                        _ => unreachable!("resolve_path only accepts directories"),
+                    };
+
+                    // Other engines (and the kernel) would follow this link from the host's point
+                    // of view, so it mustn't point outside the sandbox.
+                    if !symlink_stays_inside(root, &link, Path::new(target)) {
+                        return Err(Errno::Notcapable);
+                    }
+
+                    symlink(Path::new(target), &link)
+                });
+
+                errno_result(result)
+            }
+            "path_unlink_file" => {
+                let fd = arguments[0].expect_i32().unwrap() as usize;
+                let ptr_path = arguments[1].expect_i32().unwrap() as usize;
+                let path_len = arguments[2].expect_i32().unwrap() as usize;
+
+                let result = self
+                    .resolve_path(fd, memory, ptr_path, path_len, false)
+                    .and_then(|host_path| {
+                        let metadata = fs::symlink_metadata(&host_path).map_err(errno_from_io)?;
+                        if metadata.is_dir() {
+                            return Err(Errno::Isdir);
+                        }
+
+                        fs::remove_file(host_path).map_err(errno_from_io)
+                    });
+
+                errno_result(result)
+            }
+            "poll_oneoff" => {
+                // Array of subscriptions to wait for
+                let ptr_subscriptions = arguments[0].expect_i32().unwrap() as usize;
+                // Array of events to write, one for each subscription at most
+                let ptr_events = arguments[1].expect_i32().unwrap() as usize;
+                let n_subscriptions = arguments[2].expect_i32().unwrap() as usize;
+                // Out param: the number of events written
+                let ptr_nevents = arguments[3].expect_i32().unwrap() as usize;
+
+                match self.poll_oneoff(memory, ptr_subscriptions, n_subscriptions) {
+                    Ok(events) => {
+                        for (i, event) in events.iter().enumerate() {
+                            event.write(memory, ptr_events + EVENT_SIZE * i);
+                        }
+                        write_u32(memory, ptr_nevents, events.len() as u32);
+                        success_code
+                    }
+                    Err(errno) => Some(Value::I32(errno as i32)),
+                }
+            }
             "proc_exit" => {
                 let exit_code = arguments[0].expect_i32().unwrap();
                 exit(exit_code);
             }
             "proc_raise" => todo!("WASI {}({:?})", function_name, arguments),
-            "sched_yield" => todo!("WASI {}({:?})", function_name, arguments),
+            "sched_yield" => {
+                thread::yield_now();
+                success_code
+            }
             "random_get" => {
                 // A pointer to a buffer where the random bytes will be written
                 let ptr_buf = arguments[0].expect_i32().unwrap() as usize;
@@ -335,6 +909,583 @@ impl<'a> WasiDispatcher<'a> {
     }
 }
 
+impl WasiDispatcher<'_> {
+    /// Wait for at least one of the subscriptions. Files are always ready to read and write,
+    /// so we only actually wait if all of them are timers.
+    fn poll_oneoff(
+        &mut self,
+        memory: &[u8],
+        ptr_subscriptions: usize,
+        n_subscriptions: usize,
+    ) -> Result<Vec<Event>, Errno> {
+        if n_subscriptions == 0 {
+            return Err(Errno::Inval);
+        }
+
+        let mut events = Vec::new();
+        let mut timers = Vec::new();
+
+        for i in 0..n_subscriptions {
+            // struct subscription {
+            //     userdata userdata;  // u64 at offset 0
+            //     u8 tag;             // at offset 8
+            //     union {             // at offset 16
+            //         struct subscription_clock {
+            //             clockid id;         // u32 at offset 16
+            //             timestamp timeout;  // u64 at offset 24
+            //             timestamp precision;// u64 at offset 32
+            //             subclockflags flags;// u16 at offset 40
+            //         };
+            //         struct subscription_fd_readwrite {
+            //             fd file_descriptor; // u32 at offset 16
+            //         };
+            //     };
+            // };
+            let ptr_subscription = ptr_subscriptions + SUBSCRIPTION_SIZE * i;
+            let userdata = read_u64(memory, ptr_subscription);
+            let tag = memory[ptr_subscription + 8];
+
+            match tag {
+                EVENTTYPE_CLOCK => {
+                    let clock_id = read_u32(memory, ptr_subscription + 16);
+                    let timeout = read_u64(memory, ptr_subscription + 24);
+                    let flags = read_u32(memory, ptr_subscription + 40) as u16;
+
+                    let deadline = if flags & SUBCLOCKFLAGS_SUBSCRIPTION_CLOCK_ABSTIME != 0 {
+                        Ok(timeout)
+                    } else {
+                        self.clock
+                            .now(clock_id)
+                            .map(|now| now.saturating_add(timeout))
+                    };
+
+                    match deadline {
+                        Ok(deadline) => timers.push((userdata, clock_id, deadline)),
+                        Err(error) => events.push(Event::new(userdata, error, tag)),
+                    }
+                }
+                EVENTTYPE_FD_READ | EVENTTYPE_FD_WRITE => {
+                    let fd = read_u32(memory, ptr_subscription + 16) as usize;
+                    let error = match self.files.get(fd) {
+                        Some(WasiFile::Closed) | None => Errno::Badf,
+                        Some(_) => Errno::Success,
+                    };
+
+                    events.push(Event::new(userdata, error, tag));
+                }
+                _ => return Err(Errno::Inval),
+            }
+        }
+
+        // Unless something is ready already, sleep until the first timer goes off. The realtime
+        // clock can jump around, so we check again afterwards instead of assuming it went off.
+        loop {
+            let mut until_first = u64::MAX;
+
+            for &(userdata, clock_id, deadline) in timers.iter() {
+                let now = self.clock.now(clock_id)?;
+
+                if now >= deadline {
+                    events.push(Event::new(userdata, Errno::Success, EVENTTYPE_CLOCK));
+                } else {
+                    until_first = until_first.min(deadline - now);
+                }
+            }
+
+            if !events.is_empty() {
+                return Ok(events);
+            }
+
+            self.clock.sleep(until_first);
+        }
+    }
+}
+
+/// Something that happened that the app subscribed to with poll_oneoff
+struct Event {
+    userdata: u64,
+    error: Errno,
+    event_type: u8,
+}
+
+impl Event {
+    fn new(userdata: u64, error: Errno, event_type: u8) -> Self {
+        Event {
+            userdata,
+            error,
+            event_type,
+        }
+    }
+
+    fn write(&self, memory: &mut [u8], ptr_event: usize) {
+        // struct event {
+        //     userdata userdata;  // u64 at offset 0
+        //     errno error;        // u16 at offset 8
+        //     eventtype type;     // u8 at offset 10
+        //     struct event_fd_readwrite {
+        //         filesize nbytes;            // u64 at offset 16
+        //         eventrwflags flags;         // u16 at offset 24
+        //     };
+        // };
+        // We don't know how many bytes can be read or written, so we leave nbytes as 0.
+        let event = &mut memory[ptr_event..][..EVENT_SIZE];
+        event.fill(0);
+        event[0..8].copy_from_slice(&self.userdata.to_le_bytes());
+        event[8..10].copy_from_slice(&(self.error as u16).to_le_bytes());
+        event[10] = self.event_type;
+    }
+}
+
+// File system helpers
+impl WasiDispatcher<'_> {
+    /// The host path for a path the app gave us, relative to one of its open directories.
+    ///
+    /// The path must stay inside the preopened directory that `fd` is in, including after
+    /// following symlinks. The last component is only followed if `follow_symlinks` is set,
+    /// so that e.g. a symlink that points outside can still be deleted.
+    fn resolve_path(
+        &self,
+        fd: usize,
+        memory: &[u8],
+        ptr_path: usize,
+        path_len: usize,
+        follow_symlinks: bool,
+    ) -> Result<PathBuf, Errno> {
+        let dir = match self.files.get(fd) {
+            Some(WasiFile::PreopenedDir { dir, .. }) | Some(WasiFile::HostDir(dir)) => dir,
+            Some(WasiFile::Closed) | None => return Err(Errno::Badf),
+            Some(_) => return Err(Errno::Notdir),
+        };
+
+        let path = read_str(memory, ptr_path, path_len)?;
+        if path.is_empty() {
+            return Err(Errno::Noent);
+        }
+
+        let mut host_path = dir.path.clone();
+        for component in Path::new(path).components() {
+            match component {
+                Component::Normal(name) => host_path.push(name),
+                Component::CurDir => {}
+                Component::ParentDir => {
+                    if host_path == dir.root {
+                        return Err(Errno::Notcapable);
+                    }
+                    host_path.pop();
+                }
+                Component::RootDir | Component::Prefix(_) => return Err(Errno::Notcapable),
+            }
+        }
+
+        let to_check = if follow_symlinks {
+            follow_last_symlinks(host_path.clone())?
+        } else {
+            match host_path.parent() {
+                Some(parent) if host_path != dir.root => parent.to_path_buf(),
+                _ => host_path.clone(),
+            }
+        };
+
+        // Paths that are about to be created don't exist yet, so check the closest ancestor
+        // that does. The root always exists, since it's canonical.
+        let canonical = to_check
+            .ancestors()
+            .find_map(|ancestor| ancestor.canonicalize().ok())
+            .ok_or(Errno::Noent)?;
+
+        if canonical.starts_with(&dir.root) {
+            Ok(host_path)
+        } else {
+            Err(Errno::Notcapable)
+        }
+    }
+
+    /// Put a newly opened file in the lowest free file descriptor, like POSIX does
+    fn insert_file(&mut self, file: WasiFile) -> usize {
+        match self
+            .files
+            .iter()
+            .position(|existing| matches!(existing, WasiFile::Closed))
+        {
+            Some(fd) => {
+                self.files[fd] = file;
+                fd
+            }
+            None => {
+                self.files.push(file);
+                self.files.len() - 1
+            }
+        }
+    }
+
+    fn host_file(&mut self, fd: usize) -> Result<&mut HostFile, Errno> {
+        match self.files.get_mut(fd) {
+            Some(WasiFile::HostFile(host_file)) => Ok(host_file),
+            Some(WasiFile::PreopenedDir { .. }) | Some(WasiFile::HostDir(_)) => Err(Errno::Isdir),
+            _ => Err(Errno::Badf),
+        }
+    }
+
+    fn seek(&mut self, fd: usize, seek_from: SeekFrom) -> Result<u64, Errno> {
+        match self.files.get_mut(fd) {
+            Some(WasiFile::HostFile(host_file)) => {
+                host_file.file.seek(seek_from).map_err(errno_from_io)
+            }
+            Some(WasiFile::PreopenedDir { .. }) | Some(WasiFile::HostDir(_)) => Err(Errno::Isdir),
+            Some(WasiFile::Closed) | None => Err(Errno::Badf),
+            // stdio
+            Some(_) => Err(Errno::Spipe),
+        }
+    }
+
+    fn sync(&mut self, fd: usize, sync: fn(&File) -> io::Result<()>) -> Result<(), Errno> {
+        match self.files.get(fd) {
+            Some(WasiFile::HostFile(host_file)) => sync(&host_file.file).map_err(errno_from_io),
+            Some(WasiFile::Closed) | None => Err(Errno::Badf),
+            // Nothing to do for directories and stdio
+            Some(_) => Ok(()),
+        }
+    }
+}
+
+/// Open a path that `resolve_path` returned. If `follow_symlinks` isn't set, the last
+/// component must not be a symlink, like with `O_NOFOLLOW`.
+fn open(
+    root: PathBuf,
+    path: PathBuf,
+    follow_symlinks: bool,
+    oflags: u16,
+    rights_base: u64,
+    fdflags: u16,
+) -> Result<WasiFile, Errno> {
+    let metadata = if follow_symlinks {
+        fs::metadata(&path)
+    } else {
+        fs::symlink_metadata(&path)
+    };
+
+    if !follow_symlinks
+        && metadata
+            .as_ref()
+            .is_ok_and(|metadata| metadata.is_symlink())
+    {
+        return Err(Errno::Loop);
+    }
+
+    let is_dir = metadata.as_ref().is_ok_and(|metadata| metadata.is_dir());
+    let read = rights_base & RIGHTS_FD_READ != 0;
+    let write = rights_base & RIGHTS_FD_WRITE != 0;
+
+    if oflags & OFLAGS_DIRECTORY != 0 || (is_dir && !write) {
+        return match metadata {
+            Ok(_) if is_dir => {
+                check_inside(&root, &path, follow_symlinks)?;
+                Ok(WasiFile::HostDir(HostDir { root, path }))
+            }
+            Ok(_) => Err(Errno::Notdir),
+            Err(error) => Err(errno_from_io(error)),
+        };
+    }
+
+    if is_dir {
+        return Err(Errno::Isdir);
+    }
+
+    let append = fdflags & FDFLAGS_APPEND != 0;
+    let mut options = OpenOptions::new();
+    options
+        // Opening a file without reading or writing is allowed (e.g. to stat it)
+        .read(read || !write)
+        .write(write && !append)
+        .append(append)
+        .create(oflags & OFLAGS_CREAT != 0)
+        .create_new(oflags & OFLAGS_CREAT != 0 && oflags & OFLAGS_EXCL != 0)
+        .truncate(oflags & OFLAGS_TRUNC != 0);
+
+    // The path could have been replaced by a symlink since we looked at it
+    #[cfg(unix)]
+    if !follow_symlinks {
+        std::os::unix::fs::OpenOptionsExt::custom_flags(&mut options, libc::O_NOFOLLOW);
+    }
+
+    let file = options.open(&path).map_err(errno_from_io)?;
+    check_inside(&root, &path, follow_symlinks)?;
+
+    Ok(WasiFile::HostFile(HostFile { file, append }))
+}
+
+/// After opening a path we followed symlinks in, check that where it actually led to is still
+/// inside the sandbox, in case something changed it after `resolve_path` looked at it.
+fn check_inside(root: &Path, path: &Path, follow_symlinks: bool) -> Result<(), Errno> {
+    if !follow_symlinks {
+        return Ok(());
+    }
+
+    match path.canonicalize() {
+        Ok(canonical) if canonical.starts_with(root) => Ok(()),
+        Ok(_) => Err(Errno::Notcapable),
+        Err(error) => Err(errno_from_io(error)),
+    }
+}
+
+/// Follow the symlinks in the last component of `path`, even if they lead to something that
+/// doesn't exist yet (e.g. a file that `path_open` is about to create through the link).
+fn follow_last_symlinks(mut path: PathBuf) -> Result<PathBuf, Errno> {
+    for _ in 0..MAX_SYMLINKS {
+        match fs::read_link(&path) {
+            // A relative target is relative to the directory the link is in, and joining an
+            // absolute target replaces the whole path.
+            Ok(target) => match path.parent() {
+                Some(parent) => path = parent.join(target),
+                None => path = target,
+            },
+            Err(_) => return Ok(path),
+        }
+    }
+
+    Err(Errno::Loop)
+}
+
+/// Whether a symlink at `link` that points to `target` would still point inside `root`
+fn symlink_stays_inside(root: &Path, link: &Path, target: &Path) -> bool {
+    let mut resolved = match link.parent() {
+        Some(parent) => parent.to_path_buf(),
+        None => return false,
+    };
+
+    for component in target.components() {
+        match component {
+            Component::Normal(name) => resolved.push(name),
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if resolved == root {
+                    return false;
+                }
+                resolved.pop();
+            }
+            Component::RootDir | Component::Prefix(_) => return false,
+        }
+    }
+
+    resolved.starts_with(root)
+}
+
+#[cfg(unix)]
+fn symlink(target: &Path, link: &Path) -> Result<(), Errno> {
+    std::os::unix::fs::symlink(target, link).map_err(errno_from_io)
+}
+
+#[cfg(not(unix))]
+fn symlink(_target: &Path, _link: &Path) -> Result<(), Errno> {
+    Err(Errno::Nosys)
+}
+
+/// Run `f` with the file at `offset`, and then put it back where it was
+fn at_offset<T>(
+    file: &mut File,
+    offset: u64,
+    f: impl FnOnce(&mut File) -> Result<T, Errno>,
+) -> Result<T, Errno> {
+    let position = file.stream_position().map_err(errno_from_io)?;
+    file.seek(SeekFrom::Start(offset)).map_err(errno_from_io)?;
+
+    let result = f(file);
+
+    file.seek(SeekFrom::Start(position))
+        .map_err(errno_from_io)?;
+    result
+}
+
+// https://man7.org/linux/man-pages/man2/readv.2.html
+// struct iovec {
+//     void  *iov_base;    /* Starting address */
+//     size_t iov_len;     /* Number of bytes to transfer */
+// };
+fn iov(memory: &[u8], ptr_iovs: usize, index: usize) -> (usize, usize) {
+    let ptr_iov = ptr_iovs + 8 * index;
+    let iov_base = read_u32(memory, ptr_iov) as usize;
+    let iov_len = read_u32(memory, ptr_iov + 4) as usize;
+    (iov_base, iov_len)
+}
+
+fn read_iovs(
+    file: &mut File,
+    memory: &mut [u8],
+    ptr_iovs: usize,
+    iovs_len: usize,
+) -> Result<usize, Errno> {
+    let mut n_read = 0;
+
+    for i in 0..iovs_len {
+        let (iov_base, iov_len) = iov(memory, ptr_iovs, i);
+        let n = file
+            .read(&mut memory[iov_base..][..iov_len])
+            .map_err(errno_from_io)?;
+
+        n_read += n;
+        if n < iov_len {
+            break;
+        }
+    }
+
+    Ok(n_read)
+}
+
+fn write_iovs(
+    file: &mut File,
+    memory: &[u8],
+    ptr_iovs: usize,
+    iovs_len: usize,
+) -> Result<usize, Errno> {
+    let mut n_written = 0;
+
+    for i in 0..iovs_len {
+        let (iov_base, iov_len) = iov(memory, ptr_iovs, i);
+        file.write_all(&memory[iov_base..][..iov_len])
+            .map_err(errno_from_io)?;
+        n_written += iov_len;
+    }
+
+    Ok(n_written)
+}
+
+/// The directory's entries from index `cookie` onwards, serialized as WASI dirents, stopping
+/// once there are at least `buf_len` bytes.
+fn read_dir_entries(dir_path: &Path, cookie: u64, buf_len: usize) -> Result<Vec<u8>, Errno> {
+    let mut entries = vec![
+        (".".into(), 0, FileType::Directory),
+        ("..".into(), 0, FileType::Directory),
+    ];
+
+    let mut children = Vec::new();
+    for entry in fs::read_dir(dir_path).map_err(errno_from_io)? {
+        let entry = entry.map_err(errno_from_io)?;
+        let file_type = entry.file_type().map_err(errno_from_io)?;
+        let metadata = entry.metadata().map_err(errno_from_io)?;
+        let name = entry.file_name().to_string_lossy().into_owned();
+
+        children.push((name, inode(&metadata), FileType::from(file_type)));
+    }
+
+    // The order has to be the same every time, since the app reads the entries in batches.
+    children.sort_by(|(a, _, _), (b, _, _)| a.cmp(b));
+    entries.extend(children);
+
+    let mut bytes = Vec::new();
+    for (index, (name, ino, file_type)) in entries.iter().enumerate().skip(cookie as usize) {
+        // struct dirent {
+        //     dircookie d_next;   // u64 at offset 0
+        //     inode d_ino;        // u64 at offset 8
+        //     dirnamlen d_namlen; // u32 at offset 16
+        //     filetype d_type;    // u8 at offset 20
+        // };
+        // followed by the name, which is not zero-terminated
+        bytes.extend_from_slice(&(index as u64 + 1).to_le_bytes());
+        bytes.extend_from_slice(&ino.to_le_bytes());
+        bytes.extend_from_slice(&(name.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(&[*file_type as u8, 0, 0, 0]);
+        bytes.extend_from_slice(name.as_bytes());
+
+        if bytes.len() >= buf_len {
+            break;
+        }
+    }
+
+    Ok(bytes)
+}
+
+fn write_filestat(memory: &mut [u8], ptr_buf: usize, metadata: &Metadata) {
+    let nanos = |time: io::Result<SystemTime>| {
+        time.ok()
+            .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+            .map_or(0, |duration| duration.as_nanos() as u64)
+    };
+
+    // struct filestat {
+    //     device dev;         // u64 at offset 0
+    //     inode ino;          // u64 at offset 8
+    //     filetype filetype;  // u8 at offset 16
+    //     linkcount nlink;    // u64 at offset 24
+    //     filesize size;      // u64 at offset 32
+    //     timestamp atim;     // u64 at offset 40
+    //     timestamp mtim;     // u64 at offset 48
+    //     timestamp ctim;     // u64 at offset 56
+    // };
+    let buf = &mut memory[ptr_buf..][..FILESTAT_SIZE];
+    buf.fill(0);
+    buf[0..8].copy_from_slice(&device(metadata).to_le_bytes());
+    buf[8..16].copy_from_slice(&inode(metadata).to_le_bytes());
+    buf[16] = FileType::from(metadata.file_type()) as u8;
+    buf[24..32].copy_from_slice(&link_count(metadata).to_le_bytes());
+    buf[32..40].copy_from_slice(&metadata.len().to_le_bytes());
+    buf[40..48].copy_from_slice(&nanos(metadata.accessed()).to_le_bytes());
+    buf[48..56].copy_from_slice(&nanos(metadata.modified()).to_le_bytes());
+    buf[56..64].copy_from_slice(&nanos(metadata.created().or(metadata.modified())).to_le_bytes());
+}
+
+#[cfg(unix)]
+fn device(metadata: &Metadata) -> u64 {
+    std::os::unix::fs::MetadataExt::dev(metadata)
+}
+
+#[cfg(unix)]
+fn inode(metadata: &Metadata) -> u64 {
+    std::os::unix::fs::MetadataExt::ino(metadata)
+}
+
+#[cfg(unix)]
+fn link_count(metadata: &Metadata) -> u64 {
+    std::os::unix::fs::MetadataExt::nlink(metadata)
+}
+
+#[cfg(not(unix))]
+fn device(_metadata: &Metadata) -> u64 {
+    0
+}
+
+#[cfg(not(unix))]
+fn inode(_metadata: &Metadata) -> u64 {
+    0
+}
+
+#[cfg(not(unix))]
+fn link_count(_metadata: &Metadata) -> u64 {
+    1
+}
+
+fn read_str(memory: &[u8], ptr: usize, len: usize) -> Result<&str, Errno> {
+    let bytes = memory.get(ptr..ptr + len).ok_or(Errno::Fault)?;
+    std::str::from_utf8(bytes).map_err(|_| Errno::Ilseq)
+}
+
+fn errno_result(result: Result<(), Errno>) -> Option<Value> {
+    let errno = match result {
+        Ok(()) => Errno::Success,
+        Err(errno) => errno,
+    };
+
+    Some(Value::I32(errno as i32))
+}
+
+fn errno_from_io(error: io::Error) -> Errno {
+    // io::ErrorKind doesn't have a variant for this on the Rust we support
+    #[cfg(unix)]
+    if error.raw_os_error() == Some(libc::ELOOP) {
+        return Errno::Loop;
+    }
+
+    match error.kind() {
+        io::ErrorKind::NotFound => Errno::Noent,
+        io::ErrorKind::PermissionDenied => Errno::Access,
+        io::ErrorKind::AlreadyExists => Errno::Exist,
+        io::ErrorKind::InvalidInput => Errno::Inval,
+        io::ErrorKind::Interrupted => Errno::Intr,
+        io::ErrorKind::WouldBlock => Errno::Again,
+        io::ErrorKind::Unsupported => Errno::Notsup,
+        _ => Errno::Io,
+    }
+}
+
 fn read_u32(memory: &[u8], addr: usize) -> u32 {
     let mut bytes = [0; 4];
     bytes.copy_from_slice(&memory[addr..][..4]);
@@ -355,12 +1506,86 @@ fn write_i32(memory: &mut [u8], addr: usize, value: i32) {
     memory[addr..][..4].copy_from_slice(&value.to_le_bytes());
 }
 
+fn read_u64(memory: &[u8], addr: usize) -> u64 {
+    let mut bytes = [0; 8];
+    bytes.copy_from_slice(&memory[addr..][..8]);
+    u64::from_le_bytes(bytes)
+}
+
+fn write_u64(memory: &mut [u8], addr: usize, value: u64) {
+    memory[addr..][..8].copy_from_slice(&value.to_le_bytes());
+}
+
+// Flags and rights from the WASI preview1 spec
+// https://github.com/WebAssembly/WASI/blob/main/legacy/preview1/docs.md
+const RIGHTS_FD_READ: u64 = 1 << 1;
+const RIGHTS_FD_WRITE: u64 = 1 << 6;
+const RIGHTS_ALL: u64 = (1 << 29) - 1;
+const OFLAGS_CREAT: u16 = 1 << 0;
+const OFLAGS_DIRECTORY: u16 = 1 << 1;
+const OFLAGS_EXCL: u16 = 1 << 2;
+const OFLAGS_TRUNC: u16 = 1 << 3;
+const FDFLAGS_APPEND: u16 = 1 << 0;
+const LOOKUPFLAGS_SYMLINK_FOLLOW: u32 = 1 << 0;
+/// How many symlinks we follow in a row before giving up, like Linux's `MAXSYMLINKS`
+const MAX_SYMLINKS: usize = 40;
+const WHENCE_SET: i32 = 0;
+const WHENCE_CUR: i32 = 1;
+const WHENCE_END: i32 = 2;
+const FILESTAT_SIZE: usize = 64;
+const CLOCKID_REALTIME: u32 = 0;
+const CLOCKID_MONOTONIC: u32 = 1;
+const CLOCKID_PROCESS_CPUTIME_ID: u32 = 2;
+const CLOCKID_THREAD_CPUTIME_ID: u32 = 3;
+const SUBCLOCKFLAGS_SUBSCRIPTION_CLOCK_ABSTIME: u16 = 1 << 0;
+const EVENTTYPE_CLOCK: u8 = 0;
+const EVENTTYPE_FD_READ: u8 = 1;
+const EVENTTYPE_FD_WRITE: u8 = 2;
+const SUBSCRIPTION_SIZE: usize = 48;
+const EVENT_SIZE: usize = 32;
+
+/// The type of a file descriptor or file.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FileType {
+    /// The type of the file descriptor or file is unknown or is different from any of the other types specified.
+    Unknown,
+    /// The file descriptor or file refers to a block device inode.
+    BlockDevice,
+    /// The file descriptor or file refers to a character device inode.
+    CharacterDevice,
+    /// The file descriptor or file refers to a directory inode.
+    Directory,
+    /// The file descriptor or file refers to a regular file inode.
+    RegularFile,
+    /// The file descriptor or file refers to a datagram socket.
+    SocketDgram,
+    /// The file descriptor or file refers to a byte-stream socket.
+    SocketStream,
+    /// The file refers to a symbolic link inode.
+    SymbolicLink,
+}
+
+impl From<fs::FileType> for FileType {
+    fn from(file_type: fs::FileType) -> Self {
+        if file_type.is_dir() {
+            FileType::Directory
+        } else if file_type.is_file() {
+            FileType::RegularFile
+        } else if file_type.is_symlink() {
+            FileType::SymbolicLink
+        } else {
+            FileType::Unknown
+        }
+    }
+}
+
 /// Error codes returned by functions.
 /// Not all of these error codes are returned by the functions provided by this
 /// API; some are used in higher-level library layers, and others are provided
 /// merely for alignment with POSIX.
 #[repr(u8)]
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Errno {
     /// No error occurred. System call completed successfully.
     Success,
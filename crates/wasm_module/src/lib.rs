@@ -3,6 +3,7 @@ pub mod opcodes;
 pub mod parse;
 pub mod sections;
 pub mod serialize;
+mod wat;
 
 use std::iter::repeat;
 
@@ -258,7 +258,7 @@ pub struct TypeSection<'a> {
     /// Private. See WasmModule::add_function_signature
     arena: &'a Bump,
     bytes: Vec<'a, u8>,
-    offsets: Vec<'a, usize>,
+    pub(crate) offsets: Vec<'a, usize>,
 }
 
 impl<'a> TypeSection<'a> {
@@ -299,7 +299,7 @@ impl<'a> TypeSection<'a> {
         self.bytes.is_empty()
     }
 
-    pub fn look_up(&'a self, sig_index: u32) -> (SignatureParamsIter<'a>, Option<ValueType>) {
+    pub fn look_up(&self, sig_index: u32) -> (SignatureParamsIter<'_>, Option<ValueType>) {
         let mut offset = self.offsets[sig_index as usize];
         offset += 1; // separator
         let param_count = u32::parse((), &self.bytes, &mut offset).unwrap() as usize;
@@ -965,11 +965,13 @@ impl Parse<()> for ConstExpr {
             OpCode::F32CONST => {
                 let mut b = [0; 4];
                 b.copy_from_slice(&bytes[*cursor..][..4]);
+                *cursor += 4;
                 Ok(ConstExpr::F32(f32::from_le_bytes(b)))
             }
             OpCode::F64CONST => {
                 let mut b = [0; 8];
                 b.copy_from_slice(&bytes[*cursor..][..8]);
+                *cursor += 8;
                 Ok(ConstExpr::F64(f64::from_le_bytes(b)))
             }
             _ => Err(ParseError {
@@ -1545,8 +1547,8 @@ impl Serialize for DataSegment<'_> {
 #[derive(Debug)]
 pub struct DataSection<'a> {
     pub end_addr: u32,
-    count: u32,
-    bytes: Vec<'a, u8>,
+    pub(crate) count: u32,
+    pub(crate) bytes: Vec<'a, u8>,
 }
 
 impl<'a> DataSection<'a> {
@@ -1652,7 +1654,7 @@ impl<'a> Serialize for DataSection<'a> {
 /// but may be present in a preloaded binary
 #[derive(Debug, Default)]
 pub struct OpaqueSection<'a> {
-    bytes: &'a [u8],
+    pub(crate) bytes: &'a [u8],
 }
 
 impl<'a> OpaqueSection<'a> {
@@ -1921,4 +1923,28 @@ mod tests {
         }
         test_assert_types_preload(arena, &section);
     }
+
+    #[test]
+    fn test_const_expr_round_trip() {
+        let arena = &Bump::new();
+        let exprs = [
+            ConstExpr::I32(-1024),
+            ConstExpr::I64(1 << 40),
+            ConstExpr::F32(0.25),
+            ConstExpr::F64(-1.5),
+        ];
+
+        // Parse them back to back, so each one has to leave the cursor at the start of the next
+        let mut bytes = Vec::new_in(arena);
+        for expr in exprs.iter() {
+            expr.serialize(&mut bytes);
+        }
+
+        let mut cursor = 0;
+        for expr in exprs.iter() {
+            let parsed = ConstExpr::parse((), &bytes, &mut cursor).unwrap();
+            assert_eq!(format!("{parsed:?}"), format!("{expr:?}"));
+        }
+        assert_eq!(cursor, bytes.len());
+    }
 }
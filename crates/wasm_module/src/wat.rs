@@ -0,0 +1,1009 @@
+use std::collections::HashSet;
+use std::fmt::{self, Write};
+
+use crate::opcodes::{immediates_for, MemoryInstruction, OpCode, OpImmediates, LOOKUP_TABLE};
+use crate::parse::{Parse, ParseError};
+use crate::sections::{ConstExpr, DataMode, ExportType, GlobalType, ImportDesc, Limits, RefType};
+use crate::{ValueType, WasmModule, DUMMY_FUNCTION};
+
+/*******************************************************************
+ *
+ * WebAssembly text format
+ * https://webassembly.github.io/spec/core/text/index.html
+ *
+ * A disassembler for debugging generated code, without needing external tools.
+ *
+ *******************************************************************/
+
+const INDENT: &str = "  ";
+
+impl<'a> WasmModule<'a> {
+    /// Print the module in the WebAssembly text format (WAT)
+    pub fn to_wat(&self) -> String {
+        let mut wat = String::with_capacity(4 * self.code.bytes.len());
+        self.write_wat(&mut wat).unwrap();
+        wat
+    }
+
+    /// Write the module in the WebAssembly text format (WAT).
+    /// Functions are labelled with names from the NameSection, where there is one.
+    /// Code that can't be decoded is written as a comment, so we can still read the rest of the module.
+    pub fn write_wat<W: Write>(&self, w: &mut W) -> fmt::Result {
+        WatPrinter::new(self).write_module(w)
+    }
+}
+
+enum WatError {
+    Fmt(fmt::Error),
+    Parse(ParseError),
+}
+
+impl From<fmt::Error> for WatError {
+    fn from(e: fmt::Error) -> Self {
+        WatError::Fmt(e)
+    }
+}
+
+impl From<ParseError> for WatError {
+    fn from(e: ParseError) -> Self {
+        WatError::Parse(e)
+    }
+}
+
+struct WatPrinter<'m, 'a> {
+    module: &'m WasmModule<'a>,
+    import_fn_count: u32,
+    /// Identifiers for functions that have names, by function index. e.g. `$roc_alloc`
+    fn_ids: Vec<Option<String>>,
+}
+
+impl<'m, 'a> WatPrinter<'m, 'a> {
+    fn new(module: &'m WasmModule<'a>) -> Self {
+        let import_fn_count = module.import.function_count() as u32;
+        let fn_count = import_fn_count as usize + module.function.signatures.len();
+
+        let mut fn_ids = vec![None; fn_count];
+        let mut used = HashSet::with_capacity(module.names.function_names.len());
+        for (fn_index, name) in module.names.function_names.iter() {
+            let fn_index = *fn_index as usize;
+            if fn_index >= fn_count || fn_ids[fn_index].is_some() || name.is_empty() {
+                continue;
+            }
+            let base_id = wat_id(name);
+            let mut id = base_id.clone();
+            let mut suffix = 1;
+            while !used.insert(id.clone()) {
+                id = format!("{base_id}.{suffix}");
+                suffix += 1;
+            }
+            fn_ids[fn_index] = Some(id);
+        }
+
+        WatPrinter {
+            module,
+            import_fn_count,
+            fn_ids,
+        }
+    }
+
+    /// A reference to a function from an instruction or another section
+    fn fn_ref(&self, fn_index: u32) -> String {
+        match self.fn_ids.get(fn_index as usize) {
+            Some(Some(id)) => id.clone(),
+            _ => fn_index.to_string(),
+        }
+    }
+
+    /// The identifier in a function declaration. Unnamed functions just get their index as a comment.
+    fn fn_decl(&self, fn_index: u32) -> String {
+        match self.fn_ids.get(fn_index as usize) {
+            Some(Some(id)) => id.clone(),
+            _ => format!("(;{fn_index};)"),
+        }
+    }
+
+    fn write_module<W: Write>(&self, w: &mut W) -> fmt::Result {
+        writeln!(w, "(module")?;
+        self.write_types(w)?;
+        self.write_imports(w)?;
+        self.write_functions(w)?;
+        self.write_table(w)?;
+        self.write_memory(w)?;
+        self.write_globals(w)?;
+        self.write_exports(w)?;
+        self.write_start(w)?;
+        self.write_elements(w)?;
+        self.write_data(w)?;
+        writeln!(w, ")")
+    }
+
+    fn write_types<W: Write>(&self, w: &mut W) -> fmt::Result {
+        for sig_index in 0..self.module.types.offsets.len() {
+            write!(w, "{INDENT}(type (;{sig_index};) (func")?;
+            self.write_signature(w, sig_index as u32)?;
+            writeln!(w, "))")?;
+        }
+        Ok(())
+    }
+
+    fn write_signature<W: Write>(&self, w: &mut W, sig_index: u32) -> fmt::Result {
+        if sig_index as usize >= self.module.types.offsets.len() {
+            return write!(w, " (; invalid type index ;)");
+        }
+        let (params, ret_type) = self.module.types.look_up(sig_index);
+        if params.len() > 0 {
+            write!(w, " (param")?;
+            for ty in params {
+                write!(w, " {}", value_type_name(ty))?;
+            }
+            write!(w, ")")?;
+        }
+        if let Some(ty) = ret_type {
+            write!(w, " (result {})", value_type_name(ty))?;
+        }
+        Ok(())
+    }
+
+    fn write_imports<W: Write>(&self, w: &mut W) -> fmt::Result {
+        let mut fn_index = 0;
+        let mut table_index = 0;
+        let mut memory_index = 0;
+        let mut global_index = 0;
+
+        for import in self.module.import.imports.iter() {
+            write!(
+                w,
+                "{INDENT}(import {} {} ",
+                wat_string(import.module.as_bytes()),
+                wat_string(import.name.as_bytes())
+            )?;
+            match &import.description {
+                ImportDesc::Func { signature_index } => {
+                    write!(
+                        w,
+                        "(func {} (type {signature_index})",
+                        self.fn_decl(fn_index)
+                    )?;
+                    self.write_signature(w, *signature_index)?;
+                    write!(w, ")")?;
+                    fn_index += 1;
+                }
+                ImportDesc::Table { ty } => {
+                    write!(
+                        w,
+                        "(table (;{table_index};) {} {})",
+                        limits(&ty.limits),
+                        ref_type_name(ty.ref_type)
+                    )?;
+                    table_index += 1;
+                }
+                ImportDesc::Mem { limits: lim } => {
+                    write!(w, "(memory (;{memory_index};) {})", limits(lim))?;
+                    memory_index += 1;
+                }
+                ImportDesc::Global { ty } => {
+                    write!(w, "(global (;{global_index};) {})", global_type(ty))?;
+                    global_index += 1;
+                }
+            }
+            writeln!(w, ")")?;
+        }
+        Ok(())
+    }
+
+    fn write_functions<W: Write>(&self, w: &mut W) -> fmt::Result {
+        let code = &self.module.code;
+        let dummy_count = code.dead_import_dummy_count as usize;
+
+        for (i, sig_index) in self.module.function.signatures.iter().enumerate() {
+            let fn_index = self.import_fn_count + i as u32;
+            write!(
+                w,
+                "{INDENT}(func {} (type {sig_index})",
+                self.fn_decl(fn_index)
+            )?;
+            self.write_signature(w, *sig_index)?;
+            writeln!(w)?;
+
+            // Dead imports are replaced with dummy functions at the start of the code section
+            let result = if i < dummy_count {
+                self.write_function_body(w, &DUMMY_FUNCTION, 0, DUMMY_FUNCTION.len())
+            } else if let Some(offset) = code.function_offsets.get(i - dummy_count) {
+                let mut cursor = *offset as usize;
+                match u32::parse((), &code.bytes, &mut cursor) {
+                    Ok(size) => {
+                        let end = (cursor + size as usize).min(code.bytes.len());
+                        self.write_function_body(w, &code.bytes, cursor, end)
+                    }
+                    Err(e) => Err(WatError::Parse(e)),
+                }
+            } else {
+                Err(WatError::Parse(ParseError {
+                    offset: code.bytes.len(),
+                    message: "This function has no code".into(),
+                }))
+            };
+
+            match result {
+                Ok(()) => {}
+                Err(WatError::Fmt(e)) => return Err(e),
+                Err(WatError::Parse(e)) => {
+                    writeln!(
+                        w,
+                        "{INDENT}{INDENT};; Failed to decode at file offset {:#x}: {}",
+                        code.section_offset as usize + e.offset,
+                        e.message
+                    )?;
+                }
+            }
+            writeln!(w, "{INDENT})")?;
+        }
+        Ok(())
+    }
+
+    /// Write the local variable declarations and instructions of a function.
+    /// Instructions are indented by block depth. The final `end` is implied by the closing bracket.
+    fn write_function_body<W: Write>(
+        &self,
+        w: &mut W,
+        bytes: &[u8],
+        start: usize,
+        end: usize,
+    ) -> Result<(), WatError> {
+        let mut cursor = start;
+
+        let local_group_count = u32::parse((), bytes, &mut cursor)?;
+        for _ in 0..local_group_count {
+            let count = u32::parse((), bytes, &mut cursor)?;
+            let ty = parse_value_type(bytes, &mut cursor)?;
+            write!(w, "{INDENT}{INDENT}(local")?;
+            for _ in 0..count {
+                write!(w, " {}", value_type_name(ty))?;
+            }
+            writeln!(w, ")")?;
+        }
+
+        let mut depth = 0;
+        while cursor < end {
+            let op_offset = cursor;
+            let op = parse_opcode(bytes, &mut cursor)?;
+            let immediates = immediates_for(op).map_err(|message| ParseError {
+                offset: op_offset,
+                message,
+            })?;
+
+            // An `end` or `else` outside of any block can only come from malformed code.
+            // Print it anyway, since this is often used to debug code like that.
+            let unbalanced = matches!(op, OpCode::END | OpCode::ELSE) && depth == 0;
+            let indent_depth = match op {
+                // The function's own `end` is implied by the closing bracket
+                OpCode::END if unbalanced && cursor >= end => {
+                    return Ok(());
+                }
+                _ if unbalanced => 0,
+                OpCode::END => {
+                    depth -= 1;
+                    depth
+                }
+                OpCode::ELSE => depth - 1,
+                _ => depth,
+            };
+            for _ in 0..(2 + indent_depth) {
+                w.write_str(INDENT)?;
+            }
+
+            if op != OpCode::MEMORY {
+                w.write_str(op.wat_name())?;
+            }
+
+            match immediates {
+                OpImmediates::NoImmediate => {}
+                OpImmediates::Byte1 => {
+                    let byte = parse_bytes::<1>(bytes, &mut cursor)?[0];
+                    if matches!(op, OpCode::BLOCK | OpCode::LOOP | OpCode::IF) {
+                        depth += 1;
+                        if byte != ValueType::VOID {
+                            let ty = parse_value_type(&[byte], &mut 0)?;
+                            write!(w, " (result {})", value_type_name(ty))?;
+                        }
+                    }
+                }
+                OpImmediates::Bytes4 => {
+                    let x = f32::from_le_bytes(parse_bytes(bytes, &mut cursor)?);
+                    write!(w, " {}", f32_literal(x))?;
+                }
+                OpImmediates::Bytes8 => {
+                    let x = f64::from_le_bytes(parse_bytes(bytes, &mut cursor)?);
+                    write!(w, " {}", f64_literal(x))?;
+                }
+                OpImmediates::Leb32x1 => match op {
+                    OpCode::I32CONST => {
+                        write!(w, " {}", i32::parse((), bytes, &mut cursor)?)?;
+                    }
+                    OpCode::CALL => {
+                        let fn_index = u32::parse((), bytes, &mut cursor)?;
+                        write!(w, " {}", self.fn_ref(fn_index))?;
+                    }
+                    _ => {
+                        write!(w, " {}", u32::parse((), bytes, &mut cursor)?)?;
+                    }
+                },
+                OpImmediates::Leb64x1 => {
+                    write!(w, " {}", i64::parse((), bytes, &mut cursor)?)?;
+                }
+                OpImmediates::Leb32x2 => {
+                    let a = u32::parse((), bytes, &mut cursor)?;
+                    let b = u32::parse((), bytes, &mut cursor)?;
+                    if op == OpCode::CALLINDIRECT {
+                        let (sig_index, table_index) = (a, b);
+                        if table_index != 0 {
+                            write!(w, " {table_index}")?;
+                        }
+                        write!(w, " (type {sig_index})")?;
+                    } else {
+                        let (align_log2, offset) = (a, b);
+                        if offset != 0 {
+                            write!(w, " offset={offset}")?;
+                        }
+                        if align_log2 != natural_alignment_log2(op) {
+                            match 1u64.checked_shl(align_log2) {
+                                Some(align) => write!(w, " align={align}")?,
+                                None => write!(w, " (; align=2**{align_log2} ;)")?,
+                            }
+                        }
+                    }
+                }
+                OpImmediates::BrTable => {
+                    let label_count = 1 + u32::parse((), bytes, &mut cursor)?;
+                    for _ in 0..label_count {
+                        write!(w, " {}", u32::parse((), bytes, &mut cursor)?)?;
+                    }
+                }
+                OpImmediates::Memory => {
+                    let sub_op_offset = cursor;
+                    let sub_op_byte = parse_bytes::<1>(bytes, &mut cursor)?[0];
+                    let sub_op =
+                        MemoryInstruction::try_from(sub_op_byte).map_err(|byte| ParseError {
+                            offset: sub_op_offset,
+                            message: format!(
+                                "Unknown Wasm instruction 0x{:02x} 0x{byte:02x}",
+                                op as u8
+                            ),
+                        })?;
+                    w.write_str(sub_op.wat_name())?;
+                    match sub_op {
+                        MemoryInstruction::MemoryInit => {
+                            let data_index = u32::parse((), bytes, &mut cursor)?;
+                            parse_bytes::<1>(bytes, &mut cursor)?; // memory index
+                            write!(w, " {data_index}")?;
+                        }
+                        MemoryInstruction::DataDrop => {
+                            let data_index = u32::parse((), bytes, &mut cursor)?;
+                            write!(w, " {data_index}")?;
+                        }
+                        MemoryInstruction::MemoryCopy => {
+                            parse_bytes::<2>(bytes, &mut cursor)?; // memory indices
+                        }
+                        MemoryInstruction::MemoryFill => {
+                            parse_bytes::<1>(bytes, &mut cursor)?; // memory index
+                        }
+                    }
+                }
+            }
+            if unbalanced {
+                w.write_str(" ;; malformed: not inside a block")?;
+            }
+            writeln!(w)?;
+        }
+
+        Err(WatError::Parse(ParseError {
+            offset: cursor,
+            message: "Function ended without an END instruction".into(),
+        }))
+    }
+
+    fn write_table<W: Write>(&self, w: &mut W) -> fmt::Result {
+        // Like serialization, only include the table if something uses it
+        if self.module.element.is_empty() {
+            return Ok(());
+        }
+        let table_index = self
+            .module
+            .import
+            .imports
+            .iter()
+            .filter(|imp| matches!(imp.description, ImportDesc::Table { .. }))
+            .count();
+        let table = &self.module.table.function_table;
+        writeln!(
+            w,
+            "{INDENT}(table (;{table_index};) {} {})",
+            limits(&table.limits),
+            ref_type_name(table.ref_type)
+        )
+    }
+
+    fn write_memory<W: Write>(&self, w: &mut W) -> fmt::Result {
+        let memory = &self.module.memory;
+        if memory.count == 0 {
+            return Ok(());
+        }
+        let memory_index = self
+            .module
+            .import
+            .imports
+            .iter()
+            .filter(|imp| matches!(imp.description, ImportDesc::Mem { .. }))
+            .count();
+        match Limits::parse((), &memory.bytes, &mut 0) {
+            Ok(lim) => writeln!(w, "{INDENT}(memory (;{memory_index};) {})", limits(&lim)),
+            Err(e) => writeln!(w, "{INDENT};; Failed to decode memory: {}", e.message),
+        }
+    }
+
+    fn write_globals<W: Write>(&self, w: &mut W) -> fmt::Result {
+        let global = &self.module.global;
+        let import_count = self
+            .module
+            .import
+            .imports
+            .iter()
+            .filter(|imp| matches!(imp.description, ImportDesc::Global { .. }))
+            .count();
+
+        let mut cursor = 0;
+        for i in 0..global.count as usize {
+            let parsed = GlobalType::parse((), &global.bytes, &mut cursor).and_then(|ty| {
+                let init = ConstExpr::parse((), &global.bytes, &mut cursor)?;
+                Ok((ty, init))
+            });
+            match parsed {
+                Ok((ty, init)) => writeln!(
+                    w,
+                    "{INDENT}(global (;{};) {} {})",
+                    import_count + i,
+                    global_type(&ty),
+                    const_expr(&init)
+                )?,
+                Err(e) => {
+                    return writeln!(w, "{INDENT};; Failed to decode globals: {}", e.message);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn write_exports<W: Write>(&self, w: &mut W) -> fmt::Result {
+        for export in self.module.export.exports.iter() {
+            let desc = match export.ty {
+                ExportType::Func => format!("func {}", self.fn_ref(export.index)),
+                ExportType::Table => format!("table {}", export.index),
+                ExportType::Mem => format!("memory {}", export.index),
+                ExportType::Global => format!("global {}", export.index),
+            };
+            writeln!(
+                w,
+                "{INDENT}(export {} ({desc}))",
+                wat_string(export.name.as_bytes())
+            )?;
+        }
+        Ok(())
+    }
+
+    fn write_start<W: Write>(&self, w: &mut W) -> fmt::Result {
+        // The opaque section includes its header: ID byte and size
+        let bytes = self.module.start.bytes;
+        if bytes.is_empty() {
+            return Ok(());
+        }
+        let mut cursor = 1;
+        let fn_index =
+            u32::parse((), bytes, &mut cursor).and_then(|_size| u32::parse((), bytes, &mut cursor));
+        match fn_index {
+            Ok(fn_index) => writeln!(w, "{INDENT}(start {})", self.fn_ref(fn_index)),
+            Err(e) => writeln!(w, "{INDENT};; Failed to decode start: {}", e.message),
+        }
+    }
+
+    fn write_elements<W: Write>(&self, w: &mut W) -> fmt::Result {
+        let segments = self.module.element.segments.iter();
+        for (i, segment) in segments.enumerate() {
+            if segment.fn_indices.is_empty() {
+                continue;
+            }
+            write!(
+                w,
+                "{INDENT}(elem (;{i};) {} func",
+                const_expr(&segment.offset)
+            )?;
+            for fn_index in segment.fn_indices.iter() {
+                write!(w, " {}", self.fn_ref(*fn_index))?;
+            }
+            writeln!(w, ")")?;
+        }
+        Ok(())
+    }
+
+    fn write_data<W: Write>(&self, w: &mut W) -> fmt::Result {
+        let data = &self.module.data;
+        let mut cursor = 0;
+        for i in 0..data.count {
+            let parsed = DataMode::parse((), &data.bytes, &mut cursor).and_then(|mode| {
+                let len = u32::parse((), &data.bytes, &mut cursor)? as usize;
+                Ok((mode, len))
+            });
+            let (mode, len) = match parsed {
+                Ok(ok) if cursor + ok.1 <= data.bytes.len() => ok,
+                Ok(_) => {
+                    return writeln!(w, "{INDENT};; Data segment {i} is out of bounds");
+                }
+                Err(e) => {
+                    return writeln!(w, "{INDENT};; Failed to decode data: {}", e.message);
+                }
+            };
+            let init = &data.bytes[cursor..][..len];
+            cursor += len;
+
+            match mode {
+                DataMode::Active { offset } => writeln!(
+                    w,
+                    "{INDENT}(data (;{i};) {} {})",
+                    const_expr(&offset),
+                    wat_string(init)
+                )?,
+                DataMode::Passive => writeln!(w, "{INDENT}(data (;{i};) {})", wat_string(init))?,
+            }
+        }
+        Ok(())
+    }
+}
+
+fn parse_opcode(bytes: &[u8], cursor: &mut usize) -> Result<OpCode, ParseError> {
+    let byte = parse_bytes::<1>(bytes, cursor)?[0];
+    LOOKUP_TABLE[byte as usize].ok_or_else(|| ParseError {
+        offset: *cursor - 1,
+        message: format!("Unknown Wasm instruction 0x{byte:02x}"),
+    })
+}
+
+fn parse_value_type(bytes: &[u8], cursor: &mut usize) -> Result<ValueType, ParseError> {
+    let byte = parse_bytes::<1>(bytes, cursor)?[0];
+    match byte {
+        0x7f => Ok(ValueType::I32),
+        0x7e => Ok(ValueType::I64),
+        0x7d => Ok(ValueType::F32),
+        0x7c => Ok(ValueType::F64),
+        _ => Err(ParseError {
+            offset: *cursor - 1,
+            message: format!("Invalid ValueType 0x{byte:02x}"),
+        }),
+    }
+}
+
+fn parse_bytes<const N: usize>(bytes: &[u8], cursor: &mut usize) -> Result<[u8; N], ParseError> {
+    match bytes.get(*cursor..*cursor + N) {
+        Some(slice) => {
+            *cursor += N;
+            let mut result = [0; N];
+            result.copy_from_slice(slice);
+            Ok(result)
+        }
+        None => Err(ParseError {
+            offset: *cursor,
+            message: "Unexpected end of code".into(),
+        }),
+    }
+}
+
+/// Loads and stores are "naturally aligned" by default, to the size of the value in memory
+fn natural_alignment_log2(op: OpCode) -> u32 {
+    use OpCode::*;
+    match op {
+        I32LOAD8S | I32LOAD8U | I64LOAD8S | I64LOAD8U | I32STORE8 | I64STORE8 => 0,
+        I32LOAD16S | I32LOAD16U | I64LOAD16S | I64LOAD16U | I32STORE16 | I64STORE16 => 1,
+        I32LOAD | F32LOAD | I64LOAD32S | I64LOAD32U | I32STORE | F32STORE | I64STORE32 => 2,
+        _ => 3,
+    }
+}
+
+fn value_type_name(ty: ValueType) -> &'static str {
+    match ty {
+        ValueType::I32 => "i32",
+        ValueType::I64 => "i64",
+        ValueType::F32 => "f32",
+        ValueType::F64 => "f64",
+    }
+}
+
+fn ref_type_name(ty: RefType) -> &'static str {
+    match ty {
+        RefType::Func => "funcref",
+        RefType::Extern => "externref",
+    }
+}
+
+fn global_type(ty: &GlobalType) -> String {
+    let name = value_type_name(ty.value_type);
+    if ty.is_mutable {
+        format!("(mut {name})")
+    } else {
+        name.to_string()
+    }
+}
+
+fn limits(limits: &Limits) -> String {
+    match limits {
+        Limits::Min(min) => min.to_string(),
+        Limits::MinMax(min, max) => format!("{min} {max}"),
+    }
+}
+
+fn const_expr(expr: &ConstExpr) -> String {
+    match expr {
+        ConstExpr::I32(x) => format!("(i32.const {x})"),
+        ConstExpr::I64(x) => format!("(i64.const {x})"),
+        ConstExpr::F32(x) => format!("(f32.const {})", f32_literal(*x)),
+        ConstExpr::F64(x) => format!("(f64.const {})", f64_literal(*x)),
+    }
+}
+
+/// Rust's Debug format for floats round-trips exactly, and is valid WAT syntax, including `inf`.
+/// NaNs need their sign and payload bits to round-trip.
+fn f32_literal(x: f32) -> String {
+    if x.is_nan() {
+        let bits = x.to_bits();
+        let sign = if bits >> 31 == 0 { "" } else { "-" };
+        format!("{sign}nan:{:#x}", bits & 0x7f_ffff)
+    } else {
+        format!("{x:?}")
+    }
+}
+
+fn f64_literal(x: f64) -> String {
+    if x.is_nan() {
+        let bits = x.to_bits();
+        let sign = if bits >> 63 == 0 { "" } else { "-" };
+        format!("{sign}nan:{:#x}", bits & 0xf_ffff_ffff_ffff)
+    } else {
+        format!("{x:?}")
+    }
+}
+
+/// A quoted string, with anything other than printable ASCII escaped as hex bytes
+fn wat_string(bytes: &[u8]) -> String {
+    let mut s = String::with_capacity(bytes.len() + 2);
+    s.push('"');
+    for byte in bytes {
+        match byte {
+            b'"' | b'\\' => write!(s, "\\{}", *byte as char).unwrap(),
+            0x20..=0x7e => s.push(*byte as char),
+            _ => write!(s, "\\{byte:02x}").unwrap(),
+        }
+    }
+    s.push('"');
+    s
+}
+
+/// An identifier like `$name`. Characters that aren't allowed in identifiers are replaced with `_`.
+fn wat_id(name: &str) -> String {
+    let mut id = String::with_capacity(name.len() + 1);
+    id.push('$');
+    for c in name.chars() {
+        let is_id_char = c.is_ascii_alphanumeric() || "!#$%&'*+-./:<=>?@\\^_`|~".contains(c);
+        id.push(if is_id_char { c } else { '_' });
+    }
+    id
+}
+
+/*******************************************************************
+ *
+ * Unit tests
+ *
+ *******************************************************************/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sections::{DataSegment, ElementSegment, Import, MemorySection};
+    use crate::{Export, Global, SerialBuffer, Signature};
+    use bumpalo::Bump;
+
+    fn add_function<'a>(
+        module: &mut WasmModule<'a>,
+        signature: Signature<'a>,
+        locals: &[(u32, ValueType)],
+        write_instructions: impl FnOnce(&mut bumpalo::collections::Vec<'a, u8>),
+    ) {
+        module.add_function_signature(signature);
+
+        let offset = module.code.bytes.encode_padded_u32(0);
+        let start = module.code.bytes.len();
+        module.code.bytes.encode_u32(locals.len() as u32);
+        for (count, ty) in locals {
+            module.code.bytes.encode_u32(*count);
+            module.code.bytes.append_u8(*ty as u8);
+        }
+        write_instructions(&mut module.code.bytes);
+        let len = module.code.bytes.len() - start;
+        module.code.bytes.overwrite_padded_u32(offset, len as u32);
+
+        module.code.function_count += 1;
+        module.code.function_offsets.push(offset as u32);
+    }
+
+    #[test]
+    fn test_wat_module() {
+        use OpCode::*;
+
+        let arena = &Bump::new();
+        let mut module = WasmModule::new(arena);
+
+        let log_sig = module.types.insert(Signature {
+            param_types: bumpalo::vec![in arena; ValueType::I32],
+            ret_type: None,
+        });
+        module.import.imports.push(Import {
+            module: "env",
+            name: "log",
+            description: ImportDesc::Func {
+                signature_index: log_sig,
+            },
+        });
+        module.memory = MemorySection::new(arena, MemorySection::PAGE_SIZE);
+        module.global.append(Global {
+            ty: GlobalType {
+                value_type: ValueType::I32,
+                is_mutable: true,
+            },
+            init: ConstExpr::I32(1024),
+        });
+        module.global.append(Global {
+            ty: GlobalType {
+                value_type: ValueType::F64,
+                is_mutable: false,
+            },
+            init: ConstExpr::F64(0.5),
+        });
+
+        // func[1]
+        add_function(
+            &mut module,
+            Signature {
+                param_types: bumpalo::vec![in arena; ValueType::I32, ValueType::I32],
+                ret_type: Some(ValueType::I32),
+            },
+            &[(1, ValueType::I64), (2, ValueType::F32)],
+            |buf| {
+                buf.append_u8(BLOCK as u8);
+                buf.append_u8(ValueType::VOID);
+                buf.append_u8(GETLOCAL as u8);
+                buf.encode_u32(0);
+                buf.append_u8(IF as u8);
+                buf.append_u8(ValueType::I32 as u8);
+                buf.append_u8(I32CONST as u8);
+                buf.encode_i32(-1);
+                buf.append_u8(ELSE as u8);
+                buf.append_u8(GETLOCAL as u8);
+                buf.encode_u32(1);
+                buf.append_u8(I32LOAD as u8);
+                buf.encode_u32(2);
+                buf.encode_u32(8);
+                buf.append_u8(END as u8);
+                buf.append_u8(CALL as u8);
+                buf.encode_u32(0);
+                buf.append_u8(GETLOCAL as u8);
+                buf.encode_u32(1);
+                buf.append_u8(BRTABLE as u8);
+                buf.encode_u32(1);
+                buf.encode_u32(0);
+                buf.encode_u32(1);
+                buf.append_u8(END as u8);
+                buf.append_u8(F32CONST as u8);
+                buf.encode_f32(-1.5);
+                buf.append_u8(SETLOCAL as u8);
+                buf.encode_u32(3);
+                buf.append_u8(I64CONST as u8);
+                buf.encode_i64(i64::MIN);
+                buf.append_u8(I64STORE8 as u8);
+                buf.encode_u32(0);
+                buf.encode_u32(0);
+                buf.append_u8(GETLOCAL as u8);
+                buf.encode_u32(0);
+                buf.append_u8(I32CONST as u8);
+                buf.encode_i32(0);
+                buf.append_u8(I32CONST as u8);
+                buf.encode_i32(16);
+                buf.append_u8(MEMORY as u8);
+                buf.append_u8(MemoryInstruction::MemoryFill as u8);
+                buf.append_u8(0);
+                buf.append_u8(GETLOCAL as u8);
+                buf.encode_u32(0);
+                buf.append_u8(END as u8);
+            },
+        );
+
+        // func[2], with no name
+        add_function(
+            &mut module,
+            Signature {
+                param_types: bumpalo::vec![in arena],
+                ret_type: None,
+            },
+            &[],
+            |buf| {
+                buf.append_u8(I32CONST as u8);
+                buf.encode_i32(0);
+                buf.append_u8(CALLINDIRECT as u8);
+                buf.encode_u32(log_sig);
+                buf.encode_u32(0);
+                buf.append_u8(END as u8);
+            },
+        );
+
+        module.names.append_function(0, "log");
+        module.names.append_function(1, "Num.add (i32)");
+        module.export.append(Export {
+            name: "add",
+            ty: ExportType::Func,
+            index: 1,
+        });
+        module.export.append(Export {
+            name: "memory",
+            ty: ExportType::Mem,
+            index: 0,
+        });
+        module.element.segments.push(ElementSegment {
+            offset: ConstExpr::I32(1),
+            fn_indices: bumpalo::vec![in arena; 1, 2],
+        });
+        module.data.append_segment(DataSegment {
+            mode: DataMode::active_at(1024),
+            init: bumpalo::vec![in arena; b'h', b'i', b'"', 0, 0xff],
+        });
+
+        let expected = [
+            "(module",
+            "  (type (;0;) (func (param i32)))",
+            "  (type (;1;) (func (param i32 i32) (result i32)))",
+            "  (type (;2;) (func))",
+            "  (import \"env\" \"log\" (func $log (type 0) (param i32)))",
+            "  (func $Num.add__i32_ (type 1) (param i32 i32) (result i32)",
+            "    (local i64)",
+            "    (local f32 f32)",
+            "    block",
+            "      local.get 0",
+            "      if (result i32)",
+            "        i32.const -1",
+            "      else",
+            "        local.get 1",
+            "        i32.load offset=8",
+            "      end",
+            "      call $log",
+            "      local.get 1",
+            "      br_table 0 1",
+            "    end",
+            "    f32.const -1.5",
+            "    local.set 3",
+            "    i64.const -9223372036854775808",
+            "    i64.store8",
+            "    local.get 0",
+            "    i32.const 0",
+            "    i32.const 16",
+            "    memory.fill",
+            "    local.get 0",
+            "  )",
+            "  (func (;2;) (type 2)",
+            "    i32.const 0",
+            "    call_indirect (type 0)",
+            "  )",
+            "  (table (;0;) 0 funcref)",
+            "  (memory (;0;) 1)",
+            "  (global (;0;) (mut i32) (i32.const 1024))",
+            "  (global (;1;) f64 (f64.const 0.5))",
+            "  (export \"add\" (func $Num.add__i32_))",
+            "  (export \"memory\" (memory 0))",
+            "  (elem (;0;) (i32.const 1) func $Num.add__i32_ 2)",
+            "  (data (;0;) (i32.const 1024) \"hi\\\"\\00\\ff\")",
+            ")",
+            "",
+        ]
+        .join("\n");
+
+        assert_eq!(module.to_wat(), expected);
+    }
+
+    #[test]
+    fn test_wat_invalid_code() {
+        let arena = &Bump::new();
+        let mut module = WasmModule::new(arena);
+        let signature = || Signature {
+            param_types: bumpalo::vec![in arena],
+            ret_type: None,
+        };
+        add_function(&mut module, signature(), &[], |buf| {
+            buf.append_u8(OpCode::NOP as u8);
+            buf.append_u8(0xff);
+            buf.append_u8(OpCode::END as u8);
+        });
+        add_function(&mut module, signature(), &[], |buf| {
+            buf.append_u8(OpCode::NOP as u8);
+            buf.append_u8(OpCode::END as u8);
+        });
+        module.code.section_offset = 0x100;
+
+        // The first function is padded with a 5-byte length and has 1 byte for locals
+        let expected = [
+            "(module",
+            "  (type (;0;) (func))",
+            "  (func (;0;) (type 0)",
+            "    nop",
+            "    ;; Failed to decode at file offset 0x107: Unknown Wasm instruction 0xff",
+            "  )",
+            "  (func (;1;) (type 0)",
+            "    nop",
+            "  )",
+            ")",
+            "",
+        ]
+        .join("\n");
+
+        assert_eq!(module.to_wat(), expected);
+    }
+
+    #[test]
+    fn test_wat_unbalanced_blocks() {
+        use OpCode::*;
+
+        let arena = &Bump::new();
+        let mut module = WasmModule::new(arena);
+        let signature = Signature {
+            param_types: bumpalo::vec![in arena],
+            ret_type: None,
+        };
+        add_function(&mut module, signature, &[], |buf| {
+            buf.append_u8(ELSE as u8);
+            buf.append_u8(END as u8);
+            buf.append_u8(BLOCK as u8);
+            buf.append_u8(ValueType::VOID);
+            buf.append_u8(NOP as u8);
+            buf.append_u8(END as u8);
+            buf.append_u8(END as u8);
+        });
+
+        let expected = [
+            "(module",
+            "  (type (;0;) (func))",
+            "  (func (;0;) (type 0)",
+            "    else ;; malformed: not inside a block",
+            "    end ;; malformed: not inside a block",
+            "    block",
+            "      nop",
+            "    end",
+            "  )",
+            ")",
+            "",
+        ]
+        .join("\n");
+
+        assert_eq!(module.to_wat(), expected);
+    }
+
+    #[test]
+    fn test_wat_floats() {
+        assert_eq!(f32_literal(1.0), "1.0");
+        assert_eq!(f32_literal(f32::NEG_INFINITY), "-inf");
+        assert_eq!(f32_literal(f32::from_bits(0x7fc0_0000)), "nan:0x400000");
+        assert_eq!(f64_literal(1e-300), "1e-300");
+        assert_eq!(
+            f64_literal(f64::from_bits(0xfff0_0000_0000_0001)),
+            "-nan:0x1"
+        );
+    }
+
+    #[test]
+    fn test_wat_ids() {
+        assert_eq!(wat_id("roc_alloc"), "$roc_alloc");
+        assert_eq!(wat_id("#UserApp_main_1"), "$#UserApp_main_1");
+        assert_eq!(wat_id("List.map (a, b)"), "$List.map__a__b_");
+    }
+}